@@ -24,6 +24,10 @@ UPLOAD_DIR = Path(tempfile.gettempdir()) / "mofa-converter"
 UPLOAD_DIR.mkdir(exist_ok=True)
 OUTPUT_DIR = UPLOAD_DIR / "outputs"
 OUTPUT_DIR.mkdir(exist_ok=True)
+# Files picked through the host app, linked here instead of uploaded so the
+# host can still trash or archive the original afterwards
+STAGING_DIR = UPLOAD_DIR / "staged"
+STAGING_DIR.mkdir(exist_ok=True)
 
 # Supported formats
 AUDIO_FORMATS = {'.mp3', '.wav', '.m4a', '.flac', '.ogg', '.aac', '.wma'}
@@ -51,6 +55,13 @@ def update_job(job_id: str, **kwargs):
         jobs[job_id].update(kwargs)
 
 
+def save_text_result(job_id: str, content: str) -> str:
+    """Write a text result to OUTPUT_DIR so the host can open or reveal it."""
+    filename = f"{job_id}_output.txt"
+    (OUTPUT_DIR / filename).write_text(content, encoding='utf-8')
+    return filename
+
+
 def extract_audio_from_video(video_path: str, output_path: str) -> bool:
     """Extract audio from video using ffmpeg."""
     try:
@@ -285,6 +296,7 @@ def process_conversion_job(job_id: str, source_type: str, target_type: str,
                     result={
                         'type': 'text',
                         'content': result['text'],
+                        'filename': save_text_result(job_id, result['text']),
                         'segments': result['segments'],
                         'language': result['language'],
                         'duration': result['duration']
@@ -310,6 +322,7 @@ def process_conversion_job(job_id: str, source_type: str, target_type: str,
                     result={
                         'type': 'text',
                         'content': summary,
+                        'filename': save_text_result(job_id, summary),
                         'word_count': len(summary),
                         'original_length': len(text_content or '')
                     },
@@ -531,6 +544,7 @@ class ConverterHandler(SimpleHTTPRequestHandler):
         parts = body.split(boundary_bytes)
 
         file_data = None
+        staged = None
         filename = 'upload'
         target_type = 'text'
         options = {}
@@ -560,6 +574,10 @@ class ConverterHandler(SimpleHTTPRequestHandler):
                         end = line.find('"', start)
                         if end > start:
                             filename = line[start:end]
+            elif 'name="staged"' in header_text:
+                staged = content.decode('utf-8').strip()
+            elif 'name="filename"' in header_text:
+                filename = content.decode('utf-8').strip() or filename
             elif 'name="target"' in header_text:
                 target_type = content.decode('utf-8').strip()
             elif 'name="options"' in header_text:
@@ -568,12 +586,20 @@ class ConverterHandler(SimpleHTTPRequestHandler):
                 except:
                     pass
 
-        if not file_data:
+        staged_path = None
+        if staged is not None:
+            staged_path = STAGING_DIR / staged
+            if Path(staged).name != staged or not staged_path.is_file():
+                self._json_response({'error': 'Staged file not found'}, 400)
+                return
+        elif not file_data:
             self._json_response({'error': 'No file provided'}, 400)
             return
 
         ext = Path(filename).suffix.lower()
         if ext not in ALL_FORMATS:
+            if staged_path:
+                staged_path.unlink(missing_ok=True)
             self._json_response({
                 'error': f'Unsupported format: {ext}',
                 'supported': list(ALL_FORMATS)
@@ -586,8 +612,11 @@ class ConverterHandler(SimpleHTTPRequestHandler):
         job_id = str(uuid.uuid4())[:8]
         file_path = UPLOAD_DIR / f"{job_id}{ext}"
 
-        with open(file_path, 'wb') as f:
-            f.write(file_data)
+        if staged_path:
+            staged_path.replace(file_path)
+        else:
+            with open(file_path, 'wb') as f:
+                f.write(file_data)
 
         jobs[job_id] = {
             'id': job_id,
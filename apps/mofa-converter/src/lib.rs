@@ -1,11 +1,24 @@
 //! MoFA Content Converter
 //!
-//! A simple tool for converting between audio, video, and text formats
+//! A simple tool for converting between audio, video, and text formats.
+//! Text files are read aloud natively ([`text_to_audio`]) when the page
+//! runs inside the app, and files picked through the app can be trashed or
+//! archived after converting ([`originals`]).
 
+pub mod originals;
 pub mod screen;
+pub mod text_to_audio;
 
 use makepad_widgets::*;
 use mofa_widgets::{AppInfo, MofaApp};
+use mofa_widgets::app_storage::AppStorage;
+
+/// Data this app keeps under `~/.mofa-studio`
+pub const STORAGE: AppStorage = AppStorage {
+    prefs: &[],
+    cache: &[],
+    webview_profile: true,
+};
 
 pub struct MoFaConverterApp;
 
@@ -18,6 +31,7 @@ impl MofaApp for MoFaConverterApp {
             tab_id: Some(live_id!(converter_tab)),
             page_id: Some(live_id!(converter_page)),
             show_in_sidebar: true,
+            storage: STORAGE,
             ..Default::default()
         }
     }
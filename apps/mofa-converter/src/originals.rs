@@ -0,0 +1,175 @@
+//! Originals - source files picked through the app, and what happens to
+//! them after a successful conversion
+//!
+//! Files chosen in the page's own file input reach the backend as uploaded
+//! copies, so the app never learns where they came from. Files picked with
+//! the app's dialog (`pick_files`) are linked into the backend's staging
+//! directory instead and remembered here by id; once their conversion has
+//! succeeded the page can ask to move the original to the trash or to
+//! archive it in a [`ARCHIVE_DIR`] folder next to it.
+
+use mofa_widgets::handoff;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// Folder, next to an original, that archived originals are moved into
+pub const ARCHIVE_DIR: &str = "Converted originals";
+
+/// A picked file as reported to the page
+#[derive(Debug, Clone, PartialEq)]
+pub struct PickedFile {
+    pub id: String,
+    pub name: String,
+    /// Name in the staging directory, or why it could not be staged
+    pub staged: Result<String, String>,
+}
+
+impl PickedFile {
+    pub fn to_json(&self) -> serde_json::Value {
+        match &self.staged {
+            Ok(staged) => serde_json::json!({ "id": self.id, "name": self.name, "staged": staged }),
+            Err(e) => serde_json::json!({ "id": self.id, "name": self.name, "error": e }),
+        }
+    }
+}
+
+/// Paths of the originals picked this session, by id
+#[derive(Debug, Default)]
+pub struct Originals {
+    paths: HashMap<String, PathBuf>,
+    next_id: u64,
+}
+
+impl Originals {
+    /// Remember a picked file and return its id
+    pub fn add(&mut self, path: PathBuf) -> String {
+        self.next_id += 1;
+        let id = format!("original-{}", self.next_id);
+        self.paths.insert(id.clone(), path);
+        id
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Path> {
+        self.paths.get(id).map(PathBuf::as_path)
+    }
+
+    /// Forget an original, returning where it was
+    pub fn remove(&mut self, id: &str) -> Option<PathBuf> {
+        self.paths.remove(id)
+    }
+
+    /// Link the originals `ids` into `dir` on a background thread; large
+    /// files on another volume have to be copied
+    pub fn stage(&self, ids: &[String], dir: PathBuf) -> Receiver<Vec<PickedFile>> {
+        let files: Vec<(String, PathBuf)> = ids
+            .iter()
+            .filter_map(|id| self.paths.get(id).map(|path| (id.clone(), path.clone())))
+            .collect();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let picked = files
+                .into_iter()
+                .map(|(id, path)| {
+                    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                    let staged = staged_name(&id, &path);
+                    let staged = handoff::link_or_copy(&path, &dir.join(&staged))
+                        .map(|_| staged)
+                        .map_err(|e| format!("{}: {}", path.display(), e));
+                    PickedFile { id, name, staged }
+                })
+                .collect();
+            let _ = tx.send(picked);
+        });
+        rx
+    }
+}
+
+/// Staged copies are named by id, keeping the extension the backend
+/// detects the file type from
+fn staged_name(id: &str, path: &Path) -> String {
+    match path.extension() {
+        Some(ext) => format!("{}.{}", id, ext.to_string_lossy().to_lowercase()),
+        None => id.to_string(),
+    }
+}
+
+/// Move an original into the [`ARCHIVE_DIR`] folder beside it, numbering
+/// the name if an earlier original of the same name is already there
+pub fn archive(path: &Path) -> Result<PathBuf, String> {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return Err(format!("Not a file: {}", path.display()));
+    };
+    if !path.is_file() {
+        return Err(format!("File not found: {}", path.display()));
+    }
+    let dir = parent.join(ARCHIVE_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let target = unique_path(&dir, &name.to_string_lossy());
+    fs::rename(path, &target).map_err(|e| format!("Could not archive {}: {}", path.display(), e))?;
+    Ok(target)
+}
+
+/// `dir/name`, or `dir/stem-2.ext`, `dir/stem-3.ext`... if that exists
+fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let first = dir.join(name);
+    if !first.exists() {
+        return first;
+    }
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, format!(".{}", ext)),
+        _ => (name, String::new()),
+    };
+    (2..)
+        .map(|n| dir.join(format!("{}-{}{}", stem, n, ext)))
+        .find(|path| !path.exists())
+        .expect("some numbered name is free")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_numbers_repeated_names() {
+        let dir = std::env::temp_dir().join(format!("mofa-originals-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let original = dir.join("talk.MP4");
+        fs::write(&original, "first").unwrap();
+        assert_eq!(archive(&original).unwrap(), dir.join(ARCHIVE_DIR).join("talk.MP4"));
+        fs::write(&original, "second").unwrap();
+        let second = archive(&original).unwrap();
+        assert_eq!(second, dir.join(ARCHIVE_DIR).join("talk-2.MP4"));
+        assert_eq!(fs::read_to_string(second).unwrap(), "second");
+        assert!(!original.exists());
+        assert!(archive(&original).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_stage_links_picked_files_by_id() {
+        let dir = std::env::temp_dir().join(format!("mofa-staging-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("Talk.WAV");
+        fs::write(&source, "audio").unwrap();
+
+        let mut originals = Originals::default();
+        let found = originals.add(source);
+        let missing = originals.add(dir.join("gone.mp3"));
+        let picked = originals.stage(&[found.clone(), missing], dir.join("staged")).recv().unwrap();
+
+        assert_eq!(picked[0].name, "Talk.WAV");
+        assert_eq!(picked[0].staged, Ok(format!("{}.wav", found)));
+        assert_eq!(fs::read_to_string(dir.join("staged").join(format!("{}.wav", found))).unwrap(), "audio");
+        assert!(picked[1].staged.is_err());
+        assert_eq!(originals.remove(&found), Some(dir.join("Talk.WAV")));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
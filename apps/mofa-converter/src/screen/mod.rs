@@ -3,9 +3,17 @@
 //! WebView-based content converter for audio, video, and text
 
 use makepad_widgets::*;
+use mofa_widgets::activity::{ActivityHandle, ActivityKind, ActivityRegistry};
+use mofa_widgets::desktop;
+use mofa_widgets::handoff::{Handoff, HandoffBus};
+use mofa_widgets::jobs::{JobHandle, JobRegistry};
 use mofa_widgets::webview::{WebViewAction, WebViewContainerWidgetExt};
+use crate::originals::{self, Originals, PickedFile};
+use crate::text_to_audio::{self, ConversionState, TextToAudioJob, TextToAudioRequest};
+use std::collections::HashMap;
 use std::net::TcpListener;
 use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{Receiver, TryRecvError};
 use std::sync::{Arc, Mutex};
 use std::path::PathBuf;
 use std::fs;
@@ -166,6 +174,7 @@ live_design! {
                     webview = <WebViewContainer> {
                         width: Fill, height: Fill
                         url: "about:blank"
+                        profile: "mofa-converter"
                     }
                 }
             }
@@ -246,6 +255,39 @@ live_design! {
     }
 }
 
+/// Where the backend writes conversion results (`OUTPUT_DIR` in app.py)
+fn output_dir() -> PathBuf {
+    std::env::temp_dir().join("mofa-converter").join("outputs")
+}
+
+/// Where picked originals are linked for the backend (`STAGING_DIR` in app.py)
+fn staging_dir() -> PathBuf {
+    std::env::temp_dir().join("mofa-converter").join("staged")
+}
+
+/// A result file named by the page, refusing anything outside `output_dir()`
+fn output_file(filename: &str) -> Result<PathBuf, String> {
+    let name = std::path::Path::new(filename);
+    match name.file_name() {
+        Some(file) if file == name.as_os_str() => Ok(output_dir().join(file)),
+        _ => Err(format!("Invalid output file: {}", filename)),
+    }
+}
+
+fn parse_ipc_payload(data: &str) -> Option<serde_json::Value> {
+    let parsed = match serde_json::from_str(data) {
+        Ok(serde_json::Value::String(inner)) => serde_json::from_str(&inner),
+        other => other,
+    };
+    match parsed {
+        Ok(value) => Some(value),
+        Err(e) => {
+            ::log::warn!("Invalid IPC payload: {}", e);
+            None
+        }
+    }
+}
+
 fn find_available_port() -> Option<u16> {
     TcpListener::bind("127.0.0.1:0")
         .ok()
@@ -403,14 +445,54 @@ pub struct ConverterScreen {
     #[rust]
     server: Arc<Mutex<PythonServer>>,
 
+    /// Lists the server in the shell's running panel while it runs
+    #[rust]
+    server_activity: Option<ActivityHandle>,
+
     #[rust]
     url_loaded: bool,
+
+    /// Conversions running in the page, by job id
+    #[rust]
+    jobs: HashMap<String, JobHandle>,
+
+    /// Native text-to-audio conversions by the page's job id, with the
+    /// state last sent to the page
+    #[rust]
+    tts_jobs: HashMap<String, (TextToAudioJob, Option<ConversionState>)>,
+
+    #[rust]
+    tts_timer: Timer,
+
+    /// Source files picked through the app's dialog this session
+    #[rust]
+    originals: Originals,
+
+    /// Picked files being linked into the staging directory
+    #[rust]
+    staging: Option<Receiver<Vec<PickedFile>>>,
+
+    #[rust]
+    staging_timer: Timer,
 }
 
 impl Widget for ConverterScreen {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
 
+        if self.tts_timer.is_event(event).is_some() {
+            self.poll_tts_jobs(cx);
+        }
+
+        if self.staging_timer.is_event(event).is_some() {
+            self.poll_staging(cx);
+        }
+
+        // Stop requests from the shell's running panel
+        if self.server_activity.as_ref().is_some_and(|a| a.stop_requested()) {
+            self.toggle_server(cx);
+        }
+
         let actions = match event {
             Event::Actions(actions) => actions.as_slice(),
             _ => &[],
@@ -456,7 +538,18 @@ impl Widget for ConverterScreen {
                                 self.set_status(cx, "Connected", 1.0);
                             }
                         }
-                        WebViewAction::IpcMessage { .. } | WebViewAction::None => {}
+                        WebViewAction::IpcMessage { channel, data } => {
+                            match channel.as_str() {
+                                "job" => self.handle_job_update(&data),
+                                "post_action" => self.handle_post_action(&data),
+                                "pick_files" => self.handle_pick_files(cx),
+                                "tts_voices" => self.send_to_page("tts_voices", &text_to_audio::voices_json()),
+                                "tts_convert" => self.handle_tts_convert(cx, &data),
+                                "tts_cancel" => self.handle_tts_cancel(&data),
+                                _ => {}
+                            }
+                        }
+                        WebViewAction::InitTiming { .. } | WebViewAction::None => {}
                     }
                 }
             }
@@ -479,6 +572,13 @@ impl ConverterScreen {
             let mut server = self.server.lock().unwrap();
             server.stop();
             drop(server);
+            self.jobs.clear();
+            for (job, _) in self.tts_jobs.values() {
+                job.cancel();
+            }
+            self.tts_jobs.clear();
+            cx.stop_timer(self.tts_timer);
+            self.server_activity = None;
             self.set_status(cx, "Server stopped", 0.0);
             self.url_loaded = false;
             self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Start Server");
@@ -493,6 +593,11 @@ impl ConverterScreen {
             match result {
                 Ok(port) => {
                     ::log::info!("Converter server started on port {}", port);
+                    let activity = ActivityRegistry::global()
+                        .register(ActivityKind::Server, "mofa-converter", "Converter server")
+                        .stoppable();
+                    activity.set_state(&format!("port {}", port));
+                    self.server_activity = Some(activity);
                     self.set_status(cx, &format!("Server running on port {}", port), 2.0);
                     self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Stop Server");
 
@@ -527,6 +632,196 @@ impl ConverterScreen {
         }
     }
 
+    /// Mirror a conversion's progress into the shared job registry
+    fn handle_job_update(&mut self, data: &str) {
+        let Some(update) = parse_ipc_payload(data) else {
+            return;
+        };
+        let Some(job_id) = update.get("job_id").and_then(|v| v.as_str()) else {
+            return;
+        };
+
+        match update.get("status").and_then(|v| v.as_str()).unwrap_or("") {
+            "completed" => {
+                if let Some(job) = self.jobs.remove(job_id) {
+                    job.finish();
+                }
+            }
+            "error" => {
+                self.jobs.remove(job_id);
+            }
+            _ => {
+                let job = self.jobs.entry(job_id.to_string())
+                    .or_insert_with(|| JobRegistry::global().start("mofa-converter", "converting"));
+                if let Some(progress) = update.get("progress").and_then(|v| v.as_f64()) {
+                    job.set_progress(progress as f32 / 100.0);
+                }
+            }
+        }
+    }
+
+    /// Act on finished conversions: open or reveal an output, open the
+    /// output folder, send outputs to another app, or trash / archive a
+    /// picked original
+    ///
+    /// The outcome goes back to the page on its own channel, with the page's
+    /// `row`; a failure here never touches the job, which has already
+    /// completed.
+    fn handle_post_action(&mut self, data: &str) {
+        let request = parse_ipc_payload(data).unwrap_or_default();
+        let text = |key: &str| request.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let action = text("action");
+
+        let result = match action.as_str() {
+            "open" => output_file(&text("filename")).and_then(|path| desktop::open_file(&path)),
+            "reveal" => output_file(&text("filename")).and_then(|path| desktop::reveal(&path)),
+            "reveal_folder" => desktop::open_folder(&output_dir()),
+            "send" => self.send_outputs(&request),
+            "trash_original" | "archive_original" => {
+                let id = text("original");
+                let result = match self.originals.get(&id) {
+                    Some(path) if action == "trash_original" => desktop::move_to_trash(path),
+                    Some(path) => originals::archive(path).map(|_| ()),
+                    None => Err(format!("Unknown original: {}", id)),
+                };
+                if result.is_ok() {
+                    self.originals.remove(&id);
+                }
+                result
+            }
+            _ => Err(format!("Unknown action: {}", action)),
+        };
+        if let Err(ref e) = result {
+            ::log::warn!("Post-conversion action '{}' failed: {}", action, e);
+        }
+
+        let reply = serde_json::json!({
+            "action": action,
+            "row": request.get("row"),
+            "ok": result.is_ok(),
+            "error": result.err(),
+        });
+        self.send_to_page("post_action", &reply);
+    }
+
+    /// Hand output files to another app through the hand-off bus; the shell
+    /// then switches to it
+    fn send_outputs(&self, request: &serde_json::Value) -> Result<(), String> {
+        let target = request.get("target").and_then(|v| v.as_str()).unwrap_or_default();
+        let files: Vec<PathBuf> = request
+            .get("filenames")
+            .and_then(|v| v.as_array())
+            .map(|names| names.iter().filter_map(|n| n.as_str()).map(output_file).collect())
+            .unwrap_or_else(|| Ok(Vec::new()))?;
+        if target.is_empty() || files.is_empty() {
+            return Err("Nothing to send".to_string());
+        }
+        if let Some(missing) = files.iter().find(|path| !path.is_file()) {
+            return Err(format!("File not found: {}", missing.display()));
+        }
+        HandoffBus::global().send(Handoff::new("mofa-converter", target, files));
+        Ok(())
+    }
+
+    /// Pick source files with the system dialog, so their originals can be
+    /// trashed or archived after converting
+    fn handle_pick_files(&mut self, cx: &mut Cx) {
+        let paths = rfd::FileDialog::new()
+            .set_title("Choose files to convert")
+            .pick_files()
+            .unwrap_or_default();
+        let ids: Vec<String> = paths.into_iter().map(|path| self.originals.add(path)).collect();
+        if ids.is_empty() {
+            self.send_to_page("picked", &serde_json::json!({ "files": [] }));
+            return;
+        }
+        self.staging = Some(self.originals.stage(&ids, staging_dir()));
+        self.staging_timer = cx.start_interval(0.2);
+    }
+
+    /// Tell the page which picked files are ready to convert
+    fn poll_staging(&mut self, cx: &mut Cx) {
+        let Some(staging) = &self.staging else {
+            cx.stop_timer(self.staging_timer);
+            return;
+        };
+        let picked = match staging.try_recv() {
+            Ok(picked) => picked,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Vec::new(),
+        };
+        for file in picked.iter().filter(|file| file.staged.is_err()) {
+            self.originals.remove(&file.id);
+        }
+        let files: Vec<serde_json::Value> = picked.iter().map(PickedFile::to_json).collect();
+        self.send_to_page("picked", &serde_json::json!({ "files": files }));
+        self.staging = None;
+        cx.stop_timer(self.staging_timer);
+    }
+
+    /// Read text sent by the page aloud into an audio file
+    fn handle_tts_convert(&mut self, cx: &mut Cx, data: &str) {
+        let mut value = parse_ipc_payload(data).unwrap_or_default();
+        // Picked originals are read here; the page only knows their names
+        let original = value.get("original").and_then(|v| v.as_str()).and_then(|id| self.originals.get(id));
+        if let Some(text) = original.map(|path| fs::read_to_string(path).unwrap_or_default()) {
+            value["text"] = serde_json::Value::String(text);
+        }
+        let job_id = value.get("job_id").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+
+        match TextToAudioRequest::from_json(&value) {
+            Ok(request) => {
+                ::log::info!("Text to audio: {} ({} chars)", request.name, request.text.len());
+                if self.tts_jobs.is_empty() {
+                    self.tts_timer = cx.start_interval(0.25);
+                }
+                let job = TextToAudioJob::start(request, output_dir());
+                if let Some((previous, _)) = self.tts_jobs.insert(job_id, (job, None)) {
+                    previous.cancel();
+                }
+            }
+            Err(e) => {
+                let message = ConversionState::Failed(e).to_json(&job_id);
+                self.send_to_page("tts_progress", &message);
+            }
+        }
+    }
+
+    fn handle_tts_cancel(&mut self, data: &str) {
+        let value = parse_ipc_payload(data).unwrap_or_default();
+        let job_id = value.get("job_id").and_then(|v| v.as_str()).unwrap_or_default();
+        if let Some((job, _)) = self.tts_jobs.get(job_id) {
+            job.cancel();
+        }
+    }
+
+    /// Forward changed conversion states to the page, dropping finished jobs
+    fn poll_tts_jobs(&mut self, cx: &mut Cx) {
+        let mut updates = Vec::new();
+        for (job_id, (job, sent)) in &mut self.tts_jobs {
+            let state = job.state();
+            if sent.as_ref() != Some(&state) {
+                updates.push(state.to_json(job_id));
+                *sent = Some(state);
+            }
+        }
+        for message in &updates {
+            self.send_to_page("tts_progress", message);
+        }
+
+        self.tts_jobs.retain(|_, (_, sent)| !sent.as_ref().is_some_and(ConversionState::is_finished));
+        if self.tts_jobs.is_empty() {
+            cx.stop_timer(self.tts_timer);
+        }
+    }
+
+    fn send_to_page(&self, channel: &str, message: &serde_json::Value) {
+        let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+        if let Err(e) = webview.send_to_js(channel, &message.to_string()) {
+            ::log::warn!("Failed to send {} to the page: {}", channel, e);
+        }
+    }
+
     fn go_back(&self) {
         let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
         let _ = webview.go_back();
@@ -646,9 +941,7 @@ impl ConverterScreenRef {
             );
 
             // Send theme to WebView
-            let webview = inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
-            let js = format!("if(window.setTheme) window.setTheme({});", dark_mode);
-            let _ = webview.eval(&js);
+            inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview)).set_dark_mode(dark_mode);
 
             inner.view.redraw(cx);
         }
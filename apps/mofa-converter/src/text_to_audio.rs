@@ -0,0 +1,353 @@
+//! Text to audio - read a .txt or .md file into an audio file
+//!
+//! The page sends the file's text over IPC (`tts_convert`); a worker thread
+//! splits it into sentence-sized chunks, synthesizes each one with the
+//! shared [`mofa_widgets::tts`] backend into a temporary WAV, then joins and
+//! encodes them with [`mofa_widgets::audio_export`]. Progress is counted in
+//! chunks and mirrored into the shell's job list, where the conversion can
+//! be cancelled between chunks.
+
+use mofa_widgets::audio_export::{self, AudioFileFormat};
+use mofa_widgets::jobs::{JobHandle, JobRegistry};
+use mofa_widgets::tts::{self, chunk_text, TtsOptions};
+use mofa_widgets::voices::{favorite_voices, favorites_first};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Characters per synthesized chunk
+const MAX_CHUNK_CHARS: usize = 400;
+
+/// A conversion as requested by the page
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextToAudioRequest {
+    /// Name of the source file; the output takes its stem
+    pub name: String,
+    pub text: String,
+    /// Strip Markdown syntax before reading
+    pub markdown: bool,
+    pub options: TtsOptions,
+    pub format: AudioFileFormat,
+}
+
+impl TextToAudioRequest {
+    /// Parse the `tts_convert` payload
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, String> {
+        let text_field = |key: &str| value.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+        let name = text_field("name").to_string();
+        let text = text_field("text").to_string();
+        if text.trim().is_empty() {
+            return Err("There is no text to read".to_string());
+        }
+        let format = match text_field("format") {
+            "" => AudioFileFormat::Mp3,
+            ext => AudioFileFormat::from_extension(ext).ok_or_else(|| format!("Unsupported audio format: {}", ext))?,
+        };
+        let markdown = value
+            .get("markdown")
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| name.to_ascii_lowercase().ends_with(".md"));
+        let options = TtsOptions {
+            voice: Some(text_field("voice").to_string()).filter(|v| !v.is_empty()),
+            speed: value.get("speed").and_then(|v| v.as_f64()).unwrap_or(1.0) as f32,
+        };
+        Ok(Self { name, text, markdown, options, format })
+    }
+}
+
+/// Voices of the default backend for the page's dropdown, favorites first
+pub fn voices_json() -> serde_json::Value {
+    let backend = tts::default_backend();
+    match backend.voices() {
+        Ok(mut voices) => {
+            favorites_first(&mut voices, &favorite_voices(), backend.name(), |voice| &voice.id);
+            let list: Vec<_> = voices
+                .iter()
+                .map(|v| serde_json::json!({ "id": v.id, "name": v.name, "language": v.language }))
+                .collect();
+            serde_json::json!({ "backend": backend.name(), "voices": list })
+        }
+        Err(e) => serde_json::json!({ "backend": backend.name(), "voices": [], "error": e }),
+    }
+}
+
+/// Text to speak: Markdown loses its markup, code blocks and link targets
+pub fn plain_text(content: &str, markdown: bool) -> String {
+    if !markdown {
+        return content.to_string();
+    }
+
+    let mut out = String::new();
+    let mut in_code = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        // Rules and table separators have nothing to say
+        if in_code || (!trimmed.is_empty() && trimmed.chars().all(|c| matches!(c, '-' | '*' | '_' | '=' | '|' | ':' | ' '))) {
+            continue;
+        }
+
+        let mut text = trimmed.trim_start_matches(['#', '>']).trim_start();
+        for marker in ["- [ ] ", "- [x] ", "- ", "* ", "+ "] {
+            if let Some(rest) = text.strip_prefix(marker) {
+                text = rest;
+                break;
+            }
+        }
+        if let Some((number, rest)) = text.split_once(". ") {
+            if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+                text = rest;
+            }
+        }
+
+        let text = strip_inline(text);
+        let text = text.replace('|', " ");
+        let text = text.trim();
+        if text.is_empty() {
+            // Keep paragraph breaks so sentences do not run together
+            if !out.ends_with("\n\n") && !out.is_empty() {
+                out.push('\n');
+            }
+            continue;
+        }
+        out.push_str(text);
+        out.push('\n');
+    }
+    out.trim_end().to_string()
+}
+
+/// Drop emphasis, inline code ticks, images, HTML tags and link targets
+fn strip_inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' | '_' | '`' | '~' => {}
+            '!' if chars.peek() == Some(&'[') => {
+                // Images are skipped along with their alt text
+                skip_through(&mut chars, ']');
+                if chars.peek() == Some(&'(') {
+                    skip_through(&mut chars, ')');
+                }
+            }
+            ']' if chars.peek() == Some(&'(') => skip_through(&mut chars, ')'),
+            '[' => {}
+            '<' => skip_through(&mut chars, '>'),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn skip_through(chars: &mut impl Iterator<Item = char>, end: char) {
+    for c in chars.by_ref() {
+        if c == end {
+            break;
+        }
+    }
+}
+
+/// Output file name from the source name, e.g. `notes.md` -> `notes.mp3`;
+/// `-2`, `-3`... is appended while `taken` reports the name as used.
+/// Names stay ASCII so they can be put in a download URL as they are.
+pub fn output_name(source: &str, format: AudioFileFormat, taken: impl Fn(&str) -> bool) -> String {
+    let stem = Path::new(source).file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let stem: String = stem
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    let stem = match stem.trim_matches('_') {
+        "" => "speech",
+        stem => stem,
+    };
+
+    let ext = format.extension();
+    let mut name = format!("{}.{}", stem, ext);
+    let mut n = 2;
+    while taken(&name) {
+        name = format!("{}-{}.{}", stem, n, ext);
+        n += 1;
+    }
+    name
+}
+
+/// Where a conversion is
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConversionState {
+    Reading { done: usize, total: usize },
+    Encoding,
+    /// File name inside the output folder
+    Done(String),
+    Failed(String),
+    Cancelled,
+}
+
+impl ConversionState {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done(_) | Self::Failed(_) | Self::Cancelled)
+    }
+
+    /// The `tts_progress` message for the page
+    pub fn to_json(&self, job_id: &str) -> serde_json::Value {
+        let (status, done, total) = match self {
+            Self::Reading { done, total } => ("reading", *done, *total),
+            Self::Encoding => ("encoding", 0, 0),
+            Self::Done(_) => ("completed", 0, 0),
+            Self::Failed(_) => ("error", 0, 0),
+            Self::Cancelled => ("cancelled", 0, 0),
+        };
+        let mut message = serde_json::json!({ "job_id": job_id, "status": status, "done": done, "total": total });
+        match self {
+            Self::Done(filename) => {
+                message["filename"] = filename.as_str().into();
+                message["download_url"] = format!("/api/download/{}", filename).into();
+            }
+            Self::Failed(error) => message["error"] = error.as_str().into(),
+            _ => {}
+        }
+        message
+    }
+}
+
+/// A running conversion, cancellable from the page or the shell's job list
+pub struct TextToAudioJob {
+    state: Arc<Mutex<ConversionState>>,
+    job_id: u64,
+}
+
+impl TextToAudioJob {
+    pub fn start(request: TextToAudioRequest, output_dir: PathBuf) -> Self {
+        let chunks = chunk_text(&plain_text(&request.text, request.markdown), MAX_CHUNK_CHARS);
+        let state = Arc::new(Mutex::new(ConversionState::Reading { done: 0, total: chunks.len() }));
+        let job = JobRegistry::global().start("mofa-converter", "reading aloud").cancellable();
+        let job_id = job.id();
+
+        let worker = Worker { state: Arc::clone(&state), job };
+        std::thread::spawn(move || {
+            let final_state = match worker.run(&request, &chunks, &output_dir) {
+                Ok(state) => state,
+                Err(e) => {
+                    ::log::error!("Text to audio failed: {}", e);
+                    ConversionState::Failed(e)
+                }
+            };
+            if matches!(final_state, ConversionState::Done(_)) {
+                worker.job.finish();
+            }
+            worker.set_state(final_state);
+        });
+
+        Self { state, job_id }
+    }
+
+    pub fn state(&self) -> ConversionState {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn cancel(&self) {
+        JobRegistry::global().cancel(self.job_id);
+    }
+}
+
+struct Worker {
+    state: Arc<Mutex<ConversionState>>,
+    job: JobHandle,
+}
+
+impl Worker {
+    fn set_state(&self, state: ConversionState) {
+        *self.state.lock().unwrap_or_else(|e| e.into_inner()) = state;
+    }
+
+    fn run(&self, request: &TextToAudioRequest, chunks: &[String], output_dir: &Path) -> Result<ConversionState, String> {
+        if chunks.is_empty() {
+            return Err("There is no text to read".to_string());
+        }
+        let work_dir = std::env::temp_dir().join("mofa-converter").join(format!("tts-{}", self.job.id()));
+        fs::create_dir_all(&work_dir).map_err(|e| format!("Cannot create {}: {}", work_dir.display(), e))?;
+        let result = self.synthesize(request, chunks, &work_dir, output_dir);
+        let _ = fs::remove_dir_all(&work_dir);
+        result
+    }
+
+    fn synthesize(
+        &self,
+        request: &TextToAudioRequest,
+        chunks: &[String],
+        work_dir: &Path,
+        output_dir: &Path,
+    ) -> Result<ConversionState, String> {
+        let backend = tts::default_backend();
+        let total = chunks.len();
+        // Joining and encoding count as one more step
+        let steps = (total + 1) as f32;
+
+        let mut parts = Vec::with_capacity(total);
+        for (index, chunk) in chunks.iter().enumerate() {
+            if self.job.is_cancelled() {
+                return Ok(ConversionState::Cancelled);
+            }
+            let audio = backend
+                .synthesize(chunk, &request.options)
+                .map_err(|e| format!("Chunk {} of {}: {}", index + 1, total, e))?;
+            let part = work_dir.join(format!("chunk-{:05}.wav", index));
+            tts::write_wav(&part, &audio)?;
+            parts.push(part);
+
+            self.set_state(ConversionState::Reading { done: index + 1, total });
+            self.job.set_progress((index + 1) as f32 / steps);
+        }
+        if self.job.is_cancelled() {
+            return Ok(ConversionState::Cancelled);
+        }
+
+        self.set_state(ConversionState::Encoding);
+        let joined = work_dir.join("joined.wav");
+        audio_export::concat_wav(&parts, &joined)?;
+
+        fs::create_dir_all(output_dir).map_err(|e| format!("Cannot create {}: {}", output_dir.display(), e))?;
+        let filename = output_name(&request.name, request.format, |name| output_dir.join(name).exists());
+        audio_export::encode(&joined, &output_dir.join(&filename), request.format)?;
+
+        ::log::info!("Text to audio saved {} ({} chunks)", filename, total);
+        Ok(ConversionState::Done(filename))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_strips_markdown() {
+        let md = "# Title\n\nSome **bold** and [a link](http://x.y) with `code`.\n\n```\nlet x = 1;\n```\n- item one\n2. item two\n\n---\n| a | b |\n|---|---|\n![logo](logo.png) <br>done";
+        assert_eq!(
+            plain_text(md, true),
+            "Title\n\nSome bold and a link with code.\n\nitem one\nitem two\n\na   b\ndone"
+        );
+        assert_eq!(plain_text("# not markdown", false), "# not markdown");
+    }
+
+    #[test]
+    fn test_output_name() {
+        let taken = |name: &str| name == "notes.mp3" || name == "notes-2.mp3";
+        assert_eq!(output_name("notes.md", AudioFileFormat::Mp3, taken), "notes-3.mp3");
+        assert_eq!(output_name("a/my notes?.txt", AudioFileFormat::Wav, |_| false), "my_notes.wav");
+        assert_eq!(output_name("笔记.md", AudioFileFormat::M4a, |_| false), "speech.m4a");
+    }
+
+    #[test]
+    fn test_request_from_json() {
+        let value = serde_json::json!({ "name": "Notes.MD", "text": "Hi.", "voice": "", "speed": 1.5, "format": "m4a" });
+        let request = TextToAudioRequest::from_json(&value).unwrap();
+        assert!(request.markdown);
+        assert_eq!(request.options, TtsOptions { voice: None, speed: 1.5 });
+        assert_eq!(request.format, AudioFileFormat::M4a);
+
+        assert!(TextToAudioRequest::from_json(&serde_json::json!({ "text": "  " })).is_err());
+        assert!(TextToAudioRequest::from_json(&serde_json::json!({ "text": "Hi", "format": "ogg" })).is_err());
+    }
+}
@@ -0,0 +1,421 @@
+//! Debate judging - LLM-generated verdicts persisted per session
+//!
+//! When a debate ends - its dataflow stops, on its own or because the user
+//! stopped it - the accumulated transcript is sent to the configured chat
+//! provider with a judging prompt. The reply is parsed into a structured
+//! [`Verdict`] and stored on the [`DebateSession`], which is saved under
+//! `~/.mofa-studio/debate/sessions/`. Every verdict is kept, so re-judging
+//! with another preset never overwrites earlier results.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use mofa_settings::data::{ChatEndpoint, ChatError, ChatRequest};
+
+/// Saved sessions, relative to the MoFA Studio data directory
+pub const SESSIONS_DIR: &str = "debate/sessions";
+
+/// Request timeout for judge calls
+const JUDGE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Prompt presets for judging
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum JudgePreset {
+    #[default]
+    Balanced,
+    Evidence,
+    Persuasion,
+}
+
+impl JudgePreset {
+    /// All presets, in dropdown order
+    pub const ALL: [JudgePreset; 3] = [
+        JudgePreset::Balanced,
+        JudgePreset::Evidence,
+        JudgePreset::Persuasion,
+    ];
+
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL.get(index).copied().unwrap_or_default()
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            JudgePreset::Balanced => "Balanced",
+            JudgePreset::Evidence => "Evidence",
+            JudgePreset::Persuasion => "Persuasion",
+        }
+    }
+
+    /// Criteria the judge must score, in order
+    pub fn criteria(&self) -> &'static [&'static str] {
+        match self {
+            JudgePreset::Balanced => &["argument", "evidence", "rebuttal", "clarity"],
+            JudgePreset::Evidence => &["evidence", "accuracy", "logic"],
+            JudgePreset::Persuasion => &["persuasiveness", "rebuttal", "delivery"],
+        }
+    }
+
+    fn focus(&self) -> &'static str {
+        match self {
+            JudgePreset::Balanced => "Weigh all criteria equally.",
+            JudgePreset::Evidence => {
+                "Reward claims backed by facts and sound reasoning; penalize unsupported assertions."
+            }
+            JudgePreset::Persuasion => {
+                "Reward the side that would most likely convince a neutral audience."
+            }
+        }
+    }
+
+    /// System prompt asking for a JSON verdict
+    pub fn system_prompt(&self) -> String {
+        format!(
+            "You are an impartial debate judge. PRO is Student 1, CON is Student 2; \
+             the Tutor only moderates and is not scored. {} \
+             Score each side from 0 to 10 on these criteria: {}. \
+             Reply with JSON only, no prose, in this shape: \
+             {{\"winner\": \"pro\" | \"con\" | \"tie\", \
+             \"scores\": [{{\"criterion\": string, \"pro\": number, \"con\": number}}], \
+             \"rationale\": string}}. \
+             Write the rationale in the language of the debate.",
+            self.focus(),
+            self.criteria().join(", ")
+        )
+    }
+}
+
+/// Outcome of a debate
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Winner {
+    Pro,
+    Con,
+    Tie,
+}
+
+impl Winner {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Winner::Pro => "PRO (Student 1)",
+            Winner::Con => "CON (Student 2)",
+            Winner::Tie => "Tie",
+        }
+    }
+}
+
+/// Score for one criterion
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CriterionScore {
+    pub criterion: String,
+    pub pro: f32,
+    pub con: f32,
+}
+
+/// A structured verdict returned by the judge
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Verdict {
+    pub winner: Winner,
+    pub scores: Vec<CriterionScore>,
+    pub rationale: String,
+    #[serde(default)]
+    pub preset: JudgePreset,
+    #[serde(default)]
+    pub model: String,
+    /// Unix timestamp (milliseconds)
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+impl Verdict {
+    /// Sum of scores for (PRO, CON)
+    pub fn totals(&self) -> (f32, f32) {
+        self.scores
+            .iter()
+            .fold((0.0, 0.0), |(pro, con), s| (pro + s.pro, con + s.con))
+    }
+
+    /// Render as Markdown for the results panel and exports
+    pub fn to_markdown(&self) -> String {
+        let (pro_total, con_total) = self.totals();
+        let mut out = format!(
+            "**Winner: {}**  \nPreset: {} · Model: {} · PRO {:.1} / CON {:.1}\n\n",
+            self.winner.label(),
+            self.preset.label(),
+            if self.model.is_empty() { "unknown" } else { &self.model },
+            pro_total,
+            con_total
+        );
+        for score in &self.scores {
+            out.push_str(&format!(
+                "- {}: PRO {:.1} · CON {:.1}\n",
+                score.criterion, score.pro, score.con
+            ));
+        }
+        out.push('\n');
+        out.push_str(&self.rationale);
+        out
+    }
+}
+
+/// Errors raised while judging
+#[derive(Debug, Clone, PartialEq)]
+pub enum JudgeError {
+    EmptyTranscript,
+    NoProvider,
+    Request(String),
+    Parse(String),
+}
+
+impl std::fmt::Display for JudgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JudgeError::EmptyTranscript => write!(f, "Nothing to judge yet"),
+            JudgeError::NoProvider => write!(f, "No enabled provider with an API key"),
+            JudgeError::Request(e) => write!(f, "Provider request failed: {}", e),
+            JudgeError::Parse(e) => write!(f, "Could not read verdict: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for JudgeError {}
+
+impl From<ChatError> for JudgeError {
+    fn from(e: ChatError) -> Self {
+        match e {
+            ChatError::Request(e) => JudgeError::Request(e),
+            ChatError::Parse(e) => JudgeError::Parse(e),
+        }
+    }
+}
+
+/// One line of the debate transcript
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptLine {
+    pub sender: String,
+    pub content: String,
+    pub timestamp: u64,
+}
+
+/// A debate session with its transcript and every verdict produced for it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DebateSession {
+    pub id: String,
+    pub topic: String,
+    pub started_at: u64,
+    pub transcript: Vec<TranscriptLine>,
+    pub verdicts: Vec<Verdict>,
+}
+
+impl DebateSession {
+    pub fn new(topic: impl Into<String>) -> Self {
+        let started_at = now_millis();
+        Self {
+            id: format!("debate-{}", started_at),
+            topic: topic.into(),
+            started_at,
+            transcript: Vec::new(),
+            verdicts: Vec::new(),
+        }
+    }
+
+    /// Directory where sessions are stored
+    pub fn sessions_dir() -> PathBuf {
+        mofa_widgets::app_storage::data_path(SESSIONS_DIR)
+    }
+
+    pub fn path(&self) -> PathBuf {
+        Self::sessions_dir().join(format!("{}.json", self.id))
+    }
+
+    /// Save the session (transcript and verdicts) to disk
+    pub fn save(&self) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let path = self.path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    /// Transcript formatted for the judge prompt
+    pub fn transcript_text(&self) -> String {
+        self.transcript
+            .iter()
+            .filter(|line| !line.content.trim().is_empty())
+            .map(|line| format!("[{}] {}", line.sender, line.content.trim()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Text bundle used for clipboard export: transcript followed by verdicts
+    pub fn export_text(&self) -> String {
+        let mut out = self
+            .transcript
+            .iter()
+            .map(|line| format!("[{}] {}", line.sender, line.content))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        for (i, verdict) in self.verdicts.iter().enumerate() {
+            out.push_str(&format!("\n\n=== Verdict {} ===\n{}", i + 1, verdict.to_markdown()));
+        }
+        out
+    }
+}
+
+/// Run the judge on a background thread; the result arrives on the receiver
+pub fn spawn_judge(
+    endpoint: ChatEndpoint,
+    session: &DebateSession,
+    preset: JudgePreset,
+) -> Receiver<Result<Verdict, JudgeError>> {
+    let (tx, rx) = mpsc::channel();
+    let transcript = session.transcript_text();
+    let topic = session.topic.clone();
+    std::thread::spawn(move || {
+        let _ = tx.send(request_verdict(&endpoint, &topic, &transcript, preset));
+    });
+    rx
+}
+
+/// Call the provider and parse its verdict
+pub fn request_verdict(
+    endpoint: &ChatEndpoint,
+    topic: &str,
+    transcript: &str,
+    preset: JudgePreset,
+) -> Result<Verdict, JudgeError> {
+    if transcript.trim().is_empty() {
+        return Err(JudgeError::EmptyTranscript);
+    }
+
+    let reply = endpoint.complete(&ChatRequest {
+        system: &preset.system_prompt(),
+        user: &format!("Topic: {}\n\nTranscript:\n{}", topic, transcript),
+        temperature: 0.2,
+        max_tokens: None,
+        timeout: JUDGE_TIMEOUT,
+    })?;
+
+    let mut verdict = parse_verdict(&reply.content)?;
+    verdict.preset = preset;
+    verdict.model = endpoint.model.clone();
+    verdict.created_at = now_millis();
+    Ok(verdict)
+}
+
+/// Parse a verdict from model output
+///
+/// Tolerates Markdown code fences and prose around the JSON object.
+pub fn parse_verdict(raw: &str) -> Result<Verdict, JudgeError> {
+    let start = raw.find('{');
+    let end = raw.rfind('}');
+    let json = match (start, end) {
+        (Some(start), Some(end)) if end > start => &raw[start..=end],
+        _ => return Err(JudgeError::Parse("no JSON object in reply".to_string())),
+    };
+
+    let verdict: Verdict =
+        serde_json::from_str(json).map_err(|e| JudgeError::Parse(e.to_string()))?;
+    if verdict.scores.is_empty() {
+        return Err(JudgeError::Parse("verdict has no scores".to_string()));
+    }
+    Ok(verdict)
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERDICT_JSON: &str = r#"{
+        "winner": "pro",
+        "scores": [
+            {"criterion": "argument", "pro": 8, "con": 6.5},
+            {"criterion": "evidence", "pro": 7, "con": 7}
+        ],
+        "rationale": "PRO rebutted every point."
+    }"#;
+
+    #[test]
+    fn test_parse_plain_json() {
+        let verdict = parse_verdict(VERDICT_JSON).unwrap();
+        assert_eq!(verdict.winner, Winner::Pro);
+        assert_eq!(verdict.scores.len(), 2);
+        assert_eq!(verdict.totals(), (15.0, 13.5));
+        assert_eq!(verdict.preset, JudgePreset::Balanced);
+    }
+
+    #[test]
+    fn test_parse_fenced_json_with_prose() {
+        let raw = format!("Here is my verdict:\n```json\n{}\n```\nThanks!", VERDICT_JSON);
+        let verdict = parse_verdict(&raw).unwrap();
+        assert_eq!(verdict.rationale, "PRO rebutted every point.");
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_winner() {
+        let raw = VERDICT_JSON.replace("\"pro\",", "\"audience\",");
+        assert!(matches!(parse_verdict(&raw), Err(JudgeError::Parse(_))));
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_json() {
+        assert!(matches!(
+            parse_verdict("PRO wins."),
+            Err(JudgeError::Parse(_))
+        ));
+        assert!(matches!(
+            parse_verdict(r#"{"winner": "tie", "scores": [], "rationale": ""}"#),
+            Err(JudgeError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_empty_transcript_is_not_sent() {
+        let endpoint = ChatEndpoint {
+            provider: "local".to_string(),
+            api_url: "http://127.0.0.1:9".to_string(),
+            api_key: "key".to_string(),
+            model: "m".to_string(),
+        };
+        let result = request_verdict(&endpoint, "topic", "  ", JudgePreset::Balanced);
+        assert_eq!(result.unwrap_err(), JudgeError::EmptyTranscript);
+    }
+
+    #[test]
+    fn test_session_keeps_every_verdict_in_export() {
+        let mut session = DebateSession::new("AI in schools");
+        session.transcript.push(TranscriptLine {
+            sender: "Student 1".to_string(),
+            content: "Yes.".to_string(),
+            timestamp: 1,
+        });
+        let mut first = parse_verdict(VERDICT_JSON).unwrap();
+        first.preset = JudgePreset::Balanced;
+        let mut second = first.clone();
+        second.preset = JudgePreset::Evidence;
+        second.winner = Winner::Tie;
+        session.verdicts.push(first);
+        session.verdicts.push(second);
+
+        let export = session.export_text();
+        assert!(export.starts_with("[Student 1] Yes."));
+        assert!(export.contains("=== Verdict 1 ==="));
+        assert!(export.contains("Winner: Tie"));
+
+        let json = serde_json::to_string(&session).unwrap();
+        let restored: DebateSession = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.verdicts.len(), 2);
+        assert_eq!(restored.verdicts[1].preset, JudgePreset::Evidence);
+    }
+}
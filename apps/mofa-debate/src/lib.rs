@@ -2,7 +2,10 @@
 
 pub mod audio_player;
 pub mod dora_integration;
+pub mod judge;
+pub mod openings;
 pub mod screen;
+pub mod topic_pack;
 
 pub use dora_integration::{DoraCommand, DoraEvent, DoraIntegration};
 // Re-export shared modules from mofa-ui
@@ -17,6 +20,14 @@ pub use screen::MoFaDebateScreenWidgetRefExt; // Export WidgetRefExt for timer c
 
 use makepad_widgets::{Cx, live_id, LiveId};
 use mofa_widgets::{AppInfo, MofaApp};
+use mofa_widgets::app_storage::AppStorage;
+
+/// Data this app keeps under `~/.mofa-studio`
+pub const STORAGE: AppStorage = AppStorage {
+    prefs: &[],
+    cache: &[judge::SESSIONS_DIR, topic_pack::PACK_DIR],
+    webview_profile: false,
+};
 
 /// MoFA Debate app descriptor
 pub struct MoFaDebateApp;
@@ -30,6 +41,7 @@ impl MofaApp for MoFaDebateApp {
             tab_id: Some(live_id!(debate_tab)),
             page_id: Some(live_id!(debate_page)),
             show_in_sidebar: true,
+            storage: STORAGE,
             ..Default::default()
         }
     }
@@ -0,0 +1,145 @@
+//! Scripted warm-up rounds
+//!
+//! Plays a topic pack's opening statements through the system TTS before the
+//! live agents start. Each statement goes to the debate's audio player under
+//! its speaker's participant id, so the participant panels light up as for
+//! live speech. The screen polls [`OpeningsJob::state`] and starts the
+//! dataflow once the job is done.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use mofa_widgets::tts::{self, TtsOptions};
+
+use crate::audio_player::AudioPlayer;
+use crate::topic_pack::Opening;
+
+/// Buffered audio below which the current statement counts as finished
+const DRAINED_SECS: f64 = 0.1;
+
+/// How often the worker checks playback and cancellation
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Progress of the warm-up
+#[derive(Clone, Debug, PartialEq)]
+pub enum OpeningsState {
+    /// Synthesizing or playing statement `index`
+    Speaking { index: usize },
+    Done,
+    Failed(String),
+    Cancelled,
+}
+
+/// Opening statements being played on a worker thread
+pub struct OpeningsJob {
+    openings: Vec<Opening>,
+    state: Arc<Mutex<OpeningsState>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl OpeningsJob {
+    pub fn start(openings: Vec<Opening>, player: Arc<AudioPlayer>) -> Self {
+        let state = Arc::new(Mutex::new(OpeningsState::Speaking { index: 0 }));
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let worker_openings = openings.clone();
+        let worker_state = state.clone();
+        let worker_cancel = cancel.clone();
+        std::thread::spawn(move || {
+            let result = play_openings(&worker_openings, &player, &worker_state, &worker_cancel);
+            if worker_cancel.load(Ordering::Relaxed) {
+                player.reset();
+            }
+            *worker_state.lock().unwrap() = result;
+        });
+
+        Self { openings, state, cancel }
+    }
+
+    /// The statements, in speaking order
+    pub fn openings(&self) -> &[Opening] {
+        &self.openings
+    }
+
+    pub fn state(&self) -> OpeningsState {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Stop after the current chunk of audio; the buffer is cleared
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+fn play_openings(
+    openings: &[Opening],
+    player: &AudioPlayer,
+    state: &Mutex<OpeningsState>,
+    cancel: &AtomicBool,
+) -> OpeningsState {
+    let backend = tts::default_backend();
+
+    for (index, opening) in openings.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            return OpeningsState::Cancelled;
+        }
+        *state.lock().unwrap() = OpeningsState::Speaking { index };
+
+        let options = TtsOptions {
+            voice: opening.voice.clone(),
+            ..Default::default()
+        };
+        let audio = match backend.synthesize(&opening.text, &options) {
+            Ok(audio) => audio,
+            Err(e) => return OpeningsState::Failed(format!("Opening {} ({}): {}", index + 1, opening.role.key(), e)),
+        };
+        let samples = resample(&audio.samples, audio.sample_rate, player.sample_rate());
+        player.write_audio(&samples, Some(opening.role.participant_id().to_string()));
+
+        // Wait for the statement to play out before the next speaker
+        std::thread::sleep(POLL_INTERVAL);
+        while player.buffer_seconds() > DRAINED_SECS {
+            if cancel.load(Ordering::Relaxed) {
+                return OpeningsState::Cancelled;
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    OpeningsState::Done
+}
+
+/// Linear resampling; good enough for speech
+fn resample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to || from == 0 || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let ratio = from as f64 / to as f64;
+    let len = (samples.len() as f64 / ratio).round() as usize;
+    (0..len)
+        .map(|i| {
+            let pos = i as f64 * ratio;
+            let idx = pos as usize;
+            let frac = (pos - idx as f64) as f32;
+            let a = samples[idx.min(samples.len() - 1)];
+            let b = samples[(idx + 1).min(samples.len() - 1)];
+            a + (b - a) * frac
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resample() {
+        let samples = [0.0, 1.0, 0.0, -1.0];
+        assert_eq!(resample(&samples, 16000, 16000), samples);
+        assert_eq!(resample(&samples, 16000, 32000), vec![0.0, 0.5, 1.0, 0.5, 0.0, -0.5, -1.0, -1.0]);
+        assert_eq!(resample(&samples, 32000, 16000), vec![0.0, 0.0]);
+        assert_eq!(resample(&[0.5; 22050], 22050, 32000).len(), 32000);
+        assert!(resample(&[], 22050, 32000).is_empty());
+    }
+}
@@ -5,10 +5,29 @@
 use makepad_widgets::*;
 use mofa_settings::data::Preferences;
 use mofa_ui::{LedMeterWidgetExt, LedColors};
+use mofa_widgets::activity::{ActivityKind, ActivityRegistry};
 
 use super::MoFaDebateScreen;
 
 impl MoFaDebateScreen {
+    /// List the audio and dora timers in the shell's running panel
+    pub(super) fn track_timers(&mut self) {
+        if self.timer_activity.is_none() {
+            let activity = ActivityRegistry::global()
+                .register(ActivityKind::Timer, "mofa-debate", "Audio and dataflow polling")
+                .stoppable();
+            activity.set_state("every 50 ms / 100 ms");
+            self.timer_activity = Some(activity);
+        }
+    }
+
+    /// Stop the audio and dora timers
+    pub(super) fn stop_polling(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.audio_timer);
+        cx.stop_timer(self.dora_timer);
+        self.timer_activity = None;
+    }
+
     /// Initialize audio manager and populate device dropdowns
     pub(super) fn init_audio(&mut self, cx: &mut Cx) {
         let mut audio_manager = mofa_ui::AudioManager::new();
@@ -108,6 +127,7 @@ impl MoFaDebateScreen {
 
         // Start dora timer for participant panel updates (needed for audio visualization)
         self.dora_timer = cx.start_interval(0.1);
+        self.track_timers();
 
         // AEC enabled by default (blink animation is shader-driven, no timer needed)
         self.aec_enabled = true;
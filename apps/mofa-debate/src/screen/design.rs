@@ -224,6 +224,76 @@ live_design! {
         }
     }
 
+    // Small secondary button used by the topic pack row
+    PackButton = <Button> {
+        width: Fit, height: 24
+        padding: {left: 10, right: 10}
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+            fn get_color(self) -> vec4 {
+                return mix((GRAY_700), (SLATE_300), self.dark_mode);
+            }
+        }
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            instance dark_mode: 0.0
+            border_radius: 4.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.border_radius);
+                let base = mix((HOVER_BG), (SLATE_600), self.dark_mode);
+                let hover_color = mix((SLATE_200), (SLATE_500), self.dark_mode);
+                let pressed_color = mix((SLATE_300), (SLATE_400), self.dark_mode);
+                sdf.fill(mix(mix(base, hover_color, self.hover), pressed_color, self.pressed));
+                return sdf.result;
+            }
+        }
+    }
+
+    // Secondary text in the topic pack rows
+    PackLabel = <Label> {
+        width: Fit, height: Fit
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_REGULAR>{ font_size: 10.0 }
+            wrap: Word
+            fn get_color(self) -> vec4 {
+                return mix((TEXT_SECONDARY), (TEXT_SECONDARY_DARK), self.dark_mode);
+            }
+        }
+    }
+
+    // Editable stance of one agent
+    StanceInput = <TextInput> {
+        width: Fill, height: Fit
+        padding: {left: 10, right: 10, top: 6, bottom: 6}
+        draw_bg: {
+            instance dark_mode: 0.0
+            border_radius: 4.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.border_radius);
+                sdf.fill(mix((SLATE_200), (SLATE_700), self.dark_mode));
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_REGULAR>{ font_size: 10.0 }
+            fn get_color(self) -> vec4 {
+                return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+            }
+        }
+        draw_selection: {
+            color: (INDIGO_200)
+        }
+        draw_cursor: {
+            color: (ACCENT_BLUE)
+        }
+    }
+
     // MoFA FM Screen - adaptive horizontal layout with left content and right log panel
     pub MoFaDebateScreen = {{MoFaDebateScreen}} {
         width: Fill, height: Fill
@@ -418,6 +488,165 @@ live_design! {
                 }
             }
 
+            // Judge verdict panel - filled after the debate ends
+            judge_container = <View> {
+                width: Fill, height: Fit
+                flow: Down
+
+                judge_section = <RoundedView> {
+                    width: Fill, height: Fit
+                    show_bg: true
+                    draw_bg: {
+                        instance dark_mode: 0.0
+                        border_radius: (PANEL_RADIUS)
+                        border_size: 1.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.border_radius);
+                            let bg = mix((PANEL_BG), (PANEL_BG_DARK), self.dark_mode);
+                            let border = mix((BORDER), (SLATE_600), self.dark_mode);
+                            sdf.fill(bg);
+                            sdf.stroke(border, self.border_size);
+                            return sdf.result;
+                        }
+                    }
+                    flow: Down
+
+                    judge_header = <PanelHeader> {
+                        spacing: 8
+                        judge_title = <Label> {
+                            text: "Judge Verdict"
+                            draw_text: {
+                                instance dark_mode: 0.0
+                                text_style: <FONT_SEMIBOLD>{ font_size: 13.0 }
+                                fn get_color(self) -> vec4 {
+                                    return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                                }
+                            }
+                        }
+                        judge_status = <Label> {
+                            text: ""
+                            draw_text: {
+                                instance dark_mode: 0.0
+                                text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                                fn get_color(self) -> vec4 {
+                                    return mix((TEXT_SECONDARY), (TEXT_SECONDARY_DARK), self.dark_mode);
+                                }
+                            }
+                        }
+                        <Filler> {}
+
+                        // Prompt preset used for the next judge run
+                        judge_preset = <DropDown> {
+                            width: 100, height: 24
+                            popup_menu_position: BelowInput
+                            draw_bg: {
+                                instance dark_mode: 0.0
+                                fn pixel(self) -> vec4 {
+                                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                    sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 2.0);
+                                    sdf.fill(mix((HOVER_BG), (SLATE_700), self.dark_mode));
+                                    let ax = self.rect_size.x - 12.0;
+                                    let ay = self.rect_size.y * 0.5 - 2.0;
+                                    sdf.move_to(ax - 3.0, ay);
+                                    sdf.line_to(ax, ay + 4.0);
+                                    sdf.line_to(ax + 3.0, ay);
+                                    sdf.stroke(mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode), 1.5);
+                                    return sdf.result;
+                                }
+                            }
+                            draw_text: {
+                                instance dark_mode: 0.0
+                                text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+                                fn get_color(self) -> vec4 {
+                                    return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                                }
+                            }
+                            popup_menu: {
+                                draw_bg: {
+                                    color: (WHITE)
+                                    border_color: (BORDER)
+                                    border_size: 1.0
+                                    border_radius: 2.0
+                                }
+                                menu_item: {
+                                    draw_bg: {
+                                        color: (WHITE)
+                                        color_hover: (GRAY_100)
+                                    }
+                                    draw_text: {
+                                        fn get_color(self) -> vec4 {
+                                            return mix(
+                                                mix((GRAY_700), (TEXT_PRIMARY), self.active),
+                                                (TEXT_PRIMARY),
+                                                self.hover
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                            labels: ["Balanced", "Evidence", "Persuasion"]
+                            values: [Balanced, Evidence, Persuasion]
+                        }
+
+                        judge_btn = <Button> {
+                            width: Fit, height: 24
+                            padding: {left: 12, right: 12}
+                            text: "Judge"
+                            draw_text: {
+                                color: (WHITE)
+                                text_style: <FONT_SEMIBOLD>{ font_size: 10.0 }
+                            }
+                            draw_bg: {
+                                instance hover: 0.0
+                                instance pressed: 0.0
+                                border_radius: 4.0
+                                fn pixel(self) -> vec4 {
+                                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                    let color = mix(
+                                        mix((ACCENT_BLUE), (BLUE_600), self.hover),
+                                        (BLUE_700),
+                                        self.pressed
+                                    );
+                                    sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.border_radius);
+                                    sdf.fill(color);
+                                    return sdf.result;
+                                }
+                            }
+                        }
+                    }
+
+                    judge_scroll = <ScrollYView> {
+                        width: Fill, height: 160
+                        flow: Down
+                        scroll_bars: <ScrollBars> {
+                            show_scroll_x: false
+                            show_scroll_y: true
+                        }
+
+                        judge_content_wrapper = <View> {
+                            width: Fill, height: Fit
+                            padding: (PANEL_PADDING)
+                            flow: Down
+
+                            judge_content = <Markdown> {
+                                width: Fill, height: Fit
+                                font_size: 12.0
+                                font_color: (TEXT_PRIMARY)
+                                paragraph_spacing: 6
+
+                                draw_normal: {
+                                    text_style: <FONT_REGULAR>{ font_size: 12.0 }
+                                }
+                                draw_bold: {
+                                    text_style: <FONT_SEMIBOLD>{ font_size: 12.0 }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
             // Audio control panel container - horizontal layout with individual containers
             audio_container = <View> {
                 width: Fill, height: Fit
@@ -769,6 +998,94 @@ live_design! {
                     flow: Down
                     spacing: 8
 
+                    // Topic pack: sets the motion and agent stances, and can
+                    // play scripted openings before the live debate
+                    pack_row = <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 8
+                        align: {y: 0.5}
+
+                        pack_title = <PackLabel> {
+                            text: "No topic pack - import one or drop a .json file here"
+                        }
+                        <Filler> {}
+
+                        // Labels follow topic_pack::SAMPLE_PACKS
+                        pack_samples = <DropDown> {
+                            width: 140, height: 24
+                            popup_menu_position: BelowInput
+                            draw_bg: {
+                                instance dark_mode: 0.0
+                                fn pixel(self) -> vec4 {
+                                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                    sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 2.0);
+                                    sdf.fill(mix((HOVER_BG), (SLATE_700), self.dark_mode));
+                                    let ax = self.rect_size.x - 12.0;
+                                    let ay = self.rect_size.y * 0.5 - 2.0;
+                                    sdf.move_to(ax - 3.0, ay);
+                                    sdf.line_to(ax, ay + 4.0);
+                                    sdf.line_to(ax + 3.0, ay);
+                                    sdf.stroke(mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode), 1.5);
+                                    return sdf.result;
+                                }
+                            }
+                            draw_text: {
+                                instance dark_mode: 0.0
+                                text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+                                fn get_color(self) -> vec4 {
+                                    return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                                }
+                            }
+                            popup_menu: {
+                                draw_bg: {
+                                    color: (WHITE)
+                                    border_color: (BORDER)
+                                    border_size: 1.0
+                                    border_radius: 2.0
+                                }
+                                menu_item: {
+                                    draw_bg: {
+                                        color: (WHITE)
+                                        color_hover: (GRAY_100)
+                                    }
+                                    draw_text: {
+                                        fn get_color(self) -> vec4 {
+                                            return mix(
+                                                mix((GRAY_700), (TEXT_PRIMARY), self.active),
+                                                (TEXT_PRIMARY),
+                                                self.hover
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                            labels: ["Sample packs", "Remote work", "AI homework help"]
+                            values: [None, RemoteWork, AiHomework]
+                        }
+
+                        import_pack_btn = <PackButton> { text: "Import..." }
+                        export_pack_btn = <PackButton> { text: "Export..." }
+                        openings_btn = <PackButton> { text: "Openings: On" }
+                    }
+
+                    // Load result, or validation errors with field paths
+                    pack_status = <PackLabel> {
+                        width: Fill
+                        text: ""
+                    }
+
+                    stance_rows = <View> {
+                        visible: false
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        pro_stance = <StanceInput> { empty_text: "PRO stance (Student1)" }
+                        con_stance = <StanceInput> { empty_text: "CON stance (Student2)" }
+                        moderator_stance = <StanceInput> { empty_text: "Moderator instructions (Tutor)" }
+                    }
+
                     prompt_row = <View> {
                         width: Fill, height: Fit
                         flow: Right
@@ -17,6 +17,7 @@ use crate::dora_integration::{DoraEvent, DoraIntegration};
 use mofa_settings::data::Preferences;
 use mofa_ui::{ConnectionStatus, MofaHeroWidgetExt};
 
+use super::pack_panel::PendingLaunch;
 use super::{ChatMessageEntry, MoFaDebateScreen};
 
 impl MoFaDebateScreen {
@@ -36,6 +37,7 @@ impl MoFaDebateScreen {
 
         // Start timer to poll for dora events (100ms interval)
         self.dora_timer = cx.start_interval(0.1);
+        self.track_timers();
 
         // Look for default dataflow relative to current working directory
         // Check multiple possible locations
@@ -120,11 +122,11 @@ impl MoFaDebateScreen {
 
         // Update chat display if new messages
         if let Some(messages) = chat_messages {
-            // Keep user messages (sender == "You") from local state
+            // Keep user messages (sender == "You") and scripted openings from local state
             let user_messages: Vec<ChatMessageEntry> = self
                 .chat_messages
                 .iter()
-                .filter(|m| m.sender == "You")
+                .filter(|m| m.sender == "You" || m.scripted)
                 .cloned()
                 .collect();
 
@@ -137,6 +139,7 @@ impl MoFaDebateScreen {
                     timestamp: m.timestamp,
                     is_streaming: m.is_streaming,
                     session_id: m.session_id,
+                    scripted: false,
                 })
                 .collect();
 
@@ -255,6 +258,8 @@ impl MoFaDebateScreen {
                     self.connected_bridges.clear();
                     self.processed_dora_log_count = 0;
                     self.pending_prompts.clear();
+                    // Debate is over - ask the judge for a verdict
+                    self.judge_on_debate_end(cx);
                 }
                 DoraEvent::Error { message } => {
                     ::log::error!("Dora error: {}", message);
@@ -464,6 +469,9 @@ impl MoFaDebateScreen {
             .mofa_hero(ids!(left_column.mofa_hero))
             .set_connection_status(cx, ConnectionStatus::Connecting);
 
+        // A loaded pack follows edits to the motion and stances
+        let pack = self.synced_topic_pack(cx);
+
         // Require a topic from the input box; if empty, fill a placeholder and proceed
         let mut kickoff_topic = self.current_prompt_text().trim().to_string();
         if kickoff_topic.is_empty() {
@@ -480,20 +488,53 @@ impl MoFaDebateScreen {
                 .set_text(cx, &kickoff_topic);
         }
 
-        let mut kickoff_sent = false;
-        let kickoff_prompt = format!(
-            "Start a formal debate. Roles: Student1 = PRO, Student2 = CON, Tutor = Judge/Moderator. Debate topic: {}. Keep turns concise and alternate speakers. Tutor manages order and summarizes at the end.",
-            kickoff_topic
-        );
+        self.begin_debate_session(cx, &kickoff_topic);
+
+        // A topic pack runs a copy of the dataflow with its prompts
+        let mut launch = PendingLaunch {
+            dataflow: dataflow_path.clone(),
+            env_vars,
+            kickoff_prompt: format!(
+                "Start a formal debate. Roles: Student1 = PRO, Student2 = CON, Tutor = Judge/Moderator. Debate topic: {}. Keep turns concise and alternate speakers. Tutor manages order and summarizes at the end.",
+                kickoff_topic
+            ),
+        };
+        self.dataflow_path = Some(dataflow_path.clone());
+        let Some(pack) = pack else {
+            self.launch_dataflow(cx, launch);
+            return;
+        };
 
+        match pack.write_dataflow(&dataflow_path) {
+            Ok(path) => launch.dataflow = path,
+            Err(e) => {
+                self.add_log(cx, &format!("[ERROR] [App] Topic pack: {}", e));
+                let hero = self.view.mofa_hero(ids!(left_column.mofa_hero));
+                hero.set_running(cx, false);
+                hero.set_connection_status(cx, ConnectionStatus::Failed);
+                return;
+            }
+        }
+        let play_openings = self.plays_openings(&pack);
+        launch.kickoff_prompt = pack.kickoff_prompt(play_openings);
+        if play_openings {
+            self.start_openings(cx, &pack, launch);
+        } else {
+            self.launch_dataflow(cx, launch);
+        }
+    }
+
+    /// Start the dataflow and send the kickoff prompt
+    pub(super) fn launch_dataflow(&mut self, cx: &mut Cx, launch: PendingLaunch) {
+        let mut kickoff_sent = false;
         if let Some(ref dora) = self.dora_integration {
-            if !dora.start_dataflow_with_env(&dataflow_path, env_vars) {
+            if !dora.start_dataflow_with_env(&launch.dataflow, launch.env_vars) {
                 self.add_log(cx, "[ERROR] [App] Failed to send start command");
                 self.view
                     .mofa_hero(ids!(left_column.mofa_hero))
                     .set_connection_status(cx, ConnectionStatus::Failed);
             } else {
-                dora.send_prompt(kickoff_prompt.clone());
+                dora.send_prompt(launch.kickoff_prompt.clone());
                 kickoff_sent = true;
             }
         }
@@ -501,17 +542,20 @@ impl MoFaDebateScreen {
         if kickoff_sent {
             self.add_log(
                 cx,
-                &format!("[INFO] [App] Kickoff prompt: {}", kickoff_topic),
+                &format!("[INFO] [App] Kickoff prompt: {}", launch.kickoff_prompt),
             );
         }
-
-        self.dataflow_path = Some(dataflow_path);
     }
 
     /// Handle MoFA stop button click
     pub(super) fn handle_mofa_stop(&mut self, cx: &mut Cx) {
         ::log::info!("MoFA Stop clicked");
 
+        // Stopped during the openings: there is no dataflow yet
+        if self.cancel_openings(cx) {
+            return;
+        }
+
         self.add_log(cx, "[INFO] [App] Force stopping MoFA dataflow...");
 
         // Show "Stopping" state while stop is in progress
@@ -568,7 +612,7 @@ impl MoFaDebateScreen {
     }
 
     /// Read the current prompt input text (used as debate topic)
-    fn current_prompt_text(&self) -> String {
+    pub(super) fn current_prompt_text(&self) -> String {
         self.view
             .text_input(ids!(
                 left_column
@@ -0,0 +1,181 @@
+//! Judge panel methods for MoFaDebateScreen
+//!
+//! Runs the judge after a debate ends, keeps every verdict on the session
+//! and renders them in the results panel.
+
+use makepad_widgets::*;
+use std::sync::mpsc::TryRecvError;
+
+use crate::judge::{spawn_judge, DebateSession, JudgeError, JudgePreset, TranscriptLine};
+use mofa_settings::data::{ChatEndpoint, Preferences};
+
+use super::MoFaDebateScreen;
+
+impl MoFaDebateScreen {
+    /// Start a new session for the given topic (previous verdicts stay on disk)
+    pub(super) fn begin_debate_session(&mut self, cx: &mut Cx, topic: &str) {
+        self.debate_session = Some(DebateSession::new(topic));
+        self.judge_rx = None;
+        self.set_judge_status(cx, "");
+        self.set_judge_button_text(cx, "Judge");
+        self.update_judge_display(cx);
+    }
+
+    /// Copy the chat timeline into the session transcript
+    pub(super) fn sync_session_transcript(&mut self) {
+        if let Some(ref mut session) = self.debate_session {
+            session.transcript = self
+                .chat_messages
+                .iter()
+                .filter(|m| !m.is_streaming)
+                .map(|m| TranscriptLine {
+                    sender: m.sender.clone(),
+                    content: m.content.clone(),
+                    timestamp: m.timestamp,
+                })
+                .collect();
+        }
+    }
+
+    /// Called when the dataflow stops, whether the debate ran its course or
+    /// was stopped early: judge once if the transcript has debate turns and
+    /// nothing was judged yet
+    pub(super) fn judge_on_debate_end(&mut self, cx: &mut Cx) {
+        self.sync_session_transcript();
+        let should_judge = self.debate_session.as_ref().is_some_and(|s| {
+            s.verdicts.is_empty() && s.transcript.iter().any(|line| line.sender != "You")
+        });
+        if should_judge {
+            self.start_judging(cx);
+        }
+    }
+
+    /// Send the transcript to the provider with the selected preset
+    pub(super) fn start_judging(&mut self, cx: &mut Cx) {
+        if self.judge_rx.is_some() {
+            return;
+        }
+        if self.debate_session.is_none() {
+            let topic = self.current_prompt_text();
+            self.debate_session = Some(DebateSession::new(topic));
+        }
+        self.sync_session_transcript();
+
+        let endpoint = match ChatEndpoint::from_preferences(&Preferences::load()) {
+            Some(endpoint) => endpoint,
+            None => {
+                let e = JudgeError::NoProvider;
+                self.add_log(cx, &format!("[WARN] [Judge] {}", e));
+                self.set_judge_status(cx, &e.to_string());
+                self.set_judge_button_text(cx, "Retry");
+                return;
+            }
+        };
+
+        let preset = JudgePreset::from_index(self.judge_preset);
+        let Some(ref session) = self.debate_session else {
+            return;
+        };
+        let message = format!(
+            "[INFO] [Judge] Judging {} lines with {} preset ({})",
+            session.transcript.len(),
+            preset.label(),
+            endpoint.model
+        );
+        self.judge_rx = Some(spawn_judge(endpoint, session, preset));
+        self.add_log(cx, &message);
+        self.set_judge_status(cx, "Judging...");
+        self.set_judge_button_text(cx, "Judging...");
+    }
+
+    /// Poll for a finished judge run
+    pub(super) fn poll_judge(&mut self, cx: &mut Cx) {
+        let result = match self.judge_rx.as_ref().map(|rx| rx.try_recv()) {
+            Some(Ok(result)) => result,
+            Some(Err(TryRecvError::Empty)) | None => return,
+            Some(Err(TryRecvError::Disconnected)) => {
+                Err(JudgeError::Request("judge thread exited".to_string()))
+            }
+        };
+        self.judge_rx = None;
+
+        match result {
+            Ok(verdict) => {
+                let winner = verdict.winner.label();
+                if let Some(ref mut session) = self.debate_session {
+                    session.verdicts.push(verdict);
+                    match session.save() {
+                        Ok(path) => ::log::info!("Saved debate session to {:?}", path),
+                        Err(e) => ::log::warn!("Failed to save debate session: {}", e),
+                    }
+                }
+                self.add_log(cx, &format!("[INFO] [Judge] Verdict: {}", winner));
+                self.set_judge_status(cx, "");
+                self.set_judge_button_text(cx, "Re-judge");
+                self.update_judge_display(cx);
+            }
+            Err(e) => {
+                // Transcript stays on the session; Retry re-sends it
+                self.add_log(cx, &format!("[ERROR] [Judge] {}", e));
+                self.set_judge_status(cx, &e.to_string());
+                self.set_judge_button_text(cx, "Retry");
+            }
+        }
+    }
+
+    /// Render all verdicts for the current session, newest first
+    pub(super) fn update_judge_display(&mut self, cx: &mut Cx) {
+        let text = match self.debate_session {
+            Some(ref session) if !session.verdicts.is_empty() => session
+                .verdicts
+                .iter()
+                .rev()
+                .map(|v| {
+                    format!(
+                        "*{}*  \n{}",
+                        Self::format_timestamp(v.created_at),
+                        v.to_markdown()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n---\n\n"),
+            _ => "The verdict appears here when the debate ends.".to_string(),
+        };
+
+        self.view
+            .markdown(ids!(
+                left_column
+                    .judge_container
+                    .judge_section
+                    .judge_scroll
+                    .judge_content_wrapper
+                    .judge_content
+            ))
+            .set_text(cx, &text);
+        self.view.redraw(cx);
+    }
+
+    fn set_judge_status(&mut self, cx: &mut Cx, text: &str) {
+        self.view
+            .label(ids!(
+                left_column
+                    .judge_container
+                    .judge_section
+                    .judge_header
+                    .judge_status
+            ))
+            .set_text(cx, text);
+    }
+
+    fn set_judge_button_text(&mut self, cx: &mut Cx, text: &str) {
+        self.view
+            .button(ids!(
+                left_column
+                    .judge_container
+                    .judge_section
+                    .judge_header
+                    .judge_btn
+            ))
+            .set_text(cx, text);
+    }
+}
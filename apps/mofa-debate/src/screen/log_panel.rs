@@ -206,6 +206,15 @@ impl MoFaDebateScreen {
 
     /// Copy chat messages to clipboard
     pub(super) fn copy_chat_to_clipboard(&mut self, cx: &mut Cx) {
+        // Sessions with verdicts export the full bundle (transcript + verdicts)
+        self.sync_session_transcript();
+        if let Some(ref session) = self.debate_session {
+            if !session.verdicts.is_empty() {
+                cx.copy_to_clipboard(&session.export_text());
+                return;
+            }
+        }
+
         let chat_text = if self.chat_messages.is_empty() {
             "No chat messages".to_string()
         } else {
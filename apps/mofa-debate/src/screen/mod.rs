@@ -4,16 +4,24 @@
 //! - `design.rs` - UI layout and styling (live_design! DSL)
 //! - `audio_controls.rs` - Audio device selection, mic monitoring
 //! - `chat_panel.rs` - Chat display, prompt input
+//! - `judge_panel.rs` - Judge verdicts for finished debates
 //! - `log_panel.rs` - Log display, filtering
+//! - `pack_panel.rs` - Topic pack import/export, opening statements
 //! - `dora_handlers.rs` - Dora event handling, dataflow control
 
 mod audio_controls;
 mod chat_panel;
 pub mod design; // Public for Makepad live_design path resolution
 mod dora_handlers;
+mod judge_panel;
 mod log_panel;
+mod pack_panel;
 
 use crate::dora_integration::{DoraCommand, DoraIntegration};
+use crate::judge::{DebateSession, JudgeError, Verdict};
+use crate::openings::OpeningsJob;
+use crate::topic_pack::TopicPack;
+use pack_panel::PendingLaunch;
 use makepad_widgets::*;
 use mofa_ui::{
     MofaHeroAction, MofaHeroWidgetExt,
@@ -24,8 +32,10 @@ use mofa_ui::{
     log_bridge,
 };
 use mofa_widgets::participant_panel::ParticipantPanelWidgetExt;
+use mofa_widgets::activity::ActivityHandle;
 use mofa_widgets::{StateChangeListener, TimerControl};
 use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
 
 /// Register live design for this module
 pub fn live_design(cx: &mut Cx) {
@@ -40,6 +50,8 @@ pub struct ChatMessageEntry {
     pub timestamp: u64,
     pub is_streaming: bool,
     pub session_id: Option<String>,
+    /// Played from a topic pack rather than sent by the dataflow
+    pub scripted: bool,
 }
 
 impl ChatMessageEntry {
@@ -53,6 +65,7 @@ impl ChatMessageEntry {
                 .unwrap_or(0),
             is_streaming: false,
             session_id: None,
+            scripted: false,
         }
     }
 }
@@ -108,6 +121,9 @@ pub struct MoFaDebateScreen {
     dataflow_path: Option<PathBuf>,
     #[rust]
     dora_timer: Timer,
+    /// Lists the audio and dora timers in the shell's running panel
+    #[rust]
+    timer_activity: Option<ActivityHandle>,
     // NextFrame-based animation for copy buttons (smooth fade instead of timer reset)
     #[rust]
     copy_chat_flash_active: bool,
@@ -136,6 +152,26 @@ pub struct MoFaDebateScreen {
     processed_dora_log_count: usize,
     #[rust]
     pending_prompts: Vec<String>,
+
+    // Judge state
+    #[rust]
+    debate_session: Option<DebateSession>,
+    #[rust]
+    judge_rx: Option<Receiver<Result<Verdict, JudgeError>>>,
+    #[rust]
+    judge_preset: usize,
+
+    // Topic pack state
+    #[rust]
+    topic_pack: Option<TopicPack>,
+    #[rust]
+    skip_openings: bool,
+    #[rust]
+    openings_job: Option<OpeningsJob>,
+    #[rust]
+    openings_shown: usize,
+    #[rust]
+    pending_launch: Option<PendingLaunch>,
 }
 
 impl Widget for MoFaDebateScreen {
@@ -147,9 +183,15 @@ impl Widget for MoFaDebateScreen {
             // Initialize log bridge to capture Rust logs
             log_bridge::init();
             self.init_audio(cx);
+            self.update_judge_display(cx);
             self.audio_initialized = true;
         }
 
+        // Stop requests from the shell's running panel
+        if self.timer_activity.as_ref().is_some_and(|a| a.stop_requested()) {
+            self.stop_polling(cx);
+        }
+
         // Handle audio timer for mic level updates, log polling, and buffer status
         if self.audio_timer.is_event(event).is_some() {
             self.update_mic_level(cx);
@@ -168,8 +210,13 @@ impl Widget for MoFaDebateScreen {
         // Handle dora timer for polling dora events
         if self.dora_timer.is_event(event).is_some() {
             self.poll_dora_events(cx);
+            self.poll_judge(cx);
+            self.poll_openings(cx);
         }
 
+        // Topic pack files dropped onto the screen
+        self.handle_pack_drop(cx, event);
+
         // Handle NextFrame for smooth copy button fade animation
         if let Event::NextFrame(nf) = event {
             let mut needs_redraw = false;
@@ -493,6 +540,36 @@ impl Widget for MoFaDebateScreen {
             self.send_prompt(cx);
         }
 
+        // Handle judge preset selection
+        if let Some(selected) = self
+            .view
+            .drop_down(ids!(
+                left_column
+                    .judge_container
+                    .judge_section
+                    .judge_header
+                    .judge_preset
+            ))
+            .selected(actions)
+        {
+            self.judge_preset = selected;
+        }
+
+        // Handle Judge / Re-judge / Retry button click
+        if self
+            .view
+            .button(ids!(
+                left_column
+                    .judge_container
+                    .judge_section
+                    .judge_header
+                    .judge_btn
+            ))
+            .clicked(actions)
+        {
+            self.start_judging(cx);
+        }
+
         // Handle Reset button click
         if self
             .view
@@ -508,6 +585,60 @@ impl Widget for MoFaDebateScreen {
         {
             self.reset_conversation(cx);
         }
+
+        // Handle topic pack controls
+        if let Some(selected) = self
+            .view
+            .drop_down(ids!(
+                left_column
+                    .prompt_container
+                    .prompt_section
+                    .pack_row
+                    .pack_samples
+            ))
+            .selected(actions)
+        {
+            self.load_sample_pack(cx, selected);
+        }
+        if self
+            .view
+            .button(ids!(
+                left_column
+                    .prompt_container
+                    .prompt_section
+                    .pack_row
+                    .import_pack_btn
+            ))
+            .clicked(actions)
+        {
+            self.import_topic_pack(cx);
+        }
+        if self
+            .view
+            .button(ids!(
+                left_column
+                    .prompt_container
+                    .prompt_section
+                    .pack_row
+                    .export_pack_btn
+            ))
+            .clicked(actions)
+        {
+            self.export_topic_pack(cx);
+        }
+        if self
+            .view
+            .button(ids!(
+                left_column
+                    .prompt_container
+                    .prompt_section
+                    .pack_row
+                    .openings_btn
+            ))
+            .clicked(actions)
+        {
+            self.toggle_openings(cx);
+        }
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
@@ -577,9 +708,8 @@ impl TimerControl for MoFaDebateScreenRef {
     /// to prevent timer callbacks on inactive state
     /// Note: AEC blink animation is shader-driven and doesn't need stopping
     fn stop_timers(&self, cx: &mut Cx) {
-        if let Some(inner) = self.borrow_mut() {
-            cx.stop_timer(inner.audio_timer);
-            cx.stop_timer(inner.dora_timer);
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.stop_polling(cx);
             ::log::debug!("MoFaDebateScreen timers stopped");
         }
     }
@@ -590,6 +720,7 @@ impl TimerControl for MoFaDebateScreenRef {
         if let Some(mut inner) = self.borrow_mut() {
             inner.audio_timer = cx.start_interval(0.05); // 50ms for mic level
             inner.dora_timer = cx.start_interval(0.1); // 100ms for dora events
+            inner.track_timers();
             ::log::debug!("MoFaDebateScreen timers started");
         }
     }
@@ -695,6 +826,94 @@ impl StateChangeListener for MoFaDebateScreenRef {
                 );
             }
 
+            // Apply dark mode to judge panel
+            inner
+                .view
+                .view(ids!(left_column.judge_container.judge_section))
+                .apply_over(
+                    cx,
+                    live! {
+                        draw_bg: { dark_mode: (dark_mode) }
+                    },
+                );
+            inner
+                .view
+                .view(ids!(left_column.judge_container.judge_section.judge_header))
+                .apply_over(
+                    cx,
+                    live! {
+                        draw_bg: { dark_mode: (dark_mode) }
+                    },
+                );
+            inner
+                .view
+                .label(ids!(
+                    left_column
+                        .judge_container
+                        .judge_section
+                        .judge_header
+                        .judge_title
+                ))
+                .apply_over(
+                    cx,
+                    live! {
+                        draw_text: { dark_mode: (dark_mode) }
+                    },
+                );
+            inner
+                .view
+                .label(ids!(
+                    left_column
+                        .judge_container
+                        .judge_section
+                        .judge_header
+                        .judge_status
+                ))
+                .apply_over(
+                    cx,
+                    live! {
+                        draw_text: { dark_mode: (dark_mode) }
+                    },
+                );
+            inner
+                .view
+                .drop_down(ids!(
+                    left_column
+                        .judge_container
+                        .judge_section
+                        .judge_header
+                        .judge_preset
+                ))
+                .apply_over(
+                    cx,
+                    live! {
+                        draw_bg: { dark_mode: (dark_mode) }
+                        draw_text: { dark_mode: (dark_mode) }
+                    },
+                );
+            let judge_markdown = inner.view.markdown(ids!(
+                left_column
+                    .judge_container
+                    .judge_section
+                    .judge_scroll
+                    .judge_content_wrapper
+                    .judge_content
+            ));
+            let judge_color = if dark_mode > 0.5 {
+                vec4(0.945, 0.961, 0.976, 1.0) // TEXT_PRIMARY_DARK (#f1f5f9)
+            } else {
+                vec4(0.122, 0.161, 0.216, 1.0) // TEXT_PRIMARY (#1f2937)
+            };
+            judge_markdown.apply_over(
+                cx,
+                live! {
+                    font_color: (judge_color)
+                    draw_normal: { color: (judge_color) }
+                    draw_bold: { color: (judge_color) }
+                    draw_italic: { color: (judge_color) }
+                },
+            );
+
             // Apply dark mode to audio control containers
             inner
                 .view
@@ -849,6 +1068,42 @@ impl StateChangeListener for MoFaDebateScreenRef {
                     },
                 );
 
+            // Apply dark mode to topic pack controls
+            for label in [
+                ids!(left_column.prompt_container.prompt_section.pack_row.pack_title),
+                ids!(left_column.prompt_container.prompt_section.pack_status),
+            ] {
+                inner.view.label(label).apply_over(
+                    cx,
+                    live! {
+                        draw_text: { dark_mode: (dark_mode) }
+                    },
+                );
+            }
+            for button in [
+                ids!(left_column.prompt_container.prompt_section.pack_row.import_pack_btn),
+                ids!(left_column.prompt_container.prompt_section.pack_row.export_pack_btn),
+                ids!(left_column.prompt_container.prompt_section.pack_row.openings_btn),
+            ] {
+                inner.view.button(button).apply_over(
+                    cx,
+                    live! {
+                        draw_bg: { dark_mode: (dark_mode) }
+                        draw_text: { dark_mode: (dark_mode) }
+                    },
+                );
+            }
+            inner
+                .view
+                .drop_down(ids!(left_column.prompt_container.prompt_section.pack_row.pack_samples))
+                .apply_over(
+                    cx,
+                    live! {
+                        draw_bg: { dark_mode: (dark_mode) }
+                        draw_text: { dark_mode: (dark_mode) }
+                    },
+                );
+
             // Apply dark mode to splitter
             inner.view.view(ids!(splitter)).apply_over(
                 cx,
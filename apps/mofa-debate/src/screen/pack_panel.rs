@@ -0,0 +1,274 @@
+//! Topic pack methods for MoFaDebateScreen
+//!
+//! Imports packs from a file, a drop onto the screen or the bundled
+//! samples, keeps the motion and stance fields in sync with the loaded pack,
+//! exports it again and plays its opening statements before the debate.
+
+use makepad_widgets::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::openings::{OpeningsJob, OpeningsState};
+use crate::topic_pack::{PackRole, TopicPack, PACK_EXTENSION, SAMPLE_PACKS};
+use mofa_ui::{ConnectionStatus, MofaHeroWidgetExt};
+
+use super::{ChatMessageEntry, MoFaDebateScreen};
+
+/// Dataflow start deferred until the opening statements have played
+pub(super) struct PendingLaunch {
+    pub dataflow: PathBuf,
+    pub env_vars: HashMap<String, String>,
+    pub kickoff_prompt: String,
+}
+
+impl MoFaDebateScreen {
+    /// Pick a pack file and load it
+    pub(super) fn import_topic_pack(&mut self, cx: &mut Cx) {
+        let file_dialog = rfd::FileDialog::new()
+            .add_filter("Topic pack", &[PACK_EXTENSION])
+            .set_title("Import topic pack");
+
+        if let Some(path) = file_dialog.pick_file() {
+            self.import_topic_pack_file(cx, &path);
+        }
+    }
+
+    pub(super) fn import_topic_pack_file(&mut self, cx: &mut Cx, path: &Path) {
+        match TopicPack::from_file(path) {
+            Ok(pack) => {
+                let source = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                self.load_topic_pack(cx, pack, &source);
+            }
+            Err(e) => {
+                self.add_log(cx, &format!("[WARN] [App] Topic pack {:?}: {}", path, e));
+                self.set_pack_status(cx, &e.to_string());
+            }
+        }
+    }
+
+    /// Load bundled sample `index` (0 is the dropdown's placeholder)
+    pub(super) fn load_sample_pack(&mut self, cx: &mut Cx, index: usize) {
+        let Some((name, json)) = index.checked_sub(1).and_then(|i| SAMPLE_PACKS.get(i)) else {
+            return;
+        };
+        match TopicPack::from_json(json) {
+            Ok(pack) => self.load_topic_pack(cx, pack, name),
+            Err(e) => self.set_pack_status(cx, &e.to_string()),
+        }
+    }
+
+    /// Fill the motion and stance fields from `pack`
+    fn load_topic_pack(&mut self, cx: &mut Cx, pack: TopicPack, source: &str) {
+        self.view
+            .text_input(ids!(left_column.prompt_container.prompt_section.prompt_row.prompt_input))
+            .set_text(cx, &pack.motion);
+        for role in PackRole::ALL {
+            self.stance_input(role).set_text(cx, pack.stances.get(role));
+        }
+        self.view
+            .view(ids!(left_column.prompt_container.prompt_section.stance_rows))
+            .set_visible(cx, true);
+        self.view
+            .label(ids!(left_column.prompt_container.prompt_section.pack_row.pack_title))
+            .set_text(cx, pack.display_title());
+
+        let message = format!("Loaded {} ({} opening statements)", source, pack.openings.len());
+        self.add_log(cx, &format!("[INFO] [App] Topic pack: {}", message));
+        self.set_pack_status(cx, &message);
+        self.topic_pack = Some(pack);
+        self.view.redraw(cx);
+    }
+
+    /// The loaded pack with the motion and stances as currently edited
+    pub(super) fn synced_topic_pack(&mut self, cx: &mut Cx) -> Option<TopicPack> {
+        let motion = self.current_prompt_text().trim().to_string();
+        let stances: Vec<(PackRole, String)> = PackRole::ALL
+            .iter()
+            .map(|&role| (role, self.stance_input(role).text().trim().to_string()))
+            .collect();
+
+        let pack = self.topic_pack.as_mut()?;
+        if motion.is_empty() {
+            self.view
+                .text_input(ids!(left_column.prompt_container.prompt_section.prompt_row.prompt_input))
+                .set_text(cx, &pack.motion);
+        } else {
+            pack.motion = motion;
+        }
+        for (role, stance) in stances {
+            pack.stances.set(role, stance);
+        }
+        Some(pack.clone())
+    }
+
+    /// Save the loaded pack, edits included
+    pub(super) fn export_topic_pack(&mut self, cx: &mut Cx) {
+        let Some(pack) = self.synced_topic_pack(cx) else {
+            self.set_pack_status(cx, "No topic pack loaded");
+            return;
+        };
+        let file_name = format!("{}.{}", file_stem(pack.display_title()), PACK_EXTENSION);
+        let file_dialog = rfd::FileDialog::new()
+            .add_filter("Topic pack", &[PACK_EXTENSION])
+            .set_file_name(&file_name)
+            .set_title("Export topic pack");
+
+        if let Some(path) = file_dialog.save_file() {
+            match pack.save(&path) {
+                Ok(()) => self.set_pack_status(cx, &format!("Exported to {}", path.display())),
+                Err(e) => self.set_pack_status(cx, &e.to_string()),
+            }
+        }
+    }
+
+    pub(super) fn toggle_openings(&mut self, cx: &mut Cx) {
+        self.skip_openings = !self.skip_openings;
+        let text = if self.skip_openings { "Openings: Off" } else { "Openings: On" };
+        self.view
+            .button(ids!(left_column.prompt_container.prompt_section.pack_row.openings_btn))
+            .set_text(cx, text);
+    }
+
+    /// Whether a start should play the pack's openings first
+    pub(super) fn plays_openings(&self, pack: &TopicPack) -> bool {
+        !self.skip_openings && !pack.openings.is_empty() && self.audio_player.is_some()
+    }
+
+    /// Play the openings, then start the dataflow
+    pub(super) fn start_openings(&mut self, cx: &mut Cx, pack: &TopicPack, launch: PendingLaunch) {
+        let Some(player) = self.audio_player.clone() else {
+            self.launch_dataflow(cx, launch);
+            return;
+        };
+        self.add_log(cx, &format!("[INFO] [App] Playing {} opening statements", pack.openings.len()));
+        self.openings_job = Some(OpeningsJob::start(pack.openings.clone(), player));
+        self.openings_shown = 0;
+        self.pending_launch = Some(launch);
+    }
+
+    /// Called on the dora timer: show each opening as it starts and launch
+    /// the dataflow after the last
+    pub(super) fn poll_openings(&mut self, cx: &mut Cx) {
+        let Some(ref job) = self.openings_job else {
+            return;
+        };
+        let state = job.state();
+        let spoken = match state {
+            OpeningsState::Speaking { index } => index + 1,
+            OpeningsState::Done => job.openings().len(),
+            OpeningsState::Failed(_) | OpeningsState::Cancelled => self.openings_shown,
+        };
+        let new: Vec<ChatMessageEntry> = job
+            .openings()
+            .iter()
+            .take(spoken)
+            .skip(self.openings_shown)
+            .map(|opening| ChatMessageEntry {
+                scripted: true,
+                ..ChatMessageEntry::new(opening.role.label(), opening.text.trim())
+            })
+            .collect();
+        if !new.is_empty() {
+            self.openings_shown += new.len();
+            self.chat_messages.extend(new);
+            self.update_chat_display(cx);
+        }
+
+        match state {
+            OpeningsState::Speaking { .. } => {}
+            OpeningsState::Done => {
+                self.openings_job = None;
+                if let Some(launch) = self.pending_launch.take() {
+                    self.launch_dataflow(cx, launch);
+                }
+            }
+            OpeningsState::Failed(e) => {
+                // The debate itself does not need the openings
+                self.openings_job = None;
+                self.add_log(cx, &format!("[WARN] [App] Skipping opening statements: {}", e));
+                if let Some(launch) = self.pending_launch.take() {
+                    self.launch_dataflow(cx, launch);
+                }
+            }
+            OpeningsState::Cancelled => {
+                self.openings_job = None;
+                self.pending_launch = None;
+            }
+        }
+    }
+
+    /// Stop the openings before the dataflow was started
+    pub(super) fn cancel_openings(&mut self, cx: &mut Cx) -> bool {
+        let Some(job) = self.openings_job.take() else {
+            return false;
+        };
+        job.cancel();
+        self.pending_launch = None;
+        self.add_log(cx, "[INFO] [App] Opening statements cancelled");
+        let hero = self.view.mofa_hero(ids!(left_column.mofa_hero));
+        hero.set_running(cx, false);
+        hero.set_connection_status(cx, ConnectionStatus::Stopped);
+        true
+    }
+
+    /// Accept a pack file dragged over the screen
+    pub(super) fn handle_pack_drop(&mut self, cx: &mut Cx, event: &Event) {
+        match event {
+            Event::Drag(e) => {
+                if self.view.area().rect(cx).contains(e.abs) && dropped_pack(&e.items).is_some() {
+                    e.response.set(DragResponse::Copy);
+                }
+            }
+            Event::Drop(e) => {
+                if !self.view.area().rect(cx).contains(e.abs) {
+                    return;
+                }
+                if let Some(path) = dropped_pack(&e.items) {
+                    self.import_topic_pack_file(cx, &path);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn stance_input(&self, role: PackRole) -> TextInputRef {
+        let path = match role {
+            PackRole::Pro => ids!(left_column.prompt_container.prompt_section.stance_rows.pro_stance),
+            PackRole::Con => ids!(left_column.prompt_container.prompt_section.stance_rows.con_stance),
+            PackRole::Moderator => ids!(left_column.prompt_container.prompt_section.stance_rows.moderator_stance),
+        };
+        self.view.text_input(path)
+    }
+
+    fn set_pack_status(&mut self, cx: &mut Cx, text: &str) {
+        self.view
+            .label(ids!(left_column.prompt_container.prompt_section.pack_status))
+            .set_text(cx, text);
+    }
+}
+
+/// First dropped file that looks like a pack
+fn dropped_pack(items: &[DragItem]) -> Option<PathBuf> {
+    items.iter().find_map(|item| match item {
+        DragItem::FilePath { path, .. } => {
+            let path = PathBuf::from(path);
+            let is_pack = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case(PACK_EXTENSION));
+            is_pack.then_some(path)
+        }
+        _ => None,
+    })
+}
+
+/// File name for an exported pack
+fn file_stem(title: &str) -> String {
+    let stem: String = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+        .take(60)
+        .collect();
+    if stem.trim_matches('_').is_empty() {
+        "topic-pack".to_string()
+    } else {
+        stem
+    }
+}
@@ -0,0 +1,557 @@
+//! Debate topic packs
+//!
+//! A topic pack is a JSON file holding a motion, a background brief, the
+//! stance each agent argues and optional scripted opening statements:
+//!
+//! ```json
+//! {
+//!   "title": "Remote work",
+//!   "motion": "Remote work should be the default for office jobs",
+//!   "background": "Since 2020 many companies ...",
+//!   "stances": { "pro": "Argue that ...", "con": "Argue that ...", "moderator": "..." },
+//!   "openings": [ { "role": "pro", "text": "Good evening ..." } ]
+//! }
+//! ```
+//!
+//! Loading a pack writes copies of the agent configs with the pack folded
+//! into their system prompts, plus a dataflow pointing at those copies, to
+//! [`PACK_DIR`] in the app's data directory; the app folder may be read-only.
+//! Node paths and build commands in the copy are made absolute, so it runs
+//! from there. The stock files are never modified.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+/// File extension of topic packs
+pub const PACK_EXTENSION: &str = "json";
+
+/// Packs shipped with the app, as (name, JSON)
+pub const SAMPLE_PACKS: [(&str, &str); 2] = [
+    ("Remote work", include_str!("../resources/topic-packs/remote-work.json")),
+    ("AI homework help", include_str!("../resources/topic-packs/ai-homework.json")),
+];
+
+/// Config and dataflow copies for the loaded pack, relative to the MoFA
+/// Studio data directory
+pub const PACK_DIR: &str = "debate/topic-pack";
+
+/// Infix of the config and dataflow copies written for a pack
+const PACK_INFIX: &str = "pack";
+
+/// Agent a stance or opening belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackRole {
+    Pro,
+    Con,
+    Moderator,
+}
+
+impl PackRole {
+    pub const ALL: [PackRole; 3] = [PackRole::Pro, PackRole::Con, PackRole::Moderator];
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "pro" => Some(PackRole::Pro),
+            "con" => Some(PackRole::Con),
+            "moderator" => Some(PackRole::Moderator),
+            _ => None,
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            PackRole::Pro => "pro",
+            PackRole::Con => "con",
+            PackRole::Moderator => "moderator",
+        }
+    }
+
+    /// Chat sender name, as used by the dataflow
+    pub fn label(&self) -> &'static str {
+        match self {
+            PackRole::Pro => "Student1 (PRO)",
+            PackRole::Con => "Student2 (CON)",
+            PackRole::Moderator => "Tutor (Moderator)",
+        }
+    }
+
+    /// Dataflow node speaking for this role
+    pub fn participant_id(&self) -> &'static str {
+        match self {
+            PackRole::Pro => "student1",
+            PackRole::Con => "student2",
+            PackRole::Moderator => "tutor",
+        }
+    }
+
+    /// Agent config the role's node reads
+    pub fn config_file(&self) -> &'static str {
+        match self {
+            PackRole::Pro => "debate_config_pro.toml",
+            PackRole::Con => "debate_config_con.toml",
+            PackRole::Moderator => "debate_config_judge.toml",
+        }
+    }
+}
+
+/// Stance each agent is told to argue; empty means the stock prompt only
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Stances {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub pro: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub con: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub moderator: String,
+}
+
+impl Stances {
+    pub fn get(&self, role: PackRole) -> &str {
+        match role {
+            PackRole::Pro => &self.pro,
+            PackRole::Con => &self.con,
+            PackRole::Moderator => &self.moderator,
+        }
+    }
+
+    pub fn set(&mut self, role: PackRole, stance: String) {
+        match role {
+            PackRole::Pro => self.pro = stance,
+            PackRole::Con => self.con = stance,
+            PackRole::Moderator => self.moderator = stance,
+        }
+    }
+}
+
+/// A scripted statement played before the live agents start
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Opening {
+    pub role: PackRole,
+    pub text: String,
+    /// TTS voice, the system default when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice: Option<String>,
+}
+
+/// A debate topic with everything needed to brief the agents
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TopicPack {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    pub motion: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub background: String,
+    #[serde(default)]
+    pub stances: Stances,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub openings: Vec<Opening>,
+}
+
+/// A problem with one field of a pack
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldError {
+    /// e.g. `openings[1].role`
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+#[derive(Debug)]
+pub enum PackError {
+    Io(String),
+    Syntax(String),
+    Invalid(Vec<FieldError>),
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackError::Io(e) => write!(f, "{}", e),
+            PackError::Syntax(e) => write!(f, "Not valid JSON: {}", e),
+            PackError::Invalid(errors) => {
+                let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "Invalid topic pack: {}", errors.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for PackError {}
+
+impl TopicPack {
+    /// Parse and validate a pack, reporting every bad field at once
+    pub fn from_json(json: &str) -> Result<Self, PackError> {
+        let value: Value = serde_json::from_str(json).map_err(|e| PackError::Syntax(e.to_string()))?;
+        let errors = validate(&value);
+        if !errors.is_empty() {
+            return Err(PackError::Invalid(errors));
+        }
+        serde_json::from_value(value).map_err(|e| PackError::Syntax(e.to_string()))
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, PackError> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| PackError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+        Self::from_json(&json)
+    }
+
+    /// Export the pack, including edits made since it was loaded
+    pub fn save(&self, path: &Path) -> Result<(), PackError> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| PackError::Syntax(e.to_string()))?;
+        std::fs::write(path, json + "\n")
+            .map_err(|e| PackError::Io(format!("Failed to write {}: {}", path.display(), e)))
+    }
+
+    /// Title for the UI, the motion when the pack has none
+    pub fn display_title(&self) -> &str {
+        if self.title.trim().is_empty() {
+            &self.motion
+        } else {
+            &self.title
+        }
+    }
+
+    /// Pack briefing appended to a role's stock system prompt
+    pub fn system_prompt(&self, role: PackRole, base: &str) -> String {
+        let mut prompt = base.trim_end().to_string();
+        prompt.push_str(&format!("\n\nMotion: {}\n", self.motion.trim()));
+        if !self.background.trim().is_empty() {
+            prompt.push_str(&format!("\nBackground:\n{}\n", self.background.trim()));
+        }
+        let stance = self.stances.get(role).trim();
+        if !stance.is_empty() {
+            prompt.push_str(&format!("\nYour stance:\n{}\n", stance));
+        }
+        prompt
+    }
+
+    /// Kickoff sent to the dataflow; mentions the openings the agents did
+    /// not hear themselves
+    pub fn kickoff_prompt(&self, openings_played: bool) -> String {
+        let mut prompt = format!(
+            "Start a formal debate. Roles: Student1 = PRO, Student2 = CON, Tutor = Judge/Moderator. Debate topic: {}. Keep turns concise and alternate speakers. Tutor manages order and summarizes at the end.",
+            self.motion.trim()
+        );
+        if openings_played && !self.openings.is_empty() {
+            prompt.push_str(" Opening statements already given:");
+            for opening in &self.openings {
+                prompt.push_str(&format!("\n{}: {}", opening.role.label(), opening.text.trim()));
+            }
+            prompt.push_str("\nContinue from there; do not repeat them.");
+        }
+        prompt
+    }
+
+    /// Write config copies carrying the pack's prompts to [`PACK_DIR`], and
+    /// a copy of `dataflow` using them. Returns the dataflow copy.
+    pub fn write_dataflow(&self, dataflow: &Path) -> Result<PathBuf, PackError> {
+        self.write_dataflow_to(dataflow, &mofa_widgets::app_storage::data_path(PACK_DIR))
+    }
+
+    fn write_dataflow_to(&self, dataflow: &Path, out_dir: &Path) -> Result<PathBuf, PackError> {
+        let dataflow = std::path::absolute(dataflow)
+            .map_err(|e| PackError::Io(format!("Failed to resolve {}: {}", dataflow.display(), e)))?;
+        let dir = dataflow.parent().unwrap_or(Path::new("/"));
+        let yaml = std::fs::read_to_string(&dataflow)
+            .map_err(|e| PackError::Io(format!("Failed to read {}: {}", dataflow.display(), e)))?;
+        std::fs::create_dir_all(out_dir)
+            .map_err(|e| PackError::Io(format!("Failed to create {}: {}", out_dir.display(), e)))?;
+
+        let mut pack_yaml = absolute_node_paths(&yaml, dir);
+        for role in PackRole::ALL {
+            let stock = role.config_file();
+            let copy = out_dir.join(pack_file_name(stock));
+            let key = format!("MAAS_CONFIG_PATH: {}", stock);
+            if !pack_yaml.contains(&key) {
+                return Err(PackError::Io(format!("{} does not use {}", dataflow.display(), stock)));
+            }
+            pack_yaml = pack_yaml.replace(&key, &format!("MAAS_CONFIG_PATH: {}", copy.display()));
+
+            let config = std::fs::read_to_string(dir.join(stock))
+                .map_err(|e| PackError::Io(format!("Failed to read {}: {}", stock, e)))?;
+            let config = self.apply_to_config(role, &config)?;
+            std::fs::write(&copy, config)
+                .map_err(|e| PackError::Io(format!("Failed to write {}: {}", copy.display(), e)))?;
+        }
+
+        let file_name = dataflow.file_name().and_then(|n| n.to_str()).unwrap_or("voice-chat.yml");
+        let path = out_dir.join(pack_file_name(file_name));
+        std::fs::write(&path, pack_yaml)
+            .map_err(|e| PackError::Io(format!("Failed to write {}: {}", path.display(), e)))?;
+        Ok(path)
+    }
+
+    /// Agent config with the pack folded into its `system_prompt`
+    fn apply_to_config(&self, role: PackRole, config: &str) -> Result<String, PackError> {
+        let mut table: toml::Table = config
+            .parse()
+            .map_err(|e| PackError::Io(format!("Failed to parse {}: {}", role.config_file(), e)))?;
+        let base = table.get("system_prompt").and_then(|v| v.as_str()).unwrap_or_default();
+        let prompt = self.system_prompt(role, base);
+        table.insert("system_prompt".to_string(), toml::Value::String(prompt));
+        toml::to_string(&table).map_err(|e| PackError::Io(format!("Failed to write {}: {}", role.config_file(), e)))
+    }
+}
+
+/// `voice-chat.yml` -> `voice-chat.pack.yml`
+fn pack_file_name(name: &str) -> String {
+    match name.rsplit_once('.') {
+        Some((stem, ext)) => format!("{}.{}.{}", stem, PACK_INFIX, ext),
+        None => format!("{}.{}", name, PACK_INFIX),
+    }
+}
+
+/// Dataflow YAML with the relative paths in `path:` and `build:` lines
+/// resolved against `dir`, the stock dataflow's folder
+fn absolute_node_paths(yaml: &str, dir: &Path) -> String {
+    let is_relative = |word: &str| word.starts_with("./") || word.starts_with("../");
+    let mut out: Vec<String> = yaml
+        .lines()
+        .map(|line| {
+            let value = line.trim_start();
+            let indent = &line[..line.len() - value.len()];
+            if let Some(path) = value.strip_prefix("path: ").filter(|p| is_relative(p.trim())) {
+                format!("{}path: {}", indent, resolve(dir, path.trim()).display())
+            } else if let Some(command) = value.strip_prefix("build: ") {
+                let words: Vec<String> = command
+                    .split(' ')
+                    .map(|word| if is_relative(word) { resolve(dir, word).display().to_string() } else { word.to_string() })
+                    .collect();
+                format!("{}build: {}", indent, words.join(" "))
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    if yaml.ends_with('\n') {
+        out.push(String::new());
+    }
+    out.join("\n")
+}
+
+/// `relative` joined to `dir` with `.` and `..` folded away
+fn resolve(dir: &Path, relative: &str) -> PathBuf {
+    let mut path = dir.to_path_buf();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::ParentDir => {
+                path.pop();
+            }
+            Component::CurDir => {}
+            other => path.push(other),
+        }
+    }
+    path
+}
+
+/// Check the pack's shape, collecting errors with their field paths
+fn validate(value: &Value) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let mut error = |path: String, message: &str| errors.push(FieldError { path, message: message.to_string() });
+
+    let Some(root) = value.as_object() else {
+        error("(root)".to_string(), "must be an object");
+        return errors;
+    };
+
+    match root.get("motion") {
+        None => error("motion".to_string(), "is required"),
+        Some(Value::String(s)) if s.trim().is_empty() => error("motion".to_string(), "must not be empty"),
+        Some(Value::String(_)) => {}
+        Some(_) => error("motion".to_string(), "must be a string"),
+    }
+    for key in ["title", "background"] {
+        if root.get(key).is_some_and(|v| !v.is_string()) {
+            error(key.to_string(), "must be a string");
+        }
+    }
+
+    match root.get("stances") {
+        None => {}
+        Some(Value::Object(stances)) => {
+            for (key, stance) in stances {
+                let path = format!("stances.{}", key);
+                if PackRole::from_key(key).is_none() {
+                    error(path, "unknown role, expected pro, con or moderator");
+                } else if !stance.is_string() {
+                    error(path, "must be a string");
+                }
+            }
+        }
+        Some(_) => error("stances".to_string(), "must be an object"),
+    }
+
+    match root.get("openings") {
+        None => {}
+        Some(Value::Array(openings)) => {
+            for (i, opening) in openings.iter().enumerate() {
+                let path = format!("openings[{}]", i);
+                let Some(opening) = opening.as_object() else {
+                    error(path, "must be an object");
+                    continue;
+                };
+                validate_opening(&path, opening, &mut error);
+            }
+        }
+        Some(_) => error("openings".to_string(), "must be an array"),
+    }
+
+    errors
+}
+
+fn validate_opening(path: &str, opening: &Map<String, Value>, error: &mut impl FnMut(String, &str)) {
+    match opening.get("role") {
+        None => error(format!("{}.role", path), "is required"),
+        Some(Value::String(role)) if PackRole::from_key(role).is_none() => {
+            error(format!("{}.role", path), "unknown role, expected pro, con or moderator")
+        }
+        Some(Value::String(_)) => {}
+        Some(_) => error(format!("{}.role", path), "must be a string"),
+    }
+    match opening.get("text") {
+        None => error(format!("{}.text", path), "is required"),
+        Some(Value::String(s)) if s.trim().is_empty() => error(format!("{}.text", path), "must not be empty"),
+        Some(Value::String(_)) => {}
+        Some(_) => error(format!("{}.text", path), "must be a string"),
+    }
+    if opening.get("voice").is_some_and(|v| !v.is_string() && !v.is_null()) {
+        error(format!("{}.voice", path), "must be a string");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(json: &str) -> Vec<String> {
+        match TopicPack::from_json(json) {
+            Err(PackError::Invalid(errors)) => errors.iter().map(|e| e.to_string()).collect(),
+            other => panic!("expected validation errors, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_sample_packs_are_valid() {
+        for (name, json) in SAMPLE_PACKS {
+            let pack = TopicPack::from_json(json).unwrap_or_else(|e| panic!("{}: {}", name, e));
+            assert!(!pack.openings.is_empty(), "{} has no openings", name);
+        }
+    }
+
+    #[test]
+    fn test_validation_reports_field_paths() {
+        let json = r#"{
+            "title": 3,
+            "stances": { "pro": "For", "judge": "Neutral", "con": 1 },
+            "openings": [
+                { "role": "pro", "text": "Hello" },
+                { "role": "audience", "text": "" },
+                "not an object"
+            ]
+        }"#;
+        assert_eq!(
+            errors(json),
+            vec![
+                "motion: is required",
+                "title: must be a string",
+                "stances.con: must be a string",
+                "stances.judge: unknown role, expected pro, con or moderator",
+                "openings[1].role: unknown role, expected pro, con or moderator",
+                "openings[1].text: must not be empty",
+                "openings[2]: must be an object",
+            ]
+        );
+        assert_eq!(errors("[]"), vec!["(root): must be an object"]);
+        assert!(matches!(TopicPack::from_json("{"), Err(PackError::Syntax(_))));
+    }
+
+    #[test]
+    fn test_round_trip_after_edit() {
+        let mut pack = TopicPack::from_json(r#"{ "motion": "Cats over dogs" }"#).unwrap();
+        assert_eq!(pack.display_title(), "Cats over dogs");
+        assert_eq!(pack.stances, Stances::default());
+
+        pack.stances.set(PackRole::Con, "Dogs are loyal".to_string());
+        let json = serde_json::to_string_pretty(&pack).unwrap();
+        assert!(!json.contains("openings"));
+        assert_eq!(TopicPack::from_json(&json).unwrap(), pack);
+    }
+
+    #[test]
+    fn test_prompts() {
+        let pack = TopicPack::from_json(
+            r#"{
+                "motion": "Cats over dogs",
+                "background": "Pets are popular.",
+                "stances": { "pro": "Cats are independent." },
+                "openings": [ { "role": "con", "text": "Dogs!" } ]
+            }"#,
+        )
+        .unwrap();
+
+        let pro = pack.system_prompt(PackRole::Pro, "You are PRO.\n");
+        assert_eq!(
+            pro,
+            "You are PRO.\n\nMotion: Cats over dogs\n\nBackground:\nPets are popular.\n\nYour stance:\nCats are independent.\n"
+        );
+        assert!(!pack.system_prompt(PackRole::Con, "You are CON.").contains("Your stance"));
+
+        assert!(!pack.kickoff_prompt(false).contains("Dogs!"));
+        assert!(pack.kickoff_prompt(true).contains("Student2 (CON): Dogs!"));
+    }
+
+    #[test]
+    fn test_write_dataflow() {
+        let root = std::env::temp_dir().join(format!("mofa-debate-pack-{}", std::process::id()));
+        let dir = root.join("apps").join("mofa-debate").join("dataflow");
+        let out_dir = root.join("data");
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut yaml = String::from(
+            "nodes:\n  - id: bridge\n    build: cargo build --manifest-path ../../../node-hub/bridge/Cargo.toml\n    path: ../../../node-hub/bridge/target/release/bridge\n  - id: segmenter\n    path: dora-text-segmenter\n",
+        );
+        for role in PackRole::ALL {
+            yaml.push_str(&format!("  - id: {}\n    env:\n      MAAS_CONFIG_PATH: {}\n", role.participant_id(), role.config_file()));
+            std::fs::write(
+                dir.join(role.config_file()),
+                "default_model = \"gpt-4.1\"\nsystem_prompt = \"\"\"\nStock prompt.\n\"\"\"\n",
+            )
+            .unwrap();
+        }
+        let dataflow = dir.join("voice-chat.yml");
+        std::fs::write(&dataflow, &yaml).unwrap();
+
+        let pack = TopicPack::from_json(r#"{ "motion": "Cats over dogs", "stances": { "moderator": "Be fair." } }"#).unwrap();
+        let path = pack.write_dataflow_to(&dataflow, &out_dir).unwrap();
+
+        assert_eq!(path, out_dir.join("voice-chat.pack.yml"));
+        let pack_yaml = std::fs::read_to_string(&path).unwrap();
+        let judge_copy = out_dir.join("debate_config_judge.pack.toml");
+        assert!(pack_yaml.contains(&format!("MAAS_CONFIG_PATH: {}", judge_copy.display())));
+        assert!(!pack_yaml.contains("debate_config_pro.toml"));
+        let bridge = root.join("node-hub").join("bridge");
+        assert!(pack_yaml.contains(&format!("build: cargo build --manifest-path {}", bridge.join("Cargo.toml").display())));
+        assert!(pack_yaml.contains(&format!("path: {}\n", bridge.join("target/release/bridge").display())));
+        assert!(pack_yaml.contains("path: dora-text-segmenter\n"));
+        assert_eq!(std::fs::read_to_string(&dataflow).unwrap(), yaml);
+        assert!(!dir.join("debate_config_judge.pack.toml").exists());
+
+        let judge: toml::Table = std::fs::read_to_string(&judge_copy)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(judge["default_model"].as_str(), Some("gpt-4.1"));
+        let prompt = judge["system_prompt"].as_str().unwrap();
+        assert!(prompt.starts_with("Stock prompt.\n\nMotion: Cats over dogs"));
+        assert!(prompt.ends_with("Your stance:\nBe fair.\n"));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
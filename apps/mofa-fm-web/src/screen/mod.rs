@@ -166,6 +166,7 @@ live_design! {
                     webview = <WebViewContainer> {
                         width: Fill, height: Fill
                         url: "about:blank"
+                        profile: "mofa-fm-web"
                     }
                 }
             }
@@ -293,11 +294,11 @@ fn get_python_path() -> Option<PathBuf> {
     None
 }
 
+/// Preferences file, relative to the MoFA Studio data directory
+pub(crate) const CONFIG_FILE: &str = "mofa-fm-web.json";
+
 fn get_config_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".mofa-studio")
-        .join("mofa-fm-web.json")
+    mofa_widgets::app_storage::data_path(CONFIG_FILE)
 }
 
 fn find_embedded_python_cmd() -> Option<String> {
@@ -497,7 +498,9 @@ impl Widget for MofaFmWebScreen {
                                 self.set_status(cx, "Connected", 1.0);
                             }
                         }
-                        WebViewAction::IpcMessage { .. } | WebViewAction::None => {}
+                        WebViewAction::IpcMessage { .. }
+                        | WebViewAction::InitTiming { .. }
+                        | WebViewAction::None => {}
                     }
                 }
             }
@@ -698,9 +701,7 @@ impl MofaFmWebScreenRef {
             );
 
             // Send theme to WebView
-            let webview = inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
-            let js = format!("if(window.setTheme) window.setTheme({});", dark_mode);
-            let _ = webview.eval(&js);
+            inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview)).set_dark_mode(dark_mode);
 
             inner.view.redraw(cx);
         }
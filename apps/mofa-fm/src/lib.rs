@@ -15,8 +15,8 @@ pub use mofa_ui::{
 pub use screen::MoFaFMScreen;
 pub use screen::MoFaFMScreenWidgetRefExt; // Export WidgetRefExt for timer control
 
-use makepad_widgets::{Cx, live_id, LiveId};
-use mofa_widgets::{AppInfo, MofaApp};
+use makepad_widgets::{Cx, ids, live_id, LiveId};
+use mofa_widgets::{AppInfo, MofaApp, TourBuilder};
 
 /// MoFA FM app descriptor
 pub struct MoFaFMApp;
@@ -39,6 +39,29 @@ impl MofaApp for MoFaFMApp {
         // Apps only need to register their own screen widgets
         screen::live_design(cx);
     }
+
+    fn tour(tour: TourBuilder) -> TourBuilder {
+        tour.step(
+            ids!(fm_page.mofa_hero),
+            "Start MoFA",
+            "Start the voice dataflow here. The panel shows its status and CPU and memory use while it runs.",
+        )
+        .step(
+            ids!(fm_page.audio_controls_row),
+            "Microphone",
+            "Mute the mic, switch echo cancellation and watch the input level.",
+        )
+        .step(
+            ids!(fm_page.device_container),
+            "Audio devices",
+            "Choose the input and output devices. What each one supports is listed underneath.",
+        )
+        .step(
+            ids!(fm_page.prompt_container),
+            "Prompt",
+            "Send the participants a text prompt instead of speaking.",
+        )
+    }
 }
 
 /// Register all MoFA FM widgets with Makepad
@@ -1,14 +1,43 @@
 //! Audio control methods for MoFaFMScreen
 //!
-//! Handles audio device selection, mic monitoring, and level visualization.
+//! Handles audio device selection, mic monitoring, input passthrough
+//! (hearing yourself through the output), and level visualization.
 
 use makepad_widgets::*;
 use mofa_settings::data::Preferences;
 use mofa_ui::LedMeterWidgetExt;
+use mofa_widgets::confirm_dialog::ConfirmDialogWidgetExt;
+use mofa_widgets::activity::{ActivityKind, ActivityRegistry};
+use mofa_widgets::searchable_drop_down::SearchableDropDownWidgetExt;
 
 use super::MoFaFMScreen;
 
+/// Rate the ASR pipeline takes mic audio at
+const CAPTURE_SAMPLE_RATE: u32 = 16000;
+
+/// Rate and channels TTS playback opens the output with (PrimeSpeech)
+const PLAYBACK_SAMPLE_RATE: u32 = 32000;
+const PLAYBACK_CHANNELS: u16 = 1;
+
 impl MoFaFMScreen {
+    /// List the audio and dora timers in the shell's running panel
+    pub(super) fn track_timers(&mut self) {
+        if self.timer_activity.is_none() {
+            let activity = ActivityRegistry::global()
+                .register(ActivityKind::Timer, "mofa-fm", "Audio and dataflow polling")
+                .stoppable();
+            activity.set_state("every 50 ms / 100 ms");
+            self.timer_activity = Some(activity);
+        }
+    }
+
+    /// Stop the audio and dora timers
+    pub(super) fn stop_polling(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.audio_timer);
+        cx.stop_timer(self.dora_timer);
+        self.timer_activity = None;
+    }
+
     /// Initialize audio manager and populate device dropdowns
     pub(super) fn init_audio(&mut self, cx: &mut Cx) {
         let mut audio_manager = mofa_ui::AudioManager::new();
@@ -25,42 +54,55 @@ impl MoFaFMScreen {
                 d.name.clone()
             }
         }).collect();
-        self.input_devices = input_devices.iter().map(|d| d.name.clone()).collect();
+        self.input_devices = input_devices;
 
-        // Get output devices
-        let output_devices = audio_manager.get_output_devices();
-        let output_labels: Vec<String> = output_devices.iter().map(|d| {
+        // Get output devices, headphones first so the dropdown can group them
+        let mut output_devices = audio_manager.get_output_devices();
+        output_devices.sort_by_key(|d| !mofa_ui::is_likely_headphones(&d.name));
+        let headphone_count = output_devices.iter().filter(|d| mofa_ui::is_likely_headphones(&d.name)).count();
+        let mut output_labels: Vec<String> = output_devices.iter().map(|d| {
             if d.is_default {
                 format!("{} (Default)", d.name)
             } else {
                 d.name.clone()
             }
         }).collect();
-        self.output_devices = output_devices.iter().map(|d| d.name.clone()).collect();
+        let speaker_labels = output_labels.split_off(headphone_count);
+        let output_groups = vec![
+            ("Headphones".to_string(), output_labels),
+            ("Speakers and other outputs".to_string(), speaker_labels),
+        ];
+        self.output_devices = output_devices;
 
         // Populate input dropdown
+        let mut input_idx = None;
         if !input_labels.is_empty() {
-            let dropdown = self.view.drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.input_device_group.input_device_dropdown));
+            let dropdown = self.view.searchable_drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.input_device_group.input_device_dropdown));
             dropdown.set_labels(cx, input_labels);
             // Restore saved selection or default to first
             let selected_idx = prefs.audio_input_device
                 .as_ref()
-                .and_then(|saved| self.input_devices.iter().position(|d| d == saved))
+                .and_then(|saved| self.input_devices.iter().position(|d| &d.name == saved))
                 .unwrap_or(0);
             dropdown.set_selected_item(cx, selected_idx);
+            input_idx = Some(selected_idx);
         }
 
         // Populate output dropdown
-        if !output_labels.is_empty() {
-            let dropdown = self.view.drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.output_device_group.output_device_dropdown));
-            dropdown.set_labels(cx, output_labels);
+        let mut output_idx = None;
+        if !self.output_devices.is_empty() {
+            let dropdown = self.view.searchable_drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.output_device_group.output_device_dropdown));
+            dropdown.set_grouped_labels(cx, output_groups);
             // Restore saved selection or default to first
             let selected_idx = prefs.audio_output_device
                 .as_ref()
-                .and_then(|saved| self.output_devices.iter().position(|d| d == saved))
+                .and_then(|saved| self.output_devices.iter().position(|d| &d.name == saved))
                 .unwrap_or(0);
             dropdown.set_selected_item(cx, selected_idx);
+            audio_manager.set_output_device(&self.output_devices[selected_idx].name);
+            output_idx = Some(selected_idx);
         }
+        self.update_device_caps(cx, input_idx, output_idx);
 
         // Start mic monitoring with saved device or default
         let input_device = prefs.audio_input_device.as_deref();
@@ -76,7 +118,7 @@ impl MoFaFMScreen {
         self.audio_manager = Some(audio_manager);
 
         // Initialize audio player for TTS playback (32kHz for PrimeSpeech)
-        match crate::audio_player::create_audio_player(32000) {
+        match crate::audio_player::create_audio_player(PLAYBACK_SAMPLE_RATE) {
             Ok(player) => {
                 ::log::info!("Audio player initialized (32kHz)");
                 self.audio_player = Some(player);
@@ -93,6 +135,7 @@ impl MoFaFMScreen {
         // Start dora timer for participant panel updates (needed for audio visualization)
         self.dora_timer = cx.start_interval(0.1);
         ::log::info!("Dora timer started: {:?}", self.dora_timer);
+        self.track_timers();
 
         // Also request next frame to ensure continuous updates
         cx.new_next_frame();
@@ -106,13 +149,39 @@ impl MoFaFMScreen {
         self.view.redraw(cx);
     }
 
+    /// Show the capabilities of the devices at `input_idx` / `output_idx`
+    /// under their dropdowns, flagging any that can't run the format FM
+    /// opens them with. `None` leaves that side's line as it is.
+    pub(super) fn update_device_caps(&mut self, cx: &mut Cx, input_idx: Option<usize>, output_idx: Option<usize>) {
+        let sides = [
+            (input_idx.and_then(|i| self.input_devices.get(i)), CAPTURE_SAMPLE_RATE, 1,
+                ids!(running_tab_content.audio_container.device_container.device_caps.input_device_caps)),
+            (output_idx.and_then(|i| self.output_devices.get(i)), PLAYBACK_SAMPLE_RATE, PLAYBACK_CHANNELS,
+                ids!(running_tab_content.audio_container.device_container.device_caps.output_device_caps)),
+        ];
+        for (device, rate, channels, path) in sides {
+            let Some(device) = device else { continue };
+            let warning = device.compatibility_warning(rate, channels);
+            let text = match warning {
+                Some(ref warning) => format!("{} \u{2014} {}", device.capability_summary(), warning),
+                None => device.capability_summary(),
+            };
+            let label = self.view.label(path);
+            label.set_text(cx, &text);
+            let warning = if warning.is_some() { 1.0 } else { 0.0 };
+            label.apply_over(cx, live!{
+                draw_text: { warning: (warning) }
+            });
+        }
+        self.view.redraw(cx);
+    }
+
     /// Initialize log entries with a startup message
     pub(super) fn init_demo_logs(&mut self, cx: &mut Cx) {
         // Start with empty logs - real logs will come from log_bridge
-        self.log_entries = vec![
-            "[INFO] [App] MoFA FM initialized".to_string(),
-            "[INFO] [App] System log ready - Rust logs will appear here".to_string(),
-        ];
+        self.log_buffer.clear();
+        self.log_buffer.push("[INFO] [App] MoFA FM initialized".to_string());
+        self.log_buffer.push("[INFO] [App] System log ready - Rust logs will appear here".to_string());
 
         // Update the log display
         self.update_log_display(cx);
@@ -136,6 +205,34 @@ impl MoFaFMScreen {
         // Use the LedMeter widget from mofa-ui
         self.view.led_meter(ids!(running_tab_content.audio_container.audio_controls_row.mic_container.mic_group.mic_level_meter))
             .set_level(cx, level);
+        self.update_agc_badge(cx);
+    }
+
+    /// Switch automatic gain control of the monitored input on or off
+    pub(super) fn toggle_agc(&mut self, cx: &mut Cx) {
+        let Some(ref audio_manager) = self.audio_manager else {
+            return;
+        };
+        let enabled = !audio_manager.is_agc_enabled();
+        audio_manager.set_agc_enabled(enabled);
+        ::log::info!("AGC toggled: enabled={}", enabled);
+        self.update_agc_badge(cx);
+    }
+
+    /// Show whether AGC is on and the gain it applies, e.g. "AGC +12 dB"
+    fn update_agc_badge(&mut self, cx: &mut Cx) {
+        let gain = self.audio_manager.as_ref().and_then(|audio_manager| audio_manager.agc_gain());
+        let enabled = if gain.is_some() { 1.0 } else { 0.0 };
+        let text = match gain {
+            Some(gain) => format!("AGC {:+.0} dB", 20.0 * gain.max(1e-3).log10()),
+            None => "AGC".to_string(),
+        };
+
+        let badge = self.view.view(ids!(running_tab_content.audio_container.audio_controls_row.mic_container.mic_group.agc_badge));
+        badge.apply_over(cx, live!{ draw_bg: { enabled: (enabled) } });
+        let label = badge.label(ids!(agc_label));
+        label.apply_over(cx, live!{ draw_text: { enabled: (enabled) } });
+        label.set_text(cx, &text);
     }
 
     /// Update mic level LEDs from dora shared state (AEC input bridge)
@@ -194,6 +291,14 @@ impl MoFaFMScreen {
     pub(super) fn select_output_device(&mut self, device_name: &str) {
         if let Some(ref mut audio_manager) = self.audio_manager {
             audio_manager.set_output_device(device_name);
+
+            // Move a running passthrough to the new output
+            if audio_manager.is_passthrough_active() {
+                let gain = audio_manager.passthrough_gain();
+                if let Err(e) = audio_manager.start_input_passthrough(Some(device_name), gain) {
+                    ::log::error!("Failed to move input monitoring to '{}': {}", device_name, e);
+                }
+            }
         }
 
         // Save preference
@@ -203,4 +308,95 @@ impl MoFaFMScreen {
             eprintln!("Failed to save audio output preference: {}", e);
         }
     }
+
+    /// Handle the headphone toggle: stop monitoring, or start it after a
+    /// feedback warning when the output looks like speakers and AEC is off
+    pub(super) fn toggle_input_monitor(&mut self, cx: &mut Cx) {
+        let Some(ref mut audio_manager) = self.audio_manager else {
+            return;
+        };
+
+        if audio_manager.is_passthrough_active() {
+            audio_manager.stop_input_passthrough();
+            ::log::info!("Input monitoring stopped");
+            self.update_monitor_ui(cx);
+            return;
+        }
+
+        let output = audio_manager
+            .current_output_device()
+            .map(str::to_string)
+            .or_else(|| self.output_devices.first().map(|d| d.name.clone()))
+            .unwrap_or_default();
+        if !self.aec_enabled && !mofa_ui::is_likely_headphones(&output) {
+            let rect = self.view.area().rect(cx);
+            let dialog = self.view.confirm_dialog(ids!(monitor_confirm));
+            dialog.apply_over(cx, live!{
+                abs_pos: (rect.pos)
+                width: (rect.size.x)
+                height: (rect.size.y)
+            });
+            dialog.set_confirm_text(cx, "Monitor anyway");
+            dialog.show(
+                cx,
+                live_id!(monitor_speakers),
+                "Monitor through speakers?",
+                &format!(
+                    "\"{}\" does not look like headphones and echo cancellation is off. \
+                     Your microphone may pick up the output and cause loud feedback.",
+                    output
+                ),
+            );
+            return;
+        }
+
+        self.start_input_monitor(cx);
+    }
+
+    /// Start routing the selected microphone to the selected output
+    pub(super) fn start_input_monitor(&mut self, cx: &mut Cx) {
+        if let Some(ref mut audio_manager) = self.audio_manager {
+            match audio_manager.start_input_passthrough(None, 1.0) {
+                Ok(()) => ::log::info!("Input monitoring started"),
+                Err(e) => ::log::error!("Failed to start input monitoring: {}", e),
+            }
+        }
+        self.update_monitor_ui(cx);
+    }
+
+    /// Pick up passthrough that stopped on its own and refresh the latency readout.
+    /// Called from the audio timer.
+    pub(super) fn poll_input_monitor(&mut self, cx: &mut Cx) {
+        let Some(ref mut audio_manager) = self.audio_manager else {
+            return;
+        };
+        if let Some(reason) = audio_manager.take_passthrough_stop_reason() {
+            ::log::warn!("Input monitoring stopped: {}", reason);
+            self.update_monitor_ui(cx);
+        } else if audio_manager.is_passthrough_active() {
+            self.update_monitor_ui(cx);
+        }
+    }
+
+    pub(super) fn update_monitor_ui(&mut self, cx: &mut Cx) {
+        let (active, latency) = match self.audio_manager {
+            Some(ref audio_manager) => (
+                audio_manager.is_passthrough_active(),
+                audio_manager.passthrough_latency_ms(),
+            ),
+            None => (false, None),
+        };
+
+        self.view.view(ids!(running_tab_content.audio_container.audio_controls_row.monitor_container.monitor_group.monitor_toggle_btn))
+            .apply_over(cx, live!{ draw_bg: { enabled: (if active { 1.0 } else { 0.0 }) } });
+
+        let label = self.view.label(ids!(running_tab_content.audio_container.audio_controls_row.monitor_container.monitor_group.monitor_latency));
+        label.set_visible(cx, active);
+        let text = match latency {
+            Some(ms) => format!("{:.0} ms", ms),
+            None => "...".to_string(),
+        };
+        label.set_text(cx, &text);
+        self.view.redraw(cx);
+    }
 }
@@ -0,0 +1,366 @@
+//! Audio test wizard for MoFaFMScreen
+//!
+//! Helps tell a dead mic from a wrong device or a muted input:
+//! 1. a generated sweep plays through the selected output and the user
+//!    confirms hearing it;
+//! 2. two seconds are recorded from the selected input, the peak level is
+//!    shown and the recording is played back;
+//! 3. a summary suggests what to change.
+//!
+//! Tone, recording and playback all go through the screen's `AudioManager`
+//! and are advanced by the audio timer; closing the wizard releases them.
+
+use makepad_widgets::*;
+use mofa_ui::LedMeterWidgetExt;
+use std::time::{Duration, Instant};
+
+use super::MoFaFMScreen;
+
+const TONE_SECS: f32 = 1.5;
+const TONE_SAMPLE_RATE: u32 = 48000;
+const RECORD_SECS: f32 = 2.0;
+/// Extra wait before a recording that never fills up (stalled capture) is taken as is
+const RECORD_GRACE: Duration = Duration::from_secs(1);
+
+/// Below this peak the recording counts as silence
+const SILENT_PEAK: f32 = 0.02;
+/// Below this peak the input works but is very quiet
+const QUIET_PEAK: f32 = 0.1;
+/// At or above this peak the input clips
+const CLIPPING_PEAK: f32 = 0.99;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(super) enum AudioTestStep {
+    #[default]
+    Closed,
+    PlayingTone,
+    AskTone,
+    ReadyToRecord,
+    Recording(Instant),
+    PlayingBack,
+    AskPlayback,
+    Summary,
+}
+
+/// What the wizard found out so far
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(super) struct AudioTestResults {
+    pub tone_heard: Option<bool>,
+    pub output_error: Option<String>,
+    pub input_peak: Option<f32>,
+    pub input_error: Option<String>,
+    pub playback_heard: Option<bool>,
+}
+
+impl AudioTestResults {
+    /// One line per device with what worked and what to try
+    pub fn summary(&self, input: &str, output: &str, mic_muted: bool) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        lines.push(match (&self.output_error, self.tone_heard) {
+            (Some(e), _) => format!("Speaker: could not play on \"{}\" ({}). Pick another speaker.", output, e),
+            (None, Some(true)) => format!("Speaker: working, the tone played on \"{}\".", output),
+            (None, Some(false)) => format!(
+                "Speaker: no tone heard on \"{}\". Check the volume and that the device is not muted, or pick another speaker.",
+                output
+            ),
+            (None, None) => "Speaker: not tested.".to_string(),
+        });
+
+        let percent = |peak: f32| (peak * 100.0).round() as u32;
+        lines.push(match (&self.input_error, self.input_peak) {
+            (Some(e), _) => format!("Microphone: could not record from \"{}\" ({}). Pick another microphone.", input, e),
+            (None, None) => "Microphone: not tested.".to_string(),
+            (None, Some(peak)) if peak < SILENT_PEAK => format!(
+                "Microphone: \"{}\" recorded silence. Check that it is not muted, pick another microphone, \
+                 or allow microphone access for MoFA Studio in the system privacy settings.",
+                input
+            ),
+            (None, Some(peak)) if peak < QUIET_PEAK => format!(
+                "Microphone: very quiet (peak {}%). Move closer or raise the input level in the system sound settings.",
+                percent(peak)
+            ),
+            (None, Some(peak)) if peak >= CLIPPING_PEAK => {
+                "Microphone: clipping (peak 100%). Lower the input level in the system sound settings.".to_string()
+            }
+            (None, Some(peak)) => format!("Microphone: working (peak {}%).", percent(peak)),
+        });
+
+        let heard_tone = self.tone_heard == Some(true);
+        let recorded = self.input_peak.is_some_and(|peak| peak >= SILENT_PEAK);
+        if self.playback_heard == Some(false) && heard_tone && recorded {
+            lines.push(
+                "Playback: the tone was audible but your recording was not. The microphone may be picking up \
+                 another source; try another input device."
+                    .to_string(),
+            );
+        }
+        if mic_muted {
+            lines.push("The FM mic button is muted; unmute it to be heard in a session.".to_string());
+        }
+        lines
+    }
+}
+
+/// Wizard state kept on the screen
+#[derive(Debug, Default)]
+pub(super) struct AudioTest {
+    pub step: AudioTestStep,
+    pub results: AudioTestResults,
+}
+
+impl MoFaFMScreen {
+    /// Show the wizard over the screen and play the test tone
+    pub(super) fn open_audio_test(&mut self, cx: &mut Cx) {
+        if self.audio_manager.is_none() {
+            return;
+        }
+        self.release_audio_test();
+        // Monitoring would feed the tone and the playback back into the mic
+        if let Some(ref mut audio_manager) = self.audio_manager {
+            if audio_manager.is_passthrough_active() {
+                audio_manager.stop_input_passthrough();
+                self.update_monitor_ui(cx);
+            }
+        }
+
+        let rect = self.view.area().rect(cx);
+        let wizard = self.view.view(ids!(audio_test_wizard));
+        wizard.apply_over(cx, live!{
+            abs_pos: (rect.pos)
+            width: (rect.size.x)
+            height: (rect.size.y)
+        });
+        wizard.set_visible(cx, true);
+
+        self.audio_test.results = AudioTestResults::default();
+        self.play_test_tone(cx);
+    }
+
+    /// Hide the wizard and release its streams
+    pub(super) fn close_audio_test(&mut self, cx: &mut Cx) {
+        if self.audio_test.step == AudioTestStep::Closed {
+            return;
+        }
+        self.release_audio_test();
+        self.audio_test.step = AudioTestStep::Closed;
+        self.view.view(ids!(audio_test_wizard)).set_visible(cx, false);
+        self.view.redraw(cx);
+    }
+
+    /// Stop a tone, recording or playback still running
+    fn release_audio_test(&mut self) {
+        if let Some(ref mut audio_manager) = self.audio_manager {
+            audio_manager.stop_clip();
+            if audio_manager.is_recording() {
+                audio_manager.take_recording();
+            }
+        }
+    }
+
+    fn play_test_tone(&mut self, cx: &mut Cx) {
+        let Some(ref mut audio_manager) = self.audio_manager else { return };
+        let tone = mofa_ui::test_tone(TONE_SAMPLE_RATE, TONE_SECS);
+        self.audio_test.step = match audio_manager.play_clip(&tone, TONE_SAMPLE_RATE) {
+            Ok(()) => AudioTestStep::PlayingTone,
+            Err(e) => {
+                ::log::warn!("Audio test tone failed: {}", e);
+                self.audio_test.results.output_error = Some(e);
+                AudioTestStep::ReadyToRecord
+            }
+        };
+        self.update_audio_test_ui(cx);
+    }
+
+    fn start_test_recording(&mut self, cx: &mut Cx) {
+        let Some(ref mut audio_manager) = self.audio_manager else { return };
+        audio_manager.stop_clip();
+        self.audio_test.step = match audio_manager.start_recording(RECORD_SECS) {
+            Ok(()) => AudioTestStep::Recording(Instant::now()),
+            Err(e) => {
+                ::log::warn!("Audio test recording failed: {}", e);
+                self.audio_test.results.input_error = Some(e);
+                AudioTestStep::Summary
+            }
+        };
+        self.update_audio_test_ui(cx);
+    }
+
+    /// Advance the wizard when a tone, recording or playback ends.
+    /// Called from the audio timer.
+    pub(super) fn poll_audio_test(&mut self, cx: &mut Cx) {
+        let Some(ref mut audio_manager) = self.audio_manager else { return };
+        let next = match self.audio_test.step {
+            AudioTestStep::PlayingTone if !audio_manager.is_clip_playing() => AudioTestStep::AskTone,
+            AudioTestStep::PlayingBack if !audio_manager.is_clip_playing() => AudioTestStep::AskPlayback,
+            AudioTestStep::Recording(started) => {
+                let level = audio_manager.get_mic_level();
+                self.view.led_meter(ids!(audio_test_wizard.test_level_row.test_level_meter)).set_level(cx, level);
+
+                let stalled = started.elapsed() > Duration::from_secs_f32(RECORD_SECS) + RECORD_GRACE;
+                if audio_manager.is_recording() && !stalled {
+                    return;
+                }
+                let recording = audio_manager.take_recording();
+                let peak = recording.peak();
+                self.audio_test.results.input_peak = Some(peak);
+                if peak < SILENT_PEAK {
+                    // Nothing worth playing back
+                    AudioTestStep::Summary
+                } else {
+                    match audio_manager.play_clip(&recording.samples, recording.sample_rate) {
+                        Ok(()) => AudioTestStep::PlayingBack,
+                        Err(e) => {
+                            ::log::warn!("Audio test playback failed: {}", e);
+                            AudioTestStep::Summary
+                        }
+                    }
+                }
+            }
+            _ => return,
+        };
+        self.audio_test.step = next;
+        self.update_audio_test_ui(cx);
+    }
+
+    /// Handle the wizard's buttons
+    pub(super) fn handle_audio_test_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(running_tab_content.audio_container.audio_test_btn)).clicked(actions) {
+            self.open_audio_test(cx);
+            return;
+        }
+        if self.audio_test.step == AudioTestStep::Closed {
+            return;
+        }
+
+        if self.view.button(ids!(audio_test_wizard.test_close_btn)).clicked(actions) {
+            self.close_audio_test(cx);
+            return;
+        }
+        let retry = self.view.button(ids!(audio_test_wizard.test_retry_btn)).clicked(actions);
+        let no = self.view.button(ids!(audio_test_wizard.test_no_btn)).clicked(actions);
+        let yes = self.view.button(ids!(audio_test_wizard.test_yes_btn)).clicked(actions);
+
+        match self.audio_test.step {
+            AudioTestStep::AskTone if retry => self.play_test_tone(cx),
+            AudioTestStep::AskTone if yes || no => {
+                self.audio_test.results.tone_heard = Some(yes);
+                self.audio_test.step = AudioTestStep::ReadyToRecord;
+                self.update_audio_test_ui(cx);
+            }
+            AudioTestStep::ReadyToRecord if yes => self.start_test_recording(cx),
+            AudioTestStep::AskPlayback if retry => self.start_test_recording(cx),
+            AudioTestStep::AskPlayback if yes || no => {
+                self.audio_test.results.playback_heard = Some(yes);
+                self.audio_test.step = AudioTestStep::Summary;
+                self.update_audio_test_ui(cx);
+            }
+            AudioTestStep::Summary if retry => self.open_audio_test(cx),
+            AudioTestStep::Summary if yes => self.close_audio_test(cx),
+            _ => {}
+        }
+    }
+
+    /// Step caption, message and buttons for the current step
+    fn update_audio_test_ui(&mut self, cx: &mut Cx) {
+        let (input, output) = match self.audio_manager {
+            Some(ref audio_manager) => (
+                audio_manager.current_input_device().unwrap_or("the default microphone").to_string(),
+                audio_manager.current_output_device().unwrap_or("the default speaker").to_string(),
+            ),
+            None => return,
+        };
+        let peak = self.audio_test.results.input_peak.map_or(0, |p| (p * 100.0).round() as u32);
+
+        // (step caption, message, retry, no, yes); an empty label hides the button
+        let (step, message, retry, no, yes) = match self.audio_test.step {
+            AudioTestStep::Closed => return,
+            AudioTestStep::PlayingTone => (
+                "Step 1 of 3 \u{00b7} Speaker",
+                format!("Playing a test tone on \"{}\"...", output),
+                "", "", "",
+            ),
+            AudioTestStep::AskTone => (
+                "Step 1 of 3 \u{00b7} Speaker",
+                format!("Did you hear a rising tone on \"{}\"?", output),
+                "Play again", "No sound", "I heard it",
+            ),
+            AudioTestStep::ReadyToRecord => (
+                "Step 2 of 3 \u{00b7} Microphone",
+                format!(
+                    "Press Record and say a few words. Two seconds are recorded from \"{}\" and played back.",
+                    input
+                ),
+                "", "", "Record",
+            ),
+            AudioTestStep::Recording(_) => (
+                "Step 2 of 3 \u{00b7} Microphone",
+                "Recording... speak now.".to_string(),
+                "", "", "",
+            ),
+            AudioTestStep::PlayingBack => (
+                "Step 2 of 3 \u{00b7} Microphone",
+                format!("Peak level {}%. Playing your recording back...", peak),
+                "", "", "",
+            ),
+            AudioTestStep::AskPlayback => (
+                "Step 2 of 3 \u{00b7} Microphone",
+                format!("Peak level {}%. Did you hear your recording?", peak),
+                "Record again", "Didn't hear it", "Sounds right",
+            ),
+            AudioTestStep::Summary => (
+                "Step 3 of 3 \u{00b7} Results",
+                self.audio_test.results.summary(&input, &output, self.mic_muted).join("\n\n"),
+                "Test again", "", "Done",
+            ),
+        };
+
+        self.view.label(ids!(audio_test_wizard.test_step)).set_text(cx, step);
+        self.view.label(ids!(audio_test_wizard.test_message)).set_text(cx, &message);
+        let recording = matches!(self.audio_test.step, AudioTestStep::Recording(_));
+        self.view.view(ids!(audio_test_wizard.test_level_row)).set_visible(cx, recording);
+        for (path, text) in [
+            (ids!(audio_test_wizard.test_retry_btn), retry),
+            (ids!(audio_test_wizard.test_no_btn), no),
+            (ids!(audio_test_wizard.test_yes_btn), yes),
+        ] {
+            let button = self.view.button(path);
+            button.set_text(cx, text);
+            button.set_visible(cx, !text.is_empty());
+        }
+        self.view.redraw(cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_suggestions() {
+        let working = AudioTestResults {
+            tone_heard: Some(true),
+            input_peak: Some(0.4),
+            playback_heard: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            working.summary("USB Mic", "Speakers", false),
+            vec!["Speaker: working, the tone played on \"Speakers\".", "Microphone: working (peak 40%)."]
+        );
+
+        let silent = AudioTestResults { tone_heard: Some(false), input_peak: Some(0.001), ..Default::default() };
+        let lines = silent.summary("USB Mic", "HDMI", true);
+        assert!(lines[0].starts_with("Speaker: no tone heard on \"HDMI\""));
+        assert!(lines[1].contains("recorded silence") && lines[1].contains("privacy settings"));
+        assert!(lines[2].contains("mic button is muted"));
+
+        let wrong_source = AudioTestResults { playback_heard: Some(false), ..working.clone() };
+        assert!(wrong_source.summary("USB Mic", "Speakers", false)[2].contains("another source"));
+
+        let failed = AudioTestResults { input_error: Some("Mic monitoring is not running".into()), ..Default::default() };
+        let lines = failed.summary("USB Mic", "Speakers", false);
+        assert_eq!(lines[0], "Speaker: not tested.");
+        assert!(lines[1].contains("could not record"));
+    }
+}
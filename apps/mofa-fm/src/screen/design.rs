@@ -18,6 +18,8 @@ live_design! {
     use mofa_widgets::theme::*;
     use mofa_widgets::participant_panel::ParticipantPanel;
     use mofa_widgets::log_panel::LogPanel;
+    use mofa_widgets::searchable_drop_down::SearchableDropDown;
+    use mofa_widgets::confirm_dialog::ConfirmDialog;
     use mofa_ui::widgets::mofa_hero::MofaHero;
 
     // Local layout constants (colors imported from theme)
@@ -197,6 +199,75 @@ live_design! {
         }
     }
 
+    // Input monitoring (mic -> output passthrough) toggle
+    MonitorButton = <View> {
+        width: Fit
+        height: Fit
+        padding: 6
+        cursor: Hand
+        show_bg: true
+
+        draw_bg: {
+            instance enabled: 0.0   // 1.0 = passthrough running
+            instance dark_mode: 0.0
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 4.0);
+                let gray = mix(vec4(0.667, 0.686, 0.725, 1.0), vec4(0.278, 0.333, 0.412, 1.0), self.dark_mode);
+                let blue = vec4(0.231, 0.510, 0.965, 1.0);
+                sdf.fill(mix(gray, blue, self.enabled));
+                return sdf.result;
+            }
+        }
+
+        align: {x: 0.5, y: 0.5}
+
+        icon = <Icon> {
+            draw_icon: {
+                svg_file: dep("crate://self/resources/icons/headphones.svg")
+                fn get_color(self) -> vec4 {
+                    return vec4(1.0, 1.0, 1.0, 1.0);  // WHITE
+                }
+            }
+            icon_walk: {width: 20, height: 20}
+        }
+    }
+
+    // Small pill next to the mic meter: click to toggle automatic gain
+    // control; shows the applied gain while on
+    AgcBadge = <View> {
+        width: Fit, height: Fit
+        padding: {left: 6, right: 6, top: 3, bottom: 3}
+        cursor: Hand
+        show_bg: true
+        draw_bg: {
+            instance enabled: 0.0   // 1.0 = AGC on
+            instance dark_mode: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 3.0);
+                let off = mix((GRAY_200), (SLATE_700), self.dark_mode);
+                let on = mix((ACCENT_BLUE), (ACCENT_BLUE_DARK), self.dark_mode);
+                sdf.fill(mix(off, on, self.enabled));
+                return sdf.result;
+            }
+        }
+
+        agc_label = <Label> {
+            text: "AGC"
+            draw_text: {
+                instance enabled: 0.0
+                instance dark_mode: 0.0
+                text_style: <FONT_MEDIUM>{ font_size: 9.0 }
+                fn get_color(self) -> vec4 {
+                    let off = mix((GRAY_500), (TEXT_SECONDARY_DARK), self.dark_mode);
+                    return mix(off, (WHITE), self.enabled);
+                }
+            }
+        }
+    }
+
     // Tab button style
     TabButton = <View> {
         width: Fit, height: Fit
@@ -273,6 +344,186 @@ live_design! {
         }
     }
 
+    // Capability line under a device dropdown, amber when the device
+    // can't run the format FM uses
+    DeviceCapsLabel = <Label> {
+        width: Fill, height: Fit
+        padding: {left: 98}  // Under the dropdown, past the 90px device label
+        text: ""
+        draw_text: {
+            instance dark_mode: 0.0
+            instance warning: 0.0
+            text_style: <FONT_REGULAR>{ font_size: 9.0 }
+            fn get_color(self) -> vec4 {
+                let normal = mix((GRAY_500), (TEXT_SECONDARY_DARK), self.dark_mode);
+                return mix(normal, (AMBER_500), self.warning);
+            }
+        }
+    }
+
+    // Small text button for log panel actions (Marker, Clear)
+    LogActionButton = <Button> {
+        width: Fit, height: 24
+        padding: {left: 10, right: 10}
+
+        animator: {
+            hover = {
+                default: off,
+                off = {
+                    from: {all: Forward {duration: 0.15}}
+                    apply: { draw_bg: {hover: 0.0} }
+                }
+                on = {
+                    from: {all: Forward {duration: 0.15}}
+                    apply: { draw_bg: {hover: 1.0} }
+                }
+            }
+            pressed = {
+                default: off,
+                off = {
+                    from: {all: Forward {duration: 0.1}}
+                    apply: { draw_bg: {pressed: 0.0} }
+                }
+                on = {
+                    from: {all: Forward {duration: 0.1}}
+                    apply: { draw_bg: {pressed: 1.0} }
+                }
+            }
+        }
+
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+            fn get_color(self) -> vec4 {
+                return mix((GRAY_700), (SLATE_300), self.dark_mode);
+            }
+        }
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            instance dark_mode: 0.0
+            border_radius: 2.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.border_radius);
+                let base = mix((HOVER_BG), (SLATE_600), self.dark_mode);
+                let hover_color = mix((SLATE_200), (SLATE_500), self.dark_mode);
+                let pressed_color = mix((SLATE_300), (SLATE_400), self.dark_mode);
+                let color = mix(mix(base, hover_color, self.hover), pressed_color, self.pressed);
+                sdf.fill(color);
+                return sdf.result;
+            }
+        }
+    }
+
+    // Log action button that stays highlighted while its mode is on
+    LogToggleButton = <LogActionButton> {
+        draw_text: {
+            instance active: 0.0
+            fn get_color(self) -> vec4 {
+                return mix(mix((GRAY_700), (SLATE_300), self.dark_mode), (WHITE), self.active);
+            }
+        }
+        draw_bg: {
+            instance active: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.border_radius);
+                let base = mix((HOVER_BG), (SLATE_600), self.dark_mode);
+                let hover_color = mix((SLATE_200), (SLATE_500), self.dark_mode);
+                let pressed_color = mix((SLATE_300), (SLATE_400), self.dark_mode);
+                let active_color = mix((ACCENT_BLUE), (ACCENT_BLUE_DARK), self.dark_mode);
+                let color = mix(mix(base, hover_color, self.hover), pressed_color, self.pressed);
+                sdf.fill(mix(color, active_color, self.active));
+                return sdf.result;
+            }
+        }
+    }
+
+    // One system log line. The dot pins it; a pinned line is tinted and
+    // shows a note field under it.
+    LogRow = <View> {
+        width: Fill, height: Fit
+        flow: Down
+        padding: {left: 6, right: 12, top: 1, bottom: 1}
+        spacing: 2
+        show_bg: true
+        draw_bg: {
+            instance dark_mode: 0.0
+            instance pinned: 0.0
+            fn pixel(self) -> vec4 {
+                let tint = mix((BLUE_50), (SLATE_700), self.dark_mode);
+                return mix(vec4(0.0, 0.0, 0.0, 0.0), tint, self.pinned);
+            }
+        }
+
+        line = <View> {
+            width: Fill, height: Fit
+            flow: Right
+            spacing: 4
+
+            pin_btn = <View> {
+                width: 14, height: 14
+                cursor: Hand
+                show_bg: true
+                draw_bg: {
+                    instance dark_mode: 0.0
+                    instance pinned: 0.0
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        let c = self.rect_size * 0.5;
+                        let idle = mix((GRAY_300), (SLATE_500), self.dark_mode);
+                        let on = mix((ACCENT_BLUE), (ACCENT_BLUE_DARK), self.dark_mode);
+                        sdf.circle(c.x, c.y, 3.5);
+                        sdf.fill_keep(mix(vec4(0.0, 0.0, 0.0, 0.0), on, self.pinned));
+                        sdf.stroke(mix(idle, on, self.pinned), 1.2);
+                        return sdf.result;
+                    }
+                }
+            }
+
+            log_text = <Label> {
+                width: Fill, height: Fit
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                    wrap: Word
+                    fn get_color(self) -> vec4 {
+                        return mix((GRAY_600), (TEXT_PRIMARY_DARK), self.dark_mode);
+                    }
+                }
+                text: ""
+            }
+        }
+
+        note_input = <TextInput> {
+            visible: false
+            width: Fill, height: 22
+            margin: {left: 18}
+            empty_text: "Note..."
+            draw_bg: {
+                instance dark_mode: 0.0
+                border_radius: 2.0
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.border_radius);
+                    sdf.fill(mix((WHITE), (SLATE_800), self.dark_mode));
+                    return sdf.result;
+                }
+            }
+            draw_text: {
+                instance dark_mode: 0.0
+                text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                fn get_color(self) -> vec4 {
+                    return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                }
+            }
+            draw_cursor: {
+                color: (ACCENT_BLUE)
+            }
+        }
+    }
+
     // MoFA FM Screen - adaptive horizontal layout with left content and right log panel
     pub MoFaFMScreen = {{MoFaFMScreen}} {
         width: Fill, height: Fill
@@ -532,6 +783,8 @@ live_design! {
                         mic_mute_btn = <MicButton> {}
 
                         mic_level_meter = <LedMeter> {}
+
+                        agc_badge = <AgcBadge> {}
                     }
                 }
 
@@ -565,6 +818,48 @@ live_design! {
                     }
                 }
 
+                // Input monitoring toggle container
+                monitor_container = <RoundedView> {
+                    width: Fit, height: Fit
+                    padding: (PANEL_PADDING)
+                    show_bg: true
+                    draw_bg: {
+                        instance dark_mode: 0.0
+                        border_radius: (PANEL_RADIUS)
+                        border_size: 1.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.border_radius);
+                            let bg = mix((PANEL_BG), (PANEL_BG_DARK), self.dark_mode);
+                            let border = mix((BORDER), (SLATE_600), self.dark_mode);
+                            sdf.fill(bg);
+                            sdf.stroke(border, self.border_size);
+                            return sdf.result;
+                        }
+                    }
+
+                    monitor_group = <View> {
+                        width: Fit, height: Fit
+                        flow: Right
+                        spacing: 8
+                        align: {y: 0.5}
+
+                        monitor_toggle_btn = <MonitorButton> {}
+
+                        monitor_latency = <Label> {
+                            visible: false
+                            draw_text: {
+                                instance dark_mode: 0.0
+                                text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                                fn get_color(self) -> vec4 {
+                                    return mix((GRAY_500), (TEXT_SECONDARY_DARK), self.dark_mode);
+                                }
+                            }
+                            text: ""
+                        }
+                    }
+                }
+
                 // Audio buffer indicator container
                 buffer_container = <RoundedView> {
                     width: Fit, height: Fit
@@ -622,6 +917,8 @@ live_design! {
                 device_container = <RoundedView> {
                     width: Fill, height: Fit
                     padding: (PANEL_PADDING)
+                    flow: Down
+                    spacing: 6
                     show_bg: true
                     draw_bg: {
                         instance dark_mode: 0.0
@@ -663,75 +960,9 @@ live_design! {
                                 }
                             }
 
-                            input_device_dropdown = <DropDown> {
-                                width: Fill, height: Fit
-                                padding: {left: 10, right: 10, top: 6, bottom: 6}
-                                popup_menu_position: BelowInput
+                            input_device_dropdown = <SearchableDropDown> {
                                 // Labels will be set at runtime by init_audio()
-                                labels: []
-                                values: []
-                                selected_item: 0
-                                draw_bg: {
-                                    instance dark_mode: 0.0
-                                    fn pixel(self) -> vec4 {
-                                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                                        sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 3.0);
-                                        let bg = mix((SLATE_100), (SLATE_700), self.dark_mode);
-                                        sdf.fill(bg);
-                                        return sdf.result;
-                                    }
-                                }
-                                draw_text: {
-                                    instance dark_mode: 0.0
-                                    text_style: <FONT_REGULAR>{ font_size: 11.0 }
-                                    fn get_color(self) -> vec4 {
-                                        let light = mix((SLATE_500), (TEXT_PRIMARY), self.focus);
-                                        let dark = mix((SLATE_300), (TEXT_PRIMARY_DARK), self.focus);
-                                        return mix(light, dark, self.dark_mode);
-                                    }
-                                }
-                                popup_menu: {
-                                    width: 250
-                                    draw_bg: {
-                                        instance dark_mode: 0.0
-                                        border_size: 1.0
-                                        fn pixel(self) -> vec4 {
-                                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                                            sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 2.0);
-                                            let bg = mix((WHITE), (SLATE_800), self.dark_mode);
-                                            let border = mix((BORDER), (SLATE_600), self.dark_mode);
-                                            sdf.fill(bg);
-                                            sdf.stroke(border, self.border_size);
-                                            return sdf.result;
-                                        }
-                                    }
-                                    menu_item: {
-                                        width: Fill
-                                        draw_bg: {
-                                            instance dark_mode: 0.0
-                                            fn pixel(self) -> vec4 {
-                                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                                                sdf.rect(0., 0., self.rect_size.x, self.rect_size.y);
-                                                let base = mix((WHITE), (SLATE_800), self.dark_mode);
-                                                let hover_color = mix((GRAY_100), (SLATE_700), self.dark_mode);
-                                                sdf.fill(mix(base, hover_color, self.hover));
-                                                return sdf.result;
-                                            }
-                                        }
-                                        draw_text: {
-                                            instance dark_mode: 0.0
-                                            fn get_color(self) -> vec4 {
-                                                let light_base = mix((GRAY_700), (TEXT_PRIMARY), self.active);
-                                                let dark_base = mix((SLATE_300), (TEXT_PRIMARY_DARK), self.active);
-                                                let base = mix(light_base, dark_base, self.dark_mode);
-                                                let light_hover = (TEXT_PRIMARY);
-                                                let dark_hover = (TEXT_PRIMARY_DARK);
-                                                let hover_color = mix(light_hover, dark_hover, self.dark_mode);
-                                                return mix(base, hover_color, self.hover);
-                                            }
-                                        }
-                                    }
-                                }
+                                empty_text: "Search microphones"
                             }
                         }
 
@@ -756,78 +987,27 @@ live_design! {
                                 }
                             }
 
-                            output_device_dropdown = <DropDown> {
-                                width: Fill, height: Fit
-                                padding: {left: 10, right: 10, top: 6, bottom: 6}
-                                popup_menu_position: BelowInput
+                            output_device_dropdown = <SearchableDropDown> {
                                 // Labels will be set at runtime by init_audio()
-                                labels: []
-                                values: []
-                                selected_item: 0
-                                draw_bg: {
-                                    instance dark_mode: 0.0
-                                    fn pixel(self) -> vec4 {
-                                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                                        sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 3.0);
-                                        let bg = mix((SLATE_100), (SLATE_700), self.dark_mode);
-                                        sdf.fill(bg);
-                                        return sdf.result;
-                                    }
-                                }
-                                draw_text: {
-                                    instance dark_mode: 0.0
-                                    text_style: <FONT_REGULAR>{ font_size: 11.0 }
-                                    fn get_color(self) -> vec4 {
-                                        let light = mix((SLATE_500), (TEXT_PRIMARY), self.focus);
-                                        let dark = mix((SLATE_300), (TEXT_PRIMARY_DARK), self.focus);
-                                        return mix(light, dark, self.dark_mode);
-                                    }
-                                }
-                                popup_menu: {
-                                    width: 250
-                                    draw_bg: {
-                                        instance dark_mode: 0.0
-                                        border_size: 1.0
-                                        fn pixel(self) -> vec4 {
-                                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                                            sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 2.0);
-                                            let bg = mix((WHITE), (SLATE_800), self.dark_mode);
-                                            let border = mix((BORDER), (SLATE_600), self.dark_mode);
-                                            sdf.fill(bg);
-                                            sdf.stroke(border, self.border_size);
-                                            return sdf.result;
-                                        }
-                                    }
-                                    menu_item: {
-                                        width: Fill
-                                        draw_bg: {
-                                            instance dark_mode: 0.0
-                                            fn pixel(self) -> vec4 {
-                                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                                                sdf.rect(0., 0., self.rect_size.x, self.rect_size.y);
-                                                let base = mix((WHITE), (SLATE_800), self.dark_mode);
-                                                let hover_color = mix((GRAY_100), (SLATE_700), self.dark_mode);
-                                                sdf.fill(mix(base, hover_color, self.hover));
-                                                return sdf.result;
-                                            }
-                                        }
-                                        draw_text: {
-                                            instance dark_mode: 0.0
-                                            fn get_color(self) -> vec4 {
-                                                let light_base = mix((GRAY_700), (TEXT_PRIMARY), self.active);
-                                                let dark_base = mix((SLATE_300), (TEXT_PRIMARY_DARK), self.active);
-                                                let base = mix(light_base, dark_base, self.dark_mode);
-                                                let light_hover = (TEXT_PRIMARY);
-                                                let dark_hover = (TEXT_PRIMARY_DARK);
-                                                let hover_color = mix(light_hover, dark_hover, self.dark_mode);
-                                                return mix(base, hover_color, self.hover);
-                                            }
-                                        }
-                                    }
-                                }
+                                empty_text: "Search outputs"
                             }
                         }
                     }
+
+                    // Capabilities of the selected devices, set by update_device_caps()
+                    device_caps = <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 16
+
+                        input_device_caps = <DeviceCapsLabel> {}
+                        output_device_caps = <DeviceCapsLabel> {}
+                    }
+                }
+
+                // Opens the audio test wizard
+                audio_test_btn = <LogActionButton> {
+                    text: "Test audio"
                 }
             }
 
@@ -878,7 +1058,13 @@ live_design! {
                                 }
                             }
                             draw_selection: {
-                                color: (INDIGO_200)
+                                instance dark_mode: 0.0
+                                fn pixel(self) -> vec4 {
+                                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                    sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 1.0);
+                                    sdf.fill(mix((INDIGO_200), vec4(0.26, 0.52, 0.96, 0.4), self.dark_mode));
+                                    return sdf.result;
+                                }
                             }
                             draw_cursor: {
                                 color: (ACCENT_BLUE)
@@ -2914,49 +3100,64 @@ live_design! {
                             width: 70, height: 24
                             popup_menu_position: BelowInput
                             draw_bg: {
-                                color: (HOVER_BG)
-                                border_color: (SLATE_200)
+                                instance dark_mode: 0.0
                                 border_radius: 2.0
                                 fn pixel(self) -> vec4 {
                                     let sdf = Sdf2d::viewport(self.pos * self.rect_size);
                                     // Background
                                     sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 2.0);
-                                    sdf.fill((HOVER_BG));
+                                    sdf.fill(mix((HOVER_BG), (SLATE_700), self.dark_mode));
                                     // Down arrow on right side
                                     let ax = self.rect_size.x - 12.0;
                                     let ay = self.rect_size.y * 0.5 - 2.0;
                                     sdf.move_to(ax - 3.0, ay);
                                     sdf.line_to(ax, ay + 4.0);
                                     sdf.line_to(ax + 3.0, ay);
-                                    sdf.stroke((TEXT_PRIMARY), 1.5);
+                                    sdf.stroke(mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode), 1.5);
                                     return sdf.result;
                                 }
                             }
                             draw_text: {
+                                instance dark_mode: 0.0
                                 text_style: <FONT_MEDIUM>{ font_size: 10.0 }
                                 fn get_color(self) -> vec4 {
-                                    return (TEXT_PRIMARY);
+                                    return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
                                 }
                             }
                             popup_menu: {
                                 draw_bg: {
-                                    color: (WHITE)
-                                    border_color: (BORDER)
+                                    instance dark_mode: 0.0
                                     border_size: 1.0
-                                    border_radius: 2.0
+                                    fn pixel(self) -> vec4 {
+                                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                        sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 2.0);
+                                        let bg = mix((WHITE), (SLATE_800), self.dark_mode);
+                                        let border = mix((BORDER), (SLATE_600), self.dark_mode);
+                                        sdf.fill(bg);
+                                        sdf.stroke(border, self.border_size);
+                                        return sdf.result;
+                                    }
                                 }
                                 menu_item: {
                                     draw_bg: {
-                                        color: (WHITE)
-                                        color_hover: (GRAY_100)
+                                        instance dark_mode: 0.0
+                                        fn pixel(self) -> vec4 {
+                                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                            sdf.rect(0., 0., self.rect_size.x, self.rect_size.y);
+                                            let base = mix((WHITE), (SLATE_800), self.dark_mode);
+                                            let hover_color = mix((GRAY_100), (SLATE_700), self.dark_mode);
+                                            sdf.fill(mix(base, hover_color, self.hover));
+                                            return sdf.result;
+                                        }
                                     }
                                     draw_text: {
+                                        instance dark_mode: 0.0
                                         fn get_color(self) -> vec4 {
-                                            return mix(
-                                                mix((GRAY_700), (TEXT_PRIMARY), self.active),
-                                                (TEXT_PRIMARY),
-                                                self.hover
-                                            );
+                                            let light_base = mix((GRAY_700), (TEXT_PRIMARY), self.active);
+                                            let dark_base = mix((SLATE_300), (TEXT_PRIMARY_DARK), self.active);
+                                            let base = mix(light_base, dark_base, self.dark_mode);
+                                            let hover_color = mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                                            return mix(base, hover_color, self.hover);
                                         }
                                     }
                                 }
@@ -2970,49 +3171,64 @@ live_design! {
                             width: 85, height: 24
                             popup_menu_position: BelowInput
                             draw_bg: {
-                                color: (HOVER_BG)
-                                border_color: (SLATE_200)
+                                instance dark_mode: 0.0
                                 border_radius: 2.0
                                 fn pixel(self) -> vec4 {
                                     let sdf = Sdf2d::viewport(self.pos * self.rect_size);
                                     // Background
                                     sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 2.0);
-                                    sdf.fill((HOVER_BG));
+                                    sdf.fill(mix((HOVER_BG), (SLATE_700), self.dark_mode));
                                     // Down arrow on right side
                                     let ax = self.rect_size.x - 12.0;
                                     let ay = self.rect_size.y * 0.5 - 2.0;
                                     sdf.move_to(ax - 3.0, ay);
                                     sdf.line_to(ax, ay + 4.0);
                                     sdf.line_to(ax + 3.0, ay);
-                                    sdf.stroke((TEXT_PRIMARY), 1.5);
+                                    sdf.stroke(mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode), 1.5);
                                     return sdf.result;
                                 }
                             }
                             draw_text: {
+                                instance dark_mode: 0.0
                                 text_style: <FONT_MEDIUM>{ font_size: 10.0 }
                                 fn get_color(self) -> vec4 {
-                                    return (TEXT_PRIMARY);
+                                    return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
                                 }
                             }
                             popup_menu: {
                                 draw_bg: {
-                                    color: (WHITE)
-                                    border_color: (BORDER)
+                                    instance dark_mode: 0.0
                                     border_size: 1.0
-                                    border_radius: 2.0
+                                    fn pixel(self) -> vec4 {
+                                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                        sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 2.0);
+                                        let bg = mix((WHITE), (SLATE_800), self.dark_mode);
+                                        let border = mix((BORDER), (SLATE_600), self.dark_mode);
+                                        sdf.fill(bg);
+                                        sdf.stroke(border, self.border_size);
+                                        return sdf.result;
+                                    }
                                 }
                                 menu_item: {
                                     draw_bg: {
-                                        color: (WHITE)
-                                        color_hover: (GRAY_100)
+                                        instance dark_mode: 0.0
+                                        fn pixel(self) -> vec4 {
+                                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                            sdf.rect(0., 0., self.rect_size.x, self.rect_size.y);
+                                            let base = mix((WHITE), (SLATE_800), self.dark_mode);
+                                            let hover_color = mix((GRAY_100), (SLATE_700), self.dark_mode);
+                                            sdf.fill(mix(base, hover_color, self.hover));
+                                            return sdf.result;
+                                        }
                                     }
                                     draw_text: {
+                                        instance dark_mode: 0.0
                                         fn get_color(self) -> vec4 {
-                                            return mix(
-                                                mix((GRAY_700), (TEXT_PRIMARY), self.active),
-                                                (TEXT_PRIMARY),
-                                                self.hover
-                                            );
+                                            let light_base = mix((GRAY_700), (TEXT_PRIMARY), self.active);
+                                            let dark_base = mix((SLATE_300), (TEXT_PRIMARY_DARK), self.active);
+                                            let base = mix(light_base, dark_base, self.dark_mode);
+                                            let hover_color = mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                                            return mix(base, hover_color, self.hover);
                                         }
                                     }
                                 }
@@ -3065,7 +3281,13 @@ live_design! {
                                 }
                             }
                             draw_selection: {
-                                color: (INDIGO_200)
+                                instance dark_mode: 0.0
+                                fn pixel(self) -> vec4 {
+                                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                    sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 1.0);
+                                    sdf.fill(mix((INDIGO_200), vec4(0.26, 0.52, 0.96, 0.4), self.dark_mode));
+                                    return sdf.result;
+                                }
                             }
                             draw_cursor: {
                                 color: (ACCENT_BLUE)
@@ -3132,34 +3354,220 @@ live_design! {
                             }
                         }
                     }
-                }
 
-                log_scroll = <ScrollYView> {
-                    width: Fill, height: Fill
-                    flow: Down
-                    scroll_bars: <ScrollBars> {
-                        show_scroll_x: false
-                        show_scroll_y: true
+                    // Action row: marker note + Marker / Clear
+                    log_action_row = <View> {
+                        width: Fill, height: 30
+                        flow: Right
+                        align: {y: 0.5}
+                        padding: {left: 8, right: 8, bottom: 6}
+                        spacing: 6
+
+                        marker_input = <TextInput> {
+                            width: Fill, height: 24
+                            empty_text: "Marker note..."
+                            draw_bg: {
+                                instance dark_mode: 0.0
+                                border_radius: 2.0
+                                fn pixel(self) -> vec4 {
+                                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                    sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.border_radius);
+                                    let bg = mix((WHITE), (SLATE_700), self.dark_mode);
+                                    sdf.fill(bg);
+                                    return sdf.result;
+                                }
+                            }
+                            draw_text: {
+                                instance dark_mode: 0.0
+                                text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                                fn get_color(self) -> vec4 {
+                                    return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                                }
+                            }
+                            draw_selection: {
+                                instance dark_mode: 0.0
+                                fn pixel(self) -> vec4 {
+                                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                    sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 1.0);
+                                    sdf.fill(mix((INDIGO_200), vec4(0.26, 0.52, 0.96, 0.4), self.dark_mode));
+                                    return sdf.result;
+                                }
+                            }
+                            draw_cursor: {
+                                color: (ACCENT_BLUE)
+                            }
+                        }
+
+                        marker_btn = <LogActionButton> {
+                            text: "Marker"
+                        }
+
+                        clear_log_btn = <LogActionButton> {
+                            text: "Clear"
+                        }
                     }
 
-                    log_content_wrapper = <View> {
-                        width: Fill, height: Fit
-                        padding: { left: 12, right: 12, top: 8, bottom: 8 }
-                        flow: Down
+                    // Pin row: Pinned only / pin count / Export pinned
+                    log_pin_row = <View> {
+                        width: Fill, height: 30
+                        flow: Right
+                        align: {y: 0.5}
+                        padding: {left: 8, right: 8, bottom: 6}
+                        spacing: 6
 
-                        // Use Label instead of Markdown for much faster rendering
-                        log_content = <Label> {
-                            width: Fill, height: Fit
+                        pinned_only_btn = <LogToggleButton> {
+                            text: "Pinned only"
+                        }
+
+                        pin_summary = <Label> {
+                            width: Fill
+                            text: "No pinned entries"
                             draw_text: {
                                 instance dark_mode: 0.0
                                 text_style: <FONT_REGULAR>{ font_size: 10.0 }
-                                wrap: Word
                                 fn get_color(self) -> vec4 {
-                                    return mix((GRAY_600), (TEXT_PRIMARY_DARK), self.dark_mode);
+                                    return mix((TEXT_SECONDARY), (TEXT_SECONDARY_DARK), self.dark_mode);
                                 }
                             }
-                            text: ""
                         }
+
+                        export_pinned_btn = <LogActionButton> {
+                            text: "Export pinned"
+                        }
+                    }
+                }
+
+                log_empty = <Label> {
+                    visible: false
+                    padding: {left: 12, top: 8}
+                    text: "No log entries"
+                    draw_text: {
+                        instance dark_mode: 0.0
+                        text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                        fn get_color(self) -> vec4 {
+                            return mix((GRAY_600), (TEXT_PRIMARY_DARK), self.dark_mode);
+                        }
+                    }
+                }
+
+                // One row per line; only the visible rows are drawn
+                log_list = <PortalList> {
+                    width: Fill, height: Fill
+                    flow: Down
+                    auto_tail: true
+                    padding: {top: 6, bottom: 8}
+
+                    log_row = <LogRow> {}
+                }
+            }
+        }
+
+        // Clear-logs confirmation; positioned over the screen when shown
+        clear_log_confirm = <ConfirmDialog> {
+            abs_pos: vec2(0.0, 0.0)
+        }
+
+        // Feedback warning before monitoring through speakers
+        monitor_confirm = <ConfirmDialog> {
+            abs_pos: vec2(0.0, 0.0)
+        }
+
+        // Output tone / mic loop check; positioned over the screen when shown
+        audio_test_wizard = <View> {
+            abs_pos: vec2(0.0, 0.0)
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+
+            // Dimmed background; swallows clicks behind the wizard
+            overlay = <View> {
+                width: Fill, height: Fill
+                show_bg: true
+                draw_bg: {
+                    fn pixel(self) -> vec4 {
+                        return vec4(0.0, 0.0, 0.0, 0.5);
+                    }
+                }
+            }
+
+            dialog_container = <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                dialog = <View> {
+                    width: 420, height: Fit
+                    padding: 20
+                    flow: Down
+                    spacing: 12
+                    show_bg: true
+                    draw_bg: {
+                        instance dark_mode: 0.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 10.0);
+                            sdf.fill(mix((WHITE), (SLATE_800), self.dark_mode));
+                            return sdf.result;
+                        }
+                    }
+
+                    test_title = <Label> {
+                        text: "Test audio"
+                        draw_text: {
+                            instance dark_mode: 0.0
+                            text_style: <FONT_BOLD>{ font_size: 14.0 }
+                            fn get_color(self) -> vec4 {
+                                return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                            }
+                        }
+                    }
+
+                    test_step = <Label> {
+                        text: ""
+                        draw_text: {
+                            instance dark_mode: 0.0
+                            text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+                            fn get_color(self) -> vec4 {
+                                return mix((GRAY_500), (TEXT_SECONDARY_DARK), self.dark_mode);
+                            }
+                        }
+                    }
+
+                    test_message = <Label> {
+                        width: Fill
+                        text: ""
+                        draw_text: {
+                            instance dark_mode: 0.0
+                            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                            wrap: Word
+                            fn get_color(self) -> vec4 {
+                                return mix((TEXT_SECONDARY), (TEXT_SECONDARY_DARK), self.dark_mode);
+                            }
+                        }
+                    }
+
+                    // Live input level while recording, set by poll_audio_test()
+                    test_level_row = <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 8
+                        align: {y: 0.5}
+                        visible: false
+
+                        test_level_meter = <LedMeter> {}
+                    }
+
+                    test_actions = <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 8
+                        align: {y: 0.5}
+                        margin: {top: 4}
+
+                        test_close_btn = <LogActionButton> { text: "Close" }
+                        <View> { width: Fill, height: 1 }
+                        test_retry_btn = <LogActionButton> { text: "Play again" }
+                        test_no_btn = <LogActionButton> { text: "No sound" }
+                        test_yes_btn = <LogActionButton> { text: "I heard it" }
                     }
                 }
             }
@@ -17,6 +17,7 @@ use crate::dora_integration::{DoraIntegration, DoraEvent};
 use mofa_settings::data::Preferences;
 use mofa_ui::{AecButtonWidgetExt, MicButtonWidgetExt, MofaHeroWidgetExt, ConnectionStatus};
 
+use super::log_ingest::DEFAULT_RATE_LIMIT;
 use super::{MoFaFMScreen, ChatMessageEntry};
 
 impl MoFaFMScreen {
@@ -44,6 +45,7 @@ impl MoFaFMScreen {
 
         // Start timer to poll for dora events (100ms interval)
         self.dora_timer = cx.start_interval(0.1);
+        self.track_timers();
 
         // Look for default dataflow relative to current working directory
         // Check multiple possible locations
@@ -179,9 +181,7 @@ impl MoFaFMScreen {
         if let Some(entries) = log_entries {
             // Only process entries we haven't seen yet
             for entry in entries.into_iter().skip(self.processed_dora_log_count) {
-                let level_str = format!("{:?}", entry.level).to_uppercase();
-                let log_line = format!("[{}] [{}] {}", level_str, entry.node_id, entry.message);
-                self.add_log(cx, &log_line);
+                self.ingest_dora_log(cx, entry);
                 self.processed_dora_log_count += 1;
             }
         }
@@ -425,6 +425,8 @@ impl MoFaFMScreen {
         self.last_chat_count = 0;
         self.update_chat_display(cx);
         self.clear_logs(cx);
+        let rate_limit = Preferences::load().log_rate_limit.unwrap_or(DEFAULT_RATE_LIMIT);
+        self.reset_log_ingest(rate_limit);
 
         // Initialize dora if not already done
         self.init_dora(cx);
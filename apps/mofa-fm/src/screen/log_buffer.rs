@@ -0,0 +1,258 @@
+//! System log lines with pins and notes
+//!
+//! Lines keep the number they were pushed under, so repeat counters and
+//! rate-limit markers can rewrite a line in place even after older lines
+//! were evicted. Pinning a line exempts it from eviction (up to
+//! [`MAX_PINNED_LINES`]) and lets the user attach a note; the pinned set
+//! exports as Markdown for bug reports.
+
+use std::collections::VecDeque;
+
+/// Unpinned lines kept in memory (oldest are evicted first)
+pub const MAX_LOG_LINES: usize = 5000;
+
+/// Pinned lines kept on top of [`MAX_LOG_LINES`]
+pub const MAX_PINNED_LINES: usize = 200;
+
+/// One line of the system log
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogLine {
+    /// Position among all lines ever pushed
+    pub number: u64,
+    pub text: String,
+    pub pinned: bool,
+    /// The user's note; only shown and exported while pinned
+    pub note: String,
+}
+
+/// Bounded system log, oldest line first
+#[derive(Debug)]
+pub struct LogBuffer {
+    lines: VecDeque<LogLine>,
+    /// Lines ever pushed; the next line's number
+    pushed: u64,
+    pinned: usize,
+    max_lines: usize,
+    max_pinned: usize,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(MAX_LOG_LINES, MAX_PINNED_LINES)
+    }
+}
+
+impl LogBuffer {
+    pub fn new(max_lines: usize, max_pinned: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            pushed: 0,
+            pinned: 0,
+            max_lines,
+            max_pinned,
+        }
+    }
+
+    /// Append a line, evicting the oldest unpinned line over the limit.
+    /// Returns the line's number for later in-place updates.
+    pub fn push(&mut self, text: String) -> u64 {
+        let number = self.pushed;
+        self.pushed += 1;
+        self.lines.push_back(LogLine { number, text, pinned: false, note: String::new() });
+        self.evict_over_limit();
+        number
+    }
+
+    /// Rewrite line `number`, keeping its pin and note; false once it was
+    /// evicted or cleared
+    pub fn replace(&mut self, number: u64, text: String) -> bool {
+        match self.get_mut(number) {
+            Some(line) => {
+                line.text = text;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get(&self, number: u64) -> Option<&LogLine> {
+        let index = self.index_of(number)?;
+        self.lines.get(index)
+    }
+
+    fn get_mut(&mut self, number: u64) -> Option<&mut LogLine> {
+        let index = self.index_of(number)?;
+        self.lines.get_mut(index)
+    }
+
+    /// Lines are ordered by number, with gaps where lines were evicted
+    fn index_of(&self, number: u64) -> Option<usize> {
+        self.lines.binary_search_by_key(&number, |line| line.number).ok()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LogLine> {
+        self.lines.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn pinned_count(&self) -> usize {
+        self.pinned
+    }
+
+    /// Pin or unpin a line. Returns false if the line is gone or
+    /// [`MAX_PINNED_LINES`] are already pinned.
+    pub fn set_pinned(&mut self, number: u64, pinned: bool) -> bool {
+        let at_cap = self.pinned >= self.max_pinned;
+        let Some(line) = self.get_mut(number) else {
+            return false;
+        };
+        if line.pinned == pinned {
+            return true;
+        }
+        if pinned && at_cap {
+            return false;
+        }
+        line.pinned = pinned;
+        if pinned {
+            self.pinned += 1;
+        } else {
+            self.pinned -= 1;
+            // The line is evictable again
+            self.evict_over_limit();
+        }
+        true
+    }
+
+    /// Drop the oldest unpinned lines beyond the limit
+    fn evict_over_limit(&mut self) {
+        while self.lines.len() - self.pinned > self.max_lines {
+            match self.lines.iter().position(|line| !line.pinned) {
+                Some(oldest) => {
+                    self.lines.remove(oldest);
+                }
+                None => break,
+            }
+        }
+    }
+
+    pub fn set_note(&mut self, number: u64, note: &str) -> bool {
+        match self.get_mut(number) {
+            Some(line) => {
+                line.note = note.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove every line, pinned ones included; numbering continues
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.pinned = 0;
+    }
+
+    /// Pinned lines as Markdown, each followed by its note; `None` when
+    /// nothing is pinned
+    pub fn export_pinned(&self) -> Option<String> {
+        if self.pinned == 0 {
+            return None;
+        }
+        let mut markdown = format!("## Pinned log entries ({})\n", self.pinned);
+        for line in self.lines.iter().filter(|line| line.pinned) {
+            let fence = code_fence(&line.text);
+            markdown.push_str(&format!("\n{}text\n{}\n{}\n", fence, line.text, fence));
+            let note = line.note.trim();
+            if !note.is_empty() {
+                for note_line in note.lines() {
+                    markdown.push_str(&format!("\n> {}", note_line));
+                }
+                markdown.push('\n');
+            }
+        }
+        Some(markdown)
+    }
+}
+
+/// A backtick fence longer than any backtick run in `text`
+fn code_fence(text: &str) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_lines_survive_eviction() {
+        let mut buffer = LogBuffer::new(3, 1);
+        let first = buffer.push("first".to_string());
+        assert!(buffer.set_pinned(first, true));
+        for i in 0..5 {
+            buffer.push(format!("line {}", i));
+        }
+
+        let texts: Vec<&str> = buffer.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(texts, ["first", "line 2", "line 3", "line 4"]);
+        // The cap keeps a second pin from growing the buffer
+        let second = buffer.iter().nth(1).unwrap().number;
+        assert!(!buffer.set_pinned(second, true));
+
+        // Unpinning makes the line the oldest evictable one again
+        assert!(buffer.set_pinned(first, false));
+        assert_eq!(buffer.len(), 3);
+        assert!(buffer.get(first).is_none());
+        assert!(!buffer.replace(first, "gone".to_string()));
+    }
+
+    #[test]
+    fn test_replace_keeps_pin_and_note() {
+        let mut buffer = LogBuffer::default();
+        buffer.push("[INFO] [App] ready".to_string());
+        let run = buffer.push("[WARN] [tts] reconnecting".to_string());
+        buffer.set_pinned(run, true);
+        buffer.set_note(run, "starts after the swap");
+
+        assert!(buffer.replace(run, "[WARN] [tts] reconnecting ×12".to_string()));
+        let line = buffer.get(run).unwrap();
+        assert!(line.pinned);
+        assert_eq!(line.note, "starts after the swap");
+        assert_eq!(buffer.pinned_count(), 1);
+
+        buffer.clear();
+        assert_eq!(buffer.pinned_count(), 0);
+        assert_eq!(buffer.push("after clear".to_string()), run + 1);
+    }
+
+    #[test]
+    fn test_export_pinned_interleaves_notes() {
+        let mut buffer = LogBuffer::default();
+        assert_eq!(buffer.export_pinned(), None);
+
+        let a = buffer.push("[ERROR] [asr] no input".to_string());
+        buffer.push("[INFO] [App] unrelated".to_string());
+        let b = buffer.push("[WARN] [llm] uses ```fences```".to_string());
+        buffer.set_pinned(a, true);
+        buffer.set_pinned(b, true);
+        buffer.set_note(a, "mic unplugged\nreplugged at 12:01");
+
+        assert_eq!(
+            buffer.export_pinned().unwrap(),
+            "## Pinned log entries (2)\n\
+             \n```text\n[ERROR] [asr] no input\n```\n\
+             \n> mic unplugged\n> replugged at 12:01\n\
+             \n````text\n[WARN] [llm] uses ```fences```\n````\n"
+        );
+    }
+}
@@ -0,0 +1,298 @@
+//! Dora log ingestion - collapsing repeats and per-node rate limiting
+//!
+//! A misbehaving node can log the same "reconnecting" line thousands of
+//! times a minute. Before entries reach the system log:
+//! - identical entries (level, node, message) following each other from the
+//!   same node within [`COLLAPSE_WINDOW_MS`] become one line whose "×N"
+//!   counter is updated in place
+//! - each node may add at most `rate_limit` new lines per second; the rest
+//!   are dropped and counted on a single "rate-limited" marker line
+//!
+//! Counters are part of the formatted line, so copied logs include them.
+
+use mofa_dora_bridge::data::{LogEntry, LogLevel};
+use std::collections::HashMap;
+
+/// Gap after which an identical entry starts a new line
+pub const COLLAPSE_WINDOW_MS: u64 = 2000;
+
+/// New lines per node and second when not configured
+pub const DEFAULT_RATE_LIMIT: u32 = 50;
+
+/// Length of a rate limit window
+const RATE_WINDOW_MS: u64 = 1000;
+
+/// Metadata key holding how often a collapsed entry was seen
+const REPEAT_KEY: &str = "repeat";
+
+/// Metadata key holding how many lines a marker stands for
+const DROPPED_KEY: &str = "dropped";
+
+/// Change to the system log caused by one entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogUpdate {
+    /// Add a line; it is the node's current run
+    Append(LogEntry),
+    /// Rewrite the node's current run with its new counter
+    UpdateRun(LogEntry),
+    /// Add (`first`) or rewrite the node's drop marker
+    Marker { entry: LogEntry, first: bool },
+}
+
+#[derive(Default)]
+struct NodeState {
+    /// Last line added for the node, with its repeat count
+    run: Option<LogEntry>,
+    run_last_seen: u64,
+    window_start: u64,
+    lines_in_window: u32,
+    /// Marker counting the lines dropped since the node was last let through
+    marker: Option<LogEntry>,
+}
+
+/// Collapses and rate-limits entries on their way into the system log
+pub struct LogIngest {
+    /// New lines per node and second, 0 for no limit
+    rate_limit: u32,
+    nodes: HashMap<String, NodeState>,
+}
+
+impl Default for LogIngest {
+    fn default() -> Self {
+        Self::new(DEFAULT_RATE_LIMIT)
+    }
+}
+
+impl LogIngest {
+    pub fn new(rate_limit: u32) -> Self {
+        Self {
+            rate_limit,
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// What `entry` changes in the log
+    pub fn ingest(&mut self, entry: LogEntry) -> LogUpdate {
+        let node = self.nodes.entry(entry.node_id.clone()).or_default();
+        let now = entry.timestamp;
+
+        if let Some(run) = node.run.as_mut() {
+            let same = run.level == entry.level && run.message == entry.message;
+            if same && now.saturating_sub(node.run_last_seen) <= COLLAPSE_WINDOW_MS {
+                let count = repeat_count(run) + 1;
+                run.metadata.insert(REPEAT_KEY.to_string(), count.to_string());
+                node.run_last_seen = now;
+                return LogUpdate::UpdateRun(run.clone());
+            }
+        }
+
+        // Fixed windows; a clock going backwards starts a new one
+        if now >= node.window_start + RATE_WINDOW_MS || now < node.window_start {
+            node.window_start = now;
+            node.lines_in_window = 0;
+        }
+
+        if self.rate_limit > 0 && node.lines_in_window >= self.rate_limit {
+            // Whatever comes after the gap is not a repeat of the run
+            node.run = None;
+            return match node.marker.as_mut() {
+                Some(marker) => {
+                    let dropped = dropped_count(marker) + 1;
+                    set_dropped(marker, dropped);
+                    LogUpdate::Marker { entry: marker.clone(), first: false }
+                }
+                None => {
+                    let mut marker = LogEntry::new(LogLevel::Warning, "", entry.node_id.clone());
+                    marker.timestamp = now;
+                    set_dropped(&mut marker, 1);
+                    node.marker = Some(marker.clone());
+                    LogUpdate::Marker { entry: marker, first: true }
+                }
+            };
+        }
+
+        node.lines_in_window += 1;
+        node.marker = None;
+        node.run = Some(entry.clone());
+        node.run_last_seen = now;
+        LogUpdate::Append(entry)
+    }
+}
+
+/// How often a collapsed entry was seen, 1 when it was not collapsed
+pub fn repeat_count(entry: &LogEntry) -> u64 {
+    entry
+        .metadata
+        .get(REPEAT_KEY)
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(1)
+}
+
+/// How many lines a drop marker stands for, 0 for other entries
+pub fn dropped_count(entry: &LogEntry) -> u64 {
+    entry
+        .metadata
+        .get(DROPPED_KEY)
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0)
+}
+
+fn set_dropped(marker: &mut LogEntry, dropped: u64) {
+    marker.metadata.insert(DROPPED_KEY.to_string(), dropped.to_string());
+    marker.message = format!(
+        "rate-limited, dropped {} line{}",
+        dropped,
+        if dropped == 1 { "" } else { "s" }
+    );
+}
+
+/// The system log line for an entry, with its counter
+pub fn format_line(entry: &LogEntry) -> String {
+    let level = format!("{:?}", entry.level).to_uppercase();
+    let line = format!("[{}] [{}] {}", level, entry.node_id, entry.message);
+    match repeat_count(entry) {
+        1 => line,
+        count => format!("{} ×{}", line, count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(node: &str, message: &str, timestamp: u64) -> LogEntry {
+        let mut entry = LogEntry::new(LogLevel::Warning, message, node);
+        entry.timestamp = timestamp;
+        entry
+    }
+
+    /// Replay entries the way the system log applies the updates
+    fn replay(ingest: &mut LogIngest, entries: Vec<LogEntry>) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        let mut runs: HashMap<String, usize> = HashMap::new();
+        let mut markers: HashMap<String, usize> = HashMap::new();
+        for entry in entries {
+            match ingest.ingest(entry) {
+                LogUpdate::Append(entry) => {
+                    runs.insert(entry.node_id.clone(), lines.len());
+                    lines.push(format_line(&entry));
+                }
+                LogUpdate::UpdateRun(entry) => lines[runs[&entry.node_id]] = format_line(&entry),
+                LogUpdate::Marker { entry, first: true } => {
+                    markers.insert(entry.node_id.clone(), lines.len());
+                    lines.push(format_line(&entry));
+                }
+                LogUpdate::Marker { entry, first: false } => lines[markers[&entry.node_id]] = format_line(&entry),
+            }
+        }
+        lines
+    }
+
+    #[test]
+    fn test_repeats_collapse_in_place() {
+        let mut ingest = LogIngest::default();
+        let reset = "Connection reset by peer - reconnecting";
+        let lines = replay(
+            &mut ingest,
+            vec![
+                entry("asr", reset, 0),
+                entry("asr", reset, 100),
+                entry("tts", "ready", 150),
+                entry("asr", reset, 200),
+                // Same text at another level is a different entry
+                LogEntry { level: LogLevel::Error, ..entry("asr", reset, 300) },
+                entry("asr", reset, 400),
+            ],
+        );
+        assert_eq!(
+            lines,
+            vec![
+                "[WARNING] [asr] Connection reset by peer - reconnecting ×3",
+                "[WARNING] [tts] ready",
+                "[ERROR] [asr] Connection reset by peer - reconnecting",
+                "[WARNING] [asr] Connection reset by peer - reconnecting",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeat_after_window_starts_new_line() {
+        let mut ingest = LogIngest::default();
+        let lines = replay(
+            &mut ingest,
+            vec![
+                entry("llm", "retrying", 0),
+                entry("llm", "retrying", COLLAPSE_WINDOW_MS),
+                entry("llm", "retrying", 2 * COLLAPSE_WINDOW_MS + 1),
+            ],
+        );
+        assert_eq!(lines, vec!["[WARNING] [llm] retrying ×2", "[WARNING] [llm] retrying"]);
+    }
+
+    #[test]
+    fn test_rate_limit_drops_with_one_marker() {
+        let mut ingest = LogIngest::new(3);
+        let mut entries: Vec<LogEntry> = (0..10).map(|i| entry("asr", &format!("chunk {}", i), i * 10)).collect();
+        // Another node is not affected
+        entries.push(entry("tts", "ready", 200));
+        // Next window: let through again, a later flood gets a new marker
+        entries.extend((0..5).map(|i| entry("asr", &format!("late {}", i), 1000 + i)));
+
+        let lines = replay(&mut ingest, entries);
+        assert_eq!(
+            lines,
+            vec![
+                "[WARNING] [asr] chunk 0",
+                "[WARNING] [asr] chunk 1",
+                "[WARNING] [asr] chunk 2",
+                "[WARNING] [asr] rate-limited, dropped 7 lines",
+                "[WARNING] [tts] ready",
+                "[WARNING] [asr] late 0",
+                "[WARNING] [asr] late 1",
+                "[WARNING] [asr] late 2",
+                "[WARNING] [asr] rate-limited, dropped 2 lines",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeats_do_not_count_against_rate_limit() {
+        let mut ingest = LogIngest::new(2);
+        let mut entries: Vec<LogEntry> = (0..100).map(|i| entry("bridge", "buffer low", i)).collect();
+        entries.push(entry("bridge", "buffer ok", 100));
+        entries.push(entry("bridge", "buffer low", 101));
+
+        let lines = replay(&mut ingest, entries);
+        assert_eq!(
+            lines,
+            vec![
+                "[WARNING] [bridge] buffer low ×100",
+                "[WARNING] [bridge] buffer ok",
+                "[WARNING] [bridge] rate-limited, dropped 1 line",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let mut ingest = LogIngest::new(0);
+        let entries = (0..500).map(|i| entry("asr", &format!("line {}", i), 0)).collect();
+        assert_eq!(replay(&mut ingest, entries).len(), 500);
+    }
+
+    #[test]
+    fn test_counters_in_metadata() {
+        let mut ingest = LogIngest::new(1);
+        ingest.ingest(entry("asr", "a", 0));
+        let LogUpdate::UpdateRun(run) = ingest.ingest(entry("asr", "a", 1)) else {
+            panic!("expected a repeat");
+        };
+        assert_eq!(repeat_count(&run), 2);
+
+        let LogUpdate::Marker { entry: marker, first: true } = ingest.ingest(entry("asr", "b", 2)) else {
+            panic!("expected a marker");
+        };
+        assert_eq!((dropped_count(&marker), repeat_count(&marker)), (1, 1));
+        assert_eq!(marker.timestamp, 2);
+    }
+}
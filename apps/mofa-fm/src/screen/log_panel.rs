@@ -1,28 +1,84 @@
 //! Log panel methods for MoFaFMScreen
 //!
-//! Handles log display, filtering, and clipboard operations.
+//! Handles log display, filtering, markers, pins, and clipboard operations.
+//! Dora log entries pass through [`LogIngest`] first, which collapses
+//! repeats and rate-limits noisy nodes; lines live in a [`LogBuffer`].
 //! Optimized for performance with:
 //! - Timestamp-based throttled updates (200ms) to avoid per-entry re-renders
-//! - Plain Label instead of Markdown for faster text rendering
+//! - A PortalList that only draws the rows in view
 //! - Cached filter state to skip unnecessary re-filtering
-//! - Maximum log entry limit to bound memory
+//! - Maximum log line limit to bound memory
 
 use makepad_widgets::*;
+use mofa_dora_bridge::LogEntry;
 use mofa_ui::log_bridge;
 use std::time::{Duration, Instant};
 
-use super::MoFaFMScreen;
-
-/// Maximum number of log entries to keep in memory (oldest entries are pruned)
-const MAX_LOG_ENTRIES: usize = 5000;
+use mofa_ui::apply_text_input_dark_mode;
+use mofa_widgets::confirm_dialog::ConfirmDialogWidgetExt;
 
-/// Maximum number of log entries to display (for performance)
-/// Full history still searchable, but only recent entries rendered
-const MAX_DISPLAY_ENTRIES: usize = 200;
+use super::log_buffer::{LogLine, MAX_PINNED_LINES};
+use super::log_ingest::{format_line, LogIngest, LogUpdate};
+use super::MoFaFMScreen;
 
 /// Throttle interval for log display updates
 const LOG_UPDATE_THROTTLE: Duration = Duration::from_millis(200);
 
+/// Prefix that identifies marker entries (markers bypass all filters)
+const MARKER_PREFIX: &str = "━━━━ MARKER";
+
+/// Whether a log entry is a user-inserted marker
+fn is_marker(entry: &str) -> bool {
+    entry.starts_with(MARKER_PREFIX)
+}
+
+/// Format a marker divider entry with time and optional note
+fn format_marker(time: &str, note: &str) -> String {
+    let note = note.trim();
+    if note.is_empty() {
+        format!("{} {} ━━━━", MARKER_PREFIX, time)
+    } else {
+        format!("{} {} · {} ━━━━", MARKER_PREFIX, time, note)
+    }
+}
+
+/// Check an entry against the level/node/search filters.
+/// `search_text` must already be lowercase. Markers always match.
+fn entry_matches(entry: &str, level_filter: usize, node_filter: usize, search_text: &str) -> bool {
+    if is_marker(entry) {
+        return true;
+    }
+
+    // Level filter: 0=ALL, 1=DEBUG, 2=INFO, 3=WARN, 4=ERROR
+    let level_match = match level_filter {
+        0 => true, // ALL
+        1 => entry.contains("[DEBUG]"),
+        2 => entry.contains("[INFO]"),
+        3 => entry.contains("[WARN]"),
+        4 => entry.contains("[ERROR]"),
+        _ => true,
+    };
+    if !level_match { return false; }
+
+    // Node filter: 0=ALL, 1=ASR, 2=TTS, 3=LLM, 4=Bridge, 5=Monitor, 6=App
+    // Use case-insensitive matching only when needed
+    let node_match = match node_filter {
+        0 => true, // All Nodes
+        1 => entry.contains("[ASR]") || entry.contains("asr") || entry.contains("ASR"),
+        2 => entry.contains("[TTS]") || entry.contains("tts") || entry.contains("TTS"),
+        3 => entry.contains("[LLM]") || entry.contains("llm") || entry.contains("LLM"),
+        4 => entry.contains("[Bridge]") || entry.contains("bridge") || entry.contains("Bridge"),
+        5 => entry.contains("[Monitor]") || entry.contains("monitor") || entry.contains("Monitor"),
+        6 => entry.contains("[App]") || entry.contains("app") || entry.contains("App"),
+        _ => true,
+    };
+    if !node_match { return false; }
+
+    // Search filter - only do lowercase conversion if search is active
+    // (most expensive operation)
+    search_text.is_empty() || entry.to_lowercase().contains(search_text)
+}
+
 impl MoFaFMScreen {
     /// Toggle log panel visibility
     pub(super) fn toggle_log_panel(&mut self, cx: &mut Cx) {
@@ -99,79 +155,33 @@ impl MoFaFMScreen {
     /// Force immediate update of log display (called by filter change or expand)
     fn update_log_display_now(&mut self, cx: &mut Cx) {
         let search_text = self.view.text_input(ids!(log_section.log_content_column.log_header.log_filter_row.log_search)).text().to_lowercase();
-        let level_filter = self.log_level_filter;
-        let node_filter = self.log_node_filter;
 
         // Update filter cache
-        self.log_filter_cache = (level_filter, node_filter, search_text.clone());
-
-        // Filter log entries with optimized matching
-        let filtered_logs: Vec<&str> = self.log_entries.iter()
-            .filter_map(|entry| {
-                // Level filter: 0=ALL, 1=DEBUG, 2=INFO, 3=WARN, 4=ERROR
-                let level_match = match level_filter {
-                    0 => true, // ALL
-                    1 => entry.contains("[DEBUG]"),
-                    2 => entry.contains("[INFO]"),
-                    3 => entry.contains("[WARN]"),
-                    4 => entry.contains("[ERROR]"),
-                    _ => true,
-                };
-                if !level_match { return None; }
-
-                // Node filter: 0=ALL, 1=ASR, 2=TTS, 3=LLM, 4=Bridge, 5=Monitor, 6=App
-                // Use case-insensitive matching only when needed
-                let node_match = match node_filter {
-                    0 => true, // All Nodes
-                    1 => entry.contains("[ASR]") || entry.contains("asr") || entry.contains("ASR"),
-                    2 => entry.contains("[TTS]") || entry.contains("tts") || entry.contains("TTS"),
-                    3 => entry.contains("[LLM]") || entry.contains("llm") || entry.contains("LLM"),
-                    4 => entry.contains("[Bridge]") || entry.contains("bridge") || entry.contains("Bridge"),
-                    5 => entry.contains("[Monitor]") || entry.contains("monitor") || entry.contains("Monitor"),
-                    6 => entry.contains("[App]") || entry.contains("app") || entry.contains("App"),
-                    _ => true,
-                };
-                if !node_match { return None; }
-
-                // Search filter - only do lowercase conversion if search is active
-                if !search_text.is_empty() {
-                    // Use contains with lowercase only for search (most expensive operation)
-                    let entry_lower = entry.to_lowercase();
-                    if !entry_lower.contains(&search_text) {
-                        return None;
-                    }
-                }
+        self.log_filter_cache = (self.log_level_filter, self.log_node_filter, search_text.clone());
 
-                Some(entry.as_str())
-            })
+        // The list draws these lines; text is read from the buffer at draw time
+        self.log_rows = self.filtered_log_lines(&search_text)
+            .map(|line| line.number)
             .collect();
 
-        // Limit display to last MAX_DISPLAY_ENTRIES for performance
-        // (keeps UI responsive while full history remains searchable)
-        let total_filtered = filtered_logs.len();
-        let display_logs: Vec<&str> = if total_filtered > MAX_DISPLAY_ENTRIES {
-            filtered_logs.into_iter().skip(total_filtered - MAX_DISPLAY_ENTRIES).collect()
-        } else {
-            filtered_logs
-        };
-
-        // Build display text (single newlines for plain Label)
-        let log_text = if display_logs.is_empty() {
-            "No log entries".to_string()
-        } else if total_filtered > MAX_DISPLAY_ENTRIES {
-            // Show indicator that older logs are hidden
-            format!("... ({} older entries hidden) ...\n{}",
-                total_filtered - MAX_DISPLAY_ENTRIES,
-                display_logs.join("\n"))
-        } else {
-            display_logs.join("\n")
-        };
-
-        // Update Label display (much faster than Markdown)
-        self.view.label(ids!(log_section.log_content_column.log_scroll.log_content_wrapper.log_content)).set_text(cx, &log_text);
+        let empty = self.view.label(ids!(log_section.log_content_column.log_empty));
+        empty.set_visible(cx, self.log_rows.is_empty());
+        empty.set_text(cx, if self.log_pinned_only { "No pinned entries" } else { "No log entries" });
+        self.update_pin_summary(cx);
         self.view.redraw(cx);
     }
 
+    /// Lines passing the level/node/search filters and the "Pinned only"
+    /// toggle. `search_text` must already be lowercase.
+    fn filtered_log_lines<'a>(&'a self, search_text: &'a str) -> impl Iterator<Item = &'a LogLine> + 'a {
+        let level_filter = self.log_level_filter;
+        let node_filter = self.log_node_filter;
+        let pinned_only = self.log_pinned_only;
+        self.log_buffer.iter()
+            .filter(move |line| !pinned_only || line.pinned)
+            .filter(move |line| entry_matches(&line.text, level_filter, node_filter, search_text))
+    }
+
     /// Update log display based on current filter and search
     /// This is the public API - it marks dirty and schedules throttled update
     pub(super) fn update_log_display(&mut self, cx: &mut Cx) {
@@ -188,46 +198,13 @@ impl MoFaFMScreen {
         }
     }
 
-    /// Copy filtered logs to clipboard
+    /// Copy filtered logs to clipboard (markers included)
     pub(super) fn copy_logs_to_clipboard(&mut self, cx: &mut Cx) {
         let search_text = self.view.text_input(ids!(log_section.log_content_column.log_header.log_filter_row.log_search)).text().to_lowercase();
-        let level_filter = self.log_level_filter;
-        let node_filter = self.log_node_filter;
 
-        // Filter log entries (same logic as update_log_display_now)
-        let filtered_logs: Vec<&str> = self.log_entries.iter()
-            .filter_map(|entry| {
-                let level_match = match level_filter {
-                    0 => true,
-                    1 => entry.contains("[DEBUG]"),
-                    2 => entry.contains("[INFO]"),
-                    3 => entry.contains("[WARN]"),
-                    4 => entry.contains("[ERROR]"),
-                    _ => true,
-                };
-                if !level_match { return None; }
-
-                let node_match = match node_filter {
-                    0 => true,
-                    1 => entry.contains("[ASR]") || entry.contains("asr") || entry.contains("ASR"),
-                    2 => entry.contains("[TTS]") || entry.contains("tts") || entry.contains("TTS"),
-                    3 => entry.contains("[LLM]") || entry.contains("llm") || entry.contains("LLM"),
-                    4 => entry.contains("[Bridge]") || entry.contains("bridge") || entry.contains("Bridge"),
-                    5 => entry.contains("[Monitor]") || entry.contains("monitor") || entry.contains("Monitor"),
-                    6 => entry.contains("[App]") || entry.contains("app") || entry.contains("App"),
-                    _ => true,
-                };
-                if !node_match { return None; }
-
-                if !search_text.is_empty() {
-                    let entry_lower = entry.to_lowercase();
-                    if !entry_lower.contains(&search_text) {
-                        return None;
-                    }
-                }
-
-                Some(entry.as_str())
-            })
+        // Same filter as the display, so copied logs include markers
+        let filtered_logs: Vec<&str> = self.filtered_log_lines(&search_text)
+            .map(|line| line.text.as_str())
             .collect();
 
         let log_text = if filtered_logs.is_empty() {
@@ -254,15 +231,47 @@ impl MoFaFMScreen {
 
     /// Add a log entry (throttled - doesn't immediately update display)
     pub(super) fn add_log(&mut self, cx: &mut Cx, entry: &str) {
-        self.log_entries.push(entry.to_string());
+        self.log_buffer.push(entry.to_string());
+
+        // Mark dirty for throttled update (don't update immediately)
+        self.mark_log_dirty(cx);
+    }
+
+    /// Start collapsing and rate limiting afresh, e.g. for a new dataflow
+    pub(super) fn reset_log_ingest(&mut self, rate_limit: u32) {
+        self.log_ingest = LogIngest::new(rate_limit);
+        self.log_run_lines.clear();
+        self.log_marker_lines.clear();
+    }
 
-        // Prune oldest entries if over limit
-        if self.log_entries.len() > MAX_LOG_ENTRIES {
-            let excess = self.log_entries.len() - MAX_LOG_ENTRIES;
-            self.log_entries.drain(0..excess);
+    /// Add a dora log entry: a repeat or a dropped line updates an existing
+    /// line in place instead of adding one
+    pub(super) fn ingest_dora_log(&mut self, cx: &mut Cx, entry: LogEntry) {
+        match self.log_ingest.ingest(entry) {
+            LogUpdate::Append(entry) => {
+                let number = self.log_buffer.push(format_line(&entry));
+                self.log_run_lines.insert(entry.node_id, number);
+            }
+            LogUpdate::UpdateRun(entry) => {
+                let line = format_line(&entry);
+                let updated = self.log_run_lines.get(&entry.node_id).copied()
+                    .is_some_and(|number| self.log_buffer.replace(number, line.clone()));
+                if !updated {
+                    let number = self.log_buffer.push(line);
+                    self.log_run_lines.insert(entry.node_id, number);
+                }
+            }
+            LogUpdate::Marker { entry, first } => {
+                let line = format_line(&entry);
+                let updated = !first && self.log_marker_lines.get(&entry.node_id).copied()
+                    .is_some_and(|number| self.log_buffer.replace(number, line.clone()));
+                if !updated {
+                    let number = self.log_buffer.push(line);
+                    self.log_marker_lines.insert(entry.node_id, number);
+                }
+            }
         }
 
-        // Mark dirty for throttled update (don't update immediately)
         self.mark_log_dirty(cx);
     }
 
@@ -274,13 +283,7 @@ impl MoFaFMScreen {
         }
 
         for log_msg in logs {
-            self.log_entries.push(log_msg.format());
-        }
-
-        // Prune oldest entries if over limit
-        if self.log_entries.len() > MAX_LOG_ENTRIES {
-            let excess = self.log_entries.len() - MAX_LOG_ENTRIES;
-            self.log_entries.drain(0..excess);
+            self.log_buffer.push(log_msg.format());
         }
 
         // Mark dirty for throttled update (don't update immediately)
@@ -289,9 +292,186 @@ impl MoFaFMScreen {
 
     /// Clear all logs
     pub(super) fn clear_logs(&mut self, cx: &mut Cx) {
-        self.log_entries.clear();
+        self.log_buffer.clear();
         self.log_display_dirty = false;
         // Immediate update for clear (user expects instant feedback)
         self.update_log_display_now(cx);
     }
+
+    /// Insert a highlighted marker divider, using the inline note if any
+    pub(super) fn insert_log_marker(&mut self, cx: &mut Cx) {
+        let note_input = self.view.text_input(ids!(log_section.log_content_column.log_header.log_action_row.marker_input));
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let marker = format_marker(&Self::format_timestamp(now_ms), &note_input.text());
+        note_input.set_text(cx, "");
+
+        self.log_buffer.push(marker);
+
+        // Immediate update so the marker shows right where the user clicked
+        self.log_display_dirty = false;
+        self.last_log_update = Some(Instant::now());
+        self.update_log_display_now(cx);
+    }
+
+    /// Ask for confirmation before clearing the log
+    pub(super) fn request_clear_logs(&mut self, cx: &mut Cx) {
+        if self.log_buffer.is_empty() {
+            return;
+        }
+
+        // The dialog is absolutely positioned; cover the whole screen
+        let rect = self.view.area().rect(cx);
+        let dialog = self.view.confirm_dialog(ids!(clear_log_confirm));
+        dialog.apply_over(cx, live!{
+            abs_pos: (rect.pos)
+            width: (rect.size.x)
+            height: (rect.size.y)
+        });
+        dialog.set_confirm_text(cx, "Clear");
+        dialog.show(
+            cx,
+            live_id!(clear_logs),
+            "Clear system log?",
+            &clear_logs_message(self.log_buffer.len(), self.log_buffer.pinned_count()),
+        );
+    }
+
+    /// Fill the visible log rows from the buffer
+    pub(super) fn draw_log_rows(&mut self, cx: &mut Cx2d, scope: &mut Scope, list: &mut PortalList) {
+        let dark_mode = self.log_dark_mode;
+        list.set_item_range(cx, 0, self.log_rows.len());
+        while let Some(item_id) = list.next_visible_item(cx) {
+            let Some(line) = self.log_rows.get(item_id).and_then(|&number| self.log_buffer.get(number)) else {
+                continue;
+            };
+            let item = list.item(cx, item_id, live_id!(log_row));
+            let pinned = if line.pinned { 1.0 } else { 0.0 };
+            item.apply_over(cx, live!{ draw_bg: { dark_mode: (dark_mode), pinned: (pinned) } });
+            item.view(ids!(line.pin_btn)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode), pinned: (pinned) }
+            });
+            let text = item.label(ids!(line.log_text));
+            text.set_text(cx, &line.text);
+            text.apply_over(cx, live!{ draw_text: { dark_mode: (dark_mode) } });
+
+            let note = item.text_input(ids!(note_input));
+            note.set_visible(cx, line.pinned);
+            if line.pinned {
+                // Rows are reused while scrolling; only overwrite a stale note
+                // so typing keeps its cursor
+                if note.text() != line.note {
+                    note.set_text(cx, &line.note);
+                }
+                apply_text_input_dark_mode(cx, &note, dark_mode);
+            }
+            item.draw_all(cx, scope);
+        }
+    }
+
+    /// Pin clicks and note edits on the log rows
+    pub(super) fn handle_log_row_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        let list = self.view.portal_list(ids!(log_section.log_content_column.log_list));
+        let mut changed = false;
+        for (item_id, item) in list.items_with_actions(actions) {
+            let Some(&number) = self.log_rows.get(item_id) else {
+                continue;
+            };
+            if item.view(ids!(line.pin_btn)).finger_up(actions).is_some() {
+                let pinned = self.log_buffer.get(number).is_some_and(|line| line.pinned);
+                if self.log_buffer.set_pinned(number, !pinned) {
+                    changed = true;
+                } else {
+                    self.view.label(ids!(log_section.log_content_column.log_header.log_pin_row.pin_summary))
+                        .set_text(cx, &format!("Pin limit reached ({})", MAX_PINNED_LINES));
+                }
+            }
+            if let Some(note) = item.text_input(ids!(note_input)).changed(actions) {
+                self.log_buffer.set_note(number, &note);
+            }
+        }
+        if changed {
+            // Unpinning under "Pinned only" removes the row
+            self.update_log_display_now(cx);
+        }
+    }
+
+    /// Show only pinned lines, or everything again
+    pub(super) fn toggle_pinned_only(&mut self, cx: &mut Cx) {
+        self.log_pinned_only = !self.log_pinned_only;
+        let active = if self.log_pinned_only { 1.0 } else { 0.0 };
+        self.view.button(ids!(log_section.log_content_column.log_header.log_pin_row.pinned_only_btn))
+            .apply_over(cx, live!{ draw_bg: { active: (active) }, draw_text: { active: (active) } });
+        self.update_log_display_now(cx);
+    }
+
+    /// Copy the pinned lines and their notes to the clipboard as Markdown
+    pub(super) fn export_pinned(&mut self, cx: &mut Cx) {
+        let Some(markdown) = self.log_buffer.export_pinned() else {
+            return;
+        };
+        cx.copy_to_clipboard(&markdown);
+        self.view.label(ids!(log_section.log_content_column.log_header.log_pin_row.pin_summary))
+            .set_text(cx, &format!("Copied {} pinned as Markdown", self.log_buffer.pinned_count()));
+    }
+
+    fn update_pin_summary(&mut self, cx: &mut Cx) {
+        let summary = match self.log_buffer.pinned_count() {
+            0 => "No pinned entries".to_string(),
+            1 => "1 pinned".to_string(),
+            n => format!("{} pinned", n),
+        };
+        self.view.label(ids!(log_section.log_content_column.log_header.log_pin_row.pin_summary))
+            .set_text(cx, &summary);
+    }
+}
+
+/// Confirmation text for clearing `total` lines, `pinned` of them pinned
+fn clear_logs_message(total: usize, pinned: usize) -> String {
+    match pinned {
+        0 => format!("This removes all {} log entries, including markers.", total),
+        1 => format!("This removes all {} log entries, including markers and 1 pinned entry.", total),
+        _ => format!("This removes all {} log entries, including markers and {} pinned entries.", total, pinned),
+    }
+}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markers_bypass_filters() {
+        let marker = format_marker("12:00:00", "before TTS swap");
+        assert!(is_marker(&marker));
+        assert!(marker.contains("before TTS swap"));
+
+        // ERROR level, TTS node, and an unrelated search all keep the marker
+        assert!(entry_matches(&marker, 4, 2, "no such text"));
+        assert!(!entry_matches("[INFO] [asr] ready", 4, 0, ""));
+    }
+
+    #[test]
+    fn test_marker_without_note() {
+        assert_eq!(format_marker("08:30:05", "  "), "━━━━ MARKER 08:30:05 ━━━━");
+    }
+
+    #[test]
+    fn test_clear_message_mentions_pins() {
+        assert_eq!(clear_logs_message(3, 0), "This removes all 3 log entries, including markers.");
+        assert!(clear_logs_message(3, 1).ends_with("and 1 pinned entry."));
+        assert!(clear_logs_message(3, 2).ends_with("and 2 pinned entries."));
+    }
+
+    #[test]
+    fn test_entry_matches_filters() {
+        let entry = "[WARN] [Bridge] buffer low";
+        assert!(entry_matches(entry, 0, 0, ""));
+        assert!(entry_matches(entry, 3, 4, "buffer"));
+        assert!(!entry_matches(entry, 2, 0, ""));
+        assert!(!entry_matches(entry, 0, 1, ""));
+        assert!(!entry_matches(entry, 0, 0, "missing"));
+    }
 }
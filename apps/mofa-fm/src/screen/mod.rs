@@ -3,26 +3,38 @@
 //! This module is split into sub-modules for better organization:
 //! - `design.rs` - UI layout and styling (live_design! DSL)
 //! - `audio_controls.rs` - Audio device selection, mic monitoring
+//! - `audio_test.rs` - Speaker / microphone test wizard
 //! - `chat_panel.rs` - Chat display, prompt input
-//! - `log_panel.rs` - Log display, filtering
+//! - `log_panel.rs` - Log display, filtering, markers, pins
+//! - `log_buffer.rs` - Bounded log lines with pins and notes
+//! - `log_ingest.rs` - Collapsing and rate limiting of dora log entries
 //! - `dora_handlers.rs` - Dora event handling, dataflow control
 
 mod audio_controls;
+mod audio_test;
 mod chat_panel;
 pub mod design;  // Public for Makepad live_design path resolution
 mod dora_handlers;
+mod log_buffer;
+mod log_ingest;
 mod log_panel;
 mod role_config;
 
 use role_config::{RoleConfig, get_role_config_path, get_yaml_path, read_yaml_voice, VOICE_OPTIONS};
 
 use makepad_widgets::*;
-use mofa_ui::{MofaHeroWidgetExt, MofaHeroAction, AudioManager};
+use mofa_ui::{MofaHeroWidgetExt, MofaHeroAction, AudioManager, AudioDeviceInfo};
+use mofa_ui::{apply_drop_down_dark_mode, apply_text_input_dark_mode};
 use mofa_ui::log_bridge;
 use crate::dora_integration::{DoraIntegration, DoraCommand};
 use mofa_widgets::participant_panel::ParticipantPanelWidgetExt;
-use mofa_widgets::{StateChangeListener, TimerControl};
+use mofa_widgets::confirm_dialog::ConfirmDialogWidgetExt;
+use mofa_widgets::searchable_drop_down::SearchableDropDownWidgetExt;
+use mofa_widgets::activity::ActivityHandle;
+use mofa_widgets::{StateChangeListener, TabLifecycle, TimerControl};
+use mofa_widgets::tab_state::TabState;
 use mofa_ui::{LedMeterWidgetExt, MicButtonWidgetExt, AecButtonWidgetExt};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
@@ -83,21 +95,33 @@ pub struct MoFaFMScreen {
     #[rust]
     audio_initialized: bool,
     #[rust]
-    input_devices: Vec<String>,
+    input_devices: Vec<AudioDeviceInfo>,
     #[rust]
-    output_devices: Vec<String>,
+    output_devices: Vec<AudioDeviceInfo>,
     #[rust]
     log_level_filter: usize,  // 0=ALL, 1=DEBUG, 2=INFO, 3=WARN, 4=ERROR
     #[rust]
     log_node_filter: usize,   // 0=ALL, 1=ASR, 2=TTS, 3=LLM, 4=Bridge, 5=Monitor, 6=App
     #[rust]
-    log_entries: Vec<String>,  // Raw log entries for filtering
+    log_buffer: log_buffer::LogBuffer,  // Raw log lines for filtering
+    #[rust]
+    log_rows: Vec<u64>,  // Line numbers the log list shows, after filtering
+    #[rust]
+    log_pinned_only: bool,
+    #[rust]
+    log_dark_mode: f64,  // Applied to log rows as they are drawn
     #[rust]
     log_display_dirty: bool,   // Flag to track if log display needs update
     #[rust]
     last_log_update: Option<std::time::Instant>,  // Timestamp of last log display update
     #[rust]
     log_filter_cache: (usize, usize, String),  // Cache: (level, node, search) to detect filter changes
+    #[rust]
+    log_ingest: log_ingest::LogIngest,
+    #[rust]
+    log_run_lines: HashMap<String, u64>,     // Node -> line of its current repeat run
+    #[rust]
+    log_marker_lines: HashMap<String, u64>,  // Node -> line of its rate limit marker
 
     // AEC toggle state
     #[rust]
@@ -107,6 +131,9 @@ pub struct MoFaFMScreen {
     // Mic mute state
     #[rust]
     mic_muted: bool,
+    // Audio test wizard; Closed while hidden
+    #[rust]
+    audio_test: audio_test::AudioTest,
 
     // Dora integration
     #[rust]
@@ -115,6 +142,9 @@ pub struct MoFaFMScreen {
     dataflow_path: Option<PathBuf>,
     #[rust]
     dora_timer: Timer,
+    /// Lists the audio and dora timers in the shell's running panel
+    #[rust]
+    timer_activity: Option<ActivityHandle>,
     // NextFrame-based animation for copy buttons (smooth fade instead of timer reset)
     #[rust]
     copy_chat_flash_active: bool,
@@ -196,6 +226,10 @@ pub struct MoFaFMScreen {
     save_animation_timer: Timer,
     #[rust]
     save_animation_role: Option<String>,
+
+    // Chat/log scroll offsets and input focus kept while the tab is hidden
+    #[rust]
+    tab_state: TabState,
 }
 
 impl Widget for MoFaFMScreen {
@@ -259,6 +293,11 @@ impl Widget for MoFaFMScreen {
             }
         }
 
+        // Stop requests from the shell's running panel
+        if self.timer_activity.as_ref().is_some_and(|a| a.stop_requested()) {
+            self.stop_polling(cx);
+        }
+
         // Handle audio timer for mic level updates, log polling, and buffer status
         if self.audio_timer.is_event(event).is_some() {
             // Debug: log timer firing
@@ -270,6 +309,8 @@ impl Widget for MoFaFMScreen {
                 }
             }
             self.update_mic_level(cx);
+            self.poll_input_monitor(cx);
+            self.poll_audio_test(cx);
             // Poll Rust logs (50ms interval is fine for log updates)
             self.poll_rust_logs(cx);
             // Send actual buffer fill percentage to dora for backpressure control
@@ -483,6 +524,22 @@ impl Widget for MoFaFMScreen {
             }
         }
 
+        // Handle the AGC badge next to the mic meter
+        if self.view.view(ids!(running_tab_content.audio_container.audio_controls_row.mic_container.mic_group.agc_badge)).finger_up(&actions).is_some() {
+            self.toggle_agc(cx);
+        }
+
+        // Handle input monitoring (headphone) toggle
+        if self.view.view(ids!(running_tab_content.audio_container.audio_controls_row.monitor_container.monitor_group.monitor_toggle_btn)).finger_up(&actions).is_some() {
+            self.toggle_input_monitor(cx);
+        }
+        if self.view.confirm_dialog(ids!(monitor_confirm)).confirmed(&actions) == Some(live_id!(monitor_speakers)) {
+            self.start_input_monitor(cx);
+        }
+
+        // Handle the audio test button and wizard
+        self.handle_audio_test_actions(cx, &actions);
+
         // Handle tab clicks
         let running_tab = self.view.view(ids!(left_column.tab_bar.running_tab));
         let settings_tab = self.view.view(ids!(left_column.tab_bar.settings_tab));
@@ -576,18 +633,20 @@ impl Widget for MoFaFMScreen {
         }
 
         // Handle input device dropdown selection
-        if let Some(item) = self.view.drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.input_device_group.input_device_dropdown)).selected(&actions) {
+        if let Some(item) = self.view.searchable_drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.input_device_group.input_device_dropdown)).selected(&actions) {
             if item < self.input_devices.len() {
-                let device_name = self.input_devices[item].clone();
+                let device_name = self.input_devices[item].name.clone();
                 self.select_input_device(cx, &device_name);
+                self.update_device_caps(cx, Some(item), None);
             }
         }
 
         // Handle output device dropdown selection
-        if let Some(item) = self.view.drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.output_device_group.output_device_dropdown)).selected(&actions) {
+        if let Some(item) = self.view.searchable_drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.output_device_group.output_device_dropdown)).selected(&actions) {
             if item < self.output_devices.len() {
-                let device_name = self.output_devices[item].clone();
+                let device_name = self.output_devices[item].name.clone();
                 self.select_output_device(&device_name);
+                self.update_device_caps(cx, None, Some(item));
             }
         }
 
@@ -640,6 +699,30 @@ impl Widget for MoFaFMScreen {
             self.update_log_display(cx);
         }
 
+        // Handle log marker button (or Enter in the marker note field)
+        if self.view.button(ids!(log_section.log_content_column.log_header.log_action_row.marker_btn)).clicked(&actions)
+            || self.view.text_input(ids!(log_section.log_content_column.log_header.log_action_row.marker_input)).returned(&actions).is_some()
+        {
+            self.insert_log_marker(cx);
+        }
+
+        // Handle clear log button - confirmed through the dialog below
+        if self.view.button(ids!(log_section.log_content_column.log_header.log_action_row.clear_log_btn)).clicked(&actions) {
+            self.request_clear_logs(cx);
+        }
+        if self.view.confirm_dialog(ids!(clear_log_confirm)).confirmed(&actions) == Some(live_id!(clear_logs)) {
+            self.clear_logs(cx);
+        }
+
+        // Handle pinned-only toggle, pinned export, and pin/note edits on rows
+        if self.view.button(ids!(log_section.log_content_column.log_header.log_pin_row.pinned_only_btn)).clicked(&actions) {
+            self.toggle_pinned_only(cx);
+        }
+        if self.view.button(ids!(log_section.log_content_column.log_header.log_pin_row.export_pinned_btn)).clicked(&actions) {
+            self.export_pinned(cx);
+        }
+        self.handle_log_row_actions(cx, &actions);
+
         // Handle Send button click
         if self.view.button(ids!(left_column.prompt_container.prompt_section.prompt_row.button_group.send_prompt_btn)).clicked(&actions) {
             self.send_prompt(cx);
@@ -693,7 +776,13 @@ impl Widget for MoFaFMScreen {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
-        self.view.draw_walk(cx, scope, walk)
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                self.draw_log_rows(cx, scope, &mut list);
+            }
+        }
+        self.tab_state.apply_pending(cx, &self.view);
+        DrawStep::done()
     }
 }
 
@@ -1366,6 +1455,27 @@ impl MoFaFMScreenRef {
     pub fn update_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
         self.on_dark_mode_change(cx, dark_mode);
     }
+
+    /// Insert a marker into the system log (same as the Marker button)
+    pub fn insert_log_marker(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.insert_log_marker(cx);
+        }
+    }
+
+    /// Ask to clear the system log (same as the Clear button)
+    pub fn request_clear_logs(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.request_clear_logs(cx);
+        }
+    }
+
+    /// Open the audio test wizard (same as the Test audio button)
+    pub fn open_audio_test(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.open_audio_test(cx);
+        }
+    }
 }
 
 impl TimerControl for MoFaFMScreenRef {
@@ -1373,9 +1483,10 @@ impl TimerControl for MoFaFMScreenRef {
     /// to prevent timer callbacks on inactive state
     /// Note: AEC blink animation is shader-driven and doesn't need stopping
     fn stop_timers(&self, cx: &mut Cx) {
-        if let Some(inner) = self.borrow_mut() {
-            cx.stop_timer(inner.audio_timer);
-            cx.stop_timer(inner.dora_timer);
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.stop_polling(cx);
+            // The wizard is driven by the audio timer; don't leave its streams running
+            inner.close_audio_test(cx);
             ::log::debug!("MoFaFMScreen timers stopped");
         }
     }
@@ -1386,11 +1497,42 @@ impl TimerControl for MoFaFMScreenRef {
         if let Some(mut inner) = self.borrow_mut() {
             inner.audio_timer = cx.start_interval(0.05);  // 50ms for mic level
             inner.dora_timer = cx.start_interval(0.1);    // 100ms for dora events
+            inner.track_timers();
             ::log::debug!("MoFaFMScreen timers started");
         }
     }
 }
 
+/// Scrollers and inputs whose state survives switching to another page
+fn tracked_tab_state() -> TabState {
+    TabState::new()
+        .scroll(ids!(left_column.running_tab_content.chat_container.chat_section.chat_scroll))
+        .scroll(ids!(left_column.settings_tab_content.settings_panel.settings_scroll))
+        .portal_list(ids!(log_section.log_content_column.log_list))
+        .focusable(ids!(left_column.running_tab_content.prompt_container.prompt_section.prompt_row.prompt_input))
+        .focusable(ids!(log_section.log_content_column.log_header.log_filter_row.log_search))
+        .focusable(ids!(log_section.log_content_column.log_header.log_action_row.marker_input))
+}
+
+impl TabLifecycle for MoFaFMScreenRef {
+    fn on_deactivate(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            let inner = &mut *inner;
+            if inner.tab_state.is_empty() {
+                inner.tab_state = tracked_tab_state();
+            }
+            inner.tab_state.save(cx, &inner.view);
+        }
+    }
+
+    fn on_activate(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.tab_state.restore_after_draw();
+            inner.view.redraw(cx);
+        }
+    }
+}
+
 impl StateChangeListener for MoFaFMScreenRef {
     fn on_dark_mode_change(&self, cx: &mut Cx, dark_mode: f64) {
         if let Some(mut inner) = self.borrow_mut() {
@@ -1465,9 +1607,24 @@ impl StateChangeListener for MoFaFMScreenRef {
                 .apply_dark_mode(cx, dark_mode);
             inner.view.led_meter(ids!(running_tab_content.audio_container.audio_controls_row.mic_container.mic_group.mic_level_meter))
                 .apply_dark_mode(cx, dark_mode);
+            inner.view.view(ids!(running_tab_content.audio_container.audio_controls_row.mic_container.mic_group.agc_badge)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+            inner.view.label(ids!(running_tab_content.audio_container.audio_controls_row.mic_container.mic_group.agc_badge.agc_label)).apply_over(cx, live!{
+                draw_text: { dark_mode: (dark_mode) }
+            });
             inner.view.view(ids!(running_tab_content.audio_container.audio_controls_row.aec_container)).apply_over(cx, live!{
                 draw_bg: { dark_mode: (dark_mode) }
             });
+            inner.view.view(ids!(running_tab_content.audio_container.audio_controls_row.monitor_container)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+            inner.view.view(ids!(running_tab_content.audio_container.audio_controls_row.monitor_container.monitor_group.monitor_toggle_btn)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+            inner.view.label(ids!(running_tab_content.audio_container.audio_controls_row.monitor_container.monitor_group.monitor_latency)).apply_over(cx, live!{
+                draw_text: { dark_mode: (dark_mode) }
+            });
             inner.view.view(ids!(running_tab_content.audio_container.audio_controls_row.buffer_container)).apply_over(cx, live!{
                 draw_bg: { dark_mode: (dark_mode) }
             });
@@ -1479,19 +1636,19 @@ impl StateChangeListener for MoFaFMScreenRef {
             });
 
             // Apply dark mode to device dropdowns
-            inner.view.drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.input_device_group.input_device_dropdown)).apply_over(cx, live!{
+            inner.view.searchable_drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.input_device_group.input_device_dropdown)).update_dark_mode(cx, dark_mode);
+            inner.view.searchable_drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.output_device_group.output_device_dropdown)).update_dark_mode(cx, dark_mode);
+            // Apply dark mode to device labels
+            inner.view.label(ids!(running_tab_content.audio_container.device_container.device_selectors.input_device_group.input_device_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
-                draw_bg: { dark_mode: (dark_mode) }
             });
-            inner.view.drop_down(ids!(running_tab_content.audio_container.device_container.device_selectors.output_device_group.output_device_dropdown)).apply_over(cx, live!{
+            inner.view.label(ids!(running_tab_content.audio_container.device_container.device_selectors.output_device_group.output_device_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
-                draw_bg: { dark_mode: (dark_mode) }
             });
-            // Apply dark mode to device labels
-            inner.view.label(ids!(running_tab_content.audio_container.device_container.device_selectors.input_device_group.input_device_label)).apply_over(cx, live!{
+            inner.view.label(ids!(running_tab_content.audio_container.device_container.device_caps.input_device_caps)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
-            inner.view.label(ids!(running_tab_content.audio_container.device_container.device_selectors.output_device_group.output_device_label)).apply_over(cx, live!{
+            inner.view.label(ids!(running_tab_content.audio_container.device_container.device_caps.output_device_caps)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
 
@@ -1507,7 +1664,7 @@ impl StateChangeListener for MoFaFMScreenRef {
             inner.view.view(ids!(left_column.running_tab_content.prompt_container.prompt_section)).apply_over(cx, live!{
                 draw_bg: { dark_mode: (dark_mode) }
             });
-            // NOTE: TextInput apply_over causes "target class not found" errors
+            apply_text_input_dark_mode(cx, &inner.view.text_input(ids!(left_column.running_tab_content.prompt_container.prompt_section.prompt_row.prompt_input)), dark_mode);
             inner.view.button(ids!(left_column.running_tab_content.prompt_container.prompt_section.prompt_row.button_group.reset_btn)).apply_over(cx, live!{
                 draw_bg: { dark_mode: (dark_mode) }
                 draw_text: { dark_mode: (dark_mode) }
@@ -1548,17 +1705,11 @@ impl StateChangeListener for MoFaFMScreenRef {
             inner.view.label(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student1_config.student1_model_row.student1_model_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
-            inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student1_config.student1_model_row.student1_model_dropdown)).apply_over(cx, live!{
-                draw_bg: { dark_mode: (dark_mode) }
-                draw_text: { dark_mode: (dark_mode) }
-            });
+            apply_drop_down_dark_mode(cx, &inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student1_config.student1_model_row.student1_model_dropdown)), dark_mode);
             inner.view.label(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student1_config.student1_voice_row.student1_voice_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
-            inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student1_config.student1_voice_row.student1_voice_dropdown)).apply_over(cx, live!{
-                draw_bg: { dark_mode: (dark_mode) }
-                draw_text: { dark_mode: (dark_mode) }
-            });
+            apply_drop_down_dark_mode(cx, &inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student1_config.student1_voice_row.student1_voice_dropdown)), dark_mode);
             inner.view.label(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student1_config.student1_prompt_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
@@ -1582,17 +1733,11 @@ impl StateChangeListener for MoFaFMScreenRef {
             inner.view.label(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student2_config.student2_model_row.student2_model_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
-            inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student2_config.student2_model_row.student2_model_dropdown)).apply_over(cx, live!{
-                draw_bg: { dark_mode: (dark_mode) }
-                draw_text: { dark_mode: (dark_mode) }
-            });
+            apply_drop_down_dark_mode(cx, &inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student2_config.student2_model_row.student2_model_dropdown)), dark_mode);
             inner.view.label(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student2_config.student2_voice_row.student2_voice_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
-            inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student2_config.student2_voice_row.student2_voice_dropdown)).apply_over(cx, live!{
-                draw_bg: { dark_mode: (dark_mode) }
-                draw_text: { dark_mode: (dark_mode) }
-            });
+            apply_drop_down_dark_mode(cx, &inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student2_config.student2_voice_row.student2_voice_dropdown)), dark_mode);
             inner.view.label(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.student2_config.student2_prompt_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
@@ -1616,17 +1761,11 @@ impl StateChangeListener for MoFaFMScreenRef {
             inner.view.label(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.tutor_config.tutor_model_row.tutor_model_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
-            inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.tutor_config.tutor_model_row.tutor_model_dropdown)).apply_over(cx, live!{
-                draw_bg: { dark_mode: (dark_mode) }
-                draw_text: { dark_mode: (dark_mode) }
-            });
+            apply_drop_down_dark_mode(cx, &inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.tutor_config.tutor_model_row.tutor_model_dropdown)), dark_mode);
             inner.view.label(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.tutor_config.tutor_voice_row.tutor_voice_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
-            inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.tutor_config.tutor_voice_row.tutor_voice_dropdown)).apply_over(cx, live!{
-                draw_bg: { dark_mode: (dark_mode) }
-                draw_text: { dark_mode: (dark_mode) }
-            });
+            apply_drop_down_dark_mode(cx, &inner.view.drop_down(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.tutor_config.tutor_voice_row.tutor_voice_dropdown)), dark_mode);
             inner.view.label(ids!(left_column.settings_tab_content.settings_panel.settings_scroll.settings_content.role_section.tutor_config.tutor_prompt_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
@@ -1699,15 +1838,74 @@ impl StateChangeListener for MoFaFMScreenRef {
                 draw_text: { dark_mode: (dark_mode) }
             });
 
+            // Apply dark mode to log filters and search
+            apply_drop_down_dark_mode(cx, &inner.view.drop_down(ids!(log_section.log_content_column.log_header.log_filter_row.level_filter)), dark_mode);
+            apply_drop_down_dark_mode(cx, &inner.view.drop_down(ids!(log_section.log_content_column.log_header.log_filter_row.node_filter)), dark_mode);
+            apply_text_input_dark_mode(cx, &inner.view.text_input(ids!(log_section.log_content_column.log_header.log_filter_row.log_search)), dark_mode);
+            apply_text_input_dark_mode(cx, &inner.view.text_input(ids!(log_section.log_content_column.log_header.log_action_row.marker_input)), dark_mode);
+
             // Apply dark mode to copy log button
             inner.view.view(ids!(log_section.log_content_column.log_header.log_filter_row.copy_log_btn)).apply_over(cx, live!{
                 draw_bg: { dark_mode: (dark_mode) }
             });
 
-            // Apply dark mode to log content Label
-            inner.view.label(ids!(log_section.log_content_column.log_scroll.log_content_wrapper.log_content)).apply_over(cx, live!{
+            // Apply dark mode to marker / clear buttons and the clear confirmation
+            inner.view.button(ids!(log_section.log_content_column.log_header.log_action_row.marker_btn)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
                 draw_text: { dark_mode: (dark_mode) }
             });
+            inner.view.button(ids!(log_section.log_content_column.log_header.log_action_row.clear_log_btn)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.button(ids!(log_section.log_content_column.log_header.log_pin_row.pinned_only_btn)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.button(ids!(log_section.log_content_column.log_header.log_pin_row.export_pinned_btn)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.confirm_dialog(ids!(clear_log_confirm)).update_dark_mode(cx, dark_mode);
+            inner.view.confirm_dialog(ids!(monitor_confirm)).update_dark_mode(cx, dark_mode);
+
+            // Apply dark mode to the audio test button and wizard
+            inner.view.view(ids!(audio_test_wizard.dialog_container.dialog)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+            for label in [
+                ids!(audio_test_wizard.test_title),
+                ids!(audio_test_wizard.test_step),
+                ids!(audio_test_wizard.test_message),
+            ] {
+                inner.view.label(label).apply_over(cx, live!{
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
+            for button in [
+                ids!(running_tab_content.audio_container.audio_test_btn),
+                ids!(audio_test_wizard.test_close_btn),
+                ids!(audio_test_wizard.test_retry_btn),
+                ids!(audio_test_wizard.test_no_btn),
+                ids!(audio_test_wizard.test_yes_btn),
+            ] {
+                inner.view.button(button).apply_over(cx, live!{
+                    draw_bg: { dark_mode: (dark_mode) }
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
+            inner.view.led_meter(ids!(audio_test_wizard.test_level_row.test_level_meter)).apply_dark_mode(cx, dark_mode);
+
+            // Log rows pick up dark mode as they are drawn
+            inner.log_dark_mode = dark_mode;
+            for label in [
+                ids!(log_section.log_content_column.log_header.log_pin_row.pin_summary),
+                ids!(log_section.log_content_column.log_empty),
+            ] {
+                inner.view.label(label).apply_over(cx, live!{
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
 
             inner.view.redraw(cx);
         }
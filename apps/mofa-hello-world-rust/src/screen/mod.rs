@@ -3,14 +3,13 @@
 //! WebView-based example app with a Rust HTTP server
 
 use makepad_widgets::*;
+use mofa_widgets::activity::{ActivityHandle, ActivityKind, ActivityRegistry};
+use mofa_widgets::local_server::{HttpRequest, HttpResponse, LocalServer};
 use mofa_widgets::webview::{WebViewAction, WebViewContainerWidgetExt};
-use std::net::{TcpListener, TcpStream};
-use std::sync::{mpsc, Arc, Mutex};
-use std::thread;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::path::PathBuf;
 use std::fs;
-use std::io::{BufRead, BufReader, Read, Write};
 use serde_json::json;
 
 live_design! {
@@ -169,6 +168,7 @@ live_design! {
                     webview = <WebViewContainer> {
                         width: Fill, height: Fill
                         url: "about:blank"
+                        profile: "mofa-hello-world-rust"
                     }
                 }
             }
@@ -256,12 +256,6 @@ struct ServerAssets {
     static_root: Option<PathBuf>,
 }
 
-struct HttpRequest {
-    method: String,
-    path: String,
-    body: Vec<u8>,
-}
-
 fn resolve_static_root() -> Option<PathBuf> {
     if let Ok(exe_path) = std::env::current_exe() {
         // Check inside app bundle (macOS): .app/Contents/Resources/apps/mofa-hello-world-rust/static
@@ -312,63 +306,6 @@ fn load_index_html(static_root: Option<&PathBuf>) -> String {
     INDEX_HTML.to_string()
 }
 
-fn read_request(reader: &mut BufReader<TcpStream>) -> std::io::Result<Option<HttpRequest>> {
-    let mut request_line = String::new();
-    if reader.read_line(&mut request_line)? == 0 {
-        return Ok(None);
-    }
-    if request_line.trim().is_empty() {
-        return Ok(None);
-    }
-
-    let mut parts = request_line.trim().split_whitespace();
-    let method = parts.next().unwrap_or("").to_string();
-    let raw_path = parts.next().unwrap_or("/").to_string();
-    let path = raw_path.split('?').next().unwrap_or("/").to_string();
-
-    let mut content_length = 0usize;
-    loop {
-        let mut line = String::new();
-        let n = reader.read_line(&mut line)?;
-        if n == 0 {
-            break;
-        }
-        let trimmed = line.trim_end_matches(&['\r', '\n'][..]);
-        if trimmed.is_empty() {
-            break;
-        }
-        if let Some((key, value)) = trimmed.split_once(':') {
-            if key.eq_ignore_ascii_case("content-length") {
-                content_length = value.trim().parse().unwrap_or(0);
-            }
-        }
-    }
-
-    let mut body = vec![0u8; content_length];
-    if content_length > 0 {
-        reader.read_exact(&mut body)?;
-    }
-
-    Ok(Some(HttpRequest { method, path, body }))
-}
-
-fn write_response(
-    stream: &mut TcpStream,
-    status: &str,
-    content_type: &str,
-    body: &[u8],
-) -> std::io::Result<()> {
-    let header = format!(
-        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n",
-        status,
-        content_type,
-        body.len()
-    );
-    stream.write_all(header.as_bytes())?;
-    stream.write_all(body)?;
-    Ok(())
-}
-
 fn content_type_for_path(path: &str) -> &'static str {
     let ext = path.rsplit('.').next().unwrap_or("");
     match ext {
@@ -396,163 +333,51 @@ fn load_static_file(path: &str, assets: &ServerAssets) -> Option<(Vec<u8>, &'sta
     Some((bytes, content_type_for_path(rel)))
 }
 
-fn handle_connection(mut stream: TcpStream, assets: &ServerAssets) {
-    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
-    let _ = stream.set_write_timeout(Some(Duration::from_secs(5)));
-
-    let mut reader = match stream.try_clone() {
-        Ok(s) => BufReader::new(s),
-        Err(_) => return,
-    };
-
-    let request = match read_request(&mut reader) {
-        Ok(Some(req)) => req,
-        _ => return,
-    };
-
+fn handle_request(request: &HttpRequest, assets: &ServerAssets) -> HttpResponse {
     let method = request.method.as_str();
     let path = request.path.as_str();
 
-    let (status, content_type, body) = match (method, path) {
-        ("GET", "/api/info") => {
-            let body = serde_json::to_vec(&json!({
-                "name": "Hello World Rust Plugin",
-                "version": "1.0.0",
-                "message": "This is a Rust-powered example plugin!"
-            }))
-            .unwrap_or_else(|_| b"{}".to_vec());
-            ("200 OK", "application/json; charset=utf-8", body)
-        }
+    match (method, path) {
+        ("GET", "/api/info") => HttpResponse::json("200 OK", &json!({
+            "name": "Hello World Rust Plugin",
+            "version": "1.0.0",
+            "message": "This is a Rust-powered example plugin!"
+        })),
         ("GET", "/api/time") => {
             let now_ms = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_else(|_| Duration::from_secs(0))
                 .as_millis() as u64;
-            let body = serde_json::to_vec(&json!({ "time": now_ms }))
-                .unwrap_or_else(|_| b"{}".to_vec());
-            ("200 OK", "application/json; charset=utf-8", body)
+            HttpResponse::json("200 OK", &json!({ "time": now_ms }))
         }
         ("POST", "/api/greet") => {
             let name = serde_json::from_slice::<serde_json::Value>(&request.body)
                 .ok()
                 .and_then(|v| v.get("name").and_then(|n| n.as_str()).map(|s| s.to_string()))
                 .unwrap_or_else(|| "World".to_string());
-            let body = serde_json::to_vec(&json!({
+            HttpResponse::json("200 OK", &json!({
                 "greeting": format!("Hello, {}! Welcome to MoFA Studio plugins.", name)
             }))
-            .unwrap_or_else(|_| b"{}".to_vec());
-            ("200 OK", "application/json; charset=utf-8", body)
         }
         ("GET", "/") | ("GET", "/index.html") => {
-            let body = assets.index_html.as_bytes().to_vec();
-            ("200 OK", "text/html; charset=utf-8", body)
-        }
-        ("GET", _) => {
-            if let Some((bytes, ctype)) = load_static_file(path, assets) {
-                ("200 OK", ctype, bytes)
-            } else {
-                let body = b"Not Found".to_vec();
-                ("404 Not Found", "text/plain; charset=utf-8", body)
-            }
-        }
-        _ => {
-            let body = b"Method Not Allowed".to_vec();
-            ("405 Method Not Allowed", "text/plain; charset=utf-8", body)
-        }
-    };
-
-    let _ = write_response(&mut stream, status, content_type, &body);
-}
-
-fn server_loop(listener: TcpListener, shutdown_rx: mpsc::Receiver<()>, assets: Arc<ServerAssets>) {
-    loop {
-        if shutdown_rx.try_recv().is_ok() {
-            break;
-        }
-
-        match listener.accept() {
-            Ok((stream, _)) => {
-                handle_connection(stream, &assets);
-            }
-            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                thread::sleep(Duration::from_millis(30));
-            }
-            Err(_) => break,
-        }
-    }
-}
-
-struct RustServer {
-    handle: Option<thread::JoinHandle<()>>,
-    shutdown: Option<mpsc::Sender<()>>,
-    port: u16,
-}
-
-impl Default for RustServer {
-    fn default() -> Self {
-        Self {
-            handle: None,
-            shutdown: None,
-            port: 0,
+            HttpResponse::new("200 OK", "text/html; charset=utf-8", assets.index_html.as_bytes().to_vec())
         }
+        ("GET", _) => match load_static_file(path, assets) {
+            Some((bytes, ctype)) => HttpResponse::new("200 OK", ctype, bytes),
+            None => HttpResponse::not_found(),
+        },
+        _ => HttpResponse::method_not_allowed(),
     }
 }
 
-impl RustServer {
-    fn is_running(&self) -> bool {
-        self.handle.is_some()
-    }
-
-    fn start(&mut self) -> Result<u16, String> {
-        if self.handle.is_some() {
-            return Ok(self.port);
-        }
-
-        let listener = TcpListener::bind("127.0.0.1:0")
-            .map_err(|e| format!("Failed to bind server: {}", e))?;
-        let port = listener
-            .local_addr()
-            .map_err(|e| format!("Failed to read port: {}", e))?
-            .port();
-        listener
-            .set_nonblocking(true)
-            .map_err(|e| format!("Failed to set non-blocking: {}", e))?;
-
-        let static_root = resolve_static_root();
-        let assets = Arc::new(ServerAssets {
-            index_html: load_index_html(static_root.as_ref()),
-            static_root,
-        });
-
-        let (tx, rx) = mpsc::channel();
-        let handle = thread::spawn(move || server_loop(listener, rx, assets));
-
-        self.shutdown = Some(tx);
-        self.handle = Some(handle);
-        self.port = port;
-
-        Ok(port)
-    }
-
-    fn stop(&mut self) {
-        if let Some(tx) = self.shutdown.take() {
-            let _ = tx.send(());
-        }
-        if let Some(handle) = self.handle.take() {
-            let _ = handle.join();
-        }
-        self.port = 0;
-    }
-
-    fn url(&self) -> String {
-        format!("http://127.0.0.1:{}", self.port)
-    }
-}
-
-impl Drop for RustServer {
-    fn drop(&mut self) {
-        self.stop();
-    }
+/// Start the shared local server with this app's routes and static assets
+fn start_app_server(server: &mut LocalServer) -> Result<u16, String> {
+    let static_root = resolve_static_root();
+    let assets = ServerAssets {
+        index_html: load_index_html(static_root.as_ref()),
+        static_root,
+    };
+    server.start(move |request| handle_request(request, &assets))
 }
 
 #[derive(Live, LiveHook, Widget)]
@@ -561,7 +386,11 @@ pub struct HelloWorldRustScreen {
     view: View,
 
     #[rust]
-    server: Arc<Mutex<RustServer>>,
+    server: Arc<Mutex<LocalServer>>,
+
+    /// Lists the server in the shell's running panel while it runs
+    #[rust]
+    server_activity: Option<ActivityHandle>,
 
     #[rust]
     url_loaded: bool,
@@ -571,6 +400,11 @@ impl Widget for HelloWorldRustScreen {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
 
+        // Stop requests from the shell's running panel
+        if self.server_activity.as_ref().is_some_and(|a| a.stop_requested()) {
+            self.toggle_server(cx);
+        }
+
         let actions = match event {
             Event::Actions(actions) => actions.as_slice(),
             _ => &[],
@@ -616,7 +450,9 @@ impl Widget for HelloWorldRustScreen {
                                 self.set_status(cx, "Connected", 1.0);
                             }
                         }
-                        WebViewAction::IpcMessage { .. } | WebViewAction::None => {}
+                        WebViewAction::IpcMessage { .. }
+                        | WebViewAction::InitTiming { .. }
+                        | WebViewAction::None => {}
                     }
                 }
             }
@@ -639,6 +475,7 @@ impl HelloWorldRustScreen {
             let mut server = self.server.lock().unwrap();
             server.stop();
             drop(server);
+            self.server_activity = None;
             self.set_status(cx, "Server stopped", 0.0);
             self.url_loaded = false;
             self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Start Server");
@@ -647,12 +484,17 @@ impl HelloWorldRustScreen {
 
             let result = {
                 let mut server = self.server.lock().unwrap();
-                server.start()
+                start_app_server(&mut server)
             };
 
             match result {
                 Ok(port) => {
                     ::log::info!("Hello World Rust server started on port {}", port);
+                    let activity = ActivityRegistry::global()
+                        .register(ActivityKind::Server, "mofa-hello-world-rust", "Hello World Rust server")
+                        .stoppable();
+                    activity.set_state(&format!("port {}", port));
+                    self.server_activity = Some(activity);
                     self.set_status(cx, &format!("Server running on port {}", port), 2.0);
                     self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Stop Server");
                     self.load_url(cx);
@@ -804,9 +646,7 @@ impl HelloWorldRustScreenRef {
             );
 
             // Send theme to WebView
-            let webview = inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
-            let js = format!("if(window.setTheme) window.setTheme({});", dark_mode);
-            let _ = webview.eval(&js);
+            inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview)).set_dark_mode(dark_mode);
 
             inner.view.redraw(cx);
         }
@@ -6,6 +6,14 @@ pub mod screen;
 
 use makepad_widgets::*;
 use mofa_widgets::{AppInfo, MofaApp};
+use mofa_widgets::app_storage::AppStorage;
+
+/// Data this app keeps under `~/.mofa-studio`
+pub const STORAGE: AppStorage = AppStorage {
+    prefs: &[screen::CONFIG_FILE],
+    cache: &[],
+    webview_profile: true,
+};
 
 pub struct MoFaHelloWorldApp;
 
@@ -18,6 +26,7 @@ impl MofaApp for MoFaHelloWorldApp {
             tab_id: Some(live_id!(hello_world_tab)),
             page_id: Some(live_id!(hello_world_page)),
             show_in_sidebar: true,
+            storage: STORAGE,
             ..Default::default()
         }
     }
@@ -7,6 +7,8 @@ Features:
 - Create, read, update, delete notes
 - Persist notes to JSON file
 - Search notes by title or content
+- Append quick captures to a daily note
+- Export a note as Markdown through the studio's save dialog
 - Dark mode theme support
 """
 
@@ -92,6 +94,19 @@ class NotesStorage:
             return True
         return False
 
+    def append_to_daily(self, text: str, captured_at: datetime) -> dict:
+        """Append a timestamped line to the note titled with the capture date.
+
+        The daily note is created if it does not exist yet.
+        """
+        title = captured_at.strftime("%Y-%m-%d")
+        line = f"- {captured_at.strftime('%H:%M')} {text.strip()}"
+        note = next((n for n in self.notes.values() if n.get("title") == title), None)
+        if note is None:
+            return self.create(title, line)
+        content = note.get("content", "").rstrip("\n")
+        return self.update(note["id"], content=f"{content}\n{line}" if content else line)
+
     def search(self, query: str) -> list[dict]:
         """Search notes by title or content."""
         query = query.lower()
@@ -159,6 +174,20 @@ class NoteHandler(SimpleHTTPRequestHandler):
             content = data.get("content", "")
             note = storage.create(title, content)
             self._send_json(note, 201)
+        elif path == "/api/daily/append":
+            # Quick capture: {"text": "...", "captured_at": <unix seconds, optional>}
+            data = self._get_json_body()
+            text = (data or {}).get("text", "")
+            if not isinstance(text, str) or not text.strip():
+                self._send_error(400, "Missing text")
+                return
+            try:
+                captured_at = datetime.fromtimestamp(data["captured_at"]) if "captured_at" in data else datetime.now()
+            except (TypeError, ValueError, OverflowError, OSError):
+                self._send_error(400, "Invalid captured_at")
+                return
+            note = storage.append_to_daily(text, captured_at)
+            self._send_json(note)
         else:
             self._send_error(404, "Not found")
 
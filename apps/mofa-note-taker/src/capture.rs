@@ -0,0 +1,254 @@
+//! Quick capture - append a line to today's note from anywhere
+//!
+//! Text typed into the shell's quick-capture window is posted to the Note
+//! Taker backend (`POST /api/daily/append`). Captures that cannot be delivered
+//! (server not running, still booting, request failed) are kept in a queue
+//! file and sent, oldest first, with the next capture or when the server
+//! starts.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Undelivered captures, relative to the app data root
+pub const CAPTURE_QUEUE_FILE: &str = "note-taker/capture-queue.json";
+
+const APPEND_PATH: &str = "/api/daily/append";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long to wait for a freshly started server to answer
+const SERVER_WAIT: Duration = Duration::from_secs(10);
+const SERVER_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Serializes queue file access between worker threads
+static QUEUE_LOCK: Mutex<()> = Mutex::new(());
+
+/// A captured line waiting to be appended
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PendingCapture {
+    pub text: String,
+    /// Unix seconds; the backend files the line under this day and time
+    pub captured_at: u64,
+}
+
+impl PendingCapture {
+    pub fn new(text: &str) -> Self {
+        let captured_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self {
+            text: text.trim().to_string(),
+            captured_at,
+        }
+    }
+}
+
+/// Captures not yet delivered, oldest first
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CaptureQueue {
+    pub entries: Vec<PendingCapture>,
+}
+
+impl CaptureQueue {
+    pub fn path() -> PathBuf {
+        mofa_widgets::app_storage::data_path(CAPTURE_QUEUE_FILE)
+    }
+
+    pub fn load() -> Self {
+        Self::load_from(&Self::path())
+    }
+
+    /// Read the queue; an unreadable file is moved aside to `<name>.bad` so
+    /// its captures are not overwritten by the next save
+    pub fn load_from(path: &Path) -> Self {
+        let Ok(content) = fs::read_to_string(path) else {
+            return Self::default();
+        };
+        match serde_json::from_str(&content) {
+            Ok(queue) => queue,
+            Err(e) => {
+                let mut bad = path.as_os_str().to_owned();
+                bad.push(".bad");
+                let bad = PathBuf::from(bad);
+                match fs::rename(path, &bad) {
+                    Ok(()) => ::log::warn!(
+                        "Capture queue is corrupt ({}), moved it to {} and starting a new one",
+                        e,
+                        bad.display()
+                    ),
+                    Err(rename_err) => ::log::warn!(
+                        "Capture queue is corrupt ({}) and could not be moved aside: {}",
+                        e,
+                        rename_err
+                    ),
+                }
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        self.save_to(&Self::path());
+    }
+
+    /// Write the queue, removing the file once it is empty
+    pub fn save_to(&self, path: &Path) {
+        if self.entries.is_empty() {
+            let _ = fs::remove_file(path);
+            return;
+        }
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => {
+                if let Err(e) = fs::write(path, json) {
+                    ::log::error!("Failed to save capture queue: {}", e);
+                }
+            }
+            Err(e) => ::log::error!("Failed to serialize capture queue: {}", e),
+        }
+    }
+
+    /// Send entries oldest first, stopping at the first failure so order is
+    /// kept. Returns how many were delivered.
+    pub fn flush<F>(&mut self, mut send: F) -> usize
+    where
+        F: FnMut(&PendingCapture) -> Result<(), String>,
+    {
+        let mut sent = 0;
+        for entry in &self.entries {
+            if let Err(e) = send(entry) {
+                ::log::warn!("Quick capture not delivered, keeping it queued: {}", e);
+                break;
+            }
+            sent += 1;
+        }
+        self.entries.drain(..sent);
+        sent
+    }
+}
+
+/// Append one capture through the backend at `base_url`
+pub fn post_capture(base_url: &str, capture: &PendingCapture) -> Result<(), String> {
+    let body = serde_json::to_string(capture).map_err(|e| e.to_string())?;
+    ureq::post(&format!("{}{}", base_url, APPEND_PATH))
+        .timeout(REQUEST_TIMEOUT)
+        .set("Content-Type", "application/json")
+        .send_string(&body)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Wait until the backend answers, for servers that were just started
+fn wait_for_server(base_url: &str) -> bool {
+    let deadline = Instant::now() + SERVER_WAIT;
+    loop {
+        if ureq::get(&format!("{}/api/info", base_url))
+            .timeout(SERVER_POLL_INTERVAL)
+            .call()
+            .is_ok()
+        {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(SERVER_POLL_INTERVAL);
+    }
+}
+
+/// Queue `capture` and try to deliver everything queued
+fn deliver(base_url: Option<&str>, capture: Option<PendingCapture>) {
+    let _guard = QUEUE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut queue = CaptureQueue::load();
+    queue.entries.extend(capture);
+    if queue.entries.is_empty() {
+        return;
+    }
+
+    if let Some(url) = base_url {
+        if wait_for_server(url) {
+            let sent = queue.flush(|entry| post_capture(url, entry));
+            ::log::info!("Delivered {} quick capture(s)", sent);
+        } else {
+            ::log::warn!("Note Taker server at {} did not respond", url);
+        }
+    }
+
+    if !queue.entries.is_empty() {
+        ::log::info!("{} quick capture(s) queued for retry", queue.entries.len());
+    }
+    queue.save();
+}
+
+/// Submit captured text in the background.
+///
+/// `base_url` is the running Note Taker server, or `None` when it could not
+/// be started, in which case the text is only queued.
+pub fn submit(base_url: Option<String>, text: &str) {
+    let capture = PendingCapture::new(text);
+    if capture.text.is_empty() {
+        return;
+    }
+    std::thread::spawn(move || deliver(base_url.as_deref(), Some(capture)));
+}
+
+/// Retry queued captures against a server that just started
+pub fn retry_pending(base_url: String) {
+    if !CaptureQueue::path().exists() {
+        return;
+    }
+    std::thread::spawn(move || deliver(Some(&base_url), None));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capture(text: &str, captured_at: u64) -> PendingCapture {
+        PendingCapture { text: text.to_string(), captured_at }
+    }
+
+    #[test]
+    fn test_flush_keeps_order_after_failure() {
+        let mut queue = CaptureQueue {
+            entries: vec![capture("one", 1), capture("two", 2), capture("three", 3)],
+        };
+
+        let mut attempts = Vec::new();
+        let sent = queue.flush(|entry| {
+            attempts.push(entry.text.clone());
+            if entry.text == "two" { Err("offline".to_string()) } else { Ok(()) }
+        });
+
+        assert_eq!(sent, 1);
+        assert_eq!(attempts, vec!["one", "two"]);
+        assert_eq!(queue.entries, vec![capture("two", 2), capture("three", 3)]);
+    }
+
+    #[test]
+    fn test_queue_round_trip_and_cleanup() {
+        let dir = std::env::temp_dir().join(format!("mofa-capture-queue-{}", std::process::id()));
+        let path = dir.join("queue.json");
+        let _ = fs::remove_dir_all(&dir);
+
+        let queue = CaptureQueue { entries: vec![capture("buy milk", 1_700_000_000)] };
+        queue.save_to(&path);
+        assert_eq!(CaptureQueue::load_from(&path).entries, queue.entries);
+
+        CaptureQueue::default().save_to(&path);
+        assert!(!path.exists());
+        assert!(CaptureQueue::load_from(&path).entries.is_empty());
+
+        // A corrupt file is kept aside, not overwritten by the next save
+        fs::write(&path, "{ not json").unwrap();
+        assert!(CaptureQueue::load_from(&path).entries.is_empty());
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(dir.join("queue.json.bad")).unwrap(), "{ not json");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -2,10 +2,19 @@
 //!
 //! A simple note-taking application with WebView UI
 
+pub mod capture;
 pub mod screen;
 
 use makepad_widgets::*;
 use mofa_widgets::{AppInfo, MofaApp};
+use mofa_widgets::app_storage::AppStorage;
+
+/// Data this app keeps under `~/.mofa-studio`
+pub const STORAGE: AppStorage = AppStorage {
+    prefs: &[screen::CONFIG_FILE],
+    cache: &[capture::CAPTURE_QUEUE_FILE],
+    webview_profile: true,
+};
 
 pub struct MoFaNoteTakerApp;
 
@@ -18,6 +27,7 @@ impl MofaApp for MoFaNoteTakerApp {
             tab_id: Some(live_id!(note_taker_tab)),
             page_id: Some(live_id!(note_taker_page)),
             show_in_sidebar: true,
+            storage: STORAGE,
             ..Default::default()
         }
     }
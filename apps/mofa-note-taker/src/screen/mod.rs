@@ -3,7 +3,9 @@
 //! WebView-based note-taking application
 
 use makepad_widgets::*;
+use mofa_widgets::activity::{ActivityHandle, ActivityKind, ActivityRegistry};
 use mofa_widgets::webview::{WebViewAction, WebViewContainerWidgetExt};
+use mofa_widgets::webview::export::{self, ExportJob, EXPORT_NOTE_CHANNEL};
 use std::net::TcpListener;
 use std::process::{Child, Command, Stdio};
 use std::sync::{Arc, Mutex};
@@ -166,6 +168,7 @@ live_design! {
                     webview = <WebViewContainer> {
                         width: Fill, height: Fill
                         url: "about:blank"
+                        profile: "mofa-note-taker"
                     }
                 }
             }
@@ -293,11 +296,11 @@ fn get_python_path() -> Option<PathBuf> {
     None
 }
 
+/// Preferences file, relative to the MoFA Studio data directory
+pub(crate) const CONFIG_FILE: &str = "note-taker.json";
+
 fn get_config_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".mofa-studio")
-        .join("note-taker.json")
+    mofa_widgets::app_storage::data_path(CONFIG_FILE)
 }
 
 fn find_embedded_python_cmd() -> Option<String> {
@@ -418,14 +421,34 @@ pub struct NoteTakerScreen {
     #[rust]
     server: Arc<Mutex<PythonServer>>,
 
+    /// Lists the server in the shell's running panel while it runs
+    #[rust]
+    server_activity: Option<ActivityHandle>,
+
     #[rust]
     url_loaded: bool,
+
+    /// Note exports still writing, polled on `export_timer`
+    #[rust]
+    exports: Vec<ExportJob>,
+
+    #[rust]
+    export_timer: Timer,
 }
 
 impl Widget for NoteTakerScreen {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
 
+        // Stop requests from the shell's running panel
+        if self.server_activity.as_ref().is_some_and(|a| a.stop_requested()) {
+            self.toggle_server(cx);
+        }
+
+        if self.export_timer.is_event(event).is_some() {
+            self.poll_exports(cx);
+        }
+
         let actions = match event {
             Event::Actions(actions) => actions.as_slice(),
             _ => &[],
@@ -471,7 +494,12 @@ impl Widget for NoteTakerScreen {
                                 self.set_status(cx, "Connected", 1.0);
                             }
                         }
-                        WebViewAction::IpcMessage { .. } | WebViewAction::None => {}
+                        WebViewAction::IpcMessage { channel, data } => {
+                            if channel == EXPORT_NOTE_CHANNEL {
+                                self.handle_export_note(cx, &data);
+                            }
+                        }
+                        WebViewAction::InitTiming { .. } | WebViewAction::None => {}
                     }
                 }
             }
@@ -494,6 +522,7 @@ impl NoteTakerScreen {
             let mut server = self.server.lock().unwrap();
             server.stop();
             drop(server);
+            self.server_activity = None;
             self.set_status(cx, "Server stopped", 0.0);
             self.url_loaded = false;
             self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Start Server");
@@ -508,9 +537,17 @@ impl NoteTakerScreen {
             match result {
                 Ok(port) => {
                     ::log::info!("Note Taker server started on port {}", port);
+                    let activity = ActivityRegistry::global()
+                        .register(ActivityKind::Server, "mofa-note-taker", "Note Taker server")
+                        .stoppable();
+                    activity.set_state(&format!("port {}", port));
+                    self.server_activity = Some(activity);
                     self.set_status(cx, &format!("Server running on port {}", port), 2.0);
                     self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Stop Server");
 
+                    let url = self.server.lock().unwrap().url();
+                    crate::capture::retry_pending(url);
+
                     std::thread::sleep(std::time::Duration::from_millis(1500));
                     self.load_url(cx);
                 }
@@ -542,6 +579,32 @@ impl NoteTakerScreen {
         }
     }
 
+    /// Save a note from the page through the native save dialog
+    fn handle_export_note(&mut self, cx: &mut Cx, data: &str) {
+        let page_url = self.server.lock().unwrap().url();
+        if self.exports.is_empty() {
+            self.export_timer = cx.start_interval(0.2);
+        }
+        self.exports.push(export::handle_export_note(data, &page_url));
+    }
+
+    /// Reply to the page for each finished export
+    fn poll_exports(&mut self, cx: &mut Cx) {
+        let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+        self.exports.retain(|job| match job.try_reply() {
+            Some(reply) => {
+                if let Err(e) = webview.send_to_js(EXPORT_NOTE_CHANNEL, &reply.to_string()) {
+                    ::log::warn!("Failed to send export reply: {}", e);
+                }
+                false
+            }
+            None => true,
+        });
+        if self.exports.is_empty() {
+            cx.stop_timer(self.export_timer);
+        }
+    }
+
     fn go_back(&self) {
         let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
         let _ = webview.go_back();
@@ -583,6 +646,13 @@ impl NoteTakerScreenRef {
         }
     }
 
+    /// Base URL of the backend, if the server is running
+    pub fn server_url(&self) -> Option<String> {
+        let inner = self.borrow()?;
+        let server = inner.server.lock().unwrap();
+        server.is_running().then(|| server.url())
+    }
+
     pub fn stop_server(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() {
             let is_running = {
@@ -661,9 +731,7 @@ impl NoteTakerScreenRef {
             );
 
             // Send theme to WebView
-            let webview = inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
-            let js = format!("if(window.setTheme) window.setTheme({});", dark_mode);
-            let _ = webview.eval(&js);
+            inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview)).set_dark_mode(dark_mode);
 
             inner.view.redraw(cx);
         }
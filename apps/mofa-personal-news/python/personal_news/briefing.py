@@ -0,0 +1,115 @@
+"""Spoken version of the broadcast script.
+
+The editor returns Markdown (see editor/prompt.txt). For read-aloud the
+script is split into its sections at headings and stripped down to plain
+text, so the native app can synthesize one section at a time.
+"""
+
+from __future__ import annotations
+
+import json
+import os
+import re
+import time
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+# Set by the native app so the last briefing survives server restarts
+BRIEFING_FILE_ENV = "PERSONAL_NEWS_BRIEFING_FILE"
+
+_HEADING_RE = re.compile(r"^\s{0,3}#{1,6}\s+(.+?)\s*#*\s*$")
+_BOLD_HEADING_RE = re.compile(r"^\s*\*\*([^*]+)\*\*\s*[:：]?\s*$")
+_SPEAKER_RE = re.compile(r"^[（(]?\s*[男女]?\s*播报员\s*[A-Za-z0-9一二]?\s*[)）]?\s*[:：]\s*")
+_LINK_RE = re.compile(r"!?\[([^\]]*)\]\([^)]*\)")
+_RULE_RE = re.compile(r"^\s*([-*_]\s*){3,}$")
+_TABLE_RULE_RE = re.compile(r"^\s*\|?\s*:?-{3,}.*$")
+
+_last_script: Optional[Dict[str, Any]] = None
+
+
+def remember_script(script: str) -> None:
+    """Keep the latest generated script for /api/briefing."""
+    global _last_script
+    _last_script = {"script": script, "generated_at": int(time.time())}
+    path = _briefing_file()
+    if not path:
+        return
+    try:
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(json.dumps(_last_script, ensure_ascii=False), encoding="utf-8")
+    except OSError as exc:
+        print(f"Failed to save briefing: {exc}", flush=True)
+
+
+def last_script() -> Optional[Dict[str, Any]]:
+    global _last_script
+    if _last_script is None:
+        path = _briefing_file()
+        if path and path.exists():
+            try:
+                _last_script = json.loads(path.read_text(encoding="utf-8"))
+            except (OSError, ValueError):
+                _last_script = None
+    return _last_script
+
+
+def _briefing_file() -> Optional[Path]:
+    value = os.environ.get(BRIEFING_FILE_ENV)
+    return Path(value) if value else None
+
+
+def split_sections(script: str) -> List[Dict[str, str]]:
+    """Split a Markdown script into [{"title", "text"}] at its headings.
+
+    Text before the first heading becomes an untitled section. Sections
+    without any speakable text are dropped.
+    """
+    sections: List[Dict[str, Any]] = []
+    title = ""
+    lines: List[str] = []
+
+    def flush() -> None:
+        text = to_plain_text("\n".join(lines))
+        if text:
+            sections.append({"title": title, "text": text})
+
+    for line in script.splitlines():
+        match = _HEADING_RE.match(line) or _BOLD_HEADING_RE.match(line)
+        if match:
+            flush()
+            title = to_plain_text(match.group(1))
+            lines = []
+        else:
+            lines.append(line)
+    flush()
+    return sections
+
+
+def to_plain_text(markdown: str) -> str:
+    """Remove Markdown markup and speaker labels, keeping one line per paragraph."""
+    out: List[str] = []
+    for raw in markdown.splitlines():
+        line = raw.strip()
+        if not line or _RULE_RE.match(line) or _TABLE_RULE_RE.match(line):
+            continue
+        line = re.sub(r"^#{1,6}\s+", "", line)
+        line = re.sub(r"^>\s?", "", line)
+        line = re.sub(r"^([-*+]|\d+[.)])\s+", "", line)
+        line = _LINK_RE.sub(r"\1", line)
+        line = re.sub(r"(\*\*|__|\*|`|~~)", "", line)
+        line = line.strip("| ").replace(" | ", "，")
+        line = _SPEAKER_RE.sub("", line).strip()
+        if line:
+            out.append(line)
+    return "\n".join(out)
+
+
+def briefing_payload() -> Optional[Dict[str, Any]]:
+    """Sections of the last generated script, or None if there is none."""
+    last = last_script()
+    if not last or not last.get("script"):
+        return None
+    return {
+        "generated_at": last.get("generated_at", 0),
+        "sections": split_sections(last["script"]),
+    }
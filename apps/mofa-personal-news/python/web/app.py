@@ -16,6 +16,7 @@ PROJECT_DIR = BASE_DIR.parent
 sys.path.insert(0, str(PROJECT_DIR))
 
 from personal_news import generate_broadcast  # noqa: E402
+from personal_news.briefing import briefing_payload, remember_script  # noqa: E402
 
 
 def _json_response(handler: SimpleHTTPRequestHandler, status: int, payload: Dict[str, Any]) -> None:
@@ -180,6 +181,13 @@ class NewsRequestHandler(SimpleHTTPRequestHandler):
             content = readme_path.read_text(encoding="utf-8")
             _json_response(self, HTTPStatus.OK, {"content": content})
             return
+        if parsed.path == "/api/briefing":
+            payload = briefing_payload()
+            if payload is None:
+                _json_response(self, HTTPStatus.NOT_FOUND, {"error": "No briefing generated yet"})
+                return
+            _json_response(self, HTTPStatus.OK, payload)
+            return
         if parsed.path == "/":
             self.path = "/index.html"
         return super().do_GET()
@@ -192,6 +200,7 @@ class NewsRequestHandler(SimpleHTTPRequestHandler):
                 config = payload.get("config", {})
                 inputs = payload.get("inputs", [])
                 script = generate_broadcast(config, inputs)
+                remember_script(script)
             except (ValueError, TypeError, json.JSONDecodeError) as exc:
                 _json_response(self, HTTPStatus.BAD_REQUEST, {"error": f"Invalid payload: {exc}"})
                 return
@@ -140,6 +140,7 @@ async function runGenerate() {
       return;
     }
     output.textContent = data.script || "";
+    renderArticles(payload.inputs);
   } catch (error) {
     output.textContent = "JSON 解析失败，请检查输入格式。";
   }
@@ -233,6 +234,197 @@ function loadDefaultTests() {
   testsInput.value = JSON.stringify(sampleTests, null, 2);
 }
 
+// Article list: the host remembers which articles were read and sends
+// j/k/Enter/o as navigation commands.
+const articles = document.querySelector("#articles");
+
+// The host injects __mofa_ipc on its first page only; recreate the bridge
+// after navigating within the site.
+if (!window.__mofa_ipc && window.ipc) {
+  window.__mofa_ipc = {
+    callbacks: {},
+    send(channel, data) {
+      window.ipc.postMessage(JSON.stringify({ channel, data }));
+    },
+    on(channel, callback) {
+      (this.callbacks[channel] = this.callbacks[channel] || []).push(callback);
+    },
+    receive(channel, data) {
+      (this.callbacks[channel] || []).forEach((cb) => cb(data));
+    },
+  };
+}
+const ipc = window.__mofa_ipc;
+
+function articleId(source, item) {
+  const link = item.url || item.link;
+  if (link) {
+    return link;
+  }
+  if (item.id) {
+    return `${source}:${item.id}`;
+  }
+  return `${source}:${item.title || item.text || item.summary || ""}`;
+}
+
+function markRead(elements) {
+  const ids = elements
+    .filter((element) => !element.classList.contains("is-read"))
+    .map((element) => {
+      element.classList.add("is-read");
+      return element.dataset.articleId;
+    });
+  if (ids.length && ipc) {
+    ipc.send("news_read", { ids });
+  }
+}
+
+// An article counts as read once it was fully on screen or, if it is
+// taller than the screen and so never fully visible, once it was scrolled
+// to its end
+function seenToEnd(element) {
+  const rect = element.getBoundingClientRect();
+  const height = window.innerHeight;
+  return rect.bottom <= height + 1 && (rect.top >= -1 || rect.height > height);
+}
+
+const visibleArticles = new Set();
+
+function markSeenArticles() {
+  markRead(Array.from(visibleArticles).filter(seenToEnd));
+}
+
+const readObserver =
+  "IntersectionObserver" in window
+    ? new IntersectionObserver(
+        (entries) => {
+          entries.forEach((entry) => {
+            if (entry.isIntersecting) {
+              visibleArticles.add(entry.target);
+            } else {
+              visibleArticles.delete(entry.target);
+            }
+          });
+          markSeenArticles();
+        },
+        { threshold: [0, 1] },
+      )
+    : null;
+
+// A tall article reaches its end without crossing a threshold, so check
+// the visible ones as the page scrolls
+let seenCheckPending = false;
+window.addEventListener(
+  "scroll",
+  () => {
+    if (seenCheckPending) {
+      return;
+    }
+    seenCheckPending = true;
+    requestAnimationFrame(() => {
+      seenCheckPending = false;
+      markSeenArticles();
+    });
+  },
+  { passive: true },
+);
+
+function renderArticles(inputs) {
+  if (!articles) {
+    return;
+  }
+  articles.innerHTML = "";
+  visibleArticles.clear();
+  (inputs || []).forEach((group) => {
+    (group.items || []).forEach((item) => {
+      const element = document.createElement("li");
+      element.className = "article";
+      element.dataset.articleId = articleId(group.source, item);
+      const link = item.url || item.link;
+      if (link) {
+        element.dataset.link = link;
+      }
+      const title = document.createElement("div");
+      title.className = "article__title";
+      title.textContent = item.title || item.text || item.summary || "";
+      const meta = document.createElement("div");
+      meta.className = "article__meta";
+      meta.textContent = [group.source, item.source_name || item.author].filter(Boolean).join(" · ");
+      const body = document.createElement("div");
+      body.className = "article__body";
+      body.textContent = item.title ? item.summary || item.text || "" : "";
+      element.append(title, meta, body);
+      element.addEventListener("click", () => {
+        setCurrentArticle(element);
+        openArticle(element);
+      });
+      articles.appendChild(element);
+      if (readObserver) {
+        readObserver.observe(element);
+      }
+    });
+  });
+  if (ipc) {
+    ipc.send("news_read_state", {});
+  }
+}
+
+function articleElements() {
+  return articles ? Array.from(articles.querySelectorAll("[data-article-id]")) : [];
+}
+
+function setCurrentArticle(element) {
+  articleElements().forEach((other) => other.classList.toggle("is-current", other === element));
+  element.scrollIntoView({ block: "nearest", behavior: "smooth" });
+}
+
+function openArticle(element) {
+  element.classList.toggle("is-open");
+  markRead([element]);
+}
+
+function onNewsCommand(message) {
+  const elements = articleElements();
+  if (!elements.length) {
+    return;
+  }
+  const index = elements.findIndex((element) => element.classList.contains("is-current"));
+  const current = elements[index];
+  switch (message.command) {
+    case "next":
+      setCurrentArticle(elements[Math.min(index + 1, elements.length - 1)]);
+      break;
+    case "previous":
+      setCurrentArticle(elements[Math.max(index - 1, 0)]);
+      break;
+    case "open":
+      if (current) {
+        openArticle(current);
+      }
+      break;
+    case "open_link":
+      if (current && current.dataset.link) {
+        markRead([current]);
+        window.open(current.dataset.link, "_blank");
+      }
+      break;
+    default:
+      break;
+  }
+}
+
+function onReadState(state) {
+  const read = new Set(state.ids || []);
+  articleElements().forEach((element) => {
+    element.classList.toggle("is-read", read.has(element.dataset.articleId));
+  });
+}
+
+if (ipc) {
+  ipc.on("news_command", onNewsCommand);
+  ipc.on("news_read_state", onReadState);
+}
+
 const runBtn = document.querySelector("#run-btn");
 if (runBtn) {
   runBtn.addEventListener("click", runGenerate);
@@ -256,6 +448,7 @@ if (page === "module") {
 
 if (page === "run") {
   loadReadme();
+  renderArticles(defaultInputs);
 }
 
 if (page === "tests") {
@@ -0,0 +1,433 @@
+//! Read briefing - speak the current digest aloud
+//!
+//! Fetches the last generated script from the backend (`GET /api/briefing`,
+//! already split into plain-text sections) and runs it through the shared
+//! [`mofa_widgets::tts`] backend on a worker thread. Sections are synthesized
+//! in sentence-sized chunks and queued on an [`AudioPlayer`] as they finish,
+//! so playback starts after the first chunk. In MP3 mode the audio is
+//! collected, encoded and saved to the configured folder instead.
+//!
+//! Voice, speed, output mode and folder are stored under the `briefing` key
+//! of the app's preferences file.
+
+use chrono::{Local, NaiveDateTime};
+use mofa_widgets::audio_player::AudioPlayer;
+use mofa_widgets::audio_tags::{write_tags, AudioTags};
+use mofa_widgets::jobs::{JobHandle, JobRegistry};
+use mofa_widgets::tts::{self, chunk_text, TtsAudio, TtsOptions};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Last generated script, kept by the backend across restarts; relative to
+/// the app data root
+pub const BRIEFING_CACHE_FILE: &str = "personal-news/last-briefing.json";
+/// Environment variable telling the backend where to keep it
+pub const BRIEFING_CACHE_ENV: &str = "PERSONAL_NEWS_BRIEFING_FILE";
+
+const BRIEFING_PATH: &str = "/api/briefing";
+/// Key in the preferences file
+const SETTINGS_KEY: &str = "briefing";
+/// Characters per synthesis request; short enough that the first chunk is ready quickly
+const MAX_CHUNK_CHARS: usize = 400;
+/// Stay well under the player's 60 s ring buffer, which overwrites the oldest audio when full
+const MAX_BUFFERED_SECS: f64 = 40.0;
+/// How long to wait for a freshly started server to answer
+const SERVER_WAIT: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Speeds offered in the UI
+pub const SPEEDS: [f32; 6] = [0.75, 1.0, 1.25, 1.5, 1.75, 2.0];
+
+/// What to do with the synthesized briefing
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BriefingOutput {
+    /// Play through the speakers as sections are synthesized
+    #[default]
+    Play,
+    /// Save an MP3 to [`BriefingSettings::save_dir`]
+    SaveMp3,
+}
+
+/// Read-aloud preferences
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BriefingSettings {
+    /// TTS voice id; `None` uses the system voice
+    pub voice: Option<String>,
+    pub speed: f32,
+    pub output: BriefingOutput,
+    /// Folder for saved MP3s; `None` uses [`default_save_dir`]
+    pub save_dir: Option<PathBuf>,
+}
+
+impl Default for BriefingSettings {
+    fn default() -> Self {
+        Self {
+            voice: None,
+            speed: 1.0,
+            output: BriefingOutput::Play,
+            save_dir: None,
+        }
+    }
+}
+
+impl BriefingSettings {
+    pub fn load() -> Self {
+        Self::load_from(&crate::screen::get_config_path())
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|json| json.get(SETTINGS_KEY).cloned())
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        self.save_to(&crate::screen::get_config_path())
+    }
+
+    /// Store the settings, keeping the other keys in the preferences file
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        let value = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        crate::screen::write_config_value(path, SETTINGS_KEY, value)
+    }
+
+    pub fn output_dir(&self) -> PathBuf {
+        self.save_dir.clone().unwrap_or_else(default_save_dir)
+    }
+
+    fn tts_options(&self) -> TtsOptions {
+        TtsOptions {
+            voice: self.voice.clone(),
+            speed: self.speed,
+        }
+    }
+}
+
+/// `~/Music/MoFA Briefings`, or under the home directory without a music folder
+pub fn default_save_dir() -> PathBuf {
+    dirs::audio_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("MoFA Briefings")
+}
+
+/// One section of the digest
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct BriefingSection {
+    #[serde(default)]
+    pub title: String,
+    pub text: String,
+}
+
+#[derive(Deserialize)]
+struct BriefingResponse {
+    sections: Vec<BriefingSection>,
+}
+
+/// Fetch the current digest, waiting briefly for a server that is still starting
+pub fn fetch_sections(base_url: &str) -> Result<Vec<BriefingSection>, String> {
+    let url = format!("{}{}", base_url, BRIEFING_PATH);
+    let deadline = Instant::now() + SERVER_WAIT;
+    loop {
+        match ureq::get(&url).timeout(Duration::from_secs(5)).call() {
+            Ok(response) => {
+                let body = response.into_string().map_err(|e| e.to_string())?;
+                let body: BriefingResponse = serde_json::from_str(&body)
+                    .map_err(|e| format!("Invalid briefing response: {}", e))?;
+                return Ok(body.sections);
+            }
+            Err(ureq::Error::Status(404, _)) => {
+                return Err("No briefing yet - generate one first".to_string());
+            }
+            Err(ureq::Error::Status(code, _)) => {
+                return Err(format!("Server returned {}", code));
+            }
+            Err(ureq::Error::Transport(e)) => {
+                if Instant::now() >= deadline {
+                    return Err(format!("Server not reachable: {}", e));
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Name for a saved briefing, e.g. `briefing-2026-10-16-0730.mp3`
+pub fn briefing_filename(time: &NaiveDateTime, extension: &str) -> String {
+    format!("briefing-{}.{}", time.format("%Y-%m-%d-%H%M"), extension)
+}
+
+/// Progress of a read-aloud job
+#[derive(Clone, Debug, PartialEq)]
+pub enum BriefingState {
+    Fetching,
+    /// Synthesizing section `section` (1-based) of `total`
+    Reading { section: usize, total: usize, title: String },
+    /// Playing the tail of the queue, or encoding the file
+    Finishing(String),
+    Done(String),
+    Failed(String),
+    Cancelled,
+}
+
+impl BriefingState {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done(_) | Self::Failed(_) | Self::Cancelled)
+    }
+
+    /// Status line for the UI
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Fetching => "Fetching briefing...".to_string(),
+            Self::Reading { section, total, title } if title.is_empty() => {
+                format!("Reading {}/{}", section, total)
+            }
+            Self::Reading { section, total, title } => {
+                format!("Reading {}/{}: {}", section, total, title)
+            }
+            Self::Finishing(message) | Self::Done(message) => message.clone(),
+            Self::Failed(error) => format!("Failed: {}", error),
+            Self::Cancelled => "Cancelled".to_string(),
+        }
+    }
+}
+
+/// A running read-aloud job, listed in the shell's jobs while it runs;
+/// cancelled when dropped
+pub struct BriefingJob {
+    state: Arc<Mutex<BriefingState>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl BriefingJob {
+    pub fn start(base_url: String, settings: BriefingSettings) -> Self {
+        let state = Arc::new(Mutex::new(BriefingState::Fetching));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let verb = match settings.output {
+            BriefingOutput::Play => "reading aloud",
+            BriefingOutput::SaveMp3 => "saving briefing",
+        };
+
+        let worker = Worker {
+            state: Arc::clone(&state),
+            cancel: Arc::clone(&cancel),
+            job: JobRegistry::global().start("mofa-personal-news", verb).cancellable(),
+            settings,
+        };
+        std::thread::spawn(move || {
+            let result = worker.run(&base_url);
+            let final_state = match result {
+                Ok(state) => state,
+                Err(e) => {
+                    ::log::error!("Briefing failed: {}", e);
+                    BriefingState::Failed(e)
+                }
+            };
+            // Dropping the handle with the worker counts a cancelled briefing
+            if !matches!(final_state, BriefingState::Cancelled) {
+                worker.job.finish();
+            }
+            worker.set_state(final_state);
+        });
+
+        Self { state, cancel }
+    }
+
+    pub fn state(&self) -> BriefingState {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for BriefingJob {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+struct Worker {
+    state: Arc<Mutex<BriefingState>>,
+    cancel: Arc<AtomicBool>,
+    job: JobHandle,
+    settings: BriefingSettings,
+}
+
+impl Worker {
+    fn set_state(&self, state: BriefingState) {
+        *self.state.lock().unwrap_or_else(|e| e.into_inner()) = state;
+    }
+
+    /// Cancelled from the app or from the shell's jobs
+    fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed) || self.job.is_cancelled()
+    }
+
+    fn run(&self, base_url: &str) -> Result<BriefingState, String> {
+        let sections = fetch_sections(base_url)?;
+        if sections.is_empty() {
+            return Err("The briefing has no text to read".to_string());
+        }
+        match self.settings.output {
+            BriefingOutput::Play => self.play(&sections),
+            BriefingOutput::SaveMp3 => self.save(&sections),
+        }
+    }
+
+    /// Synthesize chunk by chunk, calling `sink` with each one
+    fn synthesize_sections<F>(&self, sections: &[BriefingSection], mut sink: F) -> Result<bool, String>
+    where
+        F: FnMut(TtsAudio) -> bool,
+    {
+        let backend = tts::default_backend();
+        let options = self.settings.tts_options();
+        let total = sections.len();
+
+        for (index, section) in sections.iter().enumerate() {
+            self.job.set_progress(index as f32 / total as f32);
+            self.set_state(BriefingState::Reading {
+                section: index + 1,
+                total,
+                title: section.title.clone(),
+            });
+            for chunk in chunk_text(&section.text, MAX_CHUNK_CHARS) {
+                if self.cancelled() {
+                    return Ok(false);
+                }
+                let audio = backend.synthesize(&chunk, &options)?;
+                if !sink(audio) {
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(!self.cancelled())
+    }
+
+    fn play(&self, sections: &[BriefingSection]) -> Result<BriefingState, String> {
+        let player = AudioPlayer::new(tts::default_backend().sample_rate())?;
+
+        let completed = self.synthesize_sections(sections, |audio| {
+            while player.buffer_seconds() + audio.duration_secs() > MAX_BUFFERED_SECS {
+                if self.cancelled() {
+                    return false;
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+            player.write_audio(&audio.samples, None, None);
+            true
+        })?;
+        if !completed {
+            player.reset();
+            return Ok(BriefingState::Cancelled);
+        }
+
+        self.set_state(BriefingState::Finishing("Playing...".to_string()));
+        // Give the audio thread a moment to pick up the last chunk
+        std::thread::sleep(POLL_INTERVAL);
+        while player.buffer_seconds() > 0.0 {
+            if self.cancelled() {
+                player.reset();
+                return Ok(BriefingState::Cancelled);
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+        Ok(BriefingState::Done("Briefing finished".to_string()))
+    }
+
+    fn save(&self, sections: &[BriefingSection]) -> Result<BriefingState, String> {
+        let mut audio = TtsAudio::default();
+        let completed = self.synthesize_sections(sections, |chunk| {
+            audio.append(&chunk);
+            true
+        })?;
+        if !completed {
+            return Ok(BriefingState::Cancelled);
+        }
+
+        self.set_state(BriefingState::Finishing("Encoding MP3...".to_string()));
+        let dir = self.settings.output_dir();
+        fs::create_dir_all(&dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+
+        let now = Local::now().naive_local();
+        let wav_path = dir.join(briefing_filename(&now, "wav"));
+        let mp3_path = dir.join(briefing_filename(&now, "mp3"));
+        tts::write_wav(&wav_path, &audio)?;
+
+        let (path, message) = match tts::encode_mp3(&wav_path, &mp3_path) {
+            Ok(()) => {
+                let _ = fs::remove_file(&wav_path);
+                let message = format!("Saved {}", mp3_path.display());
+                (mp3_path, message)
+            }
+            Err(e) => {
+                ::log::warn!("MP3 encoding failed, keeping WAV: {}", e);
+                let message = format!("Saved {} ({})", wav_path.display(), e);
+                (wav_path, message)
+            }
+        };
+
+        let tags = AudioTags {
+            title: Some(format!("Personal News {}", now.format("%Y-%m-%d"))),
+            artist: Some("MoFA Studio".to_string()),
+            album: Some("Personal News".to_string()),
+            genre: Some("News".to_string()),
+            ..Default::default()
+        };
+        if let Err(e) = write_tags(&path, &tags) {
+            ::log::warn!("Failed to tag {}: {}", path.display(), e);
+        }
+
+        ::log::info!("Briefing saved to {}", path.display());
+        Ok(BriefingState::Done(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_keep_other_config_keys() {
+        let dir = std::env::temp_dir().join(format!("mofa-briefing-settings-{}", std::process::id()));
+        let path = dir.join("personal-news.json");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, r#"{"python_path": "/usr/bin/python3"}"#).unwrap();
+
+        assert_eq!(BriefingSettings::load_from(&path), BriefingSettings::default());
+
+        let settings = BriefingSettings {
+            voice: Some("Tingting".to_string()),
+            speed: 1.5,
+            output: BriefingOutput::SaveMp3,
+            save_dir: Some(PathBuf::from("/tmp/briefings")),
+        };
+        settings.save_to(&path).unwrap();
+        assert_eq!(BriefingSettings::load_from(&path), settings);
+
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["python_path"], "/usr/bin/python3");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_briefing_filename() {
+        let time = chrono::NaiveDate::from_ymd_opt(2026, 3, 7)
+            .unwrap()
+            .and_hms_opt(7, 5, 0)
+            .unwrap();
+        assert_eq!(briefing_filename(&time, "mp3"), "briefing-2026-03-07-0705.mp3");
+    }
+}
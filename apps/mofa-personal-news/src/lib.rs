@@ -1,7 +1,17 @@
+pub mod briefing;
+pub mod read_state;
 pub mod screen;
 
 use makepad_widgets::*;
 use mofa_widgets::{AppInfo, MofaApp};
+use mofa_widgets::app_storage::AppStorage;
+
+/// Data this app keeps under `~/.mofa-studio`
+pub const STORAGE: AppStorage = AppStorage {
+    prefs: &[screen::CONFIG_FILE],
+    cache: &[briefing::BRIEFING_CACHE_FILE],
+    webview_profile: true,
+};
 
 pub struct MoFaPersonalNewsApp;
 
@@ -14,6 +24,7 @@ impl MofaApp for MoFaPersonalNewsApp {
             tab_id: Some(live_id!(personal_news_tab)),
             page_id: Some(live_id!(personal_news_page)),
             show_in_sidebar: true,
+            storage: STORAGE,
             ..Default::default()
         }
     }
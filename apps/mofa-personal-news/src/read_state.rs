@@ -0,0 +1,168 @@
+//! Read articles - which digest items the user has already seen
+//!
+//! The page reports article ids over IPC as they are scrolled past or
+//! opened; the host keeps them under the `read_articles` key of the app's
+//! preferences file, so they survive server restarts, and sends them back
+//! when the page asks:
+//!
+//! ```js
+//! window.__mofa_ipc.send("news_read", { ids: ["rss:https://example.com/a"] });
+//! window.__mofa_ipc.on("news_read_state", (state) => greyOut(state.ids));
+//! window.__mofa_ipc.send("news_read_state", {});
+//! ```
+//!
+//! Navigation shortcuts arrive on [`COMMAND_CHANNEL`] as
+//! `{ "command": "next" }` (see [`SHORTCUTS`]).
+//!
+//! Only the [`MAX_READ_IDS`] most recently read ids are kept; reading an
+//! article again makes it the most recent.
+
+use makepad_widgets::KeyCode;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+
+/// Page → host: articles that were read, `{ "ids": [...] }`
+pub const MARK_READ_CHANNEL: &str = "news_read";
+/// Page → host asks for the read ids; host → page answers `{ "ids": [...] }`
+pub const READ_STATE_CHANNEL: &str = "news_read_state";
+/// Host → page: a navigation shortcut, `{ "command": "next" }`
+pub const COMMAND_CHANNEL: &str = "news_command";
+
+/// Read ids kept before the least recently read are dropped
+pub const MAX_READ_IDS: usize = 3000;
+/// Key in the preferences file
+const SETTINGS_KEY: &str = "read_articles";
+/// Longest id accepted from the page
+const MAX_ID_LEN: usize = 512;
+
+/// Keys the screen forwards to the page: key, command, description
+pub const SHORTCUTS: [(KeyCode, &str, &str); 4] = [
+    (KeyCode::KeyJ, "next", "Next article"),
+    (KeyCode::KeyK, "previous", "Previous article"),
+    (KeyCode::ReturnKey, "open", "Expand the current article"),
+    (KeyCode::KeyO, "open_link", "Open the current article's source"),
+];
+
+/// Read article ids, least recently read first
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReadState {
+    ids: VecDeque<String>,
+}
+
+impl ReadState {
+    pub fn load() -> Self {
+        Self::load_from(&crate::screen::get_config_path())
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        let ids = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+            .and_then(|json| serde_json::from_value::<Vec<String>>(json.get(SETTINGS_KEY)?.clone()).ok())
+            .unwrap_or_default();
+        let mut state = Self::default();
+        state.mark(ids);
+        state
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        self.save_to(&crate::screen::get_config_path())
+    }
+
+    /// Store the ids, keeping the other keys in the preferences file
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        crate::screen::write_config_value(path, SETTINGS_KEY, json!(self.ids))
+    }
+
+    /// Record articles as read, most recent last. Returns whether anything
+    /// changed; ids that are empty or too long are ignored.
+    pub fn mark<I: IntoIterator<Item = String>>(&mut self, ids: I) -> bool {
+        let mut changed = false;
+        for id in ids {
+            if id.is_empty() || id.len() > MAX_ID_LEN {
+                continue;
+            }
+            if self.ids.back() == Some(&id) {
+                continue;
+            }
+            if let Some(pos) = self.ids.iter().position(|read| *read == id) {
+                self.ids.remove(pos);
+            }
+            self.ids.push_back(id);
+            changed = true;
+        }
+        while self.ids.len() > MAX_READ_IDS {
+            self.ids.pop_front();
+        }
+        changed
+    }
+
+    /// Apply a [`MARK_READ_CHANNEL`] message
+    pub fn mark_from_page(&mut self, data: &str) -> bool {
+        let ids = serde_json::from_str::<Value>(data)
+            .ok()
+            .and_then(|value| serde_json::from_value::<Vec<String>>(value.get("ids")?.clone()).ok())
+            .unwrap_or_default();
+        self.mark(ids)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids.iter().any(|read| read == id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// The [`READ_STATE_CHANNEL`] reply
+    pub fn to_json(&self) -> Value {
+        json!({ "ids": self.ids })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_least_recently_read_are_evicted() {
+        let mut state = ReadState::default();
+        assert!(state.mark((0..MAX_READ_IDS).map(|i| format!("a{}", i))));
+        // Reading a0 again makes a1 the oldest
+        assert!(state.mark_from_page(r#"{"ids": ["a0", "", "new"]}"#));
+        assert!(!state.mark(["new".to_string()]));
+
+        assert_eq!(state.len(), MAX_READ_IDS);
+        assert!(state.contains("a0"));
+        assert!(!state.contains("a1"));
+        assert!(state.contains("new"));
+        assert!(!state.mark_from_page("not json"));
+    }
+
+    #[test]
+    fn test_read_state_keeps_other_config_keys() {
+        let dir = std::env::temp_dir().join(format!("mofa-news-read-state-{}", std::process::id()));
+        let path = dir.join("personal-news.json");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, r#"{"python_path": "/usr/bin/python3"}"#).unwrap();
+
+        assert!(ReadState::load_from(&path).is_empty());
+        let mut state = ReadState::default();
+        state.mark(["rss:b".to_string(), "x:42".to_string()]);
+        state.save_to(&path).unwrap();
+
+        assert_eq!(ReadState::load_from(&path), state);
+        assert_eq!(state.to_json(), json!({ "ids": ["rss:b", "x:42"] }));
+        let json: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["python_path"], "/usr/bin/python3");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
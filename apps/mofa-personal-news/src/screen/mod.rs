@@ -1,13 +1,23 @@
 //! Personal News Screen
 //!
 //! WebView-based Personal News display with embedded Python server
+//!
+//! The host remembers which articles were read and forwards j/k/Enter/o to
+//! the page as navigation commands (see [`crate::read_state`]).
 
 use makepad_widgets::*;
+use mofa_widgets::activity::{ActivityHandle, ActivityKind, ActivityRegistry};
+use mofa_widgets::shortcuts::ShortcutRegistry;
+use mofa_widgets::tts::{self, TtsVoice};
+use mofa_widgets::voices::{favorite_voices, favorites_first};
 use mofa_widgets::webview::{WebViewAction, WebViewContainerWidgetExt};
+use crate::briefing::{BriefingJob, BriefingOutput, BriefingSettings, SPEEDS};
+use crate::read_state::{self, ReadState};
+use serde_json::json;
 use std::net::TcpListener;
 use std::process::{Child, Command, Stdio};
 use std::sync::{Arc, Mutex};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 use std::io::Write;
 
@@ -181,6 +191,108 @@ live_design! {
         }
     }
 
+    // Label style for the panels
+    PanelLabel = <Label> {
+        width: Fit
+        margin: {right: 8}
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: { font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix(
+                    vec4(0.3, 0.3, 0.35, 1.0),
+                    vec4(0.7, 0.7, 0.75, 1.0),
+                    self.dark_mode
+                );
+            }
+        }
+    }
+
+    // Dropdown style
+    PanelDropDown = <DropDown> {
+        width: 140, height: 28
+        margin: {right: 8}
+        padding: {left: 8, right: 20}
+        draw_bg: {
+            instance dark_mode: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 4.0);
+                let bg = mix(
+                    vec4(1.0, 1.0, 1.0, 1.0),
+                    vec4(0.18, 0.19, 0.22, 1.0),
+                    self.dark_mode
+                );
+                sdf.fill(bg);
+                let border = mix(
+                    vec4(0.8, 0.82, 0.85, 1.0),
+                    vec4(0.3, 0.32, 0.36, 1.0),
+                    self.dark_mode
+                );
+                sdf.stroke(border, 1.0);
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: { font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix(
+                    vec4(0.2, 0.2, 0.25, 1.0),
+                    vec4(0.85, 0.85, 0.9, 1.0),
+                    self.dark_mode
+                );
+            }
+        }
+        popup_menu: {
+            draw_bg: {
+                instance dark_mode: 0.0
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 4.0);
+                    sdf.fill(mix(
+                        vec4(1.0, 1.0, 1.0, 1.0),
+                        vec4(0.18, 0.19, 0.22, 1.0),
+                        self.dark_mode
+                    ));
+                    return sdf.result;
+                }
+            }
+            menu_item: {
+                draw_bg: {
+                    instance dark_mode: 0.0
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.rect(0., 0., self.rect_size.x, self.rect_size.y);
+                        let base = mix(
+                            vec4(1.0, 1.0, 1.0, 1.0),
+                            vec4(0.18, 0.19, 0.22, 1.0),
+                            self.dark_mode
+                        );
+                        let hover_color = mix(
+                            vec4(0.92, 0.93, 0.95, 1.0),
+                            vec4(0.26, 0.28, 0.32, 1.0),
+                            self.dark_mode
+                        );
+                        sdf.fill(mix(base, hover_color, self.hover));
+                        return sdf.result;
+                    }
+                }
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: { font_size: 11.0 }
+                    fn get_color(self) -> vec4 {
+                        return mix(
+                            vec4(0.2, 0.2, 0.25, 1.0),
+                            vec4(0.85, 0.85, 0.9, 1.0),
+                            self.dark_mode
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     pub PersonalNewsScreen = {{PersonalNewsScreen}} {
         width: Fill, height: Fill
         flow: Down
@@ -226,6 +338,7 @@ live_design! {
                     webview = <WebViewContainer> {
                         width: Fill, height: Fill
                         url: "about:blank"
+                        profile: "mofa-personal-news"
                     }
                 }
             }
@@ -265,6 +378,56 @@ live_design! {
             }
         }
 
+        // Read briefing panel (hidden by default)
+        briefing_panel = <ConfigPanel> {
+            visible: false
+
+            voice_label = <PanelLabel> { text: "Voice:" }
+            voice_dropdown = <PanelDropDown> {
+                width: 180
+                labels: ["System default"]
+            }
+
+            speed_label = <PanelLabel> { text: "Speed:" }
+            speed_dropdown = <PanelDropDown> {
+                width: 80
+                labels: ["0.75x", "1x", "1.25x", "1.5x", "1.75x", "2x"]
+                selected_item: 1
+            }
+
+            output_dropdown = <PanelDropDown> {
+                width: 110
+                labels: ["Play now", "Save MP3"]
+            }
+
+            folder_label = <PanelLabel> { text: "Folder:" }
+            folder_input = <ConfigInput> {
+                width: 220
+                empty_text: "~/Music/MoFA Briefings"
+            }
+
+            <View> { width: 8, height: 1 }
+
+            read_btn = <NavButton> {
+                width: Fit
+                padding: {left: 12, right: 12}
+                text: "Read"
+            }
+
+            cancel_btn = <NavButton> {
+                width: Fit
+                padding: {left: 12, right: 12}
+                text: "Cancel"
+            }
+
+            <View> { width: 8, height: 1 }
+
+            progress_label = <PanelLabel> {
+                width: Fill
+                text: ""
+            }
+        }
+
         // Status bar with navigation
         status_bar = <View> {
             width: Fill, height: 36
@@ -295,6 +458,13 @@ live_design! {
                 text: "⚙"
             }
 
+            // Read briefing button
+            briefing_btn = <NavButton> {
+                width: Fit
+                padding: {left: 8, right: 8}
+                text: "Read briefing"
+            }
+
             // Navigation buttons
             back_btn = <NavButton> {
                 text: "<"
@@ -400,12 +570,33 @@ fn get_python_path() -> Option<PathBuf> {
     None
 }
 
+/// App id shortcuts are registered under
+const APP_ID: &str = "mofa-personal-news";
+
+/// Preferences file, relative to the MoFA Studio data directory
+pub(crate) const CONFIG_FILE: &str = "personal-news.json";
+
 /// Get config file path
-fn get_config_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".mofa-studio")
-        .join("personal-news.json")
+pub(crate) fn get_config_path() -> PathBuf {
+    mofa_widgets::app_storage::data_path(CONFIG_FILE)
+}
+
+/// Set one top-level key in the config file, keeping the others
+pub(crate) fn write_config_value(path: &Path, key: &str, value: serde_json::Value) -> Result<(), String> {
+    let mut json = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .filter(|json| json.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    json[key] = value;
+
+    // Create directory if needed
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let mut file = fs::File::create(path).map_err(|e| e.to_string())?;
+    file.write_all(json.to_string().as_bytes()).map_err(|e| e.to_string())
 }
 
 fn find_embedded_python_cmd() -> Option<String> {
@@ -457,19 +648,7 @@ fn load_python_config() -> String {
 
 /// Save Python path to config
 fn save_python_config(python_path: &str) -> Result<(), String> {
-    let config_path = get_config_path();
-
-    // Create directory if needed
-    if let Some(parent) = config_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-    }
-
-    let json = serde_json::json!({
-        "python_path": python_path
-    });
-
-    let mut file = fs::File::create(&config_path).map_err(|e| e.to_string())?;
-    file.write_all(json.to_string().as_bytes()).map_err(|e| e.to_string())?;
+    write_config_value(&get_config_path(), "python_path", serde_json::json!(python_path))?;
 
     ::log::info!("Saved Python config: {}", python_path);
     Ok(())
@@ -531,6 +710,10 @@ server.serve_forever()
 "#,
                 port, port
             )])
+            .env(
+                crate::briefing::BRIEFING_CACHE_ENV,
+                mofa_widgets::app_storage::data_path(crate::briefing::BRIEFING_CACHE_FILE),
+            )
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
             .spawn()
@@ -569,6 +752,10 @@ pub struct PersonalNewsScreen {
     #[rust]
     server: Arc<Mutex<PythonServer>>,
 
+    /// Lists the server in the shell's running panel while it runs
+    #[rust]
+    server_activity: Option<ActivityHandle>,
+
     #[rust]
     url_loaded: bool,
 
@@ -577,23 +764,66 @@ pub struct PersonalNewsScreen {
 
     #[rust]
     config_initialized: bool,
+
+    #[rust]
+    briefing_visible: bool,
+
+    #[rust]
+    briefing_initialized: bool,
+
+    #[rust]
+    briefing_settings: BriefingSettings,
+
+    /// Voices behind the voice dropdown, after "System default"
+    #[rust]
+    briefing_voices: Vec<TtsVoice>,
+
+    #[rust]
+    briefing_job: Option<BriefingJob>,
+
+    #[rust]
+    briefing_timer: Timer,
+
+    /// Read article ids, loaded on the page's first message
+    #[rust]
+    read_state: Option<ReadState>,
 }
 
 impl Widget for PersonalNewsScreen {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
 
+        // Stop requests from the shell's running panel
+        if self.server_activity.as_ref().is_some_and(|a| a.stop_requested()) {
+            self.start_server(cx);
+        }
+
         // Get actions from event
         let actions = match event {
             Event::Actions(actions) => actions.as_slice(),
             _ => &[],
         };
 
+        // Poll read-aloud progress
+        if self.briefing_timer.is_event(event).is_some() {
+            self.poll_briefing(cx);
+        }
+
         // Initialize config input on first run
         if !self.config_initialized {
             self.config_initialized = true;
             let python_path = load_python_config();
             self.view.text_input(ids!(config_panel.python_input)).set_text(cx, &python_path);
+            for (key, command, description) in read_state::SHORTCUTS {
+                ShortcutRegistry::global().register(APP_ID, key, command, description);
+            }
+        }
+
+        // Navigation shortcuts go to the page, unless a panel with inputs is open
+        if !self.config_visible && !self.briefing_visible {
+            if let Some(command) = ShortcutRegistry::global().command(APP_ID, event) {
+                self.send_to_page(read_state::COMMAND_CHANNEL, &json!({ "command": command }));
+            }
         }
 
         // Handle start button click
@@ -626,6 +856,20 @@ impl Widget for PersonalNewsScreen {
             }
         }
 
+        // Handle read briefing button click - toggle briefing panel
+        if self.view.button(ids!(status_bar.briefing_btn)).clicked(actions) {
+            self.briefing_visible = !self.briefing_visible;
+            if self.briefing_visible {
+                self.init_briefing_panel(cx);
+            }
+            self.view.view(ids!(briefing_panel)).set_visible(cx, self.briefing_visible);
+            self.view.redraw(cx);
+        }
+
+        if self.briefing_visible {
+            self.handle_briefing_panel(cx, actions);
+        }
+
         // Handle navigation button clicks
         if self.view.button(ids!(status_bar.back_btn)).clicked(actions) {
             self.go_back();
@@ -664,7 +908,10 @@ impl Widget for PersonalNewsScreen {
                                 self.set_status(cx, "Connected", 1.0);
                             }
                         }
-                        WebViewAction::IpcMessage { .. } | WebViewAction::None => {}
+                        WebViewAction::IpcMessage { channel, data } => {
+                            self.handle_ipc(&channel, &data);
+                        }
+                        WebViewAction::InitTiming { .. } | WebViewAction::None => {}
                     }
                 }
             }
@@ -688,6 +935,7 @@ impl PersonalNewsScreen {
             let mut server = self.server.lock().unwrap();
             server.stop();
             drop(server);
+            self.server_activity = None;
             self.set_status(cx, "Server stopped", 0.0);
             self.url_loaded = false;
             // Update button text
@@ -704,6 +952,11 @@ impl PersonalNewsScreen {
             match result {
                 Ok(port) => {
                     ::log::info!("Python server started on port {}", port);
+                    let activity = ActivityRegistry::global()
+                        .register(ActivityKind::Server, "mofa-personal-news", "Personal News server")
+                        .stoppable();
+                    activity.set_state(&format!("port {}", port));
+                    self.server_activity = Some(activity);
                     self.set_status(cx, &format!("Server running on port {}", port), 2.0);
                     // Update button text
                     self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Stop Server");
@@ -740,6 +993,32 @@ impl PersonalNewsScreen {
         }
     }
 
+    /// Read state reported or requested by the page
+    fn handle_ipc(&mut self, channel: &str, data: &str) {
+        let state = self.read_state.get_or_insert_with(ReadState::load);
+        match channel {
+            read_state::MARK_READ_CHANNEL => {
+                if state.mark_from_page(data) {
+                    if let Err(e) = state.save() {
+                        ::log::warn!("Failed to save read articles: {}", e);
+                    }
+                }
+            }
+            read_state::READ_STATE_CHANNEL => {
+                let reply = state.to_json();
+                self.send_to_page(read_state::READ_STATE_CHANNEL, &reply);
+            }
+            _ => {}
+        }
+    }
+
+    fn send_to_page(&self, channel: &str, message: &serde_json::Value) {
+        let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+        if let Err(e) = webview.send_to_js(channel, &message.to_string()) {
+            ::log::warn!("Failed to send {} to the page: {}", channel, e);
+        }
+    }
+
     fn go_back(&self) {
         let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
         let _ = webview.go_back();
@@ -755,6 +1034,165 @@ impl PersonalNewsScreen {
         let _ = webview.reload();
     }
 
+    /// Fill the briefing panel from saved settings (first open only)
+    fn init_briefing_panel(&mut self, cx: &mut Cx) {
+        if self.briefing_initialized {
+            return;
+        }
+        self.briefing_initialized = true;
+        self.briefing_settings = BriefingSettings::load();
+        let backend = tts::default_backend();
+        self.briefing_voices = backend.voices().unwrap_or_else(|e| {
+            ::log::warn!("Failed to list TTS voices: {}", e);
+            Vec::new()
+        });
+        favorites_first(&mut self.briefing_voices, &favorite_voices(), backend.name(), |voice| &voice.id);
+
+        let settings = &self.briefing_settings;
+        let mut labels = vec!["System default".to_string()];
+        labels.extend(self.briefing_voices.iter().map(|voice| {
+            if voice.language.is_empty() {
+                voice.name.clone()
+            } else {
+                format!("{} ({})", voice.name, voice.language)
+            }
+        }));
+        let voice_idx = settings
+            .voice
+            .as_ref()
+            .and_then(|id| self.briefing_voices.iter().position(|voice| &voice.id == id))
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+        let voice_dropdown = self.view.drop_down(ids!(briefing_panel.voice_dropdown));
+        voice_dropdown.set_labels(cx, labels);
+        voice_dropdown.set_selected_item(cx, voice_idx);
+
+        let speed_idx = SPEEDS
+            .iter()
+            .position(|speed| (speed - settings.speed).abs() < 0.01)
+            .unwrap_or(1);
+        self.view.drop_down(ids!(briefing_panel.speed_dropdown)).set_selected_item(cx, speed_idx);
+
+        let output_idx = match settings.output {
+            BriefingOutput::Play => 0,
+            BriefingOutput::SaveMp3 => 1,
+        };
+        self.view.drop_down(ids!(briefing_panel.output_dropdown)).set_selected_item(cx, output_idx);
+
+        let folder = settings
+            .save_dir
+            .as_ref()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.view.text_input(ids!(briefing_panel.folder_input)).set_text(cx, &folder);
+    }
+
+    fn handle_briefing_panel(&mut self, cx: &mut Cx, actions: &[Action]) {
+        let mut changed = false;
+
+        if let Some(idx) = self.view.drop_down(ids!(briefing_panel.voice_dropdown)).selected(actions) {
+            self.briefing_settings.voice = idx
+                .checked_sub(1)
+                .and_then(|idx| self.briefing_voices.get(idx))
+                .map(|voice| voice.id.clone());
+            changed = true;
+        }
+        if let Some(idx) = self.view.drop_down(ids!(briefing_panel.speed_dropdown)).selected(actions) {
+            self.briefing_settings.speed = SPEEDS.get(idx).copied().unwrap_or(1.0);
+            changed = true;
+        }
+        if let Some(idx) = self.view.drop_down(ids!(briefing_panel.output_dropdown)).selected(actions) {
+            self.briefing_settings.output = if idx == 1 {
+                BriefingOutput::SaveMp3
+            } else {
+                BriefingOutput::Play
+            };
+            changed = true;
+        }
+        let folder_input = self.view.text_input(ids!(briefing_panel.folder_input));
+        if folder_input.returned(actions).is_some() {
+            self.apply_briefing_folder();
+            changed = true;
+        }
+
+        if changed {
+            if let Err(e) = self.briefing_settings.save() {
+                self.set_briefing_progress(cx, &format!("Save failed: {}", e));
+            }
+        }
+
+        if self.view.button(ids!(briefing_panel.read_btn)).clicked(actions) {
+            self.start_briefing(cx);
+        }
+        if self.view.button(ids!(briefing_panel.cancel_btn)).clicked(actions) {
+            if let Some(ref job) = self.briefing_job {
+                job.cancel();
+                self.set_briefing_progress(cx, "Cancelling...");
+            }
+        }
+    }
+
+    fn apply_briefing_folder(&mut self) {
+        let folder = self.view.text_input(ids!(briefing_panel.folder_input)).text();
+        let folder = folder.trim();
+        self.briefing_settings.save_dir = if folder.is_empty() {
+            None
+        } else if let Some(rest) = folder.strip_prefix("~/") {
+            dirs::home_dir().map(|home| home.join(rest))
+        } else {
+            Some(PathBuf::from(folder))
+        };
+    }
+
+    fn start_briefing(&mut self, cx: &mut Cx) {
+        if self.briefing_job.is_some() {
+            return;
+        }
+
+        self.apply_briefing_folder();
+        if let Err(e) = self.briefing_settings.save() {
+            ::log::warn!("Failed to save briefing settings: {}", e);
+        }
+
+        // The digest comes from the backend, so make sure it is running
+        let is_running = self.server.lock().unwrap().is_running();
+        if !is_running {
+            self.start_server(cx);
+        }
+        let url = {
+            let server = self.server.lock().unwrap();
+            server.is_running().then(|| server.url())
+        };
+        let Some(url) = url else {
+            self.set_briefing_progress(cx, "Server not running");
+            return;
+        };
+
+        self.briefing_job = Some(BriefingJob::start(url, self.briefing_settings.clone()));
+        self.briefing_timer = cx.start_interval(0.25);
+        self.set_briefing_progress(cx, "Fetching briefing...");
+    }
+
+    fn poll_briefing(&mut self, cx: &mut Cx) {
+        let Some(state) = self.briefing_job.as_ref().map(|job| job.state()) else {
+            cx.stop_timer(self.briefing_timer);
+            return;
+        };
+
+        self.set_briefing_progress(cx, &state.describe());
+        if state.is_finished() {
+            cx.stop_timer(self.briefing_timer);
+            self.briefing_job = None;
+        }
+    }
+
+    fn set_briefing_progress(&mut self, cx: &mut Cx, text: &str) {
+        self.view
+            .label(ids!(briefing_panel.progress_label))
+            .set_text(cx, text);
+        self.view.redraw(cx);
+    }
+
     fn set_status(&mut self, cx: &mut Cx, text: &str, status: f64) {
         self.view
             .label(ids!(status_bar.status_text))
@@ -857,6 +1295,64 @@ impl PersonalNewsScreenRef {
                         draw_text: { dark_mode: (dark_mode) }
                     },
                 );
+            inner.view.button(ids!(status_bar.briefing_btn)).apply_over(
+                cx,
+                live! {
+                    draw_bg: { dark_mode: (dark_mode) }
+                    draw_text: { dark_mode: (dark_mode) }
+                },
+            );
+
+            // Briefing panel
+            inner.view.view(ids!(briefing_panel)).apply_over(
+                cx,
+                live! {
+                    draw_bg: { dark_mode: (dark_mode) }
+                },
+            );
+            for path in [
+                ids!(briefing_panel.voice_label),
+                ids!(briefing_panel.speed_label),
+                ids!(briefing_panel.folder_label),
+                ids!(briefing_panel.progress_label),
+            ] {
+                inner.view.label(path).apply_over(
+                    cx,
+                    live! {
+                        draw_text: { dark_mode: (dark_mode) }
+                    },
+                );
+            }
+            for path in [
+                ids!(briefing_panel.voice_dropdown),
+                ids!(briefing_panel.speed_dropdown),
+                ids!(briefing_panel.output_dropdown),
+            ] {
+                inner.view.drop_down(path).apply_over(
+                    cx,
+                    live! {
+                        draw_bg: { dark_mode: (dark_mode) }
+                        draw_text: { dark_mode: (dark_mode) }
+                    },
+                );
+            }
+            inner.view.text_input(ids!(briefing_panel.folder_input)).apply_over(
+                cx,
+                live! {
+                    draw_bg: { dark_mode: (dark_mode) }
+                    draw_text: { dark_mode: (dark_mode) }
+                },
+            );
+            for path in [ids!(briefing_panel.read_btn), ids!(briefing_panel.cancel_btn)] {
+                inner.view.button(path).apply_over(
+                    cx,
+                    live! {
+                        draw_bg: { dark_mode: (dark_mode) }
+                        draw_text: { dark_mode: (dark_mode) }
+                    },
+                );
+            }
+
             inner
                 .view
                 .label(ids!(status_bar.version_label))
@@ -867,6 +1363,9 @@ impl PersonalNewsScreenRef {
                     },
                 );
 
+            // Send theme to WebView
+            inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview)).set_dark_mode(dark_mode);
+
             inner.view.redraw(cx);
         }
     }
@@ -1,7 +1,8 @@
 """
 Podcast Factory Backend
 
-Generate multi-episode podcast series from books using AI.
+Generate multi-episode podcast series from books using AI, or have the
+book read aloud chapter by chapter in straight narration mode.
 """
 
 import os
@@ -17,6 +18,15 @@ from http.server import HTTPServer, SimpleHTTPRequestHandler
 from urllib.parse import parse_qs, urlparse
 import tempfile
 
+from show_notes import (
+    MANIFEST_FILE, build_manifest, load_manifest, notes_prompt, parse_notes,
+    apply_edits, save_notes, wav_duration,
+)
+from feed import publish_project
+from narration import narration_notes, narration_outline, narration_script, narration_segments
+from script_review import apply_script_edit, edit_blocked, script_diff
+import pipeline
+
 # Optional imports for file parsing
 try:
     import PyPDF2
@@ -37,6 +47,15 @@ projects: Dict[str, Dict[str, Any]] = {}
 OUTPUT_DIR = Path.home() / "Documents" / "MoFaPodcastFactory"
 OUTPUT_DIR.mkdir(parents=True, exist_ok=True)
 
+# Saved in each project folder so projects and paused runs survive a restart
+PROJECT_FILE = "project.json"
+
+CHAT_MODEL = "gpt-4o-mini"
+
+# Series modes: LLM-written dialogue, or the book text read by one narrator
+MODE_DIALOGUE = "dialogue"
+MODE_NARRATION = "narration"
+
 # macOS voices
 VOICES = {
     "female_en": "Samantha",
@@ -46,6 +65,21 @@ VOICES = {
     "narrator": "Alex",
 }
 
+# Voices starred in MoFA Studio Settings, shared by all apps
+VOICE_PREFS_FILE = Path.home() / ".mofa-studio" / "voices" / "favorites.json"
+
+
+def favorite_voices() -> List[str]:
+    """Ids of the starred `say` voices, in the order they were starred."""
+    try:
+        prefs = json.loads(VOICE_PREFS_FILE.read_text(encoding="utf-8"))
+    except (OSError, ValueError):
+        return []
+    return [
+        fav["id"] for fav in prefs.get("favorites", [])
+        if isinstance(fav, dict) and fav.get("backend") == "say" and "id" in fav
+    ]
+
 
 def parse_txt_file(file_path: Path) -> str:
     """Parse plain text file."""
@@ -106,23 +140,77 @@ def parse_book_file(file_path: Path) -> str:
         raise ValueError(f"Unsupported file format: {ext}")
 
 
-def call_openai(messages: List[Dict], api_key: str, max_tokens: int = 2000) -> Optional[str]:
-    """Call OpenAI API."""
+def save_project(project: Dict):
+    """Write the project to its folder; failures are logged, never raised."""
+    path = Path(project["dir"]) / PROJECT_FILE
+    try:
+        with pipeline.lock:
+            text = json.dumps(project, ensure_ascii=False)
+        tmp = path.with_suffix(".tmp")
+        tmp.write_text(text, encoding="utf-8")
+        tmp.replace(path)
+    except (OSError, TypeError, ValueError) as e:
+        print(f"Failed to save project {project.get('id')}: {e}")
+
+
+def load_projects():
+    """Load the projects saved under OUTPUT_DIR; interrupted runs come back paused."""
+    for path in OUTPUT_DIR.glob(f"*/{PROJECT_FILE}"):
+        try:
+            project = json.loads(path.read_text(encoding="utf-8"))
+        except (OSError, ValueError) as e:
+            print(f"Skipping {path}: {e}")
+            continue
+        if project.get("pipeline"):
+            pipeline.recover(project)
+        projects[project["id"]] = project
+
+
+def new_usage() -> Dict[str, int]:
+    return {"prompt_tokens": 0, "completion_tokens": 0, "total_tokens": 0}
+
+
+def budget_exceeded(project: Dict) -> bool:
+    """True once the project's token usage reached its budget (0 = unlimited)."""
+    budget = project.get("token_budget") or 0
+    return budget > 0 and project["usage"]["total_tokens"] >= budget
+
+
+def is_narration(project: Dict) -> bool:
+    return project.get("mode") == MODE_NARRATION
+
+
+def call_openai(
+    messages: List[Dict],
+    api_key: str,
+    max_tokens: int = 2000,
+    usage: Optional[Dict[str, int]] = None
+) -> Optional[str]:
+    """Call OpenAI API, adding the token counts to `usage` if given."""
     try:
         import openai
         client = openai.OpenAI(api_key=api_key)
         response = client.chat.completions.create(
-            model="gpt-4o-mini",
+            model=CHAT_MODEL,
             messages=messages,
             max_tokens=max_tokens
         )
+        if usage is not None and response.usage:
+            for key in ("prompt_tokens", "completion_tokens", "total_tokens"):
+                usage[key] = usage.get(key, 0) + (getattr(response.usage, key, 0) or 0)
         return response.choices[0].message.content
     except Exception as e:
         print(f"OpenAI error: {e}")
         return None
 
 
-def generate_outline(book_content: str, num_episodes: int, style: str, api_key: str) -> Optional[Dict]:
+def generate_outline(
+    book_content: str,
+    num_episodes: int,
+    style: str,
+    api_key: str,
+    usage: Optional[Dict[str, int]] = None
+) -> Optional[Dict]:
     """Generate episode outline from book content."""
 
     system_prompt = f"""You are a podcast series planner. Your task is to convert book content into a {num_episodes}-episode podcast series.
@@ -152,7 +240,7 @@ Output as JSON array:
         {"role": "user", "content": f"Create a {num_episodes}-episode podcast outline from this content:\n\n{book_content[:15000]}"}
     ]
 
-    result = call_openai(messages, api_key, max_tokens=3000)
+    result = call_openai(messages, api_key, max_tokens=3000, usage=usage)
     if not result:
         return None
 
@@ -174,7 +262,8 @@ def generate_episode_script(
     book_content: str,
     personas: List[Dict],
     style: str,
-    api_key: str
+    api_key: str,
+    usage: Optional[Dict[str, int]] = None
 ) -> Optional[Dict]:
     """Generate script for a single episode."""
 
@@ -209,10 +298,18 @@ Output the script directly, no markdown code blocks."""
         {"role": "user", "content": f"Write the script for Episode {episode_info['episode']}: {episode_info['title']}\n\nRelevant content:\n{book_content[:8000]}"}
     ]
 
-    script_text = call_openai(messages, api_key, max_tokens=4000)
+    script_text = call_openai(messages, api_key, max_tokens=4000, usage=usage)
     if not script_text:
         return None
 
+    return {
+        "script": script_text,
+        "segments": parse_dialogue(script_text, personas)
+    }
+
+
+def parse_dialogue(script_text: str, personas: List[Dict]) -> List[Dict]:
+    """Split a "Role: line" script into segments with the speaker's voice."""
     # Parse into segments - be more lenient with role matching
     # Build mapping from various possible names to voice
     voice_map = {}
@@ -253,10 +350,203 @@ Output the script directly, no markdown code blocks."""
 
                     segments.append({"role": role, "text": text, "voice": voice})
 
-    return {
-        "script": script_text,
-        "segments": segments
-    }
+    return segments
+
+
+def generate_episode_notes(
+    episode_data: Dict,
+    style: str,
+    api_key: str,
+    usage: Optional[Dict[str, int]] = None
+) -> Optional[Dict]:
+    """Second pass: listener-facing titles, description and show notes."""
+    chapters = (episode_data.get("manifest") or {}).get("chapters", [])
+
+    messages = [
+        {"role": "system", "content": notes_prompt(episode_data["title"], style, chapters)},
+        {"role": "user", "content": f"Episode script:\n\n{episode_data['script'][:12000]}"}
+    ]
+
+    result = call_openai(messages, api_key, max_tokens=1200, usage=usage)
+    if not result:
+        return None
+    return parse_notes(result, chapters)
+
+
+def outline_episode(project: Dict, episode_num: int) -> Optional[Dict]:
+    for ep in (project.get("outline") or {}).get("episodes", []):
+        if ep["episode"] == episode_num:
+            return ep
+    return None
+
+
+def build_episode_script(project: Dict, episode_info: Dict, api_key: Optional[str]) -> Optional[Dict]:
+    """Dialogue from the LLM, or the chapter text for the narrator."""
+    if is_narration(project):
+        return narration_script(episode_info, project["personas"][0]["voice"])
+    return generate_episode_script(
+        episode_info,
+        project["book_content"],
+        project["personas"],
+        project["style"],
+        api_key,
+        project["usage"]
+    )
+
+
+def script_segments(project: Dict, script: str) -> List[Dict]:
+    """Segments for a script the user edited, parsed the way the mode writes them."""
+    if is_narration(project):
+        return narration_segments(script, project["personas"][0]["voice"])
+    return parse_dialogue(script, project["personas"])
+
+
+def check_provider(api_key: Optional[str]) -> Optional[str]:
+    """Why the chat provider cannot be used right now, or None if it can."""
+    if not api_key:
+        return "API key required"
+    try:
+        import openai
+        openai.OpenAI(api_key=api_key).models.retrieve(CHAT_MODEL)
+        return None
+    except Exception as e:
+        return f"AI provider unavailable: {e}"
+
+
+def installed_voices() -> Optional[set]:
+    """Names of the installed `say` voices; None if they cannot be listed."""
+    try:
+        result = subprocess.run(['say', '-v', '?'], capture_output=True, text=True, timeout=30)
+    except (OSError, subprocess.SubprocessError):
+        return None
+    if result.returncode != 0:
+        return None
+    # "Ting-Ting           zh_CN    # Hello, my name is Ting-Ting."
+    voices = set()
+    for line in result.stdout.splitlines():
+        m = re.match(r"^(.+?)\s+[a-z]{2,3}[_-]\w+\s+#", line)
+        if m:
+            voices.add(m.group(1).strip())
+    return voices
+
+
+def episode_voices(project: Dict, key: str) -> set:
+    """Voices an episode will be rendered with: its segments' once scripted."""
+    personas = {p["name"]: p["voice"] for p in project["personas"]}
+    default_voice = project["personas"][0]["voice"] if project["personas"] else "Samantha"
+    episode = project["episodes"].get(key)
+    if pipeline.entries(project)[key]["state"] == pipeline.SCRIPTED and episode:
+        return {seg.get("voice") or personas.get(seg["role"], default_voice) for seg in episode["segments"]}
+    return set(personas.values())
+
+
+def revalidate(project: Dict, api_key: Optional[str]) -> Dict[str, str]:
+    """Check the provider and voices before a run continues.
+
+    Returns the reason each affected episode cannot continue, by episode key;
+    episodes that are not affected carry on.
+    """
+    provider_error = None if is_narration(project) else check_provider(api_key)
+    voices = installed_voices()
+    problems = {}
+    for key, entry in pipeline.entries(project).items():
+        state = entry["state"]
+        if state in (pipeline.DONE, pipeline.REVIEW):
+            # Scripts waiting for approval stay there; resuming skips them
+            continue
+        # Failed episodes are retried from the stage they failed in
+        needs_script = state == pipeline.QUEUED or (state == pipeline.FAILED and entry.get("stage") != "tts")
+        if needs_script and provider_error:
+            problems[key] = provider_error
+            continue
+        missing = sorted(episode_voices(project, key) - voices) if voices is not None else []
+        if missing:
+            problems[key] = f"Voice not installed: {', '.join(missing)}"
+    return problems
+
+
+def pipeline_workers(project: Dict, api_key: Optional[str], rate: int = 180) -> Dict[str, pipeline.Worker]:
+    """Stage workers for a generate-all run: scripting (LLM) and rendering (TTS)."""
+    project_dir = Path(project["dir"])
+
+    def write_script(key: str) -> Optional[str]:
+        ep_info = outline_episode(project, int(key))
+        if not ep_info:
+            return "Episode not found in outline"
+        if not is_narration(project) and budget_exceeded(project):
+            return "Token budget exhausted"
+
+        script_result = build_episode_script(project, ep_info, api_key)
+        if not script_result:
+            return "Failed to generate script"
+
+        script_path = project_dir / f"episode_{int(key):02d}" / "script.md"
+        script_path.parent.mkdir(exist_ok=True)
+        script_path.write_text(script_result["script"])
+
+        with pipeline.lock:
+            previous = project["episodes"].get(key, {})
+            project["episodes"][key] = {
+                "episode": ep_info["episode"],
+                "title": ep_info["title"],
+                "script": script_result["script"],
+                "segments": script_result["segments"],
+                "audio_path": None,
+                "manifest": None,
+                "notes": previous.get("notes")
+            }
+        return None
+
+    def render(key: str) -> Optional[str]:
+        episode_data = dict(project["episodes"][key])
+        audio_path = generate_episode_audio(
+            episode_data["episode"],
+            episode_data["segments"],
+            project["personas"],
+            project_dir,
+            rate
+        )
+        if not audio_path:
+            return "Failed to render audio"
+        episode_data["audio_path"] = str(audio_path)
+        episode_data["manifest"] = load_manifest(audio_path.parent)
+
+        refresh_episode_notes(project, episode_data, api_key)
+        with pipeline.lock:
+            project["episodes"][key] = episode_data
+        return None
+
+    return {"llm": write_script, "tts": render}
+
+
+def refresh_episode_notes(
+    project: Dict,
+    episode_data: Dict,
+    api_key: Optional[str],
+    overwrite: bool = False
+) -> str:
+    """Generate notes for an episode unless that would discard manual edits.
+
+    Returns "generated", "edited" (kept the user's notes), "budget" or "failed".
+    Narration episodes get notes from their own text, without the LLM.
+    """
+    previous = episode_data.get("notes")
+    if previous and previous.get("edited") and not overwrite:
+        return "edited"
+
+    if is_narration(project):
+        episode_info = outline_episode(project, episode_data["episode"]) or {}
+        notes = narration_notes(episode_data, episode_info.get("text", ""))
+    elif budget_exceeded(project):
+        return "budget"
+    else:
+        notes = generate_episode_notes(episode_data, project["style"], api_key, project["usage"])
+    if not notes:
+        return "failed"
+
+    episode_data["notes"] = notes
+    save_notes(Path(project["dir"]) / f"episode_{episode_data['episode']:02d}", notes)
+    return "generated"
 
 
 def speak_macos(text: str, voice: str, output_path: Path, rate: int = 180) -> bool:
@@ -342,6 +632,7 @@ def generate_episode_audio(
 
     # Generate audio for each segment
     segment_files = []
+    rendered = []
     total = len(segments)
 
     for i, seg in enumerate(segments):
@@ -356,10 +647,15 @@ def generate_episode_audio(
         seg_path = episode_dir / f"seg_{i:04d}.wav"
         if speak_macos(text, voice, seg_path, rate):
             segment_files.append(seg_path)
+            rendered.append(seg)
 
     if not segment_files:
         return None
 
+    # Timings for chapter markers, taken before the segments are removed
+    manifest = build_manifest(rendered, [wav_duration(f) for f in segment_files])
+    (episode_dir / MANIFEST_FILE).write_text(json.dumps(manifest, ensure_ascii=False, indent=2))
+
     # Concatenate
     output_path = episode_dir / f"episode_{episode_num:02d}.wav"
     if concatenate_audio(segment_files, output_path):
@@ -382,16 +678,21 @@ class PodcastFactoryHandler(SimpleHTTPRequestHandler):
         parsed = urlparse(self.path)
 
         if parsed.path == "/api/projects":
-            self._json_response(200, {"projects": list(projects.keys())})
+            self._json_response(200, {
+                "projects": list(projects.keys()),
+                "paused": [pid for pid, p in projects.items() if p.get("status") == "paused"],
+                "awaiting_review": [pid for pid, p in projects.items() if p.get("status") == "awaiting_review"]
+            })
         elif parsed.path == "/api/project":
             params = parse_qs(parsed.query)
             pid = params.get("id", [None])[0]
             if pid and pid in projects:
-                self._json_response(200, projects[pid])
+                with pipeline.lock:
+                    self._json_response(200, projects[pid])
             else:
                 self._json_response(404, {"error": "Project not found"})
         elif parsed.path == "/api/voices":
-            self._json_response(200, {"voices": VOICES})
+            self._json_response(200, {"voices": VOICES, "favorites": favorite_voices()})
         elif parsed.path == "/api/formats":
             # Report supported formats
             formats = [".txt"]
@@ -416,6 +717,26 @@ class PodcastFactoryHandler(SimpleHTTPRequestHandler):
             self._handle_generate_episode()
         elif parsed.path == "/api/generate-all":
             self._handle_generate_all()
+        elif parsed.path == "/api/pause":
+            self._handle_pause()
+        elif parsed.path == "/api/resume":
+            self._handle_resume()
+        elif parsed.path == "/api/concurrency":
+            self._handle_concurrency()
+        elif parsed.path == "/api/series-settings":
+            self._handle_series_settings()
+        elif parsed.path == "/api/update-script":
+            self._handle_update_script()
+        elif parsed.path == "/api/script-diff":
+            self._handle_script_diff()
+        elif parsed.path == "/api/approve-scripts":
+            self._handle_approve_scripts()
+        elif parsed.path == "/api/generate-notes":
+            self._handle_generate_notes()
+        elif parsed.path == "/api/update-notes":
+            self._handle_update_notes()
+        elif parsed.path == "/api/publish":
+            self._handle_publish()
         elif parsed.path == "/api/preview-voice":
             self._handle_preview_voice()
         elif parsed.path == "/api/open-folder":
@@ -506,6 +827,23 @@ class PodcastFactoryHandler(SimpleHTTPRequestHandler):
             self._json_response(400, {"error": "Invalid JSON"})
             return
 
+        mode = data.get("mode", MODE_DIALOGUE)
+        if mode not in (MODE_DIALOGUE, MODE_NARRATION):
+            self._json_response(400, {"error": f"Unknown mode: {mode}"})
+            return
+
+        if mode == MODE_NARRATION:
+            personas = [{
+                "name": "Narrator",
+                "personality": "Reads the book aloud",
+                "voice": data.get("narrator_voice") or VOICES["narrator"]
+            }]
+        else:
+            personas = data.get("personas", [
+                {"name": "Host A", "personality": "Curious and engaging host", "voice": "Samantha"},
+                {"name": "Host B", "personality": "Knowledgeable expert", "voice": "Daniel"}
+            ])
+
         project_id = str(uuid.uuid4())[:8]
         project_dir = OUTPUT_DIR / project_id
         project_dir.mkdir(exist_ok=True)
@@ -513,19 +851,23 @@ class PodcastFactoryHandler(SimpleHTTPRequestHandler):
         projects[project_id] = {
             "id": project_id,
             "name": data.get("name", "Untitled"),
+            "mode": mode,
             "book_content": data.get("book_content", ""),
             "book_filename": data.get("book_filename", ""),
             "num_episodes": data.get("num_episodes", 10),
             "style": data.get("style", "conversational"),
-            "personas": data.get("personas", [
-                {"name": "Host A", "personality": "Curious and engaging host", "voice": "Samantha"},
-                {"name": "Host B", "personality": "Knowledgeable expert", "voice": "Daniel"}
-            ]),
+            "personas": personas,
             "outline": None,
             "episodes": {},
+            "usage": new_usage(),
+            "token_budget": int(data.get("token_budget") or 0),
+            "review_required": bool(data.get("review_required")),
             "status": "created",
+            "paused": False,
+            "concurrency": dict(pipeline.DEFAULT_CONCURRENCY),
             "dir": str(project_dir)
         }
+        save_project(projects[project_id])
 
         self._json_response(200, {"project_id": project_id})
 
@@ -542,23 +884,41 @@ class PodcastFactoryHandler(SimpleHTTPRequestHandler):
             self._json_response(404, {"error": "Project not found"})
             return
 
+        project = projects[project_id]
+        if is_narration(project):
+            # Chapters come from the book itself
+            outline = narration_outline(project["book_content"], project["num_episodes"])
+            if not outline["episodes"]:
+                self._json_response(400, {"error": "The book has no text to narrate"})
+                return
+            project["outline"] = outline
+            project["status"] = "outline_ready"
+            save_project(project)
+            self._json_response(200, {"outline": outline})
+            return
+
         if not api_key:
             self._json_response(400, {"error": "API key required"})
             return
 
-        project = projects[project_id]
+        if budget_exceeded(project):
+            self._json_response(400, {"error": "Token budget exhausted"})
+            return
+
         project["status"] = "generating_outline"
 
         outline = generate_outline(
             project["book_content"],
             project["num_episodes"],
             project["style"],
-            api_key
+            api_key,
+            project["usage"]
         )
 
         if outline:
             project["outline"] = outline
             project["status"] = "outline_ready"
+            save_project(project)
             self._json_response(200, {"outline": outline})
         else:
             project["status"] = "error"
@@ -585,36 +945,31 @@ class PodcastFactoryHandler(SimpleHTTPRequestHandler):
             self._json_response(400, {"error": "Generate outline first"})
             return
 
-        # Find episode info
-        episode_info = None
-        for ep in project["outline"]["episodes"]:
-            if ep["episode"] == episode_num:
-                episode_info = ep
-                break
-
+        episode_info = outline_episode(project, episode_num)
         if not episode_info:
             self._json_response(404, {"error": "Episode not found in outline"})
             return
 
+        if not is_narration(project) and budget_exceeded(project):
+            self._json_response(400, {"error": "Token budget exhausted"})
+            return
+
         # Generate script
-        script_result = generate_episode_script(
-            episode_info,
-            project["book_content"],
-            project["personas"],
-            project["style"],
-            api_key
-        )
+        script_result = build_episode_script(project, episode_info, api_key)
 
         if not script_result:
             self._json_response(500, {"error": "Failed to generate script"})
             return
 
+        previous = project["episodes"].get(str(episode_num), {})
         episode_data = {
             "episode": episode_num,
             "title": episode_info["title"],
             "script": script_result["script"],
             "segments": script_result["segments"],
-            "audio_path": None
+            "audio_path": None,
+            "manifest": None,
+            "notes": previous.get("notes")
         }
 
         # Generate audio if requested
@@ -629,6 +984,7 @@ class PodcastFactoryHandler(SimpleHTTPRequestHandler):
             )
             if audio_path:
                 episode_data["audio_path"] = str(audio_path)
+                episode_data["manifest"] = load_manifest(audio_path.parent)
 
         project["episodes"][str(episode_num)] = episode_data
 
@@ -637,7 +993,15 @@ class PodcastFactoryHandler(SimpleHTTPRequestHandler):
         script_path.parent.mkdir(exist_ok=True)
         script_path.write_text(script_result["script"])
 
-        self._json_response(200, {"episode": episode_data})
+        # Manual edits survive; the UI offers /api/generate-notes to replace them
+        notes_status = refresh_episode_notes(project, episode_data, api_key)
+        save_project(project)
+
+        self._json_response(200, {
+            "episode": episode_data,
+            "notes_status": notes_status,
+            "usage": project["usage"]
+        })
 
     def _handle_generate_all(self):
         """Generate all episodes in background."""
@@ -659,57 +1023,253 @@ class PodcastFactoryHandler(SimpleHTTPRequestHandler):
             self._json_response(400, {"error": "Generate outline first"})
             return
 
-        def generate_all_episodes():
-            project["status"] = "generating_episodes"
-            total = len(project["outline"]["episodes"])
-
-            for i, ep_info in enumerate(project["outline"]["episodes"]):
-                project["current_episode"] = ep_info["episode"]
-                project["progress"] = f"Episode {i+1}/{total}"
-
-                script_result = generate_episode_script(
-                    ep_info,
-                    project["book_content"],
-                    project["personas"],
-                    project["style"],
-                    api_key
-                )
-
-                if script_result:
-                    episode_data = {
-                        "episode": ep_info["episode"],
-                        "title": ep_info["title"],
-                        "script": script_result["script"],
-                        "segments": script_result["segments"],
-                        "audio_path": None
-                    }
-
-                    # Generate audio
-                    project_dir = Path(project["dir"])
-                    audio_path = generate_episode_audio(
-                        ep_info["episode"],
-                        script_result["segments"],
-                        project["personas"],
-                        project_dir
-                    )
-                    if audio_path:
-                        episode_data["audio_path"] = str(audio_path)
-
-                    project["episodes"][str(ep_info["episode"])] = episode_data
-
-                    # Save script
-                    script_path = project_dir / f"episode_{ep_info['episode']:02d}" / "script.md"
-                    script_path.parent.mkdir(exist_ok=True)
-                    script_path.write_text(script_result["script"])
-
-            project["status"] = "completed"
-            project["progress"] = "All episodes generated"
-
-        thread = threading.Thread(target=generate_all_episodes)
-        thread.start()
+        if pipeline.is_running(project_id):
+            self._json_response(409, {"error": "Generation is already running"})
+            return
+
+        pipeline.new_run(project, [ep["episode"] for ep in project["outline"]["episodes"]])
+        pipeline.start(project, pipeline_workers(project, api_key), lambda: save_project(project))
 
         self._json_response(200, {"status": "started", "total_episodes": len(project["outline"]["episodes"])})
 
+    def _run_project(self, data: Dict) -> Optional[Dict]:
+        """Look up a project that has a generate-all run, replying with the error if not."""
+        project = projects.get(data.get("project_id"))
+        if not project:
+            self._json_response(404, {"error": "Project not found"})
+            return None
+        if not project.get("pipeline"):
+            self._json_response(400, {"error": "Start a run with Generate All first"})
+            return None
+        return project
+
+    def _handle_pause(self):
+        """Stop dispatching new scripts and renders; items in flight finish."""
+        data = self._read_json()
+        if not data:
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        project = self._run_project(data)
+        if not project:
+            return
+        if not pipeline.unfinished(project):
+            self._json_response(400, {"error": "The run has finished"})
+            return
+        pipeline.pause(project)
+        save_project(project)
+        self._json_response(200, {"status": project["status"], "progress": project["progress"]})
+
+    def _handle_resume(self):
+        """Check the provider and voices, then continue a paused run.
+
+        Episodes that cannot continue are marked failed with the reason and
+        the rest of the queue carries on; failed episodes are retried.
+        """
+        data = self._read_json()
+        if not data:
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        project = self._run_project(data)
+        if not project:
+            return
+        api_key = data.get("api_key") or os.environ.get("OPENAI_API_KEY")
+
+        problems = revalidate(project, api_key)
+        pipeline.resume(project, problems, pipeline_workers(project, api_key), lambda: save_project(project))
+        save_project(project)
+        self._json_response(200, {"status": project["status"], "problems": problems})
+
+    def _handle_concurrency(self):
+        """Set how many LLM requests or TTS renders may be in flight."""
+        data = self._read_json()
+        if not data:
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        project = projects.get(data.get("project_id"))
+        if not project:
+            self._json_response(404, {"error": "Project not found"})
+            return
+        stage = data.get("stage")
+        if stage not in pipeline.STAGES:
+            self._json_response(400, {"error": f"Unknown stage: {stage}"})
+            return
+        try:
+            value = pipeline.set_concurrency(project, stage, data.get("value", 1))
+        except (TypeError, ValueError):
+            self._json_response(400, {"error": "Invalid value"})
+            return
+        save_project(project)
+        self._json_response(200, {"concurrency": project["concurrency"], "stage": stage, "value": value})
+
+    def _handle_series_settings(self):
+        """Change series settings that apply mid-run: script review.
+
+        Turning review off releases the scripts waiting for approval.
+        """
+        data = self._read_json()
+        if not data or not isinstance(data.get("review_required"), bool):
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        project = projects.get(data.get("project_id"))
+        if not project:
+            self._json_response(404, {"error": "Project not found"})
+            return
+        api_key = data.get("api_key") or os.environ.get("OPENAI_API_KEY")
+
+        with pipeline.lock:
+            project["review_required"] = data["review_required"]
+        approved = []
+        if not project["review_required"]:
+            approved = pipeline.approve(project, None, pipeline_workers(project, api_key), lambda: save_project(project))
+        save_project(project)
+        self._json_response(200, {"review_required": project["review_required"], "approved": approved})
+
+    def _handle_update_script(self):
+        """Save an edited script before the episode is rendered."""
+        data = self._read_json()
+        if not data or not isinstance(data.get("script"), str):
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        episode = self._find_episode(data)
+        if not episode:
+            return
+        project = projects[data["project_id"]]
+        key = str(episode["episode"])
+
+        segments = script_segments(project, data["script"])
+        if not segments:
+            self._json_response(400, {"error": "The script has no lines to speak"})
+            return
+
+        with pipeline.lock:
+            entry = pipeline.entries(project).get(key)
+            blocked = edit_blocked(episode, entry and entry["state"])
+            if not blocked:
+                apply_script_edit(episode, data["script"], segments)
+        if blocked:
+            self._json_response(409, {"error": blocked})
+            return
+
+        script_path = Path(project["dir"]) / f"episode_{episode['episode']:02d}" / "script.md"
+        script_path.parent.mkdir(exist_ok=True)
+        script_path.write_text(episode["script"])
+        save_project(project)
+        self._json_response(200, {
+            "segments": len(segments),
+            "edited": episode["script_edited"]
+        })
+
+    def _handle_script_diff(self):
+        """The edits to an episode's script, against what the LLM wrote."""
+        data = self._read_json()
+        if not data:
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        episode = self._find_episode(data)
+        if not episode:
+            return
+        with pipeline.lock:
+            diff = script_diff(episode)
+        self._json_response(200, {"diff": diff, "edited": bool(diff)})
+
+    def _handle_approve_scripts(self):
+        """Approve reviewed scripts for rendering: the listed episodes, or all."""
+        data = self._read_json()
+        if not data:
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        project = self._run_project(data)
+        if not project:
+            return
+        api_key = data.get("api_key") or os.environ.get("OPENAI_API_KEY")
+
+        episodes = data.get("episodes")
+        keys = None if episodes is None else [str(n) for n in episodes]
+        approved = pipeline.approve(project, keys, pipeline_workers(project, api_key), lambda: save_project(project))
+        save_project(project)
+        self._json_response(200, {"approved": approved, "status": project["status"]})
+
+    def _find_episode(self, data: Dict) -> Optional[Dict]:
+        """Look up the episode named by project_id/episode_num, replying 404 if missing."""
+        project = projects.get(data.get("project_id"))
+        episode = project and project["episodes"].get(str(data.get("episode_num")))
+        if not episode:
+            self._json_response(404, {"error": "Episode not found"})
+            return None
+        return episode
+
+    def _handle_generate_notes(self):
+        """Regenerate listener notes; edited notes need "overwrite": true."""
+        data = self._read_json()
+        if not data:
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        episode = self._find_episode(data)
+        if not episode:
+            return
+        project = projects[data["project_id"]]
+        api_key = data.get("api_key") or os.environ.get("OPENAI_API_KEY")
+        if not api_key and not is_narration(project):
+            self._json_response(400, {"error": "API key required"})
+            return
+
+        status = refresh_episode_notes(project, episode, api_key, bool(data.get("overwrite")))
+        if status == "edited":
+            self._json_response(409, {
+                "error": "Notes were edited manually",
+                "needs_confirmation": True
+            })
+        elif status == "budget":
+            self._json_response(400, {"error": "Token budget exhausted"})
+        elif status == "failed":
+            self._json_response(500, {"error": "Failed to generate notes"})
+        else:
+            save_project(project)
+            self._json_response(200, {"notes": episode["notes"], "usage": project["usage"]})
+
+    def _handle_update_notes(self):
+        """Save inline edits to an episode's notes."""
+        data = self._read_json()
+        if not data or not isinstance(data.get("notes"), dict):
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        episode = self._find_episode(data)
+        if not episode:
+            return
+        project = projects[data["project_id"]]
+
+        episode["notes"] = apply_edits(episode.get("notes"), data["notes"])
+        save_notes(Path(project["dir"]) / f"episode_{episode['episode']:02d}", episode["notes"])
+        save_project(project)
+        self._json_response(200, {"notes": episode["notes"]})
+
+    def _handle_publish(self):
+        """Export tagged MP3s and the RSS feed."""
+        data = self._read_json()
+        if not data:
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        project = projects.get(data.get("project_id"))
+        if not project:
+            self._json_response(404, {"error": "Project not found"})
+            return
+
+        feed_path = publish_project(project, data.get("base_url", ""))
+        if feed_path:
+            self._json_response(200, {"feed_path": str(feed_path)})
+        else:
+            self._json_response(500, {"error": "No rendered episodes could be exported (is ffmpeg installed?)"})
+
     def _handle_preview_voice(self):
         """Preview a voice with sample text."""
         data = self._read_json()
@@ -757,6 +1317,7 @@ class PodcastFactoryHandler(SimpleHTTPRequestHandler):
 
 def run_server(port: int = 8082):
     """Run the HTTP server."""
+    load_projects()
     server = HTTPServer(("127.0.0.1", port), PodcastFactoryHandler)
     print(f"Podcast Factory server running on http://127.0.0.1:{port}")
     server.serve_forever()
@@ -0,0 +1,141 @@
+"""
+Publishing: MP3 export with ID3 tags and the podcast RSS feed.
+
+Episodes are rendered as WAV; publishing encodes each one to MP3 (ffmpeg),
+tags it with the episode title and notes through id3_tags (the same frames
+the studio's other apps write), and writes feed.xml next to
+the episode folders. Enclosure URLs are relative to the feed unless a
+base URL is given.
+"""
+
+import email.utils
+import subprocess
+import time
+from pathlib import Path
+from typing import Dict, List, Optional
+from xml.sax.saxutils import escape
+
+from id3_tags import write_mp3_tags
+from show_notes import notes_as_text
+
+FEED_FILE = "feed.xml"
+
+
+def encode_mp3(wav_path: Path, mp3_path: Path, tags: Dict[str, str]) -> bool:
+    """Encode WAV to MP3 with ffmpeg, then write the tags with id3_tags.
+
+    ffmpeg is told to write no tag of its own, so its frame choices (and
+    any metadata carried over from the WAV) never end up in the file.
+    """
+    cmd = ['ffmpeg', '-y', '-loglevel', 'error', '-i', str(wav_path),
+           '-map_metadata', '-1', '-codec:a', 'libmp3lame', '-q:a', '2',
+           '-id3v2_version', '0', '-write_id3v1', '0',
+           str(mp3_path)]
+    try:
+        result = subprocess.run(cmd, capture_output=True, timeout=600)
+    except (OSError, subprocess.TimeoutExpired) as e:
+        print(f"ffmpeg error: {e}")
+        return False
+    if result.returncode != 0:
+        print(f"ffmpeg error: {result.stderr.decode(errors='replace')}")
+        return False
+    if not mp3_path.exists():
+        return False
+    try:
+        write_mp3_tags(mp3_path, tags)
+    except OSError as e:
+        print(f"Tagging error: {e}")
+        return False
+    return True
+
+
+def episode_tags(project: Dict, episode: Dict) -> Dict[str, str]:
+    notes = episode.get("notes") or {}
+    return {
+        "title": notes.get("title") or episode["title"],
+        "album": project["name"],
+        "artist": ", ".join(p["name"] for p in project.get("personas", [])),
+        "track": str(episode["episode"]),
+        "genre": "Podcast",
+        "comment": notes_as_text(notes) if notes else "",
+    }
+
+
+def notes_as_html(notes: Dict) -> str:
+    parts = [f"<p>{escape(notes.get('description', ''))}</p>"]
+    if notes.get("show_notes"):
+        items = "".join(
+            f"<li>{escape(n['time'] + ' ' if n.get('time') else '')}{escape(n['text'])}</li>"
+            for n in notes["show_notes"]
+        )
+        parts.append(f"<ul>{items}</ul>")
+    return "".join(parts)
+
+
+def build_rss(project: Dict, published: List[Dict], base_url: str = "") -> str:
+    """RSS 2.0 feed for the published episodes.
+
+    Each entry of `published` is the episode dict plus "mp3_path",
+    "mp3_size", "duration" and "pub_date" (unix seconds).
+    """
+    base = base_url.rstrip('/') + '/' if base_url else ""
+    items = []
+    for ep in published:
+        notes = ep.get("notes") or {}
+        title = notes.get("title") or ep["title"]
+        mp3 = Path(ep["mp3_path"])
+        url = escape(f"{base}{mp3.parent.name}/{mp3.name}", {'"': "&quot;"})
+        items.append(f"""    <item>
+      <title>{escape(title)}</title>
+      <description><![CDATA[{notes_as_html(notes) if notes else ''}]]></description>
+      <itunes:summary>{escape(notes.get('description', ''))}</itunes:summary>
+      <itunes:episode>{ep['episode']}</itunes:episode>
+      <itunes:duration>{int(ep.get('duration', 0))}</itunes:duration>
+      <enclosure url="{url}" length="{ep['mp3_size']}" type="audio/mpeg"/>
+      <guid isPermaLink="false">{escape(project['id'])}-{ep['episode']}</guid>
+      <pubDate>{email.utils.formatdate(ep['pub_date'], usegmt=True)}</pubDate>
+    </item>""")
+
+    return f"""<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+  <channel>
+    <title>{escape(project['name'])}</title>
+    <description>{escape(project.get('description') or project['name'])}</description>
+    <language>{escape(project.get('language', 'en'))}</language>
+    <lastBuildDate>{email.utils.formatdate(time.time(), usegmt=True)}</lastBuildDate>
+{chr(10).join(items)}
+  </channel>
+</rss>
+"""
+
+
+def publish_project(project: Dict, base_url: str = "") -> Optional[Path]:
+    """Export every rendered episode as tagged MP3 and write the feed.
+
+    Returns the feed path, or None if no episode could be exported.
+    """
+    project_dir = Path(project["dir"])
+    published = []
+    episodes = sorted(project["episodes"].values(), key=lambda e: e["episode"])
+    for ep in episodes:
+        if not ep.get("audio_path"):
+            continue
+        wav = Path(ep["audio_path"])
+        mp3 = wav.with_suffix('.mp3')
+        if not wav.exists() or not encode_mp3(wav, mp3, episode_tags(project, ep)):
+            continue
+        published.append(dict(
+            ep,
+            mp3_path=str(mp3),
+            mp3_size=mp3.stat().st_size,
+            duration=(ep.get("manifest") or {}).get("duration", 0),
+            pub_date=int(wav.stat().st_mtime),
+        ))
+        ep["mp3_path"] = str(mp3)
+
+    if not published:
+        return None
+
+    feed_path = project_dir / FEED_FILE
+    feed_path.write_text(build_rss(project, published, base_url), encoding="utf-8")
+    return feed_path
@@ -0,0 +1,70 @@
+"""
+ID3v2 tags for published MP3s.
+
+Python port of the ID3v2.3 writer in mofa-widgets/src/audio_tags.rs
+(`encode_id3v2`), which is the reference. Only the frames `feed.episode_tags`
+fills are ported: UTF-16 text frames, written at the start of the file. Both
+writers are tested against mofa-widgets/tests/fixtures/episode.id3.
+
+| Key       | Frame |
+|-----------|-------|
+| title     | TIT2  |
+| artist    | TPE1  |
+| album     | TALB  |
+| genre     | TCON  |
+| track     | TRCK  |
+| comment   | COMM (language "eng", empty description) |
+"""
+
+import os
+import struct
+from pathlib import Path
+from typing import Dict
+
+TEXT_FRAMES = [("title", b"TIT2"), ("artist", b"TPE1"), ("album", b"TALB"), ("genre", b"TCON")]
+
+
+def _syncsafe(size: int) -> bytes:
+    return bytes([(size >> 21) & 0x7f, (size >> 14) & 0x7f, (size >> 7) & 0x7f, size & 0x7f])
+
+
+def _utf16_with_bom(text: str) -> bytes:
+    return b"\xff\xfe" + text.encode("utf-16-le")
+
+
+def _frame(frame_id: bytes, body: bytes) -> bytes:
+    return frame_id + struct.pack(">I", len(body)) + b"\0\0" + body
+
+
+def encode_id3v2(tags: Dict) -> bytes:
+    """ID3v2.3 tag for `tags`; empty values are left out."""
+    frames = b""
+    for key, frame_id in TEXT_FRAMES:
+        if tags.get(key):
+            frames += _frame(frame_id, b"\x01" + _utf16_with_bom(tags[key]))
+    if tags.get("track") not in (None, ""):
+        frames += _frame(b"TRCK", b"\x01" + _utf16_with_bom(str(tags["track"])))
+    if tags.get("comment"):
+        # encoding, language, empty description (BOM + terminator), text
+        body = b"\x01" + b"eng" + b"\xff\xfe\0\0" + _utf16_with_bom(tags["comment"])
+        frames += _frame(b"COMM", body)
+    return b"ID3" + bytes([3, 0, 0]) + _syncsafe(len(frames)) + frames
+
+
+def _existing_id3_len(data: bytes) -> int:
+    if len(data) < 10 or not data.startswith(b"ID3"):
+        return 0
+    footer = 10 if data[5] & 0x10 else 0
+    size = 0
+    for b in data[6:10]:
+        size = (size << 7) | (b & 0x7f)
+    return 10 + size + footer
+
+
+def write_mp3_tags(path: Path, tags: Dict):
+    """Replace the MP3's ID3v2 tag, writing next to it and then swapping."""
+    data = path.read_bytes()
+    audio = data[min(_existing_id3_len(data), len(data)):]
+    tmp = path.with_suffix(".tagging.tmp")
+    tmp.write_bytes(encode_id3v2(tags) + audio)
+    os.replace(tmp, path)
@@ -0,0 +1,196 @@
+"""
+Straight narration: the book read aloud, without LLM dialogue.
+
+The outline comes from the book's own chapter headings (or an even split
+when it has none), each episode's script is the cleaned chapter text, and
+the text is cut into TTS-sized chunks that end on sentence breaks so no
+`say` call or API request runs over its length limit. Episode notes are
+derived from the text and the audio manifest, so these episodes spend no
+tokens.
+"""
+
+import re
+import time
+from typing import Dict, List, Optional
+
+from show_notes import format_timestamp
+
+NARRATOR_ROLE = "Narrator"
+
+# Longest text sent to the TTS backend in one call
+MAX_CHUNK_CHARS = 1500
+
+# A sentence up to its end punctuation (Latin before a space, or CJK) and
+# any closing quotes, or the unterminated rest of the paragraph
+SENTENCE = re.compile(r'.+?(?:[.!?…]+["\'”’)\]]*(?=\s|$)|[。！？]+["”’)]*|$)')
+
+CHAPTER_HEADING = re.compile(
+    r'^\s*(?:(?:chapter|part|book)\s+(?:\d+|[ivxlcdm]+|[a-z]+)\b.*'
+    r'|第[\d一二三四五六七八九十百千零]+[章节回卷].*)$',
+    re.IGNORECASE,
+)
+PAGE_NUMBER = re.compile(r'^\s*(?:page\s+)?\d+\s*$', re.IGNORECASE)
+
+# Characters of chapter text used for the generated description
+DESCRIPTION_CHARS = 300
+
+
+def clean_text(text: str) -> str:
+    """Undo print layout: page numbers, hyphenated and hard-wrapped lines."""
+    lines = [l for l in text.replace('\r\n', '\n').split('\n') if not PAGE_NUMBER.match(l)]
+    text = '\n'.join(lines)
+    text = re.sub(r'(\w)-\n(\w)', r'\1\2', text)
+    paragraphs = re.split(r'\n\s*\n', text)
+    cleaned = [re.sub(r'\s+', ' ', p).strip() for p in paragraphs]
+    return '\n\n'.join(p for p in cleaned if p)
+
+
+def split_chapters(text: str, num_episodes: int) -> List[Dict[str, str]]:
+    """Chapters as {"title", "text"}, by headings or an even split."""
+    chapters = []
+    title, body = None, []
+    for line in text.replace('\r\n', '\n').split('\n'):
+        if CHAPTER_HEADING.match(line) and len(line.strip()) <= 80:
+            if title is not None or ''.join(body).strip():
+                chapters.append({"title": title or "Opening", "text": '\n'.join(body)})
+            title, body = line.strip(), []
+        else:
+            body.append(line)
+    chapters.append({"title": title or "Opening", "text": '\n'.join(body)})
+    chapters = [
+        {"title": c["title"], "text": clean_text(c["text"])}
+        for c in chapters
+    ]
+    chapters = [c for c in chapters if c["text"]]
+
+    if len(chapters) >= 2:
+        return chapters
+    return split_evenly(clean_text(text), max(1, num_episodes))
+
+
+def split_evenly(text: str, parts: int) -> List[Dict[str, str]]:
+    """Split at paragraph breaks into about `parts` equal parts."""
+    paragraphs = [p for p in text.split('\n\n') if p]
+    if not paragraphs:
+        return []
+    target = sum(len(p) for p in paragraphs) / min(parts, len(paragraphs))
+    chapters, current = [], []
+    for p in paragraphs:
+        current.append(p)
+        if sum(len(c) for c in current) >= target and len(chapters) < parts - 1:
+            chapters.append(current)
+            current = []
+    if current:
+        chapters.append(current)
+    return [
+        {"title": f"Part {i + 1}", "text": '\n\n'.join(c)}
+        for i, c in enumerate(chapters)
+    ]
+
+
+def split_sentences(paragraph: str) -> List[str]:
+    return [s.strip() for s in SENTENCE.findall(paragraph) if s.strip()]
+
+
+def chunk_text(text: str, max_chars: int = MAX_CHUNK_CHARS) -> List[str]:
+    """Pack sentences into chunks of at most `max_chars`.
+
+    Chunks end at sentence breaks; paragraphs start a new chunk. A single
+    sentence longer than the limit is split at commas or spaces, never
+    inside a word.
+    """
+    chunks = []
+    for paragraph in text.split('\n\n'):
+        current = ""
+        for sentence in split_sentences(paragraph):
+            for piece in split_long(sentence, max_chars):
+                if current and len(current) + 1 + len(piece) > max_chars:
+                    chunks.append(current)
+                    current = piece
+                else:
+                    current = f"{current} {piece}" if current else piece
+        if current:
+            chunks.append(current)
+    return chunks
+
+
+def split_long(sentence: str, max_chars: int) -> List[str]:
+    if len(sentence) <= max_chars:
+        return [sentence]
+    pieces = []
+    rest = sentence
+    while len(rest) > max_chars:
+        window = rest[:max_chars + 1]
+        cut = max(window.rfind(', '), window.rfind('; '), window.rfind('，'))
+        if cut > max_chars // 2:
+            cut += 1
+        else:
+            cut = window.rfind(' ')
+        if cut <= 0:
+            cut = max_chars  # One unbroken run of text (e.g. CJK without commas)
+        pieces.append(rest[:cut].strip())
+        rest = rest[cut:].strip()
+    if rest:
+        pieces.append(rest)
+    return pieces
+
+
+def narration_outline(book_content: str, num_episodes: int) -> Dict:
+    """Outline with one episode per chapter, carrying the chapter text."""
+    episodes = []
+    for i, chapter in enumerate(split_chapters(book_content, num_episodes)):
+        words = len(chapter["text"].split())
+        episodes.append({
+            "episode": i + 1,
+            "title": chapter["title"],
+            "theme": "Straight narration",
+            "key_points": [],
+            # Roughly 150 spoken words per minute
+            "duration_minutes": max(1, round(words / 150)),
+            "text": chapter["text"],
+        })
+    return {"episodes": episodes}
+
+
+def narration_script(episode_info: Dict, voice: str) -> Dict:
+    """Script and single-narrator segments for an outline episode."""
+    text = episode_info.get("text", "")
+    return {
+        "script": f"# {episode_info['title']}\n\n{text}\n",
+        "segments": [
+            {"role": NARRATOR_ROLE, "text": chunk, "voice": voice}
+            for chunk in chunk_text(text)
+        ],
+    }
+
+
+def narration_segments(script: str, voice: str) -> List[Dict]:
+    """Segments for an edited narration script; its "# title" line is not read."""
+    text = re.sub(r'\A\s*#[^\n]*\n', '', script)
+    return [{"role": NARRATOR_ROLE, "text": chunk, "voice": voice} for chunk in chunk_text(text)]
+
+
+def narration_notes(episode_data: Dict, chapter_text: str) -> Optional[Dict]:
+    """Notes from the chapter itself: its opening as the description and
+    the manifest's chapter markers as show notes."""
+    sentences = split_sentences(chapter_text.split('\n\n')[0]) if chapter_text else []
+    description = ""
+    for sentence in sentences:
+        if description and len(description) + len(sentence) > DESCRIPTION_CHARS:
+            break
+        description = f"{description} {sentence}".strip()
+    if not description:
+        return None
+
+    chapters = (episode_data.get("manifest") or {}).get("chapters", [])
+    return {
+        "title": episode_data["title"],
+        "titles": [],
+        "description": description,
+        "show_notes": [
+            {"time": format_timestamp(c["start"]), "text": c["title"]}
+            for c in chapters
+        ],
+        "edited": False,
+        "generated_at": int(time.time()),
+    }
@@ -0,0 +1,236 @@
+"""
+Generate-all pipeline: scripts from the LLM, then audio from `say`.
+
+The two stages run side by side with their own concurrency limits, so the
+next scripts are written while earlier episodes render. Each episode moves
+queued -> scripting -> scripted -> rendering -> done, or to failed with an
+error; one failed episode never stops the others. When the series requires
+script review (project["review_required"]), a written script stops in
+review until the user approves it, and only then waits for rendering.
+
+Pausing stops the dispatcher from starting new work; items already in
+flight finish. The states, the paused flag and the limits live in the
+project dict (project["pipeline"], project["paused"],
+project["concurrency"]), so they are saved with it and shown by the page.
+"""
+
+import threading
+from typing import Callable, Dict, Iterable, List, Optional
+
+QUEUED = "queued"
+SCRIPTING = "scripting"
+SCRIPTED = "scripted"
+REVIEW = "review"
+RENDERING = "rendering"
+DONE = "done"
+FAILED = "failed"
+
+# Stage -> (waiting state, running state, finished state)
+STAGES = {
+    "llm": (QUEUED, SCRIPTING, SCRIPTED),
+    "tts": (SCRIPTED, RENDERING, DONE),
+}
+
+DEFAULT_CONCURRENCY = {"llm": 2, "tts": 1}
+MAX_CONCURRENCY = 8
+
+# A stage worker takes the episode key ("3") and returns an error message,
+# or None when the episode may move on
+Worker = Callable[[str], Optional[str]]
+
+# Guards every project's pipeline state; hold it to read or change a
+# project while a run may be going
+lock = threading.Condition()
+_dispatchers: Dict[str, "Dispatcher"] = {}
+
+
+def new_run(project: Dict, episode_nums: Iterable[int]):
+    """Queue every episode for a fresh run."""
+    project["pipeline"] = {
+        "episodes": {str(n): {"state": QUEUED, "error": None, "stage": None} for n in episode_nums}
+    }
+    project.setdefault("concurrency", dict(DEFAULT_CONCURRENCY))
+    project["paused"] = False
+    update_status(project)
+
+
+def entries(project: Dict) -> Dict[str, Dict]:
+    return (project.get("pipeline") or {}).get("episodes", {})
+
+
+def unfinished(project: Dict) -> bool:
+    return any(e["state"] not in (DONE, FAILED) for e in entries(project).values())
+
+
+def update_status(project: Dict):
+    """Derive project["status"] and the progress line from the episode states."""
+    states = [e["state"] for e in entries(project).values()]
+    done = states.count(DONE)
+    failed = states.count(FAILED)
+    running = states.count(SCRIPTING) + states.count(RENDERING)
+    review = states.count(REVIEW)
+    summary = f"{done}/{len(states)} done" + (f", {failed} failed" if failed else "")
+    if review:
+        summary += f", {review} awaiting review"
+
+    if not unfinished(project):
+        project["status"] = "completed"
+        project["progress"] = "All episodes generated" if not failed else summary
+    elif project.get("paused"):
+        project["status"] = "paused"
+        project["progress"] = f"Pausing: {running} in flight, {summary}" if running else f"Paused: {summary}"
+    elif review and not running and not (states.count(QUEUED) or states.count(SCRIPTED)):
+        # Nothing can move until a script is approved
+        project["status"] = "awaiting_review"
+        project["progress"] = f"Waiting for script approval: {summary}"
+    else:
+        project["status"] = "generating_episodes"
+        project["progress"] = (
+            f"{summary}: {states.count(SCRIPTING)} scripting, {states.count(RENDERING)} rendering"
+        )
+
+
+def recover(project: Dict):
+    """After a restart, put interrupted items back in line.
+
+    A run that was going when the server stopped comes back paused: the API
+    key only lives in memory, so the user has to resume it.
+    """
+    for entry in entries(project).values():
+        if entry["state"] == SCRIPTING:
+            entry["state"] = QUEUED
+        elif entry["state"] == RENDERING:
+            entry["state"] = SCRIPTED
+    if unfinished(project):
+        project["paused"] = True
+    update_status(project)
+
+
+def set_concurrency(project: Dict, stage: str, value: int) -> int:
+    """Change a stage's limit, mid-run if need be; returns the clamped value."""
+    value = max(1, min(MAX_CONCURRENCY, int(value)))
+    with lock:
+        project.setdefault("concurrency", dict(DEFAULT_CONCURRENCY))[stage] = value
+        lock.notify_all()
+    return value
+
+
+def pause(project: Dict):
+    with lock:
+        project["paused"] = True
+        update_status(project)
+        lock.notify_all()
+
+
+def resume(project: Dict, problems: Dict[str, str], workers: Dict[str, Worker],
+           on_change: Callable[[], None]):
+    """Retry failed episodes, fail the ones in `problems`, and dispatch again.
+
+    `problems` maps episode keys to the reason they cannot continue, as
+    found when checking the provider and voices.
+    """
+    with lock:
+        for key, entry in entries(project).items():
+            if entry["state"] == FAILED:
+                entry["state"] = STAGES[entry.get("stage") or "llm"][0]
+                entry["error"] = None
+            if key in problems and entry["state"] != DONE:
+                fail(entry, "tts" if entry["state"] == SCRIPTED else "llm", problems[key])
+        project["paused"] = False
+        start(project, workers, on_change)
+
+
+def approve(project: Dict, keys: Optional[Iterable[str]], workers: Dict[str, Worker],
+            on_change: Callable[[], None]) -> List[str]:
+    """Release reviewed scripts to rendering; all of them when `keys` is None.
+
+    Returns the approved episode keys. A paused run stays paused.
+    """
+    with lock:
+        wanted = None if keys is None else {str(k) for k in keys}
+        approved = []
+        for key, entry in entries(project).items():
+            if entry["state"] == REVIEW and (wanted is None or key in wanted):
+                entry["state"] = SCRIPTED
+                approved.append(key)
+        if approved:
+            start(project, workers, on_change)
+        return sorted(approved, key=int)
+
+
+def fail(entry: Dict, stage: str, error: str):
+    entry.update(state=FAILED, error=error, stage=stage)
+
+
+def start(project: Dict, workers: Dict[str, Worker], on_change: Callable[[], None]):
+    """Dispatch the project's waiting episodes, reusing a running dispatcher."""
+    with lock:
+        update_status(project)
+        dispatcher = _dispatchers.get(project["id"])
+        if dispatcher:
+            dispatcher.workers = workers
+            lock.notify_all()
+            return
+        dispatcher = Dispatcher(project, workers, on_change)
+        _dispatchers[project["id"]] = dispatcher
+        threading.Thread(target=dispatcher.run, daemon=True).start()
+
+
+def is_running(project_id: str) -> bool:
+    with lock:
+        return project_id in _dispatchers
+
+
+class Dispatcher:
+    """Starts stage workers while there are free slots and the run is not paused.
+
+    Exits once nothing is in flight and nothing can start, which is when the
+    run is finished, fully paused or waiting for script approval; resuming
+    or approving starts a new one.
+    """
+
+    def __init__(self, project: Dict, workers: Dict[str, Worker], on_change: Callable[[], None]):
+        self.project = project
+        self.workers = workers
+        self.on_change = on_change
+        self.running = {stage: 0 for stage in STAGES}
+
+    def run(self):
+        with lock:
+            while True:
+                if not self.project.get("paused"):
+                    self._dispatch()
+                update_status(self.project)
+                self.on_change()
+                if not any(self.running.values()):
+                    del _dispatchers[self.project["id"]]
+                    return
+                lock.wait()
+
+    def _dispatch(self):
+        limits = self.project.get("concurrency") or DEFAULT_CONCURRENCY
+        for stage, (waiting, active, _) in STAGES.items():
+            for key, entry in sorted(entries(self.project).items(), key=lambda item: int(item[0])):
+                if self.running[stage] >= limits.get(stage, 1):
+                    break
+                if entry["state"] == waiting:
+                    entry["state"] = active
+                    self.running[stage] += 1
+                    threading.Thread(target=self._work, args=(stage, key), daemon=True).start()
+
+    def _work(self, stage: str, key: str):
+        try:
+            error = self.workers[stage](key)
+        except Exception as e:
+            error = str(e)
+        with lock:
+            entry = entries(self.project)[key]
+            if error:
+                fail(entry, stage, error)
+            else:
+                finished = STAGES[stage][2]
+                if finished == SCRIPTED and self.project.get("review_required"):
+                    finished = REVIEW
+                entry.update(state=finished, error=None, stage=None)
+            self.running[stage] -= 1
+            lock.notify_all()
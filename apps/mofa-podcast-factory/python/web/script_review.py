@@ -0,0 +1,52 @@
+"""
+Reviewing episode scripts before they are rendered.
+
+The user can edit a written script until its audio exists. The first edit
+keeps the LLM's version next to it, so the changes can be shown as a diff;
+regenerating the script replaces both.
+"""
+
+import difflib
+from typing import Dict, List, Optional
+
+import pipeline
+
+# Unchanged lines shown around each change in the diff
+DIFF_CONTEXT = 2
+
+
+def edit_blocked(episode: Optional[Dict], state: Optional[str]) -> Optional[str]:
+    """Why the episode's script cannot be edited now, or None if it can.
+
+    `state` is the episode's generate-all state, None outside a run.
+    """
+    if not episode or not episode.get("script"):
+        return "The episode has no script yet"
+    if state in (pipeline.SCRIPTING, pipeline.RENDERING):
+        return "The episode is being generated"
+    if episode.get("audio_path"):
+        return "The episode is already rendered; regenerate it to change the script"
+    return None
+
+
+def apply_script_edit(episode: Dict, script: str, segments: List[Dict]):
+    """Replace the script and its segments, keeping the LLM's version."""
+    episode.setdefault("original_script", episode["script"])
+    episode["script"] = script
+    episode["segments"] = segments
+    episode["script_edited"] = script != episode["original_script"]
+
+
+def script_diff(episode: Dict) -> List[str]:
+    """Unified diff of the edited script against the LLM's; empty if unchanged."""
+    original = episode.get("original_script")
+    if original is None:
+        return []
+    return list(difflib.unified_diff(
+        original.splitlines(),
+        episode["script"].splitlines(),
+        fromfile="LLM script",
+        tofile="Edited script",
+        n=DIFF_CONTEXT,
+        lineterm="",
+    ))
@@ -0,0 +1,197 @@
+"""
+Listener-facing episode notes.
+
+After an episode's script (and audio) is generated, a second LLM pass turns
+the script and the audio manifest's chapter markers into title suggestions,
+a short description and timestamped show notes. The notes are what goes
+into the RSS feed and the ID3 comment of the published MP3.
+"""
+
+import json
+import time
+import wave
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+# A new chapter starts at the first segment after this many seconds
+CHAPTER_MIN_SECONDS = 60.0
+CHAPTER_TITLE_WORDS = 8
+
+NOTES_FILE = "notes.json"
+MANIFEST_FILE = "manifest.json"
+
+
+def wav_duration(path: Path) -> float:
+    """Length of a WAV file in seconds, 0.0 if it can't be read."""
+    try:
+        with wave.open(str(path), 'rb') as w:
+            return w.getnframes() / float(w.getframerate() or 1)
+    except (OSError, wave.Error, EOFError):
+        return 0.0
+
+
+def format_timestamp(seconds: float) -> str:
+    """Format seconds as MM:SS, or H:MM:SS past the hour."""
+    total = max(0, int(round(seconds)))
+    hours, rest = divmod(total, 3600)
+    minutes, secs = divmod(rest, 60)
+    if hours:
+        return f"{hours}:{minutes:02d}:{secs:02d}"
+    return f"{minutes:02d}:{secs:02d}"
+
+
+def parse_timestamp(value: Any) -> Optional[float]:
+    """Parse MM:SS / H:MM:SS (or a plain number of seconds)."""
+    if isinstance(value, (int, float)):
+        return float(value)
+    if not isinstance(value, str):
+        return None
+    try:
+        parts = [int(p) for p in value.strip().split(':')]
+    except ValueError:
+        return None
+    if not parts or len(parts) > 3:
+        return None
+    seconds = 0
+    for p in parts:
+        seconds = seconds * 60 + p
+    return float(seconds)
+
+
+def build_manifest(segments: List[Dict], durations: List[float]) -> Dict:
+    """Audio manifest: start time of each segment plus coarse chapter markers.
+
+    Chapters group consecutive segments so that each lasts at least
+    CHAPTER_MIN_SECONDS; a chapter is titled after its opening line.
+    """
+    entries = []
+    chapters = []
+    start = 0.0
+    for seg, duration in zip(segments, durations):
+        if not chapters or start - chapters[-1]["start"] >= CHAPTER_MIN_SECONDS:
+            words = seg["text"].split()
+            title = " ".join(words[:CHAPTER_TITLE_WORDS])
+            if len(words) > CHAPTER_TITLE_WORDS:
+                title += "..."
+            chapters.append({"start": round(start, 2), "title": title})
+        entries.append({
+            "start": round(start, 2),
+            "duration": round(duration, 2),
+            "role": seg["role"],
+        })
+        start += duration
+    return {"duration": round(start, 2), "segments": entries, "chapters": chapters}
+
+
+def load_manifest(episode_dir: Path) -> Optional[Dict]:
+    try:
+        return json.loads((episode_dir / MANIFEST_FILE).read_text())
+    except (OSError, ValueError):
+        return None
+
+
+def notes_prompt(episode_title: str, style: str, chapters: List[Dict]) -> str:
+    if chapters:
+        markers = "\n".join(
+            f"- {format_timestamp(c['start'])} {c['title']}" for c in chapters
+        )
+        timing = f"""Chapter markers from the rendered audio (timestamp, opening line):
+{markers}
+
+Each show note must start at one of these timestamps."""
+    else:
+        timing = "The audio has not been rendered yet, so use \"\" for every timestamp."
+
+    return f"""You write listener-facing notes for a podcast episode.
+
+Episode working title: {episode_title}
+Style: {style}
+
+{timing}
+
+Write for someone browsing a podcast app, not for the hosts:
+1. "titles": 3 catchy alternative episode titles
+2. "description": 2-3 sentences saying what the listener will learn
+3. "show_notes": one bullet per chapter summarizing what is discussed
+
+Output JSON only:
+{{
+  "titles": ["...", "...", "..."],
+  "description": "...",
+  "show_notes": [{{"time": "00:00", "text": "..."}}]
+}}"""
+
+
+def parse_notes(text: str, chapters: List[Dict]) -> Optional[Dict]:
+    """Parse the LLM reply, snapping show-note times to the nearest chapter."""
+    start = text.find('{')
+    end = text.rfind('}') + 1
+    if start < 0 or end <= start:
+        return None
+    try:
+        raw = json.loads(text[start:end])
+    except json.JSONDecodeError as e:
+        print(f"Notes JSON parse error: {e}")
+        return None
+
+    titles = [str(t).strip() for t in raw.get("titles", []) if str(t).strip()]
+    description = str(raw.get("description", "")).strip()
+    if not description:
+        return None
+
+    starts = [c["start"] for c in chapters]
+    show_notes = []
+    for item in raw.get("show_notes", []):
+        if not isinstance(item, dict) or not str(item.get("text", "")).strip():
+            continue
+        seconds = parse_timestamp(item.get("time"))
+        if starts:
+            seconds = min(starts, key=lambda s: abs(s - (seconds or 0.0)))
+        show_notes.append({
+            "time": format_timestamp(seconds) if seconds is not None else "",
+            "text": str(item["text"]).strip(),
+        })
+    show_notes.sort(key=lambda n: parse_timestamp(n["time"]) or 0.0)
+
+    return {
+        "title": titles[0] if titles else "",
+        "titles": titles,
+        "description": description,
+        "show_notes": show_notes,
+        "edited": False,
+        "generated_at": int(time.time()),
+    }
+
+
+def apply_edits(notes: Optional[Dict], edits: Dict) -> Dict:
+    """Merge inline edits from the UI and mark the notes as manually edited."""
+    merged = dict(notes or {})
+    if "title" in edits:
+        merged["title"] = str(edits["title"]).strip()
+    if "description" in edits:
+        merged["description"] = str(edits["description"]).strip()
+    if "show_notes" in edits:
+        merged["show_notes"] = [
+            {"time": str(n.get("time", "")).strip(), "text": str(n.get("text", "")).strip()}
+            for n in edits["show_notes"]
+            if isinstance(n, dict) and str(n.get("text", "")).strip()
+        ]
+    merged.setdefault("titles", [])
+    merged["edited"] = True
+    return merged
+
+
+def notes_as_text(notes: Dict) -> str:
+    """Plain-text rendering used for the ID3 comment."""
+    lines = [notes.get("description", "")]
+    if notes.get("show_notes"):
+        lines.append("")
+        for n in notes["show_notes"]:
+            prefix = f"{n['time']} " if n.get("time") else ""
+            lines.append(f"{prefix}{n['text']}")
+    return "\n".join(lines).strip()
+
+
+def save_notes(episode_dir: Path, notes: Dict) -> None:
+    episode_dir.mkdir(parents=True, exist_ok=True)
+    (episode_dir / NOTES_FILE).write_text(json.dumps(notes, ensure_ascii=False, indent=2))
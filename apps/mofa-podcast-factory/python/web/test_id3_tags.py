@@ -0,0 +1,41 @@
+"""
+Checks id3_tags against the fixture mofa-widgets/src/audio_tags.rs is tested
+with. Run from this directory: python -m unittest test_id3_tags
+"""
+
+import tempfile
+import unittest
+from pathlib import Path
+
+from id3_tags import _existing_id3_len, encode_id3v2, write_mp3_tags
+
+FIXTURE = Path(__file__).resolve().parents[4] / "mofa-widgets" / "tests" / "fixtures" / "episode.id3"
+
+TAGS = {
+    "title": "第一集 · Pilot",
+    "artist": "Ada, Lin",
+    "album": "MoFA Weekly",
+    "track": "7",
+    "genre": "Podcast",
+    "comment": "Show notes",
+}
+
+
+class Id3TagsTest(unittest.TestCase):
+    def test_matches_shared_fixture(self):
+        self.assertEqual(encode_id3v2(TAGS), FIXTURE.read_bytes())
+
+    def test_tag_is_replaced_not_stacked(self):
+        frames = b"\xff\xfb\x90\x00"
+        with tempfile.TemporaryDirectory() as tmp:
+            path = Path(tmp) / "episode.mp3"
+            path.write_bytes(frames)
+            write_mp3_tags(path, {"title": "Old"})
+            write_mp3_tags(path, TAGS)
+            data = path.read_bytes()
+        self.assertEqual(data[:_existing_id3_len(data)], FIXTURE.read_bytes())
+        self.assertEqual(data[_existing_id3_len(data):], frames)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -3,6 +3,7 @@
 //! WebView-based multi-episode podcast generator
 
 use makepad_widgets::*;
+use mofa_widgets::activity::{ActivityHandle, ActivityKind, ActivityRegistry};
 use mofa_widgets::webview::{WebViewAction, WebViewContainerWidgetExt};
 use std::net::TcpListener;
 use std::process::{Child, Command, Stdio};
@@ -166,6 +167,7 @@ live_design! {
                     webview = <WebViewContainer> {
                         width: Fill, height: Fill
                         url: "about:blank"
+                        profile: "mofa-podcast-factory"
                     }
                 }
             }
@@ -293,11 +295,11 @@ fn get_python_path() -> Option<PathBuf> {
     None
 }
 
+/// Preferences file, relative to the MoFA Studio data directory
+pub(crate) const CONFIG_FILE: &str = "podcast-factory.json";
+
 fn get_config_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".mofa-studio")
-        .join("podcast-factory.json")
+    mofa_widgets::app_storage::data_path(CONFIG_FILE)
 }
 
 fn find_embedded_python_cmd() -> Option<String> {
@@ -418,6 +420,10 @@ pub struct PodcastFactoryScreen {
     #[rust]
     server: Arc<Mutex<PythonServer>>,
 
+    /// Lists the server in the shell's running panel while it runs
+    #[rust]
+    server_activity: Option<ActivityHandle>,
+
     #[rust]
     url_loaded: bool,
 }
@@ -426,6 +432,11 @@ impl Widget for PodcastFactoryScreen {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
 
+        // Stop requests from the shell's running panel
+        if self.server_activity.as_ref().is_some_and(|a| a.stop_requested()) {
+            self.toggle_server(cx);
+        }
+
         let actions = match event {
             Event::Actions(actions) => actions.as_slice(),
             _ => &[],
@@ -471,7 +482,9 @@ impl Widget for PodcastFactoryScreen {
                                 self.set_status(cx, "Connected", 1.0);
                             }
                         }
-                        WebViewAction::IpcMessage { .. } | WebViewAction::None => {}
+                        WebViewAction::IpcMessage { .. }
+                        | WebViewAction::InitTiming { .. }
+                        | WebViewAction::None => {}
                     }
                 }
             }
@@ -494,6 +507,7 @@ impl PodcastFactoryScreen {
             let mut server = self.server.lock().unwrap();
             server.stop();
             drop(server);
+            self.server_activity = None;
             self.set_status(cx, "Server stopped", 0.0);
             self.url_loaded = false;
             self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Start Server");
@@ -508,6 +522,11 @@ impl PodcastFactoryScreen {
             match result {
                 Ok(port) => {
                     ::log::info!("Podcast Factory server started on port {}", port);
+                    let activity = ActivityRegistry::global()
+                        .register(ActivityKind::Server, "mofa-podcast-factory", "Podcast Factory server")
+                        .stoppable();
+                    activity.set_state(&format!("port {}", port));
+                    self.server_activity = Some(activity);
                     self.set_status(cx, &format!("Server running on port {}", port), 2.0);
                     self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Stop Server");
 
@@ -661,9 +680,7 @@ impl PodcastFactoryScreenRef {
             );
 
             // Send theme to WebView
-            let webview = inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
-            let js = format!("if(window.setTheme) window.setTheme({});", dark_mode);
-            let _ = webview.eval(&js);
+            inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview)).set_dark_mode(dark_mode);
 
             inner.view.redraw(cx);
         }
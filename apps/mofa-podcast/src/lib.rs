@@ -8,6 +8,14 @@ pub mod screen;
 
 use makepad_widgets::*;
 use mofa_widgets::{AppInfo, MofaApp};
+use mofa_widgets::app_storage::AppStorage;
+
+/// Data this app keeps under `~/.mofa-studio`
+pub const STORAGE: AppStorage = AppStorage {
+    prefs: &[models::PROJECT_FILE],
+    cache: &[],
+    webview_profile: false,
+};
 
 pub struct MoFaPodcastApp;
 
@@ -20,6 +28,7 @@ impl MofaApp for MoFaPodcastApp {
             tab_id: Some(live_id!(podcast_tab)),
             page_id: Some(live_id!(podcast_page)),
             show_in_sidebar: true,
+            storage: STORAGE,
             ..Default::default()
         }
     }
@@ -21,4 +21,10 @@ pub enum PodcastError {
 
     #[error("Voice not assigned for role: {0}")]
     VoiceNotAssigned(String),
+
+    #[error("Another generation is writing to {0}")]
+    OutputBusy(String),
+
+    #[error("Not enough disk space: about {needed_mb} MB needed, {available_mb} MB free")]
+    InsufficientSpace { needed_mb: u64, available_mb: u64 },
 }
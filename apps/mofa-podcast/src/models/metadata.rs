@@ -0,0 +1,150 @@
+//! Per-episode metadata and output file naming
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Default output filename template
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{number:02} - {title}.{ext}";
+
+/// Longest file stem we produce (leaves room for the extension)
+const MAX_STEM_LEN: usize = 120;
+
+/// Episode metadata written into the exported audio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EpisodeMetadata {
+    pub title: String,
+    pub author: String,
+    pub episode_number: Option<u32>,
+    pub description: String,
+    /// Language code such as `en` or `zh`; picks default voices
+    pub language: String,
+    pub artwork_path: Option<PathBuf>,
+    /// Output filename, see [`render_filename`]
+    pub filename_template: String,
+    /// Overwrite tags edited outside the app on regeneration
+    pub overwrite_tags: bool,
+}
+
+impl Default for EpisodeMetadata {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            author: String::new(),
+            episode_number: None,
+            description: String::new(),
+            language: String::new(),
+            artwork_path: None,
+            filename_template: DEFAULT_FILENAME_TEMPLATE.to_string(),
+            overwrite_tags: false,
+        }
+    }
+}
+
+/// Render an output filename from a template.
+///
+/// Supported placeholders: `{title}`, `{author}`, `{number}`, `{number:NN}`
+/// (zero-padded to NN digits) and `{ext}`. Unknown placeholders are kept
+/// verbatim. The result always ends in `.ext`, even if the template omits it.
+pub fn render_filename(template: &str, meta: &EpisodeMetadata, ext: &str) -> String {
+    let template = template.trim();
+    let template = if template.is_empty() { DEFAULT_FILENAME_TEMPLATE } else { template };
+    let template = template.strip_suffix(".{ext}").unwrap_or(template);
+    let number = meta.episode_number.unwrap_or(0);
+
+    let mut stem = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}').map(|i| open + i) else {
+            break;
+        };
+        stem.push_str(&rest[..open]);
+        match &rest[open + 1..close] {
+            "title" => stem.push_str(&meta.title),
+            "author" => stem.push_str(&meta.author),
+            "number" => stem.push_str(&number.to_string()),
+            "ext" => stem.push_str(ext),
+            other => match other.strip_prefix("number:").and_then(|w| w.parse::<usize>().ok()) {
+                Some(width) => stem.push_str(&format!("{:0width$}", number, width = width)),
+                None => stem.push_str(&rest[open..=close]),
+            },
+        }
+        rest = &rest[close + 1..];
+    }
+    stem.push_str(rest);
+
+    format!("{}.{}", sanitize_filename(&stem), ext)
+}
+
+/// Make a string safe to use as a file name on macOS, Linux and Windows
+pub fn sanitize_filename(name: &str) -> String {
+    let cleaned = replace_invalid_chars(name);
+    // Empty fields leave dangling separators, e.g. "03 - " without a title
+    let is_edge = |c: char| c.is_whitespace() || c == '-' || c == '.';
+    let truncated: String = cleaned.trim_matches(is_edge).chars().take(MAX_STEM_LEN).collect();
+    let result = truncated.trim_end_matches(is_edge);
+
+    if result.is_empty() {
+        "podcast".to_string()
+    } else {
+        result.to_string()
+    }
+}
+
+fn replace_invalid_chars(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(title: &str, number: Option<u32>) -> EpisodeMetadata {
+        EpisodeMetadata {
+            title: title.to_string(),
+            author: "MoFA".to_string(),
+            episode_number: number,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_default_template() {
+        let name = render_filename(DEFAULT_FILENAME_TEMPLATE, &meta("Pilot", Some(3)), "wav");
+        assert_eq!(name, "03 - Pilot.wav");
+    }
+
+    #[test]
+    fn test_placeholders_and_missing_ext() {
+        let name = render_filename("{author}_{number}_{title}", &meta("第一集", Some(12)), "mp3");
+        assert_eq!(name, "MoFA_12_第一集.mp3");
+
+        let name = render_filename("{title} {unknown}.{ext}", &meta("A", None), "wav");
+        assert_eq!(name, "A {unknown}.wav");
+    }
+
+    #[test]
+    fn test_invalid_characters_are_sanitized() {
+        let name = render_filename("{title}.{ext}", &meta("What? AI/ML: \"Part 1\"", Some(1)), "wav");
+        assert_eq!(name, "What_ AI_ML_ _Part 1_.wav");
+
+        assert_eq!(sanitize_filename("  ..  "), "podcast");
+        assert_eq!(sanitize_filename("notes..."), "notes");
+        assert_eq!(sanitize_filename(&"x".repeat(500)).len(), MAX_STEM_LEN);
+    }
+
+    #[test]
+    fn test_empty_title_falls_back() {
+        let name = render_filename("{title}.{ext}", &meta("", None), "wav");
+        assert_eq!(name, "podcast.wav");
+
+        let name = render_filename(DEFAULT_FILENAME_TEMPLATE, &meta("", Some(4)), "wav");
+        assert_eq!(name, "04.wav");
+    }
+}
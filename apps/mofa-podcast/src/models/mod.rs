@@ -3,7 +3,13 @@
 mod script;
 mod voice;
 mod errors;
+mod metadata;
+mod project;
+mod stats;
 
-pub use script::{PodcastScript, ScriptFormat, CharacterRole, DialogueSegment};
-pub use voice::{VoiceAssignment, AudioSettings, AudioFormat, MacOSVoice};
+pub use script::{PodcastScript, ScriptFormat, ScriptMetadata, CharacterRole, DialogueSegment};
+pub use voice::{VoiceAssignment, AudioSettings, AudioFormat, MacOSVoice, SilenceTrim};
 pub use errors::PodcastError;
+pub use metadata::{EpisodeMetadata, render_filename, sanitize_filename, DEFAULT_FILENAME_TEMPLATE};
+pub use project::{PodcastProject, PROJECT_FILE};
+pub use stats::{SegmentTiming, SpeakerStats, RoleStats, estimate_secs, format_duration, DEFAULT_MAX_SHARE};
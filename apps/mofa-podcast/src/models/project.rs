@@ -0,0 +1,83 @@
+//! Podcast project file
+//!
+//! The current script, voice assignments and episode metadata are kept in
+//! `~/.mofa-studio/podcast/project.json` so they survive restarts. The tags
+//! last written to the output are recorded too, so regeneration can tell
+//! which tags were edited by hand afterwards.
+
+use super::{EpisodeMetadata, DEFAULT_MAX_SHARE};
+use mofa_widgets::audio_tags::TagSnapshot;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Project file, relative to the MoFA Studio data directory
+pub const PROJECT_FILE: &str = "podcast/project.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PodcastProject {
+    pub script_content: String,
+    /// Role name -> voice id
+    pub voice_assignments: HashMap<String, String>,
+    pub metadata: EpisodeMetadata,
+    /// Where generated episodes are written; `None` uses [`default_output_dir`]
+    pub output_dir: Option<PathBuf>,
+    /// Output file the tags below were written to
+    pub last_output: Option<PathBuf>,
+    pub last_written_tags: Option<TagSnapshot>,
+    /// Airtime share above which the Stats tab flags a role
+    pub max_speaker_share: f64,
+    /// Replace an existing episode file after confirming, instead of
+    /// numbering the new one ("-2", "-3", ...)
+    pub replace_existing: bool,
+}
+
+impl Default for PodcastProject {
+    fn default() -> Self {
+        Self {
+            script_content: String::new(),
+            voice_assignments: HashMap::new(),
+            metadata: EpisodeMetadata::default(),
+            output_dir: None,
+            last_output: None,
+            last_written_tags: None,
+            max_speaker_share: DEFAULT_MAX_SHARE,
+            replace_existing: false,
+        }
+    }
+}
+
+/// `~/Documents/MoFaPodcast`
+pub fn default_output_dir() -> PathBuf {
+    dirs::document_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("MoFaPodcast")
+}
+
+impl PodcastProject {
+    pub fn path() -> PathBuf {
+        mofa_widgets::app_storage::data_path(PROJECT_FILE)
+    }
+
+    /// Load the saved project, or an empty one if none exists
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn output_dir(&self) -> PathBuf {
+        self.output_dir.clone().unwrap_or_else(default_output_dir)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 //! Podcast script data structures
 
+use super::EpisodeMetadata;
+use mofa_widgets::tts::TtsSpan;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Script format enumeration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -23,7 +26,60 @@ pub struct CharacterRole {
 pub struct DialogueSegment {
     pub index: usize,
     pub role: String,
+    /// Line of the script the segment starts on (1-based, counting the
+    /// front matter); `None` for JSON scripts
+    pub line: Option<usize>,
+    /// Spoken text without pacing markup
     pub text: String,
+    /// The text split into runs of the same pacing
+    pub spans: Vec<TtsSpan>,
+}
+
+impl DialogueSegment {
+    pub fn new(index: usize, role: String, spans: Vec<TtsSpan>) -> Self {
+        let text = spans.iter().map(|span| span.text.as_str()).collect();
+        Self { index, role, line: None, text, spans }
+    }
+
+    /// Whether any part of the segment is paced differently
+    pub fn has_pacing(&self) -> bool {
+        self.spans.iter().any(|span| !span.is_plain())
+    }
+}
+
+/// Settings a script carries in its front matter (see
+/// [`front_matter`](crate::services::front_matter))
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptMetadata {
+    pub title: Option<String>,
+    pub episode: Option<u32>,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    /// Language code such as `en` or `zh`
+    pub language: Option<String>,
+    /// Role name -> voice id hints
+    pub voices: BTreeMap<String, String>,
+}
+
+impl ScriptMetadata {
+    /// Copy the fields the script sets into the episode metadata
+    pub fn apply_to(&self, meta: &mut EpisodeMetadata) {
+        let fields = [
+            (&self.title, &mut meta.title),
+            (&self.author, &mut meta.author),
+            (&self.description, &mut meta.description),
+            (&self.language, &mut meta.language),
+        ];
+        for (value, target) in fields {
+            if let Some(value) = value {
+                *target = value.clone();
+            }
+        }
+        if self.episode.is_some() {
+            meta.episode_number = self.episode;
+        }
+    }
 }
 
 /// Represents a podcast script with content and metadata
@@ -31,10 +87,20 @@ pub struct DialogueSegment {
 pub struct PodcastScript {
     pub id: String,
     pub title: String,
+    /// Script text without the front matter
     pub content: String,
     pub format: ScriptFormat,
     pub roles: Vec<CharacterRole>,
     pub file_path: Option<String>,
+    /// Front matter, if the script has one
+    #[serde(default)]
+    pub metadata: Option<ScriptMetadata>,
+    /// Problems found while parsing, e.g. unknown front matter keys
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Lines the front matter took up before `content`
+    #[serde(default)]
+    pub front_matter_lines: usize,
 }
 
 impl PodcastScript {
@@ -46,6 +112,9 @@ impl PodcastScript {
             format,
             roles: Vec::new(),
             file_path: None,
+            metadata: None,
+            warnings: Vec::new(),
+            front_matter_lines: 0,
         }
     }
 }
@@ -0,0 +1,203 @@
+//! Speaker airtime statistics
+//!
+//! Producers watch how airtime is split between hosts. Segment lengths come
+//! from the last render while the script is unchanged, and from a reading
+//! speed estimate otherwise, so the numbers are there before anything has
+//! been synthesized.
+
+use super::DialogueSegment;
+
+/// Spoken words per minute assumed for alphabetic text
+pub const WORDS_PER_MINUTE: f64 = 150.0;
+
+/// Spoken characters per minute assumed for Chinese, Japanese and Korean
+pub const CJK_CHARS_PER_MINUTE: f64 = 250.0;
+
+/// Share of airtime above which a role is flagged
+pub const DEFAULT_MAX_SHARE: f64 = 0.7;
+
+/// Estimated speaking time of `text`, in seconds
+pub fn estimate_secs(text: &str) -> f64 {
+    let cjk = text.chars().filter(|&c| is_cjk(c)).count();
+    let words = text
+        .split(|c: char| c.is_whitespace() || is_cjk(c))
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count();
+    words as f64 * 60.0 / WORDS_PER_MINUTE + cjk as f64 * 60.0 / CJK_CHARS_PER_MINUTE
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF     // Hiragana, Katakana
+        | 0x3400..=0x4DBF   // CJK Extension A
+        | 0x4E00..=0x9FFF   // CJK Unified Ideographs
+        | 0xAC00..=0xD7AF   // Hangul syllables
+        | 0xF900..=0xFAFF)  // CJK Compatibility Ideographs
+}
+
+/// "m:ss"
+pub fn format_duration(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// How long one segment is, and who speaks it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentTiming {
+    pub role: String,
+    pub secs: f64,
+    /// Length of each part when the segment was too long for one TTS
+    /// request and was rendered in pieces, before trimming; empty otherwise
+    pub parts: Vec<f64>,
+}
+
+impl SegmentTiming {
+    /// Timings estimated from the segments' text
+    pub fn estimate(segments: &[DialogueSegment]) -> Vec<Self> {
+        segments
+            .iter()
+            .map(|segment| Self {
+                role: segment.role.clone(),
+                secs: estimate_secs(&segment.text),
+                parts: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// Airtime of one role
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoleStats {
+    pub role: String,
+    pub secs: f64,
+    /// Fraction of the total, 0.0 to 1.0
+    pub share: f64,
+    /// Runs of consecutive segments by this role
+    pub turns: usize,
+    /// Longest single turn, in seconds
+    pub longest_turn_secs: f64,
+}
+
+/// Airtime of every role in a script
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpeakerStats {
+    /// In order of first appearance
+    pub roles: Vec<RoleStats>,
+    pub total_secs: f64,
+    /// Whether the timings were estimated rather than measured
+    pub estimated: bool,
+}
+
+impl SpeakerStats {
+    /// Consecutive segments by the same role count as one turn
+    pub fn from_timings(timings: &[SegmentTiming], estimated: bool) -> Self {
+        let mut roles: Vec<RoleStats> = Vec::new();
+        let mut previous: Option<usize> = None;
+        let mut turn_secs = 0.0;
+
+        for timing in timings {
+            let idx = match roles.iter().position(|r| r.role == timing.role) {
+                Some(idx) => idx,
+                None => {
+                    roles.push(RoleStats {
+                        role: timing.role.clone(),
+                        secs: 0.0,
+                        share: 0.0,
+                        turns: 0,
+                        longest_turn_secs: 0.0,
+                    });
+                    roles.len() - 1
+                }
+            };
+
+            if previous == Some(idx) {
+                turn_secs += timing.secs;
+            } else {
+                roles[idx].turns += 1;
+                turn_secs = timing.secs;
+            }
+            let role = &mut roles[idx];
+            role.secs += timing.secs;
+            role.longest_turn_secs = role.longest_turn_secs.max(turn_secs);
+            previous = Some(idx);
+        }
+
+        let total_secs: f64 = roles.iter().map(|r| r.secs).sum();
+        if total_secs > 0.0 {
+            for role in &mut roles {
+                role.share = role.secs / total_secs;
+            }
+        }
+
+        Self { roles, total_secs, estimated }
+    }
+
+    /// The role holding more than `max_share` of the airtime, if any.
+    /// A single-role script is never flagged.
+    pub fn dominant(&self, max_share: f64) -> Option<&RoleStats> {
+        if self.roles.len() < 2 {
+            return None;
+        }
+        self.roles.iter().find(|r| r.share > max_share)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(role: &str, secs: f64) -> SegmentTiming {
+        SegmentTiming { role: role.to_string(), secs, parts: Vec::new() }
+    }
+
+    #[test]
+    fn test_turns_and_longest_monologue() {
+        let timings = [
+            timing("Host", 10.0),
+            timing("Guest", 5.0),
+            timing("Guest", 20.0),
+            timing("Host", 15.0),
+            timing("Guest", 10.0),
+        ];
+        let stats = SpeakerStats::from_timings(&timings, false);
+
+        assert_eq!(stats.total_secs, 60.0);
+        assert_eq!(stats.roles.len(), 2);
+
+        let host = &stats.roles[0];
+        assert_eq!((host.role.as_str(), host.secs, host.turns), ("Host", 25.0, 2));
+        assert_eq!(host.longest_turn_secs, 15.0);
+
+        // The two back-to-back Guest segments are one 25 s turn
+        let guest = &stats.roles[1];
+        assert_eq!((guest.secs, guest.turns, guest.longest_turn_secs), (35.0, 2, 25.0));
+        assert!((guest.share - 35.0 / 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dominant_role() {
+        let balanced = SpeakerStats::from_timings(&[timing("A", 60.0), timing("B", 40.0)], true);
+        assert_eq!(balanced.dominant(DEFAULT_MAX_SHARE), None);
+
+        let lopsided = SpeakerStats::from_timings(&[timing("A", 80.0), timing("B", 20.0)], true);
+        assert_eq!(lopsided.dominant(DEFAULT_MAX_SHARE).map(|r| r.role.as_str()), Some("A"));
+
+        let solo = SpeakerStats::from_timings(&[timing("Narrator", 100.0)], true);
+        assert_eq!(solo.dominant(DEFAULT_MAX_SHARE), None);
+        assert_eq!(SpeakerStats::from_timings(&[], true), SpeakerStats { estimated: true, ..Default::default() });
+    }
+
+    #[test]
+    fn test_estimate() {
+        // 150 words is a minute
+        let words = vec!["word"; 150].join(" ");
+        assert!((estimate_secs(&words) - 60.0).abs() < 1e-9);
+        // 25 characters of Chinese, punctuation not counted as words
+        assert!((estimate_secs("今天我们来聊一聊人工智能在播客制作中的应用未来发展，") - 6.0).abs() < 1e-9);
+        assert!((estimate_secs("Hello, 世界!") - (0.4 + 0.48)).abs() < 1e-9);
+        assert_eq!(estimate_secs("  ... "), 0.0);
+
+        assert_eq!(format_duration(0.4), "0:00");
+        assert_eq!(format_duration(125.6), "2:06");
+    }
+}
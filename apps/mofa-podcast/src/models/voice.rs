@@ -17,11 +17,23 @@ pub enum AudioFormat {
     Aiff,
 }
 
+impl AudioFormat {
+    /// File extension for this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Aiff => "aiff",
+        }
+    }
+}
+
 /// Audio generation settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioSettings {
     pub format: AudioFormat,
     pub sample_rate: u32,
+    #[serde(default)]
+    pub trim: SilenceTrim,
 }
 
 impl Default for AudioSettings {
@@ -29,6 +41,28 @@ impl Default for AudioSettings {
         Self {
             format: AudioFormat::Wav,
             sample_rate: 22050,
+            trim: SilenceTrim::default(),
+        }
+    }
+}
+
+/// Trimming of the silence TTS leaves at the start and end of each segment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SilenceTrim {
+    pub enabled: bool,
+    /// Level below which audio counts as silence, in dBFS
+    pub threshold_dbfs: f32,
+    /// Silence kept before and after the speech, in milliseconds
+    pub keep_ms: u32,
+}
+
+impl Default for SilenceTrim {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_dbfs: -50.0,
+            keep_ms: 80,
         }
     }
 }
@@ -3,8 +3,17 @@
 //! Makepad native UI for podcast generation
 
 use makepad_widgets::*;
-use crate::models::{PodcastScript, AudioSettings};
-use crate::services::{parser, generator::AudioGenerator};
+use crate::models::{PodcastScript, AudioSettings, EpisodeMetadata, MacOSVoice, PodcastProject, ScriptMetadata, SegmentTiming, SpeakerStats, format_duration, sanitize_filename};
+use crate::services::{front_matter, parser, tagging};
+use crate::services::generator::{estimate_render, unique_path, AudioGenerator, GenerationRun, GenerationUpdate};
+use crate::services::tagging::ExistingTags;
+use crate::services::tts::TTSEngine;
+use crate::services::voice_compare::{AbPlayer, Side, VoiceComparison};
+use mofa_widgets::confirm_dialog::ConfirmDialogWidgetExt;
+use mofa_widgets::path_picker::PathPickerWidgetExt;
+use mofa_widgets::searchable_drop_down::SearchableDropDownWidgetExt;
+use mofa_widgets::tts::{TtsCostModel, TtsSpeedLog};
+use mofa_widgets::voices::{favorites_first, VoicePrefs};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -14,14 +23,9 @@ live_design! {
     use link::widgets::*;
 
     use mofa_widgets::theme::*;
-
-    // Voice dropdown values
-    VoiceTingTing = LiveId,
-    VoiceMeiJia = LiveId,
-    VoiceSinji = LiveId,
-    VoiceSamantha = LiveId,
-    VoiceAlex = LiveId,
-    VoiceDaniel = LiveId,
+    use mofa_widgets::path_picker::PathPicker;
+    use mofa_widgets::searchable_drop_down::SearchableDropDown;
+    use mofa_widgets::confirm_dialog::ConfirmDialog;
 
     // Panel with subtle border
     PanelBg = <RoundedView> {
@@ -160,8 +164,13 @@ live_design! {
         }
     }
 
-    // Voice select dropdown
-    VoiceDropdown = <DropDown> {
+    // Voice select dropdown, filterable and grouped by language
+    VoiceDropdown = <SearchableDropDown> {
+        empty_text: "Search voices"
+    }
+
+    // Role select dropdown of the compare tool
+    RoleDropdown = <DropDown> {
         width: Fill, height: 32
         draw_bg: {
             instance dark_mode: 0.0
@@ -193,8 +202,150 @@ live_design! {
                 );
             }
         }
-        labels: ["Ting-Ting (Chinese)", "Mei-Jia (Chinese)", "Sin-ji (Cantonese)", "Samantha (English)", "Alex (English)", "Daniel (British)"]
-        values: [VoiceTingTing, VoiceMeiJia, VoiceSinji, VoiceSamantha, VoiceAlex, VoiceDaniel]
+        labels: []
+        values: []
+    }
+
+    // Single-line metadata field
+    MetaInput = <TextInput> {
+        width: Fill, height: 28
+        text: ""
+        draw_bg: {
+            instance dark_mode: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 4.0);
+                let bg = mix(
+                    vec4(0.96, 0.96, 0.97, 1.0),
+                    vec4(0.20, 0.21, 0.24, 1.0),
+                    self.dark_mode
+                );
+                sdf.fill(bg);
+                sdf.stroke(mix(
+                    vec4(0.85, 0.86, 0.88, 1.0),
+                    vec4(0.30, 0.31, 0.35, 1.0),
+                    self.dark_mode
+                ), 1.0);
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: { font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix(
+                    vec4(0.20, 0.20, 0.25, 1.0),
+                    vec4(0.88, 0.88, 0.92, 1.0),
+                    self.dark_mode
+                );
+            }
+        }
+    }
+
+    // Small caption above a metadata field
+    MetaCaption = <Label> {
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: { font_size: 10.0 }
+            fn get_color(self) -> vec4 {
+                return mix(
+                    vec4(0.40, 0.40, 0.45, 1.0),
+                    vec4(0.60, 0.60, 0.65, 1.0),
+                    self.dark_mode
+                );
+            }
+        }
+    }
+
+    // Tab switch at the top of the config panel
+    ConfigTab = <SecondaryButton> {
+        width: Fill
+        draw_bg: {
+            instance active: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 4.0);
+                let base = mix(
+                    vec4(0.92, 0.93, 0.94, 1.0),
+                    vec4(0.25, 0.26, 0.30, 1.0),
+                    self.dark_mode
+                );
+                let active = mix(
+                    vec4(0.84, 0.89, 0.97, 1.0),
+                    vec4(0.24, 0.33, 0.48, 1.0),
+                    self.dark_mode
+                );
+                let color = mix(mix(base, active, self.active), mix(vec4(0.88, 0.89, 0.90, 1.0), vec4(0.30, 0.31, 0.35, 1.0), self.dark_mode), self.hover * (1.0 - self.active));
+                sdf.fill(color);
+                return sdf.result;
+            }
+        }
+    }
+
+    // Segment colors of the balance bar, in order of first appearance
+    ROLE_COLOR_0 = #4d85d9
+    ROLE_COLOR_1 = #59b373
+    ROLE_COLOR_2 = #e69e33
+    ROLE_COLOR_3 = #9973d9
+
+    // Stacked bar of airtime shares: `end0`..`end3` are where each of the
+    // first four roles' segments end (0.0 to 1.0); the rest is "others"
+    BalanceBar = <View> {
+        width: Fill, height: 14
+        show_bg: true
+        draw_bg: {
+            instance dark_mode: 0.0
+            instance end0: 0.0
+            instance end1: 0.0
+            instance end2: 0.0
+            instance end3: 0.0
+            instance others: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 3.0);
+                let track = mix(vec4(0.90, 0.91, 0.92, 1.0), vec4(0.22, 0.23, 0.27, 1.0), self.dark_mode);
+                let x = self.pos.x;
+                let color = track;
+                if x < self.end0 {
+                    color = (ROLE_COLOR_0);
+                } else if x < self.end1 {
+                    color = (ROLE_COLOR_1);
+                } else if x < self.end2 {
+                    color = (ROLE_COLOR_2);
+                } else if x < self.end3 {
+                    color = (ROLE_COLOR_3);
+                } else if x < self.others {
+                    color = mix(vec4(0.65, 0.66, 0.70, 1.0), vec4(0.45, 0.46, 0.50, 1.0), self.dark_mode);
+                }
+                sdf.fill(color);
+                return sdf.result;
+            }
+        }
+    }
+
+    // Role name under the bar, in its segment's color
+    LegendLabel = <Label> {
+        text: ""
+        draw_text: {
+            text_style: { font_size: 10.0 }
+        }
+    }
+
+    // Column of the stats table, header on the first line
+    StatsColumn = <Label> {
+        width: Fit
+        text: ""
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: { font_size: 10.0, line_spacing: 1.4 }
+            fn get_color(self) -> vec4 {
+                return mix(
+                    vec4(0.25, 0.25, 0.30, 1.0),
+                    vec4(0.80, 0.80, 0.85, 1.0),
+                    self.dark_mode
+                );
+            }
+        }
     }
 
     pub PodcastScreen = {{PodcastScreen}} {
@@ -239,6 +390,10 @@ live_design! {
                     text: "Import File"
                 }
 
+                export_btn = <SecondaryButton> {
+                    text: "Save Script"
+                }
+
                 clear_btn = <DangerButton> {
                     text: "Clear"
                 }
@@ -277,6 +432,24 @@ live_design! {
                     }
                 }
             }
+
+            // Front matter problems, hidden while there are none
+            validation_label = <Label> {
+                width: Fill
+                text: ""
+                visible: false
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: { font_size: 10.0 }
+                    fn get_color(self) -> vec4 {
+                        return mix(
+                            vec4(0.75, 0.50, 0.15, 1.0),
+                            vec4(0.95, 0.72, 0.40, 1.0),
+                            self.dark_mode
+                        );
+                    }
+                }
+            }
         }
 
         // Right: Config panel (fixed width)
@@ -321,98 +494,315 @@ live_design! {
                 padding: 12
                 spacing: 12
 
-                // Role sections (hidden by default)
-                role_section_1 = <View> {
+                // Voices / Stats tabs
+                tab_row = <View> {
                     width: Fill, height: Fit
+                    flow: Right
+                    spacing: 6
+
+                    voices_tab_btn = <ConfigTab> { text: "Voices", draw_bg: { active: 1.0 } }
+                    stats_tab_btn = <ConfigTab> { text: "Stats" }
+                }
+
+                voices_tab = <View> {
+                    width: Fill, height: Fill
                     flow: Down
-                    spacing: 4
-                    visible: false
+                    spacing: 12
+
+                    // Role sections (hidden by default)
+                    role_section_1 = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 4
+                        visible: false
+
+                        role_1_label = <Label> {
+                            text: "Role 1"
+                            draw_text: {
+                                instance dark_mode: 0.0
+                                text_style: { font_size: 11.0 }
+                                fn get_color(self) -> vec4 {
+                                    return mix(
+                                        vec4(0.25, 0.25, 0.30, 1.0),
+                                        vec4(0.75, 0.75, 0.80, 1.0),
+                                        self.dark_mode
+                                    );
+                                }
+                            }
+                        }
 
-                    role_1_label = <Label> {
-                        text: "Role 1"
-                        draw_text: {
-                            instance dark_mode: 0.0
-                            text_style: { font_size: 11.0 }
-                            fn get_color(self) -> vec4 {
-                                return mix(
-                                    vec4(0.25, 0.25, 0.30, 1.0),
-                                    vec4(0.75, 0.75, 0.80, 1.0),
-                                    self.dark_mode
-                                );
+                        role_1_voice = <VoiceDropdown> {}
+                    }
+
+                    role_section_2 = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 4
+                        visible: false
+
+                        role_2_label = <Label> {
+                            text: "Role 2"
+                            draw_text: {
+                                instance dark_mode: 0.0
+                                text_style: { font_size: 11.0 }
+                                fn get_color(self) -> vec4 {
+                                    return mix(
+                                        vec4(0.25, 0.25, 0.30, 1.0),
+                                        vec4(0.75, 0.75, 0.80, 1.0),
+                                        self.dark_mode
+                                    );
+                                }
                             }
                         }
+
+                        role_2_voice = <VoiceDropdown> {}
                     }
 
-                    role_1_voice = <VoiceDropdown> {}
-                }
+                    role_section_3 = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 4
+                        visible: false
+
+                        role_3_label = <Label> {
+                            text: "Role 3"
+                            draw_text: {
+                                instance dark_mode: 0.0
+                                text_style: { font_size: 11.0 }
+                                fn get_color(self) -> vec4 {
+                                    return mix(
+                                        vec4(0.25, 0.25, 0.30, 1.0),
+                                        vec4(0.75, 0.75, 0.80, 1.0),
+                                        self.dark_mode
+                                    );
+                                }
+                            }
+                        }
 
-                role_section_2 = <View> {
-                    width: Fill, height: Fit
-                    flow: Down
-                    spacing: 4
-                    visible: false
+                        role_3_voice = <VoiceDropdown> {}
+                    }
 
-                    role_2_label = <Label> {
-                        text: "Role 2"
+                    // Info text
+                    info_label = <Label> {
+                        width: Fill
+                        text: "Paste script or click Import to detect roles automatically"
                         draw_text: {
                             instance dark_mode: 0.0
-                            text_style: { font_size: 11.0 }
+                            text_style: { font_size: 10.0 }
                             fn get_color(self) -> vec4 {
                                 return mix(
-                                    vec4(0.25, 0.25, 0.30, 1.0),
-                                    vec4(0.75, 0.75, 0.80, 1.0),
+                                    vec4(0.50, 0.50, 0.55, 1.0),
+                                    vec4(0.50, 0.50, 0.55, 1.0),
                                     self.dark_mode
                                 );
                             }
                         }
                     }
 
-                    role_2_voice = <VoiceDropdown> {}
+                    // A/B voice comparison (hidden until opened)
+                    compare_open_btn = <SecondaryButton> {
+                        width: Fill
+                        text: "Compare voices..."
+                    }
+
+                    compare_section = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 4
+                        visible: false
+
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            align: {y: 0.5}
+
+                            <SectionTitle> {
+                                text: "COMPARE VOICES"
+                            }
+
+                            <View> { width: Fill, height: 1 }
+
+                            compare_close_btn = <SecondaryButton> {
+                                text: "Close"
+                            }
+                        }
+
+                        compare_role_caption = <MetaCaption> { text: "Role" }
+                        compare_role = <RoleDropdown> {}
+
+                        compare_text_caption = <MetaCaption> { text: "Paragraph" }
+                        compare_text = <MetaInput> { height: 56 }
+
+                        compare_voices = <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            spacing: 6
+
+                            compare_voice_a = <VoiceDropdown> {}
+                            compare_voice_b = <VoiceDropdown> {}
+                        }
+
+                        compare_synth_btn = <SecondaryButton> {
+                            width: Fill
+                            text: "Synthesize A/B"
+                        }
+
+                        compare_player = <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            spacing: 6
+                            visible: false
+
+                            compare_play_btn = <SecondaryButton> {
+                                text: "Play"
+                            }
+
+                            compare_toggle_btn = <SecondaryButton> {
+                                width: Fill
+                                text: "A"
+                            }
+
+                            compare_use_btn = <SecondaryButton> {
+                                text: "Use this voice"
+                            }
+                        }
+
+                        compare_status = <MetaCaption> {
+                            width: Fill
+                            text: ""
+                        }
+                    }
+
+                    // Episode metadata (written into the output as tags)
+                    metadata_section = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 4
+
+                        <SectionTitle> {
+                            text: "EPISODE"
+                        }
+
+                        title_caption = <MetaCaption> { text: "Title" }
+                        title_input = <MetaInput> { empty_text: "Defaults to script title" }
+
+                        author_caption = <MetaCaption> { text: "Author" }
+                        author_input = <MetaInput> {}
+
+                        number_caption = <MetaCaption> { text: "Episode #" }
+                        number_input = <MetaInput> { empty_text: "e.g. 12" }
+
+                        description_caption = <MetaCaption> { text: "Description" }
+                        description_input = <MetaInput> { height: 56 }
+
+                        language_caption = <MetaCaption> { text: "Language" }
+                        language_input = <MetaInput> { empty_text: "e.g. en, zh" }
+
+                        artwork_caption = <MetaCaption> { text: "Artwork" }
+                        artwork_row = <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            spacing: 6
+                            align: {y: 0.5}
+
+                            artwork_label = <MetaCaption> {
+                                width: Fill
+                                text: "None"
+                            }
+
+                            artwork_btn = <SecondaryButton> {
+                                text: "Choose..."
+                            }
+
+                            artwork_clear_btn = <DangerButton> {
+                                text: "Remove"
+                                visible: false
+                            }
+                        }
+
+                        template_caption = <MetaCaption> { text: "File name" }
+                        template_input = <MetaInput> {}
+
+                        overwrite_btn = <SecondaryButton> {
+                            width: Fill
+                            text: "Keep manually edited tags"
+                        }
+
+                        replace_btn = <SecondaryButton> {
+                            width: Fill
+                            text: "Keep existing files"
+                        }
+                    }
+
+                    output_picker = <PathPicker> {
+                        picker_id: "podcast-output"
+                        mode: Folder
+                        dialog_title: "Choose output folder"
+                        label = { text: "Output folder" }
+                    }
                 }
 
-                role_section_3 = <View> {
-                    width: Fill, height: Fit
+                // Airtime per role, from the last render or an estimate
+                stats_tab = <View> {
+                    width: Fill, height: Fill
                     flow: Down
-                    spacing: 4
+                    spacing: 8
                     visible: false
 
-                    role_3_label = <Label> {
-                        text: "Role 3"
+                    <SectionTitle> {
+                        text: "AIRTIME"
+                    }
+
+                    balance_bar = <BalanceBar> {}
+
+                    balance_legend = <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 8
+
+                        legend_0 = <LegendLabel> { draw_text: { color: (ROLE_COLOR_0) } }
+                        legend_1 = <LegendLabel> { draw_text: { color: (ROLE_COLOR_1) } }
+                        legend_2 = <LegendLabel> { draw_text: { color: (ROLE_COLOR_2) } }
+                        legend_3 = <LegendLabel> { draw_text: { color: (ROLE_COLOR_3) } }
+                    }
+
+                    // One multi-line label per column keeps the rows aligned
+                    stats_table = <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 10
+
+                        col_role = <StatsColumn> { width: Fill }
+                        col_time = <StatsColumn> {}
+                        col_share = <StatsColumn> {}
+                        col_turns = <StatsColumn> {}
+                        col_longest = <StatsColumn> {}
+                    }
+
+                    balance_warning = <Label> {
+                        width: Fill
+                        text: ""
                         draw_text: {
                             instance dark_mode: 0.0
-                            text_style: { font_size: 11.0 }
+                            text_style: { font_size: 10.0 }
                             fn get_color(self) -> vec4 {
                                 return mix(
-                                    vec4(0.25, 0.25, 0.30, 1.0),
-                                    vec4(0.75, 0.75, 0.80, 1.0),
+                                    vec4(0.75, 0.50, 0.15, 1.0),
+                                    vec4(0.95, 0.72, 0.40, 1.0),
                                     self.dark_mode
                                 );
                             }
                         }
                     }
 
-                    role_3_voice = <VoiceDropdown> {}
-                }
-
-                // Info text
-                info_label = <Label> {
-                    width: Fill
-                    text: "Paste script or click Import to detect roles automatically"
-                    draw_text: {
-                        instance dark_mode: 0.0
-                        text_style: { font_size: 10.0 }
-                        fn get_color(self) -> vec4 {
-                            return mix(
-                                vec4(0.50, 0.50, 0.55, 1.0),
-                                vec4(0.50, 0.50, 0.55, 1.0),
-                                self.dark_mode
-                            );
-                        }
+                    stats_source = <MetaCaption> {
+                        width: Fill
+                        text: ""
                     }
-                }
 
-                // Spacer
-                <View> { width: Fill, height: Fill }
+                    max_share_caption = <MetaCaption> { text: "Warn when one role exceeds (%)" }
+                    max_share_input = <MetaInput> { width: 60 }
+                }
 
                 // Output info
                 output_label = <Label> {
@@ -431,16 +821,46 @@ live_design! {
                     }
                 }
 
+                // Rough price and render time of the current script
+                render_estimate = <MetaCaption> {
+                    width: Fill
+                    text: ""
+                }
+
                 // Generate button
                 generate_btn = <PrimaryButton> {
                     text: "Generate Audio"
                 }
             }
         }
+
+        // Asks before an existing episode file is replaced
+        replace_confirm = <ConfirmDialog> {
+            abs_pos: vec2(0.0, 0.0)
+        }
     }
 }
 
-const VOICE_IDS: &[&str] = &["Ting-Ting", "Mei-Jia", "Sin-ji", "Samantha", "Alex", "Daniel"];
+/// `say` voices offered by the voice dropdowns, with their labels and the
+/// section they are listed under
+const VOICES: &[(&str, &str, &str)] = &[
+    ("Ting-Ting", "Ting-Ting (Chinese)", "Chinese"),
+    ("Mei-Jia", "Mei-Jia (Chinese)", "Chinese"),
+    ("Sin-ji", "Sin-ji (Cantonese)", "Chinese"),
+    ("Samantha", "Samantha (English)", "English"),
+    ("Alex", "Alex (English)", "English"),
+    ("Daniel", "Daniel (British)", "English"),
+];
+
+/// A generation in flight, with what is needed to tag its output
+struct PendingEpisode {
+    run: GenerationRun,
+    meta: EpisodeMetadata,
+    /// Tags edited by hand in the file being replaced
+    existing: Option<ExistingTags>,
+    /// Script the run renders, for matching its segment timings later
+    script_content: String,
+}
 
 #[derive(Live, LiveHook, Widget)]
 pub struct PodcastScreen {
@@ -455,12 +875,76 @@ pub struct PodcastScreen {
 
     #[rust]
     script: Option<PodcastScript>,
+
+    /// Persisted script, voices and episode metadata
+    #[rust]
+    project: PodcastProject,
+
+    #[rust]
+    project_loaded: bool,
+
+    /// Voice comparison being synthesized or played
+    #[rust]
+    comparison: Option<VoiceComparison>,
+
+    #[rust]
+    ab_player: Option<AbPlayer>,
+
+    /// Polls the comparison while the compare tool is open
+    #[rust]
+    compare_timer: Timer,
+
+    /// Segment lengths from the last render, with the script they belong to
+    #[rust]
+    rendered_timings: Option<(String, Vec<SegmentTiming>)>,
+
+    /// Price and measured speed of the TTS backend, reloaded after each run
+    #[rust]
+    render_cost: Option<TtsCostModel>,
+
+    /// A generation is running; Generate is ignored until it ends
+    #[rust]
+    generating: bool,
+
+    /// The running generation and what tagging needs once it ends
+    #[rust]
+    generation: Option<PendingEpisode>,
+
+    /// Polls the generation while it runs
+    #[rust]
+    generation_timer: Timer,
+
+    /// Existing output file waiting for the user to confirm replacing it
+    #[rust]
+    pending_replace: Option<PathBuf>,
+
+    /// Voice ids in dropdown order, favorites first
+    #[rust]
+    voice_ids: Vec<&'static str>,
+
+    /// Front matter last copied into the project, so edits made in the
+    /// metadata fields stick until the script's block changes
+    #[rust]
+    applied_front_matter: Option<ScriptMetadata>,
 }
 
 impl Widget for PodcastScreen {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
 
+        if !self.project_loaded {
+            self.project_loaded = true;
+            self.load_voice_order(cx);
+            self.restore_project(cx);
+        }
+
+        if self.compare_timer.is_event(event).is_some() {
+            self.poll_comparison(cx);
+        }
+        if self.generation_timer.is_event(event).is_some() {
+            self.poll_generation(cx);
+        }
+
         let actions = match event {
             Event::Actions(actions) => actions.as_slice(),
             _ => return,
@@ -471,15 +955,83 @@ impl Widget for PodcastScreen {
             self.import_script(cx);
         }
 
+        if self.view.button(ids!(editor_section.toolbar.export_btn)).clicked(actions) {
+            self.export_script(cx);
+        }
+
         // Clear button
         if self.view.button(ids!(editor_section.toolbar.clear_btn)).clicked(actions) {
             self.clear_all(cx);
         }
 
+        // Config tabs
+        if self.view.button(ids!(config_section.config_panel.tab_row.voices_tab_btn)).clicked(actions) {
+            self.show_config_tab(cx, false);
+        }
+        if self.view.button(ids!(config_section.config_panel.tab_row.stats_tab_btn)).clicked(actions) {
+            self.show_config_tab(cx, true);
+        }
+
+        if let Some(text) = self.view.text_input(ids!(config_section.config_panel.stats_tab.max_share_input)).changed(actions) {
+            if let Ok(percent) = text.trim().parse::<f64>() {
+                self.project.max_speaker_share = (percent / 100.0).clamp(0.5, 1.0);
+                self.save_project();
+                self.update_stats(cx);
+            }
+        }
+
         // Generate button
         if self.view.button(ids!(config_section.config_panel.generate_btn)).clicked(actions) {
             self.generate_audio(cx);
         }
+        if self.view.confirm_dialog(ids!(replace_confirm)).confirmed(actions) == Some(live_id!(replace_episode)) {
+            if let Some(path) = self.pending_replace.take() {
+                self.replace_audio(cx, path);
+            }
+        }
+
+        // Episode metadata
+        let metadata_inputs = [
+            ids!(config_section.config_panel.metadata_section.title_input),
+            ids!(config_section.config_panel.metadata_section.author_input),
+            ids!(config_section.config_panel.metadata_section.number_input),
+            ids!(config_section.config_panel.metadata_section.description_input),
+            ids!(config_section.config_panel.metadata_section.language_input),
+            ids!(config_section.config_panel.metadata_section.template_input),
+        ];
+        if metadata_inputs.iter().any(|id| self.view.text_input(*id).changed(actions).is_some()) {
+            self.sync_metadata_from_ui();
+            self.save_project();
+        }
+
+        if self.view.button(ids!(config_section.config_panel.metadata_section.artwork_row.artwork_btn)).clicked(actions) {
+            self.choose_artwork(cx);
+        }
+
+        if self.view.button(ids!(config_section.config_panel.metadata_section.artwork_row.artwork_clear_btn)).clicked(actions) {
+            self.project.metadata.artwork_path = None;
+            self.update_artwork_ui(cx);
+            self.save_project();
+        }
+
+        if let Some(dir) = self.view.path_picker(ids!(config_section.config_panel.output_picker)).path_changed(actions) {
+            self.project.output_dir = Some(dir);
+            self.save_project();
+        }
+
+        if self.view.button(ids!(config_section.config_panel.metadata_section.overwrite_btn)).clicked(actions) {
+            self.project.metadata.overwrite_tags = !self.project.metadata.overwrite_tags;
+            self.update_overwrite_ui(cx);
+            self.save_project();
+        }
+
+        if self.view.button(ids!(config_section.config_panel.metadata_section.replace_btn)).clicked(actions) {
+            self.project.replace_existing = !self.project.replace_existing;
+            self.update_overwrite_ui(cx);
+            self.save_project();
+        }
+
+        self.handle_compare_actions(cx, actions);
 
         // Handle dropdown changes
         for i in 0..3 {
@@ -489,19 +1041,24 @@ impl Widget for PodcastScreen {
                 _ => ids!(config_section.config_panel.role_section_3.role_3_voice),
             };
 
-            if let Some(selected) = self.view.drop_down(dropdown_id).selected(actions) {
+            if let Some(selected) = self.view.searchable_drop_down(dropdown_id).selected(actions) {
                 if i < self.detected_roles.len() {
                     let role = &self.detected_roles[i];
-                    let voice_id = VOICE_IDS.get(selected).unwrap_or(&"Ting-Ting");
+                    let voice_id = self.voice_ids.get(selected).unwrap_or(&"Ting-Ting");
                     self.role_voice_mapping.insert(role.clone(), voice_id.to_string());
                     ::log::info!("Assigned voice {} to role {}", voice_id, role);
+                    self.project.voice_assignments = self.role_voice_mapping.clone();
+                    self.save_project();
                 }
             }
         }
 
         // Check for text changes to detect roles
-        if self.view.text_input(ids!(editor_section.editor_panel.script_input)).changed(actions).is_some() {
+        if let Some(content) = self.view.text_input(ids!(editor_section.editor_panel.script_input)).changed(actions) {
             self.parse_script_content(cx);
+            self.project.script_content = content;
+            self.project.voice_assignments = self.role_voice_mapping.clone();
+            self.save_project();
         }
     }
 
@@ -511,6 +1068,159 @@ impl Widget for PodcastScreen {
 }
 
 impl PodcastScreen {
+    /// Load the saved project into the UI
+    /// Fill the voice dropdowns: voices starred in Settings first, then one
+    /// section per language
+    fn load_voice_order(&mut self, cx: &mut Cx) {
+        let prefs = VoicePrefs::load();
+        let mut voices = VOICES.to_vec();
+        favorites_first(&mut voices, &prefs.favorites, "say", |(id, _, _)| id);
+
+        let mut sections: Vec<(String, Vec<(&str, &str)>)> = Vec::new();
+        for (id, label, language) in voices {
+            let section = if prefs.is_favorite("say", id) { "Favorites" } else { language };
+            match sections.iter_mut().find(|(name, _)| name == section) {
+                Some((_, voices)) => voices.push((id, label)),
+                None => sections.push((section.to_string(), vec![(id, label)])),
+            }
+        }
+        // Dropdown indexes run through the sections in order
+        self.voice_ids = sections.iter().flat_map(|(_, voices)| voices.iter().map(|(id, _)| *id)).collect();
+        let groups: Vec<(String, Vec<String>)> = sections
+            .into_iter()
+            .map(|(name, voices)| (name, voices.into_iter().map(|(_, label)| label.to_string()).collect()))
+            .collect();
+        let dropdowns = [
+            ids!(config_section.config_panel.role_section_1.role_1_voice),
+            ids!(config_section.config_panel.role_section_2.role_2_voice),
+            ids!(config_section.config_panel.role_section_3.role_3_voice),
+            ids!(config_section.config_panel.compare_section.compare_voices.compare_voice_a),
+            ids!(config_section.config_panel.compare_section.compare_voices.compare_voice_b),
+        ];
+        for path in dropdowns {
+            self.view.searchable_drop_down(path).set_grouped_labels(cx, groups.clone());
+        }
+    }
+
+    fn restore_project(&mut self, cx: &mut Cx) {
+        self.project = PodcastProject::load();
+
+        if !self.project.script_content.is_empty() {
+            let content = self.project.script_content.clone();
+            self.view.text_input(ids!(editor_section.editor_panel.script_input)).set_text(cx, &content);
+            // The saved project already holds the front matter, maybe edited
+            // since, so only a later change to it is applied again
+            self.applied_front_matter = front_matter::parse(&content).metadata;
+            self.parse_script_content(cx);
+
+            // Saved assignments win over the defaults picked while parsing
+            for (role, voice) in &self.project.voice_assignments {
+                if self.detected_roles.contains(role) {
+                    self.role_voice_mapping.insert(role.clone(), voice.clone());
+                }
+            }
+            self.update_role_ui(cx);
+        }
+
+        self.update_metadata_ui(cx);
+        self.update_artwork_ui(cx);
+        self.update_overwrite_ui(cx);
+        let max_share = format!("{:.0}", self.project.max_speaker_share * 100.0);
+        self.view.text_input(ids!(config_section.config_panel.stats_tab.max_share_input)).set_text(cx, &max_share);
+        self.update_stats(cx);
+
+        // The default folder is created on demand, so make it exist up front
+        // rather than flagging it as missing on first launch
+        let output_dir = self.project.output_dir();
+        if self.project.output_dir.is_none() {
+            let _ = std::fs::create_dir_all(&output_dir);
+        }
+        self.view.path_picker(ids!(config_section.config_panel.output_picker))
+            .set_path(cx, Some(&output_dir));
+    }
+
+    fn save_project(&mut self) {
+        if let Err(e) = self.project.save() {
+            ::log::error!("Failed to save podcast project: {}", e);
+        }
+    }
+
+    /// Show the project metadata in the text fields
+    fn update_metadata_ui(&mut self, cx: &mut Cx) {
+        let meta = self.project.metadata.clone();
+        let number = meta.episode_number.map(|n| n.to_string()).unwrap_or_default();
+        let fields = [
+            (ids!(config_section.config_panel.metadata_section.title_input), meta.title.as_str()),
+            (ids!(config_section.config_panel.metadata_section.author_input), meta.author.as_str()),
+            (ids!(config_section.config_panel.metadata_section.number_input), number.as_str()),
+            (ids!(config_section.config_panel.metadata_section.description_input), meta.description.as_str()),
+            (ids!(config_section.config_panel.metadata_section.language_input), meta.language.as_str()),
+            (ids!(config_section.config_panel.metadata_section.template_input), meta.filename_template.as_str()),
+        ];
+        for (id, text) in fields {
+            self.view.text_input(id).set_text(cx, text);
+        }
+    }
+
+    /// Copy the text fields into the project metadata
+    fn sync_metadata_from_ui(&mut self) {
+        let text = |id| self.view.text_input(id).text();
+        let title = text(ids!(config_section.config_panel.metadata_section.title_input));
+        let author = text(ids!(config_section.config_panel.metadata_section.author_input));
+        let number = text(ids!(config_section.config_panel.metadata_section.number_input));
+        let description = text(ids!(config_section.config_panel.metadata_section.description_input));
+        let language = text(ids!(config_section.config_panel.metadata_section.language_input));
+        let template = text(ids!(config_section.config_panel.metadata_section.template_input));
+
+        let meta = &mut self.project.metadata;
+        meta.title = title;
+        meta.author = author;
+        meta.episode_number = number.trim().parse().ok();
+        meta.description = description;
+        meta.language = language.trim().to_string();
+        meta.filename_template = template;
+    }
+
+    fn choose_artwork(&mut self, cx: &mut Cx) {
+        let file_dialog = rfd::FileDialog::new()
+            .add_filter("Images", &["jpg", "jpeg", "png"])
+            .set_title("Select episode artwork");
+
+        if let Some(path) = file_dialog.pick_file() {
+            self.project.metadata.artwork_path = Some(path);
+            self.update_artwork_ui(cx);
+            self.save_project();
+        }
+    }
+
+    fn update_artwork_ui(&mut self, cx: &mut Cx) {
+        let name = self.project.metadata.artwork_path.as_ref()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string());
+        self.view.label(ids!(config_section.config_panel.metadata_section.artwork_row.artwork_label))
+            .set_text(cx, name.as_deref().unwrap_or("None"));
+        self.view.button(ids!(config_section.config_panel.metadata_section.artwork_row.artwork_clear_btn))
+            .set_visible(cx, name.is_some());
+        self.view.redraw(cx);
+    }
+
+    fn update_overwrite_ui(&mut self, cx: &mut Cx) {
+        let text = if self.project.metadata.overwrite_tags {
+            "Overwrite manually edited tags"
+        } else {
+            "Keep manually edited tags"
+        };
+        self.view.button(ids!(config_section.config_panel.metadata_section.overwrite_btn)).set_text(cx, text);
+
+        let text = if self.project.replace_existing {
+            "Replace existing files (asks first)"
+        } else {
+            "Keep existing files"
+        };
+        self.view.button(ids!(config_section.config_panel.metadata_section.replace_btn)).set_text(cx, text);
+        self.view.redraw(cx);
+    }
+
     fn import_script(&mut self, cx: &mut Cx) {
         ::log::info!("Import button clicked");
 
@@ -535,31 +1245,104 @@ impl PodcastScreen {
         }
     }
 
+    /// Save the script with the episode settings and voices as front matter
+    fn export_script(&mut self, cx: &mut Cx) {
+        let content = self.view.text_input(ids!(editor_section.editor_panel.script_input)).text();
+        if content.trim().is_empty() {
+            self.set_status(cx, "No script");
+            return;
+        }
+        self.sync_metadata_from_ui();
+
+        let mut project = self.project.clone();
+        project.script_content = content;
+        project.voice_assignments = self.role_voice_mapping.iter()
+            .filter(|(role, _)| self.detected_roles.contains(role))
+            .map(|(role, voice)| (role.clone(), voice.clone()))
+            .collect();
+
+        let title = self.script.as_ref().map_or("", |s| s.title.as_str());
+        let title = if project.metadata.title.trim().is_empty() { title } else { project.metadata.title.as_str() };
+        let stem = sanitize_filename(title);
+        let file_name = format!("{}.md", if stem.is_empty() { "script" } else { stem.as_str() });
+        let file_dialog = rfd::FileDialog::new()
+            .add_filter("Markdown", &["md"])
+            .add_filter("Text", &["txt"])
+            .set_file_name(&file_name)
+            .set_title("Save script");
+
+        if let Some(path) = file_dialog.save_file() {
+            match std::fs::write(&path, front_matter::export_project(&project)) {
+                Ok(()) => self.set_status(cx, &format!("Saved: {}", path.file_name().unwrap_or_default().to_string_lossy())),
+                Err(e) => self.set_status(cx, &format!("Error: {}", e)),
+            }
+        }
+    }
+
     fn parse_script_content(&mut self, cx: &mut Cx) {
         let content = self.view.text_input(ids!(editor_section.editor_panel.script_input)).text();
 
         if content.trim().is_empty() {
             self.detected_roles.clear();
+            self.script = None;
+            self.applied_front_matter = None;
             self.update_role_ui(cx);
+            self.update_validation_ui(cx, &[]);
+            self.update_stats(cx);
             return;
         }
 
         match parser::parse_content(&content) {
             Ok(script) => {
                 self.detected_roles = script.roles.iter().map(|r| r.name.clone()).collect();
+                let front = script.metadata.clone();
+                let mut warnings = script.warnings.clone();
                 self.script = Some(script);
 
+                if front != self.applied_front_matter {
+                    if let Some(front) = &front {
+                        front.apply_to(&mut self.project.metadata);
+                        self.update_metadata_ui(cx);
+                        self.save_project();
+                    }
+                    self.applied_front_matter = front.clone();
+                }
+
                 // Set default voice assignments
+                let defaults = self.language_voices();
                 for (i, role) in self.detected_roles.iter().enumerate() {
-                    let default_voice = VOICE_IDS.get(i % VOICE_IDS.len()).unwrap_or(&"Ting-Ting");
+                    let default_voice = defaults.get(i % defaults.len()).unwrap_or(&"Ting-Ting");
                     self.role_voice_mapping.insert(role.clone(), default_voice.to_string());
                 }
 
+                // Voices named in the front matter win over the defaults
+                for (role, voice) in front.iter().flat_map(|f| &f.voices) {
+                    if !self.detected_roles.contains(role) {
+                        warnings.push(format!("Voice for `{}` ignored: no such role in the script", role));
+                    } else if let Some(id) = self.voice_ids.iter().find(|id| id.eq_ignore_ascii_case(voice)) {
+                        self.role_voice_mapping.insert(role.clone(), id.to_string());
+                    } else {
+                        warnings.push(format!("Unknown voice `{}` for {}; using the default", voice, role));
+                    }
+                }
+
+                // Segments the TTS backend can't read in one go
+                let engine = TTSEngine::new();
+                if let Some(ref script) = self.script {
+                    warnings.extend(parser::length_warnings(
+                        &parser::parse_segments(script),
+                        engine.backend_name(),
+                        engine.max_chars(),
+                    ));
+                }
+
                 self.update_role_ui(cx);
+                self.update_validation_ui(cx, &warnings);
 
                 if !self.detected_roles.is_empty() {
                     self.set_status(cx, &format!("{} roles found", self.detected_roles.len()));
                 }
+                self.update_stats(cx);
             }
             Err(e) => {
                 ::log::error!("Parse error: {}", e);
@@ -567,6 +1350,33 @@ impl PodcastScreen {
         }
     }
 
+    /// Voices in dropdown order, limited to the episode language when any
+    /// voice speaks it (`zh` matches `zh-CN` and `zh-TW`)
+    fn language_voices(&self) -> Vec<&'static str> {
+        let language = self.project.metadata.language.trim().to_ascii_lowercase();
+        let known = MacOSVoice::all_voices();
+        let speaks = |id: &&'static str| {
+            known.iter().any(|v| {
+                let code = v.language.to_ascii_lowercase();
+                v.id == *id && (code == language || code.starts_with(&format!("{}-", language)))
+            })
+        };
+        let matching: Vec<_> = self.voice_ids.iter().copied().filter(speaks).collect();
+        if language.is_empty() || matching.is_empty() {
+            self.voice_ids.clone()
+        } else {
+            matching
+        }
+    }
+
+    /// Script problems (front matter, overlong segments) under the editor, one per line
+    fn update_validation_ui(&mut self, cx: &mut Cx, warnings: &[String]) {
+        let label = self.view.label(ids!(editor_section.validation_label));
+        label.set_text(cx, &warnings.join("\n"));
+        label.set_visible(cx, !warnings.is_empty());
+        self.view.redraw(cx);
+    }
+
     fn update_role_ui(&mut self, cx: &mut Cx) {
         let role_sections = [
             (ids!(config_section.config_panel.role_section_1), ids!(config_section.config_panel.role_section_1.role_1_label), ids!(config_section.config_panel.role_section_1.role_1_voice)),
@@ -581,8 +1391,8 @@ impl PodcastScreen {
 
                 // Set default selection
                 if let Some(voice) = self.role_voice_mapping.get(&self.detected_roles[i]) {
-                    if let Some(idx) = VOICE_IDS.iter().position(|v| *v == voice) {
-                        self.view.drop_down(*dropdown_id).set_selected_item(cx, idx);
+                    if let Some(idx) = self.voice_ids.iter().position(|v| *v == voice) {
+                        self.view.searchable_drop_down(*dropdown_id).set_selected_item(cx, idx);
                     }
                 }
             } else {
@@ -610,77 +1420,534 @@ impl PodcastScreen {
         self.detected_roles.clear();
         self.role_voice_mapping.clear();
         self.script = None;
+        self.applied_front_matter = None;
         self.update_role_ui(cx);
+        self.update_validation_ui(cx, &[]);
+        self.update_stats(cx);
         self.set_status(cx, "Ready");
         self.view.label(ids!(config_section.config_panel.output_label)).set_text(cx, "");
+
+        self.project.script_content.clear();
+        self.project.voice_assignments.clear();
+        self.save_project();
+        self.close_compare(cx);
     }
 
     fn generate_audio(&mut self, cx: &mut Cx) {
         ::log::info!("Generate button clicked");
+        if self.generating {
+            return;
+        }
+        let Some((generator, script, meta)) = self.prepare_generation(cx) else {
+            return;
+        };
+
+        let settings = AudioSettings::default();
+        let output_path = generator.output_path(&meta, &settings);
+        if self.project.replace_existing && output_path.exists() {
+            self.confirm_replace(cx, output_path);
+            return;
+        }
+        self.run_generation(cx, generator, script, meta, output_path, false);
+    }
 
+    /// Generate into `output_path`, replacing the file there (confirmed)
+    fn replace_audio(&mut self, cx: &mut Cx, output_path: PathBuf) {
+        if self.generating {
+            return;
+        }
+        let Some((generator, script, meta)) = self.prepare_generation(cx) else {
+            return;
+        };
+        self.run_generation(cx, generator, script, meta, output_path, true);
+    }
+
+    fn confirm_replace(&mut self, cx: &mut Cx, output_path: PathBuf) {
+        let filename = output_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.pending_replace = Some(output_path);
+
+        // The dialog is absolutely positioned; cover the whole screen
+        let rect = self.view.area().rect(cx);
+        let dialog = self.view.confirm_dialog(ids!(replace_confirm));
+        dialog.apply_over(cx, live!{
+            abs_pos: (rect.pos)
+            width: (rect.size.x)
+            height: (rect.size.y)
+        });
+        dialog.set_confirm_text(cx, "Replace");
+        dialog.show(
+            cx,
+            live_id!(replace_episode),
+            "Replace existing episode?",
+            &format!("\"{}\" already exists in the output folder and will be overwritten.", filename),
+        );
+    }
+
+    /// Check the script, voices and output folder, and set up a generator
+    fn prepare_generation(&mut self, cx: &mut Cx) -> Option<(AudioGenerator, PodcastScript, EpisodeMetadata)> {
         let content = self.view.text_input(ids!(editor_section.editor_panel.script_input)).text();
 
         if content.trim().is_empty() {
             self.set_status(cx, "No script");
-            return;
+            return None;
         }
 
         if self.detected_roles.is_empty() {
             self.set_status(cx, "No roles");
-            return;
+            return None;
         }
 
         // Check voice assignments
         for role in &self.detected_roles {
             if !self.role_voice_mapping.contains_key(role) {
                 self.set_status(cx, "Missing voice");
-                return;
+                return None;
             }
         }
 
-        self.set_status(cx, "Generating...");
+        let picker = self.view.path_picker(ids!(config_section.config_panel.output_picker));
+        if !picker.is_valid() {
+            self.set_status(cx, "Check output folder");
+            self.view.label(ids!(config_section.config_panel.output_label))
+                .set_text(cx, picker.status().message());
+            return None;
+        }
+        let output_dir = self.project.output_dir();
+
+        let generator = match AudioGenerator::new(output_dir) {
+            Ok(generator) => generator,
+            Err(e) => {
+                self.set_status(cx, "Error");
+                self.view.label(ids!(config_section.config_panel.output_label))
+                    .set_text(cx, &format!("{}", e));
+                return None;
+            }
+        };
+        let script = self.script.clone()?;
 
-        // Get output directory
-        let output_dir = dirs::document_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("MoFaPodcast");
+        self.sync_metadata_from_ui();
+        let mut meta = self.project.metadata.clone();
+        if meta.title.trim().is_empty() {
+            meta.title = script.title.clone();
+        }
+        Some((generator, script, meta))
+    }
 
-        match AudioGenerator::new(output_dir) {
-            Ok(generator) => {
-                if let Some(ref script) = self.script {
-                    let settings = AudioSettings::default();
-
-                    match generator.generate(script, &self.role_voice_mapping, &settings, None) {
-                        Ok(output_path) => {
-                            self.set_status(cx, "Complete!");
-                            let filename = output_path.file_name()
-                                .map(|n| n.to_string_lossy().to_string())
-                                .unwrap_or_default();
-                            self.view.label(ids!(config_section.config_panel.output_label))
-                                .set_text(cx, &format!("Saved: {}", filename));
-                            ::log::info!("Audio generated: {:?}", output_path);
-                        }
-                        Err(e) => {
-                            self.set_status(cx, "Error");
-                            self.view.label(ids!(config_section.config_panel.output_label))
-                                .set_text(cx, &format!("{}", e));
-                            ::log::error!("Generation failed: {}", e);
-                        }
+    /// Start rendering the episode on a background thread that holds the
+    /// output folder's lock. Unless `replace` is set, an existing file keeps
+    /// its name and the new one is numbered.
+    fn run_generation(
+        &mut self,
+        cx: &mut Cx,
+        generator: AudioGenerator,
+        script: PodcastScript,
+        meta: EpisodeMetadata,
+        output_path: PathBuf,
+        replace: bool,
+    ) {
+        let lock = match generator.lock() {
+            Ok(lock) => lock,
+            Err(e) => {
+                self.set_status(cx, "Output folder busy");
+                self.view.label(ids!(config_section.config_panel.output_label))
+                    .set_text(cx, &format!("{}", e));
+                return;
+            }
+        };
+        // Picked under the lock, so another instance cannot take the same name
+        let output_path = if replace { output_path } else { unique_path(&output_path) };
+        let settings = AudioSettings::default();
+        self.set_generating(cx, true);
+
+        // Capture hand-edited tags before the file is replaced. The stored
+        // snapshot only describes the file it was written to.
+        let last_written = self.project.last_written_tags.as_ref()
+            .filter(|_| self.project.last_output.as_deref() == Some(output_path.as_path()));
+        let existing = tagging::capture_existing(&output_path, last_written);
+
+        let script_content = script.content.clone();
+        self.generation = Some(PendingEpisode {
+            run: GenerationRun::start(generator, script, output_path, self.role_voice_mapping.clone(), settings, lock),
+            meta,
+            existing,
+            script_content,
+        });
+        self.generation_timer = cx.start_interval(0.2);
+    }
+
+    /// Show the running generation's progress, and tag the episode once
+    /// it is written
+    fn poll_generation(&mut self, cx: &mut Cx) {
+        let Some(pending) = self.generation.as_ref() else {
+            cx.stop_timer(self.generation_timer);
+            return;
+        };
+        let mut finished = None;
+        for update in pending.run.poll() {
+            match update {
+                GenerationUpdate::Progress(progress) => self.set_status(cx, &progress.status()),
+                GenerationUpdate::Finished(result) => finished = Some(result),
+            }
+        }
+        let Some(result) = finished else {
+            return;
+        };
+        cx.stop_timer(self.generation_timer);
+        let Some(PendingEpisode { meta, existing, script_content, .. }) = self.generation.take() else {
+            return;
+        };
+        self.set_generating(cx, false);
+
+        match result {
+            Ok(report) => {
+                self.render_cost = None;
+                self.rendered_timings = Some((script_content, report.timings.clone()));
+                self.update_stats(cx);
+                let output_path = report.output.clone();
+                let filename = output_path.file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let saved = match report.summary() {
+                    Some(summary) => format!("Saved: {}, {}", filename, summary),
+                    None => format!("Saved: {}", filename),
+                };
+
+                match tagging::tag_episode(&output_path, &meta, existing.as_ref()) {
+                    Ok(snapshot) => {
+                        self.set_status(cx, "Complete!");
+                        self.view.label(ids!(config_section.config_panel.output_label))
+                            .set_text(cx, &saved);
+                        self.project.last_output = Some(output_path.clone());
+                        self.project.last_written_tags = Some(snapshot);
+                    }
+                    Err(e) => {
+                        self.set_status(cx, "Saved without tags");
+                        self.view.label(ids!(config_section.config_panel.output_label))
+                            .set_text(cx, &format!("{} ({})", saved, e));
+                        ::log::warn!("Tagging failed: {}", e);
                     }
                 }
+                self.save_project();
+                ::log::info!("Audio generated: {:?}", output_path);
             }
             Err(e) => {
                 self.set_status(cx, "Error");
                 self.view.label(ids!(config_section.config_panel.output_label))
                     .set_text(cx, &format!("{}", e));
+                ::log::error!("Generation failed: {}", e);
+            }
+        }
+    }
+
+    fn set_generating(&mut self, cx: &mut Cx, generating: bool) {
+        self.generating = generating;
+        let text = if generating { "Generating..." } else { "Generate Audio" };
+        self.view.button(ids!(config_section.config_panel.generate_btn)).set_text(cx, text);
+        if generating {
+            self.set_status(cx, "Generating...");
+        }
+    }
+
+    fn show_config_tab(&mut self, cx: &mut Cx, stats: bool) {
+        self.view.view(ids!(config_section.config_panel.voices_tab)).set_visible(cx, !stats);
+        self.view.view(ids!(config_section.config_panel.stats_tab)).set_visible(cx, stats);
+        let (voices_active, stats_active) = if stats { (0.0, 1.0) } else { (1.0, 0.0) };
+        self.view.button(ids!(config_section.config_panel.tab_row.voices_tab_btn)).apply_over(cx, live! {
+            draw_bg: { active: (voices_active) }
+        });
+        self.view.button(ids!(config_section.config_panel.tab_row.stats_tab_btn)).apply_over(cx, live! {
+            draw_bg: { active: (stats_active) }
+        });
+        self.view.redraw(cx);
+    }
+
+    /// Recompute airtime per role and redraw the Stats tab
+    ///
+    /// Uses the last render's segment lengths while the script is the one
+    /// that was rendered, and the reading speed estimate otherwise.
+    fn update_stats(&mut self, cx: &mut Cx) {
+        let stats = match self.script {
+            Some(ref script) => match self.rendered_timings {
+                Some((ref content, ref timings)) if *content == script.content => {
+                    SpeakerStats::from_timings(timings, false)
+                }
+                _ => SpeakerStats::from_timings(&SegmentTiming::estimate(&parser::parse_segments(script)), true),
+            },
+            None => SpeakerStats::default(),
+        };
+
+        // Cumulative segment ends for the bar; roles past the fourth share
+        // the "others" segment
+        let ends: [f64; 4] = std::array::from_fn(|i| stats.roles.iter().take(i + 1).map(|r| r.share).sum());
+        let others: f64 = stats.roles.iter().map(|r| r.share).sum();
+        self.view.view(ids!(config_section.config_panel.stats_tab.balance_bar)).apply_over(cx, live! {
+            draw_bg: { end0: (ends[0]), end1: (ends[1]), end2: (ends[2]), end3: (ends[3]), others: (others) }
+        });
+
+        let legends = [
+            ids!(config_section.config_panel.stats_tab.balance_legend.legend_0),
+            ids!(config_section.config_panel.stats_tab.balance_legend.legend_1),
+            ids!(config_section.config_panel.stats_tab.balance_legend.legend_2),
+            ids!(config_section.config_panel.stats_tab.balance_legend.legend_3),
+        ];
+        for (i, id) in legends.into_iter().enumerate() {
+            let name = stats.roles.get(i).map(|r| r.role.as_str()).unwrap_or("");
+            self.view.label(id).set_text(cx, name);
+        }
+
+        let mut columns = [
+            "Role".to_string(),
+            "Time".to_string(),
+            "Share".to_string(),
+            "Turns".to_string(),
+            "Longest".to_string(),
+        ];
+        for role in &stats.roles {
+            let cells = [
+                role.role.clone(),
+                format_duration(role.secs),
+                format!("{:.0}%", role.share * 100.0),
+                role.turns.to_string(),
+                format_duration(role.longest_turn_secs),
+            ];
+            for (column, cell) in columns.iter_mut().zip(cells) {
+                column.push('\n');
+                column.push_str(&cell);
             }
         }
+        let column_ids = [
+            ids!(config_section.config_panel.stats_tab.stats_table.col_role),
+            ids!(config_section.config_panel.stats_tab.stats_table.col_time),
+            ids!(config_section.config_panel.stats_tab.stats_table.col_share),
+            ids!(config_section.config_panel.stats_tab.stats_table.col_turns),
+            ids!(config_section.config_panel.stats_tab.stats_table.col_longest),
+        ];
+        for (id, text) in column_ids.into_iter().zip(columns) {
+            self.view.label(id).set_text(cx, &text);
+        }
+
+        let warning = stats.dominant(self.project.max_speaker_share)
+            .map(|role| format!(
+                "{} has {:.0}% of the airtime; consider giving the others more to say",
+                role.role,
+                role.share * 100.0
+            ))
+            .unwrap_or_default();
+        self.view.label(ids!(config_section.config_panel.stats_tab.balance_warning)).set_text(cx, &warning);
+
+        let source = if stats.roles.is_empty() {
+            "No dialogue yet".to_string()
+        } else if stats.estimated {
+            format!("Total {} (estimated from reading speed)", format_duration(stats.total_secs))
+        } else {
+            format!("Total {} (measured from the last render)", format_duration(stats.total_secs))
+        };
+        self.view.label(ids!(config_section.config_panel.stats_tab.stats_source)).set_text(cx, &source);
+
+        self.update_estimate(cx);
+        self.view.redraw(cx);
+    }
+
+    /// Show what rendering the script should cost next to Generate. Only a
+    /// guide: nothing here stops a generation.
+    fn update_estimate(&mut self, cx: &mut Cx) {
+        let cost = *self.render_cost
+            .get_or_insert_with(|| TTSEngine::new().cost_model(&TtsSpeedLog::load()));
+        let text = match self.script {
+            Some(ref script) => {
+                let segments = parser::parse_segments(script);
+                if segments.is_empty() {
+                    String::new()
+                } else {
+                    estimate_render(&segments, &cost).summary()
+                }
+            }
+            None => String::new(),
+        };
+        self.view.label(ids!(config_section.config_panel.render_estimate)).set_text(cx, &text);
     }
 
     fn set_status(&mut self, cx: &mut Cx, text: &str) {
         self.view.label(ids!(config_section.status_label)).set_text(cx, text);
         self.view.redraw(cx);
     }
+
+    fn handle_compare_actions(&mut self, cx: &mut Cx, actions: &[Action]) {
+        if self.view.button(ids!(config_section.config_panel.compare_open_btn)).clicked(actions) {
+            self.open_compare(cx);
+        }
+        if self.view.button(ids!(config_section.config_panel.compare_section.compare_close_btn)).clicked(actions) {
+            self.close_compare(cx);
+        }
+        if let Some(idx) = self.view.drop_down(ids!(config_section.config_panel.compare_section.compare_role)).selected(actions) {
+            self.select_compare_role(cx, idx);
+        }
+        if self.view.button(ids!(config_section.config_panel.compare_section.compare_synth_btn)).clicked(actions) {
+            self.start_comparison(cx);
+        }
+
+        let Some(player) = self.ab_player.as_mut() else {
+            return;
+        };
+        let result = if self.view.button(ids!(config_section.config_panel.compare_section.compare_player.compare_play_btn)).clicked(actions) {
+            if player.is_playing() {
+                player.stop();
+                Ok(())
+            } else {
+                player.play()
+            }
+        } else if self.view.button(ids!(config_section.config_panel.compare_section.compare_player.compare_toggle_btn)).clicked(actions) {
+            player.switch(player.side().other())
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            self.set_compare_status(cx, &e);
+        }
+        self.update_compare_player_ui(cx);
+
+        if self.view.button(ids!(config_section.config_panel.compare_section.compare_player.compare_use_btn)).clicked(actions) {
+            self.use_compared_voice(cx);
+        }
+    }
+
+    fn open_compare(&mut self, cx: &mut Cx) {
+        if self.detected_roles.is_empty() {
+            self.set_status(cx, "No roles");
+            return;
+        }
+
+        self.view.button(ids!(config_section.config_panel.compare_open_btn)).set_visible(cx, false);
+        self.view.view(ids!(config_section.config_panel.compare_section)).set_visible(cx, true);
+        let role_dropdown = self.view.drop_down(ids!(config_section.config_panel.compare_section.compare_role));
+        role_dropdown.set_labels(cx, self.detected_roles.clone());
+        role_dropdown.set_selected_item(cx, 0);
+        self.select_compare_role(cx, 0);
+        self.set_compare_status(cx, "Pick two voices and synthesize");
+    }
+
+    /// Close the compare tool, stopping playback and deleting the samples
+    fn close_compare(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.compare_timer);
+        self.ab_player = None;
+        self.comparison = None;
+        self.view.view(ids!(config_section.config_panel.compare_section)).set_visible(cx, false);
+        self.view.view(ids!(config_section.config_panel.compare_section.compare_player)).set_visible(cx, false);
+        self.view.button(ids!(config_section.config_panel.compare_open_btn)).set_visible(cx, true);
+        self.view.redraw(cx);
+    }
+
+    /// Default the paragraph to the role's first line and voice A to its current voice
+    fn select_compare_role(&mut self, cx: &mut Cx, idx: usize) {
+        let Some(role) = self.detected_roles.get(idx) else {
+            return;
+        };
+        let paragraph = self.script.as_ref()
+            .and_then(|script| parser::first_segment_for_role(script, role))
+            .unwrap_or_default();
+        self.view.text_input(ids!(config_section.config_panel.compare_section.compare_text))
+            .set_text(cx, &paragraph);
+
+        let current = self.role_voice_mapping.get(role)
+            .and_then(|voice| self.voice_ids.iter().position(|v| *v == voice.as_str()))
+            .unwrap_or(0);
+        self.view.searchable_drop_down(ids!(config_section.config_panel.compare_section.compare_voices.compare_voice_a))
+            .set_selected_item(cx, current);
+        self.view.searchable_drop_down(ids!(config_section.config_panel.compare_section.compare_voices.compare_voice_b))
+            .set_selected_item(cx, (current + 1) % self.voice_ids.len());
+        self.view.redraw(cx);
+    }
+
+    fn start_comparison(&mut self, cx: &mut Cx) {
+        let text = self.view.text_input(ids!(config_section.config_panel.compare_section.compare_text)).text();
+        if text.trim().is_empty() {
+            self.set_compare_status(cx, "Enter a paragraph to compare");
+            return;
+        }
+        let idx_a = self.view.searchable_drop_down(ids!(config_section.config_panel.compare_section.compare_voices.compare_voice_a)).selected_item();
+        let idx_b = self.view.searchable_drop_down(ids!(config_section.config_panel.compare_section.compare_voices.compare_voice_b)).selected_item();
+        let voice_a = self.voice_ids.get(idx_a).unwrap_or(&"Ting-Ting");
+        let voice_b = self.voice_ids.get(idx_b).unwrap_or(&"Ting-Ting");
+
+        // Replacing the old comparison deletes its samples
+        self.ab_player = None;
+        self.comparison = Some(VoiceComparison::start(text.trim(), voice_a, voice_b));
+        self.view.view(ids!(config_section.config_panel.compare_section.compare_player)).set_visible(cx, false);
+        self.set_compare_status(cx, &format!("Synthesizing {} and {}...", voice_a, voice_b));
+        cx.stop_timer(self.compare_timer);
+        self.compare_timer = cx.start_interval(0.2);
+    }
+
+    fn poll_comparison(&mut self, cx: &mut Cx) {
+        if self.ab_player.is_some() {
+            // Flip the play button back once the sample ends
+            self.update_compare_player_ui(cx);
+            return;
+        }
+        let Some(comparison) = self.comparison.as_ref() else {
+            return;
+        };
+
+        if let Some(error) = comparison.error() {
+            self.comparison = None;
+            self.set_compare_status(cx, &format!("Synthesis failed: {}", error));
+            return;
+        }
+        let Some(paths) = comparison.ready_paths() else {
+            return;
+        };
+
+        match AbPlayer::new(&paths) {
+            Ok(player) => {
+                self.ab_player = Some(player);
+                self.view.view(ids!(config_section.config_panel.compare_section.compare_player)).set_visible(cx, true);
+                self.set_compare_status(cx, "Switching keeps your place in the paragraph");
+                self.update_compare_player_ui(cx);
+            }
+            Err(e) => self.set_compare_status(cx, &e),
+        }
+    }
+
+    fn update_compare_player_ui(&mut self, cx: &mut Cx) {
+        let (Some(player), Some(comparison)) = (self.ab_player.as_ref(), self.comparison.as_ref()) else {
+            return;
+        };
+        let side = player.side();
+        let label = match side {
+            Side::A => format!("A: {}", comparison.voice(side)),
+            Side::B => format!("B: {}", comparison.voice(side)),
+        };
+        self.view.button(ids!(config_section.config_panel.compare_section.compare_player.compare_toggle_btn))
+            .set_text(cx, &label);
+        self.view.button(ids!(config_section.config_panel.compare_section.compare_player.compare_play_btn))
+            .set_text(cx, if player.is_playing() { "Stop" } else { "Play" });
+        self.view.redraw(cx);
+    }
+
+    /// Assign the voice currently heard to the compared role
+    fn use_compared_voice(&mut self, cx: &mut Cx) {
+        let (Some(player), Some(comparison)) = (self.ab_player.as_ref(), self.comparison.as_ref()) else {
+            return;
+        };
+        let idx = self.view.drop_down(ids!(config_section.config_panel.compare_section.compare_role)).selected_item();
+        let Some(role) = self.detected_roles.get(idx).cloned() else {
+            return;
+        };
+        let voice = comparison.voice(player.side()).to_string();
+
+        self.role_voice_mapping.insert(role.clone(), voice.clone());
+        self.project.voice_assignments = self.role_voice_mapping.clone();
+        self.save_project();
+        self.update_role_ui(cx);
+        self.set_compare_status(cx, &format!("{} now reads {}", voice, role));
+    }
+
+    fn set_compare_status(&mut self, cx: &mut Cx, text: &str) {
+        self.view.label(ids!(config_section.config_panel.compare_section.compare_status)).set_text(cx, text);
+        self.view.redraw(cx);
+    }
 }
 
 impl PodcastScreenRef {
@@ -704,6 +1971,92 @@ impl PodcastScreenRef {
                 draw_bg: { dark_mode: (dark_mode) }
             });
 
+            // Episode metadata
+            let inputs = [
+                ids!(config_section.config_panel.metadata_section.title_input),
+                ids!(config_section.config_panel.metadata_section.author_input),
+                ids!(config_section.config_panel.metadata_section.number_input),
+                ids!(config_section.config_panel.metadata_section.description_input),
+                ids!(config_section.config_panel.metadata_section.language_input),
+                ids!(config_section.config_panel.metadata_section.template_input),
+                ids!(config_section.config_panel.compare_section.compare_text),
+                ids!(config_section.config_panel.stats_tab.max_share_input),
+            ];
+            for id in inputs {
+                inner.view.text_input(id).apply_over(cx, live! {
+                    draw_bg: { dark_mode: (dark_mode) }
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
+            let captions = [
+                ids!(editor_section.validation_label),
+                ids!(config_section.config_panel.metadata_section.title_caption),
+                ids!(config_section.config_panel.metadata_section.author_caption),
+                ids!(config_section.config_panel.metadata_section.number_caption),
+                ids!(config_section.config_panel.metadata_section.description_caption),
+                ids!(config_section.config_panel.metadata_section.language_caption),
+                ids!(config_section.config_panel.metadata_section.artwork_caption),
+                ids!(config_section.config_panel.metadata_section.template_caption),
+                ids!(config_section.config_panel.metadata_section.artwork_row.artwork_label),
+                ids!(config_section.config_panel.compare_section.compare_role_caption),
+                ids!(config_section.config_panel.compare_section.compare_text_caption),
+                ids!(config_section.config_panel.compare_section.compare_status),
+                ids!(config_section.config_panel.stats_tab.balance_warning),
+                ids!(config_section.config_panel.stats_tab.stats_source),
+                ids!(config_section.config_panel.stats_tab.max_share_caption),
+                ids!(config_section.config_panel.render_estimate),
+                ids!(config_section.config_panel.stats_tab.stats_table.col_role),
+                ids!(config_section.config_panel.stats_tab.stats_table.col_time),
+                ids!(config_section.config_panel.stats_tab.stats_table.col_share),
+                ids!(config_section.config_panel.stats_tab.stats_table.col_turns),
+                ids!(config_section.config_panel.stats_tab.stats_table.col_longest),
+            ];
+            for id in captions {
+                inner.view.label(id).apply_over(cx, live! {
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
+            let buttons = [
+                ids!(config_section.config_panel.metadata_section.artwork_row.artwork_btn),
+                ids!(config_section.config_panel.metadata_section.artwork_row.artwork_clear_btn),
+                ids!(config_section.config_panel.metadata_section.overwrite_btn),
+                ids!(config_section.config_panel.metadata_section.replace_btn),
+                ids!(config_section.config_panel.compare_open_btn),
+                ids!(config_section.config_panel.compare_section.compare_close_btn),
+                ids!(config_section.config_panel.compare_section.compare_synth_btn),
+                ids!(config_section.config_panel.compare_section.compare_player.compare_play_btn),
+                ids!(config_section.config_panel.compare_section.compare_player.compare_toggle_btn),
+                ids!(config_section.config_panel.compare_section.compare_player.compare_use_btn),
+                ids!(config_section.config_panel.tab_row.voices_tab_btn),
+                ids!(config_section.config_panel.tab_row.stats_tab_btn),
+            ];
+            for id in buttons {
+                inner.view.button(id).apply_over(cx, live! {
+                    draw_bg: { dark_mode: (dark_mode) }
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
+            inner.view.drop_down(ids!(config_section.config_panel.compare_section.compare_role)).apply_over(cx, live! {
+                draw_bg: { dark_mode: (dark_mode) }
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            let voice_dropdowns = [
+                ids!(config_section.config_panel.role_section_1.role_1_voice),
+                ids!(config_section.config_panel.role_section_2.role_2_voice),
+                ids!(config_section.config_panel.role_section_3.role_3_voice),
+                ids!(config_section.config_panel.compare_section.compare_voices.compare_voice_a),
+                ids!(config_section.config_panel.compare_section.compare_voices.compare_voice_b),
+            ];
+            for id in voice_dropdowns {
+                inner.view.searchable_drop_down(id).update_dark_mode(cx, dark_mode);
+            }
+            inner.view.path_picker(ids!(config_section.config_panel.output_picker))
+                .update_dark_mode(cx, dark_mode);
+            inner.view.confirm_dialog(ids!(replace_confirm)).update_dark_mode(cx, dark_mode);
+            inner.view.view(ids!(config_section.config_panel.stats_tab.balance_bar)).apply_over(cx, live! {
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+
             inner.view.redraw(cx);
         }
     }
@@ -0,0 +1,316 @@
+//! Script front matter
+//!
+//! A script may start with a metadata block between `---` lines, so a
+//! single file is enough to reproduce an episode:
+//!
+//! ```text
+//! ---
+//! title: "The Pilot"
+//! episode: 1
+//! language: en
+//! voices: {Host: Samantha, Guest: Daniel}
+//! ---
+//! Host: Welcome!
+//! ```
+//!
+//! Keys are `key: value` or `key = value`; values may be quoted. `voices`
+//! takes an inline `{Role: Voice}` map or an indented block of
+//! `Role: Voice` lines. Unknown keys and malformed lines are skipped with a
+//! warning. The block is never part of the spoken script.
+
+use crate::models::{PodcastProject, ScriptMetadata};
+
+const DELIMITER: &str = "---";
+
+/// Result of [`parse`]
+#[derive(Debug)]
+pub struct FrontMatter<'a> {
+    /// `None` when the script has no (closed) front matter
+    pub metadata: Option<ScriptMetadata>,
+    pub warnings: Vec<String>,
+    /// The script after the front matter
+    pub body: &'a str,
+}
+
+/// Split a script into its front matter and body
+pub fn parse(content: &str) -> FrontMatter<'_> {
+    let unchanged = |warnings| FrontMatter { metadata: None, warnings, body: content };
+    let text = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+    let mut lines = text.split_inclusive('\n');
+    if lines.next().map(str::trim_end) != Some(DELIMITER) {
+        return unchanged(Vec::new());
+    }
+
+    let mut block = Vec::new();
+    let mut offset = text.len() - lines.clone().map(str::len).sum::<usize>();
+    let mut body = None;
+    for line in lines {
+        offset += line.len();
+        let trimmed = line.trim_end();
+        if trimmed == DELIMITER || trimmed == "..." {
+            body = Some(&text[offset..]);
+            break;
+        }
+        block.push(trimmed);
+    }
+    let Some(body) = body else {
+        return unchanged(vec!["Front matter starting with `---` is never closed; read as script text".to_string()]);
+    };
+
+    let mut metadata = ScriptMetadata::default();
+    let mut warnings = Vec::new();
+    let mut in_voices = false;
+    for (index, line) in block.iter().enumerate() {
+        // The opening delimiter is line 1
+        let line_no = index + 2;
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with(char::is_whitespace) {
+            if !in_voices {
+                warnings.push(format!("Line {}: unexpected indented line ignored", line_no));
+            } else if let Some((role, voice)) = split_pair(line.trim()) {
+                insert_voice(&mut metadata, &role, &voice, line_no, &mut warnings);
+            } else {
+                warnings.push(format!("Line {}: expected `Role: Voice`", line_no));
+            }
+            continue;
+        }
+        in_voices = false;
+
+        let Some((key, value)) = split_pair(line) else {
+            warnings.push(format!("Line {}: expected `key: value`", line_no));
+            continue;
+        };
+        let text_value = || Some(value.clone()).filter(|v| !v.is_empty());
+        match key.to_ascii_lowercase().as_str() {
+            "title" => metadata.title = text_value(),
+            "author" => metadata.author = text_value(),
+            "description" => metadata.description = text_value(),
+            "language" | "lang" => metadata.language = text_value(),
+            "episode" | "episode_number" | "number" => match value.parse() {
+                Ok(n) => metadata.episode = Some(n),
+                Err(_) if value.is_empty() => {}
+                Err(_) => warnings.push(format!("Line {}: episode must be a whole number, not `{}`", line_no, value)),
+            },
+            "voices" => {
+                let raw = line[line.find([':', '=']).map_or(line.len(), |i| i + 1)..].trim();
+                if raw.is_empty() {
+                    in_voices = true;
+                } else if let Some(inner) = raw.strip_prefix('{').and_then(|r| r.strip_suffix('}')) {
+                    for entry in split_outside_quotes(inner, b',') {
+                        match split_pair(entry.trim()) {
+                            Some((role, voice)) => insert_voice(&mut metadata, &role, &voice, line_no, &mut warnings),
+                            None if entry.trim().is_empty() => {}
+                            None => warnings.push(format!("Line {}: expected `Role: Voice` in voices", line_no)),
+                        }
+                    }
+                } else {
+                    warnings.push(format!("Line {}: voices must be a `{{Role: Voice}}` map", line_no));
+                }
+            }
+            other => warnings.push(format!("Line {}: unknown front matter key `{}` ignored", line_no, other)),
+        }
+    }
+
+    FrontMatter { metadata: Some(metadata), warnings, body }
+}
+
+fn insert_voice(metadata: &mut ScriptMetadata, role: &str, voice: &str, line_no: usize, warnings: &mut Vec<String>) {
+    if role.is_empty() || voice.is_empty() {
+        warnings.push(format!("Line {}: voice hints need a role and a voice", line_no));
+        return;
+    }
+    metadata.voices.insert(role.to_string(), voice.to_string());
+}
+
+/// Split `key: value` (or `key = value`) at the first separator outside
+/// quotes, unquoting both sides
+fn split_pair(line: &str) -> Option<(String, String)> {
+    let i = unquoted_positions(line).find(|&i| matches!(line.as_bytes()[i], b':' | b'='))?;
+    let key = unquote(line[..i].trim());
+    (!key.is_empty()).then(|| (key, unquote(line[i + 1..].trim())))
+}
+
+fn split_outside_quotes(text: &str, separator: u8) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for i in unquoted_positions(text).filter(|&i| text.as_bytes()[i] == separator) {
+        parts.push(&text[start..i]);
+        start = i + 1;
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Byte offsets of the ASCII characters of `text` that are not inside a
+/// quoted string
+fn unquoted_positions(text: &str) -> impl Iterator<Item = usize> + '_ {
+    let mut quote = None;
+    let mut escaped = false;
+    text.bytes().enumerate().filter_map(move |(i, b)| {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(b'"') if b == b'\\' => escaped = true,
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'"' || b == b'\'' => quote = Some(b),
+            None => return Some(i),
+        }
+        None
+    })
+}
+
+/// Value of a possibly quoted scalar; unquoted values lose a trailing
+/// ` # comment`
+fn unquote(value: &str) -> String {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        let mut out = String::new();
+        let mut chars = value[1..value.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        }
+        return out;
+    }
+    if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        return value[1..value.len() - 1].replace("''", "'");
+    }
+    match value.find(" #") {
+        Some(i) => value[..i].trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+/// `value`, quoted if reading it back plain would change it
+fn quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value.trim() == value
+        && !value.starts_with(['-', '[', '{'])
+        && !value.contains([':', '=', '#', '"', '\'', ',', '{', '}', '\\', '\n', '\t']);
+    if plain {
+        return value.to_string();
+    }
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+/// Front matter block for `metadata`, ending in a newline; empty if there
+/// is nothing to write
+pub fn render(metadata: &ScriptMetadata) -> String {
+    if *metadata == ScriptMetadata::default() {
+        return String::new();
+    }
+    let mut out = format!("{}\n", DELIMITER);
+    let fields = [
+        ("title", metadata.title.clone()),
+        ("episode", metadata.episode.map(|n| n.to_string())),
+        ("author", metadata.author.clone()),
+        ("description", metadata.description.clone()),
+        ("language", metadata.language.clone()),
+    ];
+    for (key, value) in fields {
+        if let Some(value) = value {
+            out.push_str(&format!("{}: {}\n", key, quote(&value)));
+        }
+    }
+    if !metadata.voices.is_empty() {
+        out.push_str("voices:\n");
+        for (role, voice) in &metadata.voices {
+            out.push_str(&format!("  {}: {}\n", quote(role), quote(voice)));
+        }
+    }
+    out.push_str(DELIMITER);
+    out.push('\n');
+    out
+}
+
+/// The project's script with its episode settings and voices as front
+/// matter, replacing any front matter the script already had
+pub fn export_project(project: &PodcastProject) -> String {
+    let meta = &project.metadata;
+    let text = |s: &str| Some(s.to_string()).filter(|s| !s.trim().is_empty());
+    let metadata = ScriptMetadata {
+        title: text(&meta.title),
+        episode: meta.episode_number,
+        author: text(&meta.author),
+        description: text(&meta.description),
+        language: text(&meta.language),
+        voices: project.voice_assignments.iter().map(|(r, v)| (r.clone(), v.clone())).collect(),
+    };
+    format!("{}{}", render(&metadata), parse(&project.script_content).body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_front_matter() {
+        let content = "---\ntitle: \"Pilot: Part 1\"\nepisode = 3\nlang: zh # Mandarin\nmood: upbeat\nvoices: {Host: Samantha, \"Guest, Jr\": Daniel}\n---\nHost: Hi\n";
+        let parsed = parse(content);
+        let metadata = parsed.metadata.unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Pilot: Part 1"));
+        assert_eq!(metadata.episode, Some(3));
+        assert_eq!(metadata.language.as_deref(), Some("zh"));
+        assert_eq!(metadata.voices.get("Guest, Jr").map(String::as_str), Some("Daniel"));
+        assert_eq!(metadata.voices.len(), 2);
+        assert_eq!(parsed.body, "Host: Hi\n");
+        assert_eq!(parsed.warnings, vec!["Line 5: unknown front matter key `mood` ignored"]);
+    }
+
+    #[test]
+    fn test_voice_block_and_bad_values() {
+        let content = "---\nvoices:\n  Host: Samantha\n  nonsense\nepisode: twelve\n---\nHost: Hi";
+        let parsed = parse(content);
+        let metadata = parsed.metadata.unwrap();
+        assert_eq!(metadata.voices.get("Host").map(String::as_str), Some("Samantha"));
+        assert_eq!(metadata.episode, None);
+        assert_eq!(parsed.warnings.len(), 2);
+        assert!(parsed.warnings[0].starts_with("Line 4"));
+        assert!(parsed.warnings[1].contains("whole number"));
+    }
+
+    #[test]
+    fn test_without_front_matter() {
+        let plain = parse("Host: Hi\n---\nGuest: Bye");
+        assert!(plain.metadata.is_none() && plain.warnings.is_empty());
+        assert_eq!(plain.body, "Host: Hi\n---\nGuest: Bye");
+
+        let unclosed = parse("---\ntitle: Pilot\nHost: Hi");
+        assert!(unclosed.metadata.is_none());
+        assert_eq!(unclosed.warnings.len(), 1);
+        assert_eq!(unclosed.body, "---\ntitle: Pilot\nHost: Hi");
+    }
+
+    #[test]
+    fn test_render_quotes_what_needs_it() {
+        let metadata = ScriptMetadata {
+            title: Some("Pilot".to_string()),
+            description: Some("Line one\n\"two\": # three".to_string()),
+            voices: [("Dr. Who".to_string(), "Alex".to_string())].into_iter().collect(),
+            ..Default::default()
+        };
+        let rendered = render(&metadata);
+        assert_eq!(
+            rendered,
+            "---\ntitle: Pilot\ndescription: \"Line one\\n\\\"two\\\": # three\"\nvoices:\n  Dr. Who: Alex\n---\n"
+        );
+        assert_eq!(parse(&rendered).metadata, Some(metadata));
+        assert_eq!(render(&ScriptMetadata::default()), "");
+    }
+}
@@ -1,13 +1,197 @@
 //! Audio generation orchestrator
+//!
+//! [`GenerationRun`] renders on its own thread, registered as a job, and
+//! sends its progress back over a channel for the screen to poll.
 
-use crate::models::{PodcastScript, AudioSettings, PodcastError};
+use crate::models::{PodcastScript, AudioSettings, DialogueSegment, EpisodeMetadata, PodcastError, SegmentTiming, estimate_secs, render_filename};
+use crate::services::output_lock::OutputLock;
 use crate::services::parser;
+use crate::services::trim::{self, TrimResult};
 use crate::services::tts::TTSEngine;
-use std::path::PathBuf;
+use mofa_widgets::audio_export;
+use mofa_widgets::jobs::{format_remaining, JobRegistry};
+use mofa_widgets::tts::{TtsCostModel, TtsEstimate, TtsSpeedLog};
+use std::path::{Path, PathBuf};
 use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+/// Free space kept on top of the estimated audio size
+const SPACE_HEADROOM_BYTES: u64 = 50 * 1024 * 1024;
+
+const MB: u64 = 1024 * 1024;
+
+/// Seconds of audio a run renders before its own speed weighs as much as
+/// the measured one it started with
+const PRIOR_WEIGHT_SECS: f64 = 30.0;
+
+/// Where a generation is
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationProgress {
+    pub step: usize,
+    pub total: usize,
+    pub message: String,
+    /// Fraction done, weighted by how long each segment should take
+    pub fraction: f32,
+    /// Estimated seconds left
+    pub remaining_secs: f64,
+}
+
+impl GenerationProgress {
+    /// Status line, with the time left while segments are still rendering
+    pub fn status(&self) -> String {
+        if self.step < self.total && self.remaining_secs > 0.0 {
+            format!("{} ({} left)", self.message, format_remaining(self.remaining_secs))
+        } else {
+            self.message.clone()
+        }
+    }
+}
 
 /// Progress callback type
-pub type ProgressCallback = Box<dyn Fn(usize, usize, &str) + Send>;
+pub type ProgressCallback = Box<dyn Fn(&GenerationProgress) + Send>;
+
+/// Outcome of a successful generation
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationReport {
+    pub output: PathBuf,
+    /// Silence removed from segment boundaries, in seconds
+    pub trimmed_secs: f64,
+    /// Segments left out because they were entirely silent
+    pub silent_segments: usize,
+    /// Length of each segment in the output, in order
+    pub timings: Vec<SegmentTiming>,
+    /// Time spent in the TTS backend, in seconds
+    pub render_secs: f64,
+    /// Audio the backend produced, before trimming, in seconds
+    pub synthesized_secs: f64,
+}
+
+impl GenerationReport {
+    /// Short note for the status line, e.g. "trimmed 2.4 s of silence"
+    pub fn summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if self.trimmed_secs >= 0.05 {
+            parts.push(format!("trimmed {:.1} s of silence", self.trimmed_secs));
+        }
+        match self.silent_segments {
+            0 => {}
+            1 => parts.push("skipped 1 silent segment".to_string()),
+            n => parts.push(format!("skipped {} silent segments", n)),
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+/// Free space a generation needs: the segments in the temp folder and the
+/// joined output, each about as long as the script reads, plus headroom
+pub fn required_space(segments: &[DialogueSegment], settings: &AudioSettings) -> u64 {
+    let secs: f64 = segments.iter().map(|segment| estimate_secs(&segment.text)).sum();
+    // 16-bit mono
+    let audio_bytes = (secs * settings.sample_rate as f64 * 2.0) as u64;
+    audio_bytes * 2 + SPACE_HEADROOM_BYTES
+}
+
+/// Rough price and render time of `segments`, shown before generating
+pub fn estimate_render(segments: &[DialogueSegment], cost: &TtsCostModel) -> TtsEstimate {
+    let chars = segments.iter().map(|segment| segment.text.chars().count()).sum();
+    let secs = segments.iter().map(|segment| estimate_secs(&segment.text)).sum();
+    cost.estimate(chars, secs)
+}
+
+/// Render time left in a generation
+///
+/// Starts from the backend's measured realtime factor and moves towards
+/// the speed of the current run as its segments finish.
+struct RenderEta {
+    /// Estimated speaking time of each segment
+    segment_secs: Vec<f64>,
+    prior_factor: f64,
+    done: usize,
+    render_secs: f64,
+    audio_secs: f64,
+}
+
+impl RenderEta {
+    fn new(segments: &[DialogueSegment], prior_factor: f64) -> Self {
+        Self {
+            segment_secs: segments.iter().map(|segment| estimate_secs(&segment.text)).collect(),
+            prior_factor,
+            done: 0,
+            render_secs: 0.0,
+            audio_secs: 0.0,
+        }
+    }
+
+    /// The next segment took `render_secs` to make `audio_secs` of audio
+    fn segment_done(&mut self, render_secs: f64, audio_secs: f64) {
+        self.done = (self.done + 1).min(self.segment_secs.len());
+        self.render_secs += render_secs;
+        self.audio_secs += audio_secs;
+    }
+
+    fn factor(&self) -> f64 {
+        (self.prior_factor * PRIOR_WEIGHT_SECS + self.render_secs) / (PRIOR_WEIGHT_SECS + self.audio_secs)
+    }
+
+    fn remaining_secs(&self) -> f64 {
+        self.segment_secs[self.done..].iter().sum::<f64>() * self.factor()
+    }
+
+    fn fraction(&self) -> f32 {
+        let total = self.render_secs + self.remaining_secs();
+        if total > 0.0 {
+            (self.render_secs / total) as f32
+        } else {
+            self.done as f32 / self.segment_secs.len().max(1) as f32
+        }
+    }
+}
+
+/// Free space on the disk holding `dir`, `None` if it cannot be told
+fn available_space(dir: &Path) -> Option<u64> {
+    let dir = dir.canonicalize().ok()?;
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| dir.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+fn check_space(needed: u64, available: Option<u64>) -> Result<(), PodcastError> {
+    match available {
+        Some(available) if available < needed => Err(PodcastError::InsufficientSpace {
+            needed_mb: needed.div_ceil(MB),
+            available_mb: available / MB,
+        }),
+        // Unknown free space does not block a generation
+        _ => Ok(()),
+    }
+}
+
+/// `path`, or the first of "name-2.ext", "name-3.ext", ... that does not
+/// exist yet
+pub fn unique_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (2..)
+        .map(|n| path.with_file_name(format!("{}-{}{}", stem, n, extension)))
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded range")
+}
+
+fn wav_duration(path: &Path) -> Result<f64, PodcastError> {
+    let reader = hound::WavReader::open(path)
+        .map_err(|e| PodcastError::AudioError(format!("Failed to read WAV: {}", e)))?;
+    Ok(reader.duration() as f64 / reader.spec().sample_rate as f64)
+}
 
 /// Audio generator
 pub struct AudioGenerator {
@@ -26,14 +210,42 @@ impl AudioGenerator {
         })
     }
 
-    /// Generate podcast audio from script
+    /// Lock the output folder against other generations; see [`OutputLock`]
+    pub fn lock(&self) -> Result<OutputLock, PodcastError> {
+        OutputLock::acquire(&self.output_dir)
+    }
+
+    /// Remember how fast a run rendered, so later estimates use it
+    pub fn record_speed(&self, report: &GenerationReport) {
+        let mut speeds = TtsSpeedLog::load();
+        if speeds.record(self.tts_engine.backend_name(), report.render_secs, report.synthesized_secs) {
+            if let Err(e) = speeds.save() {
+                ::log::warn!("Failed to save TTS speeds: {}", e);
+            }
+        }
+    }
+
+    /// Output path for an episode, named by its filename template
+    pub fn output_path(&self, metadata: &EpisodeMetadata, settings: &AudioSettings) -> PathBuf {
+        let file_name = render_filename(&metadata.filename_template, metadata, settings.format.extension());
+        self.output_dir.join(file_name)
+    }
+
+    /// Generate podcast audio from script into `output_file`
+    ///
+    /// Each segment is trimmed of leading and trailing silence before the
+    /// segments are joined; segments with no audible speech are skipped.
+    /// `lock` must be this generator's output lock, held for the whole run.
+    /// Refuses to start when the disk is short of [`required_space`].
     pub fn generate(
         &self,
         script: &PodcastScript,
+        output_file: &Path,
         voice_assignments: &HashMap<String, String>,
         settings: &AudioSettings,
+        lock: &OutputLock,
         progress: Option<ProgressCallback>,
-    ) -> Result<PathBuf, PodcastError> {
+    ) -> Result<GenerationReport, PodcastError> {
         ::log::info!("Starting audio generation for: {}", script.title);
 
         // Parse segments
@@ -41,105 +253,258 @@ impl AudioGenerator {
         if segments.is_empty() {
             return Err(PodcastError::ParseError("No dialogue segments found".into()));
         }
+        check_space(required_space(&segments, settings), available_space(&self.output_dir))?;
 
         let total_steps = segments.len() + 2;
-        let report = |step: usize, msg: &str| {
+        let cost = self.tts_engine.cost_model(&TtsSpeedLog::load());
+        let mut eta = RenderEta::new(&segments, cost.realtime_factor);
+        let report = |step: usize, msg: &str, eta: &RenderEta| {
             if let Some(ref cb) = progress {
-                cb(step, total_steps, msg);
+                cb(&GenerationProgress {
+                    step,
+                    total: total_steps,
+                    message: msg.to_string(),
+                    fraction: eta.fraction(),
+                    remaining_secs: eta.remaining_secs(),
+                });
             }
         };
 
-        report(1, "Parsing script...");
+        report(1, "Parsing script...", &eta);
 
         // Generate audio for each segment
         let mut audio_files: Vec<PathBuf> = Vec::new();
-        let temp_dir = std::env::temp_dir().join("mofa_podcast");
+        let mut trimmed_secs = 0.0;
+        let mut silent_segments = 0;
+        let mut timings = Vec::new();
+        // Per run, so concurrent generations never share segment files
+        let temp_dir = std::env::temp_dir().join(format!("mofa_podcast_{}", uuid::Uuid::new_v4()));
         std::fs::create_dir_all(&temp_dir)
             .map_err(|e| PodcastError::FileError(e.to_string()))?;
 
         for (idx, segment) in segments.iter().enumerate() {
-            report(idx + 2, &format!("Generating segment {}/{}...", idx + 1, segments.len()));
+            report(idx + 2, &format!("Generating segment {}/{}...", idx + 1, segments.len()), &eta);
+            lock.touch();
 
             let voice_id = voice_assignments.get(&segment.role)
                 .ok_or_else(|| PodcastError::VoiceNotAssigned(segment.role.clone()))?;
 
             let output_file = temp_dir.join(format!("segment_{:04}.wav", idx));
-            self.tts_engine.synthesize(&segment.text, voice_id, &output_file)?;
+            // Too-long segments come back in parts, still one file and one timing
+            let started = Instant::now();
+            let parts = self.tts_engine.synthesize_segment(segment, voice_id, &output_file)?;
+            let render_secs = started.elapsed().as_secs_f64();
+            let synthesized_secs = wav_duration(&output_file)?;
+            eta.segment_done(render_secs, synthesized_secs);
+
+            if settings.trim.enabled {
+                match trim::trim_file(&output_file, &settings.trim)? {
+                    TrimResult::Trimmed { removed_secs } => trimmed_secs += removed_secs,
+                    TrimResult::Silent => {
+                        ::log::warn!("Skipping silent segment {} ({}): {:?}", idx + 1, segment.role, segment.text);
+                        silent_segments += 1;
+                        let _ = std::fs::remove_file(&output_file);
+                        continue;
+                    }
+                }
+            }
+            timings.push(SegmentTiming {
+                role: segment.role.clone(),
+                secs: wav_duration(&output_file)?,
+                parts,
+            });
             audio_files.push(output_file);
         }
 
-        report(total_steps - 1, "Concatenating audio...");
+        if audio_files.is_empty() {
+            let _ = std::fs::remove_dir_all(&temp_dir);
+            return Err(PodcastError::AudioError("All segments are silent".into()));
+        }
+
+        report(total_steps - 1, "Concatenating audio...", &eta);
 
         // Concatenate all segments
-        let output_file = self.output_dir.join(format!("{}.wav", script.title.replace(" ", "_")));
-        self.concatenate_wav_files(&audio_files, &output_file)?;
+        audio_export::concat_wav(&audio_files, output_file).map_err(PodcastError::AudioError)?;
 
         // Clean up temp files
-        for file in &audio_files {
-            let _ = std::fs::remove_file(file);
-        }
+        let _ = std::fs::remove_dir_all(&temp_dir);
 
-        report(total_steps, "Complete!");
-        ::log::info!("Audio generated: {:?}", output_file);
+        report(total_steps, "Complete!", &eta);
+        ::log::info!("Audio generated: {:?} (trimmed {:.2}s of silence)", output_file, trimmed_secs);
 
-        Ok(output_file)
+        Ok(GenerationReport {
+            output: output_file.to_path_buf(),
+            trimmed_secs,
+            silent_segments,
+            timings,
+            render_secs: eta.render_secs,
+            synthesized_secs: eta.audio_secs,
+        })
     }
+}
 
-    /// Concatenate WAV files using sox or manual method
-    fn concatenate_wav_files(&self, input_files: &[PathBuf], output: &PathBuf) -> Result<(), PodcastError> {
-        if input_files.is_empty() {
-            return Err(PodcastError::AudioError("No input files".into()));
-        }
+/// What a [`GenerationRun`] reports back
+#[derive(Debug)]
+pub enum GenerationUpdate {
+    Progress(GenerationProgress),
+    Finished(Result<GenerationReport, PodcastError>),
+}
 
-        if input_files.len() == 1 {
-            std::fs::copy(&input_files[0], output)
-                .map_err(|e| PodcastError::FileError(e.to_string()))?;
-            return Ok(());
-        }
+/// A generation running on a background thread
+pub struct GenerationRun {
+    updates: Receiver<GenerationUpdate>,
+}
 
-        // Try using sox for concatenation
-        let sox_result = std::process::Command::new("sox")
-            .args(input_files.iter().map(|p| p.as_os_str()))
-            .arg(output.as_os_str())
-            .output();
+impl GenerationRun {
+    /// Render `script` into `output_file`; the thread holds `lock` until
+    /// the run ends and records the backend's speed when it succeeds
+    pub fn start(
+        generator: AudioGenerator,
+        script: PodcastScript,
+        output_file: PathBuf,
+        voice_assignments: HashMap<String, String>,
+        settings: AudioSettings,
+        lock: OutputLock,
+    ) -> Self {
+        let (tx, updates) = mpsc::channel();
+        thread::spawn(move || {
+            let job = Arc::new(JobRegistry::global().start("mofa-podcast", "rendering"));
+            let reporter = Arc::clone(&job);
+            let progress_tx = tx.clone();
+            let progress: ProgressCallback = Box::new(move |progress: &GenerationProgress| {
+                reporter.set_progress(progress.fraction);
+                reporter.set_remaining(progress.remaining_secs);
+                let _ = progress_tx.send(GenerationUpdate::Progress(progress.clone()));
+            });
+            let result = generator.generate(&script, &output_file, &voice_assignments, &settings, &lock, Some(progress));
+            drop(lock);
+            if let Ok(report) = &result {
+                job.finish();
+                generator.record_speed(report);
+            }
+            let _ = tx.send(GenerationUpdate::Finished(result));
+        });
+        Self { updates }
+    }
 
-        if let Ok(output_result) = sox_result {
-            if output_result.status.success() {
-                return Ok(());
+    /// Updates received since the last call; the last one is `Finished`
+    /// once the run is over
+    pub fn poll(&self) -> Vec<GenerationUpdate> {
+        let mut updates = Vec::new();
+        loop {
+            match self.updates.try_recv() {
+                Ok(update) => {
+                    let finished = matches!(update, GenerationUpdate::Finished(_));
+                    updates.push(update);
+                    if finished {
+                        break;
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    updates.push(GenerationUpdate::Finished(Err(PodcastError::AudioError(
+                        "Generation thread stopped".into(),
+                    ))));
+                    break;
+                }
             }
         }
+        updates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mofa_widgets::tts::TtsSpan;
 
-        // Fallback: manual concatenation using hound
-        self.manual_concatenate(input_files, output)
+    fn segment(text: &str) -> DialogueSegment {
+        DialogueSegment::new(0, "Host".to_string(), vec![TtsSpan::plain(text)])
     }
 
-    fn manual_concatenate(&self, input_files: &[PathBuf], output: &PathBuf) -> Result<(), PodcastError> {
-        use hound::{WavReader, WavWriter, WavSpec};
+    #[test]
+    fn test_required_space() {
+        // A minute of speech at 22.05 kHz is about 2.6 MB, needed twice
+        let words = vec!["word"; 150].join(" ");
+        let needed = required_space(&[segment(&words)], &AudioSettings::default());
+        assert_eq!(needed, 2 * 60 * 22050 * 2 + SPACE_HEADROOM_BYTES);
 
-        // Read first file to get spec
-        let first_reader = WavReader::open(&input_files[0])
-            .map_err(|e| PodcastError::AudioError(format!("Failed to read WAV: {}", e)))?;
-        let spec = first_reader.spec();
+        assert!(check_space(needed, Some(needed)).is_ok());
+        assert!(check_space(needed, None).is_ok());
+        let err = check_space(needed, Some(10 * MB)).unwrap_err();
+        assert_eq!(err.to_string(), "Not enough disk space: about 56 MB needed, 10 MB free");
+    }
 
-        // Create output writer
-        let mut writer = WavWriter::create(output, spec)
-            .map_err(|e| PodcastError::AudioError(format!("Failed to create WAV: {}", e)))?;
+    #[test]
+    fn test_render_eta_follows_the_run() {
+        // Two segments of a minute each
+        let words = vec!["word"; 150].join(" ");
+        let segments = [segment(&words), segment(&words)];
+        assert_eq!(estimate_render(&segments, &TtsCostModel::default()).render_secs, 120.0);
 
-        // Write all samples
-        for file in input_files {
-            let mut reader = WavReader::open(file)
-                .map_err(|e| PodcastError::AudioError(format!("Failed to read WAV: {}", e)))?;
+        let mut eta = RenderEta::new(&segments, 0.1);
+        assert_eq!(eta.remaining_secs(), 12.0);
+        assert_eq!(eta.fraction(), 0.0);
 
-            for sample in reader.samples::<i16>() {
-                let sample = sample.map_err(|e| PodcastError::AudioError(e.to_string()))?;
-                writer.write_sample(sample)
-                    .map_err(|e| PodcastError::AudioError(e.to_string()))?;
-            }
-        }
+        // The first minute rendered in 30 s: the factor moves from 0.1 to
+        // (0.1 * 30 + 30) / 90
+        eta.segment_done(30.0, 60.0);
+        let factor = 33.0 / 90.0;
+        assert!((eta.remaining_secs() - 60.0 * factor).abs() < 1e-9);
+        assert!((eta.fraction() as f64 - 30.0 / (30.0 + 60.0 * factor)).abs() < 1e-6);
+
+        eta.segment_done(25.0, 60.0);
+        assert_eq!(eta.remaining_secs(), 0.0);
+        assert_eq!(eta.fraction(), 1.0);
+    }
+
+    #[test]
+    fn test_render_eta_without_timings() {
+        // Nothing rendered yet: the measured factor alone, and progress by
+        // segment count when there is no speech to time
+        let words = vec!["word"; 150].join(" ");
+        let eta = RenderEta::new(&[segment(&words)], 0.5);
+        assert_eq!(eta.remaining_secs(), 30.0);
+
+        let mut eta = RenderEta::new(&[segment(""), segment("")], 0.5);
+        assert_eq!(eta.remaining_secs(), 0.0);
+        eta.segment_done(0.0, 0.0);
+        assert_eq!(eta.fraction(), 0.5);
+        eta.segment_done(0.0, 0.0);
+        eta.segment_done(0.0, 0.0);
+        assert_eq!(eta.done, 2);
+    }
+
+    #[test]
+    fn test_progress_status() {
+        let mut progress = GenerationProgress {
+            step: 3,
+            total: 7,
+            message: "Generating segment 2/5...".to_string(),
+            fraction: 0.3,
+            remaining_secs: 370.0,
+        };
+        assert_eq!(progress.status(), "Generating segment 2/5... (~6 min left)");
+        progress.remaining_secs = 0.0;
+        assert_eq!(progress.status(), "Generating segment 2/5...");
+        progress.step = 7;
+        progress.remaining_secs = 5.0;
+        progress.message = "Complete!".to_string();
+        assert_eq!(progress.status(), "Complete!");
+    }
+
+    #[test]
+    fn test_unique_path() {
+        let dir = std::env::temp_dir().join(format!("mofa-podcast-unique-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let episode = dir.join("episode.wav");
 
-        writer.finalize()
-            .map_err(|e| PodcastError::AudioError(e.to_string()))?;
+        assert_eq!(unique_path(&episode), episode);
+        std::fs::write(&episode, b"").unwrap();
+        assert_eq!(unique_path(&episode), dir.join("episode-2.wav"));
+        std::fs::write(dir.join("episode-2.wav"), b"").unwrap();
+        assert_eq!(unique_path(&episode), dir.join("episode-3.wav"));
 
-        Ok(())
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
@@ -1,5 +1,10 @@
 //! Services for podcast generation
 
 pub mod parser;
+pub mod front_matter;
 pub mod tts;
 pub mod generator;
+pub mod tagging;
+pub mod voice_compare;
+pub mod trim;
+pub mod output_lock;
@@ -0,0 +1,121 @@
+//! Output folder lock
+//!
+//! Two generations writing into the same folder, from a double click or two
+//! studio instances, would pick the same file name and clobber each other.
+//! A generation holds a lock file in the output folder while it runs and
+//! touches it after every segment, so a lock left behind by a crash goes
+//! stale and is taken over.
+
+use crate::models::PodcastError;
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Lock file name inside the output folder
+pub const LOCK_FILE: &str = ".mofa-podcast.lock";
+
+/// Age after which an untouched lock is considered abandoned. A segment
+/// takes seconds to synthesize, so a live holder is never this far behind.
+pub const STALE_AFTER: Duration = Duration::from_secs(300);
+
+/// Held for the length of a generation; released on drop
+#[derive(Debug)]
+pub struct OutputLock {
+    path: PathBuf,
+}
+
+impl OutputLock {
+    /// Lock `dir`, taking over a stale lock
+    pub fn acquire(dir: &Path) -> Result<Self, PodcastError> {
+        Self::acquire_with(dir, STALE_AFTER)
+    }
+
+    fn acquire_with(dir: &Path, stale_after: Duration) -> Result<Self, PodcastError> {
+        let path = dir.join(LOCK_FILE);
+
+        // Second attempt only after removing a stale lock
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let _ = writeln!(file, "{}", std::process::id());
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if !is_stale(&path, stale_after) {
+                        break;
+                    }
+                    ::log::warn!("Taking over stale output lock: {:?}", path);
+                    let _ = std::fs::remove_file(&path);
+                }
+                Err(e) => {
+                    return Err(PodcastError::FileError(format!("Failed to lock output folder: {}", e)));
+                }
+            }
+        }
+        Err(PodcastError::OutputBusy(dir.display().to_string()))
+    }
+
+    /// Show the lock is still in use
+    pub fn touch(&self) {
+        if let Err(e) = std::fs::write(&self.path, format!("{}\n", std::process::id())) {
+            ::log::warn!("Failed to refresh output lock: {}", e);
+        }
+    }
+}
+
+impl Drop for OutputLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// A lock that vanished in the meantime counts as stale, one modified in
+/// the future (clock skew) does not
+fn is_stale(path: &Path, stale_after: Duration) -> bool {
+    match std::fs::metadata(path).and_then(|m| m.modified()) {
+        Ok(modified) => modified.elapsed().is_ok_and(|age| age > stale_after),
+        Err(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mofa-podcast-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_lock_is_exclusive() {
+        let dir = temp_dir("lock");
+
+        let lock = OutputLock::acquire(&dir).unwrap();
+        assert!(matches!(OutputLock::acquire(&dir), Err(PodcastError::OutputBusy(_))));
+        lock.touch();
+        assert!(OutputLock::acquire(&dir).is_err());
+
+        drop(lock);
+        assert!(!dir.join(LOCK_FILE).exists());
+        assert!(OutputLock::acquire(&dir).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_stale_lock_is_taken_over() {
+        let dir = temp_dir("stale-lock");
+        std::fs::write(dir.join(LOCK_FILE), "99999\n").unwrap();
+
+        assert!(OutputLock::acquire(&dir).is_err());
+        std::thread::sleep(Duration::from_millis(20));
+        let lock = OutputLock::acquire_with(&dir, Duration::from_millis(10)).unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join(LOCK_FILE)).unwrap().trim(), std::process::id().to_string());
+
+        drop(lock);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,13 +1,31 @@
 //! Script parser service
 //! Supports Markdown, JSON, and plain text formats
-
+//!
+//! Dialogue text may carry relative pacing markup: `{slow}...{/slow}` and
+//! `{fast}...{/fast}` change the speaking speed, `*...*` emphasizes. Tags
+//! nest, the innermost speed wins, and markup that cannot be applied (an
+//! unknown `{tag}`, a stray closing tag, an unpaired `*`) is dropped so it is
+//! never read aloud.
+//!
+//! A front matter block at the top (see [`front_matter`]) is split off
+//! first: it fills [`PodcastScript::metadata`] and is never read as
+//! dialogue.
+//!
+//! [`length_warnings`] flags segments too long for a TTS backend to read in
+//! one request; the generator splits those at sentence boundaries.
+
+use super::front_matter;
 use crate::models::{PodcastScript, ScriptFormat, CharacterRole, DialogueSegment};
 use anyhow::Result;
+use mofa_widgets::tts::{TtsSpan, FAST_RATE, SLOW_RATE};
 use regex::Regex;
 use std::collections::HashMap;
 
 /// Parse a script file and return PodcastScript model
 pub fn parse_script(file_path: &str, content: &str) -> Result<PodcastScript> {
+    let front = front_matter::parse(content);
+    let front_lines = lines_before(content, front.body);
+    let content = front.body;
     let format = if file_path.ends_with(".md") {
         ScriptFormat::Markdown
     } else if file_path.ends_with(".json") {
@@ -16,16 +34,25 @@ pub fn parse_script(file_path: &str, content: &str) -> Result<PodcastScript> {
         ScriptFormat::PlainText
     };
 
-    let title = extract_title(file_path, content);
+    let title = front.metadata.as_ref()
+        .and_then(|m| m.title.clone())
+        .unwrap_or_else(|| extract_title(file_path, content));
     let mut script = PodcastScript::new(title, content.to_string(), format.clone());
     script.roles = detect_roles(content, &format);
     script.file_path = Some(file_path.to_string());
+    script.front_matter_lines = front_lines;
+    script.metadata = front.metadata;
+    script.warnings = front.warnings;
 
     Ok(script)
 }
 
 /// Parse script content directly (without file path)
 pub fn parse_content(content: &str) -> Result<PodcastScript> {
+    let front = front_matter::parse(content);
+    let front_lines = lines_before(content, front.body);
+    let content = front.body;
+
     // Try to detect format from content
     let format = if content.trim().starts_with('{') {
         ScriptFormat::Json
@@ -35,13 +62,23 @@ pub fn parse_content(content: &str) -> Result<PodcastScript> {
         ScriptFormat::PlainText
     };
 
-    let title = "Untitled Script".to_string();
+    let title = front.metadata.as_ref()
+        .and_then(|m| m.title.clone())
+        .unwrap_or_else(|| "Untitled Script".to_string());
     let mut script = PodcastScript::new(title, content.to_string(), format.clone());
     script.roles = detect_roles(content, &format);
+    script.front_matter_lines = front_lines;
+    script.metadata = front.metadata;
+    script.warnings = front.warnings;
 
     Ok(script)
 }
 
+/// Lines of `source` before `body`, which must be a suffix of it
+fn lines_before(source: &str, body: &str) -> usize {
+    source[..source.len() - body.len()].matches('\n').count()
+}
+
 fn extract_title(file_path: &str, content: &str) -> String {
     if content.starts_with("# ") {
         if let Some(line) = content.lines().next() {
@@ -154,16 +191,34 @@ fn detect_text_roles(content: &str) -> Vec<CharacterRole> {
 /// Parse script into dialogue segments
 pub fn parse_segments(script: &PodcastScript) -> Vec<DialogueSegment> {
     match &script.format {
-        ScriptFormat::Markdown | ScriptFormat::PlainText => parse_markdown_segments(&script.content),
+        ScriptFormat::Markdown | ScriptFormat::PlainText => {
+            parse_markdown_segments(&script.content, script.front_matter_lines + 1)
+        }
         ScriptFormat::Json => parse_json_segments(&script.content),
     }
 }
 
-fn parse_markdown_segments(content: &str) -> Vec<DialogueSegment> {
+/// Text of the first segment spoken by `role`
+pub fn first_segment_for_role(script: &PodcastScript, role: &str) -> Option<String> {
+    parse_segments(script)
+        .into_iter()
+        .find(|segment| segment.role == role)
+        .map(|segment| segment.text)
+}
+
+/// `first_line` is the line of the script `content` starts on
+fn parse_markdown_segments(content: &str, first_line: usize) -> Vec<DialogueSegment> {
     let mut segments = Vec::new();
     let re = Regex::new(r"(?m)^(?:\*\*)?([^\*:\n]+?)(?:\*\*)?[:：]\s*([^\n]+)").unwrap();
+    // Line counting resumes where the previous match started
+    let mut line = first_line;
+    let mut counted = 0;
 
     for (index, capture) in re.captures_iter(content).enumerate() {
+        let start = capture.get(0).unwrap().start();
+        line += content[counted..start].matches('\n').count();
+        counted = start;
+
         if let (Some(role_match), Some(text_match)) = (capture.get(1), capture.get(2)) {
             let role = role_match.as_str().trim().replace("**", "");
             let text = text_match.as_str().trim().to_string();
@@ -173,11 +228,10 @@ fn parse_markdown_segments(content: &str) -> Vec<DialogueSegment> {
                 continue;
             }
 
-            segments.push(DialogueSegment {
-                index,
-                role,
-                text,
-            });
+            if let Some(mut segment) = paced_segment(index, role, &text) {
+                segment.line = Some(line);
+                segments.push(segment);
+            }
         }
     }
 
@@ -206,8 +260,8 @@ fn parse_json_segments(content: &str) -> Vec<DialogueSegment> {
                     .unwrap_or("")
                     .to_string();
 
-                if !text.is_empty() {
-                    segments.push(DialogueSegment { index, role, text });
+                if let Some(segment) = paced_segment(index, role, &text) {
+                    segments.push(segment);
                 }
             }
         }
@@ -216,6 +270,107 @@ fn parse_json_segments(content: &str) -> Vec<DialogueSegment> {
     segments
 }
 
+/// One warning per segment longer than `max_chars`, the most the TTS
+/// backend `backend` reads in one request
+pub fn length_warnings(segments: &[DialogueSegment], backend: &str, max_chars: usize) -> Vec<String> {
+    segments
+        .iter()
+        .filter_map(|segment| {
+            let chars = segment.text.chars().count();
+            if chars <= max_chars {
+                return None;
+            }
+            let place = match segment.line {
+                Some(line) => format!("Line {}", line),
+                None => format!("Segment {}", segment.index + 1),
+            };
+            Some(format!(
+                "{}: {}'s segment has {} characters, more than `{}` reads at once ({}); it will be split at sentence boundaries",
+                place, segment.role, chars, backend, max_chars
+            ))
+        })
+        .collect()
+}
+
+/// Segment for `text`, or `None` if nothing is left to speak once the
+/// markup is removed
+fn paced_segment(index: usize, role: String, text: &str) -> Option<DialogueSegment> {
+    let spans = parse_pacing(text);
+    if spans.is_empty() {
+        return None;
+    }
+    Some(DialogueSegment::new(index, role, spans))
+}
+
+/// Relative speed of a pacing tag
+fn tag_rate(name: &str) -> Option<f32> {
+    match name {
+        "slow" => Some(SLOW_RATE),
+        "fast" => Some(FAST_RATE),
+        _ => None,
+    }
+}
+
+/// Split `text` into runs of the same pacing, removing the markup
+pub fn parse_pacing(text: &str) -> Vec<TtsSpan> {
+    let re = Regex::new(r"\{(/?)([A-Za-z]+)\}|\*+").unwrap();
+    let star_runs = re.find_iter(text).filter(|m| m.as_str().starts_with('*')).count();
+
+    let mut spans: Vec<TtsSpan> = Vec::new();
+    let mut open_tags: Vec<String> = Vec::new();
+    let mut emphasis = false;
+    let mut stars_seen = 0;
+    let mut last = 0;
+
+    for capture in re.captures_iter(text) {
+        let whole = capture.get(0).unwrap();
+        let rate = open_tags.last().and_then(|tag| tag_rate(tag)).unwrap_or(1.0);
+        push_span(&mut spans, &text[last..whole.start()], rate, emphasis);
+        last = whole.end();
+
+        if whole.as_str().starts_with('*') {
+            stars_seen += 1;
+            // Pairs open and close; an odd run out has no partner
+            if emphasis || stars_seen < star_runs {
+                emphasis = !emphasis;
+            }
+            continue;
+        }
+
+        let name = capture[2].to_ascii_lowercase();
+        if tag_rate(&name).is_none() {
+            continue;
+        }
+        if capture[1].is_empty() {
+            open_tags.push(name);
+        } else if let Some(pos) = open_tags.iter().rposition(|tag| *tag == name) {
+            // Closing an outer tag also closes the ones opened inside it
+            open_tags.truncate(pos);
+        }
+    }
+    let rate = open_tags.last().and_then(|tag| tag_rate(tag)).unwrap_or(1.0);
+    push_span(&mut spans, &text[last..], rate, emphasis);
+
+    if let Some(first) = spans.first_mut() {
+        first.text = first.text.trim_start().to_string();
+    }
+    if let Some(last) = spans.last_mut() {
+        last.text = last.text.trim_end().to_string();
+    }
+    spans.retain(|span| !span.text.is_empty());
+    spans
+}
+
+fn push_span(spans: &mut Vec<TtsSpan>, text: &str, rate: f32, emphasis: bool) {
+    if text.is_empty() {
+        return;
+    }
+    match spans.last_mut() {
+        Some(last) if last.rate == rate && last.emphasis == emphasis => last.text.push_str(text),
+        _ => spans.push(TtsSpan { text: text.to_string(), rate, emphasis }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,6 +389,9 @@ Host: Let's talk about AI.
 
         let segments = parse_segments(&script);
         assert_eq!(segments.len(), 3);
+
+        assert_eq!(first_segment_for_role(&script, "Guest").as_deref(), Some("Thank you for having me."));
+        assert_eq!(first_segment_for_role(&script, "Narrator"), None);
     }
 
     #[test]
@@ -249,4 +407,133 @@ Host: Let's talk about AI.
         let segments = parse_segments(&script);
         assert_eq!(segments.len(), 3);
     }
+
+    #[test]
+    fn test_front_matter_is_not_dialogue() {
+        let content = "---\ntitle: Pilot\nauthor: MoFA\nvoices: {Host: Samantha}\nmood: calm\n---\nHost: Welcome!\nGuest: Thanks.\n";
+        let script = parse_content(content).unwrap();
+        assert_eq!(script.title, "Pilot");
+        assert_eq!(script.roles.len(), 2);
+        assert!(script.roles.iter().all(|r| r.name == "Host" || r.name == "Guest"));
+        assert_eq!(parse_segments(&script).len(), 2);
+        assert_eq!(script.metadata.unwrap().voices.get("Host").map(String::as_str), Some("Samantha"));
+        assert_eq!(script.warnings.len(), 1);
+
+        let script = parse_script("pilot.md", content).unwrap();
+        assert_eq!(script.content, "Host: Welcome!\nGuest: Thanks.\n");
+    }
+
+    #[test]
+    fn test_length_warnings_name_the_line() {
+        let long = "很长的一句话，".repeat(20);
+        let content = format!("---\ntitle: Pilot\n---\n# Intro\n\nHost: Hi.\nGuest: {}\n", long);
+        let script = parse_content(&content).unwrap();
+        let segments = parse_segments(&script);
+        assert_eq!(segments.iter().map(|s| s.line).collect::<Vec<_>>(), vec![Some(6), Some(7)]);
+
+        let warnings = length_warnings(&segments, "say", 100);
+        assert_eq!(
+            warnings,
+            vec!["Line 7: Guest's segment has 140 characters, more than `say` reads at once (100); it will be split at sentence boundaries"]
+        );
+        assert!(length_warnings(&segments, "say", 140).is_empty());
+
+        let json = parse_content(r#"{"segments": [{"speaker": "Host", "text": "Hello there"}]}"#).unwrap();
+        assert_eq!(length_warnings(&parse_segments(&json), "say", 5)[0].split(':').next(), Some("Segment 1"));
+    }
+
+    #[test]
+    fn test_project_export_round_trip() {
+        use crate::models::{EpisodeMetadata, PodcastProject};
+
+        let mut project = PodcastProject::default();
+        project.script_content = "---\ntitle: Old\n---\nHost: Hi there\nDr. Guest: Hello: again\n".to_string();
+        project.metadata = EpisodeMetadata {
+            title: "Pilot: \"Origins\"".to_string(),
+            author: "MoFA".to_string(),
+            episode_number: Some(7),
+            description: "Two lines,\n# not a comment".to_string(),
+            language: "en".to_string(),
+            ..Default::default()
+        };
+        project.voice_assignments.insert("Host".to_string(), "Samantha".to_string());
+        project.voice_assignments.insert("Dr. Guest".to_string(), "Daniel".to_string());
+
+        let exported = front_matter::export_project(&project);
+        let script = parse_script("pilot.md", &exported).unwrap();
+        assert!(script.warnings.is_empty());
+
+        let mut imported = PodcastProject::default();
+        let metadata = script.metadata.clone().unwrap();
+        metadata.apply_to(&mut imported.metadata);
+        imported.voice_assignments = metadata.voices.into_iter().collect();
+        assert_eq!(imported.metadata, project.metadata);
+        assert_eq!(imported.voice_assignments, project.voice_assignments);
+        assert_eq!(script.content, "Host: Hi there\nDr. Guest: Hello: again\n");
+        assert_eq!(parse_segments(&script).len(), 2);
+
+        // Exporting the import again gives the same file
+        imported.script_content = exported.clone();
+        assert_eq!(front_matter::export_project(&imported), exported);
+    }
+
+    fn spans(text: &str) -> Vec<(String, f32, bool)> {
+        parse_pacing(text).into_iter().map(|s| (s.text, s.rate, s.emphasis)).collect()
+    }
+
+    #[test]
+    fn test_pacing_markup() {
+        let span = |text: &str, rate, emphasis| (text.to_string(), rate, emphasis);
+        assert_eq!(spans("Plain text."), vec![span("Plain text.", 1.0, false)]);
+        assert_eq!(
+            spans("Now {slow}take a breath{/slow} and {fast}go go go{/fast}, *really*."),
+            vec![
+                span("Now ", 1.0, false),
+                span("take a breath", SLOW_RATE, false),
+                span(" and ", 1.0, false),
+                span("go go go", FAST_RATE, false),
+                span(", ", 1.0, false),
+                span("really", 1.0, true),
+                span(".", 1.0, false),
+            ]
+        );
+        // Markdown bold counts as emphasis
+        assert_eq!(spans("**Never** again"), vec![span("Never", 1.0, true), span(" again", 1.0, false)]);
+    }
+
+    #[test]
+    fn test_pacing_nesting() {
+        let span = |text: &str, rate, emphasis| (text.to_string(), rate, emphasis);
+        assert_eq!(
+            spans("{slow}one {fast}two *three*{/fast} four{/slow}"),
+            vec![
+                span("one ", SLOW_RATE, false),
+                span("two ", FAST_RATE, false),
+                span("three", FAST_RATE, true),
+                span(" four", SLOW_RATE, false),
+            ]
+        );
+        // Closing the outer tag closes the inner one too
+        assert_eq!(
+            spans("{slow}a {fast}b{/slow} c"),
+            vec![span("a ", SLOW_RATE, false), span("b", FAST_RATE, false), span(" c", 1.0, false)]
+        );
+    }
+
+    #[test]
+    fn test_pacing_unclosed_and_unknown() {
+        let span = |text: &str, rate, emphasis| (text.to_string(), rate, emphasis);
+        // An unclosed tag runs to the end of the segment
+        assert_eq!(spans("Well {slow}then"), vec![span("Well ", 1.0, false), span("then", SLOW_RATE, false)]);
+        // Stray closers, unknown tags and an unpaired asterisk are dropped
+        assert_eq!(spans("{/fast}Hi {whisper}there{/whisper}*"), vec![span("Hi there", 1.0, false)]);
+        assert_eq!(spans("*a* and *b"), vec![span("a", 1.0, true), span(" and b", 1.0, false)]);
+        assert!(parse_pacing("{slow}{/slow}").is_empty());
+
+        let script = parse_content("Host: {pause}\nGuest: {fast}Hello{/fast} *there*\n").unwrap();
+        let segments = parse_segments(&script);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, "Hello there");
+        assert!(segments[0].has_pacing());
+    }
 }
@@ -0,0 +1,84 @@
+//! Writes episode metadata into generated audio
+//!
+//! Uses the shared [`mofa_widgets::audio_tags`] writer. Regenerating an
+//! episode replaces the output file, so tags edited by hand are captured
+//! with [`capture_existing`] *before* generation and merged back afterwards
+//! unless the user chose to overwrite them.
+
+use crate::models::{EpisodeMetadata, PodcastError};
+use mofa_widgets::audio_tags::{self, Artwork, AudioTags, TagSnapshot};
+use std::path::Path;
+
+/// Tags found on an existing output, with what the app last wrote there
+pub struct ExistingTags {
+    pub on_disk: AudioTags,
+    pub last_written: TagSnapshot,
+}
+
+/// Build the tags for an episode, loading artwork from disk
+pub fn episode_tags(meta: &EpisodeMetadata) -> Result<AudioTags, PodcastError> {
+    let non_empty = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+
+    let artwork = match meta.artwork_path {
+        Some(ref path) => Some(Artwork::from_file(path).map_err(|e| {
+            PodcastError::FileError(format!("Failed to read artwork {}: {}", path.display(), e))
+        })?),
+        None => None,
+    };
+
+    Ok(AudioTags {
+        title: non_empty(&meta.title),
+        artist: non_empty(&meta.author),
+        album: None,
+        track: meta.episode_number,
+        genre: Some("Podcast".to_string()),
+        comment: non_empty(&meta.description),
+        artwork,
+    })
+}
+
+/// Read the tags of a previous output before it is regenerated.
+///
+/// Without a record of what was last written, every tag present on disk is
+/// treated as a manual edit.
+pub fn capture_existing(path: &Path, last_written: Option<&TagSnapshot>) -> Option<ExistingTags> {
+    if !path.exists() {
+        return None;
+    }
+    match audio_tags::read_tags(path) {
+        Ok(Some(on_disk)) => Some(ExistingTags {
+            on_disk,
+            last_written: last_written.cloned().unwrap_or_default(),
+        }),
+        Ok(None) => None,
+        Err(e) => {
+            ::log::warn!("Could not read existing tags from {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Tag a freshly generated episode.
+///
+/// Returns a snapshot of the generated tags to store in the project; manual
+/// edits kept from `existing` are deliberately left out of it so they keep
+/// being recognised as edits on the next regeneration.
+pub fn tag_episode(
+    path: &Path,
+    meta: &EpisodeMetadata,
+    existing: Option<&ExistingTags>,
+) -> Result<TagSnapshot, PodcastError> {
+    let generated = episode_tags(meta)?;
+
+    let tags = match existing {
+        Some(existing) if !meta.overwrite_tags => {
+            generated.merge_preserving_edits(&existing.on_disk, &existing.last_written)
+        }
+        _ => generated.clone(),
+    };
+
+    audio_tags::write_tags(path, &tags)
+        .map_err(|e| PodcastError::AudioError(format!("Failed to write tags: {}", e)))?;
+
+    Ok(generated.snapshot())
+}
@@ -0,0 +1,138 @@
+//! Silence trimming for TTS segments
+//!
+//! `say` pads every clip with leading and trailing silence, which adds up to
+//! seconds of dead air over a multi-segment episode. Each segment is cut down
+//! to its speech plus a short head and tail before the segments are joined,
+//! so any pause added on purpose afterwards stays as long as it was meant to.
+
+use crate::models::{PodcastError, SilenceTrim};
+use mofa_widgets::tts::{self, TtsAudio};
+use std::ops::Range;
+use std::path::Path;
+
+/// Length of the windows whose level is compared to the threshold
+const WINDOW_MS: u32 = 10;
+
+/// What trimming did to a segment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TrimResult {
+    /// Silence was removed (possibly none)
+    Trimmed { removed_secs: f64 },
+    /// Nothing rose above the threshold; the file was left as it was
+    Silent,
+}
+
+/// Range of `samples` to keep: the first to the last window above the
+/// threshold, widened by `keep_ms` on each side. `None` if all of it is
+/// silence.
+pub fn speech_bounds(samples: &[f32], sample_rate: u32, trim: &SilenceTrim) -> Option<Range<usize>> {
+    let window = ((sample_rate * WINDOW_MS / 1000) as usize).max(1);
+    let threshold = 10f32.powf(trim.threshold_dbfs / 20.0);
+    let loud = |chunk: &[f32]| {
+        let mean_square = chunk.iter().map(|s| s * s).sum::<f32>() / chunk.len() as f32;
+        mean_square.sqrt() >= threshold
+    };
+
+    let first = samples.chunks(window).position(loud)?;
+    let last = samples.chunks(window).rposition(loud)?;
+
+    let keep = (sample_rate as u64 * trim.keep_ms as u64 / 1000) as usize;
+    let start = (first * window).saturating_sub(keep);
+    let end = ((last + 1) * window + keep).min(samples.len());
+    Some(start..end)
+}
+
+/// Trim the WAV file at `path` in place
+pub fn trim_file(path: &Path, trim: &SilenceTrim) -> Result<TrimResult, PodcastError> {
+    let audio = tts::read_wav(path).map_err(PodcastError::AudioError)?;
+    let Some(bounds) = speech_bounds(&audio.samples, audio.sample_rate, trim) else {
+        return Ok(TrimResult::Silent);
+    };
+
+    let removed = audio.samples.len() - bounds.len();
+    if removed > 0 {
+        let trimmed = TtsAudio {
+            samples: audio.samples[bounds].to_vec(),
+            sample_rate: audio.sample_rate,
+        };
+        tts::write_wav(path, &trimmed).map_err(PodcastError::AudioError)?;
+    }
+
+    Ok(TrimResult::Trimmed {
+        removed_secs: removed as f64 / audio.sample_rate as f64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 22050;
+
+    /// A 440 Hz tone of `tone_ms` padded with silence on both sides
+    fn padded_tone(lead_ms: u32, tone_ms: u32, tail_ms: u32) -> Vec<f32> {
+        let samples = |ms: u32| (RATE * ms / 1000) as usize;
+        let mut audio = vec![0.0; samples(lead_ms)];
+        audio.extend((0..samples(tone_ms)).map(|i| {
+            0.5 * (i as f32 * 440.0 * std::f32::consts::TAU / RATE as f32).sin()
+        }));
+        audio.extend(vec![0.0; samples(tail_ms)]);
+        audio
+    }
+
+    #[test]
+    fn test_speech_bounds_keep_head_and_tail() {
+        let trim = SilenceTrim::default();
+        let samples = padded_tone(500, 1000, 700);
+        let bounds = speech_bounds(&samples, RATE, &trim).unwrap();
+
+        let ms = |n: usize| n as f64 * 1000.0 / RATE as f64;
+        // 80 ms kept before the tone, within one detection window
+        assert!((ms(bounds.start) - 420.0).abs() <= WINDOW_MS as f64);
+        assert!((ms(bounds.end) - 1580.0).abs() <= WINDOW_MS as f64);
+    }
+
+    #[test]
+    fn test_speech_bounds_clamp_to_clip() {
+        let trim = SilenceTrim { keep_ms: 200, ..Default::default() };
+        let samples = padded_tone(0, 300, 50);
+        assert_eq!(speech_bounds(&samples, RATE, &trim), Some(0..samples.len()));
+    }
+
+    #[test]
+    fn test_quiet_noise_counts_as_silence() {
+        // -60 dBFS hiss stays below the default -50 dBFS threshold
+        let noise: Vec<f32> = (0..RATE as usize).map(|i| if i % 2 == 0 { 0.001 } else { -0.001 }).collect();
+        assert_eq!(speech_bounds(&noise, RATE, &SilenceTrim::default()), None);
+        assert_eq!(speech_bounds(&[], RATE, &SilenceTrim::default()), None);
+
+        let sensitive = SilenceTrim { threshold_dbfs: -70.0, ..Default::default() };
+        assert!(speech_bounds(&noise, RATE, &sensitive).is_some());
+    }
+
+    #[test]
+    fn test_trim_file() {
+        let dir = std::env::temp_dir().join(format!("mofa-podcast-trim-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let trim = SilenceTrim::default();
+
+        let padded = dir.join("padded.wav");
+        let audio = TtsAudio { samples: padded_tone(400, 600, 400), sample_rate: RATE };
+        tts::write_wav(&padded, &audio).unwrap();
+        let TrimResult::Trimmed { removed_secs } = trim_file(&padded, &trim).unwrap() else {
+            panic!("tone was treated as silence");
+        };
+        assert!((removed_secs - 0.64).abs() < 0.03, "removed {}", removed_secs);
+        let trimmed = tts::read_wav(&padded).unwrap();
+        assert!((trimmed.duration_secs() - 0.76).abs() < 0.03);
+
+        // A silent segment is reported and left untouched, never emptied
+        let silent = dir.join("silent.wav");
+        let audio = TtsAudio { samples: vec![0.0; RATE as usize], sample_rate: RATE };
+        tts::write_wav(&silent, &audio).unwrap();
+        assert_eq!(trim_file(&silent, &trim).unwrap(), TrimResult::Silent);
+        assert_eq!(tts::read_wav(&silent).unwrap().samples.len(), RATE as usize);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
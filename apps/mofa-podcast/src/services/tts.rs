@@ -1,6 +1,11 @@
 //! TTS service using macOS say command
+//!
+//! Rendering goes through the shared [`mofa_widgets::tts`] backend.
+//! Segments longer than the backend reads in one request are split at
+//! sentence boundaries, rendered part by part and joined into one file.
 
-use crate::models::{PodcastError, MacOSVoice};
+use crate::models::{DialogueSegment, PodcastError, MacOSVoice};
+use mofa_widgets::tts::{self, SayBackend, TtsAudio, TtsBackend, TtsCostModel, TtsOptions, TtsSpan, TtsSpeedLog};
 use std::process::Command;
 use std::path::PathBuf;
 use std::io::Read;
@@ -8,12 +13,14 @@ use std::io::Read;
 /// TTS Engine using macOS say command
 pub struct TTSEngine {
     available_voices: Vec<MacOSVoice>,
+    backend: SayBackend,
 }
 
 impl TTSEngine {
     pub fn new() -> Self {
         Self {
             available_voices: MacOSVoice::all_voices(),
+            backend: SayBackend::new(22050),
         }
     }
 
@@ -22,49 +29,72 @@ impl TTSEngine {
         &self.available_voices
     }
 
-    /// Synthesize text to audio file using macOS say command
+    /// Name of the backend, for messages
+    pub fn backend_name(&self) -> &str {
+        self.backend.name()
+    }
+
+    /// Longest segment, in characters, rendered in one request
+    pub fn max_chars(&self) -> usize {
+        self.backend.max_chars()
+    }
+
+    /// Price and speed of the backend, with its speed measured in `speeds`
+    /// when there are recent runs
+    pub fn cost_model(&self, speeds: &TtsSpeedLog) -> TtsCostModel {
+        speeds.cost_model(&self.backend)
+    }
+
+    /// Synthesize text to a 22.05 kHz WAV file using macOS say command
     pub fn synthesize(&self, text: &str, voice_id: &str, output_path: &PathBuf) -> Result<(), PodcastError> {
         ::log::info!("Synthesizing with voice '{}': {} chars", voice_id, text.chars().count());
 
-        // Create temp AIFF file path
-        let temp_aiff = output_path.with_extension("aiff");
+        let options = TtsOptions {
+            voice: Some(voice_id.to_string()),
+            ..Default::default()
+        };
+        self.backend
+            .render_to_file(text, &options, output_path)
+            .map_err(PodcastError::TTSError)?;
 
-        // Use say command to generate AIFF
-        let output = Command::new("say")
-            .arg("-v")
-            .arg(voice_id)
-            .arg("-o")
-            .arg(&temp_aiff)
-            .arg(text)
-            .output()
-            .map_err(|e| PodcastError::TTSError(format!("Failed to run say command: {}", e)))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(PodcastError::TTSError(format!("say command failed: {}", stderr)));
+        ::log::info!("TTS synthesis complete: {:?}", output_path);
+        Ok(())
+    }
+
+    /// Synthesize a segment, honoring its pacing markup
+    ///
+    /// Returns the length of each part in seconds when the segment was too
+    /// long for one request and was rendered in parts, or an empty list.
+    pub fn synthesize_segment(&self, segment: &DialogueSegment, voice_id: &str, output_path: &PathBuf) -> Result<Vec<f64>, PodcastError> {
+        let too_long = segment.text.chars().count() > self.backend.max_chars();
+        if !too_long && !segment.has_pacing() {
+            self.synthesize(&segment.text, voice_id, output_path)?;
+            return Ok(Vec::new());
         }
 
-        // Convert AIFF to WAV using afconvert (macOS built-in)
-        let convert_output = Command::new("afconvert")
-            .arg("-f")
-            .arg("WAVE")
-            .arg("-d")
-            .arg("LEI16@22050")
-            .arg(&temp_aiff)
-            .arg(output_path)
-            .output()
-            .map_err(|e| PodcastError::TTSError(format!("Failed to run afconvert: {}", e)))?;
-
-        // Clean up temp file
-        let _ = std::fs::remove_file(&temp_aiff);
-
-        if !convert_output.status.success() {
-            let stderr = String::from_utf8_lossy(&convert_output.stderr);
-            return Err(PodcastError::TTSError(format!("afconvert failed: {}", stderr)));
+        let options = TtsOptions {
+            voice: Some(voice_id.to_string()),
+            ..Default::default()
+        };
+        if !too_long {
+            ::log::info!("Synthesizing {} paced runs with voice '{}'", segment.spans.len(), voice_id);
+            let audio = self.backend
+                .synthesize_spans(&segment.spans, &options)
+                .map_err(PodcastError::TTSError)?;
+            tts::write_wav(output_path, &audio).map_err(PodcastError::FileError)?;
+            return Ok(Vec::new());
         }
 
-        ::log::info!("TTS synthesis complete: {:?}", output_path);
-        Ok(())
+        let (audio, parts) = render_in_parts(&self.backend, &segment.spans, &options)?;
+        ::log::info!(
+            "Synthesized {} chars for {} in {} parts with voice '{}'",
+            segment.text.chars().count(),
+            segment.role,
+            parts.len(),
+            voice_id
+        );
+        tts::write_wav(output_path, &audio).map_err(PodcastError::FileError)?;
+        Ok(parts)
     }
 
     /// Synthesize text and return raw audio bytes
@@ -112,3 +142,89 @@ impl Default for TTSEngine {
         Self::new()
     }
 }
+
+/// Render `spans` in parts the backend can read in one request each and
+/// join them; also returns each part's length in seconds
+fn render_in_parts(backend: &dyn TtsBackend, spans: &[TtsSpan], options: &TtsOptions) -> Result<(TtsAudio, Vec<f64>), PodcastError> {
+    let mut audio = TtsAudio { samples: Vec::new(), sample_rate: backend.sample_rate() };
+    let mut parts = Vec::new();
+    for part in tts::chunk_spans(spans, backend.max_chars()) {
+        let rendered = backend
+            .synthesize_spans(&part, options)
+            .map_err(PodcastError::TTSError)?;
+        parts.push(rendered.duration_secs());
+        audio.append(&rendered);
+    }
+    Ok((audio, parts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mofa_widgets::tts::TtsVoice;
+    use std::sync::Mutex;
+
+    /// Ten samples per character; refuses text over its limit
+    struct FakeBackend {
+        longest: Mutex<usize>,
+    }
+
+    impl TtsBackend for FakeBackend {
+        fn name(&self) -> &str {
+            "fake"
+        }
+
+        fn sample_rate(&self) -> u32 {
+            1000
+        }
+
+        fn max_chars(&self) -> usize {
+            400
+        }
+
+        fn voices(&self) -> Result<Vec<TtsVoice>, String> {
+            Ok(Vec::new())
+        }
+
+        fn synthesize(&self, text: &str, _options: &TtsOptions) -> Result<TtsAudio, String> {
+            let chars = text.chars().count();
+            if chars > self.max_chars() {
+                return Err(format!("{} characters is too long", chars));
+            }
+            let mut longest = self.longest.lock().unwrap();
+            *longest = (*longest).max(chars);
+            Ok(TtsAudio { samples: vec![0.5; chars * 10], sample_rate: 1000 })
+        }
+    }
+
+    #[test]
+    fn test_long_segment_renders_in_parts() {
+        // 10,000 characters of CJK text without a single space
+        let text = "这是一个很长的句子，用来测试分段合成。".repeat(500) + &"长".repeat(500);
+        let segment = DialogueSegment::new(0, "主持人".to_string(), vec![TtsSpan::plain(text.clone())]);
+        assert_eq!(segment.text.chars().count(), 10_000);
+
+        let backend = FakeBackend { longest: Mutex::new(0) };
+        let (audio, parts) = render_in_parts(&backend, &segment.spans, &TtsOptions::default()).unwrap();
+        assert!(parts.len() >= 25, "{} parts", parts.len());
+        assert!(*backend.longest.lock().unwrap() <= 400);
+
+        // Every character is spoken once; joining sentences only adds spaces
+        let spoken: String = tts::chunk_spans(&segment.spans, 400)
+            .into_iter()
+            .flatten()
+            .map(|span| span.text.replace(' ', ""))
+            .collect();
+        assert_eq!(spoken, text);
+        let total: f64 = parts.iter().sum();
+        assert!((audio.duration_secs() - total).abs() < 1e-9);
+        assert!(total >= 100.0);
+
+        // Written out, the parts make up a single segment file
+        let path = std::env::temp_dir().join(format!("mofa-podcast-parts-{}.wav", std::process::id()));
+        tts::write_wav(&path, &audio).unwrap();
+        let read = tts::read_wav(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!((read.duration_secs() - total).abs() < 1e-9);
+    }
+}
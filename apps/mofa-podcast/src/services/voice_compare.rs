@@ -0,0 +1,246 @@
+//! A/B voice comparison
+//!
+//! Renders one paragraph with two voices at once, each on its own thread
+//! through the active [`mofa_widgets::tts`] backend, into WAV files in a
+//! temp folder. [`AbPlayer`] plays them back with an A/B switch that keeps
+//! the playback position, so the voices can be compared line by line. The
+//! temp folder is removed when the [`VoiceComparison`] is dropped.
+
+use mofa_widgets::tts::{self, TtsAudio, TtsOptions};
+use rodio::buffer::SamplesBuffer;
+use rodio::{OutputStream, OutputStreamHandle, Sink};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// One of the two compared voices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    A,
+    B,
+}
+
+impl Side {
+    pub fn index(self) -> usize {
+        match self {
+            Side::A => 0,
+            Side::B => 1,
+        }
+    }
+
+    pub fn other(self) -> Side {
+        match self {
+            Side::A => Side::B,
+            Side::B => Side::A,
+        }
+    }
+}
+
+/// Progress of one sample
+#[derive(Debug, Clone, PartialEq)]
+pub enum SampleState {
+    Pending,
+    Ready(PathBuf),
+    Failed(String),
+}
+
+/// Two samples of the same text, synthesized in the background
+pub struct VoiceComparison {
+    voices: [String; 2],
+    samples: Arc<Mutex<[SampleState; 2]>>,
+    dir: PathBuf,
+}
+
+impl VoiceComparison {
+    /// Start synthesizing `text` with both voices concurrently
+    pub fn start(text: &str, voice_a: &str, voice_b: &str) -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "mofa-podcast-compare-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let samples = Arc::new(Mutex::new([SampleState::Pending, SampleState::Pending]));
+        let voices = [voice_a.to_string(), voice_b.to_string()];
+
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            let error = format!("Failed to create temp folder: {}", e);
+            *samples.lock().unwrap() = [SampleState::Failed(error.clone()), SampleState::Failed(error)];
+            return Self { voices, samples, dir };
+        }
+
+        for side in [Side::A, Side::B] {
+            let text = text.to_string();
+            let voice = voices[side.index()].clone();
+            let path = dir.join(format!("{:?}.wav", side));
+            let samples = samples.clone();
+            std::thread::spawn(move || {
+                let options = TtsOptions { voice: Some(voice), ..Default::default() };
+                let state = match tts::default_backend()
+                    .synthesize(&text, &options)
+                    .and_then(|audio| tts::write_wav(&path, &audio))
+                {
+                    Ok(()) => SampleState::Ready(path),
+                    Err(e) => SampleState::Failed(e),
+                };
+                samples.lock().unwrap()[side.index()] = state;
+            });
+        }
+
+        Self { voices, samples, dir }
+    }
+
+    pub fn voice(&self, side: Side) -> &str {
+        &self.voices[side.index()]
+    }
+
+    pub fn state(&self, side: Side) -> SampleState {
+        self.samples.lock().unwrap()[side.index()].clone()
+    }
+
+    /// Paths of both samples once both are synthesized
+    pub fn ready_paths(&self) -> Option<[PathBuf; 2]> {
+        match &*self.samples.lock().unwrap() {
+            [SampleState::Ready(a), SampleState::Ready(b)] => Some([a.clone(), b.clone()]),
+            _ => None,
+        }
+    }
+
+    /// First synthesis error, if any
+    pub fn error(&self) -> Option<String> {
+        self.samples.lock().unwrap().iter().find_map(|state| match state {
+            SampleState::Failed(e) => Some(e.clone()),
+            _ => None,
+        })
+    }
+}
+
+impl Drop for VoiceComparison {
+    fn drop(&mut self) {
+        // A sample still being synthesized fails to write and is discarded
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Plays sample A or B, switching sides at the same point in the text
+pub struct AbPlayer {
+    _stream: OutputStream,
+    handle: OutputStreamHandle,
+    sink: Option<Sink>,
+    audio: [TtsAudio; 2],
+    side: Side,
+    /// Position in seconds where the current playback started
+    offset: f64,
+    started: Instant,
+}
+
+impl AbPlayer {
+    pub fn new(paths: &[PathBuf; 2]) -> Result<Self, String> {
+        let audio = [tts::read_wav(&paths[0])?, tts::read_wav(&paths[1])?];
+        let (stream, handle) = OutputStream::try_default()
+            .map_err(|e| format!("No audio output: {}", e))?;
+        Ok(Self {
+            _stream: stream,
+            handle,
+            sink: None,
+            audio,
+            side: Side::A,
+            offset: 0.0,
+            started: Instant::now(),
+        })
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.sink.as_ref().is_some_and(|sink| !sink.empty())
+    }
+
+    /// Current position in the active sample, in seconds
+    pub fn position(&self) -> f64 {
+        let duration = self.audio[self.side.index()].duration_secs();
+        if self.sink.is_some() {
+            (self.offset + self.started.elapsed().as_secs_f64()).min(duration)
+        } else {
+            self.offset
+        }
+    }
+
+    /// Play from the current position, or from the start after finishing
+    pub fn play(&mut self) -> Result<(), String> {
+        let mut position = self.position();
+        if !self.is_playing() && position >= self.audio[self.side.index()].duration_secs() {
+            position = 0.0;
+        }
+        self.play_from(position)
+    }
+
+    /// Stop and rewind
+    pub fn stop(&mut self) {
+        self.sink = None;
+        self.offset = 0.0;
+    }
+
+    /// Switch to `side`, continuing from the same point in the text
+    pub fn switch(&mut self, side: Side) -> Result<(), String> {
+        if side == self.side {
+            return Ok(());
+        }
+        let position = mapped_position(
+            self.position(),
+            self.audio[self.side.index()].duration_secs(),
+            self.audio[side.index()].duration_secs(),
+        );
+        let was_playing = self.is_playing();
+        self.side = side;
+        if was_playing {
+            self.play_from(position)
+        } else {
+            self.sink = None;
+            self.offset = position;
+            Ok(())
+        }
+    }
+
+    fn play_from(&mut self, position: f64) -> Result<(), String> {
+        let audio = &self.audio[self.side.index()];
+        let start = ((position * audio.sample_rate as f64) as usize).min(audio.samples.len());
+        let sink = Sink::try_new(&self.handle).map_err(|e| format!("Playback failed: {}", e))?;
+        sink.append(SamplesBuffer::new(1, audio.sample_rate, audio.samples[start..].to_vec()));
+        // Dropping the previous sink stops it
+        self.sink = Some(sink);
+        self.offset = position;
+        self.started = Instant::now();
+        Ok(())
+    }
+}
+
+/// Map a position between samples of different length. The voices speak at
+/// different rates, so the same fraction of the text is the closest match.
+pub fn mapped_position(position: f64, from_duration: f64, to_duration: f64) -> f64 {
+    if from_duration <= 0.0 {
+        return 0.0;
+    }
+    (position / from_duration).clamp(0.0, 1.0) * to_duration
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapped_position() {
+        assert_eq!(mapped_position(5.0, 10.0, 20.0), 10.0);
+        assert_eq!(mapped_position(12.0, 10.0, 8.0), 8.0);
+        assert_eq!(mapped_position(3.0, 0.0, 8.0), 0.0);
+    }
+
+    #[test]
+    fn test_side() {
+        assert_eq!(Side::A.other(), Side::B);
+        assert_eq!(Side::B.index(), 1);
+    }
+}
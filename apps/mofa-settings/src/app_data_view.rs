@@ -0,0 +1,677 @@
+//! App Data View - Per-app data reset panel
+//!
+//! Lists every app and plugin with the size of the data it keeps on disk.
+//! Resetting first shows a dry-run preview where each category can be
+//! deselected; the confirmed reset is emitted as an action so the shell can
+//! stop the app's server before anything is deleted.
+//!
+//! The view also requests diagnostic bundles for bug reports; the shell
+//! writes them and reports progress back through
+//! [`AppDataViewRef::show_bundle_status`].
+
+use makepad_widgets::*;
+use mofa_widgets::app_storage::{format_size, DataCategory, DataLocation, ResetPlan, ResetReport};
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use mofa_widgets::theme::*;
+
+    // App row with total size and reset button
+    AppDataItem = <View> {
+        width: Fill, height: Fit
+        padding: {top: 12, bottom: 12, left: 16, right: 16}
+        margin: {bottom: 4}
+        flow: Right
+        align: {x: 0.0, y: 0.5}
+        show_bg: true
+        draw_bg: {
+            instance dark_mode: 0.0
+            fn pixel(self) -> vec4 {
+                let light = #F8FAFC;  // slate-50
+                let dark = #1E293B;   // slate-800
+                return mix(light, dark, self.dark_mode);
+            }
+        }
+
+        info = <View> {
+            width: Fill, height: Fit
+            flow: Down
+            spacing: 4
+
+            name_label = <Label> {
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: <FONT_REGULAR>{ font_size: 13.0 }
+                    fn get_color(self) -> vec4 {
+                        return mix(#334155, #E2E8F0, self.dark_mode);
+                    }
+                }
+            }
+
+            desc_label = <Label> {
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                    fn get_color(self) -> vec4 {
+                        return mix(#64748B, #94A3B8, self.dark_mode);
+                    }
+                }
+            }
+        }
+
+        size_label = <Label> {
+            width: 80, height: Fit
+            align: {x: 1.0, y: 0.5}
+            margin: {right: 12}
+            draw_text: {
+                instance dark_mode: 0.0
+                text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                fn get_color(self) -> vec4 {
+                    return mix(#64748B, #94A3B8, self.dark_mode);
+                }
+            }
+        }
+
+        reset_btn = <Button> {
+            width: 80, height: 32
+            text: "Reset data"
+            draw_text: {
+                text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                fn get_color(self) -> vec4 {
+                    return #FFFFFF;
+                }
+            }
+            draw_bg: {
+                instance empty: 0.0
+
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    // Red when there is data to remove, gray when empty
+                    let reset_color = #EF4444;
+                    let empty_color = #9CA3AF;
+                    sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                    sdf.fill(mix(reset_color, empty_color, self.empty));
+                    return sdf.result;
+                }
+            }
+        }
+    }
+
+    // Category checkbox row in the reset dialog
+    CategoryToggle = <Button> {
+        width: Fill, height: 32
+        padding: {left: 32, right: 8}
+        align: {x: 0.0, y: 0.5}
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_REGULAR>{ font_size: 12.0 }
+            fn get_color(self) -> vec4 {
+                return mix(#334155, #E2E8F0, self.dark_mode);
+            }
+        }
+        draw_bg: {
+            instance selected: 1.0
+            instance dark_mode: 0.0
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let y = (self.rect_size.y - 14.0) * 0.5;
+                sdf.box(8.0, y, 14.0, 14.0, 3.0);
+                let border = mix(#94A3B8, #64748B, self.dark_mode);
+                let fill = mix(mix(#FFFFFF, #0F172A, self.dark_mode), #3B82F6, self.selected);
+                sdf.fill_keep(fill);
+                sdf.stroke(mix(border, #3B82F6, self.selected), 1.0);
+                return sdf.result;
+            }
+        }
+    }
+
+    DialogButton = <Button> {
+        width: Fit, height: 32
+        padding: {left: 16, right: 16}
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_SEMIBOLD>{ font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix(#334155, #E2E8F0, self.dark_mode);
+            }
+        }
+        draw_bg: {
+            instance dark_mode: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                sdf.fill(mix(#E2E8F0, #334155, self.dark_mode));
+                return sdf.result;
+            }
+        }
+    }
+
+    HintLabel = <Label> {
+        width: Fill
+        draw_text: {
+            instance dark_mode: 0.0
+            wrap: Word
+            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix(#64748B, #94A3B8, self.dark_mode);
+            }
+        }
+    }
+
+    pub AppDataView = {{AppDataView}} {
+        width: Fill, height: Fill
+        flow: Overlay
+        show_bg: true
+        draw_bg: {
+            instance dark_mode: 0.0
+            fn pixel(self) -> vec4 {
+                return mix((WHITE), (SLATE_900), self.dark_mode);
+            }
+        }
+
+        content = <View> {
+            width: Fill, height: Fill
+            flow: Down
+            padding: {top: 24, bottom: 24, left: 24, right: 24}
+
+            title = <Label> {
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: <FONT_BOLD>{ font_size: 18.0 }
+                    fn get_color(self) -> vec4 {
+                        return mix(#1E293B, #F1F5F9, self.dark_mode);
+                    }
+                }
+                text: "App Data"
+            }
+
+            subtitle = <HintLabel> {
+                margin: {top: 4, bottom: 16}
+                text: "Reset the preferences, cached data and WebView profile each app keeps on disk"
+            }
+
+            app_list = <PortalList> {
+                width: Fill, height: Fill
+                flow: Down
+
+                app_item = <AppDataItem> {}
+            }
+
+            report_label = <HintLabel> {
+                margin: {top: 12}
+                text: ""
+            }
+
+            diagnostics = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 8
+                margin: {top: 16}
+
+                diagnostics_title = <Label> {
+                    draw_text: {
+                        instance dark_mode: 0.0
+                        text_style: <FONT_SEMIBOLD>{ font_size: 13.0 }
+                        fn get_color(self) -> vec4 {
+                            return mix(#1E293B, #F1F5F9, self.dark_mode);
+                        }
+                    }
+                    text: "Diagnostics"
+                }
+
+                diagnostics_hint = <HintLabel> {
+                    text: "Save logs, preferences, plugin output and a system summary to one zip for a bug report. API keys and tokens are removed."
+                }
+
+                diagnostics_row = <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    spacing: 8
+                    align: {x: 0.0, y: 0.5}
+
+                    anonymize_toggle = <CategoryToggle> {
+                        width: Fit
+                        text: "Hide home folder in paths"
+                    }
+
+                    <View> { width: Fill, height: 1 }
+
+                    bundle_btn = <DialogButton> { text: "Create diagnostic bundle" }
+                }
+
+                bundle_status = <HintLabel> { text: "" }
+            }
+        }
+
+        // Reset confirmation with per-category selection and dry-run preview
+        reset_dialog = <View> {
+            visible: false
+            width: Fill, height: Fill
+            align: {x: 0.5, y: 0.5}
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.4);
+                }
+            }
+
+            card = <RoundedView> {
+                width: 440, height: Fit
+                flow: Down
+                spacing: 8
+                padding: 20
+                show_bg: true
+                draw_bg: {
+                    instance dark_mode: 0.0
+                    border_radius: 8.0
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, self.border_radius);
+                        sdf.fill(mix((WHITE), (SLATE_800), self.dark_mode));
+                        return sdf.result;
+                    }
+                }
+
+                dialog_title = <Label> {
+                    width: Fill
+                    draw_text: {
+                        instance dark_mode: 0.0
+                        text_style: <FONT_BOLD>{ font_size: 14.0 }
+                        fn get_color(self) -> vec4 {
+                            return mix(#1E293B, #F1F5F9, self.dark_mode);
+                        }
+                    }
+                    text: ""
+                }
+
+                dialog_hint = <HintLabel> {
+                    text: "The app's server is stopped first. Deselect anything you want to keep."
+                }
+
+                prefs_toggle = <CategoryToggle> {}
+                cache_toggle = <CategoryToggle> {}
+                webview_toggle = <CategoryToggle> {}
+
+                preview_label = <HintLabel> {
+                    margin: {top: 4}
+                    text: ""
+                }
+
+                buttons = <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    spacing: 8
+                    margin: {top: 8}
+
+                    <View> { width: Fill, height: 1 }
+
+                    cancel_btn = <DialogButton> { text: "Cancel" }
+                    confirm_btn = <DialogButton> {
+                        text: "Reset"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #FFFFFF;
+                            }
+                        }
+                        draw_bg: {
+                            fn pixel(self) -> vec4 {
+                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                                sdf.fill(#EF4444);
+                                return sdf.result;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// An app or plugin whose data can be reset
+#[derive(Clone, Debug)]
+pub struct AppDataTarget {
+    pub id: String,
+    pub name: String,
+    pub locations: Vec<DataLocation>,
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum AppDataViewAction {
+    None,
+    /// User confirmed a reset; the receiver stops the app and runs the plan
+    ResetConfirmed {
+        plan: ResetPlan,
+        categories: Vec<DataCategory>,
+    },
+    /// User asked for a diagnostic bundle; the receiver asks where to save it
+    CreateDiagnosticBundle {
+        anonymize_home: bool,
+    },
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct AppDataView {
+    #[deref]
+    view: View,
+
+    #[rust]
+    targets: Vec<AppDataTarget>,
+
+    /// Total bytes on disk per target, measured on refresh
+    #[rust]
+    sizes: Vec<u64>,
+
+    /// Dry-run plan shown in the reset dialog
+    #[rust]
+    pending: Option<ResetPlan>,
+
+    /// Categories still selected in the reset dialog
+    #[rust]
+    selected: Vec<DataCategory>,
+
+    /// Keep home folder paths in diagnostic bundles (off by default)
+    #[rust]
+    show_home_paths: bool,
+
+    /// A diagnostic bundle is being written
+    #[rust]
+    bundle_running: bool,
+
+    #[rust]
+    dark_mode: f64,
+}
+
+impl Widget for AppDataView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        let actions = match event {
+            Event::Actions(actions) => actions.as_slice(),
+            _ => return,
+        };
+
+        let list = self.view.portal_list(ids!(content.app_list));
+        let clicked = list.items_with_actions(actions)
+            .into_iter()
+            .find(|(_, item)| item.button(ids!(reset_btn)).clicked(actions))
+            .map(|(index, _)| index);
+        if let Some(index) = clicked {
+            self.show_reset_dialog(cx, index);
+        }
+
+        let anonymize_toggle = self.view.button(ids!(content.diagnostics.diagnostics_row.anonymize_toggle));
+        if anonymize_toggle.clicked(actions) {
+            self.show_home_paths = !self.show_home_paths;
+            anonymize_toggle.apply_over(cx, live!{
+                draw_bg: { selected: (if self.show_home_paths { 0.0 } else { 1.0 }) }
+            });
+            self.view.redraw(cx);
+        }
+
+        if self.view.button(ids!(content.diagnostics.diagnostics_row.bundle_btn)).clicked(actions)
+            && !self.bundle_running
+        {
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                AppDataViewAction::CreateDiagnosticBundle { anonymize_home: !self.show_home_paths },
+            );
+        }
+
+        if self.pending.is_none() {
+            return;
+        }
+
+        let toggles = [
+            (ids!(reset_dialog.card.prefs_toggle), DataCategory::Preferences),
+            (ids!(reset_dialog.card.cache_toggle), DataCategory::Cache),
+            (ids!(reset_dialog.card.webview_toggle), DataCategory::WebViewProfile),
+        ];
+        for (path, category) in toggles {
+            if self.view.button(path).clicked(actions) {
+                if self.selected.contains(&category) {
+                    self.selected.retain(|c| *c != category);
+                } else {
+                    self.selected.push(category);
+                }
+                self.update_reset_dialog(cx);
+            }
+        }
+
+        if self.view.button(ids!(reset_dialog.card.buttons.cancel_btn)).clicked(actions) {
+            self.hide_reset_dialog(cx);
+        }
+
+        if self.view.button(ids!(reset_dialog.card.buttons.confirm_btn)).clicked(actions)
+            && !self.selected.is_empty()
+        {
+            if let Some(plan) = self.pending.take() {
+                let categories = std::mem::take(&mut self.selected);
+                self.view.label(ids!(content.report_label))
+                    .set_text(cx, &format!("Resetting {}...", plan.app_name));
+                cx.widget_action(
+                    self.widget_uid(),
+                    &scope.path,
+                    AppDataViewAction::ResetConfirmed { plan, categories },
+                );
+            }
+            self.hide_reset_dialog(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, self.targets.len());
+
+                while let Some(item_id) = list.next_visible_item(cx) {
+                    if item_id < self.targets.len() {
+                        let item = list.item(cx, item_id, live_id!(app_item));
+                        let target = &self.targets[item_id];
+                        let bytes = self.sizes.get(item_id).copied().unwrap_or(0);
+
+                        item.label(ids!(info.name_label)).set_text(cx, &target.name);
+                        item.label(ids!(info.desc_label)).set_text(cx, &describe_locations(&target.locations));
+                        let size = if bytes == 0 { "No data".to_string() } else { format_size(bytes) };
+                        item.label(ids!(size_label)).set_text(cx, &size);
+                        item.button(ids!(reset_btn)).apply_over(cx, live!{
+                            draw_bg: { empty: (if bytes == 0 { 1.0 } else { 0.0 }) }
+                        });
+
+                        let dm = self.dark_mode;
+                        item.apply_over(cx, live!{
+                            draw_bg: { dark_mode: (dm) }
+                            info = {
+                                name_label = { draw_text: { dark_mode: (dm) } }
+                                desc_label = { draw_text: { dark_mode: (dm) } }
+                            }
+                            size_label = { draw_text: { dark_mode: (dm) } }
+                        });
+
+                        item.draw_all(cx, scope);
+                    }
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+impl AppDataView {
+    fn show_reset_dialog(&mut self, cx: &mut Cx, index: usize) {
+        let Some(target) = self.targets.get(index) else {
+            return;
+        };
+
+        // Dry run: measure what exists right now, nothing is deleted yet
+        let plan = ResetPlan::preview(&target.id, &target.name, &target.locations);
+        self.selected = DataCategory::ALL.iter().copied().filter(|c| plan.has(*c)).collect();
+
+        self.view.label(ids!(reset_dialog.card.dialog_title))
+            .set_text(cx, &format!("Reset {}?", target.name));
+        self.pending = Some(plan);
+        self.update_reset_dialog(cx);
+        self.view.view(ids!(reset_dialog)).set_visible(cx, true);
+        self.view.redraw(cx);
+    }
+
+    fn hide_reset_dialog(&mut self, cx: &mut Cx) {
+        self.pending = None;
+        self.selected.clear();
+        self.view.view(ids!(reset_dialog)).set_visible(cx, false);
+        self.view.redraw(cx);
+    }
+
+    /// Refresh the category toggles and the preview list
+    fn update_reset_dialog(&mut self, cx: &mut Cx) {
+        let Some(plan) = self.pending.as_ref() else {
+            return;
+        };
+
+        let toggles = [
+            (ids!(reset_dialog.card.prefs_toggle), DataCategory::Preferences),
+            (ids!(reset_dialog.card.cache_toggle), DataCategory::Cache),
+            (ids!(reset_dialog.card.webview_toggle), DataCategory::WebViewProfile),
+        ];
+        for (path, category) in toggles {
+            let toggle = self.view.button(path);
+            toggle.set_visible(cx, plan.has(category));
+            toggle.set_text(cx, &format!("{} ({})", category.label(), format_size(plan.bytes(category))));
+            let selected = self.selected.contains(&category);
+            toggle.apply_over(cx, live!{
+                draw_bg: { selected: (if selected { 1.0 } else { 0.0 }) }
+            });
+        }
+
+        let preview = if plan.is_empty() {
+            "Nothing is stored for this app.".to_string()
+        } else {
+            let selected_items: Vec<String> = plan.items.iter()
+                .filter(|item| self.selected.contains(&item.category))
+                .map(|item| format!("{} ({})", item.path.display(), format_size(item.bytes)))
+                .collect();
+            if selected_items.is_empty() {
+                "Nothing selected.".to_string()
+            } else {
+                format!("Will remove:\n{}", selected_items.join("\n"))
+            }
+        };
+        self.view.label(ids!(reset_dialog.card.preview_label)).set_text(cx, &preview);
+        self.view.redraw(cx);
+    }
+
+    fn measure_sizes(&mut self) {
+        self.sizes = self.targets.iter()
+            .map(|target| {
+                let plan = ResetPlan::preview(&target.id, &target.name, &target.locations);
+                plan.items.iter().map(|item| item.bytes).sum()
+            })
+            .collect();
+    }
+}
+
+/// Short description of which categories an app declares
+fn describe_locations(locations: &[DataLocation]) -> String {
+    let labels: Vec<&str> = DataCategory::ALL.iter()
+        .filter(|c| locations.iter().any(|l| l.category == **c))
+        .map(|c| c.label())
+        .collect();
+    if labels.is_empty() {
+        "Stores no data".to_string()
+    } else {
+        labels.join(", ")
+    }
+}
+
+impl AppDataViewRef {
+    /// Set the apps and plugins to list
+    pub fn set_targets(&self, cx: &mut Cx, targets: Vec<AppDataTarget>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.targets = targets;
+            inner.measure_sizes();
+            inner.view.redraw(cx);
+        }
+    }
+
+    /// Re-measure sizes on disk
+    pub fn refresh(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.measure_sizes();
+            inner.view.redraw(cx);
+        }
+    }
+
+    /// Show what a completed reset removed
+    pub fn show_report(&self, cx: &mut Cx, app_name: &str, report: &ResetReport) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.measure_sizes();
+            inner.view.label(ids!(content.report_label))
+                .set_text(cx, &format!("{}:\n{}", app_name, report.summary()));
+            inner.view.redraw(cx);
+        }
+    }
+
+    /// Show a diagnostic bundle's progress or outcome; `finished` allows
+    /// requesting the next one
+    pub fn show_bundle_status(&self, cx: &mut Cx, text: &str, finished: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.bundle_running = !finished;
+            inner.view.label(ids!(content.diagnostics.bundle_status)).set_text(cx, text);
+            inner.view.redraw(cx);
+        }
+    }
+
+    pub fn update_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.view.apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+
+            let labels = [
+                ids!(content.title),
+                ids!(content.subtitle),
+                ids!(content.report_label),
+                ids!(content.diagnostics.diagnostics_title),
+                ids!(content.diagnostics.diagnostics_hint),
+                ids!(content.diagnostics.bundle_status),
+                ids!(reset_dialog.card.dialog_title),
+                ids!(reset_dialog.card.dialog_hint),
+                ids!(reset_dialog.card.preview_label),
+            ];
+            for path in labels {
+                inner.view.label(path).apply_over(cx, live!{
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
+
+            inner.view.view(ids!(reset_dialog.card)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+
+            let buttons = [
+                ids!(reset_dialog.card.prefs_toggle),
+                ids!(reset_dialog.card.cache_toggle),
+                ids!(reset_dialog.card.webview_toggle),
+                ids!(reset_dialog.card.buttons.cancel_btn),
+                ids!(content.diagnostics.diagnostics_row.anonymize_toggle),
+                ids!(content.diagnostics.diagnostics_row.bundle_btn),
+            ];
+            for path in buttons {
+                inner.view.button(path).apply_over(cx, live!{
+                    draw_bg: { dark_mode: (dark_mode) }
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
+
+            // List rows pick this up when they are next drawn
+            inner.dark_mode = dark_mode;
+
+            inner.view.redraw(cx);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Chat completions against the user's AI provider
+//!
+//! Apps that call the provider themselves pick it with
+//! [`Preferences::resolve_ai_provider`] and send requests through
+//! [`ChatEndpoint::complete`], so they all use the same provider and talk to
+//! it the same way.
+
+use std::time::Duration;
+
+use super::preferences::Preferences;
+use super::providers::{Provider, ProviderId};
+
+/// Model used when the provider lists none
+pub const DEFAULT_CHAT_MODEL: &str = "gpt-4o-mini";
+
+/// OpenAI-compatible chat completions endpoint of a provider
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChatEndpoint {
+    pub provider: ProviderId,
+    /// Base URL without a trailing slash
+    pub api_url: String,
+    pub api_key: String,
+    pub model: String,
+}
+
+/// One system + user exchange
+#[derive(Clone, Debug)]
+pub struct ChatRequest<'a> {
+    pub system: &'a str,
+    pub user: &'a str,
+    pub temperature: f64,
+    /// Reply length limit; None leaves it to the provider
+    pub max_tokens: Option<usize>,
+    pub timeout: Duration,
+}
+
+/// Provider reply
+#[derive(Clone, Debug)]
+pub struct ChatReply {
+    /// Message content, trimmed and never empty
+    pub content: String,
+    /// Full response body, e.g. for its `usage` block
+    pub response: serde_json::Value,
+}
+
+/// Errors raised by a chat request
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatError {
+    Request(String),
+    Parse(String),
+}
+
+impl std::fmt::Display for ChatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChatError::Request(e) => write!(f, "Provider request failed: {}", e),
+            ChatError::Parse(e) => write!(f, "Could not read the reply: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ChatError {}
+
+impl ChatEndpoint {
+    pub fn from_provider(provider: &Provider) -> Self {
+        Self {
+            provider: provider.id.clone(),
+            api_url: provider.url.trim_end_matches('/').to_string(),
+            api_key: provider.api_key.clone().unwrap_or_default(),
+            model: provider
+                .models
+                .first()
+                .cloned()
+                .unwrap_or_else(|| DEFAULT_CHAT_MODEL.to_string()),
+        }
+    }
+
+    /// Endpoint of the provider [`Preferences::resolve_ai_provider`] picks
+    pub fn from_preferences(prefs: &Preferences) -> Option<Self> {
+        prefs.resolve_ai_provider().map(Self::from_provider)
+    }
+
+    /// Send `request` to `/chat/completions` and wait for the reply
+    pub fn complete(&self, request: &ChatRequest) -> Result<ChatReply, ChatError> {
+        let mut body = serde_json::json!({
+            "model": self.model,
+            "temperature": request.temperature,
+            "messages": [
+                { "role": "system", "content": request.system },
+                { "role": "user", "content": request.user },
+            ],
+        });
+        if let Some(max_tokens) = request.max_tokens {
+            body["max_tokens"] = max_tokens.into();
+        }
+
+        let response = ureq::post(&format!("{}/chat/completions", self.api_url))
+            .timeout(request.timeout)
+            .set("Authorization", &format!("Bearer {}", self.api_key))
+            .set("Content-Type", "application/json")
+            .send_string(&body.to_string())
+            .map_err(|e| ChatError::Request(e.to_string()))?
+            .into_string()
+            .map_err(|e| ChatError::Request(e.to_string()))?;
+
+        let response: serde_json::Value =
+            serde_json::from_str(&response).map_err(|e| ChatError::Parse(e.to_string()))?;
+        let content = response
+            .pointer("/choices/0/message/content")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|content| !content.is_empty())
+            .ok_or_else(|| ChatError::Parse("response has no message content".to_string()))?
+            .to_string();
+        Ok(ChatReply { content, response })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_from_provider() {
+        let mut provider = Provider {
+            id: "deepseek".to_string(),
+            url: "https://api.deepseek.com/v1/".to_string(),
+            api_key: Some("key".to_string()),
+            enabled: true,
+            ..Default::default()
+        };
+        let endpoint = ChatEndpoint::from_provider(&provider);
+        assert_eq!(endpoint.api_url, "https://api.deepseek.com/v1");
+        assert_eq!(endpoint.model, DEFAULT_CHAT_MODEL);
+
+        provider.models = vec!["deepseek-chat".to_string()];
+        let mut prefs = Preferences::default();
+        prefs.providers.push(provider);
+        let endpoint = ChatEndpoint::from_preferences(&prefs).unwrap();
+        assert_eq!((endpoint.provider.as_str(), endpoint.model.as_str()), ("deepseek", "deepseek-chat"));
+    }
+}
@@ -1,7 +1,9 @@
 //! Data models for settings
 
+pub mod chat;
 pub mod preferences;
 pub mod providers;
 
+pub use chat::*;
 pub use preferences::*;
 pub use providers::*;
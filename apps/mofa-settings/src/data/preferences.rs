@@ -20,6 +20,19 @@ pub struct Preferences {
     /// Dark mode preference (true = dark, false = light)
     #[serde(default)]
     pub dark_mode: bool,
+    /// Global shortcut that opens Note Taker quick capture (None = disabled)
+    #[serde(default)]
+    pub quick_capture_hotkey: Option<String>,
+    /// Onboarding tour finished or skipped; it is not shown on launch again
+    #[serde(default)]
+    pub tour_completed: bool,
+    /// Interface language as a locale code (e.g. "zh-CN"); None follows the system
+    #[serde(default)]
+    pub language: Option<String>,
+    /// New system log lines per node and second in MoFA FM, beyond which
+    /// lines are dropped (None = 50, 0 = no limit)
+    #[serde(default)]
+    pub log_rate_limit: Option<u32>,
 }
 
 impl Preferences {
@@ -124,6 +137,17 @@ impl Preferences {
     pub fn get_enabled_providers(&self) -> Vec<&Provider> {
         self.providers.iter().filter(|p| p.enabled).collect()
     }
+
+    /// Provider apps use for chat: the default chat provider, else the first
+    /// provider; either must be enabled and have an API key
+    pub fn resolve_ai_provider(&self) -> Option<&Provider> {
+        let usable = |p: &&Provider| p.enabled && p.api_key.as_deref().is_some_and(|k| !k.is_empty());
+        self.default_chat_provider
+            .as_deref()
+            .and_then(|id| self.get_provider(id))
+            .filter(usable)
+            .or_else(|| self.providers.iter().find(usable))
+    }
 }
 
 #[cfg(test)]
@@ -339,4 +363,27 @@ mod tests {
         assert!(prefs.audio_input_device.is_none());
         assert!(prefs.audio_output_device.is_none());
     }
+
+    #[test]
+    fn test_resolve_ai_provider() {
+        let mut prefs = Preferences::default();
+        for id in ["first", "second"] {
+            let mut provider = create_test_provider(id, true, true);
+            provider.api_key = Some("key".to_string());
+            prefs.providers.push(provider);
+        }
+
+        // No default: first usable provider
+        assert_eq!(prefs.resolve_ai_provider().unwrap().id, "first");
+
+        prefs.default_chat_provider = Some("second".to_string());
+        assert_eq!(prefs.resolve_ai_provider().unwrap().id, "second");
+
+        // Default without a key falls back
+        prefs.get_provider_mut("second").unwrap().api_key = Some(String::new());
+        assert_eq!(prefs.resolve_ai_provider().unwrap().id, "first");
+
+        prefs.get_provider_mut("first").unwrap().enabled = false;
+        assert!(prefs.resolve_ai_provider().is_none());
+    }
 }
@@ -1,11 +1,14 @@
 //! MoFA Settings App - Provider configuration and preferences
 
 pub mod add_provider_modal;
+pub mod app_data_view;
 pub mod data;
 pub mod models_view;
 pub mod provider_view;
 pub mod providers_panel;
 pub mod screen;
+pub mod shortcuts_view;
+pub mod voices_view;
 
 pub use screen::SettingsScreenRef;
 
@@ -33,6 +36,9 @@ impl MofaApp for MoFaSettingsApp {
         provider_view::live_design(cx);
         models_view::live_design(cx);
         add_provider_modal::live_design(cx);
+        app_data_view::live_design(cx);
+        shortcuts_view::live_design(cx);
+        voices_view::live_design(cx);
         screen::live_design(cx);
     }
 }
@@ -380,6 +380,30 @@ live_design! {
 
         // Add provider button fixed at bottom (outside scroll area)
         add_button = <AddProviderButton> {}
+
+        // Opens the per-app data reset view
+        app_data_button = <AddProviderButton> {
+            add_icon = { visible: false }
+            add_label = { text: "App Data" }
+        }
+
+        // Opens the global keyboard shortcuts view
+        shortcuts_button = <AddProviderButton> {
+            add_icon = { visible: false }
+            add_label = { text: "Keyboard Shortcuts" }
+        }
+
+        // Opens the TTS voices view
+        voices_button = <AddProviderButton> {
+            add_icon = { visible: false }
+            add_label = { text: "Voices" }
+        }
+
+        // Replays the onboarding tour
+        tour_button = <AddProviderButton> {
+            add_icon = { visible: false }
+            add_label = { text: "Take the Tour" }
+        }
     }
 }
 
@@ -388,6 +412,11 @@ pub enum ProvidersPanelAction {
     None,
     Selected(ProviderId),
     AddProviderClicked,
+    AppDataClicked,
+    ShortcutsClicked,
+    VoicesClicked,
+    /// Handled by the shell, which owns the tour overlay
+    TourClicked,
 }
 
 #[derive(Live, LiveHook, Widget)]
@@ -506,6 +535,94 @@ impl Widget for ProvidersPanel {
             _ => {}
         }
 
+        // Handle hover and click for app data button
+        let app_data_button = self.view.view(ids!(app_data_button));
+        match event.hits(cx, app_data_button.area()) {
+            Hit::FingerHoverIn(_) => {
+                self.view.view(ids!(app_data_button)).apply_over(cx, live!{
+                    draw_bg: { hover: 1.0 }
+                });
+                self.view.redraw(cx);
+            }
+            Hit::FingerHoverOut(_) => {
+                self.view.view(ids!(app_data_button)).apply_over(cx, live!{
+                    draw_bg: { hover: 0.0 }
+                });
+                self.view.redraw(cx);
+            }
+            Hit::FingerUp(_) => {
+                cx.widget_action(uid, &scope.path, ProvidersPanelAction::AppDataClicked);
+                return;
+            }
+            _ => {}
+        }
+
+        // Handle hover and click for shortcuts button
+        let shortcuts_button = self.view.view(ids!(shortcuts_button));
+        match event.hits(cx, shortcuts_button.area()) {
+            Hit::FingerHoverIn(_) => {
+                self.view.view(ids!(shortcuts_button)).apply_over(cx, live!{
+                    draw_bg: { hover: 1.0 }
+                });
+                self.view.redraw(cx);
+            }
+            Hit::FingerHoverOut(_) => {
+                self.view.view(ids!(shortcuts_button)).apply_over(cx, live!{
+                    draw_bg: { hover: 0.0 }
+                });
+                self.view.redraw(cx);
+            }
+            Hit::FingerUp(_) => {
+                cx.widget_action(uid, &scope.path, ProvidersPanelAction::ShortcutsClicked);
+                return;
+            }
+            _ => {}
+        }
+
+        // Handle hover and click for voices button
+        let voices_button = self.view.view(ids!(voices_button));
+        match event.hits(cx, voices_button.area()) {
+            Hit::FingerHoverIn(_) => {
+                self.view.view(ids!(voices_button)).apply_over(cx, live!{
+                    draw_bg: { hover: 1.0 }
+                });
+                self.view.redraw(cx);
+            }
+            Hit::FingerHoverOut(_) => {
+                self.view.view(ids!(voices_button)).apply_over(cx, live!{
+                    draw_bg: { hover: 0.0 }
+                });
+                self.view.redraw(cx);
+            }
+            Hit::FingerUp(_) => {
+                cx.widget_action(uid, &scope.path, ProvidersPanelAction::VoicesClicked);
+                return;
+            }
+            _ => {}
+        }
+
+        // Handle hover and click for tour button
+        let tour_button = self.view.view(ids!(tour_button));
+        match event.hits(cx, tour_button.area()) {
+            Hit::FingerHoverIn(_) => {
+                self.view.view(ids!(tour_button)).apply_over(cx, live!{
+                    draw_bg: { hover: 1.0 }
+                });
+                self.view.redraw(cx);
+            }
+            Hit::FingerHoverOut(_) => {
+                self.view.view(ids!(tour_button)).apply_over(cx, live!{
+                    draw_bg: { hover: 0.0 }
+                });
+                self.view.redraw(cx);
+            }
+            Hit::FingerUp(_) => {
+                cx.widget_action(uid, &scope.path, ProvidersPanelAction::TourClicked);
+                return;
+            }
+            _ => {}
+        }
+
         // Handle hover and click for custom provider items
         let custom_items = [
             ids!(scroll_view.custom_section.custom_provider_1),
@@ -850,6 +967,30 @@ impl ProvidersPanelRef {
             inner.view.label(ids!(add_button.add_label)).apply_over(cx, live!{
                 draw_text: { dark_mode: (dark_mode) }
             });
+            inner.view.view(ids!(app_data_button)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+            inner.view.label(ids!(app_data_button.add_label)).apply_over(cx, live!{
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.view(ids!(shortcuts_button)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+            inner.view.label(ids!(shortcuts_button.add_label)).apply_over(cx, live!{
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.view(ids!(voices_button)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+            inner.view.label(ids!(voices_button.add_label)).apply_over(cx, live!{
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.view(ids!(tour_button)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+            inner.view.label(ids!(tour_button.add_label)).apply_over(cx, live!{
+                draw_text: { dark_mode: (dark_mode) }
+            });
 
             inner.view.redraw(cx);
         }
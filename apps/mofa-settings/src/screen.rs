@@ -6,6 +6,10 @@ use crate::providers_panel::{ProvidersPanelAction, ProvidersPanelWidgetExt};
 use crate::provider_view::ProviderViewWidgetExt;
 use crate::add_provider_modal::{AddProviderModalAction, AddProviderModalWidgetExt};
 use crate::models_view::ModelsViewWidgetExt;
+use crate::app_data_view::{AppDataTarget, AppDataViewWidgetExt};
+use crate::shortcuts_view::ShortcutsViewWidgetExt;
+use crate::voices_view::VoicesViewWidgetExt;
+use mofa_widgets::app_storage::ResetReport;
 
 live_design! {
     use link::theme::*;
@@ -18,6 +22,9 @@ live_design! {
     use crate::provider_view::ProviderView;
     use crate::models_view::ModelsView;
     use crate::add_provider_modal::AddProviderModal;
+    use crate::app_data_view::AppDataView;
+    use crate::shortcuts_view::ShortcutsView;
+    use crate::voices_view::VoicesView;
 
     // Divider line with dark mode support
     VerticalDivider = <View> {
@@ -66,6 +73,21 @@ live_design! {
                 models_view = <ModelsView> {
                     visible: false
                 }
+
+                // Per-app data reset (hidden by default)
+                app_data_view = <AppDataView> {
+                    visible: false
+                }
+
+                // Global keyboard shortcuts (hidden by default)
+                shortcuts_view = <ShortcutsView> {
+                    visible: false
+                }
+
+                // TTS voices across backends (hidden by default)
+                voices_view = <VoicesView> {
+                    visible: false
+                }
             }
         }
 
@@ -116,6 +138,9 @@ impl Widget for SettingsScreen {
         // Handle provider panel actions
         let mut selected_provider: Option<ProviderId> = None;
         let mut add_provider_clicked = false;
+        let mut app_data_clicked = false;
+        let mut shortcuts_clicked = false;
+        let mut voices_clicked = false;
 
         for action in actions {
             match action.as_widget_action().cast() {
@@ -125,6 +150,15 @@ impl Widget for SettingsScreen {
                 ProvidersPanelAction::AddProviderClicked => {
                     add_provider_clicked = true;
                 }
+                ProvidersPanelAction::AppDataClicked => {
+                    app_data_clicked = true;
+                }
+                ProvidersPanelAction::ShortcutsClicked => {
+                    shortcuts_clicked = true;
+                }
+                ProvidersPanelAction::VoicesClicked => {
+                    voices_clicked = true;
+                }
                 _ => {}
             }
         }
@@ -134,6 +168,19 @@ impl Widget for SettingsScreen {
                 self.selected_provider_id = Some(id.clone());
                 self.load_provider_to_view(cx, &id);
             }
+            self.show_provider_view(cx);
+        }
+
+        if app_data_clicked {
+            self.show_app_data_view(cx);
+        }
+
+        if shortcuts_clicked {
+            self.show_shortcuts_view(cx);
+        }
+
+        if voices_clicked {
+            self.show_voices_view(cx);
         }
 
         if add_provider_clicked {
@@ -184,17 +231,54 @@ impl SettingsScreen {
     fn show_provider_view(&mut self, cx: &mut Cx) {
         self.view.view(ids!(content.right_panel.provider_view)).set_visible(cx, true);
         self.view.view(ids!(content.right_panel.models_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.app_data_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.shortcuts_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.voices_view)).set_visible(cx, false);
         self.view.redraw(cx);
     }
 
     fn show_models_view(&mut self, cx: &mut Cx) {
         self.view.view(ids!(content.right_panel.provider_view)).set_visible(cx, false);
         self.view.view(ids!(content.right_panel.models_view)).set_visible(cx, true);
+        self.view.view(ids!(content.right_panel.app_data_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.shortcuts_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.voices_view)).set_visible(cx, false);
         // Refresh model status
         self.view.models_view(ids!(content.right_panel.models_view)).refresh(cx);
         self.view.redraw(cx);
     }
 
+    fn show_app_data_view(&mut self, cx: &mut Cx) {
+        self.view.view(ids!(content.right_panel.provider_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.models_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.app_data_view)).set_visible(cx, true);
+        self.view.view(ids!(content.right_panel.shortcuts_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.voices_view)).set_visible(cx, false);
+        // Sizes may have changed while the view was hidden
+        self.view.app_data_view(ids!(content.right_panel.app_data_view)).refresh(cx);
+        self.view.redraw(cx);
+    }
+
+    fn show_shortcuts_view(&mut self, cx: &mut Cx) {
+        self.view.view(ids!(content.right_panel.provider_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.models_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.app_data_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.shortcuts_view)).set_visible(cx, true);
+        self.view.view(ids!(content.right_panel.voices_view)).set_visible(cx, false);
+        self.view.redraw(cx);
+    }
+
+    fn show_voices_view(&mut self, cx: &mut Cx) {
+        self.view.view(ids!(content.right_panel.provider_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.models_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.app_data_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.shortcuts_view)).set_visible(cx, false);
+        self.view.view(ids!(content.right_panel.voices_view)).set_visible(cx, true);
+        // Backends are only queried once the page is opened
+        self.view.voices_view(ids!(content.right_panel.voices_view)).refresh(cx);
+        self.view.redraw(cx);
+    }
+
     fn load_provider_to_view(&mut self, cx: &mut Cx, provider_id: &ProviderId) {
         // Load preferences if needed (limited scope to avoid borrow conflicts)
         if self.preferences.is_none() {
@@ -472,6 +556,38 @@ impl SettingsScreenRef {
         }
     }
 
+    /// Set the apps and plugins listed in the App Data view
+    pub fn set_app_data_targets(&self, cx: &mut Cx, targets: Vec<AppDataTarget>) {
+        if let Some(inner) = self.borrow_mut() {
+            inner.view.app_data_view(ids!(content.right_panel.app_data_view))
+                .set_targets(cx, targets);
+        }
+    }
+
+    /// Show the result of an app data reset
+    pub fn show_reset_report(&self, cx: &mut Cx, app_name: &str, report: &ResetReport) {
+        if let Some(inner) = self.borrow_mut() {
+            inner.view.app_data_view(ids!(content.right_panel.app_data_view))
+                .show_report(cx, app_name, report);
+        }
+    }
+
+    /// Show a diagnostic bundle's progress or outcome
+    pub fn show_bundle_status(&self, cx: &mut Cx, text: &str, finished: bool) {
+        if let Some(inner) = self.borrow_mut() {
+            inner.view.app_data_view(ids!(content.right_panel.app_data_view))
+                .show_bundle_status(cx, text, finished);
+        }
+    }
+
+    /// Show the result of registering a global shortcut
+    pub fn show_shortcut_status(&self, cx: &mut Cx, text: &str) {
+        if let Some(inner) = self.borrow_mut() {
+            inner.view.shortcuts_view(ids!(content.right_panel.shortcuts_view))
+                .show_status(cx, text);
+        }
+    }
+
     /// Update dark mode for this screen
     pub fn update_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
         if let Some(mut inner) = self.borrow_mut() {
@@ -497,6 +613,18 @@ impl SettingsScreenRef {
             inner.view.models_view(ids!(content.right_panel.models_view))
                 .update_dark_mode(cx, dark_mode);
 
+            // Apply dark mode to app data view
+            inner.view.app_data_view(ids!(content.right_panel.app_data_view))
+                .update_dark_mode(cx, dark_mode);
+
+            // Apply dark mode to shortcuts view
+            inner.view.shortcuts_view(ids!(content.right_panel.shortcuts_view))
+                .update_dark_mode(cx, dark_mode);
+
+            // Apply dark mode to voices view
+            inner.view.voices_view(ids!(content.right_panel.voices_view))
+                .update_dark_mode(cx, dark_mode);
+
             // Apply dark mode to add provider modal
             inner.view.add_provider_modal(ids!(add_provider_modal))
                 .update_dark_mode(cx, dark_mode);
@@ -0,0 +1,253 @@
+//! Shortcuts View - System-wide keyboard shortcuts
+//!
+//! Edits the global shortcuts stored in [`Preferences`]. Saving writes the
+//! preferences and emits an action; the shell registers the shortcut and
+//! reports back through [`ShortcutsViewRef::show_status`].
+
+use makepad_widgets::*;
+use crate::data::Preferences;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use mofa_widgets::theme::*;
+
+    ShortcutInput = <TextInput> {
+        width: 280, height: 40
+        padding: {left: 12, right: 12, top: 10, bottom: 10}
+
+        draw_bg: {
+            instance dark_mode: 0.0
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                sdf.fill(mix(#F1F5F9, #334155, self.dark_mode));
+                sdf.stroke(mix(#CBD5E1, #475569, self.dark_mode), 1.0);
+                return sdf.result;
+            }
+        }
+
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_REGULAR>{ font_size: 13.0 }
+            fn get_color(self) -> vec4 {
+                return mix(#1E293B, #F1F5F9, self.dark_mode);
+            }
+        }
+
+        draw_cursor: {
+            fn pixel(self) -> vec4 {
+                return #3B82F6;
+            }
+        }
+
+        draw_selection: {
+            fn pixel(self) -> vec4 {
+                return vec4(0.231, 0.510, 0.965, 0.3);
+            }
+        }
+    }
+
+    HintLabel = <Label> {
+        width: Fill
+        draw_text: {
+            instance dark_mode: 0.0
+            wrap: Word
+            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix(#64748B, #94A3B8, self.dark_mode);
+            }
+        }
+    }
+
+    pub ShortcutsView = {{ShortcutsView}} {
+        width: Fill, height: Fill
+        flow: Down
+        padding: {top: 24, bottom: 24, left: 24, right: 24}
+        show_bg: true
+        draw_bg: {
+            instance dark_mode: 0.0
+            fn pixel(self) -> vec4 {
+                return mix((WHITE), (SLATE_900), self.dark_mode);
+            }
+        }
+
+        title = <Label> {
+            draw_text: {
+                instance dark_mode: 0.0
+                text_style: <FONT_BOLD>{ font_size: 18.0 }
+                fn get_color(self) -> vec4 {
+                    return mix(#1E293B, #F1F5F9, self.dark_mode);
+                }
+            }
+            text: "Keyboard Shortcuts"
+        }
+
+        subtitle = <HintLabel> {
+            margin: {top: 4, bottom: 20}
+            text: "Shortcuts that work system-wide, even when MoFA Studio is in the background"
+        }
+
+        quick_capture = <View> {
+            width: Fill, height: Fit
+            flow: Down
+            spacing: 6
+
+            label = <Label> {
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: <FONT_SEMIBOLD>{ font_size: 13.0 }
+                    fn get_color(self) -> vec4 {
+                        return mix(#334155, #E2E8F0, self.dark_mode);
+                    }
+                }
+                text: "Quick capture to Note Taker"
+            }
+
+            row = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                align: {y: 0.5}
+
+                shortcut_input = <ShortcutInput> {
+                    empty_text: "Disabled"
+                }
+
+                save_btn = <Button> {
+                    width: Fit, height: 36
+                    padding: {left: 16, right: 16}
+                    text: "Save"
+                    draw_text: {
+                        text_style: <FONT_SEMIBOLD>{ font_size: 11.0 }
+                        fn get_color(self) -> vec4 {
+                            return #FFFFFF;
+                        }
+                    }
+                    draw_bg: {
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                            sdf.fill(#3B82F6);
+                            return sdf.result;
+                        }
+                    }
+                }
+            }
+
+            hint = <HintLabel> {
+                text: "Opens a small window that appends a line to today's note. Use modifiers joined with +, e.g. CmdOrCtrl+Shift+Space or Alt+N. Leave empty to disable."
+            }
+
+            status_label = <HintLabel> {
+                margin: {top: 8}
+                text: ""
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ShortcutsViewAction {
+    None,
+    /// Quick-capture shortcut saved; `None` disables it
+    QuickCaptureChanged(Option<String>),
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct ShortcutsView {
+    #[deref]
+    view: View,
+
+    #[rust]
+    loaded: bool,
+}
+
+impl Widget for ShortcutsView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if !self.loaded {
+            self.loaded = true;
+            let shortcut = Preferences::load().quick_capture_hotkey.unwrap_or_default();
+            self.view.text_input(ids!(quick_capture.row.shortcut_input)).set_text(cx, &shortcut);
+        }
+
+        let actions = match event {
+            Event::Actions(actions) => actions.as_slice(),
+            _ => return,
+        };
+
+        let input = self.view.text_input(ids!(quick_capture.row.shortcut_input));
+        if self.view.button(ids!(quick_capture.row.save_btn)).clicked(actions)
+            || input.returned(actions).is_some()
+        {
+            let text = input.text();
+            let shortcut = Some(text.trim().to_string()).filter(|s| !s.is_empty());
+
+            let mut prefs = Preferences::load();
+            prefs.quick_capture_hotkey = shortcut.clone();
+            if let Err(e) = prefs.save() {
+                self.set_status(cx, &format!("Failed to save: {}", e));
+                return;
+            }
+
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                ShortcutsViewAction::QuickCaptureChanged(shortcut),
+            );
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl ShortcutsView {
+    fn set_status(&mut self, cx: &mut Cx, text: &str) {
+        self.view.label(ids!(quick_capture.status_label)).set_text(cx, text);
+        self.view.redraw(cx);
+    }
+}
+
+impl ShortcutsViewRef {
+    /// Show the result of registering the shortcut
+    pub fn show_status(&self, cx: &mut Cx, text: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_status(cx, text);
+        }
+    }
+
+    pub fn update_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.view.apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+
+            let labels = [
+                ids!(title),
+                ids!(subtitle),
+                ids!(quick_capture.label),
+                ids!(quick_capture.hint),
+                ids!(quick_capture.status_label),
+            ];
+            for path in labels {
+                inner.view.label(path).apply_over(cx, live!{
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
+
+            inner.view.text_input(ids!(quick_capture.row.shortcut_input)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+                draw_text: { dark_mode: (dark_mode) }
+            });
+
+            inner.view.redraw(cx);
+        }
+    }
+}
@@ -0,0 +1,557 @@
+//! Voices View - TTS voices across all backends
+//!
+//! Lists the voices of every registered TTS backend in one filterable table.
+//! A voice can be previewed with the sample sentence of its language and
+//! starred; favorites are listed first in the voice dropdowns of the apps.
+//! A backend that fails to list its voices gets an error line instead of
+//! rows, the other backends are shown as usual.
+
+use makepad_widgets::*;
+use mofa_widgets::tts::TtsVoice;
+use mofa_widgets::voices::{self, PreviewState, VoicePreview, VoicePrefs};
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use mofa_widgets::theme::*;
+
+    CellLabel = <Label> {
+        width: 90, height: Fit
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix(#64748B, #94A3B8, self.dark_mode);
+            }
+        }
+    }
+
+    RowButton = <Button> {
+        width: 72, height: 28
+        margin: {left: 8}
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix(#334155, #E2E8F0, self.dark_mode);
+            }
+        }
+        draw_bg: {
+            instance dark_mode: 0.0
+            instance selected: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                // Amber when selected (a starred voice)
+                let idle = mix(#E2E8F0, #334155, self.dark_mode);
+                sdf.fill(mix(idle, #F59E0B, self.selected));
+                return sdf.result;
+            }
+        }
+    }
+
+    // Table row: name, locale, gender, backend, actions
+    VoiceItem = <View> {
+        width: Fill, height: Fit
+        padding: {top: 8, bottom: 8, left: 16, right: 16}
+        margin: {bottom: 2}
+        flow: Right
+        align: {x: 0.0, y: 0.5}
+        show_bg: true
+        draw_bg: {
+            instance dark_mode: 0.0
+            fn pixel(self) -> vec4 {
+                let light = #F8FAFC;  // slate-50
+                let dark = #1E293B;   // slate-800
+                return mix(light, dark, self.dark_mode);
+            }
+        }
+
+        name_label = <Label> {
+            width: Fill, height: Fit
+            draw_text: {
+                instance dark_mode: 0.0
+                text_style: <FONT_REGULAR>{ font_size: 13.0 }
+                fn get_color(self) -> vec4 {
+                    return mix(#334155, #E2E8F0, self.dark_mode);
+                }
+            }
+        }
+        locale_label = <CellLabel> {}
+        gender_label = <CellLabel> {}
+        backend_label = <CellLabel> {}
+
+        preview_btn = <RowButton> { text: "Preview" }
+        star_btn = <RowButton> { text: "Favorite" }
+    }
+
+    HintLabel = <Label> {
+        width: Fill
+        draw_text: {
+            instance dark_mode: 0.0
+            wrap: Word
+            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix(#64748B, #94A3B8, self.dark_mode);
+            }
+        }
+    }
+
+    VoiceInput = <TextInput> {
+        width: Fill, height: 36
+        padding: {left: 12, right: 12, top: 8, bottom: 8}
+
+        draw_bg: {
+            instance dark_mode: 0.0
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                sdf.fill(mix(#F1F5F9, #334155, self.dark_mode));
+                sdf.stroke(mix(#CBD5E1, #475569, self.dark_mode), 1.0);
+                return sdf.result;
+            }
+        }
+
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_REGULAR>{ font_size: 12.0 }
+            fn get_color(self) -> vec4 {
+                return mix(#1E293B, #F1F5F9, self.dark_mode);
+            }
+        }
+
+        draw_cursor: {
+            fn pixel(self) -> vec4 {
+                return #3B82F6;
+            }
+        }
+
+        draw_selection: {
+            fn pixel(self) -> vec4 {
+                return vec4(0.231, 0.510, 0.965, 0.3);
+            }
+        }
+    }
+
+    pub VoicesView = {{VoicesView}} {
+        width: Fill, height: Fill
+        flow: Down
+        padding: {top: 24, bottom: 24, left: 24, right: 24}
+        show_bg: true
+        draw_bg: {
+            instance dark_mode: 0.0
+            fn pixel(self) -> vec4 {
+                return mix((WHITE), (SLATE_900), self.dark_mode);
+            }
+        }
+
+        title = <Label> {
+            draw_text: {
+                instance dark_mode: 0.0
+                text_style: <FONT_BOLD>{ font_size: 18.0 }
+                fn get_color(self) -> vec4 {
+                    return mix(#1E293B, #F1F5F9, self.dark_mode);
+                }
+            }
+            text: "Voices"
+        }
+
+        subtitle = <HintLabel> {
+            margin: {top: 4, bottom: 16}
+            text: "Text-to-speech voices of all backends. Favorites are listed first in the apps' voice menus."
+        }
+
+        filter_row = <View> {
+            width: Fill, height: Fit
+            flow: Right
+            spacing: 8
+            align: {y: 0.5}
+
+            filter_input = <VoiceInput> {
+                empty_text: "Filter by name, locale, gender or backend"
+            }
+
+            refresh_btn = <RowButton> {
+                width: Fit, height: 36
+                padding: {left: 16, right: 16}
+                margin: 0
+                text: "Refresh"
+            }
+        }
+
+        // One line per backend that could not list its voices
+        errors_label = <Label> {
+            width: Fill
+            margin: {top: 8}
+            draw_text: {
+                wrap: Word
+                text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                fn get_color(self) -> vec4 {
+                    return #EF4444;
+                }
+            }
+            text: ""
+        }
+
+        header = <View> {
+            width: Fill, height: Fit
+            padding: {top: 12, bottom: 6, left: 16, right: 16}
+            flow: Right
+
+            name_header = <HintLabel> { text: "Name" }
+            locale_header = <CellLabel> { text: "Locale" }
+            gender_header = <CellLabel> { text: "Gender" }
+            backend_header = <CellLabel> { text: "Backend" }
+            // Room for the row buttons
+            <View> { width: 160, height: 1 }
+        }
+
+        voice_list = <PortalList> {
+            width: Fill, height: Fill
+            flow: Down
+
+            voice_item = <VoiceItem> {}
+        }
+
+        sample_section = <View> {
+            width: Fill, height: Fit
+            flow: Down
+            spacing: 6
+            margin: {top: 12}
+
+            sample_label = <HintLabel> { text: "" }
+
+            sample_row = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                align: {y: 0.5}
+
+                sample_input = <VoiceInput> {
+                    empty_text: "Sample sentence"
+                }
+
+                save_sample_btn = <RowButton> {
+                    width: Fit, height: 36
+                    padding: {left: 16, right: 16}
+                    margin: 0
+                    text: "Save"
+                }
+            }
+
+            status_label = <HintLabel> { text: "" }
+        }
+    }
+}
+
+/// A table row
+struct VoiceRow {
+    backend: String,
+    voice: TtsVoice,
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct VoicesView {
+    #[deref]
+    view: View,
+
+    /// Voices of all backends that could list them
+    #[rust]
+    rows: Vec<VoiceRow>,
+
+    /// Indexes into `rows` matching the filter
+    #[rust]
+    visible: Vec<usize>,
+
+    #[rust]
+    prefs: VoicePrefs,
+
+    #[rust]
+    loaded: bool,
+
+    /// Language whose sample text is being edited
+    #[rust]
+    sample_language: String,
+
+    #[rust]
+    preview: Option<VoicePreview>,
+
+    #[rust]
+    preview_timer: Timer,
+
+    #[rust]
+    dark_mode: f64,
+}
+
+impl Widget for VoicesView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if self.preview_timer.is_event(event).is_some() {
+            self.poll_preview(cx);
+        }
+
+        let actions = match event {
+            Event::Actions(actions) => actions.as_slice(),
+            _ => return,
+        };
+
+        if self.view.text_input(ids!(filter_row.filter_input)).changed(actions).is_some() {
+            self.apply_filter(cx);
+        }
+
+        if self.view.button(ids!(filter_row.refresh_btn)).clicked(actions) {
+            self.load_catalogue(cx);
+        }
+
+        let list = self.view.portal_list(ids!(voice_list));
+        for (item_id, item) in list.items_with_actions(actions) {
+            let Some(&row) = self.visible.get(item_id) else {
+                continue;
+            };
+            if item.button(ids!(star_btn)).clicked(actions) {
+                self.toggle_favorite(cx, row);
+            }
+            if item.button(ids!(preview_btn)).clicked(actions) {
+                self.start_preview(cx, row);
+            }
+        }
+
+        let sample_input = self.view.text_input(ids!(sample_section.sample_row.sample_input));
+        if self.view.button(ids!(sample_section.sample_row.save_sample_btn)).clicked(actions)
+            || sample_input.returned(actions).is_some()
+        {
+            let language = self.sample_language.clone();
+            self.prefs.set_sample_text(&language, &sample_input.text());
+            match self.prefs.save() {
+                Ok(()) => self.set_status(cx, "Sample text saved"),
+                Err(e) => self.set_status(cx, &format!("Failed to save: {}", e)),
+            }
+            self.show_sample_text(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, self.visible.len());
+
+                while let Some(item_id) = list.next_visible_item(cx) {
+                    let Some(row) = self.visible.get(item_id).and_then(|&i| self.rows.get(i)) else {
+                        continue;
+                    };
+                    let item = list.item(cx, item_id, live_id!(voice_item));
+                    let voice = &row.voice;
+                    let or_dash = |text: &str| if text.is_empty() { "-".to_string() } else { text.to_string() };
+
+                    item.label(ids!(name_label)).set_text(cx, &voice.name);
+                    item.label(ids!(locale_label)).set_text(cx, &or_dash(&voice.language));
+                    item.label(ids!(gender_label)).set_text(cx, &or_dash(&voice.gender));
+                    item.label(ids!(backend_label)).set_text(cx, &row.backend);
+
+                    let favorite = self.prefs.is_favorite(&row.backend, &voice.id);
+                    let star_btn = item.button(ids!(star_btn));
+                    star_btn.set_text(cx, if favorite { "Starred" } else { "Favorite" });
+
+                    let dm = self.dark_mode;
+                    let selected = if favorite { 1.0 } else { 0.0 };
+                    item.apply_over(cx, live!{
+                        draw_bg: { dark_mode: (dm) }
+                        name_label = { draw_text: { dark_mode: (dm) } }
+                        locale_label = { draw_text: { dark_mode: (dm) } }
+                        gender_label = { draw_text: { dark_mode: (dm) } }
+                        backend_label = { draw_text: { dark_mode: (dm) } }
+                        preview_btn = { draw_bg: { dark_mode: (dm) } draw_text: { dark_mode: (dm) } }
+                        star_btn = { draw_bg: { dark_mode: (dm) selected: (selected) } draw_text: { dark_mode: (dm) } }
+                    });
+
+                    item.draw_all(cx, scope);
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+impl VoicesView {
+    /// Ask every backend for its voices
+    fn load_catalogue(&mut self, cx: &mut Cx) {
+        self.loaded = true;
+        self.prefs = VoicePrefs::load();
+        self.rows.clear();
+
+        let mut errors = Vec::new();
+        for entry in voices::catalogue() {
+            match entry.voices {
+                Ok(list) => self.rows.extend(list.into_iter().map(|voice| VoiceRow {
+                    backend: entry.backend.clone(),
+                    voice,
+                })),
+                Err(e) => {
+                    ::log::warn!("TTS backend {} failed to list voices: {}", entry.backend, e);
+                    errors.push(format!("{}: {}", entry.backend, e));
+                }
+            }
+        }
+        self.view.label(ids!(errors_label)).set_text(cx, &errors.join("\n"));
+
+        if self.sample_language.is_empty() {
+            self.sample_language = "en".to_string();
+        }
+        self.show_sample_text(cx);
+        self.apply_filter(cx);
+    }
+
+    fn apply_filter(&mut self, cx: &mut Cx) {
+        let filter = self.view.text_input(ids!(filter_row.filter_input)).text();
+        self.visible = self.rows.iter()
+            .enumerate()
+            .filter(|(_, row)| voices::matches_filter(&row.backend, &row.voice, &filter))
+            .map(|(index, _)| index)
+            .collect();
+        self.set_status(cx, &format!("{} of {} voices", self.visible.len(), self.rows.len()));
+        self.view.redraw(cx);
+    }
+
+    fn toggle_favorite(&mut self, cx: &mut Cx, row: usize) {
+        let Some(row) = self.rows.get(row) else {
+            return;
+        };
+        let favorite = self.prefs.toggle_favorite(&row.backend, &row.voice.id);
+        let name = row.voice.name.clone();
+        match self.prefs.save() {
+            Ok(()) => {
+                let verb = if favorite { "added to" } else { "removed from" };
+                self.set_status(cx, &format!("{} {} favorites", name, verb));
+            }
+            Err(e) => self.set_status(cx, &format!("Failed to save favorites: {}", e)),
+        }
+        self.view.redraw(cx);
+    }
+
+    /// Speak the sample text of the voice's language; switches the sample
+    /// editor to that language
+    fn start_preview(&mut self, cx: &mut Cx, row: usize) {
+        let Some(row) = self.rows.get(row) else {
+            return;
+        };
+        let language = voices::primary_language(&row.voice.language);
+        let text = if language == self.sample_language {
+            // Unsaved edits can be tried out before saving them
+            self.view.text_input(ids!(sample_section.sample_row.sample_input)).text()
+        } else {
+            self.prefs.sample_text(&language)
+        };
+        let preview = VoicePreview::start(&row.backend, &row.voice, text.trim());
+        if !language.is_empty() && language != self.sample_language {
+            self.sample_language = language;
+            self.show_sample_text(cx);
+        }
+
+        self.set_status(cx, &format!("Synthesizing {}...", preview.voice_name()));
+        // Replacing a running preview stops it
+        self.preview = Some(preview);
+        cx.stop_timer(self.preview_timer);
+        self.preview_timer = cx.start_interval(0.2);
+    }
+
+    fn poll_preview(&mut self, cx: &mut Cx) {
+        let Some(preview) = self.preview.as_ref() else {
+            cx.stop_timer(self.preview_timer);
+            return;
+        };
+        let name = preview.voice_name().to_string();
+        match preview.state() {
+            PreviewState::Synthesizing => {}
+            PreviewState::Playing => self.set_status(cx, &format!("Playing {}", name)),
+            PreviewState::Done => {
+                self.preview = None;
+                cx.stop_timer(self.preview_timer);
+                self.set_status(cx, "");
+            }
+            PreviewState::Failed(e) => {
+                self.preview = None;
+                cx.stop_timer(self.preview_timer);
+                self.set_status(cx, &format!("Preview of {} failed: {}", name, e));
+            }
+        }
+    }
+
+    fn show_sample_text(&mut self, cx: &mut Cx) {
+        let text = self.prefs.sample_text(&self.sample_language);
+        self.view.label(ids!(sample_section.sample_label))
+            .set_text(cx, &format!("Preview sentence ({})", self.sample_language));
+        self.view.text_input(ids!(sample_section.sample_row.sample_input)).set_text(cx, &text);
+        self.view.redraw(cx);
+    }
+
+    fn set_status(&mut self, cx: &mut Cx, text: &str) {
+        self.view.label(ids!(sample_section.status_label)).set_text(cx, text);
+        self.view.redraw(cx);
+    }
+}
+
+impl VoicesViewRef {
+    /// List the voices when the view is first shown
+    pub fn refresh(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if !inner.loaded {
+                inner.load_catalogue(cx);
+            }
+        }
+    }
+
+    pub fn update_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.view.apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+
+            let labels = [
+                ids!(title),
+                ids!(subtitle),
+                ids!(header.name_header),
+                ids!(header.locale_header),
+                ids!(header.gender_header),
+                ids!(header.backend_header),
+                ids!(sample_section.sample_label),
+                ids!(sample_section.status_label),
+            ];
+            for path in labels {
+                inner.view.label(path).apply_over(cx, live!{
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
+
+            let inputs = [
+                ids!(filter_row.filter_input),
+                ids!(sample_section.sample_row.sample_input),
+            ];
+            for path in inputs {
+                inner.view.text_input(path).apply_over(cx, live!{
+                    draw_bg: { dark_mode: (dark_mode) }
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
+
+            let buttons = [
+                ids!(filter_row.refresh_btn),
+                ids!(sample_section.sample_row.save_sample_btn),
+            ];
+            for path in buttons {
+                inner.view.button(path).apply_over(cx, live!{
+                    draw_bg: { dark_mode: (dark_mode) }
+                    draw_text: { dark_mode: (dark_mode) }
+                });
+            }
+
+            // List rows pick this up when they are next drawn
+            inner.dark_mode = dark_mode;
+
+            inner.view.redraw(cx);
+        }
+    }
+}
@@ -1,28 +1,59 @@
 """
 AI Transcriber Backend
 
-Transcribe audio/video files using Whisper and summarize with LLM.
+Transcribe audio/video files using Whisper. Summaries are written by the
+host app (see src/summary.rs) with the provider configured in Settings.
 """
 
 import os
 import json
+import math
 import uuid
+import wave
+import hashlib
 import asyncio
 import tempfile
 import subprocess
 from pathlib import Path
-from typing import Optional, Dict, Any
+from array import array
+from typing import Optional, Dict, Any, List, TypedDict, Callable
 from http.server import HTTPServer, SimpleHTTPRequestHandler
-from urllib.parse import parse_qs, urlparse
+from urllib.parse import parse_qs, unquote, urlparse
 import threading
 
 # Global state for transcription jobs
 jobs: Dict[str, Dict[str, Any]] = {}
 
+# Audio kept per job for the player and re-transcription:
+# {"path": transcribed audio, "hash": sha1 of the upload, "model", "glossary"}
+job_audio: Dict[str, Dict[str, Any]] = {}
+
 # Configuration
 UPLOAD_DIR = Path(tempfile.gettempdir()) / "mofa-transcriber"
 UPLOAD_DIR.mkdir(exist_ok=True)
 
+# Waveform peaks, cached by the upload's content hash so reopening the same
+# recording does not decode it again
+PEAKS_DIR = UPLOAD_DIR / "peaks"
+PEAKS_DIR.mkdir(exist_ok=True)
+PEAKS_PER_SECOND = 20
+
+# Progress of unfinished transcriptions. The host app points this into its
+# data directory so a crash or a reboot does not lose it.
+PARTIAL_DIR = Path(os.environ.get("MOFA_TRANSCRIBER_PARTIAL_DIR") or UPLOAD_DIR / "partial")
+PARTIAL_DIR.mkdir(parents=True, exist_ok=True)
+
+# Files other MoFA apps hand to the Transcriber. The host links them here
+# and the page fetches each once through /api/inbox/<name>.
+INBOX_DIR = UPLOAD_DIR / "inbox"
+INBOX_DIR.mkdir(exist_ok=True)
+
+# Sample rate audio is decoded to for transcription and peaks
+DECODE_RATE = 16000
+
+# Shortest selection worth sending to Whisper
+MIN_SELECTION_SECS = 0.5
+
 # Supported formats
 AUDIO_FORMATS = {'.mp3', '.wav', '.m4a', '.flac', '.ogg', '.wma', '.aac'}
 VIDEO_FORMATS = {'.mp4', '.mkv', '.avi', '.mov', '.webm', '.flv', '.wmv'}
@@ -47,6 +78,106 @@ def extract_audio_from_video(video_path: str, output_path: str) -> bool:
         return False
 
 
+def decode_pcm(audio_path: str, chunk_frames: int = DECODE_RATE * 10, start: float = 0.0):
+    """Yield mono 16-bit samples at DECODE_RATE as arrays, from `start`
+    seconds on.
+
+    16-bit mono WAV at that rate (what extract_audio_from_video writes) is
+    read directly; anything else is decoded through ffmpeg. Either way the
+    audio before `start` is skipped by seeking, not decoded.
+    """
+    try:
+        with wave.open(audio_path, "rb") as wav:
+            if (wav.getsampwidth(), wav.getnchannels(), wav.getframerate()) == (2, 1, DECODE_RATE):
+                first = int(start * DECODE_RATE)
+                if first >= wav.getnframes():
+                    return
+                wav.setpos(first)
+                while True:
+                    frames = wav.readframes(chunk_frames)
+                    if not frames:
+                        return
+                    yield array("h", frames)
+    except (wave.Error, EOFError):
+        pass
+
+    # -ss before -i seeks the input instead of decoding up to `start`
+    cmd = ['ffmpeg', '-v', 'error']
+    if start > 0:
+        cmd += ['-ss', f"{start:.3f}"]
+    cmd += [
+        '-i', audio_path,
+        '-f', 's16le', '-acodec', 'pcm_s16le',
+        '-ar', str(DECODE_RATE), '-ac', '1', 'pipe:1'
+    ]
+    proc = subprocess.Popen(cmd, stdout=subprocess.PIPE, stderr=subprocess.DEVNULL)
+    try:
+        while True:
+            data = proc.stdout.read(chunk_frames * 2)
+            if not data:
+                break
+            yield array("h", data[:len(data) // 2 * 2])
+    finally:
+        proc.stdout.close()
+        proc.wait()
+
+
+def compute_peaks(audio_path: str) -> Dict[str, Any]:
+    """Peak level (0..100) of every 1/PEAKS_PER_SECOND s of the audio."""
+    bucket = DECODE_RATE // PEAKS_PER_SECOND
+    peaks: List[int] = []
+    pending = array("h")
+    total = 0
+    for chunk in decode_pcm(audio_path):
+        total += len(chunk)
+        pending.extend(chunk)
+        full = len(pending) // bucket * bucket
+        for i in range(0, full, bucket):
+            window = pending[i:i + bucket]
+            peaks.append(round(max(max(window), -min(window)) * 100 / 32768))
+        del pending[:full]
+    if pending:
+        peaks.append(round(max(max(pending), -min(pending)) * 100 / 32768))
+    return {"rate": PEAKS_PER_SECOND, "duration": total / DECODE_RATE, "peaks": peaks}
+
+
+def cached_peaks(audio_path: str, content_hash: str) -> Dict[str, Any]:
+    cache = PEAKS_DIR / f"{content_hash}-{PEAKS_PER_SECOND}.json"
+    if cache.exists():
+        try:
+            return json.loads(cache.read_text())
+        except (OSError, ValueError):
+            pass
+    peaks = compute_peaks(audio_path)
+    if peaks["peaks"]:
+        cache.write_text(json.dumps(peaks, separators=(",", ":")))
+    return peaks
+
+
+def cut_audio(audio_path: str, output_path: str, start: float, end: Optional[float]) -> bool:
+    """Write [start, end) of the audio as 16 kHz mono WAV; to the end of the
+    audio if `end` is None. Decoding starts at `start`."""
+    first = int(start * DECODE_RATE)
+    last = math.inf if end is None else int(end * DECODE_RATE)
+    pos = first
+    try:
+        with wave.open(output_path, "wb") as out:
+            out.setnchannels(1)
+            out.setsampwidth(2)
+            out.setframerate(DECODE_RATE)
+            for chunk in decode_pcm(audio_path, start=start):
+                hi = min(last - pos, len(chunk))
+                if hi > 0:
+                    out.writeframes(chunk[:hi].tobytes())
+                pos += len(chunk)
+                if pos >= last:
+                    break
+        return pos > first
+    except Exception as e:
+        print(f"Cut error: {e}")
+        return False
+
+
 def get_whisper_model_dir() -> Optional[str]:
     """Resolve Whisper model directory from env or bundled resources."""
     env_dir = os.environ.get("WHISPER_MODEL_DIR")
@@ -62,8 +193,55 @@ def get_whisper_model_dir() -> Optional[str]:
     return None
 
 
-def transcribe_audio(audio_path: str, model_size: str = "tiny") -> Optional[Dict]:
-    """Transcribe audio using faster-whisper."""
+class TranscriptWord(TypedDict):
+    start: float
+    end: float
+    word: str
+    probability: float
+
+
+class TranscriptSegment(TypedDict):
+    start: float
+    end: float
+    text: str
+    # exp(avg_logprob): 0..1, higher is more certain
+    confidence: float
+    no_speech_prob: float
+    words: List[TranscriptWord]
+
+
+class TranscriptionResult(TypedDict):
+    language: str
+    language_probability: float
+    duration: float
+    text: str
+    segments: List[TranscriptSegment]
+
+
+def segment_confidence(avg_logprob: float) -> float:
+    """Map Whisper's average token log-probability to 0..1."""
+    return round(min(1.0, max(0.0, math.exp(avg_logprob))), 3)
+
+
+def glossary_prompt(terms: List[str]) -> Optional[str]:
+    """Initial prompt that nudges Whisper towards the user's spellings."""
+    terms = [t for t in terms if t]
+    if not terms:
+        return None
+    return "Glossary: " + ", ".join(terms[:50]) + "."
+
+
+def transcribe_audio(
+    audio_path: str,
+    model_size: str = "tiny",
+    glossary: Optional[List[str]] = None,
+    on_segment: Optional[Callable[[TranscriptSegment, Dict[str, Any]], None]] = None
+) -> Optional[TranscriptionResult]:
+    """Transcribe audio using faster-whisper.
+
+    `on_segment` is called with each segment as Whisper finalizes it, and
+    the language and duration of the audio.
+    """
     try:
         from faster_whisper import WhisperModel
 
@@ -74,24 +252,47 @@ def transcribe_audio(audio_path: str, model_size: str = "tiny") -> Optional[Dict
         else:
             model = WhisperModel(model_size, device="cpu", compute_type="int8")
 
-        segments, info = model.transcribe(audio_path, beam_size=5)
+        segments, info = model.transcribe(
+            audio_path,
+            beam_size=5,
+            word_timestamps=True,
+            initial_prompt=glossary_prompt(glossary or [])
+        )
 
-        # Collect segments
+        details = {
+            "language": info.language,
+            "language_probability": info.language_probability,
+            "duration": info.duration,
+        }
+
+        # Collect segments, keeping Whisper's probabilities for review
         result_segments = []
         full_text = []
 
         for segment in segments:
-            result_segments.append({
+            item = {
                 "start": segment.start,
                 "end": segment.end,
-                "text": segment.text
-            })
+                "text": segment.text,
+                "confidence": segment_confidence(segment.avg_logprob),
+                "no_speech_prob": round(segment.no_speech_prob, 3),
+                "words": [
+                    {
+                        "start": w.start,
+                        "end": w.end,
+                        "word": w.word,
+                        "probability": round(w.probability, 3)
+                    }
+                    for w in (segment.words or [])
+                ]
+            }
+            result_segments.append(item)
             full_text.append(segment.text)
+            if on_segment:
+                on_segment(item, details)
 
         return {
-            "language": info.language,
-            "language_probability": info.language_probability,
-            "duration": info.duration,
+            **details,
             "text": " ".join(full_text),
             "segments": result_segments
         }
@@ -109,37 +310,246 @@ def transcribe_audio(audio_path: str, model_size: str = "tiny") -> Optional[Dict
         return None
 
 
-def summarize_text(text: str, api_key: Optional[str] = None) -> Optional[str]:
-    """Summarize text using OpenAI API."""
-    if not api_key:
-        api_key = os.environ.get("OPENAI_API_KEY")
+def clip_segment(segment: TranscriptSegment, start: float, end: float) -> Optional[TranscriptSegment]:
+    """The part of a segment within [start, end), cut at word boundaries.
+
+    Words are kept by their midpoint. Without word timings the text cannot
+    be split, so it stays with the clipped segment. None if nothing is left.
+    """
+    words = segment.get("words") or []
+    if words:
+        words = [w for w in words if start <= (w["start"] + w["end"]) / 2 < end]
+        if not words:
+            return None
+        text = "".join(w["word"] for w in words)
+    else:
+        text = segment["text"]
+    return {
+        **segment,
+        "start": max(segment["start"], start),
+        "end": min(segment["end"], end),
+        "text": text,
+        "words": words,
+    }
+
+
+def splice_segments(
+    segments: List[TranscriptSegment],
+    replacement: List[TranscriptSegment],
+    start: float,
+    end: float
+) -> List[TranscriptSegment]:
+    """Replace what was said in [start, end) with `replacement`.
+
+    Segments inside the region are dropped; neighbours reaching into it are
+    cut back to its edges so timestamps stay in order.
+    """
+    before: List[TranscriptSegment] = []
+    after: List[TranscriptSegment] = []
+    for seg in segments:
+        if seg["end"] <= start:
+            before.append(seg)
+        elif seg["start"] >= end:
+            after.append(seg)
+        else:
+            head = clip_segment(seg, seg["start"], start) if seg["start"] < start else None
+            tail = clip_segment(seg, end, seg["end"]) if seg["end"] > end else None
+            if head:
+                before.append(head)
+            if tail:
+                after.append(tail)
+    inside = [c for c in (clip_segment(s, start, end) for s in replacement) if c]
+    return before + inside + after
+
+
+def offset_segments(segments: List[TranscriptSegment], offset: float) -> List[TranscriptSegment]:
+    """Shift a selection's timestamps to the full recording's timeline."""
+    return [
+        {
+            **seg,
+            "start": seg["start"] + offset,
+            "end": seg["end"] + offset,
+            "words": [
+                {**w, "start": w["start"] + offset, "end": w["end"] + offset}
+                for w in seg.get("words") or []
+            ],
+        }
+        for seg in segments
+    ]
 
-    if not api_key:
-        return "[No API key provided. Set OPENAI_API_KEY or provide in request.]"
 
+def format_clock(seconds: float) -> str:
+    """h:mm:ss, or m:ss under an hour."""
+    mins, secs = divmod(int(seconds), 60)
+    hours, mins = divmod(mins, 60)
+    return f"{hours}:{mins:02d}:{secs:02d}" if hours else f"{mins}:{secs:02d}"
+
+
+def parse_source(data: Any) -> Optional[Dict[str, Any]]:
+    """The page's description of a picked file: name, size and mtime (ms).
+
+    A browser never sees the file's full path, but these three are enough
+    to recognize the same recording being picked again.
+    """
     try:
-        import openai
-        client = openai.OpenAI(api_key=api_key)
+        return {"name": str(data["name"]), "size": int(data["size"]), "mtime": int(data["mtime"])}
+    except (KeyError, TypeError, ValueError):
+        return None
 
-        response = client.chat.completions.create(
-            model="gpt-4o-mini",
-            messages=[
-                {
-                    "role": "system",
-                    "content": "You are a helpful assistant that summarizes transcripts. Provide a clear, structured summary with key points."
-                },
-                {
-                    "role": "user",
-                    "content": f"Please summarize the following transcript:\n\n{text[:8000]}"
-                }
-            ],
-            max_tokens=1000
-        )
-        return response.choices[0].message.content
-    except ImportError:
-        return "[openai package not installed. Please run: pip install openai]"
-    except Exception as e:
-        return f"[Summary error: {e}]"
+
+def source_key(source: Dict[str, Any]) -> str:
+    """Name of the .partial sidecar of a source file."""
+    identity = f"{source['name']}\0{source['size']}\0{source['mtime']}"
+    return hashlib.sha1(identity.encode("utf-8")).hexdigest()[:16]
+
+
+class PartialTranscript:
+    """Segments of an unfinished transcription, saved as they finalize.
+
+    The .partial sidecar holds one JSON record per line: a header with the
+    source and the settings, the language and duration once Whisper reports
+    them, then every finished segment on the source's timeline. Appending
+    keeps each save cheap on long files; a line torn by a crash ends the
+    load, and resuming rewrites the file without it.
+    """
+
+    def __init__(self, key: str, header: Dict[str, Any]):
+        self.key = key
+        self.header = header
+        self.info: Optional[Dict[str, Any]] = None
+        self.segments: List[TranscriptSegment] = []
+
+    @staticmethod
+    def path_for(key: str) -> Path:
+        return PARTIAL_DIR / f"{key}.partial"
+
+    @classmethod
+    def load(cls, key: str) -> Optional["PartialTranscript"]:
+        try:
+            lines = cls.path_for(key).read_text(encoding="utf-8").splitlines()
+        except OSError:
+            return None
+        records = []
+        for line in lines:
+            try:
+                records.append(json.loads(line))
+            except ValueError:
+                break
+        if not records or "header" not in records[0]:
+            return None
+        partial = cls(key, records[0]["header"])
+        for record in records[1:]:
+            if "info" in record:
+                partial.info = record["info"]
+            elif "segment" in record:
+                partial.segments.append(record["segment"])
+        return partial
+
+    @classmethod
+    def create(cls, key: str, header: Dict[str, Any]) -> "PartialTranscript":
+        """Start a new sidecar, replacing an earlier one of the same source."""
+        partial = cls(key, header)
+        partial.compact()
+        return partial
+
+    @property
+    def offset(self) -> float:
+        """Where decoding resumes: the end of the last finished segment."""
+        return self.segments[-1]["end"] if self.segments else 0.0
+
+    def summary(self) -> Dict[str, Any]:
+        return {
+            "offset": self.offset,
+            "duration": (self.info or {}).get("duration"),
+            "segments": len(self.segments),
+            "model": self.header.get("model"),
+        }
+
+    def set_info(self, info: Dict[str, Any]):
+        if self.info is None:
+            self.info = info
+            self._write([{"info": info}], "a")
+
+    def add(self, segment: TranscriptSegment):
+        self.segments.append(segment)
+        self._write([{"segment": segment}], "a")
+
+    def compact(self):
+        """Rewrite the sidecar from what was loaded."""
+        records: List[Dict[str, Any]] = [{"header": self.header}]
+        if self.info is not None:
+            records.append({"info": self.info})
+        records.extend({"segment": seg} for seg in self.segments)
+        self._write(records, "w")
+
+    def discard(self):
+        try:
+            self.path_for(self.key).unlink()
+        except OSError:
+            pass
+
+    def _write(self, records: List[Dict[str, Any]], mode: str):
+        with open(self.path_for(self.key), mode, encoding="utf-8") as f:
+            for record in records:
+                f.write(json.dumps(record, ensure_ascii=False) + "\n")
+            f.flush()
+            # A sleeping or crashing machine keeps what was written
+            os.fsync(f.fileno())
+
+
+def transcribe_resumable(
+    job: Dict[str, Any],
+    audio_path: str,
+    partial: PartialTranscript,
+    model_size: str,
+    glossary: Optional[List[str]] = None
+) -> Optional[TranscriptionResult]:
+    """Transcribe what `partial` lacks and stitch it onto what it has.
+
+    Segments are saved to the sidecar as Whisper finalizes them. A resumed
+    run seeks past the last finished segment and decodes only the rest.
+    """
+    offset = partial.offset
+    duration = (partial.info or {}).get("duration") or 0.0
+    rest_path = audio_path
+    if offset > 0:
+        rest_path = str(UPLOAD_DIR / f"{job['id']}-rest.wav")
+        if not cut_audio(audio_path, rest_path, offset, None):
+            if offset < duration - MIN_SELECTION_SECS:
+                raise RuntimeError("Could not cut the rest of the audio")
+            # The last segment already reached the end
+            rest_path = None
+
+    def on_segment(segment: TranscriptSegment, details: Dict[str, Any]):
+        if offset == 0:
+            partial.set_info(details)
+        partial.add(offset_segments([segment], offset)[0])
+        total = (partial.info or {}).get("duration")
+        if total:
+            job["progress"] = 40 + int(55 * min(1.0, partial.offset / total))
+
+    result: Optional[TranscriptionResult] = None
+    if rest_path:
+        try:
+            result = transcribe_audio(rest_path, model_size, glossary, on_segment)
+        finally:
+            if rest_path != audio_path:
+                remove_temp(rest_path)
+        if not result:
+            return None
+
+    info = partial.info or {
+        "language": result["language"],
+        "language_probability": result["language_probability"],
+        "duration": result["duration"],
+    }
+    segments = partial.segments
+    return {
+        **info,
+        # Without faster-whisper there are no segments, only a notice
+        "text": " ".join(seg["text"] for seg in segments) if segments else result["text"],
+        "segments": segments,
+    }
 
 
 def generate_podcast_script(text: str, api_key: Optional[str] = None, num_hosts: int = 2) -> Optional[Dict]:
@@ -244,33 +654,64 @@ def speak_script_async(segments: list, voice_mapping: Dict[str, str], rate: int
 tts_state = {"speaking": False, "stop_requested": False}
 
 
-def process_job(job_id: str, file_path: str, model_size: str, api_key: Optional[str]):
-    """Process a transcription job in background."""
+def process_job(
+    job_id: str,
+    file_path: str,
+    model_size: str,
+    glossary: Optional[List[str]] = None,
+    source: Optional[Dict[str, Any]] = None,
+    resume_from: Optional[PartialTranscript] = None
+):
+    """Process a transcription job in background.
+
+    With a `source`, progress is saved to its .partial sidecar; with
+    `resume_from` as well, the job continues where that sidecar stopped.
+    The sidecar is removed once the transcription completes.
+    """
     job = jobs[job_id]
+    wav_path = str(UPLOAD_DIR / f"{job_id}.wav")
 
     try:
         job["status"] = "processing"
         job["progress"] = 10
 
-        # Check if video needs audio extraction
+        # Decode to 16 kHz mono WAV, which the player's peaks and
+        # re-transcribed selections are read from
         ext = Path(file_path).suffix.lower()
         audio_path = file_path
 
         if ext in VIDEO_FORMATS:
             job["stage"] = "Extracting audio from video..."
             job["progress"] = 20
-            audio_path = str(UPLOAD_DIR / f"{job_id}.wav")
 
-            if not extract_audio_from_video(file_path, audio_path):
+            if not extract_audio_from_video(file_path, wav_path):
                 job["status"] = "error"
                 job["error"] = "Failed to extract audio from video"
                 return
+            audio_path = wav_path
+        elif extract_audio_from_video(file_path, wav_path):
+            audio_path = wav_path
 
         # Transcribe
         job["stage"] = "Transcribing audio..."
         job["progress"] = 40
 
-        result = transcribe_audio(audio_path, model_size)
+        partial = None
+        if resume_from:
+            partial = resume_from
+            partial.compact()
+            job["stage"] = f"Resuming transcription at {format_clock(partial.offset)}..."
+        elif source:
+            partial = PartialTranscript.create(source_key(source), {
+                "source": source,
+                "model": model_size,
+                "glossary": glossary or [],
+            })
+
+        if partial:
+            result = transcribe_resumable(job, audio_path, partial, model_size, glossary)
+        else:
+            result = transcribe_audio(audio_path, model_size, glossary)
 
         if not result:
             job["status"] = "error"
@@ -278,29 +719,83 @@ def process_job(job_id: str, file_path: str, model_size: str, api_key: Optional[
             return
 
         job["transcription"] = result
-        job["progress"] = 70
-
-        # Summarize
-        job["stage"] = "Generating summary..."
-        job["progress"] = 80
-
-        summary = summarize_text(result["text"], api_key)
-        job["summary"] = summary
         job["progress"] = 100
         job["status"] = "completed"
         job["stage"] = "Done"
+        if partial:
+            partial.discard()
 
-        # Cleanup temp files
-        try:
-            if audio_path != file_path:
-                os.remove(audio_path)
-            os.remove(file_path)
-        except:
-            pass
+        # Keep the decoded audio for the player; the upload is not needed
+        job_audio[job_id]["path"] = audio_path
+        if audio_path != file_path:
+            remove_temp(file_path)
 
     except Exception as e:
         job["status"] = "error"
         job["error"] = str(e)
+    finally:
+        if job["status"] != "completed":
+            # Nothing to play or re-transcribe: drop the upload and its
+            # decoded copy (a resumed run uploads the file again)
+            job_audio.pop(job_id, None)
+            remove_temp(file_path)
+            remove_temp(wav_path)
+
+
+def release_job_audio(job_id: str):
+    """Delete the decoded audio kept for a finished job's player."""
+    audio = job_audio.pop(job_id, None)
+    if audio and audio.get("path"):
+        remove_temp(audio["path"])
+
+
+def release_finished_audio():
+    """Release the audio of every finished job; the page shows one
+    transcription at a time, so a new upload means they are done with."""
+    for job_id in list(job_audio):
+        job = jobs.get(job_id) or {}
+        retranscribing = (job.get("retranscribe") or {}).get("status") == "running"
+        if job.get("status") == "completed" and not retranscribing:
+            release_job_audio(job_id)
+
+
+def remove_temp(path: str):
+    try:
+        os.remove(path)
+    except OSError:
+        pass
+
+
+def process_retranscribe(job_id: str, start: float, end: float):
+    """Re-run [start, end) through Whisper and splice it into the job."""
+    job = jobs[job_id]
+    audio = job_audio[job_id]
+    state = job["retranscribe"]
+    clip_path = str(UPLOAD_DIR / f"{job_id}-selection.wav")
+
+    try:
+        if not cut_audio(audio["path"], clip_path, start, end):
+            raise RuntimeError("Could not cut the selection from the audio")
+
+        result = transcribe_audio(clip_path, audio["model"], audio["glossary"])
+        if not result:
+            raise RuntimeError("Transcription failed")
+
+        transcription = job["transcription"]
+        segments = splice_segments(
+            transcription["segments"],
+            offset_segments(result["segments"], start),
+            start,
+            end
+        )
+        transcription["segments"] = segments
+        transcription["text"] = " ".join(seg["text"] for seg in segments)
+        state["status"] = "completed"
+    except Exception as e:
+        state["status"] = "error"
+        state["error"] = str(e)
+    finally:
+        remove_temp(clip_path)
 
 
 class TranscriberHandler(SimpleHTTPRequestHandler):
@@ -328,6 +823,31 @@ class TranscriberHandler(SimpleHTTPRequestHandler):
             # List all jobs
             self._json_response(200, {"jobs": list(jobs.keys())})
 
+        elif parsed.path == "/api/partial":
+            # Unfinished transcription of the file the page describes
+            params = {k: v[0] for k, v in parse_qs(parsed.query).items()}
+            source = parse_source(params)
+            partial = PartialTranscript.load(source_key(source)) if source else None
+            if partial and partial.segments:
+                self._json_response(200, partial.summary())
+            else:
+                self._json_response(404, {"error": "Nothing to resume"})
+
+        elif parsed.path == "/api/peaks":
+            params = parse_qs(parsed.query)
+            job_id = params.get("id", [None])[0]
+            audio = job_audio.get(job_id)
+            if not audio or not audio.get("path"):
+                self._json_response(404, {"error": "No audio for this job"})
+                return
+            try:
+                self._json_response(200, cached_peaks(audio["path"], audio["hash"]))
+            except Exception as e:
+                self._json_response(500, {"error": f"Could not read audio: {e}"})
+
+        elif parsed.path.startswith("/api/inbox/"):
+            self._serve_inbox_file(unquote(parsed.path[len("/api/inbox/"):]))
+
         else:
             # Serve static files
             super().do_GET()
@@ -344,6 +864,10 @@ class TranscriberHandler(SimpleHTTPRequestHandler):
             self._handle_speak()
         elif parsed.path == "/api/stop-speak":
             self._handle_stop_speak()
+        elif parsed.path == "/api/retranscribe":
+            self._handle_retranscribe()
+        elif parsed.path == "/api/partial/discard":
+            self._handle_discard_partial()
         else:
             self._json_response(404, {"error": "Not found"})
 
@@ -430,6 +954,56 @@ class TranscriberHandler(SimpleHTTPRequestHandler):
         except Exception as e:
             self._json_response(500, {"error": str(e)})
 
+    def _handle_retranscribe(self):
+        """Start re-transcribing a selection of a finished job."""
+        content_length = int(self.headers.get("Content-Length", 0))
+        body = self.rfile.read(content_length)
+
+        try:
+            data = json.loads(body.decode("utf-8"))
+            job_id = data.get("id")
+            start = float(data.get("start", 0))
+            end = float(data.get("end", 0))
+        except (json.JSONDecodeError, TypeError, ValueError):
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        job = jobs.get(job_id)
+        audio = job_audio.get(job_id)
+        if not job or job["status"] != "completed" or not audio or not audio.get("path"):
+            self._json_response(404, {"error": "No finished transcription with audio for this job"})
+            return
+        if (job.get("retranscribe") or {}).get("status") == "running":
+            self._json_response(409, {"error": "A selection is already being re-transcribed"})
+            return
+
+        duration = job["transcription"].get("duration") or end
+        start, end = max(0.0, start), min(end, duration)
+        if end - start < MIN_SELECTION_SECS:
+            self._json_response(400, {"error": f"Select at least {MIN_SELECTION_SECS} s of audio"})
+            return
+
+        job["retranscribe"] = {"status": "running", "start": start, "end": end, "error": None}
+        thread = threading.Thread(target=process_retranscribe, args=(job_id, start, end))
+        thread.start()
+        self._json_response(200, {"status": "running", "start": start, "end": end})
+
+    def _handle_discard_partial(self):
+        """Delete the saved progress of a file the user wants to start over."""
+        content_length = int(self.headers.get("Content-Length", 0))
+        body = self.rfile.read(content_length)
+
+        try:
+            source = parse_source(json.loads(body.decode("utf-8")))
+        except json.JSONDecodeError:
+            source = None
+        if not source:
+            self._json_response(400, {"error": "Invalid JSON"})
+            return
+
+        PartialTranscript(source_key(source), {}).discard()
+        self._json_response(200, {"status": "discarded"})
+
     def _handle_stop_speak(self):
         """Stop current TTS."""
         tts_state["stop_requested"] = True
@@ -469,7 +1043,9 @@ class TranscriberHandler(SimpleHTTPRequestHandler):
             file_data = None
             filename = "upload"
             model_size = "tiny"
-            api_key = None
+            glossary = []
+            source = None
+            resume = False
 
             for part in parts:
                 if b"Content-Disposition" not in part:
@@ -503,8 +1079,15 @@ class TranscriberHandler(SimpleHTTPRequestHandler):
                                 filename = line[start:end]
                 elif 'name="model"' in header_text:
                     model_size = content.decode("utf-8").strip()
-                elif 'name="api_key"' in header_text:
-                    api_key = content.decode("utf-8").strip() or None
+                elif 'name="glossary"' in header_text:
+                    glossary = [t.strip() for t in content.decode("utf-8").split(",") if t.strip()]
+                elif 'name="source"' in header_text:
+                    try:
+                        source = parse_source(json.loads(content.decode("utf-8")))
+                    except ValueError:
+                        source = None
+                elif 'name="resume"' in header_text:
+                    resume = content.decode("utf-8").strip() == "1"
 
             if not file_data:
                 self._json_response(400, {"error": "No file provided"})
@@ -520,6 +1103,16 @@ class TranscriberHandler(SimpleHTTPRequestHandler):
                 })
                 return
 
+            # Finish an interrupted run with the settings it started with
+            resume_from = PartialTranscript.load(source_key(source)) if resume and source else None
+            if resume_from and resume_from.segments:
+                model_size = resume_from.header.get("model") or model_size
+                glossary = resume_from.header.get("glossary") or glossary
+            else:
+                resume_from = None
+
+            release_finished_audio()
+
             # Save file
             job_id = str(uuid.uuid4())[:8]
             file_path = UPLOAD_DIR / f"{job_id}{ext}"
@@ -527,6 +1120,13 @@ class TranscriberHandler(SimpleHTTPRequestHandler):
             with open(file_path, "wb") as f:
                 f.write(file_data)
 
+            job_audio[job_id] = {
+                "path": None,
+                "hash": hashlib.sha1(file_data).hexdigest(),
+                "model": model_size,
+                "glossary": glossary,
+            }
+
             # Create job
             jobs[job_id] = {
                 "id": job_id,
@@ -535,14 +1135,14 @@ class TranscriberHandler(SimpleHTTPRequestHandler):
                 "progress": 0,
                 "stage": "Queued",
                 "transcription": None,
-                "summary": None,
-                "error": None
+                "error": None,
+                "retranscribe": None
             }
 
             # Start processing in background
             thread = threading.Thread(
                 target=process_job,
-                args=(job_id, str(file_path), model_size, api_key)
+                args=(job_id, str(file_path), model_size, glossary, source, resume_from)
             )
             thread.start()
 
@@ -550,6 +1150,20 @@ class TranscriberHandler(SimpleHTTPRequestHandler):
         else:
             self._json_response(400, {"error": "Expected multipart/form-data"})
 
+    def _serve_inbox_file(self, name: str):
+        """Send a handed-off file once, then remove it from the inbox."""
+        path = INBOX_DIR / name
+        if not name or Path(name).name != name or not path.is_file():
+            self._json_response(404, {"error": "File not found"})
+            return
+        data = path.read_bytes()
+        self.send_response(200)
+        self.send_header("Content-Type", "application/octet-stream")
+        self.send_header("Content-Length", str(len(data)))
+        self.end_headers()
+        self.wfile.write(data)
+        path.unlink(missing_ok=True)
+
     def _json_response(self, status: int, data: dict):
         """Send JSON response."""
         body = json.dumps(data, ensure_ascii=False).encode("utf-8")
@@ -0,0 +1,195 @@
+//! Glossary and correction tracking
+//!
+//! Corrections made in the page's review mode are reported here. Words the
+//! user keeps typing in place of what Whisper heard are counted, and once a
+//! word has been corrected [`SUGGEST_AFTER`] times it is offered for the
+//! glossary. Glossary terms are sent with each transcription as Whisper's
+//! initial prompt, so the same mistake becomes less likely next time.
+//!
+//! The glossary lives under the `glossary` key of the Transcriber config.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Corrections of the same word before it is suggested for the glossary
+pub const SUGGEST_AFTER: u32 = 3;
+
+/// Longest token counted as a correction; longer runs are rewrites, not terms
+const MAX_TERM_CHARS: usize = 32;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Glossary {
+    pub terms: Vec<String>,
+    /// Corrected word -> times it was typed in a correction
+    pub corrections: HashMap<String, u32>,
+    /// Suggestions the user turned down
+    pub dismissed: Vec<String>,
+}
+
+/// A word worth adding to the glossary
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Suggestion {
+    pub term: String,
+    pub count: u32,
+}
+
+impl Glossary {
+    pub fn from_config(config: &serde_json::Value) -> Self {
+        config
+            .get("glossary")
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// Count the words introduced by a correction; returns them
+    pub fn record_correction(&mut self, raw: &str, corrected: &str) -> Vec<String> {
+        let terms = corrected_terms(raw, corrected);
+        for term in &terms {
+            *self.corrections.entry(term.clone()).or_insert(0) += 1;
+        }
+        terms
+    }
+
+    pub fn add_term(&mut self, term: &str) {
+        let term = term.trim();
+        if !term.is_empty() && !self.contains(term) {
+            self.terms.push(term.to_string());
+        }
+    }
+
+    pub fn remove_term(&mut self, term: &str) {
+        self.terms.retain(|t| !t.eq_ignore_ascii_case(term));
+    }
+
+    pub fn dismiss(&mut self, term: &str) {
+        if !self.dismissed.iter().any(|d| d.eq_ignore_ascii_case(term)) {
+            self.dismissed.push(term.to_string());
+        }
+    }
+
+    pub fn contains(&self, term: &str) -> bool {
+        self.terms.iter().any(|t| t.eq_ignore_ascii_case(term))
+    }
+
+    /// Frequently corrected words not yet in the glossary, most frequent first
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        let mut suggestions: Vec<Suggestion> = self
+            .corrections
+            .iter()
+            .filter(|(term, count)| {
+                **count >= SUGGEST_AFTER
+                    && !self.contains(term)
+                    && !self.dismissed.iter().any(|d| d.eq_ignore_ascii_case(term))
+            })
+            .map(|(term, count)| Suggestion { term: term.clone(), count: *count })
+            .collect();
+        suggestions.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.term.cmp(&b.term)));
+        suggestions
+    }
+}
+
+/// Words in `corrected` that were not in `raw`
+///
+/// Compared as a multiset of whitespace-separated words with surrounding
+/// punctuation stripped, so re-punctuating or reordering counts for nothing.
+/// Case matters ("mofa" -> "MoFA" is a correction) except for the first
+/// letter, which sentence capitalization changes. Single characters and
+/// numbers are ignored.
+pub fn corrected_terms(raw: &str, corrected: &str) -> Vec<String> {
+    let mut remaining: HashMap<String, usize> = HashMap::new();
+    for word in words(raw) {
+        *remaining.entry(match_key(&word)).or_insert(0) += 1;
+    }
+
+    let mut terms = Vec::new();
+    for word in words(corrected) {
+        match remaining.get_mut(&match_key(&word)) {
+            Some(n) if *n > 0 => *n -= 1,
+            _ => {
+                let chars = word.chars().count();
+                let numeric = word.chars().all(|c| c.is_ascii_digit() || c == '.' || c == ',');
+                if chars > 1 && chars <= MAX_TERM_CHARS && !numeric && !terms.contains(&word) {
+                    terms.push(word);
+                }
+            }
+        }
+    }
+    terms
+}
+
+fn match_key(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && c != '-' && c != '\''))
+        .filter(|w| !w.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corrected_terms() {
+        assert_eq!(
+            corrected_terms("welcome to mafia studio", "Welcome to MoFA Studio."),
+            vec!["MoFA"]
+        );
+        // Punctuation, sentence capitalization and numbers are not corrections
+        assert!(corrected_terms("its 10 am", "Its 10 am!").is_empty());
+        assert_eq!(corrected_terms("the mofa app", "The MoFA app"), vec!["MoFA"]);
+        assert_eq!(corrected_terms("a b", "a b 42 x Kokoro"), vec!["Kokoro"]);
+        // Repeated words only count once per correction
+        assert_eq!(corrected_terms("dora dora", "Dora Dora MoFA MoFA"), vec!["MoFA"]);
+    }
+
+    #[test]
+    fn test_suggestions_after_threshold() {
+        let mut glossary = Glossary::default();
+        for _ in 0..SUGGEST_AFTER - 1 {
+            glossary.record_correction("mofa", "MoFA");
+        }
+        assert!(glossary.suggestions().is_empty());
+
+        glossary.record_correction("the mofa app", "the MoFA app");
+        assert_eq!(
+            glossary.suggestions(),
+            vec![Suggestion { term: "MoFA".to_string(), count: SUGGEST_AFTER }]
+        );
+
+        glossary.add_term("mofa");
+        assert!(glossary.suggestions().is_empty());
+        assert_eq!(glossary.terms, vec!["mofa"]);
+
+        glossary.remove_term("MOFA");
+        for _ in 0..SUGGEST_AFTER {
+            glossary.record_correction("dura", "Dora");
+        }
+        glossary.dismiss("dora");
+        assert_eq!(glossary.suggestions().len(), 1);
+        assert_eq!(glossary.suggestions()[0].term, "MoFA");
+    }
+
+    #[test]
+    fn test_config_round_trip() {
+        let mut glossary = Glossary::default();
+        glossary.add_term("Makepad");
+        glossary.record_correction("make pad", "Makepad");
+
+        let config = serde_json::json!({ "python_path": "python3", "glossary": glossary.to_value() });
+        assert_eq!(Glossary::from_config(&config), glossary);
+        assert_eq!(Glossary::from_config(&serde_json::json!({})), Glossary::default());
+    }
+}
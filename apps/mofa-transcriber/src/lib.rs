@@ -2,10 +2,21 @@
 //!
 //! AI-powered audio/video transcription and summarization
 
+pub mod glossary;
+pub mod punctuation;
 pub mod screen;
+pub mod summary;
 
 use makepad_widgets::*;
 use mofa_widgets::{AppInfo, MofaApp};
+use mofa_widgets::app_storage::AppStorage;
+
+/// Data this app keeps under `~/.mofa-studio`
+pub const STORAGE: AppStorage = AppStorage {
+    prefs: &[screen::CONFIG_FILE],
+    cache: &[screen::PARTIAL_DIR],
+    webview_profile: true,
+};
 
 pub struct MoFaTranscriberApp;
 
@@ -18,6 +29,7 @@ impl MofaApp for MoFaTranscriberApp {
             tab_id: Some(live_id!(transcriber_tab)),
             page_id: Some(live_id!(transcriber_page)),
             show_in_sidebar: true,
+            storage: STORAGE,
             ..Default::default()
         }
     }
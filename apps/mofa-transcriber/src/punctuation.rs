@@ -0,0 +1,317 @@
+//! Punctuation and casing restoration
+//!
+//! Some local backends emit lowercase, unpunctuated text. This stage runs
+//! after transcription and before export. It works on the raw segments, so
+//! the original text is never lost and toggling it off needs no re-run.
+
+/// A post-processing stage that restores punctuation and casing.
+///
+/// `restore` receives one transcript segment at a time. Implementations
+/// should be idempotent: text that is already punctuated should come back
+/// unchanged.
+pub trait PunctuationRestorer: Send + Sync {
+    /// Short identifier, reported back to the page
+    fn name(&self) -> &'static str;
+
+    /// Restore punctuation and casing for a single segment
+    fn restore(&self, text: &str) -> String;
+
+    /// Separator used when joining restored segments into full text
+    fn joiner(&self) -> &'static str {
+        " "
+    }
+}
+
+/// Returns segments untouched (backends that already punctuate)
+pub struct PassThroughRestorer;
+
+impl PunctuationRestorer for PassThroughRestorer {
+    fn name(&self) -> &'static str {
+        "pass-through"
+    }
+
+    fn restore(&self, text: &str) -> String {
+        text.trim().to_string()
+    }
+}
+
+/// Rule-based restorer for English
+///
+/// Splits sentences at segment ends and at common spoken restarts
+/// ("okay so we...", "so today..."), capitalizes sentence starts and the
+/// pronoun "I", commas leading interjections and marks questions.
+pub struct EnglishRuleRestorer;
+
+/// Words that open a question when they start a sentence
+const QUESTION_OPENERS: &[&str] = &[
+    "what", "why", "how", "who", "whom", "whose", "where", "when", "which",
+    "is", "are", "am", "was", "were", "do", "does", "did", "can", "could",
+    "would", "will", "should", "shall", "have", "has", "may",
+];
+
+/// Interjections followed by a comma at the start of a sentence
+const INTERJECTIONS: &[&str] = &["okay", "ok", "yeah", "yes", "oh", "alright", "anyway", "hi", "hello"];
+
+/// Spoken restarts that begin a new sentence mid-segment
+const RESTART_WORDS: &[&str] = &["so", "okay", "now", "anyway", "alright"];
+
+/// Words that confirm a restart when they follow a restart word
+const RESTART_FOLLOWERS: &[&str] = &[
+    "i", "we", "you", "they", "let's", "lets", "today", "this", "that's", "here's", "now", "so",
+];
+
+/// Minimum words in the current sentence before a restart can split it
+const MIN_WORDS_BEFORE_RESTART: usize = 3;
+
+impl EnglishRuleRestorer {
+    fn split_sentences<'a>(&self, words: &[&'a str]) -> Vec<Vec<&'a str>> {
+        let mut sentences: Vec<Vec<&str>> = Vec::new();
+        let mut current: Vec<&str> = Vec::new();
+
+        for (i, word) in words.iter().enumerate() {
+            let restart = !current.is_empty()
+                && current.len() >= MIN_WORDS_BEFORE_RESTART
+                && RESTART_WORDS.contains(&normalized(word).as_str())
+                && !ends_with_any(current.last().unwrap(), &[','])
+                && words
+                    .get(i + 1)
+                    .is_some_and(|next| RESTART_FOLLOWERS.contains(&normalized(next).as_str()));
+
+            if restart {
+                sentences.push(std::mem::take(&mut current));
+            }
+
+            current.push(word);
+
+            if ends_with_any(word, &['.', '?', '!']) {
+                sentences.push(std::mem::take(&mut current));
+            }
+        }
+
+        if !current.is_empty() {
+            sentences.push(current);
+        }
+        sentences
+    }
+
+    fn finish_sentence(&self, words: &[&str]) -> String {
+        let mut out: Vec<String> = words.iter().map(|w| capitalize_pronoun(w)).collect();
+
+        if out.len() > 1 {
+            let first = normalized(&out[0]);
+            if INTERJECTIONS.contains(&first.as_str()) && !ends_with_any(&out[0], &[',', '.', '?', '!']) {
+                out[0].push(',');
+            }
+        }
+
+        if let Some(first) = out.first_mut() {
+            *first = capitalize_first(first);
+        }
+
+        if let Some(last) = out.last_mut() {
+            if !ends_with_any(last, &['.', '?', '!', '…']) {
+                let trimmed = last.trim_end_matches([',', ';', ':']).to_string();
+                let opener = normalized(words[0]);
+                let mark = if QUESTION_OPENERS.contains(&opener.as_str()) { '?' } else { '.' };
+                *last = format!("{}{}", trimmed, mark);
+            }
+        }
+
+        out.join(" ")
+    }
+}
+
+impl PunctuationRestorer for EnglishRuleRestorer {
+    fn name(&self) -> &'static str {
+        "english-rules"
+    }
+
+    fn restore(&self, text: &str) -> String {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        if words.is_empty() {
+            return String::new();
+        }
+
+        self.split_sentences(&words)
+            .iter()
+            .map(|sentence| self.finish_sentence(sentence))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Rule-based restorer for Chinese
+///
+/// Only terminates segments; FunASR-style backends punctuate inline already,
+/// so by default Chinese uses [`PassThroughRestorer`] instead.
+pub struct ChineseRuleRestorer;
+
+impl PunctuationRestorer for ChineseRuleRestorer {
+    fn name(&self) -> &'static str {
+        "chinese-rules"
+    }
+
+    fn restore(&self, text: &str) -> String {
+        let text = text.trim();
+        if text.is_empty() || ends_with_any(text, &['。', '？', '！', '.', '?', '!', '…']) {
+            return text.to_string();
+        }
+        let text = text.trim_end_matches(['，', ',', '、', '；']);
+        if text.ends_with('吗') || text.ends_with('呢') {
+            format!("{}？", text)
+        } else {
+            format!("{}。", text)
+        }
+    }
+
+    fn joiner(&self) -> &'static str {
+        ""
+    }
+}
+
+/// Per-language restoration settings (stored in transcriber.json)
+#[derive(Clone, Debug, Default)]
+pub struct PunctuationConfig {
+    /// Apply rules to Chinese instead of passing backend output through
+    pub restore_chinese: bool,
+}
+
+/// Pick the restorer for a detected language code
+pub fn restorer_for_language(language: &str, config: &PunctuationConfig) -> Box<dyn PunctuationRestorer> {
+    let lang = language.to_lowercase();
+    if lang == "en" || lang.starts_with("en-") {
+        Box::new(EnglishRuleRestorer)
+    } else if (lang == "zh" || lang.starts_with("zh-") || lang == "yue") && config.restore_chinese {
+        Box::new(ChineseRuleRestorer)
+    } else {
+        Box::new(PassThroughRestorer)
+    }
+}
+
+/// Segments and joined text after restoration
+#[derive(Clone, Debug, Default)]
+pub struct RestoredTranscript {
+    pub segments: Vec<String>,
+    pub text: String,
+}
+
+/// Run a restorer over every segment of a transcript
+pub fn restore_segments(restorer: &dyn PunctuationRestorer, segments: &[String]) -> RestoredTranscript {
+    let segments: Vec<String> = segments.iter().map(|s| restorer.restore(s)).collect();
+    let text = segments
+        .iter()
+        .filter(|s| !s.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(restorer.joiner());
+    RestoredTranscript { segments, text }
+}
+
+fn normalized(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'').to_lowercase()
+}
+
+fn ends_with_any(word: &str, marks: &[char]) -> bool {
+    word.chars().last().is_some_and(|c| marks.contains(&c))
+}
+
+fn capitalize_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn capitalize_pronoun(word: &str) -> String {
+    match normalized(word).as_str() {
+        "i" | "i'm" | "i've" | "i'll" | "i'd" => {
+            if word.starts_with('i') {
+                capitalize_first(word)
+            } else {
+                word.to_string()
+            }
+        }
+        _ => word.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (raw backend output, expected restored text)
+    const ENGLISH_FIXTURES: &[(&str, &str)] = &[
+        ("hello everyone", "Hello, everyone."),
+        ("i think this works", "I think this works."),
+        ("what do you think", "What do you think?"),
+        (
+            "thanks for joining okay so today we talk about rust",
+            "Thanks for joining. Okay, so today we talk about rust.",
+        ),
+        (
+            "that was the first part so let's move on",
+            "That was the first part. So let's move on.",
+        ),
+        ("i'm sure i'll be there", "I'm sure I'll be there."),
+        ("is it ready yet", "Is it ready yet?"),
+        ("we stopped, so we could rest", "We stopped, so we could rest."),
+        ("  lots   of    spaces  ", "Lots of spaces."),
+        ("Already punctuated. Nothing to do!", "Already punctuated. Nothing to do!"),
+        ("it ended with a comma,", "It ended with a comma."),
+    ];
+
+    const CHINESE_FIXTURES: &[(&str, &str)] = &[
+        ("今天我们聊聊播客", "今天我们聊聊播客。"),
+        ("你准备好了吗", "你准备好了吗？"),
+        ("已经有标点了。", "已经有标点了。"),
+        ("结尾是逗号，", "结尾是逗号。"),
+    ];
+
+    #[test]
+    fn test_english_fixtures() {
+        let restorer = EnglishRuleRestorer;
+        for (input, expected) in ENGLISH_FIXTURES {
+            assert_eq!(&restorer.restore(input), expected, "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_english_is_idempotent() {
+        let restorer = EnglishRuleRestorer;
+        for (input, _) in ENGLISH_FIXTURES {
+            let once = restorer.restore(input);
+            assert_eq!(restorer.restore(&once), once);
+        }
+    }
+
+    #[test]
+    fn test_chinese_fixtures() {
+        let restorer = ChineseRuleRestorer;
+        for (input, expected) in CHINESE_FIXTURES {
+            assert_eq!(&restorer.restore(input), expected, "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_chinese_passes_through_by_default() {
+        let restorer = restorer_for_language("zh", &PunctuationConfig::default());
+        assert_eq!(restorer.name(), "pass-through");
+        assert_eq!(restorer.restore("今天我们聊聊播客"), "今天我们聊聊播客");
+
+        let config = PunctuationConfig { restore_chinese: true };
+        assert_eq!(restorer_for_language("zh", &config).name(), "chinese-rules");
+    }
+
+    #[test]
+    fn test_restore_segments_keeps_segment_boundaries() {
+        let segments = vec![
+            " welcome back".to_string(),
+            " how are you doing".to_string(),
+            "".to_string(),
+        ];
+        let restored = restore_segments(&EnglishRuleRestorer, &segments);
+        assert_eq!(restored.segments, vec!["Welcome back.", "How are you doing?", ""]);
+        assert_eq!(restored.text, "Welcome back. How are you doing?");
+    }
+}
@@ -2,8 +2,17 @@
 //!
 //! WebView-based AI audio/video transcription
 
+use crate::glossary::Glossary;
+use crate::punctuation::{restore_segments, restorer_for_language, PunctuationConfig};
+use crate::summary::{
+    ProviderChat, SummaryConfig, SummaryError, SummaryJob, SummaryPoll, SummaryRun, SummaryTemplate,
+};
 use makepad_widgets::*;
+use mofa_settings::data::Preferences;
+use mofa_widgets::activity::{ActivityHandle, ActivityKind, ActivityRegistry};
+use mofa_widgets::handoff::{self, HandoffBus};
 use mofa_widgets::webview::{WebViewAction, WebViewContainerWidgetExt};
+use std::collections::HashMap;
 use std::net::TcpListener;
 use std::process::{Child, Command, Stdio};
 use std::sync::{Arc, Mutex};
@@ -226,6 +235,7 @@ live_design! {
                     webview = <WebViewContainer> {
                         width: Fill, height: Fill
                         url: "about:blank"
+                        profile: "mofa-transcriber"
                     }
                 }
             }
@@ -400,12 +410,16 @@ fn get_python_path() -> Option<PathBuf> {
     None
 }
 
+/// Preferences file, relative to the MoFA Studio data directory
+pub(crate) const CONFIG_FILE: &str = "transcriber.json";
+
+/// Saved progress of interrupted transcriptions (`.partial` sidecars),
+/// relative to the MoFA Studio data directory
+pub(crate) const PARTIAL_DIR: &str = "transcriber/partial";
+
 /// Get config file path
 fn get_config_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".mofa-studio")
-        .join("transcriber.json")
+    mofa_widgets::app_storage::data_path(CONFIG_FILE)
 }
 
 fn find_embedded_python_cmd() -> Option<String> {
@@ -455,25 +469,68 @@ fn load_python_config() -> String {
     }
 }
 
-/// Save Python path to config
-fn save_python_config(python_path: &str) -> Result<(), String> {
+/// Read the whole config file, or an empty object
+fn read_config() -> serde_json::Value {
+    fs::read_to_string(get_config_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .filter(|value| value.is_object())
+        .unwrap_or_else(|| serde_json::json!({}))
+}
+
+/// Set one key in the config file, keeping the others
+fn write_config_value(key: &str, value: serde_json::Value) -> Result<(), String> {
     let config_path = get_config_path();
 
     if let Some(parent) = config_path.parent() {
         fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
 
-    let json = serde_json::json!({
-        "python_path": python_path
-    });
+    let mut json = read_config();
+    json[key] = value;
 
     let mut file = fs::File::create(&config_path).map_err(|e| e.to_string())?;
     file.write_all(json.to_string().as_bytes()).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Save Python path to config
+fn save_python_config(python_path: &str) -> Result<(), String> {
+    // Keep other settings (e.g. punctuation) that live in the same file
+    write_config_value("python_path", serde_json::Value::String(python_path.to_string()))?;
 
     ::log::info!("Saved Transcriber config: {}", python_path);
     Ok(())
 }
 
+/// Parse an IPC payload; the page may send it pre-stringified
+fn parse_ipc_payload(data: &str) -> Option<serde_json::Value> {
+    let parsed = match serde_json::from_str(data) {
+        Ok(serde_json::Value::String(inner)) => serde_json::from_str(&inner),
+        other => other,
+    };
+    match parsed {
+        Ok(value) => Some(value),
+        Err(e) => {
+            ::log::warn!("Invalid IPC payload: {}", e);
+            None
+        }
+    }
+}
+
+/// Load punctuation settings from config
+fn load_punctuation_config() -> PunctuationConfig {
+    let mut config = PunctuationConfig::default();
+    if let Ok(content) = fs::read_to_string(get_config_path()) {
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
+            if let Some(restore) = json.get("punctuate_chinese").and_then(|v| v.as_bool()) {
+                config.restore_chinese = restore;
+            }
+        }
+    }
+    config
+}
+
 /// Python server manager
 struct PythonServer {
     process: Option<Child>,
@@ -515,6 +572,7 @@ impl PythonServer {
         let child = Command::new(&self.python_cmd)
             .current_dir(&python_path)
             .args(["app.py", &port.to_string()])
+            .env("MOFA_TRANSCRIBER_PARTIAL_DIR", mofa_widgets::app_storage::data_path(PARTIAL_DIR))
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
             .spawn()
@@ -553,6 +611,10 @@ pub struct TranscriberScreen {
     #[rust]
     server: Arc<Mutex<PythonServer>>,
 
+    /// Lists the server in the shell's running panel while it runs
+    #[rust]
+    server_activity: Option<ActivityHandle>,
+
     #[rust]
     url_loaded: bool,
 
@@ -561,17 +623,50 @@ pub struct TranscriberScreen {
 
     #[rust]
     config_initialized: bool,
+
+    /// Loaded on first use from the config file
+    #[rust]
+    glossary: Option<Glossary>,
+
+    /// The summary being generated, with its job id
+    #[rust]
+    summary_job: Option<(String, SummaryJob)>,
+
+    /// Failed runs by job id and template, resumed when asked again
+    #[rust]
+    summary_runs: HashMap<(String, SummaryTemplate), SummaryRun>,
+
+    #[rust]
+    summary_timer: Timer,
+
+    /// Whether the loaded page listens for handed-off files
+    #[rust]
+    handoff_ready: bool,
+
+    /// Numbers inbox copies so files with the same name do not collide
+    #[rust]
+    handoff_count: u64,
 }
 
 impl Widget for TranscriberScreen {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
 
+        // Stop requests from the shell's running panel
+        if self.server_activity.as_ref().is_some_and(|a| a.stop_requested()) {
+            self.start_server(cx);
+        }
+
         let actions = match event {
             Event::Actions(actions) => actions.as_slice(),
             _ => &[],
         };
 
+        // Poll summary progress
+        if self.summary_timer.is_event(event).is_some() {
+            self.poll_summary(cx);
+        }
+
         // Initialize config input on first run
         if !self.config_initialized {
             self.config_initialized = true;
@@ -643,7 +738,21 @@ impl Widget for TranscriberScreen {
                                 self.set_status(cx, "Connected", 1.0);
                             }
                         }
-                        WebViewAction::IpcMessage { .. } | WebViewAction::None => {}
+                        WebViewAction::IpcMessage { channel, data } => {
+                            match channel.as_str() {
+                                "punctuate" => self.handle_punctuate(&data),
+                                "summarize" => self.handle_summarize(cx, &data),
+                                "correction" | "glossary_get" | "glossary_add" | "glossary_dismiss" => {
+                                    self.handle_glossary(&channel, &data);
+                                }
+                                "handoff_ready" => {
+                                    self.handoff_ready = true;
+                                    self.deliver_handoffs();
+                                }
+                                _ => {}
+                            }
+                        }
+                        WebViewAction::InitTiming { .. } | WebViewAction::None => {}
                     }
                 }
             }
@@ -666,6 +775,7 @@ impl TranscriberScreen {
             let mut server = self.server.lock().unwrap();
             server.stop();
             drop(server);
+            self.server_activity = None;
             self.set_status(cx, "Server stopped", 0.0);
             self.url_loaded = false;
             self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Start Server");
@@ -680,6 +790,11 @@ impl TranscriberScreen {
             match result {
                 Ok(port) => {
                     ::log::info!("Transcriber server started on port {}", port);
+                    let activity = ActivityRegistry::global()
+                        .register(ActivityKind::Server, "mofa-transcriber", "Transcriber server")
+                        .stoppable();
+                    activity.set_state(&format!("port {}", port));
+                    self.server_activity = Some(activity);
                     self.set_status(cx, &format!("Server running on port {}", port), 2.0);
                     self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Stop Server");
 
@@ -704,6 +819,7 @@ impl TranscriberScreen {
         };
 
         self.url_loaded = true;
+        self.handoff_ready = false;
         ::log::info!("Loading URL: {}", url);
 
         let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
@@ -714,6 +830,229 @@ impl TranscriberScreen {
         }
     }
 
+    /// Restore punctuation for a finished job and send it back to the page
+    ///
+    /// The page keeps the raw transcript, so this only ever derives a view of it.
+    fn handle_punctuate(&self, data: &str) {
+        let Some(request) = parse_ipc_payload(data) else {
+            return;
+        };
+
+        let job_id = request.get("job_id").and_then(|v| v.as_str()).unwrap_or_default();
+        let language = request.get("language").and_then(|v| v.as_str()).unwrap_or("en");
+        let mut segments: Vec<String> = request
+            .get("segments")
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        // Some backends only return full text
+        if segments.is_empty() {
+            if let Some(text) = request.get("text").and_then(|v| v.as_str()) {
+                segments.push(text.to_string());
+            }
+        }
+
+        let restorer = restorer_for_language(language, &load_punctuation_config());
+        let restored = restore_segments(restorer.as_ref(), &segments);
+
+        let reply = serde_json::json!({
+            "job_id": job_id,
+            "restorer": restorer.name(),
+            "text": restored.text,
+            "segments": restored.segments,
+        });
+        let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+        if let Err(e) = webview.send_to_js("punctuated", &reply.to_string()) {
+            ::log::warn!("Failed to send punctuated transcript: {}", e);
+        }
+    }
+
+    /// Review-mode corrections and glossary edits from the page
+    ///
+    /// Every message is answered with the current glossary and suggestions.
+    fn handle_glossary(&mut self, channel: &str, data: &str) {
+        let request = parse_ipc_payload(data).unwrap_or_default();
+        let text = |key: &str| request.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+
+        let glossary = self.glossary.get_or_insert_with(|| Glossary::from_config(&read_config()));
+        let changed = match channel {
+            "correction" => !glossary.record_correction(&text("raw"), &text("corrected")).is_empty(),
+            "glossary_add" => {
+                glossary.add_term(&text("term"));
+                true
+            }
+            "glossary_dismiss" => {
+                glossary.dismiss(&text("term"));
+                true
+            }
+            _ => false,
+        };
+        if changed {
+            if let Err(e) = write_config_value("glossary", glossary.to_value()) {
+                ::log::warn!("Failed to save glossary: {}", e);
+            }
+        }
+
+        let reply = serde_json::json!({
+            "terms": glossary.terms,
+            "suggestions": glossary.suggestions(),
+        });
+        let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+        if let Err(e) = webview.send_to_js("glossary", &reply.to_string()) {
+            ::log::warn!("Failed to send glossary: {}", e);
+        }
+    }
+
+    /// Start (or resume) summarizing a finished transcript
+    fn handle_summarize(&mut self, cx: &mut Cx, data: &str) {
+        let Some(request) = parse_ipc_payload(data) else {
+            return;
+        };
+        let job_id = request.get("job_id").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let template_key = request.get("template").and_then(|v| v.as_str()).unwrap_or_default();
+        let Some(template) = SummaryTemplate::from_key(template_key) else {
+            ::log::warn!("Unknown summary template: {}", template_key);
+            return;
+        };
+        let fail = |screen: &Self, error: String| {
+            screen.send_summary(serde_json::json!({
+                "job_id": job_id,
+                "template": template.key(),
+                "error": error,
+            }));
+        };
+
+        if self.summary_job.is_some() {
+            fail(self, "A summary is already being generated".to_string());
+            return;
+        }
+
+        let custom_prompt = request.get("custom_prompt").and_then(|v| v.as_str()).unwrap_or_default();
+        let segments: Vec<String> = request
+            .get("segments")
+            .and_then(|v| v.as_array())
+            .map(|items| items.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+
+        let config = read_config();
+        let context_tokens = config
+            .get("summary_context_tokens")
+            .and_then(|v| v.as_u64())
+            .map(|tokens| tokens as usize);
+        let provider = match SummaryConfig::from_preferences(&Preferences::load(), context_tokens) {
+            Ok(provider) => provider,
+            Err(e) => return fail(self, e.to_string()),
+        };
+        let run = match SummaryRun::new(template, custom_prompt, &segments, provider.chunk_tokens()) {
+            Ok(run) => run,
+            Err(e) => return fail(self, e.to_string()),
+        };
+
+        let key = (job_id.clone(), template);
+        let run = match self.summary_runs.remove(&key) {
+            Some(previous) if previous.same_request(&run) => previous,
+            _ => run,
+        };
+        ::log::info!(
+            "Summarizing job {} as {} with {} ({} of {} chunks done)",
+            job_id,
+            template.label(),
+            provider.endpoint.model,
+            run.completed_chunks(),
+            run.chunk_count()
+        );
+        self.summary_job = Some((job_id, SummaryJob::start(run, ProviderChat { config: provider })));
+        self.summary_timer = cx.start_interval(0.25);
+        self.poll_summary(cx);
+    }
+
+    fn poll_summary(&mut self, cx: &mut Cx) {
+        let Some((job_id, job)) = self.summary_job.as_ref() else {
+            cx.stop_timer(self.summary_timer);
+            return;
+        };
+        let job_id = job_id.clone();
+
+        let (run, result) = match job.poll() {
+            SummaryPoll::Running(progress) => {
+                let reply = serde_json::json!({ "job_id": job_id, "status": progress.describe() });
+                let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+                if let Err(e) = webview.send_to_js("summary_progress", &reply.to_string()) {
+                    ::log::warn!("Failed to send summary progress: {}", e);
+                }
+                return;
+            }
+            SummaryPoll::Finished(run, result) => (Some(run), result),
+            SummaryPoll::Lost => (None, Err(SummaryError::Request("summary thread exited".to_string()))),
+        };
+        cx.stop_timer(self.summary_timer);
+        self.summary_job = None;
+
+        let mut reply = serde_json::json!({ "job_id": job_id });
+        if let Some(run) = run.as_ref() {
+            reply["template"] = run.template.key().into();
+            reply["label"] = run.template.label().into();
+            reply["chunks"] = run.chunk_count().into();
+            reply["usage"] = serde_json::to_value(run.usage).unwrap_or_default();
+        }
+        match result {
+            Ok(markdown) => reply["markdown"] = markdown.into(),
+            Err(e) => {
+                ::log::warn!("Summary of job {} failed: {}", job_id, e);
+                reply["error"] = e.to_string().into();
+                if let Some(run) = run {
+                    reply["completed"] = run.completed_chunks().into();
+                    self.summary_runs.insert((job_id, run.template), run);
+                }
+            }
+        }
+        self.send_summary(reply);
+    }
+
+    /// Pass files other apps sent to the page, once it is listening
+    ///
+    /// Each file is linked into the backend's inbox (`INBOX_DIR` in app.py),
+    /// which the page fetches it from; the sender's file stays where it is.
+    fn deliver_handoffs(&mut self) {
+        if !self.handoff_ready {
+            return;
+        }
+        let inbox = std::env::temp_dir().join("mofa-transcriber").join("inbox");
+        for received in HandoffBus::global().take("mofa-transcriber") {
+            let mut files = Vec::new();
+            for file in &received.files {
+                let Some(name) = file.file_name().map(|n| n.to_string_lossy().to_string()) else {
+                    continue;
+                };
+                self.handoff_count += 1;
+                let inbox_name = format!("{}-{}", self.handoff_count, name);
+                if let Err(e) = handoff::link_or_copy(file, &inbox.join(&inbox_name)) {
+                    ::log::warn!("Failed to receive {} from {}: {}", file.display(), received.from, e);
+                    continue;
+                }
+                let mtime = fs::metadata(file)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_millis() as u64);
+                files.push(serde_json::json!({ "name": name, "inbox": inbox_name, "mtime": mtime }));
+            }
+            ::log::info!("Received {} file(s) from {}", files.len(), received.from);
+            let message = serde_json::json!({ "from": received.from, "files": files });
+            let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+            if let Err(e) = webview.send_to_js("handoff", &message.to_string()) {
+                ::log::warn!("Failed to send handed-off files: {}", e);
+            }
+        }
+    }
+
+    fn send_summary(&self, reply: serde_json::Value) {
+        let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+        if let Err(e) = webview.send_to_js("summary", &reply.to_string()) {
+            ::log::warn!("Failed to send summary: {}", e);
+        }
+    }
+
     fn go_back(&self) {
         let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
         let _ = webview.go_back();
@@ -755,6 +1094,14 @@ impl TranscriberScreenRef {
         }
     }
 
+    /// Take files sent by other apps; they wait on the hand-off bus until
+    /// the page is loaded
+    pub fn receive_handoffs(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.deliver_handoffs();
+        }
+    }
+
     pub fn stop_server(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() {
             let is_running = {
@@ -868,9 +1215,7 @@ impl TranscriberScreenRef {
             );
 
             // Send theme to WebView
-            let webview = inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
-            let js = format!("if(window.setTheme) window.setTheme({});", dark_mode);
-            let _ = webview.eval(&js);
+            inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview)).set_dark_mode(dark_mode);
 
             inner.view.redraw(cx);
         }
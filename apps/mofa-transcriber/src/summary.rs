@@ -0,0 +1,719 @@
+//! Transcript summarization
+//!
+//! The page asks for a summary of a finished transcript with one of the
+//! [`SummaryTemplate`]s. It goes to the configured chat provider. When the
+//! transcript does not fit the model's context window, it is split into
+//! chunks at segment boundaries. Each chunk is summarized on its own, then
+//! the partial summaries are merged. If they do not fit one request either,
+//! they are merged in several rounds. The window is looked up by model name;
+//! `summary_context_tokens` in the Transcriber config overrides it.
+//!
+//! A [`SummaryRun`] keeps the chunk summaries it already has. A run that
+//! failed part way is resumed from the first missing chunk. Token usage of
+//! every request is recorded in the shared [`mofa_widgets::usage`] ledger.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use mofa_settings::data::{ChatEndpoint, ChatError, ChatRequest, Preferences};
+use mofa_widgets::jobs::JobRegistry;
+use mofa_widgets::usage::{self, TokenUsage};
+
+/// App id used in the usage ledger
+const USAGE_APP: &str = "mofa-transcriber";
+
+/// Context window for models not in [`CONTEXT_WINDOWS`]
+pub const DEFAULT_CONTEXT_TOKENS: usize = 8_192;
+
+/// Context windows by model name prefix; the first match wins
+const CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4", 8_192),
+    ("gpt-3.5-turbo", 16_385),
+    ("deepseek", 64_000),
+    ("qwen", 32_768),
+];
+
+/// Reply length asked for in every request
+const MAX_OUTPUT_TOKENS: usize = 1_024;
+
+/// Room for the instructions around the transcript text
+const PROMPT_OVERHEAD_TOKENS: usize = 512;
+
+/// Smallest chunk, however small the configured window
+const MIN_CHUNK_TOKENS: usize = 256;
+
+/// Latin-script characters per token; errs on the high side for English
+const CHARS_PER_TOKEN: usize = 3;
+
+/// Request timeout per chunk
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Kind of summary to write
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SummaryTemplate {
+    #[default]
+    MeetingMinutes,
+    LectureNotes,
+    ActionItems,
+    Custom,
+}
+
+impl SummaryTemplate {
+    /// All templates, in the page's order
+    pub const ALL: [SummaryTemplate; 4] = [
+        SummaryTemplate::MeetingMinutes,
+        SummaryTemplate::LectureNotes,
+        SummaryTemplate::ActionItems,
+        SummaryTemplate::Custom,
+    ];
+
+    /// Identifier used by the page
+    pub fn key(&self) -> &'static str {
+        match self {
+            SummaryTemplate::MeetingMinutes => "minutes",
+            SummaryTemplate::LectureNotes => "lecture",
+            SummaryTemplate::ActionItems => "actions",
+            SummaryTemplate::Custom => "custom",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.key() == key)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SummaryTemplate::MeetingMinutes => "Meeting minutes",
+            SummaryTemplate::LectureNotes => "Lecture notes",
+            SummaryTemplate::ActionItems => "Action items",
+            SummaryTemplate::Custom => "Custom",
+        }
+    }
+
+    /// What the summary should contain; `custom` is the user's prompt
+    fn instructions(&self, custom: &str) -> String {
+        match self {
+            SummaryTemplate::MeetingMinutes => {
+                "Write meeting minutes: a short overview, then sections for topics discussed, \
+                 decisions made and action items with owners and deadlines where mentioned."
+                    .to_string()
+            }
+            SummaryTemplate::LectureNotes => {
+                "Write lecture notes: the main topic, the key concepts with short explanations, \
+                 examples given, and questions or further reading mentioned."
+                    .to_string()
+            }
+            SummaryTemplate::ActionItems => {
+                "List only the action items as a Markdown checklist (- [ ] task - owner, due date), \
+                 with owners and deadlines where mentioned. If there are none, write \"No action items.\""
+                    .to_string()
+            }
+            SummaryTemplate::Custom => custom.trim().to_string(),
+        }
+    }
+}
+
+/// Errors raised while summarizing
+#[derive(Debug, Clone, PartialEq)]
+pub enum SummaryError {
+    EmptyTranscript,
+    EmptyCustomPrompt,
+    NoProvider,
+    /// Partial summaries stay too long to merge within the context window
+    TooLong,
+    Request(String),
+    Parse(String),
+}
+
+impl std::fmt::Display for SummaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SummaryError::EmptyTranscript => write!(f, "The transcript is empty"),
+            SummaryError::EmptyCustomPrompt => write!(f, "Enter a prompt for the custom template"),
+            SummaryError::NoProvider => write!(f, "No enabled provider with an API key"),
+            SummaryError::TooLong => {
+                write!(f, "The partial summaries do not fit the model's context window")
+            }
+            SummaryError::Request(e) => write!(f, "Provider request failed: {}", e),
+            SummaryError::Parse(e) => write!(f, "Could not read the reply: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SummaryError {}
+
+impl From<ChatError> for SummaryError {
+    fn from(e: ChatError) -> Self {
+        match e {
+            ChatError::Request(e) => SummaryError::Request(e),
+            ChatError::Parse(e) => SummaryError::Parse(e),
+        }
+    }
+}
+
+/// Provider settings used for summary calls
+#[derive(Clone, Debug)]
+pub struct SummaryConfig {
+    pub endpoint: ChatEndpoint,
+    pub context_tokens: usize,
+}
+
+impl SummaryConfig {
+    /// Resolve the provider from preferences. `context_tokens` overrides the
+    /// window looked up for the model.
+    pub fn from_preferences(prefs: &Preferences, context_tokens: Option<usize>) -> Result<Self, SummaryError> {
+        let endpoint = ChatEndpoint::from_preferences(prefs).ok_or(SummaryError::NoProvider)?;
+        Ok(Self {
+            context_tokens: context_tokens.unwrap_or_else(|| context_window(&endpoint.model)),
+            endpoint,
+        })
+    }
+
+    /// Most transcript tokens sent in one request
+    pub fn chunk_tokens(&self) -> usize {
+        self.context_tokens
+            .saturating_sub(MAX_OUTPUT_TOKENS + PROMPT_OVERHEAD_TOKENS)
+            .max(MIN_CHUNK_TOKENS)
+    }
+}
+
+/// Context window of a model, by name
+pub fn context_window(model: &str) -> usize {
+    let model = model.to_lowercase();
+    // "openai/gpt-4o" style names from routers
+    let name = model.rsplit('/').next().unwrap_or(&model);
+    CONTEXT_WINDOWS
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, tokens)| *tokens)
+        .unwrap_or(DEFAULT_CONTEXT_TOKENS)
+}
+
+#[derive(Default)]
+struct TokenCounter {
+    /// CJK and kana characters, about a token each
+    wide: usize,
+    other: usize,
+}
+
+impl TokenCounter {
+    fn push(&mut self, c: char) {
+        if is_wide(c) {
+            self.wide += 1;
+        } else {
+            self.other += 1;
+        }
+    }
+
+    fn tokens(&self) -> usize {
+        self.wide + self.other.div_ceil(CHARS_PER_TOKEN)
+    }
+}
+
+fn is_wide(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30ff}'     // Hiragana, Katakana
+        | '\u{3400}'..='\u{4dbf}'   // CJK Extension A
+        | '\u{4e00}'..='\u{9fff}'   // CJK Unified Ideographs
+        | '\u{ac00}'..='\u{d7af}'   // Hangul syllables
+        | '\u{3000}'..='\u{303f}'   // CJK punctuation
+        | '\u{ff00}'..='\u{ffef}'   // Full-width forms
+    )
+}
+
+/// Rough token count without the model's tokenizer
+pub fn estimate_tokens(text: &str) -> usize {
+    let mut counter = TokenCounter::default();
+    text.chars().for_each(|c| counter.push(c));
+    counter.tokens()
+}
+
+/// Split `text` into pieces of at most `budget` tokens, at whitespace where
+/// there is some
+fn split_to_budget(text: &str, budget: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut rest = text.trim();
+    while estimate_tokens(rest) > budget {
+        let mut counter = TokenCounter::default();
+        let mut cut = rest.len();
+        let mut last_space = None;
+        for (i, c) in rest.char_indices() {
+            counter.push(c);
+            if counter.tokens() > budget {
+                cut = i;
+                break;
+            }
+            if c.is_whitespace() {
+                last_space = Some(i);
+            }
+        }
+        let mut cut = last_space.filter(|&space| space > 0).unwrap_or(cut);
+        if cut == 0 {
+            cut = rest.chars().next().map_or(rest.len(), char::len_utf8);
+        }
+        pieces.push(rest[..cut].trim_end());
+        rest = rest[cut..].trim_start();
+    }
+    if !rest.is_empty() {
+        pieces.push(rest);
+    }
+    pieces
+}
+
+/// Join pieces into as few texts of at most `budget` tokens as possible,
+/// keeping their order
+fn pack<S: AsRef<str>>(pieces: &[S], budget: usize, separator: &str) -> Vec<String> {
+    let separator_tokens = estimate_tokens(separator);
+    let mut packed = Vec::new();
+    let mut current = String::new();
+    let mut current_tokens = 0;
+    for piece in pieces.iter().flat_map(|p| split_to_budget(p.as_ref(), budget)) {
+        let tokens = estimate_tokens(piece);
+        if !current.is_empty() && current_tokens + separator_tokens + tokens > budget {
+            packed.push(std::mem::take(&mut current));
+            current_tokens = 0;
+        }
+        if !current.is_empty() {
+            current.push_str(separator);
+            current_tokens += separator_tokens;
+        }
+        current.push_str(piece);
+        current_tokens += tokens;
+    }
+    if !current.is_empty() {
+        packed.push(current);
+    }
+    packed
+}
+
+/// Transcript segments grouped into chunks of at most `budget` tokens
+pub fn chunk_segments<S: AsRef<str>>(segments: &[S], budget: usize) -> Vec<String> {
+    pack(segments, budget, "\n")
+}
+
+/// A chat completion endpoint
+pub trait ChatClient {
+    /// Reply to `user` under `system`, with the tokens the request used
+    fn complete(&self, system: &str, user: &str) -> Result<(String, TokenUsage), SummaryError>;
+}
+
+/// OpenAI-compatible provider; records usage in the shared ledger
+pub struct ProviderChat {
+    pub config: SummaryConfig,
+}
+
+impl ChatClient for ProviderChat {
+    fn complete(&self, system: &str, user: &str) -> Result<(String, TokenUsage), SummaryError> {
+        let endpoint = &self.config.endpoint;
+        let reply = endpoint.complete(&ChatRequest {
+            system,
+            user,
+            temperature: 0.3,
+            max_tokens: Some(MAX_OUTPUT_TOKENS),
+            timeout: REQUEST_TIMEOUT,
+        })?;
+        let used = TokenUsage::from_response(&reply.response);
+        usage::record(USAGE_APP, &endpoint.provider, &endpoint.model, used);
+        Ok((reply.content, used))
+    }
+}
+
+/// Where a run is
+#[derive(Clone, Debug, PartialEq)]
+pub enum SummaryProgress {
+    /// Summarizing chunk `index` (0-based) of `total`
+    Chunk { index: usize, total: usize },
+    /// Merging partial summaries; round 1 is the first
+    Merge { round: usize },
+}
+
+impl SummaryProgress {
+    /// Status line for the page
+    pub fn describe(&self) -> String {
+        match self {
+            SummaryProgress::Chunk { total: 1, .. } => "Summarizing...".to_string(),
+            SummaryProgress::Chunk { index, total } => {
+                format!("Summarizing part {} of {}...", index + 1, total)
+            }
+            SummaryProgress::Merge { round: 1 } => "Merging partial summaries...".to_string(),
+            SummaryProgress::Merge { round } => {
+                format!("Merging partial summaries (round {})...", round)
+            }
+        }
+    }
+}
+
+/// A summary request with the chunk summaries finished so far
+#[derive(Clone, Debug)]
+pub struct SummaryRun {
+    pub template: SummaryTemplate,
+    custom_prompt: String,
+    budget: usize,
+    chunks: Vec<String>,
+    partials: Vec<Option<String>>,
+    /// Tokens used by all requests of the run, including failed attempts
+    pub usage: TokenUsage,
+    fingerprint: u64,
+}
+
+impl SummaryRun {
+    pub fn new<S: AsRef<str>>(
+        template: SummaryTemplate,
+        custom_prompt: &str,
+        segments: &[S],
+        budget: usize,
+    ) -> Result<Self, SummaryError> {
+        if template == SummaryTemplate::Custom && custom_prompt.trim().is_empty() {
+            return Err(SummaryError::EmptyCustomPrompt);
+        }
+        let chunks = chunk_segments(segments, budget);
+        if chunks.is_empty() {
+            return Err(SummaryError::EmptyTranscript);
+        }
+
+        let mut hasher = DefaultHasher::new();
+        (template, custom_prompt.trim(), budget, &chunks).hash(&mut hasher);
+        Ok(Self {
+            template,
+            custom_prompt: custom_prompt.trim().to_string(),
+            budget,
+            partials: vec![None; chunks.len()],
+            chunks,
+            usage: TokenUsage::default(),
+            fingerprint: hasher.finish(),
+        })
+    }
+
+    /// Whether `other` asks for the same summary of the same transcript, so
+    /// this run's finished chunks can be reused for it
+    pub fn same_request(&self, other: &SummaryRun) -> bool {
+        self.fingerprint == other.fingerprint
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn completed_chunks(&self) -> usize {
+        self.partials.iter().filter(|p| p.is_some()).count()
+    }
+
+    fn system_prompt(&self, task: &str) -> String {
+        format!(
+            "You summarize transcripts of recordings. The transcript comes from speech recognition \
+             and may contain recognition errors. Write in the language of the transcript and \
+             format the result as Markdown. {}",
+            task
+        )
+    }
+
+    /// Summarize the missing chunks and merge; stops at the first failure,
+    /// keeping what was finished
+    pub fn execute(
+        &mut self,
+        client: &dyn ChatClient,
+        progress: &mut dyn FnMut(SummaryProgress),
+    ) -> Result<String, SummaryError> {
+        let instructions = self.template.instructions(&self.custom_prompt);
+        let total = self.chunks.len();
+
+        if total == 1 {
+            progress(SummaryProgress::Chunk { index: 0, total });
+            let system = self.system_prompt(&instructions);
+            let user = format!("Transcript:\n\n{}", self.chunks[0]);
+            return self.request(client, &system, &user);
+        }
+
+        for index in 0..total {
+            if self.partials[index].is_some() {
+                continue;
+            }
+            progress(SummaryProgress::Chunk { index, total });
+            let system = self.system_prompt(&format!(
+                "This is part {} of {} of a longer transcript. Note everything from this part that \
+                 the final summary needs; it will be merged with the other parts later. \
+                 Keep names, numbers and decisions, and add no introduction. The final summary \
+                 follows these instructions: {}",
+                index + 1,
+                total,
+                instructions
+            ));
+            let user = format!("Transcript, part {}:\n\n{}", index + 1, self.chunks[index]);
+            self.partials[index] = Some(self.request(client, &system, &user)?);
+        }
+
+        let mut partials: Vec<String> = self.partials.iter().flatten().cloned().collect();
+        let mut round = 1;
+        loop {
+            progress(SummaryProgress::Merge { round });
+            let groups = pack(&partials, self.budget, "\n\n---\n\n");
+            if groups.len() > 1 && groups.len() >= partials.len() {
+                return Err(SummaryError::TooLong);
+            }
+            let last = groups.len() == 1;
+            let task = if last {
+                format!(
+                    "Merge these summaries of consecutive parts of one transcript into a single \
+                     summary without repetition. {}",
+                    instructions
+                )
+            } else {
+                "Merge these summaries of consecutive parts of one transcript into one shorter \
+                 set of notes. Keep names, numbers and decisions."
+                    .to_string()
+            };
+            let system = self.system_prompt(&task);
+            let mut merged = Vec::with_capacity(groups.len());
+            for group in &groups {
+                let user = format!("Summaries of the parts, in order:\n\n{}", group);
+                merged.push(self.request(client, &system, &user)?);
+            }
+            if last {
+                return Ok(merged.remove(0));
+            }
+            partials = merged;
+            round += 1;
+        }
+    }
+
+    fn request(&mut self, client: &dyn ChatClient, system: &str, user: &str) -> Result<String, SummaryError> {
+        let (reply, used) = client.complete(system, user)?;
+        self.usage += used;
+        Ok(reply)
+    }
+}
+
+/// State of a [`SummaryJob`]
+pub enum SummaryPoll {
+    Running(SummaryProgress),
+    /// The run, to keep for resuming if the result is an error
+    Finished(SummaryRun, Result<String, SummaryError>),
+    /// The worker thread died and took the run with it
+    Lost,
+}
+
+/// A run executing on a worker thread, listed in the shell's jobs while it runs
+pub struct SummaryJob {
+    progress: Arc<Mutex<SummaryProgress>>,
+    finished: Receiver<(SummaryRun, Result<String, SummaryError>)>,
+}
+
+impl SummaryJob {
+    pub fn start(mut run: SummaryRun, client: impl ChatClient + Send + 'static) -> Self {
+        let first = run.partials.iter().position(Option::is_none).unwrap_or(0);
+        let progress = Arc::new(Mutex::new(SummaryProgress::Chunk {
+            index: first,
+            total: run.chunk_count(),
+        }));
+        let (tx, finished) = mpsc::channel();
+
+        let worker_progress = Arc::clone(&progress);
+        let job = JobRegistry::global().start("mofa-transcriber", "summarizing");
+        std::thread::spawn(move || {
+            let result = run.execute(&client, &mut |p| {
+                // Merge rounds are short next to the chunk requests
+                if let SummaryProgress::Chunk { index, total } = p {
+                    job.set_progress(index as f32 / total.max(1) as f32);
+                }
+                *worker_progress.lock().unwrap_or_else(|e| e.into_inner()) = p;
+            });
+            // A failed run is over too; the page shows the error and offers to resume
+            job.finish();
+            let _ = tx.send((run, result));
+        });
+
+        Self { progress, finished }
+    }
+
+    pub fn poll(&self) -> SummaryPoll {
+        match self.finished.try_recv() {
+            Ok((run, result)) => SummaryPoll::Finished(run, result),
+            Err(TryRecvError::Empty) => {
+                SummaryPoll::Running(self.progress.lock().unwrap_or_else(|e| e.into_inner()).clone())
+            }
+            Err(TryRecvError::Disconnected) => SummaryPoll::Lost,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mofa_settings::data::{Provider, ProviderType};
+    use std::cell::RefCell;
+
+    /// Answers every request with a numbered reply; fails request `fail_at`
+    #[derive(Default)]
+    struct ScriptedChat {
+        requests: RefCell<Vec<(String, String)>>,
+        fail_at: Option<usize>,
+    }
+
+    impl ChatClient for ScriptedChat {
+        fn complete(&self, system: &str, user: &str) -> Result<(String, TokenUsage), SummaryError> {
+            let mut requests = self.requests.borrow_mut();
+            requests.push((system.to_string(), user.to_string()));
+            if self.fail_at == Some(requests.len() - 1) {
+                return Err(SummaryError::Request("connection reset".to_string()));
+            }
+            let used = TokenUsage { prompt_tokens: 100, completion_tokens: 10 };
+            Ok((format!("summary {}", requests.len()), used))
+        }
+    }
+
+    fn segments(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("segment {} {}", i, "word ".repeat(40))).collect()
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("hello world!"), 4);
+        assert_eq!(estimate_tokens("今天开会"), 4);
+        assert_eq!(estimate_tokens("会议 ok"), 3);
+    }
+
+    #[test]
+    fn test_chunks_respect_budget_and_order() {
+        let segments = segments(20);
+        let chunks = chunk_segments(&segments, 200);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| estimate_tokens(c) <= 200));
+        let joined = chunks.join("\n");
+        assert_eq!(joined, segments.iter().map(|s| s.trim()).collect::<Vec<_>>().join("\n"));
+
+        // A single segment longer than the budget is split, at spaces when it has any
+        let long = vec!["alpha ".repeat(300)];
+        let chunks = chunk_segments(&long, 100);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| estimate_tokens(c) <= 100 && c.ends_with("alpha")));
+        let cjk = vec!["会".repeat(250)];
+        assert_eq!(chunk_segments(&cjk, 100).iter().map(|c| c.chars().count()).collect::<Vec<_>>(), vec![100, 100, 50]);
+
+        assert!(chunk_segments(&["  ", ""], 100).is_empty());
+    }
+
+    #[test]
+    fn test_short_transcript_is_one_request() {
+        let mut run = SummaryRun::new(SummaryTemplate::ActionItems, "", &segments(3), 4_000).unwrap();
+        let chat = ScriptedChat::default();
+        let summary = run.execute(&chat, &mut |_| {}).unwrap();
+
+        assert_eq!(summary, "summary 1");
+        let requests = chat.requests.borrow();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].0.contains("Markdown checklist"));
+        assert!(requests[0].1.contains("segment 2"));
+        assert_eq!(run.usage, TokenUsage { prompt_tokens: 100, completion_tokens: 10 });
+    }
+
+    #[test]
+    fn test_long_transcript_is_mapped_then_merged() {
+        let custom = "Summarize as a haiku.";
+        let mut run = SummaryRun::new(SummaryTemplate::Custom, custom, &segments(12), 100).unwrap();
+        let total = run.chunk_count();
+        assert!(total > 2);
+
+        let chat = ScriptedChat::default();
+        let mut steps = Vec::new();
+        run.execute(&chat, &mut |p| steps.push(p)).unwrap();
+
+        let requests = chat.requests.borrow();
+        assert!(requests[..total].iter().all(|(system, _)| system.contains(custom)));
+        assert!(requests[0].0.contains(&format!("part 1 of {}", total)));
+        let (system, user) = requests.last().unwrap();
+        assert!(system.contains("single summary") && system.contains(custom));
+        assert!(user.contains("summary 1") && user.contains(&format!("summary {}", total)));
+        assert_eq!(steps.first(), Some(&SummaryProgress::Chunk { index: 0, total }));
+        assert_eq!(steps.last(), Some(&SummaryProgress::Merge { round: 1 }));
+    }
+
+    #[test]
+    fn test_failed_run_resumes_from_completed_chunks() {
+        let mut run = SummaryRun::new(SummaryTemplate::MeetingMinutes, "", &segments(12), 100).unwrap();
+        let total = run.chunk_count();
+
+        let failing = ScriptedChat { fail_at: Some(2), ..Default::default() };
+        let error = run.execute(&failing, &mut |_| {}).unwrap_err();
+        assert!(matches!(error, SummaryError::Request(_)));
+        assert_eq!(run.completed_chunks(), 2);
+
+        // The same request again picks the stored run up
+        let again = SummaryRun::new(SummaryTemplate::MeetingMinutes, "", &segments(12), 100).unwrap();
+        assert!(run.same_request(&again));
+        let other = SummaryRun::new(SummaryTemplate::LectureNotes, "", &segments(12), 100).unwrap();
+        assert!(!run.same_request(&other));
+
+        let chat = ScriptedChat::default();
+        let mut steps = Vec::new();
+        run.execute(&chat, &mut |p| steps.push(p)).unwrap();
+        let requests = chat.requests.borrow();
+        assert_eq!(requests.len(), total - 2 + 1);
+        assert!(requests[0].1.starts_with("Transcript, part 3:"));
+        assert_eq!(steps[0], SummaryProgress::Chunk { index: 2, total });
+        // Usage of the failed attempt is kept
+        assert_eq!(run.usage.prompt_tokens, 100 * (2 + total - 2 + 1) as u64);
+    }
+
+    #[test]
+    fn test_merge_in_rounds_when_partials_do_not_fit() {
+        /// Long replies, so the partial summaries overflow one request
+        struct Verbose;
+        impl ChatClient for Verbose {
+            fn complete(&self, _: &str, user: &str) -> Result<(String, TokenUsage), SummaryError> {
+                let reply = if user.starts_with("Summaries") { "merged" } else { "note " };
+                Ok((reply.repeat(60).trim().to_string(), TokenUsage::default()))
+            }
+        }
+
+        let mut run = SummaryRun::new(SummaryTemplate::LectureNotes, "", &segments(12), 300).unwrap();
+        let mut rounds = 0;
+        run.execute(&Verbose, &mut |p| {
+            if let SummaryProgress::Merge { round } = p {
+                rounds = round;
+            }
+        })
+        .unwrap();
+        assert!(rounds >= 2);
+    }
+
+    #[test]
+    fn test_templates_and_config() {
+        for template in SummaryTemplate::ALL {
+            assert_eq!(SummaryTemplate::from_key(template.key()), Some(template));
+        }
+        assert_eq!(SummaryTemplate::from_key("poem"), None);
+        assert_eq!(
+            SummaryRun::new(SummaryTemplate::Custom, "  ", &segments(1), 100).unwrap_err(),
+            SummaryError::EmptyCustomPrompt
+        );
+        assert_eq!(
+            SummaryRun::new(SummaryTemplate::MeetingMinutes, "", &["", " "], 100).unwrap_err(),
+            SummaryError::EmptyTranscript
+        );
+
+        assert_eq!(context_window("gpt-4o-mini"), 128_000);
+        assert_eq!(context_window("openai/GPT-4"), 8_192);
+        assert_eq!(context_window("some-local-model"), DEFAULT_CONTEXT_TOKENS);
+
+        let mut prefs = Preferences::default();
+        assert_eq!(SummaryConfig::from_preferences(&prefs, None).unwrap_err(), SummaryError::NoProvider);
+        prefs.providers.push(Provider {
+            id: "deepseek".to_string(),
+            url: "https://api.deepseek.com/v1/".to_string(),
+            api_key: Some("key".to_string()),
+            provider_type: ProviderType::Custom,
+            enabled: true,
+            models: vec!["deepseek-chat".to_string()],
+            ..Default::default()
+        });
+        let config = SummaryConfig::from_preferences(&prefs, None).unwrap();
+        assert_eq!((config.endpoint.api_url.as_str(), config.context_tokens), ("https://api.deepseek.com/v1", 64_000));
+        let config = SummaryConfig::from_preferences(&prefs, Some(4_096)).unwrap();
+        assert_eq!(config.chunk_tokens(), 4_096 - 1_024 - 512);
+    }
+}
@@ -7,6 +7,14 @@ pub use screen::WebViewDemoScreenWidgetRefExt;
 
 use makepad_widgets::{Cx, live_id, LiveId};
 use mofa_widgets::{AppInfo, MofaApp};
+use mofa_widgets::app_storage::AppStorage;
+
+/// Data this app keeps under `~/.mofa-studio`
+pub const STORAGE: AppStorage = AppStorage {
+    prefs: &[],
+    cache: &[],
+    webview_profile: true,
+};
 
 /// MoFA WebView Demo app descriptor
 pub struct MoFaWebViewDemoApp;
@@ -20,6 +28,7 @@ impl MofaApp for MoFaWebViewDemoApp {
             tab_id: Some(live_id!(webview_demo_tab)),
             page_id: Some(live_id!(webview_demo_page)),
             show_in_sidebar: true,
+            storage: STORAGE,
             ..Default::default()
         }
     }
@@ -1,9 +1,19 @@
 //! WebView Demo Screen
 //!
 //! A beautiful demo showcasing WebView embedding in Makepad
+//!
+//! The browser reopens the last visited page and keeps its back/forward list
+//! across restarts, unless the session is incognito. An incognito session
+//! also runs the WebView without its profile, so cookies and storage are
+//! dropped when it ends.
 
 use makepad_widgets::*;
-use mofa_widgets::webview::{WebViewAction, WebViewContainerWidgetExt};
+use mofa_widgets::webview::{
+    NavigationHistory, WebViewAction, WebViewContainerRef, WebViewContainerWidgetExt,
+};
+
+/// WebView profile of the main browser; the saved history lives in it
+const PROFILE: &str = "mofa-webview-demo";
 
 live_design! {
     use link::theme::*;
@@ -87,6 +97,7 @@ live_design! {
         draw_bg: {
             instance hover: 0.0
             instance pressed: 0.0
+            instance disabled: 0.0
             fn pixel(self) -> vec4 {
                 let sdf = Sdf2d::viewport(self.pos * self.rect_size);
                 sdf.circle(self.rect_size.x * 0.5, self.rect_size.y * 0.5, 16.0);
@@ -94,14 +105,15 @@ live_design! {
                 let hover_color = vec4(1.0, 1.0, 1.0, 0.2);
                 let pressed_color = vec4(1.0, 1.0, 1.0, 0.3);
                 let color = mix(mix(base, hover_color, self.hover), pressed_color, self.pressed);
-                sdf.fill(color);
+                sdf.fill(mix(color, base * 0.5, self.disabled));
                 return sdf.result;
             }
         }
         draw_text: {
+            instance disabled: 0.0
             text_style: { font_size: 16.0 }
             fn get_color(self) -> vec4 {
-                return vec4(1.0, 1.0, 1.0, 0.9);
+                return vec4(1.0, 1.0, 1.0, mix(0.9, 0.35, self.disabled));
             }
         }
     }
@@ -167,6 +179,40 @@ live_design! {
         }
     }
 
+    // Filler block for the scroll test
+    FillerBlock = <RoundedView> {
+        width: Fill, height: 180
+        padding: 16
+        show_bg: true
+        draw_bg: {
+            instance dark_mode: 0.0
+            border_radius: 8.0
+            fn get_color(self) -> vec4 {
+                return mix(
+                    vec4(1.0, 1.0, 1.0, 1.0),
+                    vec4(0.15, 0.16, 0.20, 1.0),
+                    self.dark_mode
+                );
+            }
+        }
+
+        label = <Label> {
+            width: Fill
+            draw_text: {
+                instance dark_mode: 0.0
+                wrap: Word
+                text_style: { font_size: 12.0 }
+                fn get_color(self) -> vec4 {
+                    return mix(
+                        vec4(0.3, 0.3, 0.35, 1.0),
+                        vec4(0.7, 0.7, 0.75, 1.0),
+                        self.dark_mode
+                    );
+                }
+            }
+        }
+    }
+
     pub WebViewDemoScreen = {{WebViewDemoScreen}} {
         width: Fill, height: Fill
         flow: Down
@@ -270,6 +316,18 @@ live_design! {
                     text: "Makepad"
                 }
 
+                scroll_test_btn = <QuickLinkCard> {
+                    text: "Scroll Test"
+                }
+
+                incognito_btn = <QuickLinkCard> {
+                    text: "Incognito: Off"
+                }
+
+                clear_data_btn = <QuickLinkCard> {
+                    text: "Clear Browsing Data"
+                }
+
                 <View> { width: Fill, height: Fill }  // Spacer
 
                 // IPC Demo section
@@ -342,9 +400,65 @@ live_design! {
                     webview = <WebViewContainer> {
                         width: Fill, height: Fill
                         url: "https://example.com"
+                        profile: "mofa-webview-demo"
                     }
                 }
             }
+
+            // Scroll test: a WebView in the middle of a scrolling page. The
+            // native view should clip at the header and status bar and hide
+            // once scrolled fully out of view.
+            scroll_area = <ScrollYView> {
+                visible: false
+                width: Fill, height: Fill
+                flow: Down
+                padding: 16
+                spacing: 16
+                scroll_bars: <ScrollBars> {
+                    show_scroll_x: false
+                    show_scroll_y: true
+                }
+
+                intro = <FillerBlock> {
+                    height: Fit
+                    label = { text: "Scroll down past the WebView. It should stay inside this area and never draw over the header or the status bar." }
+                }
+                filler_top = <FillerBlock> {
+                    label = { text: "Content above the WebView" }
+                }
+
+                scroll_webview_wrapper = <RoundedView> {
+                    width: Fill, height: 420
+                    show_bg: true
+                    draw_bg: {
+                        instance dark_mode: 0.0
+                        border_radius: 8.0
+                        fn get_color(self) -> vec4 {
+                            return mix(
+                                vec4(1.0, 1.0, 1.0, 1.0),
+                                vec4(0.15, 0.16, 0.20, 1.0),
+                                self.dark_mode
+                            );
+                        }
+                    }
+
+                    webview = <WebViewContainer> {
+                        width: Fill, height: Fill
+                        url: "https://example.com"
+                        profile: "mofa-webview-demo"
+                    }
+                }
+
+                filler_middle = <FillerBlock> {
+                    label = { text: "Content below the WebView" }
+                }
+                filler_bottom = <FillerBlock> {
+                    label = { text: "More content below the WebView" }
+                }
+                filler_end = <FillerBlock> {
+                    label = { text: "End of the scroll test" }
+                }
+            }
         }
 
         // Status bar
@@ -404,13 +518,50 @@ live_design! {
     }
 }
 
-#[derive(Live, LiveHook, Widget)]
+#[derive(Live, Widget)]
 pub struct WebViewDemoScreen {
     #[deref]
     view: View,
 
     #[rust]
     current_url: String,
+
+    /// Whether the page is active (set by the shell)
+    #[rust]
+    active: bool,
+
+    /// Showing the scroll test instead of the main WebView
+    #[rust]
+    scroll_test: bool,
+
+    /// Back/forward list of the main WebView
+    #[rust]
+    history: NavigationHistory,
+
+    /// Private session: the history is not saved and the WebView keeps
+    /// cookies and storage in memory
+    #[rust]
+    incognito: bool,
+
+    /// A back/forward load is in flight; its page replaces the current entry
+    #[rust]
+    history_nav: bool,
+}
+
+impl LiveHook for WebViewDemoScreen {
+    fn after_new_from_doc(&mut self, cx: &mut Cx) {
+        // Reopen the last page instead of the default once the WebView is created
+        self.history = NavigationHistory::load(PROFILE);
+        if let Some(url) = self.history.current() {
+            self.current_url = url.to_string();
+            self.history_nav = true;
+            self.view.text_input(ids!(header.url_bar)).set_text(cx, url);
+            self.view
+                .web_view_container(ids!(content.webview_area.webview_wrapper.webview))
+                .set_initial_url(url);
+        }
+        self.update_nav_buttons(cx);
+    }
 }
 
 impl Widget for WebViewDemoScreen {
@@ -424,11 +575,25 @@ impl Widget for WebViewDemoScreen {
         };
 
         // Handle WebView events
+        let main_uid = self
+            .view
+            .web_view_container(ids!(content.webview_area.webview_wrapper.webview))
+            .widget_uid();
         for action in actions {
+            let from_main = action
+                .as_widget_action()
+                .is_some_and(|wa| wa.widget_uid == main_uid);
             match action.as_widget_action().cast() {
                 WebViewAction::Initialized => {
                     self.set_status(cx, "WebView initialized", 1.0);
                 }
+                WebViewAction::InitTiming { elapsed_ms, attempts } => {
+                    self.set_status(
+                        cx,
+                        &format!("WebView initialized in {} ms ({} attempts)", elapsed_ms, attempts),
+                        1.0,
+                    );
+                }
                 WebViewAction::InitFailed(err) => {
                     self.set_status(cx, &format!("Failed: {}", err), 0.0);
                 }
@@ -442,7 +607,12 @@ impl Widget for WebViewDemoScreen {
                         .label(ids!(content.sidebar.ipc_section.ipc_status))
                         .set_text(cx, &format!("[{}] {}", channel, display));
                 }
-                WebViewAction::UrlChanged(_) | WebViewAction::None => {}
+                WebViewAction::UrlChanged(url) => {
+                    if from_main {
+                        self.record_navigation(cx, &url);
+                    }
+                }
+                WebViewAction::None => {}
             }
         }
 
@@ -453,6 +623,12 @@ impl Widget for WebViewDemoScreen {
         if self.view.button(ids!(header.refresh_btn)).clicked(actions) {
             self.refresh_page(cx);
         }
+        if self.view.button(ids!(header.back_btn)).clicked(actions) {
+            self.go_back(cx);
+        }
+        if self.view.button(ids!(header.forward_btn)).clicked(actions) {
+            self.go_forward(cx);
+        }
 
         // Quick links
         if self
@@ -484,6 +660,29 @@ impl Widget for WebViewDemoScreen {
             self.load_url(cx, "https://makepad.dev");
         }
 
+        if self
+            .view
+            .button(ids!(content.sidebar.scroll_test_btn))
+            .clicked(actions)
+        {
+            self.toggle_scroll_test(cx);
+        }
+
+        if self
+            .view
+            .button(ids!(content.sidebar.incognito_btn))
+            .clicked(actions)
+        {
+            self.toggle_incognito(cx);
+        }
+        if self
+            .view
+            .button(ids!(content.sidebar.clear_data_btn))
+            .clicked(actions)
+        {
+            self.clear_browsing_data(cx);
+        }
+
         // IPC demo
         if self
             .view
@@ -505,6 +704,36 @@ impl Widget for WebViewDemoScreen {
 }
 
 impl WebViewDemoScreen {
+    /// The WebView currently on screen
+    fn current_webview(&self) -> WebViewContainerRef {
+        if self.scroll_test {
+            self.view
+                .web_view_container(ids!(content.scroll_area.scroll_webview_wrapper.webview))
+        } else {
+            self.view
+                .web_view_container(ids!(content.webview_area.webview_wrapper.webview))
+        }
+    }
+
+    fn toggle_scroll_test(&mut self, cx: &mut Cx) {
+        // Only the visible WebView may be active; native views ignore Makepad visibility
+        self.current_webview().set_active(cx, false);
+        self.scroll_test = !self.scroll_test;
+        self.view
+            .view(ids!(content.webview_area))
+            .set_visible(cx, !self.scroll_test);
+        self.view
+            .view(ids!(content.scroll_area))
+            .set_visible(cx, self.scroll_test);
+        self.current_webview().set_active(cx, self.active);
+
+        let label = if self.scroll_test { "Back to Browser" } else { "Scroll Test" };
+        self.view
+            .button(ids!(content.sidebar.scroll_test_btn))
+            .set_text(cx, label);
+        self.view.redraw(cx);
+    }
+
     fn navigate_to_url(&mut self, cx: &mut Cx) {
         let url = self.view.text_input(ids!(header.url_bar)).text();
         self.load_url(cx, &url);
@@ -524,9 +753,7 @@ impl WebViewDemoScreen {
             .set_text(cx, &full_url);
 
         // Load in WebView
-        let webview = self
-            .view
-            .web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+        let webview = self.current_webview();
         if let Err(e) = webview.load_url(&full_url) {
             self.set_status(cx, &format!("Error: {}", e), 0.0);
         } else {
@@ -541,10 +768,106 @@ impl WebViewDemoScreen {
         }
     }
 
-    fn send_ipc_message(&mut self, cx: &mut Cx) {
+    fn record_navigation(&mut self, cx: &mut Cx, url: &str) {
+        if url == "about:blank" {
+            return;
+        }
+        if self.history_nav {
+            // Keep the entry, but with the URL the load ended on (redirects)
+            self.history_nav = false;
+            self.history.replace_current(url);
+        } else {
+            self.history.visit(url);
+        }
+        self.current_url = url.to_string();
+        self.view.text_input(ids!(header.url_bar)).set_text(cx, url);
+        self.save_history();
+        self.update_nav_buttons(cx);
+    }
+
+    fn go_back(&mut self, cx: &mut Cx) {
+        if let Some(url) = self.history.back().map(str::to_string) {
+            self.history_nav = true;
+            self.load_url(cx, &url);
+            self.save_history();
+            self.update_nav_buttons(cx);
+        }
+    }
+
+    fn go_forward(&mut self, cx: &mut Cx) {
+        if let Some(url) = self.history.forward().map(str::to_string) {
+            self.history_nav = true;
+            self.load_url(cx, &url);
+            self.save_history();
+            self.update_nav_buttons(cx);
+        }
+    }
+
+    fn save_history(&self) {
+        if self.incognito {
+            return;
+        }
+        if let Err(e) = self.history.save(PROFILE) {
+            ::log::warn!("Failed to save browsing history: {}", e);
+        }
+    }
+
+    fn toggle_incognito(&mut self, cx: &mut Cx) {
+        self.incognito = !self.incognito;
+        if !self.incognito {
+            // Drop what was browsed privately
+            self.history = NavigationHistory::load(PROFILE);
+            self.update_nav_buttons(cx);
+        }
+        // The WebView is rebuilt with the other data store at the page
+        // history now points at; its load replaces the current entry
+        let url = self.history.current().unwrap_or(&self.current_url).to_string();
+        let webview = self
+            .view
+            .web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+        self.history_nav = webview.is_initialized();
+        webview.set_incognito(cx, self.incognito, &url);
+        let label = if self.incognito { "Incognito: On" } else { "Incognito: Off" };
+        self.view
+            .button(ids!(content.sidebar.incognito_btn))
+            .set_text(cx, label);
+        self.view.redraw(cx);
+    }
+
+    fn clear_browsing_data(&mut self, cx: &mut Cx) {
+        self.history.clear();
+        if let Err(e) = NavigationHistory::delete(PROFILE) {
+            ::log::warn!("Failed to delete browsing history: {}", e);
+        }
         let webview = self
             .view
             .web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+        match webview.clear_browsing_data() {
+            Ok(()) => self.set_status(cx, "Browsing data cleared", 1.0),
+            Err(e) => self.set_status(cx, &format!("History cleared; {}", e), 0.0),
+        }
+        self.update_nav_buttons(cx);
+    }
+
+    fn update_nav_buttons(&mut self, cx: &mut Cx) {
+        for (id, enabled) in [
+            (ids!(header.back_btn), self.history.can_go_back()),
+            (ids!(header.forward_btn), self.history.can_go_forward()),
+        ] {
+            let disabled = if enabled { 0.0 } else { 1.0 };
+            self.view.button(id).apply_over(
+                cx,
+                live! {
+                    draw_bg: { disabled: (disabled) }
+                    draw_text: { disabled: (disabled) }
+                },
+            );
+        }
+        self.view.redraw(cx);
+    }
+
+    fn send_ipc_message(&mut self, cx: &mut Cx) {
+        let webview = self.current_webview();
         let msg = r#"{"greeting": "Hello from Makepad!", "time": "now"}"#;
         if let Err(e) = webview.send_to_js("demo", msg) {
             self.view
@@ -573,6 +896,14 @@ impl WebViewDemoScreen {
 }
 
 impl WebViewDemoScreenRef {
+    /// Activate or deactivate the WebView that is on screen
+    pub fn set_active(&self, cx: &mut Cx, active: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.active = active;
+            inner.current_webview().set_active(cx, active);
+        }
+    }
+
     pub fn update_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
         if let Some(mut inner) = self.borrow_mut() {
             // Main background
@@ -700,6 +1031,45 @@ impl WebViewDemoScreenRef {
                     },
                 );
 
+            // Scroll test and session buttons
+            for path in [
+                ids!(content.sidebar.scroll_test_btn),
+                ids!(content.sidebar.incognito_btn),
+                ids!(content.sidebar.clear_data_btn),
+            ] {
+                inner.view.button(path).apply_over(
+                    cx,
+                    live! {
+                        draw_bg: { dark_mode: (dark_mode) }
+                        draw_text: { dark_mode: (dark_mode) }
+                    },
+                );
+            }
+            for path in [
+                ids!(content.scroll_area.intro),
+                ids!(content.scroll_area.filler_top),
+                ids!(content.scroll_area.filler_middle),
+                ids!(content.scroll_area.filler_bottom),
+                ids!(content.scroll_area.filler_end),
+            ] {
+                inner.view.view(path).apply_over(
+                    cx,
+                    live! {
+                        draw_bg: { dark_mode: (dark_mode) }
+                        label = { draw_text: { dark_mode: (dark_mode) } }
+                    },
+                );
+            }
+            inner
+                .view
+                .view(ids!(content.scroll_area.scroll_webview_wrapper))
+                .apply_over(
+                    cx,
+                    live! {
+                        draw_bg: { dark_mode: (dark_mode) }
+                    },
+                );
+
             // Status bar
             inner.view.view(ids!(status_bar)).apply_over(
                 cx,
@@ -726,6 +1096,10 @@ impl WebViewDemoScreenRef {
                     },
                 );
 
+            // Send theme to both WebViews
+            inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview)).set_dark_mode(dark_mode);
+            inner.view.web_view_container(ids!(content.scroll_area.scroll_webview_wrapper.webview)).set_dark_mode(dark_mode);
+
             inner.view.redraw(cx);
         }
     }
@@ -1,18 +1,31 @@
 //! WebView Placeholder Screen
 //!
 //! WebView-based app with an embedded Rust HTTP server
+//!
+//! The server binds to `127.0.0.1` unless [`BIND_ENV`] names another address
+//! (e.g. `0.0.0.0` for headless or LAN use). Non-loopback servers require a
+//! bearer token, generated per session or taken from [`TOKEN_ENV`]; it is
+//! shown in the status bar for external clients. `/health` stays open.
 
 use makepad_widgets::*;
+use mofa_widgets::activity::{ActivityHandle, ActivityKind, ActivityRegistry};
+use mofa_widgets::local_server::{HttpRequest, HttpResponse, LocalServer, TokenAuth};
 use mofa_widgets::webview::{WebViewAction, WebViewContainerWidgetExt};
 use serde_json::json;
 use std::fs;
-use std::io::{BufRead, BufReader, Read, Write};
-use std::net::{TcpListener, TcpStream};
+use std::net::{IpAddr, Ipv4Addr};
 use std::path::PathBuf;
-use std::sync::{mpsc, Arc, Mutex};
-use std::thread;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+/// Address the server binds to; defaults to `127.0.0.1`
+const BIND_ENV: &str = "MOFA_WEBVIEW_BIND";
+
+/// Fixed access token; also turns auth on for loopback binds
+const TOKEN_ENV: &str = "MOFA_WEBVIEW_TOKEN";
+
+/// Reference page for the studio theme bridge: styled only with the
+/// `--mofa-*` properties, with fallbacks for a plain browser
 const FALLBACK_HTML: &str = r#"<!doctype html>
 <html lang="en">
 <head>
@@ -20,19 +33,43 @@ const FALLBACK_HTML: &str = r#"<!doctype html>
   <meta name="viewport" content="width=device-width, initial-scale=1" />
   <title>WebView Placeholder</title>
   <style>
-    body { font-family: system-ui, sans-serif; padding: 32px; background: #0f1115; color: #e0e0e5; }
-    code { background: #1a1d24; padding: 2px 6px; border-radius: 4px; }
-    a { color: #4a9eff; }
+    body {
+      font-family: var(--mofa-font, system-ui, sans-serif);
+      margin: 0;
+      padding: var(--mofa-space-6, 32px);
+      background: var(--mofa-bg, #f5f7fa);
+      color: var(--mofa-fg, #1f2937);
+    }
+    .card {
+      background: var(--mofa-panel, #ffffff);
+      border: 1px solid var(--mofa-border, #e5e7eb);
+      border-radius: var(--mofa-radius, 6px);
+      padding: var(--mofa-space-4, 16px) var(--mofa-space-5, 24px);
+    }
+    .muted { color: var(--mofa-fg-muted, #6b7280); }
+    code { background: var(--mofa-hover, #f1f5f9); padding: 2px 6px; border-radius: 4px; }
+    a { color: var(--mofa-accent, #3b82f6); }
   </style>
 </head>
 <body>
-  <h1>WebView Placeholder</h1>
-  <p>This page is a placeholder. Replace it with your real frontend build.</p>
-  <p>Suggested flow:</p>
-  <pre><code>cd external/webview-placeholder
+  <div class="card">
+    <h1>WebView Placeholder</h1>
+    <p>This page is a placeholder. Replace it with your real frontend build.</p>
+    <p>Suggested flow:</p>
+    <pre><code>cd external/webview-placeholder
 npm install
 npm run build</code></pre>
-  <p>Then reopen this page.</p>
+    <p>Then reopen this page.</p>
+    <p class="muted">Theme: <span id="theme-name">light</span></p>
+  </div>
+  <script>
+    // Colors follow the studio through CSS; scripts can listen for changes
+    function showTheme(theme) {
+      document.getElementById("theme-name").textContent = theme.name;
+    }
+    if (window.__mofa_theme && window.__mofa_theme.current) showTheme(window.__mofa_theme.current);
+    window.addEventListener("mofa-theme", function (e) { showTheme(e.detail); });
+  </script>
 </body>
 </html>"#;
 
@@ -192,6 +229,7 @@ live_design! {
                     webview = <WebViewContainer> {
                         width: Fill, height: Fill
                         url: "about:blank"
+                        profile: "mofa-webview-placeholder"
                     }
                 }
             }
@@ -254,6 +292,31 @@ live_design! {
 
             <View> { width: Fill, height: 1 }
 
+            token_label = <Label> {
+                visible: false
+                text: ""
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: { font_size: 10.0 }
+                    fn get_color(self) -> vec4 {
+                        return mix(
+                            vec4(0.4, 0.4, 0.45, 1.0),
+                            vec4(0.6, 0.6, 0.65, 1.0),
+                            self.dark_mode
+                        );
+                    }
+                }
+            }
+
+            copy_token_btn = <NavButton> {
+                visible: false
+                width: Fit
+                padding: {left: 8, right: 8}
+                margin: {left: 6, right: 12}
+                text: "Copy"
+                draw_text: { text_style: { font_size: 10.0 } }
+            }
+
             version_label = <Label> {
                 text: "WebView Placeholder v1.0"
                 draw_text: {
@@ -277,12 +340,6 @@ struct ServerAssets {
     static_root: Option<PathBuf>,
 }
 
-struct HttpRequest {
-    method: String,
-    path: String,
-    body: Vec<u8>,
-}
-
 fn resolve_static_root() -> Option<PathBuf> {
     if let Ok(exe_path) = std::env::current_exe() {
         if let Some(macos_dir) = exe_path.parent() {
@@ -331,63 +388,6 @@ fn load_index_html(static_root: Option<&PathBuf>) -> String {
     FALLBACK_HTML.to_string()
 }
 
-fn read_request(reader: &mut BufReader<TcpStream>) -> std::io::Result<Option<HttpRequest>> {
-    let mut request_line = String::new();
-    if reader.read_line(&mut request_line)? == 0 {
-        return Ok(None);
-    }
-    if request_line.trim().is_empty() {
-        return Ok(None);
-    }
-
-    let mut parts = request_line.trim().split_whitespace();
-    let method = parts.next().unwrap_or("").to_string();
-    let raw_path = parts.next().unwrap_or("/").to_string();
-    let path = raw_path.split('?').next().unwrap_or("/").to_string();
-
-    let mut content_length = 0usize;
-    loop {
-        let mut line = String::new();
-        let n = reader.read_line(&mut line)?;
-        if n == 0 {
-            break;
-        }
-        let trimmed = line.trim_end_matches(&['\r', '\n'][..]);
-        if trimmed.is_empty() {
-            break;
-        }
-        if let Some((key, value)) = trimmed.split_once(':') {
-            if key.eq_ignore_ascii_case("content-length") {
-                content_length = value.trim().parse().unwrap_or(0);
-            }
-        }
-    }
-
-    let mut body = vec![0u8; content_length];
-    if content_length > 0 {
-        reader.read_exact(&mut body)?;
-    }
-
-    Ok(Some(HttpRequest { method, path, body }))
-}
-
-fn write_response(
-    stream: &mut TcpStream,
-    status: &str,
-    content_type: &str,
-    body: &[u8],
-) -> std::io::Result<()> {
-    let header = format!(
-        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n",
-        status,
-        content_type,
-        body.len()
-    );
-    stream.write_all(header.as_bytes())?;
-    stream.write_all(body)?;
-    Ok(())
-}
-
 fn content_type_for_path(path: &str) -> &'static str {
     let ext = path.rsplit('.').next().unwrap_or("");
     match ext {
@@ -416,146 +416,109 @@ fn load_static_file(path: &str, assets: &ServerAssets) -> Option<(Vec<u8>, &'sta
     Some((bytes, content_type_for_path(rel)))
 }
 
-fn handle_connection(mut stream: TcpStream, assets: &ServerAssets) {
-    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
-    let _ = stream.set_write_timeout(Some(Duration::from_secs(5)));
-
-    let mut reader = match stream.try_clone() {
-        Ok(s) => BufReader::new(s),
-        Err(_) => return,
-    };
-
-    let request = match read_request(&mut reader) {
-        Ok(Some(req)) => req,
-        _ => return,
-    };
-
+fn handle_request(request: &HttpRequest, assets: &ServerAssets) -> HttpResponse {
     let method = request.method.as_str();
     let path = request.path.as_str();
 
-    let (status, content_type, body) = match (method, path) {
+    match (method, path) {
         ("GET", "/health") => {
             let now_ms = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_else(|_| Duration::from_secs(0))
                 .as_millis() as u64;
-            let body = serde_json::to_vec(&json!({
+            HttpResponse::json("200 OK", &json!({
                 "status": "ok",
                 "timestamp": now_ms
             }))
-            .unwrap_or_else(|_| b"{}".to_vec());
-            ("200 OK", "application/json; charset=utf-8", body)
         }
         ("GET", "/") | ("GET", "/index.html") => {
             let body = assets.index_html.as_bytes().to_vec();
-            ("200 OK", "text/html; charset=utf-8", body)
+            HttpResponse::new("200 OK", "text/html; charset=utf-8", body)
         }
         ("GET", _) => {
             if let Some((bytes, ctype)) = load_static_file(path, assets) {
-                ("200 OK", ctype, bytes)
+                HttpResponse::new("200 OK", ctype, bytes)
             } else {
                 // SPA fallback
                 let body = assets.index_html.as_bytes().to_vec();
-                ("200 OK", "text/html; charset=utf-8", body)
+                HttpResponse::new("200 OK", "text/html; charset=utf-8", body)
             }
         }
-        _ => {
-            let body = b"Method Not Allowed".to_vec();
-            ("405 Method Not Allowed", "text/plain; charset=utf-8", body)
-        }
-    };
-
-    let _ = write_response(&mut stream, status, content_type, &body);
+        _ => HttpResponse::method_not_allowed(),
+    }
 }
 
-fn server_loop(listener: TcpListener, shutdown_rx: mpsc::Receiver<()>, assets: Arc<ServerAssets>) {
-    loop {
-        if shutdown_rx.try_recv().is_ok() {
-            break;
-        }
-
-        match listener.accept() {
-            Ok((stream, _)) => {
-                handle_connection(stream, &assets);
-            }
-            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                thread::sleep(Duration::from_millis(30));
-            }
-            Err(_) => break,
-        }
+fn bind_addr() -> Result<IpAddr, String> {
+    match std::env::var(BIND_ENV) {
+        Ok(value) if !value.trim().is_empty() => value
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid {} address: {}", BIND_ENV, value)),
+        _ => Ok(IpAddr::V4(Ipv4Addr::LOCALHOST)),
     }
 }
 
-struct RustServer {
-    handle: Option<thread::JoinHandle<()>>,
-    shutdown: Option<mpsc::Sender<()>>,
-    port: u16,
+/// Token auth for a server bound to `bind`, if it needs any
+fn server_auth(bind: IpAddr, configured_token: Option<String>) -> Option<TokenAuth> {
+    let auth = match configured_token.filter(|token| !token.trim().is_empty()) {
+        Some(token) => TokenAuth::new(token.trim()),
+        None if !bind.is_loopback() => TokenAuth::generate(),
+        None => return None,
+    };
+    Some(auth.exempt("/health"))
 }
 
-impl Default for RustServer {
-    fn default() -> Self {
-        Self {
-            handle: None,
-            shutdown: None,
-            port: 0,
-        }
-    }
+#[derive(Default)]
+struct RustServer {
+    server: LocalServer,
+    bind: Option<IpAddr>,
+    auth: Option<TokenAuth>,
 }
 
 impl RustServer {
     fn is_running(&self) -> bool {
-        self.handle.is_some()
+        self.server.is_running()
     }
 
     fn start(&mut self) -> Result<u16, String> {
-        if self.handle.is_some() {
-            return Ok(self.port);
+        if self.server.is_running() {
+            return Ok(self.server.port());
         }
 
-        let listener = TcpListener::bind("127.0.0.1:0")
-            .map_err(|e| format!("Failed to bind server: {}", e))?;
-        let port = listener
-            .local_addr()
-            .map_err(|e| format!("Failed to read port: {}", e))?
-            .port();
-        listener
-            .set_nonblocking(true)
-            .map_err(|e| format!("Failed to set non-blocking: {}", e))?;
+        let bind = bind_addr()?;
+        let auth = server_auth(bind, std::env::var(TOKEN_ENV).ok());
 
         let static_root = resolve_static_root();
-        let assets = Arc::new(ServerAssets {
+        let assets = ServerAssets {
             index_html: load_index_html(static_root.as_ref()),
             static_root,
-        });
-
-        let (tx, rx) = mpsc::channel();
-        let handle = thread::spawn(move || server_loop(listener, rx, assets));
+        };
 
-        self.shutdown = Some(tx);
-        self.handle = Some(handle);
-        self.port = port;
+        let port = self
+            .server
+            .start_authorized(bind, auth.clone(), move |request| handle_request(request, &assets))?;
 
+        self.bind = Some(bind);
+        self.auth = auth;
         Ok(port)
     }
 
     fn stop(&mut self) {
-        if let Some(tx) = self.shutdown.take() {
-            let _ = tx.send(());
-        }
-        if let Some(handle) = self.handle.take() {
-            let _ = handle.join();
-        }
-        self.port = 0;
+        self.server.stop();
+        self.bind = None;
+        self.auth = None;
     }
 
+    /// URL for the WebView, carrying the token when auth is on
     fn url(&self) -> String {
-        format!("http://127.0.0.1:{}", self.port)
+        match &self.auth {
+            Some(auth) => auth.url_with_token(&self.server.url()),
+            None => self.server.url(),
+        }
     }
-}
 
-impl Drop for RustServer {
-    fn drop(&mut self) {
-        self.stop();
+    fn token(&self) -> Option<&str> {
+        self.auth.as_ref().map(|auth| auth.token())
     }
 }
 
@@ -566,12 +529,21 @@ pub struct WebViewPlaceholderScreen {
 
     #[rust]
     server: Arc<Mutex<RustServer>>,
+
+    /// Lists the server in the shell's running panel while it runs
+    #[rust]
+    server_activity: Option<ActivityHandle>,
 }
 
 impl Widget for WebViewPlaceholderScreen {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
 
+        // Stop requests from the shell's running panel
+        if self.server_activity.as_ref().is_some_and(|a| a.stop_requested()) {
+            self.toggle_server(cx);
+        }
+
         let actions = match event {
             Event::Actions(actions) => actions.as_slice(),
             _ => &[],
@@ -592,6 +564,11 @@ impl Widget for WebViewPlaceholderScreen {
         if self.view.button(ids!(status_bar.reload_btn)).clicked(actions) {
             self.reload();
         }
+        if self.view.button(ids!(status_bar.copy_token_btn)).clicked(actions) {
+            if let Some(token) = self.server.lock().unwrap().token() {
+                cx.copy_to_clipboard(token);
+            }
+        }
 
         // Handle WebView events
         let our_webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
@@ -617,7 +594,9 @@ impl Widget for WebViewPlaceholderScreen {
                                 self.set_status(cx, "Connected", 1.0);
                             }
                         }
-                        WebViewAction::IpcMessage { .. } | WebViewAction::None => {}
+                        WebViewAction::IpcMessage { .. }
+                        | WebViewAction::InitTiming { .. }
+                        | WebViewAction::None => {}
                     }
                 }
             }
@@ -640,8 +619,10 @@ impl WebViewPlaceholderScreen {
             let mut server = self.server.lock().unwrap();
             server.stop();
             drop(server);
+            self.server_activity = None;
             self.set_status(cx, "Server stopped", 0.0);
             self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Start Server");
+            self.update_token_ui(cx);
         } else {
             self.set_status(cx, "Starting server...", 2.0);
 
@@ -652,9 +633,21 @@ impl WebViewPlaceholderScreen {
 
             match result {
                 Ok(port) => {
+                    let bind = self.server.lock().unwrap().bind;
                     ::log::info!("WebView placeholder server started on port {}", port);
-                    self.set_status(cx, &format!("Server running on port {}", port), 2.0);
+                    let activity = ActivityRegistry::global()
+                        .register(ActivityKind::Server, "mofa-webview-placeholder", "WebView Placeholder server")
+                        .stoppable();
+                    activity.set_state(&format!("port {}", port));
+                    self.server_activity = Some(activity);
+                    match bind {
+                        Some(addr) if !addr.is_loopback() => {
+                            self.set_status(cx, &format!("Server running on {}:{}", addr, port), 2.0)
+                        }
+                        _ => self.set_status(cx, &format!("Server running on port {}", port), 2.0),
+                    }
                     self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Stop Server");
+                    self.update_token_ui(cx);
                     self.load_url(cx);
                 }
                 Err(e) => {
@@ -684,6 +677,16 @@ impl WebViewPlaceholderScreen {
         }
     }
 
+    /// Show the access token for external clients while auth is on
+    fn update_token_ui(&mut self, cx: &mut Cx) {
+        let token = self.server.lock().unwrap().token().map(str::to_string);
+        let label = self.view.label(ids!(status_bar.token_label));
+        label.set_visible(cx, token.is_some());
+        label.set_text(cx, &format!("Token: {}", token.as_deref().unwrap_or("")));
+        self.view.button(ids!(status_bar.copy_token_btn)).set_visible(cx, token.is_some());
+        self.view.redraw(cx);
+    }
+
     fn go_back(&self) {
         let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
         let _ = webview.go_back();
@@ -795,6 +798,19 @@ impl WebViewPlaceholderScreenRef {
                     draw_text: { dark_mode: (dark_mode) }
                 },
             );
+            inner.view.label(ids!(status_bar.token_label)).apply_over(
+                cx,
+                live! {
+                    draw_text: { dark_mode: (dark_mode) }
+                },
+            );
+            inner.view.button(ids!(status_bar.copy_token_btn)).apply_over(
+                cx,
+                live! {
+                    draw_bg: { dark_mode: (dark_mode) }
+                    draw_text: { dark_mode: (dark_mode) }
+                },
+            );
             inner.view.label(ids!(status_bar.version_label)).apply_over(
                 cx,
                 live! {
@@ -803,11 +819,33 @@ impl WebViewPlaceholderScreenRef {
             );
 
             // Send theme to WebView
-            let webview = inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
-            let js = format!("if(window.setTheme) window.setTheme({});", dark_mode);
-            let _ = webview.eval(&js);
+            inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview)).set_dark_mode(dark_mode);
 
             inner.view.redraw(cx);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_auth_for_bind() {
+        let lan: IpAddr = "0.0.0.0".parse().unwrap();
+        let local = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        // Loopback stays open unless a token is configured
+        assert!(server_auth(local, None).is_none());
+        assert!(server_auth(local, Some(" ".to_string())).is_none());
+        assert_eq!(server_auth(local, Some("abc".to_string())).unwrap().token(), "abc");
+
+        // Any other bind gets a generated token
+        let auth = server_auth(lan, None).unwrap();
+        assert_eq!(auth.token().len(), 32);
+        let health = HttpRequest { method: "GET".into(), path: "/health".into(), ..Default::default() };
+        assert!(auth.is_authorized(&health));
+        let index = HttpRequest { method: "GET".into(), path: "/".into(), ..Default::default() };
+        assert!(!auth.is_authorized(&index));
+    }
+}
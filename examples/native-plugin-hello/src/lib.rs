@@ -0,0 +1,67 @@
+//! Hello Native - minimal native plugin
+//!
+//! A Makepad screen built as a `cdylib` and loaded by the studio from the
+//! library named in `manifest.json`. See `mofa-plugin-api` for the ABI.
+
+use makepad_widgets::*;
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    pub HelloNativeScreen = {{HelloNativeScreen}} {
+        width: Fill, height: Fill
+        flow: Down
+        spacing: 12
+        align: {x: 0.5, y: 0.5}
+
+        title = <Label> {
+            text: "Hello from a native plugin"
+            draw_text: { text_style: { font_size: 16.0 } }
+        }
+
+        count_btn = <Button> {
+            text: "Click me"
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct HelloNativeScreen {
+    #[deref]
+    view: View,
+
+    #[rust]
+    clicks: usize,
+}
+
+impl Widget for HelloNativeScreen {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let actions = cx.capture_actions(|cx| self.view.handle_event(cx, event, scope));
+        let button = self.view.button(ids!(count_btn));
+        if button.clicked(&actions) {
+            self.clicks += 1;
+            button.set_text(cx, &format!("Clicked {} times", self.clicks));
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+fn create_screen(cx: &mut Cx) -> WidgetRef {
+    let module = LiveModuleId::from_str(module_path!()).unwrap();
+    let screen = HelloNativeScreen::new_from_module(cx, module, live_id!(HelloNativeScreen))
+        .expect("HelloNativeScreen is registered by live_design");
+    WidgetRef::new_with_inner(Box::new(screen))
+}
+
+mofa_plugin_api::export_plugin! {
+    id: "hello-native",
+    name: "Hello Native",
+    version: env!("CARGO_PKG_VERSION"),
+    description: "A Makepad screen compiled as a plugin",
+    live_design: live_design,
+    create_screen: create_screen,
+}
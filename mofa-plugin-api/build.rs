@@ -0,0 +1,20 @@
+//! Records the compiler and target a plugin was built with, so the studio
+//! can refuse libraries built by another toolchain.
+
+use std::process::Command;
+
+fn main() {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .unwrap_or_else(|| "unknown rustc".to_string());
+    let target = std::env::var("TARGET").unwrap_or_default();
+
+    println!("cargo:rustc-env=MOFA_PLUGIN_RUSTC={}", version);
+    println!("cargo:rustc-env=MOFA_PLUGIN_TARGET={}", target);
+    println!("cargo:rerun-if-env-changed=RUSTC");
+}
@@ -0,0 +1,254 @@
+//! # MoFA Plugin API
+//!
+//! The C ABI between MoFA Studio and native plugins: `cdylib` crates the
+//! studio loads at runtime from the `library` named in a plugin's
+//! `manifest.json`.
+//!
+//! A plugin exports two symbols, both generated by [`export_plugin!`]:
+//!
+//! - [`ABI_SYMBOL`], a [`PluginAbi`] static the studio reads and checks
+//!   before running any plugin code
+//! - [`ENTRY_SYMBOL`], a function returning the plugin's [`PluginVTable`]
+//!
+//! Makepad's `Cx` and `WidgetRef` cross the boundary as raw pointers. That is
+//! only sound when the plugin and the studio were built by the same compiler
+//! against the same Makepad, which is what [`BUILD_ID`] checks: build plugins
+//! inside the studio's workspace so they share its `Cargo.lock`.
+//!
+//! ```rust,ignore
+//! use makepad_widgets::*;
+//!
+//! fn create_screen(cx: &mut Cx) -> WidgetRef { /* ... */ }
+//!
+//! mofa_plugin_api::export_plugin! {
+//!     id: "hello-native",
+//!     name: "Hello Native",
+//!     version: env!("CARGO_PKG_VERSION"),
+//!     description: "A screen compiled as a plugin",
+//!     live_design: live_design,
+//!     create_screen: create_screen,
+//! }
+//! ```
+
+use std::ffi::{c_void, CStr, CString};
+use std::os::raw::c_char;
+
+/// Bumped whenever [`PluginAbi`], [`PluginVTable`] or [`PluginInfo`] change
+pub const ABI_VERSION: u32 = 1;
+
+/// Name of the exported [`PluginAbi`] static
+pub const ABI_SYMBOL: &str = "mofa_plugin_abi";
+
+/// Name of the exported [`EntryFn`]
+pub const ENTRY_SYMBOL: &str = "mofa_plugin_entry";
+
+/// Compiler, target and API version this crate was built with. Host and
+/// plugin must agree on it exactly.
+pub const BUILD_ID: &str = concat!(
+    "mofa-plugin-api ",
+    env!("CARGO_PKG_VERSION"),
+    "; ",
+    env!("MOFA_PLUGIN_RUSTC"),
+    "; ",
+    env!("MOFA_PLUGIN_TARGET")
+);
+
+const BUILD_ID_LEN: usize = 256;
+
+/// Version record a plugin exports as [`ABI_SYMBOL`]. Plain data, so the
+/// host can check it without calling into the plugin.
+#[repr(C)]
+pub struct PluginAbi {
+    pub abi_version: u32,
+    /// [`BUILD_ID`], NUL-padded
+    pub build_id: [u8; BUILD_ID_LEN],
+}
+
+impl PluginAbi {
+    /// The record of this build
+    pub const CURRENT: PluginAbi = PluginAbi {
+        abi_version: ABI_VERSION,
+        build_id: padded(BUILD_ID),
+    };
+
+    /// Build id as text, without the padding
+    pub fn build_id(&self) -> String {
+        let len = self.build_id.iter().position(|&b| b == 0).unwrap_or(BUILD_ID_LEN);
+        String::from_utf8_lossy(&self.build_id[..len]).into_owned()
+    }
+
+    /// Whether a plugin exporting this record can be used by this build
+    pub fn check(&self) -> Result<(), String> {
+        if self.abi_version != ABI_VERSION {
+            return Err(format!(
+                "plugin uses ABI version {}, the studio needs {}",
+                self.abi_version, ABI_VERSION
+            ));
+        }
+        let current = Self::CURRENT.build_id();
+        let build_id = self.build_id();
+        if build_id != current {
+            return Err(format!("plugin was built with \"{}\", the studio with \"{}\"", build_id, current));
+        }
+        Ok(())
+    }
+}
+
+/// Copy `text` into a NUL-padded array, truncated to leave a trailing NUL
+const fn padded(text: &str) -> [u8; BUILD_ID_LEN] {
+    let bytes = text.as_bytes();
+    let mut out = [0u8; BUILD_ID_LEN];
+    let mut i = 0;
+    while i < bytes.len() && i < BUILD_ID_LEN - 1 {
+        out[i] = bytes[i];
+        i += 1;
+    }
+    out
+}
+
+/// What a plugin says about itself. The strings are NUL-terminated and owned
+/// by the plugin for as long as it is loaded.
+#[repr(C)]
+pub struct PluginInfo {
+    pub id: *const c_char,
+    pub name: *const c_char,
+    pub version: *const c_char,
+    pub description: *const c_char,
+}
+
+impl PluginInfo {
+    /// Copy the fields out as `[id, name, version, description]`
+    ///
+    /// # Safety
+    /// Every field must point to a NUL-terminated string or be null.
+    pub unsafe fn to_strings(&self) -> [String; 4] {
+        let read = |ptr: *const c_char| {
+            if ptr.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(ptr).to_string_lossy().into_owned()
+            }
+        };
+        [read(self.id), read(self.name), read(self.version), read(self.description)]
+    }
+}
+
+/// A [`PluginInfo`] together with the strings it points to; what
+/// [`export_plugin!`] keeps in a static
+pub struct OwnedPluginInfo {
+    _strings: [CString; 4],
+    info: PluginInfo,
+}
+
+// The pointers only refer to the CStrings stored alongside them, which are
+// never mutated
+unsafe impl Send for OwnedPluginInfo {}
+unsafe impl Sync for OwnedPluginInfo {}
+
+impl OwnedPluginInfo {
+    /// Interior NUL bytes are dropped
+    pub fn new(id: &str, name: &str, version: &str, description: &str) -> Self {
+        let c = |text: &str| CString::new(text.replace('\0', "")).unwrap_or_default();
+        let strings = [c(id), c(name), c(version), c(description)];
+        let info = PluginInfo {
+            id: strings[0].as_ptr(),
+            name: strings[1].as_ptr(),
+            version: strings[2].as_ptr(),
+            description: strings[3].as_ptr(),
+        };
+        Self { _strings: strings, info }
+    }
+
+    pub fn as_ptr(&self) -> *const PluginInfo {
+        &self.info
+    }
+}
+
+/// Functions a plugin provides. None of them unwinds into the host: a panic
+/// is caught in the plugin and reported as null or `false`.
+#[repr(C)]
+pub struct PluginVTable {
+    /// The plugin's [`PluginInfo`]; null if it panicked
+    pub info: unsafe extern "C" fn() -> *const PluginInfo,
+    /// Register the plugin's widgets. `cx` is the host's `&mut Cx`. Returns
+    /// `false` if the plugin panicked.
+    pub live_design: unsafe extern "C" fn(cx: *mut c_void) -> bool,
+    /// Create the plugin's screen, called after `live_design`. Returns a
+    /// `Box<WidgetRef>` as a raw pointer, owned by the caller from then on;
+    /// null if the plugin panicked.
+    pub create_screen: unsafe extern "C" fn(cx: *mut c_void) -> *mut c_void,
+}
+
+/// Signature of [`ENTRY_SYMBOL`]
+pub type EntryFn = unsafe extern "C" fn() -> *const PluginVTable;
+
+/// Export the symbols the studio looks for. `live_design` and
+/// `create_screen` are plain Rust functions taking `&mut Cx`; the second
+/// returns a `WidgetRef`.
+#[macro_export]
+macro_rules! export_plugin {
+    (
+        id: $id:expr,
+        name: $name:expr,
+        version: $version:expr,
+        description: $description:expr,
+        live_design: $live_design:path,
+        create_screen: $create_screen:path $(,)?
+    ) => {
+        #[no_mangle]
+        #[allow(non_upper_case_globals)]
+        pub static mofa_plugin_abi: $crate::PluginAbi = $crate::PluginAbi::CURRENT;
+
+        #[no_mangle]
+        pub extern "C" fn mofa_plugin_entry() -> *const $crate::PluginVTable {
+            extern "C" fn plugin_info() -> *const $crate::PluginInfo {
+                static INFO: ::std::sync::OnceLock<$crate::OwnedPluginInfo> = ::std::sync::OnceLock::new();
+                ::std::panic::catch_unwind(|| {
+                    INFO.get_or_init(|| $crate::OwnedPluginInfo::new($id, $name, $version, $description))
+                        .as_ptr()
+                })
+                .unwrap_or(::std::ptr::null())
+            }
+
+            extern "C" fn plugin_live_design(cx: *mut ::std::ffi::c_void) -> bool {
+                ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                    $live_design(unsafe { &mut *cx.cast() })
+                }))
+                .is_ok()
+            }
+
+            extern "C" fn plugin_create_screen(cx: *mut ::std::ffi::c_void) -> *mut ::std::ffi::c_void {
+                ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                    let screen = $create_screen(unsafe { &mut *cx.cast() });
+                    ::std::boxed::Box::into_raw(::std::boxed::Box::new(screen)).cast()
+                }))
+                .unwrap_or(::std::ptr::null_mut())
+            }
+
+            // Named apart from the user's functions, which the bodies call
+            static VTABLE: $crate::PluginVTable = $crate::PluginVTable {
+                info: plugin_info,
+                live_design: plugin_live_design,
+                create_screen: plugin_create_screen,
+            };
+            &VTABLE
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abi_check() {
+        assert_eq!(PluginAbi::CURRENT.check(), Ok(()));
+        assert_eq!(PluginAbi::CURRENT.build_id(), BUILD_ID);
+
+        let old = PluginAbi { abi_version: 0, ..PluginAbi::CURRENT };
+        assert!(old.check().unwrap_err().contains("ABI version 0"));
+
+        let other = PluginAbi { abi_version: ABI_VERSION, build_id: padded("mofa-plugin-api 0.1.0; rustc 1.0.0") };
+        assert!(other.check().unwrap_err().contains("rustc 1.0.0"));
+    }
+}
@@ -9,11 +9,15 @@
 
 use makepad_widgets::*;
 use mofa_studio_shell::widgets::sidebar::{SidebarWidgetRefExt, SidebarAction};
+use mofa_studio_shell::widgets::running_panel::{RunningPanelAction, RunningPanelWidgetRefExt};
+use mofa_studio_shell::widgets::toasts::ToastStackWidgetRefExt;
 use mofa_ui::{MofaTheme, MofaAppData};
 use mofa_dora_bridge::SharedDoraState;
 
 use std::sync::OnceLock;
 use crate::cli::Args;
+use crate::session_journal::SessionJournal;
+use crate::diagnostics::{self, BundleRun};
 
 // ============================================================================
 // CLI ARGS STORAGE
@@ -32,17 +36,66 @@ pub fn get_cli_args() -> &'static Args {
     CLI_ARGS.get_or_init(Args::default)
 }
 
+// ============================================================================
+// SESSION STORAGE
+// ============================================================================
+
+/// Why the studio started in safe mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeModeReason {
+    /// `--safe-mode` on the command line
+    Requested,
+    /// This many runs in a row ended without a clean shutdown
+    UncleanExits(u32),
+}
+
+impl SafeModeReason {
+    /// Explanation shown in the safe-mode banner
+    pub fn message(&self) -> String {
+        let held_back = "plugins, app servers and web content are turned off";
+        match self {
+            Self::Requested => format!("Safe mode: {}.", held_back),
+            Self::UncleanExits(count) => format!(
+                "MoFA Studio did not shut down cleanly the last {} times, so it started in safe mode: {}.",
+                count, held_back
+            ),
+        }
+    }
+}
+
+/// Session journal and safe-mode state (set once at startup)
+struct Session {
+    journal: SessionJournal,
+    safe_mode: Option<SafeModeReason>,
+}
+
+static SESSION: OnceLock<Session> = OnceLock::new();
+
+/// Set the session journal and safe-mode reason (called from main.rs before app starts)
+pub fn set_session(journal: SessionJournal, safe_mode: Option<SafeModeReason>) {
+    SESSION.set(Session { journal, safe_mode }).ok();
+}
+
 // App plugin system imports
-use mofa_widgets::{MofaApp, AppRegistry, TimerControl, PageRouter, PageId, tab_clicked};
+use mofa_widgets::{MofaApp, AppRegistry, TabLifecycle, TimerControl, PageRouter, PageId, tab_clicked};
+use mofa_widgets::tour::{self, Tour, TourBuilder};
 use mofa_widgets::webview::WebViewContainerWidgetRefExt;
-use mofa_widgets::plugins::{PluginLoader, PluginScreenWidgetRefExt};
+use mofa_widgets::plugins::{system_language, ControlServer, PluginLoader, PluginScreenWidgetRefExt};
+use mofa_widgets::plugins::control::NotificationLevel;
+use mofa_widgets::safe_mode::{SafeMode, SafeModeFeature};
+use mofa_widgets::diagnostic_bundle;
+use mofa_widgets::handoff::HandoffBus;
+use mofa_widgets::shortcuts::ShortcutRegistry;
+use crate::plugin_control::ShellStudioState;
+use crate::quick_capture::QuickCapture;
+use crate::window_status::WindowStatus;
 use std::sync::{Arc, Mutex};
 use mofa_fm::{MoFaFMApp, MoFaFMScreenWidgetRefExt};
 use mofa_fm_web::MoFaFmWebApp;
 use mofa_fm_web::screen::MofaFmWebScreenWidgetRefExt;
 use mofa_debate::MoFaDebateApp;
 use mofa_settings::MoFaSettingsApp;
-use mofa_webview_demo::MoFaWebViewDemoApp;
+use mofa_webview_demo::{MoFaWebViewDemoApp, WebViewDemoScreenWidgetRefExt};
 use mofa_personal_news::MoFaPersonalNewsApp;
 use mofa_personal_news::screen::PersonalNewsScreenWidgetRefExt;
 use mofa_transcriber::MoFaTranscriberApp;
@@ -62,6 +115,9 @@ use mofa_converter::MoFaConverterApp;
 use mofa_converter::screen::ConverterScreenWidgetRefExt;
 use mofa_settings::data::Preferences;
 use mofa_settings::screen::SettingsScreenWidgetRefExt;
+use mofa_settings::app_data_view::{AppDataTarget, AppDataViewAction};
+use mofa_settings::shortcuts_view::ShortcutsViewAction;
+use mofa_settings::providers_panel::ProvidersPanelAction;
 
 // ============================================================================
 // TAB IDENTIFIER
@@ -95,13 +151,20 @@ live_design! {
     use mofa_widgets::theme::SLATE_600;
     use mofa_widgets::theme::SLATE_700;
     use mofa_widgets::theme::SLATE_800;
+    use mofa_widgets::theme::SLATE_900;
     use mofa_widgets::theme::GRAY_700;
     use mofa_widgets::theme::DIVIDER_DARK;
     use mofa_widgets::theme::BORDER_DARK;
+    use mofa_widgets::theme::WHITE;
+    use mofa_widgets::theme::SLATE_400;
+    use mofa_widgets::theme::BLUE_500;
+    use mofa_widgets::theme::FONT_SEMIBOLD;
 
     // Import extracted widgets
     use mofa_studio_shell::widgets::sidebar::Sidebar;
     use mofa_studio_shell::widgets::dashboard::Dashboard;
+    use mofa_studio_shell::widgets::running_panel::RunningPanel;
+    use mofa_studio_shell::widgets::toasts::ToastStack;
 
     // ------------------------------------------------------------------------
     // App Window
@@ -346,6 +409,176 @@ live_design! {
                     }
                 }
             }
+
+            // "What's running" panel, opened from the sidebar footer
+            running_overlay = <View> {
+                width: Fill, height: Fill
+                flow: Overlay
+                align: {x: 0.5, y: 0.5}
+                visible: false
+                show_bg: true
+                draw_bg: {
+                    fn pixel(self) -> vec4 {
+                        return vec4(0.0, 0.0, 0.0, 0.55);
+                    }
+                }
+
+                running_card = <View> {
+                    width: 860, height: 520
+                    show_bg: true
+                    draw_bg: {
+                        instance dark_mode: 0.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 8.0);
+                            sdf.fill(mix((WHITE), (SLATE_900), self.dark_mode));
+                            sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 8.0);
+                            sdf.stroke(mix((BORDER), (BORDER_DARK), self.dark_mode), 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    running_panel = <RunningPanel> {}
+                }
+            }
+
+            // Toasts posted by plugins, bottom-right
+            toasts = <ToastStack> {}
+
+            // Onboarding tour: dims the window except for a cutout around the
+            // highlighted control, with a caption card next to it
+            tour_overlay = <View> {
+                width: Fill, height: Fill
+                flow: Overlay
+                visible: false
+
+                spotlight = <View> {
+                    width: Fill, height: Fill
+                    show_bg: true
+                    draw_bg: {
+                        // Cutout in window coordinates; zero width for none
+                        instance cutout_x: 0.0
+                        instance cutout_y: 0.0
+                        instance cutout_w: 0.0
+                        instance cutout_h: 0.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.rect(0., 0., self.rect_size.x, self.rect_size.y);
+                            if self.cutout_w > 0.0 {
+                                sdf.box(self.cutout_x, self.cutout_y, self.cutout_w, self.cutout_h, 6.0);
+                                sdf.subtract();
+                            }
+                            sdf.fill(vec4(0.0, 0.0, 0.0, 0.55));
+                            if self.cutout_w > 0.0 {
+                                sdf.box(self.cutout_x, self.cutout_y, self.cutout_w, self.cutout_h, 6.0);
+                                sdf.stroke((BLUE_500), 2.0);
+                            }
+                            return sdf.result;
+                        }
+                    }
+                }
+
+                tour_card = <View> {
+                    width: 320, height: Fit
+                    abs_pos: vec2(540.0, 375.0)
+                    padding: 16
+                    flow: Down
+                    spacing: 8
+                    show_bg: true
+                    draw_bg: {
+                        instance dark_mode: 0.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 8.0);
+                            sdf.fill(mix((WHITE), (SLATE_800), self.dark_mode));
+                            sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 8.0);
+                            sdf.stroke(mix((BORDER), (BORDER_DARK), self.dark_mode), 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    tour_progress = <Label> {
+                        draw_text: {
+                            instance dark_mode: 0.0
+                            text_style: { font_size: 10.0 }
+                            fn get_color(self) -> vec4 {
+                                return mix((SLATE_500), (SLATE_400), self.dark_mode);
+                            }
+                        }
+                        text: ""
+                    }
+
+                    tour_title = <Label> {
+                        draw_text: {
+                            instance dark_mode: 0.0
+                            text_style: <FONT_SEMIBOLD>{ font_size: 13.0 }
+                            fn get_color(self) -> vec4 {
+                                return mix((GRAY_700), (SLATE_50), self.dark_mode);
+                            }
+                        }
+                        text: ""
+                    }
+
+                    tour_text = <Label> {
+                        width: Fill
+                        draw_text: {
+                            instance dark_mode: 0.0
+                            wrap: Word
+                            text_style: { font_size: 11.0 }
+                            fn get_color(self) -> vec4 {
+                                return mix((SLATE_600), (SLATE_300), self.dark_mode);
+                            }
+                        }
+                        text: ""
+                    }
+
+                    tour_buttons = <View> {
+                        width: Fill, height: Fit
+                        margin: {top: 4}
+                        flow: Right
+                        spacing: 8
+                        align: {x: 1.0, y: 0.5}
+
+                        tour_skip_btn = <Button> {
+                            width: Fit, height: 30
+                            padding: {left: 12, right: 12}
+                            text: "Skip tour"
+                            draw_text: {
+                                instance dark_mode: 0.0
+                                text_style: { font_size: 11.0 }
+                                fn get_color(self) -> vec4 {
+                                    return mix((SLATE_500), (SLATE_400), self.dark_mode);
+                                }
+                            }
+                            draw_bg: {
+                                fn pixel(self) -> vec4 {
+                                    return vec4(0.0, 0.0, 0.0, 0.0);
+                                }
+                            }
+                        }
+
+                        tour_next_btn = <Button> {
+                            width: Fit, height: 30
+                            padding: {left: 16, right: 16}
+                            text: "Next"
+                            draw_text: {
+                                text_style: <FONT_SEMIBOLD>{ font_size: 11.0 }
+                                fn get_color(self) -> vec4 {
+                                    return (WHITE);
+                                }
+                            }
+                            draw_bg: {
+                                fn pixel(self) -> vec4 {
+                                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                    sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 4.0);
+                                    sdf.fill((BLUE_500));
+                                    return sdf.result;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -412,6 +645,36 @@ pub struct App {
     /// Currently active plugin ID (when on Plugin page)
     #[rust]
     current_plugin_id: Option<String>,
+    /// State served to plugin processes by the control API
+    #[rust]
+    plugin_studio_state: Arc<ShellStudioState>,
+    /// Plugin control API (None if it failed to start)
+    #[rust]
+    plugin_control: Option<Arc<ControlServer>>,
+    /// Global shortcut and window for Note Taker quick capture
+    #[rust]
+    quick_capture: QuickCapture,
+    /// Polls the quick-capture shortcut and window
+    #[rust]
+    quick_capture_timer: Timer,
+    /// Window title and dock badge for the active app and background jobs
+    #[rust]
+    window_status: WindowStatus,
+    /// Polls the job registry for the window status
+    #[rust]
+    job_status_timer: Timer,
+    /// Diagnostic bundle being written, polled with the job status
+    #[rust]
+    diagnostic_bundle: Option<BundleRun>,
+    /// Onboarding tour being shown, if any
+    #[rust]
+    tour: Option<Tour>,
+    /// Re-measures the highlighted control while the tour is shown
+    #[rust]
+    tour_timer: Timer,
+    /// Spotlight and caption size last applied, to skip redundant redraws
+    #[rust]
+    tour_layout: Option<(Option<Rect>, DVec2)>,
 }
 
 impl LiveHook for App {
@@ -449,6 +712,7 @@ impl LiveHook for App {
         // Initialize theme using MofaTheme from mofa-ui
         self.theme = MofaTheme::default();
         self.theme.set_dark_mode(use_dark_mode);
+        mofa_widgets::webview::theme_bridge::set_accent(self.theme.accent_color.web_accent());
         self.app_data.set_dark_mode(use_dark_mode);
 
         ::log::debug!(
@@ -458,15 +722,12 @@ impl LiveHook for App {
             prefs.dark_mode
         );
 
-        // Initialize plugin loader and scan for plugins
-        let mut loader = PluginLoader::new();
-        let plugins = loader.scan_plugins();
-        if !plugins.is_empty() {
-            ::log::info!("Loaded {} plugin(s): {:?}", plugins.len(), plugins);
+        self.plugin_studio_state.set_dark_mode(use_dark_mode);
+        if SafeMode::global().allows(SafeModeFeature::Plugins) {
+            self.load_plugins();
         } else {
-            ::log::info!("No plugins found in {:?}", loader.plugins_dir());
+            ::log::info!("Safe mode: plugins are not loaded");
         }
-        self.plugin_loader = Arc::new(Mutex::new(loader));
     }
 }
 
@@ -527,6 +788,8 @@ impl LiveRegister for App {
         mofa_studio_shell::widgets::sidebar::live_design(cx);
         mofa_studio_shell::widgets::tabs::live_design(cx);
         mofa_studio_shell::widgets::dashboard::live_design(cx);
+        mofa_studio_shell::widgets::running_panel::live_design(cx);
+        mofa_studio_shell::widgets::toasts::live_design(cx);
     }
 }
 
@@ -549,11 +812,43 @@ impl AppMain for App {
                 // Update header theme toggle icon
                 self.update_theme_toggle_icon(cx);
 
-                // Initialize plugin list in sidebars
-                self.setup_plugin_list(cx);
+                // Initialize plugin lists in the saved (or system) language
+                let language = Preferences::load().language.unwrap_or_else(system_language);
+                self.set_language(cx, &language);
+
+                // Register the quick-capture shortcut, if one is configured
+                self.setup_quick_capture(cx);
+
+                // Title the window after the active app and running jobs
+                self.job_status_timer = cx.start_interval(0.5);
+                self.update_window_status();
+
+                // Walk new users through the shell on first launch
+                if !Preferences::load().tour_completed {
+                    self.start_tour(cx);
+                }
+
+                self.update_safe_mode_banner(cx);
+            }
+        }
+
+        // A clean shutdown resets the crash count in the session journal
+        if let Event::Shutdown | Event::WindowClosed(_) = event {
+            if let Some(session) = SESSION.get() {
+                session.journal.end();
             }
         }
 
+        if self.quick_capture_timer.is_event(event).is_some() {
+            self.poll_quick_capture(cx);
+        }
+
+        if self.job_status_timer.is_event(event).is_some() {
+            self.update_window_status();
+            self.poll_diagnostic_bundle(cx);
+            self.poll_handoffs(cx);
+        }
+
         // Window resize handling
         self.handle_window_resize(cx, event);
 
@@ -589,6 +884,14 @@ impl AppMain for App {
         self.handle_mofa_hero_buttons(cx, event);
         self.handle_tab_clicks(cx, &actions);
         self.handle_tab_close_clicks(cx, event);
+        self.handle_app_data_reset(cx, &actions);
+        self.handle_diagnostic_bundle(cx, &actions);
+        self.handle_shortcut_changes(cx, &actions);
+        self.handle_tour(cx, event, &actions);
+        self.handle_running_panel(cx, event, &actions);
+        self.handle_safe_mode_banner(cx, &actions);
+
+        self.poll_plugin_notifications(cx);
     }
 }
 
@@ -805,10 +1108,17 @@ impl App {
         // Check for plugin selection action from sidebar
         for action in actions {
             if let Some(wa) = action.as_widget_action() {
-                if let SidebarAction::PluginSelected(plugin_id) = wa.cast() {
-                    ::log::info!("Plugin selected: {}", plugin_id);
-                    self.navigate_to_plugin(cx, &plugin_id);
-                    return;
+                match wa.cast() {
+                    SidebarAction::PluginSelected(plugin_id) => {
+                        ::log::info!("Plugin selected: {}", plugin_id);
+                        self.navigate_to_plugin(cx, &plugin_id);
+                        return;
+                    }
+                    SidebarAction::ShowRunning => {
+                        self.open_running_panel(cx);
+                        return;
+                    }
+                    _ => {}
                 }
             }
         }
@@ -853,14 +1163,16 @@ impl App {
             return; // Already on this page
         }
 
-        // Stop timers on old page if it was FM
+        // Stop timers on old page if it was FM, keeping its scroll and focus
         if old_page == Some(PageId::MofaFM) {
-            self.ui.mo_fa_fmscreen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.fm_page)).stop_timers(cx);
+            let fm = self.ui.mo_fa_fmscreen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.fm_page));
+            fm.on_deactivate(cx);
+            fm.stop_timers(cx);
         }
 
         // Deactivate WebView when leaving WebView Demo page
         if old_page == Some(PageId::WebViewDemo) {
-            self.ui.web_view_container(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.webview_demo_page.content.webview_area.webview_wrapper.webview))
+            self.ui.web_view_demo_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.webview_demo_page))
                 .set_active(cx, false);
         }
 
@@ -927,17 +1239,20 @@ impl App {
         // Update page visibility
         self.update_page_visibility(cx);
 
-        // Update hero title panel
+        // Update hero title panel and window title
         self.update_hero_title(cx, page);
+        self.update_window_status();
 
-        // Start timers on new page if it's FM
+        // Start timers on new page if it's FM, restoring its scroll and focus
         if page == PageId::MofaFM {
-            self.ui.mo_fa_fmscreen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.fm_page)).start_timers(cx);
+            let fm = self.ui.mo_fa_fmscreen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.fm_page));
+            fm.start_timers(cx);
+            fm.on_activate(cx);
         }
 
         // Activate WebView when entering WebView Demo page
         if page == PageId::WebViewDemo {
-            self.ui.web_view_container(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.webview_demo_page.content.webview_area.webview_wrapper.webview))
+            self.ui.web_view_demo_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.webview_demo_page))
                 .set_active(cx, true);
         }
 
@@ -945,72 +1260,54 @@ impl App {
         if page == PageId::MofaFMWeb {
             self.ui.web_view_container(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.mofa_fm_web_page.content.webview_area.webview_wrapper.webview))
                 .set_active(cx, true);
-            self.ui.mofa_fm_web_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.mofa_fm_web_page))
-                .start_server(cx);
         }
 
         // Activate WebView when entering Personal News page
         if page == PageId::PersonalNews {
             self.ui.web_view_container(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.personal_news_page.content.webview_area.webview_wrapper.webview))
                 .set_active(cx, true);
-            self.ui.personal_news_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.personal_news_page))
-                .start_server(cx);
         }
 
         // Activate WebView when entering Transcriber page
         if page == PageId::Transcriber {
             self.ui.web_view_container(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.transcriber_page.content.webview_area.webview_wrapper.webview))
                 .set_active(cx, true);
-            self.ui.transcriber_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.transcriber_page))
-                .start_server(cx);
         }
 
         // Activate WebView when entering Podcast Factory page
         if page == PageId::PodcastFactory {
             self.ui.web_view_container(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.podcast_factory_page.content.webview_area.webview_wrapper.webview))
                 .set_active(cx, true);
-            self.ui.podcast_factory_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.podcast_factory_page))
-                .start_server(cx);
         }
 
         // Activate WebView when entering Note Taker page
         if page == PageId::NoteTaker {
             self.ui.web_view_container(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.note_taker_page.content.webview_area.webview_wrapper.webview))
                 .set_active(cx, true);
-            self.ui.note_taker_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.note_taker_page))
-                .start_server(cx);
         }
 
         // Activate WebView when entering Hello World page
         if page == PageId::HelloWorld {
             self.ui.web_view_container(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.hello_world_page.content.webview_area.webview_wrapper.webview))
                 .set_active(cx, true);
-            self.ui.hello_world_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.hello_world_page))
-                .start_server(cx);
         }
 
         // Activate WebView when entering Hello World (Rust) page
         if page == PageId::HelloWorldRust {
             self.ui.web_view_container(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.hello_world_rust_page.content.webview_area.webview_wrapper.webview))
                 .set_active(cx, true);
-            self.ui.hello_world_rust_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.hello_world_rust_page))
-                .start_server(cx);
         }
 
         // Activate WebView when entering WebView Placeholder page
         if page == PageId::WebViewPlaceholder {
             self.ui.web_view_container(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.webview_placeholder_page.content.webview_area.webview_wrapper.webview))
                 .set_active(cx, true);
-            self.ui.web_view_placeholder_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.webview_placeholder_page))
-                .start_server(cx);
         }
 
         // Activate WebView when entering Converter page
         if page == PageId::Converter {
             self.ui.web_view_container(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.converter_page.content.webview_area.webview_wrapper.webview))
                 .set_active(cx, true);
-            self.ui.converter_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.converter_page))
-                .start_server(cx);
         }
 
         // Activate PluginScreen WebView when entering Plugin page
@@ -1019,9 +1316,57 @@ impl App {
                 .set_active(cx, true);
         }
 
+        // Start the page's server, unless safe mode holds servers back
+        if SafeMode::global().allows(SafeModeFeature::Servers) {
+            self.start_page_server(cx, page);
+        }
+
         self.ui.redraw(cx);
     }
 
+    /// Start the local server behind a built-in page, if it has one
+    fn start_page_server(&mut self, cx: &mut Cx, page: PageId) {
+        match page {
+            PageId::MofaFMWeb => {
+                self.ui.mofa_fm_web_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.mofa_fm_web_page))
+                    .start_server(cx);
+            }
+            PageId::PersonalNews => {
+                self.ui.personal_news_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.personal_news_page))
+                    .start_server(cx);
+            }
+            PageId::Transcriber => {
+                self.ui.transcriber_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.transcriber_page))
+                    .start_server(cx);
+            }
+            PageId::PodcastFactory => {
+                self.ui.podcast_factory_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.podcast_factory_page))
+                    .start_server(cx);
+            }
+            PageId::NoteTaker => {
+                self.ui.note_taker_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.note_taker_page))
+                    .start_server(cx);
+            }
+            PageId::HelloWorld => {
+                self.ui.hello_world_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.hello_world_page))
+                    .start_server(cx);
+            }
+            PageId::HelloWorldRust => {
+                self.ui.hello_world_rust_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.hello_world_rust_page))
+                    .start_server(cx);
+            }
+            PageId::WebViewPlaceholder => {
+                self.ui.web_view_placeholder_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.webview_placeholder_page))
+                    .start_server(cx);
+            }
+            PageId::Converter => {
+                self.ui.converter_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.converter_page))
+                    .start_server(cx);
+            }
+            _ => {}
+        }
+    }
+
     /// Update page visibility based on router state
     fn update_page_visibility(&mut self, cx: &mut Cx) {
         let current = self.page_router.current();
@@ -1078,36 +1423,117 @@ impl App {
         // Store current plugin ID
         self.current_plugin_id = Some(plugin_id.to_string());
 
-        // Bind plugin to PluginScreen and auto-start server
+        // Bind plugin to PluginScreen and auto-start server (not in safe mode)
         let plugin_screen = self.ui.plugin_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.plugin_page));
-        plugin_screen.bind_plugin_and_start(cx, plugin_id.to_string(), self.plugin_loader.clone());
+        plugin_screen.set_dev_mode(cx, get_cli_args().dev);
+        if SafeMode::global().allows(SafeModeFeature::Servers) {
+            plugin_screen.bind_plugin_and_start(cx, plugin_id.to_string(), self.plugin_loader.clone());
+        } else {
+            plugin_screen.bind_plugin(cx, plugin_id.to_string(), self.plugin_loader.clone());
+        }
 
         // Update hero title with plugin info
+        self.update_plugin_title(cx);
+
+        // Navigate to plugin page
+        self.navigate_to_page(cx, PageId::Plugin);
+    }
+
+    /// Show the current plugin's name and description in the hero title
+    fn update_plugin_title(&mut self, cx: &mut Cx) {
+        let Some(ref plugin_id) = self.current_plugin_id else {
+            return;
+        };
         if let Ok(loader) = self.plugin_loader.lock() {
             if let Some(plugin) = loader.get_plugin(plugin_id) {
                 self.ui.label(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.hero_title_panel.title_container.app_title))
-                    .set_text(cx, &plugin.manifest.name);
+                    .set_text(cx, plugin.name(loader.language()));
                 self.ui.label(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.hero_title_panel.title_container.app_description))
-                    .set_text(cx, &plugin.manifest.description);
+                    .set_text(cx, plugin.description(loader.language()));
             }
         }
+    }
 
-        // Navigate to plugin page
-        self.navigate_to_page(cx, PageId::Plugin);
+    /// Switch the language plugin names and descriptions are shown in, and
+    /// refresh every place that lists them
+    pub fn set_language(&mut self, cx: &mut Cx, language: &str) {
+        if let Ok(mut loader) = self.plugin_loader.lock() {
+            loader.set_language(language);
+        }
+        self.setup_plugin_list(cx);
+        self.setup_app_data_targets(cx);
+
+        if self.page_router.current() == Some(PageId::Plugin) {
+            self.update_plugin_title(cx);
+        }
+        self.ui.plugin_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.plugin_page))
+            .refresh_plugin_name(cx);
+        self.update_window_status();
+    }
+
+    /// Start the plugin control API and scan the plugins folder
+    fn load_plugins(&mut self) {
+        // Start the plugin control API before any plugin process is launched
+        match ControlServer::start(self.plugin_studio_state.clone()) {
+            Ok(control) => {
+                ::log::info!("Plugin control API listening on {}", control.url());
+                self.plugin_control = Some(Arc::new(control));
+            }
+            Err(e) => ::log::warn!("Plugin control API unavailable: {}", e),
+        }
+
+        // Initialize plugin loader and scan for plugins, keeping the language
+        let language = self.plugin_loader.lock().ok().map(|loader| loader.language().to_string());
+        let mut loader = PluginLoader::new();
+        if let Some(language) = language {
+            loader.set_language(&language);
+        }
+        if let Some(ref control) = self.plugin_control {
+            loader.set_control_server(control.clone());
+        }
+        let plugins = loader.scan_plugins();
+        if !plugins.is_empty() {
+            ::log::info!("Loaded {} plugin(s): {:?}", plugins.len(), plugins);
+        } else {
+            ::log::info!("No plugins found in {:?}", loader.plugins_dir());
+        }
+        self.plugin_loader = Arc::new(Mutex::new(loader));
+    }
+
+    /// Show notifications posted by plugins through the control API as
+    /// toasts; they are logged too
+    fn poll_plugin_notifications(&mut self, cx: &mut Cx) {
+        let Some(ref control) = self.plugin_control else {
+            return;
+        };
+        for n in control.take_notifications() {
+            match n.level {
+                NotificationLevel::Error => ::log::error!("[Plugin {}] {}: {}", n.plugin_id, n.title, n.message),
+                NotificationLevel::Warning => ::log::warn!("[Plugin {}] {}: {}", n.plugin_id, n.title, n.message),
+                NotificationLevel::Info | NotificationLevel::Success => {
+                    ::log::info!("[Plugin {}] {}: {}", n.plugin_id, n.title, n.message)
+                }
+            }
+            let title = if n.title.is_empty() { &n.plugin_id } else { &n.title };
+            self.ui.toast_stack(ids!(toasts)).show(cx, title, &n.message, n.level);
+        }
     }
 
     /// Setup plugin list in sidebars
     fn setup_plugin_list(&mut self, cx: &mut Cx) {
         // Get plugins from loader
-        let plugins: Vec<(String, String)> = if let Ok(loader) = self.plugin_loader.lock() {
+        let mut plugins: Vec<(String, String)> = if let Ok(loader) = self.plugin_loader.lock() {
             loader.plugins()
                 .filter(|p| p.manifest.show_in_sidebar)
-                .map(|p| (p.manifest.id.clone(), p.manifest.name.clone()))
+                .map(|p| (p.manifest.id.clone(), p.name(loader.language()).to_string()))
                 .collect()
         } else {
             vec![]
         };
 
+        // Keep the order stable when the list is rebuilt on a language change
+        plugins.sort();
+
         ::log::info!("Setting up {} plugins in sidebar", plugins.len());
         // Set plugins in both sidebars (even empty, so placeholders can be hidden)
         self.ui.sidebar(ids!(sidebar_menu_overlay.sidebar_content))
@@ -1116,32 +1542,355 @@ impl App {
             .set_plugins(cx, plugins);
     }
 
+    /// Give Settings the data locations declared by every app and plugin
+    fn setup_app_data_targets(&mut self, cx: &mut Cx) {
+        let mut targets: Vec<AppDataTarget> = self.app_registry.apps().iter()
+            .filter(|app| !app.storage.is_empty())
+            .map(|app| AppDataTarget {
+                id: app.id.to_string(),
+                name: app.name.to_string(),
+                locations: app.storage.locations(app.id),
+            })
+            .collect();
+
+        if let Ok(loader) = self.plugin_loader.lock() {
+            targets.extend(loader.plugins().map(|plugin| AppDataTarget {
+                id: plugin.manifest.id.clone(),
+                name: plugin.name(loader.language()).to_string(),
+                locations: plugin.data_locations(),
+            }));
+        }
+
+        self.ui.settings_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.settings_page))
+            .set_app_data_targets(cx, targets.clone());
+        self.ui.settings_screen(ids!(body.tab_overlay.tab_content.settings_tab_page))
+            .set_app_data_targets(cx, targets);
+    }
+
+    /// Run a data reset confirmed in Settings, stopping the app first
+    fn handle_app_data_reset(&mut self, cx: &mut Cx, actions: &[Action]) {
+        for action in actions {
+            if let AppDataViewAction::ResetConfirmed { plan, categories } = action.as_widget_action().cast() {
+                self.stop_app_server(cx, &plan.app_id);
+
+                let report = plan.execute(&categories);
+                ::log::info!("Reset data for {}: {}", plan.app_id, report.summary());
+
+                self.ui.settings_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.settings_page))
+                    .show_reset_report(cx, &plan.app_name, &report);
+                self.ui.settings_screen(ids!(body.tab_overlay.tab_content.settings_tab_page))
+                    .show_reset_report(cx, &plan.app_name, &report);
+            }
+        }
+    }
+
+    /// Ask where to save a diagnostic bundle requested in Settings and start
+    /// writing it in the background
+    fn handle_diagnostic_bundle(&mut self, cx: &mut Cx, actions: &[Action]) {
+        for action in actions {
+            if let AppDataViewAction::CreateDiagnosticBundle { anonymize_home } = action.as_widget_action().cast() {
+                if self.diagnostic_bundle.is_some() {
+                    continue;
+                }
+                let Some(path) = diagnostic_bundle::choose_save_path() else {
+                    continue;
+                };
+
+                let app_prefs = self.app_registry.apps().iter()
+                    .flat_map(|app| diagnostics::declared_prefs(app.id, &app.storage.locations(app.id)))
+                    .collect();
+                let loader = self.plugin_loader.lock().unwrap_or_else(|e| e.into_inner());
+                let sources = diagnostics::sources(&loader, app_prefs);
+                drop(loader);
+
+                self.diagnostic_bundle = Some(BundleRun::start(path, sources, anonymize_home));
+                self.poll_diagnostic_bundle(cx);
+            }
+        }
+    }
+
+    /// Bring forward the app files were just sent to and hand them over
+    fn poll_handoffs(&mut self, cx: &mut Cx) {
+        for app_id in HandoffBus::global().announce() {
+            match app_id.as_str() {
+                "mofa-transcriber" => {
+                    self.navigate_to_page(cx, PageId::Transcriber);
+                    self.ui.transcriber_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.transcriber_page))
+                        .receive_handoffs();
+                }
+                _ => {
+                    let dropped = HandoffBus::global().take(&app_id);
+                    ::log::warn!("Dropped {} hand-off(s) to {}, which does not accept files", dropped.len(), app_id);
+                }
+            }
+        }
+    }
+
+    /// Show the running diagnostic bundle's progress in Settings
+    fn poll_diagnostic_bundle(&mut self, cx: &mut Cx) {
+        let Some(run) = self.diagnostic_bundle.as_ref() else {
+            return;
+        };
+        let (text, finished) = run.status();
+        self.ui.settings_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.settings_page))
+            .show_bundle_status(cx, &text, finished);
+        self.ui.settings_screen(ids!(body.tab_overlay.tab_content.settings_tab_page))
+            .show_bundle_status(cx, &text, finished);
+        if finished {
+            self.diagnostic_bundle = None;
+        }
+    }
+
+    /// Stop the server process owned by an app or plugin, if it runs one
+    fn stop_app_server(&mut self, cx: &mut Cx, app_id: &str) {
+        match app_id {
+            "mofa-fm-web" => self.ui.mofa_fm_web_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.mofa_fm_web_page))
+                .stop_server(cx),
+            "mofa-personal-news" => self.ui.personal_news_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.personal_news_page))
+                .stop_server(cx),
+            "mofa-transcriber" => self.ui.transcriber_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.transcriber_page))
+                .stop_server(cx),
+            "mofa-podcast-factory" => self.ui.podcast_factory_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.podcast_factory_page))
+                .stop_server(cx),
+            "mofa-note-taker" => self.ui.note_taker_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.note_taker_page))
+                .stop_server(cx),
+            "mofa-hello-world" => self.ui.hello_world_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.hello_world_page))
+                .stop_server(cx),
+            "mofa-hello-world-rust" => self.ui.hello_world_rust_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.hello_world_rust_page))
+                .stop_server(cx),
+            "mofa-webview-placeholder" => self.ui.web_view_placeholder_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.webview_placeholder_page))
+                .stop_server(cx),
+            "mofa-converter" => self.ui.converter_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.converter_page))
+                .stop_server(cx),
+            _ => {
+                if let Ok(mut loader) = self.plugin_loader.lock() {
+                    if loader.get_plugin(app_id).is_some() {
+                        loader.stop_plugin(app_id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Register the quick-capture shortcut saved in preferences
+    fn setup_quick_capture(&mut self, cx: &mut Cx) {
+        self.quick_capture_timer = cx.start_interval(0.1);
+        let shortcut = Preferences::load().quick_capture_hotkey;
+        self.apply_quick_capture_shortcut(cx, shortcut);
+    }
+
+    /// Re-register the shortcut after it was changed in Settings
+    fn handle_shortcut_changes(&mut self, cx: &mut Cx, actions: &[Action]) {
+        for action in actions {
+            if let ShortcutsViewAction::QuickCaptureChanged(shortcut) = action.as_widget_action().cast() {
+                self.apply_quick_capture_shortcut(cx, shortcut);
+            }
+        }
+    }
+
+    fn apply_quick_capture_shortcut(&mut self, cx: &mut Cx, shortcut: Option<String>) {
+        let status = match (self.quick_capture.set_shortcut(shortcut.as_deref()), shortcut) {
+            (Ok(()), Some(shortcut)) => {
+                ::log::info!("Quick capture shortcut registered: {}", shortcut);
+                format!("{} opens quick capture", shortcut)
+            }
+            (Ok(()), None) => "Quick capture is disabled".to_string(),
+            (Err(e), _) => {
+                ::log::warn!("Quick capture shortcut not registered: {}", e);
+                e
+            }
+        };
+
+        self.ui.settings_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.settings_page))
+            .show_shortcut_status(cx, &status);
+        self.ui.settings_screen(ids!(body.tab_overlay.tab_content.settings_tab_page))
+            .show_shortcut_status(cx, &status);
+    }
+
+    /// Open the capture window on the shortcut and send what was typed to
+    /// Note Taker, starting its server if needed
+    fn poll_quick_capture(&mut self, cx: &mut Cx) {
+        if !self.quick_capture.is_active() {
+            return;
+        }
+
+        if self.quick_capture.poll_shortcut() {
+            if let Err(e) = self.quick_capture.open_window() {
+                ::log::error!("{}", e);
+            }
+        }
+
+        if let Some(text) = self.quick_capture.poll_window() {
+            let note_taker = self.ui.note_taker_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.note_taker_page));
+            note_taker.start_server(cx);
+            // Undelivered text is queued and retried by Note Taker
+            mofa_note_taker::capture::submit(note_taker.server_url(), &text);
+        }
+    }
+
     /// Update hero title panel with current app info
     fn update_hero_title(&mut self, cx: &mut Cx, page: PageId) {
-        let (title, description) = match page {
-            PageId::MofaFM => ("MoFA FM", "AI-powered audio streaming and voice interface"),
-            PageId::MofaFMWeb => ("MoFA.fm", "Embedded MoFA.fm website"),
-            PageId::Debate => ("MoFA Debate", "Multi-agent debate and discussion platform"),
-            PageId::Settings => ("Settings", "Configure providers and preferences"),
-            PageId::App => ("Demo App", "Select an app from the sidebar"),
-            PageId::WebViewDemo => ("WebView Demo 1", "Demonstrates WebView embedding with wry"),
-            PageId::PersonalNews => ("Personal News", "Personal news broadcast"),
-            PageId::Transcriber => ("AI Transcriber", "Audio/video transcription and summarization"),
-            PageId::Podcast => ("Podcast Generator", "Generate podcast audio from scripts"),
-            PageId::PodcastFactory => ("Book Cast", "Transform books into podcast series"),
-            PageId::NoteTaker => ("Note Taker", "Web-based note taking workspace"),
-            PageId::HelloWorld => ("Hello World (Python)", "WebView demo starter app"),
-            PageId::HelloWorldRust => ("Hello World (Rust)", "Rust-powered WebView demo app"),
-            PageId::WebViewPlaceholder => ("WebView Demo 2", "Placeholder WebView app"),
-            PageId::Converter => ("内容转换器", "在音频、视频与文稿之间自由转换"),
-            PageId::Plugin => ("Plugin", "Dynamic plugin"),
-        };
+        let (title, description) = page_info(page);
 
         self.ui.label(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.hero_title_panel.title_container.app_title))
             .set_text(cx, title);
         self.ui.label(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.hero_title_panel.title_container.app_description))
             .set_text(cx, description);
     }
+
+    /// Title the window after the active tab or app and running jobs, and
+    /// hand app shortcuts to the app in front
+    fn update_window_status(&mut self) {
+        let app_name = self.active_app_name();
+        self.window_status.update(&app_name);
+        ShortcutRegistry::global().set_active_app(self.active_app_id().as_deref());
+    }
+
+    /// Id of the app whose page is in front; `None` while a tab covers it
+    fn active_app_id(&self) -> Option<String> {
+        if self.active_tab.is_some() {
+            return None;
+        }
+        match self.page_router.current().unwrap_or(PageId::MofaFM) {
+            PageId::Plugin => self.current_plugin_id.clone(),
+            page => page_app_id(page).map(str::to_string),
+        }
+    }
+
+    fn active_app_name(&self) -> String {
+        match self.active_tab {
+            Some(TabId::Profile) => return "Profile".to_string(),
+            Some(TabId::Settings) => return "Settings".to_string(),
+            None => {}
+        }
+
+        let page = self.page_router.current().unwrap_or(PageId::MofaFM);
+        if page == PageId::Plugin {
+            if let (Some(id), Ok(loader)) = (&self.current_plugin_id, self.plugin_loader.lock()) {
+                if let Some(plugin) = loader.get_plugin(id) {
+                    return plugin.name(loader.language()).to_string();
+                }
+            }
+        }
+        page_info(page).0.to_string()
+    }
+}
+
+/// Id of the app behind a built-in page
+fn page_app_id(page: PageId) -> Option<&'static str> {
+    let info = match page {
+        PageId::MofaFM => MoFaFMApp::info(),
+        PageId::MofaFMWeb => MoFaFmWebApp::info(),
+        PageId::Debate => MoFaDebateApp::info(),
+        PageId::Settings => MoFaSettingsApp::info(),
+        PageId::WebViewDemo => MoFaWebViewDemoApp::info(),
+        PageId::PersonalNews => MoFaPersonalNewsApp::info(),
+        PageId::Transcriber => MoFaTranscriberApp::info(),
+        PageId::Podcast => MoFaPodcastApp::info(),
+        PageId::PodcastFactory => MoFaPodcastFactoryApp::info(),
+        PageId::NoteTaker => MoFaNoteTakerApp::info(),
+        PageId::HelloWorld => MoFaHelloWorldApp::info(),
+        PageId::HelloWorldRust => MoFaHelloWorldRustApp::info(),
+        PageId::WebViewPlaceholder => MoFaWebViewPlaceholderApp::info(),
+        PageId::Converter => MoFaConverterApp::info(),
+        PageId::App | PageId::Plugin => return None,
+    };
+    Some(info.id)
+}
+
+/// Display name and description of a built-in page
+fn page_info(page: PageId) -> (&'static str, &'static str) {
+    match page {
+        PageId::MofaFM => ("MoFA FM", "AI-powered audio streaming and voice interface"),
+        PageId::MofaFMWeb => ("MoFA.fm", "Embedded MoFA.fm website"),
+        PageId::Debate => ("MoFA Debate", "Multi-agent debate and discussion platform"),
+        PageId::Settings => ("Settings", "Configure providers and preferences"),
+        PageId::App => ("Demo App", "Select an app from the sidebar"),
+        PageId::WebViewDemo => ("WebView Demo 1", "Demonstrates WebView embedding with wry"),
+        PageId::PersonalNews => ("Personal News", "Personal news broadcast"),
+        PageId::Transcriber => ("AI Transcriber", "Audio/video transcription and summarization"),
+        PageId::Podcast => ("Podcast Generator", "Generate podcast audio from scripts"),
+        PageId::PodcastFactory => ("Book Cast", "Transform books into podcast series"),
+        PageId::NoteTaker => ("Note Taker", "Web-based note taking workspace"),
+        PageId::HelloWorld => ("Hello World (Python)", "WebView demo starter app"),
+        PageId::HelloWorldRust => ("Hello World (Rust)", "Rust-powered WebView demo app"),
+        PageId::WebViewPlaceholder => ("WebView Demo 2", "Placeholder WebView app"),
+        PageId::Converter => ("内容转换器", "在音频、视频与文稿之间自由转换"),
+        PageId::Plugin => ("Plugin", "Dynamic plugin"),
+    }
+}
+
+// ============================================================================
+// SAFE MODE METHODS
+// ============================================================================
+
+impl App {
+    /// Show the safe-mode banner with a button per feature still held back,
+    /// or hide it once everything is re-enabled
+    fn update_safe_mode_banner(&mut self, cx: &mut Cx) {
+        let disabled = SafeMode::global().disabled();
+        let reason = SESSION.get().and_then(|session| session.safe_mode);
+        let banner = self.ui.view(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.safe_mode_banner));
+        let Some(reason) = reason.filter(|_| !disabled.is_empty()) else {
+            banner.set_visible(cx, false);
+            return;
+        };
+
+        self.ui.label(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.safe_mode_banner.safe_mode_text))
+            .set_text(cx, &reason.message());
+        for (feature, button) in [
+            (SafeModeFeature::Plugins, ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.safe_mode_banner.enable_plugins_btn)),
+            (SafeModeFeature::Servers, ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.safe_mode_banner.enable_servers_btn)),
+            (SafeModeFeature::WebViews, ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.safe_mode_banner.enable_webviews_btn)),
+        ] {
+            self.ui.button(button).set_visible(cx, disabled.contains(&feature));
+        }
+        banner.set_visible(cx, true);
+        self.ui.redraw(cx);
+    }
+
+    /// Re-enable a feature for the rest of the session from the banner
+    fn handle_safe_mode_banner(&mut self, cx: &mut Cx, actions: &[Action]) {
+        if !SafeMode::global().is_active() {
+            return;
+        }
+
+        let clicked = [
+            (SafeModeFeature::Plugins, ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.safe_mode_banner.enable_plugins_btn)),
+            (SafeModeFeature::Servers, ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.safe_mode_banner.enable_servers_btn)),
+            (SafeModeFeature::WebViews, ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.safe_mode_banner.enable_webviews_btn)),
+        ]
+        .into_iter()
+        .find(|(_, button)| self.ui.button(*button).clicked(actions))
+        .map(|(feature, _)| feature);
+        let Some(feature) = clicked else {
+            return;
+        };
+        if !SafeMode::global().enable(feature) {
+            return;
+        }
+        ::log::info!("Safe mode: {} re-enabled for this session", feature.label());
+
+        match feature {
+            SafeModeFeature::Plugins => {
+                self.load_plugins();
+                self.setup_plugin_list(cx);
+                self.setup_app_data_targets(cx);
+            }
+            // Start the server of the page in front; others start when shown
+            SafeModeFeature::Servers => {
+                if self.active_tab.is_none() {
+                    if let Some(page) = self.page_router.current() {
+                        self.start_page_server(cx, page);
+                    }
+                }
+            }
+            // Active WebView containers initialize on their next draw
+            SafeModeFeature::WebViews => {}
+        }
+        self.update_safe_mode_banner(cx);
+        self.ui.redraw(cx);
+    }
 }
 
 // ============================================================================
@@ -1265,6 +2014,7 @@ impl App {
     pub fn toggle_dark_mode(&mut self, cx: &mut Cx) {
         self.theme.toggle();
         self.app_data.set_dark_mode(self.theme.is_dark());
+        self.plugin_studio_state.set_dark_mode(self.theme.is_dark());
         self.theme_animating = true;
         self.theme_anim_start = Cx::time_now();
 
@@ -1311,6 +2061,14 @@ impl App {
             draw_bg: { dark_mode: (dm) }
         });
 
+        // Apply to safe mode banner
+        self.ui.view(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.safe_mode_banner)).apply_over(cx, live!{
+            draw_bg: { dark_mode: (dm) }
+        });
+        self.ui.label(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.safe_mode_banner.safe_mode_text)).apply_over(cx, live!{
+            draw_text: { dark_mode: (dm) }
+        });
+
         // Apply to pinned sidebar background
         self.ui.view(ids!(pinned_sidebar)).apply_over(cx, live!{
             draw_bg: { dark_mode: (dm) }
@@ -1347,6 +2105,30 @@ impl App {
             draw_bg: { dark_mode: (dm) }
         });
 
+        // Apply to tour caption
+        self.ui.view(ids!(tour_overlay.tour_card)).apply_over(cx, live!{
+            draw_bg: { dark_mode: (dm) }
+        });
+        for path in [
+            ids!(tour_overlay.tour_card.tour_progress),
+            ids!(tour_overlay.tour_card.tour_title),
+            ids!(tour_overlay.tour_card.tour_text),
+        ] {
+            self.ui.label(path).apply_over(cx, live!{
+                draw_text: { dark_mode: (dm) }
+            });
+        }
+        self.ui.button(ids!(tour_overlay.tour_card.tour_buttons.tour_skip_btn)).apply_over(cx, live!{
+            draw_text: { dark_mode: (dm) }
+        });
+
+        // Apply to running panel
+        self.ui.view(ids!(running_overlay.running_card)).apply_over(cx, live!{
+            draw_bg: { dark_mode: (dm) }
+        });
+        self.ui.running_panel(ids!(running_overlay.running_card.running_panel)).update_dark_mode(cx, dm);
+        self.ui.toast_stack(ids!(toasts)).update_dark_mode(cx, dm);
+
         // Apply to tab overlay - only when tabs are open
         if !self.open_tabs.is_empty() {
             self.ui.view(ids!(body.tab_overlay)).apply_over(cx, live!{
@@ -1419,6 +2201,9 @@ impl App {
 
     /// Apply dark mode to screens with a specific value
     fn apply_dark_mode_screens_with_value(&mut self, cx: &mut Cx, dm: f64) {
+        // WebView screens build their page theme from this
+        mofa_widgets::webview::theme_bridge::set_accent(self.theme.accent_color.web_accent());
+
         // Apply to MoFA FM screen
         self.ui.mo_fa_fmscreen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.fm_page))
             .update_dark_mode(cx, dm);
@@ -1584,6 +2369,7 @@ impl App {
             }
         }
 
+        self.update_window_status();
         self.ui.redraw(cx);
     }
 }
@@ -1616,6 +2402,248 @@ impl App {
     }
 }
 
+// ============================================================================
+// ONBOARDING TOUR METHODS
+// ============================================================================
+
+impl App {
+    /// Build the tour for the current page and show its first step
+    fn start_tour(&mut self, cx: &mut Cx) {
+        if self.tour.is_some() {
+            return;
+        }
+
+        // Settings may have been open in a tab over the page
+        self.open_tabs.clear();
+        self.active_tab = None;
+        self.ui.view(ids!(body.tab_overlay)).set_visible(cx, false);
+
+        let shell_steps = TourBuilder::new()
+            .step(
+                ids!(sidebar_trigger_overlay),
+                "Your apps",
+                "Hover over the menu to switch apps, or click it to keep the sidebar open.",
+            )
+            .step(
+                ids!(body.dashboard_wrapper.dashboard_base.header.theme_toggle),
+                "Light and dark",
+                "Switch between light and dark mode. Your choice is remembered.",
+            )
+            .step(
+                ids!(user_btn_overlay),
+                "Settings",
+                "Settings live in this menu: providers and API keys, app data, keyboard shortcuts, and this tour.",
+            );
+        let page = self.page_router.current();
+        self.tour = Some(Self::app_tour_steps(page, shell_steps).build());
+
+        // Native WebViews draw above the window's content, overlay included
+        if let Some(page) = page {
+            self.set_page_webview_active(cx, page, false);
+        }
+
+        self.ui.view(ids!(tour_overlay)).set_visible(cx, true);
+        self.tour_timer = cx.start_interval(0.25);
+        self.show_tour_step(cx);
+    }
+
+    /// Append the steps of the app shown on `page`
+    fn app_tour_steps(page: Option<PageId>, tour: TourBuilder) -> TourBuilder {
+        match page {
+            Some(PageId::MofaFM) => MoFaFMApp::tour(tour),
+            Some(PageId::MofaFMWeb) => MoFaFmWebApp::tour(tour),
+            Some(PageId::Debate) => MoFaDebateApp::tour(tour),
+            Some(PageId::Settings) => MoFaSettingsApp::tour(tour),
+            Some(PageId::WebViewDemo) => MoFaWebViewDemoApp::tour(tour),
+            Some(PageId::PersonalNews) => MoFaPersonalNewsApp::tour(tour),
+            Some(PageId::Transcriber) => MoFaTranscriberApp::tour(tour),
+            Some(PageId::Podcast) => MoFaPodcastApp::tour(tour),
+            Some(PageId::PodcastFactory) => MoFaPodcastFactoryApp::tour(tour),
+            Some(PageId::NoteTaker) => MoFaNoteTakerApp::tour(tour),
+            Some(PageId::HelloWorld) => MoFaHelloWorldApp::tour(tour),
+            Some(PageId::HelloWorldRust) => MoFaHelloWorldRustApp::tour(tour),
+            Some(PageId::WebViewPlaceholder) => MoFaWebViewPlaceholderApp::tour(tour),
+            Some(PageId::Converter) => MoFaConverterApp::tour(tour),
+            Some(PageId::App) | Some(PageId::Plugin) | None => tour,
+        }
+    }
+
+    /// Start the tour from Settings, and step through it
+    fn handle_tour(&mut self, cx: &mut Cx, event: &Event, actions: &[Action]) {
+        for action in actions {
+            if let ProvidersPanelAction::TourClicked = action.as_widget_action().cast() {
+                self.start_tour(cx);
+            }
+        }
+
+        if self.tour.is_none() {
+            return;
+        }
+
+        if self.tour_timer.is_event(event).is_some() {
+            self.update_tour_spotlight(cx);
+        }
+        if let Event::WindowGeomChange(_) = event {
+            self.update_tour_spotlight(cx);
+        }
+
+        if self.ui.button(ids!(tour_overlay.tour_card.tour_buttons.tour_next_btn)).clicked(actions) {
+            let more = self.tour.as_mut().map_or(false, |tour| tour.advance());
+            if more {
+                self.show_tour_step(cx);
+            } else {
+                self.end_tour(cx);
+            }
+        }
+
+        let escape = matches!(event, Event::KeyDown(KeyEvent { key_code: KeyCode::Escape, .. }));
+        if escape || self.ui.button(ids!(tour_overlay.tour_card.tour_buttons.tour_skip_btn)).clicked(actions) {
+            self.end_tour(cx);
+        }
+    }
+
+    /// Fill the caption for the current step and move the spotlight to it
+    fn show_tour_step(&mut self, cx: &mut Cx) {
+        let Some(tour) = &self.tour else { return };
+        let Some(step) = tour.current() else { return };
+
+        self.ui.label(ids!(tour_overlay.tour_card.tour_progress)).set_text(cx, &tour.progress());
+        self.ui.label(ids!(tour_overlay.tour_card.tour_title)).set_text(cx, &step.title);
+        self.ui.label(ids!(tour_overlay.tour_card.tour_text)).set_text(cx, &step.text);
+        let next = if tour.is_last() { "Done" } else { "Next" };
+        self.ui.button(ids!(tour_overlay.tour_card.tour_buttons.tour_next_btn)).set_text(cx, next);
+
+        self.tour_layout = None;
+        self.update_tour_spotlight(cx);
+    }
+
+    /// Cut the spotlight out around the step's target and place the caption.
+    /// Called on a timer and on resize, so the spotlight follows the target
+    /// through window resizes and layout changes such as the pinned sidebar.
+    fn update_tour_spotlight(&mut self, cx: &mut Cx) {
+        let Some(target) = self.tour.as_ref().and_then(|t| t.current()).map(|s| s.target.clone()) else {
+            return;
+        };
+
+        let window = self.ui.area().rect(cx).size;
+        let spotlight = tour::spotlight_rect(self.ui.widget(&target).area().rect(cx), window);
+        // The caption has not been measured before its first draw
+        let card = self.ui.view(ids!(tour_overlay.tour_card)).area().rect(cx).size;
+        let card = if card.y > 0.0 { card } else { dvec2(320.0, 160.0) };
+
+        if self.tour_layout == Some((spotlight, card)) {
+            return;
+        }
+        self.tour_layout = Some((spotlight, card));
+
+        let cutout = spotlight.unwrap_or_default();
+        self.ui.view(ids!(tour_overlay.spotlight)).apply_over(cx, live!{
+            draw_bg: {
+                cutout_x: (cutout.pos.x)
+                cutout_y: (cutout.pos.y)
+                cutout_w: (cutout.size.x)
+                cutout_h: (cutout.size.y)
+            }
+        });
+        let card_pos = tour::caption_pos(spotlight, card, window);
+        self.ui.view(ids!(tour_overlay.tour_card)).apply_over(cx, live!{
+            abs_pos: (card_pos)
+        });
+        self.ui.redraw(cx);
+    }
+
+    /// Hide the tour and remember not to show it on launch again
+    fn end_tour(&mut self, cx: &mut Cx) {
+        if self.tour.take().is_none() {
+            return;
+        }
+        cx.stop_timer(self.tour_timer);
+        self.tour_layout = None;
+        self.ui.view(ids!(tour_overlay)).set_visible(cx, false);
+
+        if let Some(page) = self.page_router.current() {
+            self.set_page_webview_active(cx, page, true);
+        }
+
+        let mut prefs = Preferences::load();
+        if !prefs.tour_completed {
+            prefs.tour_completed = true;
+            if let Err(e) = prefs.save() {
+                eprintln!("Failed to save tour preference: {}", e);
+            }
+        }
+        self.ui.redraw(cx);
+    }
+
+    /// Show what is running across apps over the current page
+    fn open_running_panel(&mut self, cx: &mut Cx) {
+        if self.sidebar_menu_open {
+            self.sidebar_menu_open = false;
+            self.start_sidebar_slide_out(cx);
+        }
+
+        // Native WebViews draw above the window's content, overlay included
+        if let Some(page) = self.page_router.current() {
+            self.set_page_webview_active(cx, page, false);
+        }
+
+        self.ui.view(ids!(running_overlay)).set_visible(cx, true);
+        self.ui.running_panel(ids!(running_overlay.running_card.running_panel))
+            .open(cx, self.plugin_loader.clone());
+        self.ui.redraw(cx);
+    }
+
+    /// Close the running panel on its close button or Escape
+    fn handle_running_panel(&mut self, cx: &mut Cx, event: &Event, actions: &[Action]) {
+        if !self.ui.view(ids!(running_overlay)).visible() {
+            return;
+        }
+
+        let closed = actions.iter().any(|action| {
+            matches!(action.as_widget_action().cast(), RunningPanelAction::Closed)
+        });
+        let escape = matches!(event, Event::KeyDown(KeyEvent { key_code: KeyCode::Escape, .. }));
+        if !closed && !(escape && self.tour.is_none()) {
+            return;
+        }
+
+        self.ui.running_panel(ids!(running_overlay.running_card.running_panel)).close(cx);
+        self.ui.view(ids!(running_overlay)).set_visible(cx, false);
+        if let Some(page) = self.page_router.current() {
+            self.set_page_webview_active(cx, page, true);
+        }
+        self.ui.redraw(cx);
+    }
+
+    /// Hide or show the WebView of a WebView page, without leaving the page
+    fn set_page_webview_active(&mut self, cx: &mut Cx, page: PageId, active: bool) {
+        match page {
+            PageId::WebViewDemo => {
+                self.ui.web_view_demo_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.webview_demo_page))
+                    .set_active(cx, active);
+            }
+            PageId::Plugin => {
+                self.ui.plugin_screen(ids!(body.dashboard_wrapper.dashboard_base.content_area.main_content.content.plugin_page))
+                    .set_active(cx, active);
+            }
+            PageId::MofaFMWeb
+            | PageId::PersonalNews
+            | PageId::Transcriber
+            | PageId::PodcastFactory
+            | PageId::NoteTaker
+            | PageId::HelloWorld
+            | PageId::HelloWorldRust
+            | PageId::WebViewPlaceholder
+            | PageId::Converter => {
+                self.ui.widget(&[page.page_live_id()])
+                    .web_view_container(ids!(content.webview_area.webview_wrapper.webview))
+                    .set_active(cx, active);
+            }
+            _ => {}
+        }
+    }
+}
+
 // ============================================================================
 // APP ENTRY POINT
 // ============================================================================
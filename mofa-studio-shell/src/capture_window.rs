@@ -0,0 +1,127 @@
+//! Quick capture window - runs with `--quick-capture`
+//!
+//! A minimal Makepad app: one always-on-top window with a single text field.
+//! Enter prints the text for the parent studio process (see
+//! [`crate::quick_capture`]) and exits; Escape or closing the window exits
+//! without output.
+
+use makepad_widgets::*;
+use mofa_settings::data::Preferences;
+use crate::quick_capture::format_submission;
+use std::io::Write;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use mofa_widgets::theme::*;
+
+    App = {{CaptureApp}} {
+        ui: <Window> {
+            window: { title: "Quick Capture", inner_size: vec2(560, 72) }
+            show_bg: true
+            draw_bg: {
+                instance dark_mode: 0.0
+                fn pixel(self) -> vec4 {
+                    return mix((WHITE), (SLATE_900), self.dark_mode);
+                }
+            }
+
+            body = <View> {
+                width: Fill, height: Fill
+                padding: 14
+                align: {y: 0.5}
+
+                capture_input = <TextInput> {
+                    width: Fill, height: Fit
+                    padding: {left: 12, right: 12, top: 10, bottom: 10}
+                    empty_text: "Add to today's note..."
+
+                    draw_bg: {
+                        instance dark_mode: 0.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                            sdf.fill(mix(#F1F5F9, #334155, self.dark_mode));
+                            sdf.stroke(#3B82F6, 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    draw_text: {
+                        instance dark_mode: 0.0
+                        text_style: <FONT_REGULAR>{ font_size: 14.0 }
+                        fn get_color(self) -> vec4 {
+                            return mix(#1E293B, #F1F5F9, self.dark_mode);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook)]
+pub struct CaptureApp {
+    #[live]
+    ui: WidgetRef,
+}
+
+impl LiveRegister for CaptureApp {
+    fn live_register(cx: &mut Cx) {
+        makepad_widgets::live_design(cx);
+        mofa_widgets::live_design(cx);
+    }
+}
+
+impl AppMain for CaptureApp {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event) {
+        self.ui.handle_event(cx, event, &mut Scope::empty());
+
+        match event {
+            Event::Startup => self.on_startup(cx),
+            Event::KeyDown(KeyEvent { key_code: KeyCode::Escape, .. }) => cx.quit(),
+            Event::WindowClosed(_) => cx.quit(),
+            Event::Actions(actions) => {
+                let input = self.ui.text_input(ids!(body.capture_input));
+                if input.returned(actions).is_some() {
+                    self.submit(cx, &input.text());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl CaptureApp {
+    fn on_startup(&mut self, cx: &mut Cx) {
+        // Keep the window above whatever app the user is in
+        if let Some(window) = self.ui.borrow_mut::<Window>() {
+            window.window.set_topmost(cx, true);
+        }
+
+        let dark_mode = if Preferences::load().dark_mode { 1.0 } else { 0.0 };
+        self.ui.apply_over(cx, live!{
+            draw_bg: { dark_mode: (dark_mode) }
+        });
+        self.ui.text_input(ids!(body.capture_input)).apply_over(cx, live!{
+            draw_bg: { dark_mode: (dark_mode) }
+            draw_text: { dark_mode: (dark_mode) }
+        });
+
+        self.ui.text_input(ids!(body.capture_input)).set_key_focus(cx);
+    }
+
+    fn submit(&mut self, cx: &mut Cx, text: &str) {
+        if text.trim().is_empty() {
+            return;
+        }
+        let mut stdout = std::io::stdout();
+        let _ = writeln!(stdout, "{}", format_submission(text.trim()));
+        let _ = stdout.flush();
+        cx.quit();
+    }
+}
+
+app_main!(CaptureApp);
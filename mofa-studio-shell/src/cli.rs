@@ -19,6 +19,12 @@
 //!
 //! # Custom audio sample rate
 //! mofa-studio --sample-rate 44100
+//!
+//! # Show plugin developer tools
+//! mofa-studio --dev
+//!
+//! # Start without plugins, app servers and WebViews
+//! mofa-studio --safe-mode
 //! ```
 
 use clap::Parser;
@@ -68,6 +74,26 @@ pub struct Args {
     /// Window height in pixels
     #[arg(long, default_value = "900", value_name = "PIXELS")]
     pub height: u32,
+
+    /// Enable developer tools
+    ///
+    /// Adds a "Dev" menu to plugin screens for opening the plugin's folder
+    /// and manifest, restarting its server and reloading its manifest.
+    #[arg(long)]
+    pub dev: bool,
+
+    /// Start in safe mode
+    ///
+    /// Skips loading plugins, starting app servers and creating WebViews, so
+    /// the studio opens even when one of them keeps crashing it. Each can be
+    /// re-enabled from the banner for the rest of the session. Also entered
+    /// automatically after two unclean exits in a row.
+    #[arg(long)]
+    pub safe_mode: bool,
+
+    /// Run the Note Taker quick-capture window (launched by the global shortcut)
+    #[arg(long, hide = true)]
+    pub quick_capture: bool,
 }
 
 impl Default for Args {
@@ -79,6 +105,9 @@ impl Default for Args {
             log_level: "info".to_string(),
             width: 1400,
             height: 900,
+            dev: false,
+            safe_mode: false,
+            quick_capture: false,
         }
     }
 }
@@ -114,6 +143,9 @@ mod tests {
         assert_eq!(args.log_level, "info");
         assert_eq!(args.width, 1400);
         assert_eq!(args.height, 900);
+        assert!(!args.dev);
+        assert!(!args.safe_mode);
+        assert!(!args.quick_capture);
     }
 
     #[test]
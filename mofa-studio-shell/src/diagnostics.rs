@@ -0,0 +1,259 @@
+//! Diagnostic bundle - what Settings' "Create diagnostic bundle" collects
+//!
+//! The shell snapshots what only the UI thread can reach (loaded plugins,
+//! app storage declarations) into [`BundleSource`]s; everything else is read
+//! on the bundle's own thread. The run is registered as a job, so the window
+//! title shows its progress, and Settings polls [`BundleRun::status`] for
+//! its status line.
+
+use crate::log_files;
+use crate::session_journal::SessionJournal;
+use mofa_settings::data::Preferences;
+use mofa_widgets::app_storage::{format_size, DataCategory, DataLocation};
+use mofa_widgets::diagnostic_bundle::{self, BundleFile, BundleManifest, BundleSource, Redaction};
+use mofa_widgets::jobs::JobRegistry;
+use mofa_widgets::plugins::PluginLoader;
+use mofa_widgets::safe_mode::SafeMode;
+use mofa_widgets::webview::diagnostics::published_reports;
+use parking_lot::Mutex;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+
+/// Most recent log files included
+pub const BUNDLED_LOG_FILES: usize = 3;
+
+enum BundleStatus {
+    Collecting { done: usize, total: usize, source: String },
+    Finished(Result<BundleManifest, String>),
+}
+
+/// A bundle being written on a background thread
+pub struct BundleRun {
+    path: PathBuf,
+    status: Arc<Mutex<BundleStatus>>,
+}
+
+impl BundleRun {
+    pub fn start(path: PathBuf, sources: Vec<BundleSource>, anonymize_home: bool) -> Self {
+        let status = Arc::new(Mutex::new(BundleStatus::Collecting {
+            done: 0,
+            total: sources.len(),
+            source: String::new(),
+        }));
+
+        let thread_status = Arc::clone(&status);
+        let thread_path = path.clone();
+        thread::spawn(move || {
+            let job = JobRegistry::global().start("mofa-settings", "collecting diagnostics");
+            let redaction = Redaction::new(anonymize_home);
+            let result = diagnostic_bundle::create(&thread_path, sources, &redaction, |done, total, source| {
+                job.set_progress(done as f32 / total.max(1) as f32);
+                *thread_status.lock() = BundleStatus::Collecting { done, total, source: source.to_string() };
+            });
+            match &result {
+                Ok(manifest) => {
+                    ::log::info!("Diagnostic bundle saved to {}: {}", thread_path.display(), manifest.summary());
+                    job.finish();
+                }
+                Err(e) => ::log::error!("Diagnostic bundle failed: {}", e),
+            }
+            *thread_status.lock() = BundleStatus::Finished(result);
+        });
+
+        Self { path, status }
+    }
+
+    /// Status line for Settings, and whether the run is over
+    pub fn status(&self) -> (String, bool) {
+        match &*self.status.lock() {
+            BundleStatus::Collecting { done, total, source } => (
+                format!("Creating diagnostic bundle... {}/{}: {}", done, total, source),
+                false,
+            ),
+            BundleStatus::Finished(Ok(manifest)) => (
+                format!("Diagnostic bundle saved to {} ({})", self.path.display(), manifest.summary()),
+                true,
+            ),
+            BundleStatus::Finished(Err(e)) => (format!("Diagnostic bundle failed: {}", e), true),
+        }
+    }
+}
+
+/// Every source of the bundle. `app_prefs` are the preference files apps
+/// declare, by app id.
+pub fn sources(loader: &PluginLoader, app_prefs: Vec<(String, PathBuf)>) -> Vec<BundleSource> {
+    let mut sources = vec![
+        BundleSource::new("log files", collect_logs),
+        BundleSource::new("session journal", || {
+            let path = SessionJournal::default_path();
+            BundleFile::read("session/session-journal.json", &path)
+                .map(|file| vec![file])
+                .map_err(|e| format!("{}: {}", path.display(), e))
+        }),
+        BundleSource::new("preferences", move || collect_prefs(app_prefs)),
+    ];
+    sources.extend(plugin_sources(loader));
+    sources.push(BundleSource::new("webview reports", || {
+        Ok(published_reports()
+            .into_iter()
+            .enumerate()
+            .map(|(n, report)| BundleFile::new(format!("webview/report-{}.txt", n + 1), report))
+            .collect())
+    }));
+    sources.push(BundleSource::new("system summary", || {
+        Ok(vec![BundleFile::new("system/summary.txt", system_summary())])
+    }));
+    sources.push(BundleSource::new("resource monitor", || {
+        resource_snapshot().map(|text| vec![BundleFile::new("system/resources.txt", text)])
+    }));
+    sources
+}
+
+fn collect_logs() -> Result<Vec<BundleFile>, String> {
+    let dir = log_files::log_dir();
+    let files = log_files::log_files(&dir);
+    if files.is_empty() {
+        return Err(format!("no log files in {}", dir.display()));
+    }
+    files
+        .iter()
+        .take(BUNDLED_LOG_FILES)
+        .map(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            BundleFile::read(format!("logs/{}", name), path)
+                .map_err(|e| format!("{}: {}", path.display(), e))
+        })
+        .collect()
+}
+
+/// The studio's preferences and every app's declared preference files;
+/// declared directories contribute the files directly inside them
+fn collect_prefs(app_prefs: Vec<(String, PathBuf)>) -> Result<Vec<BundleFile>, String> {
+    let mut files = Vec::new();
+    let studio = Preferences::get_preferences_path();
+    if let Ok(file) = BundleFile::read("prefs/preferences.json", &studio) {
+        files.push(file);
+    }
+    for (app_id, path) in app_prefs {
+        let paths = if path.is_dir() {
+            fs::read_dir(&path)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|p| p.is_file())
+                .collect()
+        } else {
+            vec![path]
+        };
+        for path in paths {
+            let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            if let Ok(file) = BundleFile::read(format!("prefs/{}/{}", app_id, name), &path) {
+                files.push(file);
+            }
+        }
+    }
+    if files.is_empty() {
+        return Err(format!("no preference files (looked for {})", studio.display()));
+    }
+    Ok(files)
+}
+
+/// One source per plugin: its manifest and recent server output
+fn plugin_sources(loader: &PluginLoader) -> Vec<BundleSource> {
+    let plugins: Vec<BundleSource> = loader
+        .plugins()
+        .map(|plugin| {
+            let id = plugin.manifest.id.clone();
+            let manifest_path = plugin.manifest_path();
+            let output = plugin.output.clone();
+            BundleSource::new(format!("plugin {}", id), move || {
+                let mut files = vec![BundleFile::read(format!("plugins/{}/manifest.json", id), &manifest_path)
+                    .map_err(|e| format!("{}: {}", manifest_path.display(), e))?];
+                if !output.is_empty() {
+                    let mut text = output.lines().join("\n");
+                    text.push('\n');
+                    files.push(BundleFile::new(format!("plugins/{}/output.log", id), text));
+                }
+                Ok(files)
+            })
+        })
+        .collect();
+    if plugins.is_empty() {
+        return vec![BundleSource::new("plugins", || {
+            Err("no plugins loaded".to_string())
+        })];
+    }
+    plugins
+}
+
+fn system_summary() -> String {
+    let sys = System::new_with_specifics(
+        RefreshKind::new()
+            .with_cpu(CpuRefreshKind::new())
+            .with_memory(MemoryRefreshKind::new().with_ram()),
+    );
+    let unknown = || "unknown".to_string();
+    let mut summary = String::new();
+    let _ = writeln!(summary, "MoFA Studio {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(
+        summary,
+        "OS: {} (kernel {})",
+        System::long_os_version().unwrap_or_else(unknown),
+        System::kernel_version().unwrap_or_else(unknown)
+    );
+    let _ = writeln!(summary, "Architecture: {}", System::cpu_arch().unwrap_or_else(unknown));
+    let _ = writeln!(
+        summary,
+        "CPU: {}, {} cores, {} threads",
+        sys.cpus().first().map_or("unknown", |cpu| cpu.brand()),
+        sys.physical_core_count().map_or_else(unknown, |n| n.to_string()),
+        sys.cpus().len()
+    );
+    let _ = writeln!(summary, "Memory: {}", format_size(sys.total_memory()));
+    let safe_mode = SafeMode::global();
+    if safe_mode.is_active() {
+        let disabled: Vec<&str> = safe_mode.disabled().iter().map(|f| f.label()).collect();
+        let _ = writeln!(summary, "Safe mode: on, disabled: {}", disabled.join(", "));
+    } else {
+        let _ = writeln!(summary, "Safe mode: off");
+    }
+    summary
+}
+
+/// Current readings of the resource monitor and the running jobs
+fn resource_snapshot() -> Result<String, String> {
+    use mofa_ui::system_monitor;
+    if !system_monitor::is_running() {
+        return Err("the resource monitor is not running".to_string());
+    }
+    let percent = |fraction: f64| format!("{:.1}%", fraction * 100.0);
+    let mut snapshot = String::new();
+    let _ = writeln!(snapshot, "CPU: {}", percent(system_monitor::get_cpu_usage()));
+    let _ = writeln!(snapshot, "Memory: {}", percent(system_monitor::get_memory_usage()));
+    if system_monitor::is_gpu_available() {
+        let _ = writeln!(snapshot, "GPU: {}", percent(system_monitor::get_gpu_usage()));
+        let _ = writeln!(snapshot, "VRAM: {}", percent(system_monitor::get_vram_usage()));
+    } else {
+        let _ = writeln!(snapshot, "GPU: not monitored");
+    }
+    let _ = writeln!(snapshot, "Active jobs:");
+    for job in JobRegistry::global().active() {
+        let progress = job.progress.map_or(String::new(), |p| format!(" {}", percent(p as f64)));
+        let _ = writeln!(snapshot, "  {} {}{}", job.app_id, job.verb, progress);
+    }
+    Ok(snapshot)
+}
+
+/// The preference files of an app's declared storage
+pub fn declared_prefs(app_id: &str, locations: &[DataLocation]) -> Vec<(String, PathBuf)> {
+    locations
+        .iter()
+        .filter(|location| location.category == DataCategory::Preferences)
+        .map(|location| (app_id.to_string(), location.path.clone()))
+        .collect()
+}
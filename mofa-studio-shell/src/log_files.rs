@@ -0,0 +1,112 @@
+//! Log files - each run's log under `~/.mofa-studio/logs`
+//!
+//! Besides stderr, every run writes its log to a new
+//! `mofa-studio-<unix secs>-<pid>.log`. Starting a run removes all but the
+//! newest [`KEEP_LOG_FILES`], so the directory stays small; the diagnostic
+//! bundle includes the ones that are left.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Log directory, relative to the MoFA Studio data directory
+const LOG_DIR: &str = "logs";
+
+const FILE_PREFIX: &str = "mofa-studio-";
+
+/// Log files kept, the current run's included
+pub const KEEP_LOG_FILES: usize = 5;
+
+pub fn log_dir() -> PathBuf {
+    mofa_widgets::app_storage::data_path(LOG_DIR)
+}
+
+/// Create this run's log file, pruning older ones; `None` if the
+/// directory is not writable
+pub fn open_run_log() -> Option<File> {
+    let dir = log_dir();
+    fs::create_dir_all(&dir).ok()?;
+    prune(&dir, KEEP_LOG_FILES - 1);
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let name = format!("{}{:010}-{}.log", FILE_PREFIX, secs, std::process::id());
+    File::create(dir.join(name)).ok()
+}
+
+/// Log files in `dir`, newest first
+pub fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(FILE_PREFIX) && name.ends_with(".log"))
+        })
+        .collect();
+    // Names start with a zero-padded timestamp
+    files.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+    files
+}
+
+/// Remove all but the newest `keep` log files
+fn prune(dir: &Path, keep: usize) {
+    for old in log_files(dir).into_iter().skip(keep) {
+        if let Err(e) = fs::remove_file(&old) {
+            eprintln!("Failed to remove old log file {}: {}", old.display(), e);
+        }
+    }
+}
+
+/// Log target writing to stderr and this run's log file
+pub struct Tee {
+    file: File,
+}
+
+impl Tee {
+    pub fn new(file: File) -> Self {
+        Self { file }
+    }
+}
+
+impl Write for Tee {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // A full disk must not take the stderr log down with it
+        let _ = self.file.write_all(buf);
+        io::stderr().write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let _ = self.file.flush();
+        io::stderr().flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_keeps_the_newest_files() {
+        let dir = std::env::temp_dir().join(format!("mofa-log-files-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for secs in [1700000300, 1700000100, 1700000200] {
+            fs::write(dir.join(format!("{}{}-1.log", FILE_PREFIX, secs)), "").unwrap();
+        }
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        prune(&dir, 2);
+        let names: Vec<String> = log_files(&dir)
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, ["mofa-studio-1700000300-1.log", "mofa-studio-1700000200-1.log"]);
+        assert!(dir.join("notes.txt").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
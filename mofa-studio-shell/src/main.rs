@@ -8,24 +8,45 @@
 //! mofa-studio --help          # Show help
 //! mofa-studio --dark-mode     # Start in dark mode
 //! mofa-studio --log-level debug  # Enable debug logging
+//! mofa-studio --safe-mode     # Start without plugins, servers and WebViews
 //! ```
 
 mod app;
+mod capture_window;
 mod cli;
+mod diagnostics;
+mod log_files;
+mod plugin_control;
+mod quick_capture;
+mod session_journal;
+mod window_status;
 
 pub use cli::Args;
 
 use clap::Parser;
+use mofa_widgets::safe_mode::SafeMode;
+use session_journal::SessionJournal;
 
 fn main() {
     // Parse command-line arguments
     let args = Args::parse();
 
-    // Configure logging based on CLI args
-    env_logger::Builder::from_env(
+    // Configure logging based on CLI args; the studio itself also logs to a file
+    let mut logger = env_logger::Builder::from_env(
         env_logger::Env::default().default_filter_or(args.log_filter()),
-    )
-    .init();
+    );
+    if !args.quick_capture {
+        if let Some(file) = log_files::open_run_log() {
+            logger.target(env_logger::Target::Pipe(Box::new(log_files::Tee::new(file))));
+        }
+    }
+    logger.init();
+
+    // Spawned by the global quick-capture shortcut: show only the capture window
+    if args.quick_capture {
+        capture_window::app_main();
+        return;
+    }
 
     log::info!("Starting MoFA Studio");
     log::debug!("CLI args: {:?}", args);
@@ -38,6 +59,21 @@ fn main() {
         log::info!("Using dataflow: {}", dataflow);
     }
 
+    // Runs that never recorded their end count as crashes
+    let journal = SessionJournal::start();
+    let safe_mode = if args.safe_mode {
+        Some(app::SafeModeReason::Requested)
+    } else if journal.suggests_safe_mode() {
+        Some(app::SafeModeReason::UncleanExits(journal.unclean_exits()))
+    } else {
+        None
+    };
+    if let Some(reason) = safe_mode {
+        log::warn!("Starting in safe mode: {}", reason.message());
+        SafeMode::global().enter();
+    }
+    app::set_session(journal, safe_mode);
+
     // Store args for app to access
     app::set_cli_args(args);
 
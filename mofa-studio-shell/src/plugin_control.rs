@@ -0,0 +1,38 @@
+//! Studio state served to plugins through the control API
+
+use mofa_settings::data::Preferences;
+use mofa_widgets::plugins::{AiProviderInfo, StudioState};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Shell-side view of the state plugins may query
+#[derive(Default)]
+pub struct ShellStudioState {
+    dark_mode: AtomicBool,
+}
+
+impl ShellStudioState {
+    pub fn set_dark_mode(&self, dark_mode: bool) {
+        self.dark_mode.store(dark_mode, Ordering::Relaxed);
+    }
+}
+
+impl StudioState for ShellStudioState {
+    fn dark_mode(&self) -> bool {
+        self.dark_mode.load(Ordering::Relaxed)
+    }
+
+    /// Provider resolved by [`Preferences::resolve_ai_provider`], read from
+    /// preferences per request so Settings changes apply at once.
+    fn ai_provider(&self) -> Option<AiProviderInfo> {
+        let prefs = Preferences::load();
+        let provider = prefs.resolve_ai_provider()?;
+
+        Some(AiProviderInfo {
+            id: provider.id.clone(),
+            name: provider.name.clone(),
+            url: provider.url.clone(),
+            model: provider.models.first().cloned(),
+            api_key: provider.api_key.clone(),
+        })
+    }
+}
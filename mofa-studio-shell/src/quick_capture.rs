@@ -0,0 +1,191 @@
+//! Quick capture - global shortcut that appends a line to today's note
+//!
+//! When a shortcut is configured in Settings > Keyboard Shortcuts, pressing it
+//! anywhere opens a small always-on-top window with a single text field. The
+//! window is a separate process (this binary with `--quick-capture`, see
+//! [`crate::capture_window`]) so it can appear without raising the studio
+//! window. It prints the submitted line to stdout and exits; the shell hands
+//! the text to [`mofa_note_taker::capture`].
+//!
+//! System-wide shortcuts need the `global-hotkey` cargo feature (tested on
+//! macOS). Without it a saved shortcut is reported as unavailable.
+
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// Hidden CLI flag that runs the capture window instead of the studio
+pub const CAPTURE_WINDOW_FLAG: &str = "--quick-capture";
+
+/// Owns the registered shortcut and the open capture window, if any
+#[derive(Default)]
+pub struct QuickCapture {
+    #[cfg(feature = "global-hotkey")]
+    hotkey: Option<hotkey::Registered>,
+    /// Receives the capture window's result once it exits
+    window: Option<Receiver<Option<String>>>,
+}
+
+impl QuickCapture {
+    /// Register `shortcut` (e.g. "CmdOrCtrl+Shift+Space"), replacing the
+    /// previous one. `None` disables quick capture.
+    pub fn set_shortcut(&mut self, shortcut: Option<&str>) -> Result<(), String> {
+        #[cfg(feature = "global-hotkey")]
+        {
+            // Release the old shortcut first so the same one can be re-registered
+            self.hotkey = None;
+            if let Some(shortcut) = shortcut {
+                self.hotkey = Some(hotkey::Registered::new(shortcut)?);
+            }
+        }
+
+        #[cfg(not(feature = "global-hotkey"))]
+        if shortcut.is_some() {
+            return Err("Global shortcuts are not available in this build (enable the `global-hotkey` feature)".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Whether there is anything to poll
+    pub fn is_active(&self) -> bool {
+        #[cfg(feature = "global-hotkey")]
+        if self.hotkey.is_some() {
+            return true;
+        }
+        self.window.is_some()
+    }
+
+    /// Whether the shortcut was pressed since the last poll
+    pub fn poll_shortcut(&mut self) -> bool {
+        #[cfg(feature = "global-hotkey")]
+        if let Some(ref hotkey) = self.hotkey {
+            return hotkey.pressed();
+        }
+        false
+    }
+
+    /// Open the capture window unless one is already open
+    pub fn open_window(&mut self) -> Result<(), String> {
+        if self.window.is_some() {
+            return Ok(());
+        }
+
+        let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+        let mut child = Command::new(exe)
+            .arg(CAPTURE_WINDOW_FLAG)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to open capture window: {}", e))?;
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut output = String::new();
+            if let Some(mut stdout) = child.stdout.take() {
+                let _ = stdout.read_to_string(&mut output);
+            }
+            let _ = child.wait();
+            let _ = tx.send(parse_submission(&output));
+        });
+
+        self.window = Some(rx);
+        Ok(())
+    }
+
+    /// Text submitted in the capture window, once it has closed
+    pub fn poll_window(&mut self) -> Option<String> {
+        let result = match self.window.as_ref()?.try_recv() {
+            Ok(text) => text,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => None,
+        };
+        self.window = None;
+        result
+    }
+}
+
+/// Line the capture window prints for submitted text
+pub fn format_submission(text: &str) -> String {
+    serde_json::json!({ "text": text }).to_string()
+}
+
+/// Read the capture window's output; `None` when it was dismissed
+pub fn parse_submission(output: &str) -> Option<String> {
+    output.lines().rev().find_map(|line| {
+        let value: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+        let text = value.get("text")?.as_str()?.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    })
+}
+
+#[cfg(feature = "global-hotkey")]
+mod hotkey {
+    use global_hotkey::hotkey::HotKey;
+    use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+
+    /// A shortcut registered with the OS; unregistered on drop
+    pub struct Registered {
+        manager: GlobalHotKeyManager,
+        hotkey: HotKey,
+    }
+
+    impl Registered {
+        /// Must be called on the main thread (required on macOS)
+        pub fn new(shortcut: &str) -> Result<Self, String> {
+            let hotkey: HotKey = shortcut
+                .parse()
+                .map_err(|e| format!("Invalid shortcut '{}': {}", shortcut, e))?;
+            let manager = GlobalHotKeyManager::new().map_err(|e| e.to_string())?;
+            manager
+                .register(hotkey)
+                .map_err(|e| format!("Could not register '{}': {}", shortcut, e))?;
+            Ok(Self { manager, hotkey })
+        }
+
+        pub fn pressed(&self) -> bool {
+            let mut pressed = false;
+            while let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
+                if event.id() == self.hotkey.id() && event.state() == HotKeyState::Pressed {
+                    pressed = true;
+                }
+            }
+            pressed
+        }
+    }
+
+    impl Drop for Registered {
+        fn drop(&mut self) {
+            let _ = self.manager.unregister(self.hotkey);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submission_round_trip() {
+        let line = format_submission("call Ana about \"the demo\"");
+        assert_eq!(parse_submission(&line).as_deref(), Some("call Ana about \"the demo\""));
+    }
+
+    #[test]
+    fn test_dismissed_or_noisy_output() {
+        assert_eq!(parse_submission(""), None);
+        assert_eq!(parse_submission("{\"text\":\"   \"}\n"), None);
+        // Stray log lines before the result are ignored
+        let output = format!("INFO starting\n{}\n", format_submission("buy milk"));
+        assert_eq!(parse_submission(&output).as_deref(), Some("buy milk"));
+    }
+
+    #[cfg(not(feature = "global-hotkey"))]
+    #[test]
+    fn test_shortcut_unavailable_without_feature() {
+        let mut capture = QuickCapture::default();
+        assert!(capture.set_shortcut(Some("CmdOrCtrl+Shift+Space")).is_err());
+        assert!(capture.set_shortcut(None).is_ok());
+        assert!(!capture.is_active());
+    }
+}
@@ -0,0 +1,110 @@
+//! Session journal - whether earlier runs ended cleanly
+//!
+//! A small file under `~/.mofa-studio` is marked "running" when the studio
+//! starts and cleared when it shuts down. Finding it still marked at startup
+//! means the previous run crashed or was killed; the count of such runs in a
+//! row decides whether the studio offers safe mode. A clean shutdown resets
+//! the count.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Journal file, relative to the MoFA Studio data directory
+const JOURNAL_FILE: &str = "session-journal.json";
+
+/// Unclean exits in a row after which the studio starts in safe mode
+pub const UNCLEAN_EXITS_FOR_SAFE_MODE: u32 = 2;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Record {
+    running: bool,
+    unclean_exits: u32,
+}
+
+/// The journal of the running session
+#[derive(Debug)]
+pub struct SessionJournal {
+    path: PathBuf,
+    unclean_exits: u32,
+}
+
+impl SessionJournal {
+    /// Record the start of a session in the default location
+    pub fn start() -> Self {
+        Self::start_at(&Self::default_path())
+    }
+
+    /// Where [`start`](Self::start) keeps the journal
+    pub fn default_path() -> PathBuf {
+        mofa_widgets::app_storage::data_path(JOURNAL_FILE)
+    }
+
+    /// Record the start of a session, counting the previous one as unclean
+    /// if it never recorded its end
+    pub fn start_at(path: &Path) -> Self {
+        let previous: Record = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let unclean_exits = if previous.running {
+            previous.unclean_exits.saturating_add(1)
+        } else {
+            0
+        };
+        let journal = Self { path: path.to_path_buf(), unclean_exits };
+        journal.write(&Record { running: true, unclean_exits });
+        journal
+    }
+
+    /// Unclean exits in a row before this session
+    pub fn unclean_exits(&self) -> u32 {
+        self.unclean_exits
+    }
+
+    /// Whether enough runs in a row crashed to start in safe mode
+    pub fn suggests_safe_mode(&self) -> bool {
+        self.unclean_exits >= UNCLEAN_EXITS_FOR_SAFE_MODE
+    }
+
+    /// Record a clean shutdown, which resets the crash count
+    pub fn end(&self) {
+        self.write(&Record::default());
+    }
+
+    fn write(&self, record: &Record) {
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let result = serde_json::to_string(record)
+            .map_err(|e| e.to_string())
+            .and_then(|json| fs::write(&self.path, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            log::warn!("Failed to write session journal {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unclean_exits_are_counted_until_a_clean_shutdown() {
+        let dir = std::env::temp_dir().join(format!("mofa-session-journal-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join(JOURNAL_FILE);
+
+        assert_eq!(SessionJournal::start_at(&path).unclean_exits(), 0);
+        // Each start without an end counts the previous run as a crash
+        assert!(!SessionJournal::start_at(&path).suggests_safe_mode());
+        let journal = SessionJournal::start_at(&path);
+        assert_eq!(journal.unclean_exits(), 2);
+        assert!(journal.suggests_safe_mode());
+
+        journal.end();
+        assert_eq!(SessionJournal::start_at(&path).unclean_exits(), 0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
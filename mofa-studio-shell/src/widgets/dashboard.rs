@@ -2,7 +2,8 @@
 //!
 //! The Dashboard provides the base layer for MoFA Studio with:
 //! - Header with logo, title, theme toggle, and user profile
-//! - Content area with app pages (FM, Settings, etc.)
+//! - Content area with app pages (FM, Settings, etc.), topped by the
+//!   safe-mode banner when the studio started in safe mode
 //! - Tab overlay for modal-like Profile/Settings tabs
 
 use makepad_widgets::*;
@@ -59,6 +60,26 @@ live_design! {
     // Logo image
     MOFA_LOGO = dep("crate://self/resources/mofa-logo.png")
 
+    // Re-enables one feature held back by safe mode
+    SafeModeButton = <Button> {
+        width: Fit, height: 28
+        padding: {left: 12, right: 12}
+        draw_text: {
+            text_style: <FONT_SEMIBOLD>{ font_size: 10.0 }
+            fn get_color(self) -> vec4 {
+                return (ACCENT_INDIGO);
+            }
+        }
+        draw_bg: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 4.0);
+                sdf.stroke((ACCENT_INDIGO), 1.0);
+                return sdf.result;
+            }
+        }
+    }
+
     pub Dashboard = {{Dashboard}} <View> {
         width: Fill, height: Fill
         flow: Overlay
@@ -257,6 +278,43 @@ live_design! {
                     width: Fill, height: Fill
                     flow: Down
 
+                    // Safe mode banner - says what was held back at startup and
+                    // re-enables it for the session
+                    safe_mode_banner = <RoundedView> {
+                        width: Fill, height: Fit
+                        visible: false
+                        padding: {left: 16, right: 12, top: 10, bottom: 10}
+                        margin: {bottom: 12}
+                        flow: Right
+                        spacing: 8
+                        align: {y: 0.5}
+                        show_bg: true
+                        draw_bg: {
+                            instance dark_mode: 0.0
+                            border_radius: 8.0
+                            fn get_color(self) -> vec4 {
+                                return mix(vec4(1.0, 0.97, 0.86, 1.0), vec4(0.27, 0.2, 0.08, 1.0), self.dark_mode);
+                            }
+                        }
+
+                        safe_mode_text = <Label> {
+                            width: Fill
+                            text: ""
+                            draw_text: {
+                                instance dark_mode: 0.0
+                                wrap: Word
+                                text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+                                fn get_color(self) -> vec4 {
+                                    return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                                }
+                            }
+                        }
+
+                        enable_plugins_btn = <SafeModeButton> { text: "Enable plugins" }
+                        enable_servers_btn = <SafeModeButton> { text: "Enable servers" }
+                        enable_webviews_btn = <SafeModeButton> { text: "Enable WebViews" }
+                    }
+
                     // Hero title panel - displays current app name and metadata
                     hero_title_panel = <RoundedView> {
                         width: Fill, height: Fit
@@ -5,5 +5,7 @@
 
 pub mod dashboard;
 pub mod mofa_hero;
+pub mod running_panel;
 pub mod sidebar;
 pub mod tabs;
+pub mod toasts;
@@ -0,0 +1,515 @@
+//! Running Panel - everything running in the background, across apps
+//!
+//! Lists background jobs, app servers, plugin servers, embedded WebViews and
+//! polling timers in one table, refreshed every second while the panel is
+//! open. The panel knows nothing about individual apps: it reads the job and
+//! activity registries and the plugin loader. Rows whose owner can be stopped
+//! from outside get a Stop (Cancel for jobs) button; the request goes to the
+//! owner, which stops through its usual path.
+
+use makepad_widgets::*;
+use mofa_widgets::activity::{ActivityInfo, ActivityKind, ActivityRegistry};
+use mofa_widgets::jobs::{format_remaining, JobInfo, JobRegistry};
+use mofa_widgets::plugins::PluginLoader;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Refresh interval while the panel is open (seconds)
+const REFRESH_INTERVAL: f64 = 1.0;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use mofa_widgets::theme::*;
+
+    CellLabel = <Label> {
+        width: 100, height: Fit
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix((SLATE_500), (SLATE_400), self.dark_mode);
+            }
+        }
+    }
+
+    PanelButton = <Button> {
+        width: 72, height: 28
+        margin: {left: 8}
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix((SLATE_700), (SLATE_200), self.dark_mode);
+            }
+        }
+        draw_bg: {
+            instance dark_mode: 0.0
+            instance hover: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                let idle = mix((SLATE_200), (SLATE_700), self.dark_mode);
+                let hover = mix((SLATE_300), (SLATE_600), self.dark_mode);
+                sdf.fill(mix(idle, hover, self.hover));
+                return sdf.result;
+            }
+        }
+    }
+
+    // Table row: kind, app, name, state, uptime or progress, stop
+    RunningRow = <View> {
+        width: Fill, height: Fit
+        padding: {top: 8, bottom: 8, left: 16, right: 16}
+        margin: {bottom: 2}
+        flow: Right
+        align: {x: 0.0, y: 0.5}
+        show_bg: true
+        draw_bg: {
+            instance dark_mode: 0.0
+            fn pixel(self) -> vec4 {
+                return mix((SLATE_50), (SLATE_800), self.dark_mode);
+            }
+        }
+
+        kind_label = <CellLabel> { width: 70 }
+        app_label = <CellLabel> { width: 150 }
+        name_label = <Label> {
+            width: Fill, height: Fit
+            draw_text: {
+                instance dark_mode: 0.0
+                text_style: <FONT_REGULAR>{ font_size: 12.0 }
+                fn get_color(self) -> vec4 {
+                    return mix((SLATE_700), (SLATE_200), self.dark_mode);
+                }
+            }
+        }
+        state_label = <CellLabel> { width: 160 }
+        detail_label = <CellLabel> { width: 70 }
+        stop_btn = <PanelButton> { text: "Stop" }
+    }
+
+    pub RunningPanel = {{RunningPanel}} {
+        width: Fill, height: Fill
+        flow: Down
+        padding: {top: 20, bottom: 20, left: 20, right: 20}
+
+        header = <View> {
+            width: Fill, height: Fit
+            flow: Right
+            align: {y: 0.5}
+
+            title = <Label> {
+                width: Fill
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: <FONT_BOLD>{ font_size: 16.0 }
+                    fn get_color(self) -> vec4 {
+                        return mix((SLATE_800), (SLATE_100), self.dark_mode);
+                    }
+                }
+                text: "What's running"
+            }
+
+            close_btn = <PanelButton> { text: "Close" }
+        }
+
+        summary_label = <Label> {
+            width: Fill
+            margin: {top: 4, bottom: 12}
+            draw_text: {
+                instance dark_mode: 0.0
+                wrap: Word
+                text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                fn get_color(self) -> vec4 {
+                    return mix((SLATE_500), (SLATE_400), self.dark_mode);
+                }
+            }
+            text: ""
+        }
+
+        running_list = <PortalList> {
+            width: Fill, height: Fill
+            flow: Down
+
+            running_row = <RunningRow> {}
+        }
+    }
+}
+
+/// Where a row's stop button sends the request
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunningTarget {
+    Job(u64),
+    Activity(u64),
+    Plugin(String),
+}
+
+/// A row of the panel
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunningItem {
+    pub target: RunningTarget,
+    /// "Job", "Server", "Plugin", "WebView" or "Timer"
+    pub kind: &'static str,
+    pub app_id: String,
+    pub name: String,
+    pub state: String,
+    /// Progress of a job that reports it, uptime otherwise
+    pub detail: String,
+    /// Label of the stop button; `None` if the owner cannot be stopped from here
+    pub stop_label: Option<&'static str>,
+}
+
+/// A plugin whose server is running
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginServer {
+    pub id: String,
+    pub name: String,
+    pub port: Option<u16>,
+    pub started: Option<Instant>,
+}
+
+/// Plugins of `loader` with a running server
+pub fn plugin_servers(loader: &PluginLoader) -> Vec<PluginServer> {
+    loader
+        .plugins()
+        .filter(|plugin| plugin.is_server_running())
+        .map(|plugin| PluginServer {
+            id: plugin.manifest.id.clone(),
+            name: plugin.name(loader.language()).to_string(),
+            port: plugin.server_port,
+            started: plugin.started_at,
+        })
+        .collect()
+}
+
+/// Rows for everything running: jobs first, then servers, WebViews and timers
+pub fn collect(
+    jobs: &[JobInfo],
+    activities: &[ActivityInfo],
+    plugins: &[PluginServer],
+    now: Instant,
+) -> Vec<RunningItem> {
+    let mut items: Vec<RunningItem> = jobs
+        .iter()
+        .map(|job| RunningItem {
+            target: RunningTarget::Job(job.id),
+            kind: "Job",
+            app_id: job.app_id.clone(),
+            name: job.verb.clone(),
+            state: "running".to_string(),
+            detail: match (job.progress, job.remaining_secs) {
+                (Some(progress), Some(secs)) => format!(
+                    "{:.0}%, {} left",
+                    progress.clamp(0.0, 1.0) * 100.0,
+                    format_remaining(secs)
+                ),
+                (Some(progress), None) => format!("{:.0}%", progress.clamp(0.0, 1.0) * 100.0),
+                (None, _) => format_uptime(now.saturating_duration_since(job.started)),
+            },
+            stop_label: job.cancellable.then_some("Cancel"),
+        })
+        .collect();
+
+    let activity_items = |kind: ActivityKind| {
+        activities.iter().filter(move |a| a.kind == kind).map(move |activity| RunningItem {
+            target: RunningTarget::Activity(activity.id),
+            kind: activity.kind.label(),
+            app_id: activity.app_id.clone(),
+            name: activity.name.clone(),
+            state: activity.state.clone(),
+            detail: format_uptime(now.saturating_duration_since(activity.started)),
+            stop_label: activity.stoppable.then_some("Stop"),
+        })
+    };
+
+    items.extend(activity_items(ActivityKind::Server));
+    items.extend(plugins.iter().map(|plugin| RunningItem {
+        target: RunningTarget::Plugin(plugin.id.clone()),
+        kind: "Plugin",
+        app_id: plugin.id.clone(),
+        name: plugin.name.clone(),
+        state: plugin.port.map(|port| format!("port {}", port)).unwrap_or_default(),
+        detail: plugin
+            .started
+            .map(|started| format_uptime(now.saturating_duration_since(started)))
+            .unwrap_or_else(|| "-".to_string()),
+        stop_label: Some("Stop"),
+    }));
+    items.extend(activity_items(ActivityKind::WebView));
+    items.extend(activity_items(ActivityKind::Timer));
+    items
+}
+
+/// Forward a stop to the owner of `target`; `false` if it is gone or cannot
+/// be stopped from outside
+pub fn stop(target: &RunningTarget, loader: Option<&Arc<Mutex<PluginLoader>>>) -> bool {
+    match target {
+        RunningTarget::Job(id) => JobRegistry::global().cancel(*id),
+        RunningTarget::Activity(id) => ActivityRegistry::global().request_stop(*id),
+        RunningTarget::Plugin(id) => match loader.and_then(|l| l.lock().ok()) {
+            Some(mut loader) => {
+                loader.stop_plugin(id);
+                true
+            }
+            None => false,
+        },
+    }
+}
+
+/// "45s", "3m 07s" or "2h 05m"
+pub fn format_uptime(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {:02}m", secs / 3600, secs % 3600 / 60)
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum RunningPanelAction {
+    None,
+    /// The close button was clicked
+    Closed,
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct RunningPanel {
+    #[deref]
+    view: View,
+
+    #[rust]
+    items: Vec<RunningItem>,
+
+    /// Loader of the plugins to list; set when the panel opens
+    #[rust]
+    plugin_loader: Option<Arc<Mutex<PluginLoader>>>,
+
+    #[rust]
+    refresh_timer: Timer,
+
+    #[rust]
+    dark_mode: f64,
+}
+
+impl Widget for RunningPanel {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if self.refresh_timer.is_event(event).is_some() {
+            self.refresh(cx);
+        }
+
+        let actions = match event {
+            Event::Actions(actions) => actions.as_slice(),
+            _ => return,
+        };
+
+        if self.view.button(ids!(header.close_btn)).clicked(actions) {
+            cx.widget_action(
+                self.view.widget_uid(),
+                &HeapLiveIdPath::default(),
+                RunningPanelAction::Closed,
+            );
+        }
+
+        let list = self.view.portal_list(ids!(running_list));
+        let mut stopped = false;
+        for (item_id, item) in list.items_with_actions(actions) {
+            let Some(running) = self.items.get(item_id) else {
+                continue;
+            };
+            if item.button(ids!(stop_btn)).clicked(actions) {
+                ::log::info!("Stopping {} {} of {}", running.kind, running.name, running.app_id);
+                if !stop(&running.target, self.plugin_loader.as_ref()) {
+                    ::log::warn!("{} {} could not be stopped", running.kind, running.name);
+                }
+                stopped = true;
+            }
+        }
+        if stopped {
+            self.refresh(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, self.items.len());
+
+                while let Some(item_id) = list.next_visible_item(cx) {
+                    let Some(running) = self.items.get(item_id) else {
+                        continue;
+                    };
+                    let item = list.item(cx, item_id, live_id!(running_row));
+                    let or_dash = |text: &str| if text.is_empty() { "-".to_string() } else { text.to_string() };
+
+                    item.label(ids!(kind_label)).set_text(cx, running.kind);
+                    item.label(ids!(app_label)).set_text(cx, &or_dash(&running.app_id));
+                    item.label(ids!(name_label)).set_text(cx, &running.name);
+                    item.label(ids!(state_label)).set_text(cx, &or_dash(&running.state));
+                    item.label(ids!(detail_label)).set_text(cx, &running.detail);
+
+                    let stop_btn = item.button(ids!(stop_btn));
+                    stop_btn.set_visible(cx, running.stop_label.is_some());
+                    if let Some(label) = running.stop_label {
+                        stop_btn.set_text(cx, label);
+                    }
+
+                    let dm = self.dark_mode;
+                    item.apply_over(cx, live!{
+                        draw_bg: { dark_mode: (dm) }
+                        kind_label = { draw_text: { dark_mode: (dm) } }
+                        app_label = { draw_text: { dark_mode: (dm) } }
+                        name_label = { draw_text: { dark_mode: (dm) } }
+                        state_label = { draw_text: { dark_mode: (dm) } }
+                        detail_label = { draw_text: { dark_mode: (dm) } }
+                        stop_btn = { draw_bg: { dark_mode: (dm) } draw_text: { dark_mode: (dm) } }
+                    });
+
+                    item.draw_all(cx, scope);
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+impl RunningPanel {
+    /// Re-read the registries and the plugin loader
+    fn refresh(&mut self, cx: &mut Cx) {
+        let plugins = self
+            .plugin_loader
+            .as_ref()
+            .and_then(|loader| loader.lock().ok().map(|loader| plugin_servers(&loader)))
+            .unwrap_or_default();
+        self.items = collect(
+            &JobRegistry::global().active(),
+            &ActivityRegistry::global().active(),
+            &plugins,
+            Instant::now(),
+        );
+
+        let summary = match self.items.len() {
+            0 => "Nothing is running in the background.".to_string(),
+            1 => "1 thing running. Stopping it here has the same effect as stopping it in its app.".to_string(),
+            n => format!("{} things running. Stopping one here has the same effect as stopping it in its app.", n),
+        };
+        self.view.label(ids!(summary_label)).set_text(cx, &summary);
+        self.view.redraw(cx);
+    }
+}
+
+impl RunningPanelRef {
+    /// Start refreshing; `loader` supplies the plugin servers
+    pub fn open(&self, cx: &mut Cx, loader: Arc<Mutex<PluginLoader>>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.plugin_loader = Some(loader);
+            cx.stop_timer(inner.refresh_timer);
+            inner.refresh_timer = cx.start_interval(REFRESH_INTERVAL);
+            inner.refresh(cx);
+        }
+    }
+
+    /// Stop refreshing while the panel is hidden
+    pub fn close(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            cx.stop_timer(inner.refresh_timer);
+            inner.items.clear();
+        }
+    }
+
+    pub fn update_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.dark_mode = dark_mode;
+            inner.view.apply_over(cx, live!{
+                header = {
+                    title = { draw_text: { dark_mode: (dark_mode) } }
+                    close_btn = { draw_bg: { dark_mode: (dark_mode) } draw_text: { dark_mode: (dark_mode) } }
+                }
+                summary_label = { draw_text: { dark_mode: (dark_mode) } }
+            });
+            inner.view.redraw(cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: u64, progress: Option<f32>, cancellable: bool, started: Instant) -> JobInfo {
+        JobInfo {
+            id,
+            app_id: "mofa-podcast".to_string(),
+            verb: "rendering".to_string(),
+            progress,
+            remaining_secs: None,
+            started,
+            cancellable,
+        }
+    }
+
+    fn activity(id: u64, kind: ActivityKind, stoppable: bool, started: Instant) -> ActivityInfo {
+        ActivityInfo {
+            id,
+            kind,
+            app_id: "mofa-converter".to_string(),
+            name: format!("{} {}", kind.label(), id),
+            state: String::new(),
+            started,
+            stoppable,
+        }
+    }
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(Duration::from_secs(0)), "0s");
+        assert_eq!(format_uptime(Duration::from_secs(59)), "59s");
+        assert_eq!(format_uptime(Duration::from_secs(187)), "3m 07s");
+        assert_eq!(format_uptime(Duration::from_secs(7500)), "2h 05m");
+    }
+
+    #[test]
+    fn test_collect_orders_and_labels_rows() {
+        let started = Instant::now();
+        let now = started + Duration::from_secs(65);
+        let mut rendering = job(1, Some(0.62), false, started);
+        rendering.remaining_secs = Some(250.0);
+        let jobs = [rendering, job(2, None, true, started)];
+        let activities = [
+            activity(10, ActivityKind::Timer, true, started),
+            activity(11, ActivityKind::WebView, true, started),
+            activity(12, ActivityKind::Server, false, started),
+        ];
+        let plugins = [PluginServer {
+            id: "demo".to_string(),
+            name: "Demo".to_string(),
+            port: Some(8123),
+            started: None,
+        }];
+
+        let items = collect(&jobs, &activities, &plugins, now);
+        let kinds: Vec<_> = items.iter().map(|i| i.kind).collect();
+        assert_eq!(kinds, ["Job", "Job", "Server", "Plugin", "WebView", "Timer"]);
+
+        assert_eq!(items[0].detail, "62%, ~4 min left");
+        assert_eq!(items[0].stop_label, None);
+        assert_eq!(items[1].detail, "1m 05s");
+        assert_eq!(items[1].stop_label, Some("Cancel"));
+        // Not stoppable from outside
+        assert_eq!(items[2].stop_label, None);
+        assert_eq!(items[3].target, RunningTarget::Plugin("demo".to_string()));
+        assert_eq!(items[3].state, "port 8123");
+        assert_eq!(items[3].detail, "-");
+        assert_eq!(items[5].target, RunningTarget::Activity(10));
+        assert_eq!(items[5].stop_label, Some("Stop"));
+
+        assert!(collect(&[], &[], &[], now).is_empty());
+    }
+}
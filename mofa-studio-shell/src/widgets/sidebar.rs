@@ -389,6 +389,14 @@ live_design! {
                 svg_file: dep("crate://self/resources/icons/settings.svg")
             }
         }
+
+        // Opens the shell's panel of running servers, jobs and timers
+        running_btn = <SidebarMenuButton> {
+            text: "What's running"
+            draw_icon: {
+                svg_file: dep("crate://self/resources/icons/play.svg")
+            }
+        }
     }
 }
 
@@ -417,6 +425,7 @@ pub enum SidebarAction {
     None,
     ToggleTheme,
     PluginSelected(String), // Plugin ID selected
+    ShowRunning,            // "What's running" clicked
 }
 
 #[derive(Live, LiveHook, Widget)]
@@ -507,8 +516,8 @@ impl Widget for Sidebar {
                     // Pinned sidebar: calculate available height dynamically
                     // Reserved space: logo_area (5) + mofa_fm_tab (~48) + mofa_fm_web_tab (~48)
                     //                 + settings_divider (1+16 margin) + settings_tab (~48)
-                    //                 + padding (top:15 + bottom:15) + spacing
-                    let reserved_height = 5.0 + 48.0 + 48.0 + 17.0 + 48.0 + 30.0 + 20.0; // ~216px total
+                    //                 + running_btn (~48) + padding (top:15 + bottom:15) + spacing
+                    let reserved_height = 5.0 + 48.0 + 48.0 + 17.0 + 48.0 + 48.0 + 30.0 + 20.0; // ~264px total
                     (self.cached_sidebar_height - reserved_height).max(200.0)
                 } else if self.expand_to_fill {
                     // Fallback for pinned sidebar if no cached height yet
@@ -680,6 +689,15 @@ impl Widget for Sidebar {
             self.handle_selection(cx, SidebarSelection::Settings);
         }
 
+        // Not a selection: the panel opens over the current page
+        if self.view.button(ids!(running_btn)).clicked(actions) {
+            cx.widget_action(
+                self.view.widget_uid(),
+                &HeapLiveIdPath::default(),
+                SidebarAction::ShowRunning,
+            );
+        }
+
         // Handle pinned app button click (acts same as the original app)
         if self
             .view
@@ -1623,6 +1641,13 @@ impl SidebarRef {
                     draw_text: { dark_mode: (dark_mode) }
                 },
             );
+            inner.view.button(ids!(running_btn)).apply_over(
+                cx,
+                live! {
+                    draw_bg: { dark_mode: (dark_mode) }
+                    draw_text: { dark_mode: (dark_mode) }
+                },
+            );
 
             // App buttons (1-4) in apps_wrapper.apps_scroll
             inner
@@ -0,0 +1,271 @@
+//! Toasts - short notices stacked in the window's bottom-right corner
+//!
+//! Plugins post them through the control API (`POST /notify`). The newest
+//! [`MAX_TOASTS`] are shown, newest at the bottom; each disappears after
+//! [`TOAST_SECS`], errors and warnings after [`LONG_TOAST_SECS`], or when
+//! clicked.
+
+use makepad_widgets::*;
+use mofa_widgets::plugins::control::NotificationLevel;
+use std::time::{Duration, Instant};
+
+/// Toasts on screen at once
+pub const MAX_TOASTS: usize = 3;
+/// How long info and success toasts stay (seconds)
+pub const TOAST_SECS: u64 = 5;
+/// How long warnings and errors stay (seconds)
+pub const LONG_TOAST_SECS: u64 = 10;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use mofa_widgets::theme::*;
+
+    Toast = <View> {
+        width: 340, height: Fit
+        margin: {top: 8}
+        padding: {top: 10, bottom: 10, left: 16, right: 12}
+        flow: Down
+        visible: false
+        cursor: Hand
+        show_bg: true
+        draw_bg: {
+            instance dark_mode: 0.0
+            // 0 info, 1 success, 2 warning, 3 error
+            instance level: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 6.0);
+                sdf.fill(mix((WHITE), (SLATE_800), self.dark_mode));
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 6.0);
+                sdf.stroke(mix((BORDER), (BORDER_DARK), self.dark_mode), 1.0);
+                // Level stripe on the left edge
+                let stripe = mix(
+                    mix((BLUE_500), (GREEN_500), step(0.5, self.level)),
+                    mix((AMBER_500), (RED_500), step(2.5, self.level)),
+                    step(1.5, self.level)
+                );
+                sdf.box(0., 0., 4.0, self.rect_size.y, 2.0);
+                sdf.fill(stripe);
+                return sdf.result;
+            }
+        }
+
+        title_label = <Label> {
+            width: Fill, height: Fit
+            draw_text: {
+                instance dark_mode: 0.0
+                wrap: Word
+                text_style: <FONT_SEMIBOLD>{ font_size: 12.0 }
+                fn get_color(self) -> vec4 {
+                    return mix((SLATE_800), (SLATE_100), self.dark_mode);
+                }
+            }
+        }
+        message_label = <Label> {
+            width: Fill, height: Fit
+            margin: {top: 2}
+            draw_text: {
+                instance dark_mode: 0.0
+                wrap: Word
+                text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                fn get_color(self) -> vec4 {
+                    return mix((SLATE_600), (SLATE_300), self.dark_mode);
+                }
+            }
+        }
+    }
+
+    pub ToastStack = {{ToastStack}} {
+        width: Fill, height: Fill
+        flow: Down
+        align: {x: 1.0, y: 1.0}
+        padding: {right: 24, bottom: 24}
+
+        toast_0 = <Toast> {}
+        toast_1 = <Toast> {}
+        toast_2 = <Toast> {}
+    }
+}
+
+/// A toast waiting to expire
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToastEntry {
+    pub title: String,
+    pub message: String,
+    pub level: NotificationLevel,
+    pub expires: Instant,
+}
+
+/// The toasts on screen, oldest first
+#[derive(Debug, Default)]
+pub struct ToastQueue {
+    entries: Vec<ToastEntry>,
+}
+
+impl ToastQueue {
+    /// Show a toast, pushing out the oldest when [`MAX_TOASTS`] are up
+    pub fn push(&mut self, title: &str, message: &str, level: NotificationLevel, now: Instant) {
+        let secs = match level {
+            NotificationLevel::Warning | NotificationLevel::Error => LONG_TOAST_SECS,
+            NotificationLevel::Info | NotificationLevel::Success => TOAST_SECS,
+        };
+        if self.entries.len() == MAX_TOASTS {
+            self.entries.remove(0);
+        }
+        self.entries.push(ToastEntry {
+            title: title.to_string(),
+            message: message.to_string(),
+            level,
+            expires: now + Duration::from_secs(secs),
+        });
+    }
+
+    /// Drop expired toasts; whether any went
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.expires > now);
+        self.entries.len() != before
+    }
+
+    pub fn dismiss(&mut self, index: usize) {
+        if index < self.entries.len() {
+            self.entries.remove(index);
+        }
+    }
+
+    pub fn entries(&self) -> &[ToastEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct ToastStack {
+    #[deref]
+    view: View,
+
+    #[rust]
+    queue: ToastQueue,
+
+    /// Runs while any toast is up
+    #[rust]
+    expire_timer: Timer,
+}
+
+impl Widget for ToastStack {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if self.expire_timer.is_event(event).is_some() && self.queue.expire(Instant::now()) {
+            self.sync(cx);
+        }
+
+        let slots = [ids!(toast_0), ids!(toast_1), ids!(toast_2)];
+        for (index, slot) in slots.into_iter().enumerate().rev() {
+            let view = self.view.view(slot);
+            if view.visible() && matches!(event.hits(cx, view.area()), Hit::FingerUp(fe) if fe.is_over) {
+                self.queue.dismiss(index);
+                self.sync(cx);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl ToastStack {
+    /// Fill the slots from the queue and stop the timer once it is empty
+    fn sync(&mut self, cx: &mut Cx) {
+        let slots = [ids!(toast_0), ids!(toast_1), ids!(toast_2)];
+        for (index, slot) in slots.into_iter().enumerate() {
+            let view = self.view.view(slot);
+            match self.queue.entries().get(index) {
+                Some(entry) => {
+                    let level = match entry.level {
+                        NotificationLevel::Info => 0.0,
+                        NotificationLevel::Success => 1.0,
+                        NotificationLevel::Warning => 2.0,
+                        NotificationLevel::Error => 3.0,
+                    };
+                    view.label(ids!(title_label)).set_text(cx, &entry.title);
+                    let message = view.label(ids!(message_label));
+                    message.set_text(cx, &entry.message);
+                    message.set_visible(cx, !entry.message.is_empty());
+                    view.apply_over(cx, live! { draw_bg: { level: (level) } });
+                    view.set_visible(cx, true);
+                }
+                None => view.set_visible(cx, false),
+            }
+        }
+        if self.queue.is_empty() {
+            cx.stop_timer(self.expire_timer);
+        }
+        self.view.redraw(cx);
+    }
+}
+
+impl ToastStackRef {
+    pub fn show(&self, cx: &mut Cx, title: &str, message: &str, level: NotificationLevel) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if inner.queue.is_empty() {
+                inner.expire_timer = cx.start_interval(1.0);
+            }
+            inner.queue.push(title, message, level, Instant::now());
+            inner.sync(cx);
+        }
+    }
+
+    pub fn update_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            for slot in [ids!(toast_0), ids!(toast_1), ids!(toast_2)] {
+                inner.view.view(slot).apply_over(cx, live! {
+                    draw_bg: { dark_mode: (dark_mode) }
+                    title_label = { draw_text: { dark_mode: (dark_mode) } }
+                    message_label = { draw_text: { dark_mode: (dark_mode) } }
+                });
+            }
+            inner.view.redraw(cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_keeps_the_newest_toasts() {
+        let now = Instant::now();
+        let mut queue = ToastQueue::default();
+        for n in 0..MAX_TOASTS + 1 {
+            queue.push(&format!("toast {}", n), "", NotificationLevel::Info, now);
+        }
+        let titles: Vec<&str> = queue.entries().iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, ["toast 1", "toast 2", "toast 3"]);
+
+        queue.dismiss(0);
+        assert_eq!(queue.entries()[0].title, "toast 2");
+    }
+
+    #[test]
+    fn test_errors_stay_longer() {
+        let now = Instant::now();
+        let mut queue = ToastQueue::default();
+        queue.push("saved", "", NotificationLevel::Success, now);
+        queue.push("failed", "disk full", NotificationLevel::Error, now);
+
+        assert!(!queue.expire(now + Duration::from_secs(TOAST_SECS - 1)));
+        assert!(queue.expire(now + Duration::from_secs(TOAST_SECS)));
+        assert_eq!(queue.entries()[0].title, "failed");
+        assert!(queue.expire(now + Duration::from_secs(LONG_TOAST_SECS)));
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+//! Window title and dock badge
+//!
+//! The title names the active app ("MoFA Studio — Transcriber") and, while
+//! jobs from [`mofa_widgets::jobs`] are running, their progress
+//! ("MoFA Studio — Book Cast (rendering 62%)"). On macOS the dock badge shows
+//! the number of active jobs and the dock icon bounces once when one finishes;
+//! on Windows the taskbar button flashes instead, and there is no badge.
+//!
+//! Makepad only sets the window title when the window is created, so the
+//! title is changed on the native window. Linux has no such handle to reach
+//! (Makepad owns the X11/Wayland window), so it keeps the title from
+//! `live_design!` and shows neither badge nor attention request.
+
+use mofa_widgets::jobs::JobRegistry;
+
+const APP_TITLE: &str = "MoFA Studio";
+
+/// Title for the active app and job summary
+pub fn window_title(app_name: &str, jobs_summary: Option<&str>) -> String {
+    let mut title = format!("{} \u{2014} {}", APP_TITLE, app_name);
+    if let Some(summary) = jobs_summary {
+        title.push_str(&format!(" ({})", summary));
+    }
+    title
+}
+
+/// Applies title and badge changes, skipping unchanged values
+#[derive(Default)]
+pub struct WindowStatus {
+    title: String,
+    active_jobs: usize,
+    finished_jobs: u64,
+}
+
+impl WindowStatus {
+    /// Refresh from the global job registry; cheap enough to call on a timer
+    pub fn update(&mut self, app_name: &str) {
+        let registry = JobRegistry::global();
+        let active_jobs = registry.active().len();
+        let finished_jobs = registry.finished_count();
+
+        let title = window_title(app_name, registry.summary().as_deref());
+        if title != self.title {
+            platform::set_title(&title);
+            self.title = title;
+        }
+
+        if active_jobs != self.active_jobs {
+            platform::set_badge(active_jobs);
+            self.active_jobs = active_jobs;
+        }
+
+        if finished_jobs > self.finished_jobs {
+            platform::bounce();
+        }
+        self.finished_jobs = finished_jobs;
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2::rc::Retained;
+    use objc2_app_kit::{NSApplication, NSRequestUserAttentionType, NSWindow};
+    use objc2_foundation::{MainThreadMarker, NSString};
+
+    fn app() -> Option<Retained<NSApplication>> {
+        MainThreadMarker::new().map(NSApplication::sharedApplication)
+    }
+
+    pub fn set_title(title: &str) {
+        let Some(app) = app() else { return };
+        let window: Option<Retained<NSWindow>> = app.mainWindow().or_else(|| {
+            let windows = app.windows();
+            (windows.len() > 0).then(|| windows.objectAtIndex(0))
+        });
+        if let Some(window) = window {
+            window.setTitle(&NSString::from_str(title));
+        }
+    }
+
+    pub fn set_badge(count: usize) {
+        let Some(app) = app() else { return };
+        let label = (count > 0).then(|| NSString::from_str(&count.to_string()));
+        #[allow(unused_unsafe)]
+        unsafe {
+            app.dockTile().setBadgeLabel(label.as_deref());
+        }
+    }
+
+    /// Bounce the dock icon once; does nothing while the app is active
+    pub fn bounce() {
+        let Some(app) = app() else { return };
+        #[allow(unused_unsafe)]
+        unsafe {
+            app.requestUserAttention(NSRequestUserAttentionType::InformationalRequest);
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{BOOL, HWND, LPARAM};
+    use windows::Win32::System::Threading::GetCurrentThreadId;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumThreadWindows, FlashWindowEx, GetWindow, IsWindowVisible, SetWindowTextW, FLASHWINFO,
+        FLASHW_TIMERNOFG, FLASHW_TRAY, GW_OWNER,
+    };
+
+    /// The studio window: the first visible, unowned window of the UI thread
+    fn main_window() -> Option<HWND> {
+        unsafe extern "system" fn visit(hwnd: HWND, found: LPARAM) -> BOOL {
+            if IsWindowVisible(hwnd).as_bool() && GetWindow(hwnd, GW_OWNER).is_err() {
+                *(found.0 as *mut Option<HWND>) = Some(hwnd);
+                return BOOL(0);
+            }
+            BOOL(1)
+        }
+        let mut found: Option<HWND> = None;
+        unsafe {
+            let _ = EnumThreadWindows(GetCurrentThreadId(), Some(visit), LPARAM(&mut found as *mut _ as isize));
+        }
+        found
+    }
+
+    pub fn set_title(title: &str) {
+        let Some(hwnd) = main_window() else { return };
+        let wide: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+        unsafe {
+            let _ = SetWindowTextW(hwnd, PCWSTR(wide.as_ptr()));
+        }
+    }
+
+    /// The taskbar has no count badge without an overlay icon per count
+    pub fn set_badge(_count: usize) {}
+
+    /// Flash the taskbar button until the window comes to the foreground
+    pub fn bounce() {
+        let Some(hwnd) = main_window() else { return };
+        let info = FLASHWINFO {
+            cbSize: std::mem::size_of::<FLASHWINFO>() as u32,
+            hwnd,
+            dwFlags: FLASHW_TRAY | FLASHW_TIMERNOFG,
+            uCount: 1,
+            dwTimeout: 0,
+        };
+        unsafe {
+            let _ = FlashWindowEx(&info);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub fn set_title(_title: &str) {}
+    pub fn set_badge(_count: usize) {}
+    pub fn bounce() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_title() {
+        assert_eq!(window_title("Transcriber", None), "MoFA Studio \u{2014} Transcriber");
+        assert_eq!(
+            window_title("Book Cast", Some("rendering 62%")),
+            "MoFA Studio \u{2014} Book Cast (rendering 62%)"
+        );
+    }
+}
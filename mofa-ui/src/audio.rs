@@ -1,17 +1,362 @@
 //! Audio device management and mic level monitoring
 //!
 //! Shared audio infrastructure for MoFA applications.
+//!
+//! Besides level monitoring, the manager can route the monitored input to an
+//! output device ("direct monitoring") so users can hear their microphone.
+//! The passthrough shares the monitoring capture stream and feeds the output
+//! through a lock-free single-producer ring; the output side drops samples
+//! queued beyond the target latency.
+//!
+//! For checking a setup by ear, the manager can also play a short clip (such
+//! as [`test_tone`]) through the selected output and record a few seconds
+//! from the monitored input; both streams are released as soon as they end.
+//!
+//! An optional automatic gain control stage sits at the head of the capture
+//! path, so the level meter, passthrough and recordings all see the levelled
+//! signal. It works in place on a buffer reserved up front and is skipped
+//! entirely while disabled.
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Device, Host, Stream, StreamConfig};
 use parking_lot::Mutex;
+use rtrb::{Consumer, Producer, RingBuffer};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
 use std::sync::Arc;
 
+/// Default ring buffer size for input passthrough
+pub const DEFAULT_PASSTHROUGH_LATENCY_MS: f32 = 20.0;
+
+/// Longest passthrough latency that can be set; the ring is sized for it
+const MAX_PASSTHROUGH_LATENCY_MS: f32 = 500.0;
+
+/// Highest software gain accepted for passthrough
+pub const MAX_PASSTHROUGH_GAIN: f32 = 4.0;
+
+/// Gain never pushes a sample past this, whatever the level estimate says
+const AGC_CEILING: f32 = 0.98;
+
+/// Window of the AGC's RMS level estimate
+const AGC_LEVEL_WINDOW_MS: f32 = 50.0;
+
+/// Samples reserved for the capture scratch buffers, so callbacks up to this
+/// size never allocate
+const CAPTURE_SCRATCH_SAMPLES: usize = 16384;
+
+/// Automatic gain control settings for the capture path
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AgcSettings {
+    /// Level the gain steers toward (RMS, full scale = 1.0)
+    pub target_rms: f32,
+    /// Highest gain applied (linear)
+    pub max_gain: f32,
+    /// Time constant for turning the gain down on louder input
+    pub attack_ms: f32,
+    /// Time constant for turning the gain up on quieter input
+    pub release_ms: f32,
+}
+
+impl Default for AgcSettings {
+    fn default() -> Self {
+        Self {
+            target_rms: 0.1,
+            max_gain: 8.0,
+            attack_ms: 20.0,
+            release_ms: 400.0,
+        }
+    }
+}
+
+/// Gain state of the AGC stage, owned by the capture callback
+struct AutoGain {
+    gain: f32,
+    mean_square: f32,
+}
+
+impl AutoGain {
+    fn new() -> Self {
+        Self { gain: 1.0, mean_square: 0.0 }
+    }
+
+    /// Level interleaved `data` in place
+    fn process(&mut self, data: &mut [f32], channels: usize, sample_rate: u32, settings: &AgcSettings) {
+        let rate = sample_rate.max(1) as f32;
+        // One-pole smoothing coefficient for a time constant in ms
+        let coeff = |ms: f32| 1.0 - (-1000.0 / (ms.max(0.1) * rate)).exp();
+        let level_coeff = coeff(AGC_LEVEL_WINDOW_MS);
+        let attack = coeff(settings.attack_ms);
+        let release = coeff(settings.release_ms);
+        let target = settings.target_rms.max(0.0);
+        let max_gain = settings.max_gain.max(0.0);
+
+        for frame in data.chunks_mut(channels.max(1)) {
+            let mut peak = 0.0f32;
+            let mut power = 0.0f32;
+            for &sample in frame.iter() {
+                peak = peak.max(sample.abs());
+                power += sample * sample;
+            }
+            self.mean_square += level_coeff * (power / frame.len() as f32 - self.mean_square);
+
+            let rms = self.mean_square.sqrt();
+            let wanted = if rms * max_gain > target { target / rms } else { max_gain };
+            let smoothing = if wanted < self.gain { attack } else { release };
+            self.gain += smoothing * (wanted - self.gain);
+            // A sudden loud frame would clip before the attack catches up
+            if peak * self.gain > AGC_CEILING {
+                self.gain = AGC_CEILING / peak;
+            }
+
+            for sample in frame.iter_mut() {
+                *sample *= self.gain;
+            }
+        }
+    }
+}
+
+/// AGC switch, settings and the gain last applied, shared with the capture callback
+struct AgcShared {
+    enabled: AtomicBool,
+    /// f32 bits
+    gain: AtomicU32,
+    /// f32 bits of each [`AgcSettings`] field
+    target_rms: AtomicU32,
+    max_gain: AtomicU32,
+    attack_ms: AtomicU32,
+    release_ms: AtomicU32,
+}
+
+impl AgcShared {
+    fn new() -> Self {
+        let shared = Self {
+            enabled: AtomicBool::new(false),
+            gain: AtomicU32::new(1.0f32.to_bits()),
+            target_rms: AtomicU32::new(0),
+            max_gain: AtomicU32::new(0),
+            attack_ms: AtomicU32::new(0),
+            release_ms: AtomicU32::new(0),
+        };
+        shared.set_settings(AgcSettings::default());
+        shared
+    }
+
+    fn settings(&self) -> AgcSettings {
+        let load = |value: &AtomicU32| f32::from_bits(value.load(Ordering::Relaxed));
+        AgcSettings {
+            target_rms: load(&self.target_rms),
+            max_gain: load(&self.max_gain),
+            attack_ms: load(&self.attack_ms),
+            release_ms: load(&self.release_ms),
+        }
+    }
+
+    fn set_settings(&self, settings: AgcSettings) {
+        self.target_rms.store(settings.target_rms.to_bits(), Ordering::Relaxed);
+        self.max_gain.store(settings.max_gain.to_bits(), Ordering::Relaxed);
+        self.attack_ms.store(settings.attack_ms.to_bits(), Ordering::Relaxed);
+        self.release_ms.store(settings.release_ms.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Called from the capture callback: `data` untouched while disabled,
+    /// otherwise a levelled copy in `scratch`
+    fn apply<'a>(
+        &self,
+        state: &mut AutoGain,
+        scratch: &'a mut Vec<f32>,
+        data: &'a [f32],
+        channels: usize,
+        sample_rate: u32,
+    ) -> &'a [f32] {
+        if !self.enabled.load(Ordering::Relaxed) {
+            // Start from unity when switched back on. The reported gain is
+            // reset here as well: a block levelled while bypass was being
+            // turned on may have stored its gain after set_agc_enabled did.
+            state.gain = 1.0;
+            self.gain.store(1.0f32.to_bits(), Ordering::Relaxed);
+            return data;
+        }
+        scratch.clear();
+        scratch.extend_from_slice(data);
+        state.process(scratch, channels, sample_rate, &self.settings());
+        self.gain.store(state.gain.to_bits(), Ordering::Relaxed);
+        scratch
+    }
+}
+
+/// Test tone sweep range in Hz
+const TONE_START_HZ: f32 = 300.0;
+const TONE_END_HZ: f32 = 1200.0;
+const TONE_AMPLITUDE: f32 = 0.3;
+const TONE_FADE_SECS: f32 = 0.05;
+
+/// A mono sine sweep from 300 Hz to 1.2 kHz, faded in and out so it starts
+/// and stops without clicks
+pub fn test_tone(sample_rate: u32, secs: f32) -> Vec<f32> {
+    let rate = sample_rate.max(1) as f32;
+    let len = (rate * secs.max(0.0)) as usize;
+    let fade = (rate * TONE_FADE_SECS) as usize;
+    // Exponential sweep: the phase is the integral of the frequency
+    let ratio = TONE_END_HZ / TONE_START_HZ;
+    let k = ratio.ln() / secs.max(f32::EPSILON);
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / rate;
+            let phase = 2.0 * std::f32::consts::PI * TONE_START_HZ * ((k * t).exp() - 1.0) / k;
+            let envelope = (i.min(len - 1 - i) as f32 / fade.max(1) as f32).min(1.0);
+            phase.sin() * TONE_AMPLITUDE * envelope
+        })
+        .collect()
+}
+
+/// Heuristic: does the output device name look like headphones?
+///
+/// Used to warn about acoustic feedback before monitoring through speakers.
+pub fn is_likely_headphones(device_name: &str) -> bool {
+    let name = device_name.to_lowercase();
+    ["headphone", "headset", "earphone", "earbud", "airpods", "buds"]
+        .iter()
+        .any(|hint| name.contains(hint))
+}
+
 /// Audio device info
-#[derive(Clone, Debug)]
+///
+/// Capabilities are as reported by the backend when the device list was
+/// read; empty ranges and zero channels mean the backend reported none, in
+/// which case the `supports_*` checks give the device the benefit of the
+/// doubt. Check them before asking a device for a specific config.
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct AudioDeviceInfo {
     pub name: String,
     pub is_default: bool,
+    /// Supported sample rate ranges in Hz, inclusive
+    pub sample_rates: Vec<(u32, u32)>,
+    /// Most channels any supported config offers
+    pub max_channels: u16,
+    /// Config the device uses unless asked for another
+    pub default_format: Option<AudioFormatInfo>,
+}
+
+/// A device's default stream format
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioFormatInfo {
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Sample type, e.g. "f32" or "i16"
+    pub sample_format: String,
+}
+
+impl AudioDeviceInfo {
+    fn from_device(device: &Device, name: String, is_default: bool, input: bool) -> Self {
+        let ranges: Vec<cpal::SupportedStreamConfigRange> = if input {
+            device.supported_input_configs().map(|c| c.collect()).unwrap_or_default()
+        } else {
+            device.supported_output_configs().map(|c| c.collect()).unwrap_or_default()
+        };
+        let default_config = if input {
+            device.default_input_config()
+        } else {
+            device.default_output_config()
+        };
+
+        let mut sample_rates: Vec<(u32, u32)> = ranges
+            .iter()
+            .map(|r| (r.min_sample_rate().0, r.max_sample_rate().0))
+            .collect();
+        sample_rates.sort_unstable();
+        sample_rates.dedup();
+
+        Self {
+            name,
+            is_default,
+            sample_rates,
+            max_channels: ranges.iter().map(|r| r.channels()).max().unwrap_or(0),
+            default_format: default_config.ok().map(|config| AudioFormatInfo {
+                sample_rate: config.sample_rate().0,
+                channels: config.channels(),
+                sample_format: config.sample_format().to_string(),
+            }),
+        }
+    }
+
+    /// Whether the device can run at `rate` Hz
+    pub fn supports_sample_rate(&self, rate: u32) -> bool {
+        self.sample_rates.is_empty()
+            || self.sample_rates.iter().any(|&(min, max)| (min..=max).contains(&rate))
+    }
+
+    /// Whether the device offers at least `channels` channels
+    pub fn supports_channels(&self, channels: u16) -> bool {
+        self.max_channels == 0 || self.max_channels >= channels
+    }
+
+    /// One line describing the device, e.g. "8–48 kHz · 2 ch · default 48 kHz f32"
+    pub fn capability_summary(&self) -> String {
+        let mut parts = Vec::new();
+        let min = self.sample_rates.iter().map(|r| r.0).min();
+        let max = self.sample_rates.iter().map(|r| r.1).max();
+        match (min, max) {
+            (Some(min), Some(max)) if min == max => parts.push(format_rate(min)),
+            (Some(min), Some(max)) => parts.push(format!(
+                "{}\u{2013}{}",
+                format_rate(min).trim_end_matches(" kHz"),
+                format_rate(max)
+            )),
+            _ => {}
+        }
+        if self.max_channels > 0 {
+            parts.push(format!("{} ch", self.max_channels));
+        }
+        if let Some(ref format) = self.default_format {
+            parts.push(format!("default {} {}", format_rate(format.sample_rate), format.sample_format));
+        }
+        if parts.is_empty() {
+            return "Capabilities unknown".to_string();
+        }
+        parts.join(" \u{00b7} ")
+    }
+
+    /// Warning if the device can't run at `rate` Hz with `channels` channels
+    pub fn compatibility_warning(&self, rate: u32, channels: u16) -> Option<String> {
+        let mut problems = Vec::new();
+        if !self.supports_sample_rate(rate) {
+            problems.push(format!("no {}", format_rate(rate)));
+        }
+        if !self.supports_channels(channels) {
+            problems.push(match self.max_channels {
+                1 => "mono only".to_string(),
+                n => format!("only {} channels", n),
+            });
+        }
+        (!problems.is_empty()).then(|| problems.join(", "))
+    }
+}
+
+/// "16 kHz", "44.1 kHz"
+fn format_rate(rate: u32) -> String {
+    let khz = rate as f64 / 1000.0;
+    if khz.fract() == 0.0 {
+        format!("{} kHz", khz as u32)
+    } else {
+        format!("{} kHz", (khz * 10.0).round() / 10.0)
+    }
+}
+
+/// A finished recording from the monitored input, mono
+#[derive(Clone, Debug, Default)]
+pub struct Recording {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl Recording {
+    /// Highest absolute sample (0.0 - 1.0)
+    pub fn peak(&self) -> f32 {
+        self.samples.iter().fold(0.0f32, |max, s| max.max(s.abs()))
+    }
+
+    pub fn duration_secs(&self) -> f32 {
+        self.samples.len() as f32 / self.sample_rate.max(1) as f32
+    }
 }
 
 /// Shared state for mic level
@@ -29,6 +374,224 @@ impl Default for MicLevelState {
     }
 }
 
+/// Capture side of a passthrough ring; never blocks
+struct PassthroughWriter {
+    producer: Producer<f32>,
+}
+
+impl PassthroughWriter {
+    /// Push interleaved frames, downmixed to mono. While the ring is full
+    /// the newest samples are dropped.
+    fn push_frames(&mut self, data: &[f32], channels: usize) {
+        let channels = channels.max(1);
+        for frame in data.chunks(channels) {
+            let _ = self.producer.push(frame.iter().sum::<f32>() / frame.len() as f32);
+        }
+    }
+}
+
+/// Output side of a passthrough ring
+struct PassthroughReader {
+    consumer: Consumer<f32>,
+    /// Fractional read position, for input/output sample rate conversion
+    position: f64,
+}
+
+impl PassthroughReader {
+    /// Drop the oldest samples beyond `max_len` to bound latency
+    fn trim(&mut self, max_len: usize) {
+        self.skip(self.len().saturating_sub(max_len.max(1)));
+    }
+
+    fn skip(&mut self, count: usize) {
+        if let Ok(chunk) = self.consumer.read_chunk(count.min(self.len())) {
+            chunk.commit_all();
+        }
+    }
+
+    /// Fill interleaved output frames; `ratio` is input rate / output rate.
+    /// Underruns produce silence.
+    fn fill_output(&mut self, out: &mut [f32], channels: usize, ratio: f64, gain: f32) {
+        let channels = channels.max(1);
+        for frame in out.chunks_mut(channels) {
+            let sample = match self.consumer.peek().ok().copied() {
+                Some(sample) => {
+                    self.position += ratio;
+                    let consumed = (self.position as usize).min(self.len());
+                    self.skip(consumed);
+                    self.position -= consumed as f64;
+                    (sample * gain).clamp(-1.0, 1.0)
+                }
+                None => 0.0,
+            };
+            frame.fill(sample);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.consumer.slots()
+    }
+}
+
+/// Mono ring holding up to `capacity` samples between two callbacks
+fn passthrough_ring(capacity: usize) -> (PassthroughWriter, PassthroughReader) {
+    let (producer, consumer) = RingBuffer::new(capacity.max(1));
+    (PassthroughWriter { producer }, PassthroughReader { consumer, position: 0.0 })
+}
+
+/// Fixed-length mono capture, filled from the monitoring stream's callback
+struct RecordShared {
+    active: AtomicBool,
+    max_frames: AtomicUsize,
+    samples: Mutex<Vec<f32>>,
+}
+
+impl RecordShared {
+    fn new() -> Self {
+        Self {
+            active: AtomicBool::new(false),
+            max_frames: AtomicUsize::new(0),
+            samples: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn start(&self, max_frames: usize) {
+        let mut samples = self.samples.lock();
+        samples.clear();
+        samples.reserve(max_frames);
+        self.max_frames.store(max_frames, Ordering::Relaxed);
+        self.active.store(true, Ordering::Relaxed);
+    }
+
+    /// Called from the capture callback; stops by itself once full
+    fn push_input(&self, data: &[f32], channels: usize) {
+        if !self.active.load(Ordering::Relaxed) {
+            return;
+        }
+        let channels = channels.max(1);
+        let max_frames = self.max_frames.load(Ordering::Relaxed);
+        let mut samples = self.samples.lock();
+        for frame in data.chunks(channels) {
+            if samples.len() >= max_frames {
+                break;
+            }
+            samples.push(frame.iter().sum::<f32>() / frame.len() as f32);
+        }
+        if samples.len() >= max_frames {
+            self.active.store(false, Ordering::Relaxed);
+        }
+    }
+
+    fn take(&self) -> Vec<f32> {
+        self.active.store(false, Ordering::Relaxed);
+        std::mem::take(&mut *self.samples.lock())
+    }
+}
+
+/// State shared between the capture callback, the output callback and the
+/// manager; the samples themselves go through a [`passthrough_ring`]
+struct PassthroughShared {
+    active: AtomicBool,
+    /// f32 bits
+    gain: AtomicU32,
+    /// f32 bits
+    target_latency_ms: AtomicU32,
+    /// Achieved round-trip latency estimate in ms (f32 bits, 0 = unknown)
+    latency_ms: AtomicU32,
+    input_sample_rate: AtomicU32,
+    input_callback_frames: AtomicUsize,
+    /// Why passthrough stopped on its own (capture error, device change)
+    stop_reason: Mutex<Option<String>>,
+}
+
+impl PassthroughShared {
+    fn new() -> Self {
+        Self {
+            active: AtomicBool::new(false),
+            gain: AtomicU32::new(1.0f32.to_bits()),
+            target_latency_ms: AtomicU32::new(DEFAULT_PASSTHROUGH_LATENCY_MS.to_bits()),
+            latency_ms: AtomicU32::new(0),
+            input_sample_rate: AtomicU32::new(0),
+            input_callback_frames: AtomicUsize::new(0),
+            stop_reason: Mutex::new(None),
+        }
+    }
+
+    /// Samples the output may keep queued for the current target latency
+    fn max_queued(&self) -> usize {
+        let rate = self.input_sample_rate.load(Ordering::Relaxed) as f32;
+        let target_ms = f32::from_bits(self.target_latency_ms.load(Ordering::Relaxed));
+        (rate * target_ms / 1000.0) as usize
+    }
+
+    /// Called from the capture error callback
+    fn capture_failed(&self, error: String) {
+        if self.active.swap(false, Ordering::Relaxed) {
+            *self.stop_reason.lock() = Some(error);
+        }
+    }
+}
+
+/// Capture callback end of the passthrough: the ring writer of the current
+/// output, swapped for new ones the manager hands over
+struct PassthroughFeed {
+    shared: Arc<PassthroughShared>,
+    new_writers: Consumer<PassthroughWriter>,
+    writer: Option<PassthroughWriter>,
+}
+
+impl PassthroughFeed {
+    fn push(&mut self, data: &[f32], channels: usize) {
+        self.shared
+            .input_callback_frames
+            .store(data.len() / channels.max(1), Ordering::Relaxed);
+        if let Ok(writer) = self.new_writers.pop() {
+            self.writer = Some(writer);
+        }
+        // The output stream owning the other end is gone
+        if self.writer.as_ref().is_some_and(|w| w.producer.is_abandoned()) {
+            self.writer = None;
+        }
+        if let Some(writer) = self.writer.as_mut() {
+            if self.shared.active.load(Ordering::Relaxed) {
+                writer.push_frames(data, channels);
+            }
+        }
+    }
+}
+
+/// Everything the capture callback feeds, in order: AGC, level meter,
+/// passthrough and recorder
+struct CaptureSink {
+    mic_level: Arc<Mutex<MicLevelState>>,
+    passthrough: PassthroughFeed,
+    recorder: Arc<RecordShared>,
+    agc: Arc<AgcShared>,
+    agc_state: AutoGain,
+    agc_scratch: Vec<f32>,
+    channels: usize,
+    sample_rate: u32,
+}
+
+impl CaptureSink {
+    fn push(&mut self, data: &[f32]) {
+        let data = self.agc.apply(&mut self.agc_state, &mut self.agc_scratch, data, self.channels, self.sample_rate);
+
+        let max = data.iter().fold(0.0f32, |max, sample| max.max(sample.abs()));
+        let mut state = self.mic_level.lock();
+        // Smooth the level with exponential decay
+        state.level = state.level * 0.7 + max * 0.3;
+        if max > state.peak {
+            state.peak = max;
+        } else {
+            state.peak *= 0.995; // Slow decay for peak
+        }
+        drop(state);
+        self.passthrough.push(data, self.channels);
+        self.recorder.push_input(data, self.channels);
+    }
+}
+
 /// Audio manager for device enumeration and mic monitoring
 pub struct AudioManager {
     host: Host,
@@ -36,6 +599,15 @@ pub struct AudioManager {
     mic_level: Arc<Mutex<MicLevelState>>,
     current_input_device: Option<String>,
     current_output_device: Option<String>,
+    passthrough: Arc<PassthroughShared>,
+    passthrough_stream: Option<Stream>,
+    /// Hands ring writers to the running capture callback
+    passthrough_writers: Option<Producer<PassthroughWriter>>,
+    recorder: Arc<RecordShared>,
+    agc: Arc<AgcShared>,
+    clip_stream: Option<Stream>,
+    /// Set by the clip's output callback once the clip has been played
+    clip_done: Arc<AtomicBool>,
 }
 
 impl AudioManager {
@@ -47,6 +619,13 @@ impl AudioManager {
             mic_level: Arc::new(Mutex::new(MicLevelState::default())),
             current_input_device: None,
             current_output_device: None,
+            passthrough: Arc::new(PassthroughShared::new()),
+            passthrough_stream: None,
+            passthrough_writers: None,
+            recorder: Arc::new(RecordShared::new()),
+            agc: Arc::new(AgcShared::new()),
+            clip_stream: None,
+            clip_done: Arc::new(AtomicBool::new(true)),
         }
     }
 
@@ -60,7 +639,7 @@ impl AudioManager {
             for device in input_devices {
                 if let Ok(name) = device.name() {
                     let is_default = default_name.as_ref().map_or(false, |d| d == &name);
-                    devices.push(AudioDeviceInfo { name, is_default });
+                    devices.push(AudioDeviceInfo::from_device(&device, name, is_default, true));
                 }
             }
         }
@@ -83,7 +662,7 @@ impl AudioManager {
             for device in output_devices {
                 if let Ok(name) = device.name() {
                     let is_default = default_name.as_ref().map_or(false, |d| d == &name);
-                    devices.push(AudioDeviceInfo { name, is_default });
+                    devices.push(AudioDeviceInfo::from_device(&device, name, is_default, false));
                 }
             }
         }
@@ -106,10 +685,77 @@ impl AudioManager {
         None
     }
 
+    /// Find output device by name
+    fn find_output_device(&self, name: &str) -> Option<Device> {
+        self.host
+            .output_devices()
+            .ok()?
+            .find(|device| device.name().map(|n| n == name).unwrap_or(false))
+    }
+
+    /// `name`, else the selected output, else the system default
+    fn output_device(&self, name: Option<&str>) -> Result<Device, String> {
+        match name.or(self.current_output_device.as_deref()) {
+            Some(name) => self
+                .find_output_device(name)
+                .ok_or_else(|| format!("Device not found: {}", name)),
+            None => self
+                .host
+                .default_output_device()
+                .ok_or_else(|| "No default output device".to_string()),
+        }
+    }
+
+    /// Open and start an output stream in the device's default format;
+    /// `render` fills interleaved f32 frames with `channels` channels
+    fn start_output<R, E>(device: &Device, render: impl FnOnce(usize, u32) -> R, error_callback: E) -> Result<Stream, String>
+    where
+        R: FnMut(&mut [f32]) + Send + 'static,
+        E: FnMut(cpal::StreamError) + Send + 'static,
+    {
+        let config = device
+            .default_output_config()
+            .map_err(|e| format!("Failed to get output config: {}", e))?;
+        let sample_format = config.sample_format();
+        let config: StreamConfig = config.into();
+        let mut render = render(config.channels as usize, config.sample_rate.0);
+
+        let stream = match sample_format {
+            cpal::SampleFormat::F32 => device.build_output_stream(
+                &config,
+                move |data: &mut [f32], _: &cpal::OutputCallbackInfo| render(data),
+                error_callback,
+                None,
+            ),
+            cpal::SampleFormat::I16 => {
+                let mut scratch = Vec::new();
+                device.build_output_stream(
+                    &config,
+                    move |data: &mut [i16], _: &cpal::OutputCallbackInfo| {
+                        scratch.resize(data.len(), 0.0f32);
+                        render(&mut scratch);
+                        for (out, sample) in data.iter_mut().zip(&scratch) {
+                            *out = (sample * i16::MAX as f32) as i16;
+                        }
+                    },
+                    error_callback,
+                    None,
+                )
+            }
+            _ => return Err("Unsupported sample format".to_string()),
+        }
+        .map_err(|e| format!("Failed to build output stream: {}", e))?;
+
+        stream
+            .play()
+            .map_err(|e| format!("Failed to play stream: {}", e))?;
+        Ok(stream)
+    }
+
     /// Start monitoring mic level for a specific device
     pub fn start_mic_monitoring(&mut self, device_name: Option<&str>) -> Result<(), String> {
         // Stop existing stream
-        self.stop_mic_monitoring();
+        self.stop_mic_monitoring("Input device changed");
 
         // Get device
         let device = if let Some(name) = device_name {
@@ -131,56 +777,53 @@ impl AudioManager {
 
         let sample_format = config.sample_format();
         let config: StreamConfig = config.into();
+        let channels = config.channels as usize;
+        self.passthrough
+            .input_sample_rate
+            .store(config.sample_rate.0, Ordering::Relaxed);
 
-        let mic_level = self.mic_level.clone();
+        let (writers, writers_rx) = RingBuffer::new(4);
+        let mut sink = CaptureSink {
+            mic_level: self.mic_level.clone(),
+            passthrough: PassthroughFeed {
+                shared: self.passthrough.clone(),
+                new_writers: writers_rx,
+                writer: None,
+            },
+            recorder: self.recorder.clone(),
+            agc: self.agc.clone(),
+            agc_state: AutoGain::new(),
+            agc_scratch: Vec::with_capacity(CAPTURE_SCRATCH_SAMPLES),
+            channels,
+            sample_rate: config.sample_rate.0,
+        };
+        let passthrough_err = self.passthrough.clone();
+        let error_callback = move |err: cpal::StreamError| {
+            eprintln!("Audio input error: {}", err);
+            passthrough_err.capture_failed(format!("Capture failed: {}", err));
+        };
 
         // Build stream based on sample format
         let stream = match sample_format {
-            cpal::SampleFormat::F32 => {
+            cpal::SampleFormat::F32 => device.build_input_stream(
+                &config,
+                move |data: &[f32], _: &cpal::InputCallbackInfo| sink.push(data),
+                error_callback,
+                None,
+            ),
+            cpal::SampleFormat::I16 => {
+                let mut samples = Vec::with_capacity(CAPTURE_SCRATCH_SAMPLES);
                 device.build_input_stream(
                     &config,
-                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                        let mut max = 0.0f32;
-                        for &sample in data {
-                            let abs = sample.abs();
-                            if abs > max {
-                                max = abs;
-                            }
-                        }
-                        let mut state = mic_level.lock();
-                        // Smooth the level with exponential decay
-                        state.level = state.level * 0.7 + max * 0.3;
-                        if max > state.peak {
-                            state.peak = max;
-                        } else {
-                            state.peak *= 0.995; // Slow decay for peak
-                        }
+                    move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                        samples.clear();
+                        samples.extend(data.iter().map(|&s| s as f32 / i16::MAX as f32));
+                        sink.push(&samples);
                     },
-                    |err| eprintln!("Audio input error: {}", err),
+                    error_callback,
                     None,
                 )
             }
-            cpal::SampleFormat::I16 => device.build_input_stream(
-                &config,
-                move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                    let mut max = 0.0f32;
-                    for &sample in data {
-                        let abs = (sample as f32 / i16::MAX as f32).abs();
-                        if abs > max {
-                            max = abs;
-                        }
-                    }
-                    let mut state = mic_level.lock();
-                    state.level = state.level * 0.7 + max * 0.3;
-                    if max > state.peak {
-                        state.peak = max;
-                    } else {
-                        state.peak *= 0.995;
-                    }
-                },
-                |err| eprintln!("Audio input error: {}", err),
-                None,
-            ),
             _ => return Err("Unsupported sample format".to_string()),
         }
         .map_err(|e| format!("Failed to build stream: {}", e))?;
@@ -189,13 +832,22 @@ impl AudioManager {
             .play()
             .map_err(|e| format!("Failed to play stream: {}", e))?;
         self.input_stream = Some(stream);
+        self.passthrough_writers = Some(writers);
 
         Ok(())
     }
 
-    /// Stop mic monitoring
-    pub fn stop_mic_monitoring(&mut self) {
+    /// Stop mic monitoring. Input passthrough shares the stream and stops
+    /// too, reporting `reason` through
+    /// [`take_passthrough_stop_reason`](Self::take_passthrough_stop_reason).
+    pub fn stop_mic_monitoring(&mut self, reason: &str) {
+        if self.is_passthrough_active() {
+            self.stop_input_passthrough();
+            *self.passthrough.stop_reason.lock() = Some(reason.to_string());
+        }
+        self.recorder.take();
         self.input_stream = None;
+        self.passthrough_writers = None;
         let mut state = self.mic_level.lock();
         state.level = 0.0;
         state.peak = 0.0;
@@ -226,6 +878,224 @@ impl AudioManager {
     pub fn current_output_device(&self) -> Option<&str> {
         self.current_output_device.as_deref()
     }
+
+    /// Route the monitored input to an output device.
+    ///
+    /// `output_device` falls back to the selected output, then the system
+    /// default. Requires mic monitoring to be running. Passthrough stops by
+    /// itself when the input device changes or capture fails; see
+    /// [`take_passthrough_stop_reason`](Self::take_passthrough_stop_reason).
+    pub fn start_input_passthrough(&mut self, output_device: Option<&str>, gain: f32) -> Result<(), String> {
+        self.stop_input_passthrough();
+
+        if self.input_stream.is_none() {
+            return Err("Mic monitoring is not running".to_string());
+        }
+        let Some(writers) = self.passthrough_writers.as_mut() else {
+            return Err("Mic monitoring is not running".to_string());
+        };
+
+        let shared = self.passthrough.clone();
+        let input_rate = shared.input_sample_rate.load(Ordering::Relaxed) as f64;
+        if input_rate <= 0.0 {
+            return Err("Unknown input sample rate".to_string());
+        }
+
+        // Room for the longest latency plus a whole capture block
+        let capacity = (input_rate as f32 * MAX_PASSTHROUGH_LATENCY_MS / 1000.0) as usize + CAPTURE_SCRATCH_SAMPLES;
+        let (writer, mut reader) = passthrough_ring(capacity);
+        if writers.push(writer).is_err() {
+            return Err("Capture stream is not running".to_string());
+        }
+
+        let device = self.output_device(output_device)?;
+        self.set_passthrough_gain(gain);
+        shared.latency_ms.store(0, Ordering::Relaxed);
+        *shared.stop_reason.lock() = None;
+
+        // Runs in the output callback: fill from the ring and update the
+        // latency estimate (queued input + one input and one output block)
+        let render = move |channels: usize, output_rate: u32| {
+            let output_rate = output_rate as f64;
+            let ratio = input_rate / output_rate;
+            move |out: &mut [f32]| {
+                let gain = f32::from_bits(shared.gain.load(Ordering::Relaxed));
+                reader.trim(shared.max_queued());
+                reader.fill_output(out, channels, ratio, gain);
+                let queued = reader.len();
+                let input_block = shared.input_callback_frames.load(Ordering::Relaxed);
+                let output_block = out.len() / channels.max(1);
+                let latency_ms = ((queued + input_block) as f64 / input_rate
+                    + output_block as f64 / output_rate)
+                    * 1000.0;
+                let previous = f32::from_bits(shared.latency_ms.load(Ordering::Relaxed));
+                let smoothed = if previous > 0.0 {
+                    previous * 0.9 + latency_ms as f32 * 0.1
+                } else {
+                    latency_ms as f32
+                };
+                shared.latency_ms.store(smoothed.to_bits(), Ordering::Relaxed);
+            }
+        };
+
+        let shared_err = self.passthrough.clone();
+        let error_callback = move |err: cpal::StreamError| {
+            eprintln!("Audio passthrough output error: {}", err);
+            shared_err.capture_failed(format!("Output failed: {}", err));
+        };
+
+        let stream = Self::start_output(&device, render, error_callback)?;
+        self.passthrough_stream = Some(stream);
+        self.passthrough.active.store(true, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Stop input passthrough
+    pub fn stop_input_passthrough(&mut self) {
+        self.passthrough.active.store(false, Ordering::Relaxed);
+        self.passthrough_stream = None;
+        self.passthrough.latency_ms.store(0, Ordering::Relaxed);
+    }
+
+    /// Whether the input is currently routed to an output
+    pub fn is_passthrough_active(&self) -> bool {
+        self.passthrough.active.load(Ordering::Relaxed)
+    }
+
+    /// Set passthrough software gain (linear, clamped to `0..=MAX_PASSTHROUGH_GAIN`)
+    pub fn set_passthrough_gain(&self, gain: f32) {
+        let gain = gain.clamp(0.0, MAX_PASSTHROUGH_GAIN);
+        self.passthrough.gain.store(gain.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get passthrough software gain
+    pub fn passthrough_gain(&self) -> f32 {
+        f32::from_bits(self.passthrough.gain.load(Ordering::Relaxed))
+    }
+
+    /// Set the queued input bound in milliseconds (takes effect with the
+    /// next output block). Smaller values lower latency but risk dropouts.
+    pub fn set_passthrough_latency_ms(&self, latency_ms: f32) {
+        let latency_ms = latency_ms.clamp(5.0, MAX_PASSTHROUGH_LATENCY_MS);
+        self.passthrough
+            .target_latency_ms
+            .store(latency_ms.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Achieved round-trip latency estimate, once the output has started
+    pub fn passthrough_latency_ms(&self) -> Option<f32> {
+        if !self.is_passthrough_active() {
+            return None;
+        }
+        let latency = f32::from_bits(self.passthrough.latency_ms.load(Ordering::Relaxed));
+        (latency > 0.0).then_some(latency)
+    }
+
+    /// If passthrough stopped on its own, release its output and return why.
+    ///
+    /// Poll this periodically (e.g. from the mic level timer) to update UI.
+    pub fn take_passthrough_stop_reason(&mut self) -> Option<String> {
+        let reason = self.passthrough.stop_reason.lock().take()?;
+        self.stop_input_passthrough();
+        Some(reason)
+    }
+
+    /// Turn automatic gain control of the captured input on or off; takes
+    /// effect with the next captured block
+    pub fn set_agc_enabled(&self, enabled: bool) {
+        self.agc.enabled.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.agc.gain.store(1.0f32.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    pub fn is_agc_enabled(&self) -> bool {
+        self.agc.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Gain the AGC applied to the last captured block (linear), while enabled
+    pub fn agc_gain(&self) -> Option<f32> {
+        self.is_agc_enabled()
+            .then(|| f32::from_bits(self.agc.gain.load(Ordering::Relaxed)))
+    }
+
+    pub fn agc_settings(&self) -> AgcSettings {
+        self.agc.settings()
+    }
+
+    /// Change the AGC target and timing; takes effect with the next captured block
+    pub fn set_agc_settings(&self, settings: AgcSettings) {
+        self.agc.set_settings(settings);
+    }
+
+    /// Play mono `samples` once through the selected output, replacing any
+    /// clip still playing. The stream is released by [`stop_clip`](Self::stop_clip)
+    /// or the next clip; poll [`is_clip_playing`](Self::is_clip_playing) for the end.
+    pub fn play_clip(&mut self, samples: &[f32], sample_rate: u32) -> Result<(), String> {
+        self.stop_clip();
+        let device = self.output_device(None)?;
+
+        let (mut writer, mut reader) = passthrough_ring(samples.len());
+        writer.push_frames(samples, 1);
+        let done = Arc::new(AtomicBool::new(false));
+
+        let done_render = done.clone();
+        let render = move |channels: usize, output_rate: u32| {
+            let ratio = sample_rate as f64 / output_rate.max(1) as f64;
+            move |out: &mut [f32]| {
+                reader.fill_output(out, channels, ratio, 1.0);
+                if reader.len() == 0 {
+                    done_render.store(true, Ordering::Relaxed);
+                }
+            }
+        };
+        let done_err = done.clone();
+        let error_callback = move |err: cpal::StreamError| {
+            eprintln!("Audio clip output error: {}", err);
+            done_err.store(true, Ordering::Relaxed);
+        };
+
+        self.clip_stream = Some(Self::start_output(&device, render, error_callback)?);
+        self.clip_done = done;
+        Ok(())
+    }
+
+    /// Whether a clip from [`play_clip`](Self::play_clip) is still playing
+    pub fn is_clip_playing(&self) -> bool {
+        self.clip_stream.is_some() && !self.clip_done.load(Ordering::Relaxed)
+    }
+
+    /// Stop the clip and release its output stream
+    pub fn stop_clip(&mut self) {
+        self.clip_stream = None;
+        self.clip_done.store(true, Ordering::Relaxed);
+    }
+
+    /// Record up to `secs` seconds from the monitored input. Requires mic
+    /// monitoring to be running; changing the input device discards the
+    /// recording.
+    pub fn start_recording(&mut self, secs: f32) -> Result<(), String> {
+        if self.input_stream.is_none() {
+            return Err("Mic monitoring is not running".to_string());
+        }
+        let rate = self.passthrough.input_sample_rate.load(Ordering::Relaxed);
+        self.recorder.start((rate as f32 * secs.max(0.0)) as usize);
+        Ok(())
+    }
+
+    /// Whether a recording is still filling up
+    pub fn is_recording(&self) -> bool {
+        self.recorder.active.load(Ordering::Relaxed)
+    }
+
+    /// Stop recording and return what was captured so far
+    pub fn take_recording(&mut self) -> Recording {
+        Recording {
+            samples: self.recorder.take(),
+            sample_rate: self.passthrough.input_sample_rate.load(Ordering::Relaxed),
+        }
+    }
 }
 
 impl Default for AudioManager {
@@ -233,3 +1103,177 @@ impl Default for AudioManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_downmixes_and_bounds_latency() {
+        let (mut writer, mut reader) = passthrough_ring(8);
+        // Three stereo frames, then three more: only the newest four are kept
+        writer.push_frames(&[0.2, 0.4, 0.6, 0.8, 1.0, 1.0], 2);
+        writer.push_frames(&[0.0, 0.2, 0.4, 0.4, 0.1, 0.3], 2);
+        reader.trim(4);
+        assert_eq!(reader.len(), 4);
+
+        let mut out = [9.0f32; 4];
+        reader.fill_output(&mut out, 2, 1.0, 1.0);
+        assert_eq!(out, [1.0, 1.0, 0.1, 0.1]);
+        assert_eq!(reader.len(), 2);
+    }
+
+    #[test]
+    fn test_full_ring_drops_the_newest_samples() {
+        let (mut writer, mut reader) = passthrough_ring(2);
+        writer.push_frames(&[0.1, 0.2, 0.3], 1);
+
+        let mut out = [9.0f32; 3];
+        reader.fill_output(&mut out, 1, 1.0, 1.0);
+        assert_eq!(out, [0.1, 0.2, 0.0]);
+    }
+
+    #[test]
+    fn test_ring_gain_rate_conversion_and_underrun() {
+        let (mut writer, mut reader) = passthrough_ring(16);
+        writer.push_frames(&[0.1, 0.2, 0.3, 0.4], 1);
+
+        // Output at twice the input rate repeats each input sample
+        let mut out = [0.0f32; 6];
+        reader.fill_output(&mut out, 1, 0.5, 2.0);
+        assert_eq!(out, [0.2, 0.2, 0.4, 0.4, 0.6, 0.6]);
+
+        // Gain is clamped to full scale; underrun yields silence
+        let mut out = [1.0f32; 4];
+        reader.fill_output(&mut out, 1, 1.0, 4.0);
+        assert_eq!(out, [1.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_device_capabilities() {
+        let mic = AudioDeviceInfo {
+            name: "USB Mic".to_string(),
+            sample_rates: vec![(44100, 48000)],
+            max_channels: 1,
+            default_format: Some(AudioFormatInfo {
+                sample_rate: 48000,
+                channels: 1,
+                sample_format: "i16".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert!(mic.supports_sample_rate(44100) && !mic.supports_sample_rate(16000));
+        assert_eq!(mic.capability_summary(), "44.1\u{2013}48 kHz \u{00b7} 1 ch \u{00b7} default 48 kHz i16");
+        assert_eq!(mic.compatibility_warning(48000, 1), None);
+        assert_eq!(
+            mic.compatibility_warning(16000, 2).as_deref(),
+            Some("no 16 kHz, mono only")
+        );
+
+        // Nothing reported: assume it works rather than warn
+        let unknown = AudioDeviceInfo { name: "Virtual".to_string(), ..Default::default() };
+        assert_eq!(unknown.compatibility_warning(16000, 2), None);
+        assert_eq!(unknown.capability_summary(), "Capabilities unknown");
+    }
+
+    #[test]
+    fn test_tone_is_faded_and_bounded() {
+        let tone = test_tone(8000, 0.5);
+        assert_eq!(tone.len(), 4000);
+        assert!(tone[0].abs() < 1e-6 && tone[3999].abs() < 0.01);
+        let peak = Recording { samples: tone, sample_rate: 8000 }.peak();
+        assert!(peak > 0.25 && peak <= TONE_AMPLITUDE);
+    }
+
+    #[test]
+    fn test_recording_stops_when_full() {
+        let recorder = RecordShared::new();
+        recorder.push_input(&[0.5; 8], 2);
+        assert!(recorder.samples.lock().is_empty());
+
+        recorder.start(3);
+        recorder.push_input(&[0.25, 0.75, 0.5, 1.0], 2);
+        assert!(recorder.active.load(Ordering::Relaxed));
+        recorder.push_input(&[1.0, 0.0, -1.0, -1.0, 0.5, 0.5], 2);
+        assert!(!recorder.active.load(Ordering::Relaxed));
+
+        let recording = Recording { samples: recorder.take(), sample_rate: 2 };
+        assert_eq!(recording.samples, vec![0.5, 0.75, 0.5]);
+        assert_eq!(recording.duration_secs(), 1.5);
+        assert_eq!(recording.peak(), 0.75);
+    }
+
+    /// Feed `signal` through an enabled AGC in 10 ms blocks, returning the output
+    fn run_agc(agc: &AgcShared, state: &mut AutoGain, signal: &[f32], rate: u32) -> Vec<f32> {
+        let mut scratch = Vec::with_capacity(CAPTURE_SCRATCH_SAMPLES);
+        let mut out = Vec::with_capacity(signal.len());
+        for block in signal.chunks(rate as usize / 100) {
+            out.extend_from_slice(agc.apply(state, &mut scratch, block, 1, rate));
+        }
+        out
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_agc_levels_quiet_input_to_target() {
+        let rate = 48000;
+        // A quiet talker: 440 Hz at -34 dBFS RMS for four seconds
+        let quiet: Vec<f32> = (0..rate * 4)
+            .map(|i| 0.028 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / rate as f32).sin())
+            .collect();
+        let agc = AgcShared::new();
+        agc.enabled.store(true, Ordering::Relaxed);
+        let mut state = AutoGain::new();
+
+        let out = run_agc(&agc, &mut state, &quiet, rate);
+        let last_second = &out[out.len() - rate as usize..];
+        let target = AgcSettings::default().target_rms;
+        assert!((rms(last_second) - target).abs() < target * 0.05, "rms {}", rms(last_second));
+        assert!(out.iter().all(|s| s.abs() <= AGC_CEILING));
+        let gain = f32::from_bits(agc.gain.load(Ordering::Relaxed));
+        assert!((gain - target / rms(&quiet)).abs() < 0.2, "gain {}", gain);
+    }
+
+    #[test]
+    fn test_agc_ceiling_clipping_and_bypass() {
+        let rate = 16000;
+        let agc = AgcShared::new();
+        let mut state = AutoGain::new();
+
+        // Disabled: the input itself comes back, untouched
+        let block = [0.01f32; 160];
+        let mut scratch = Vec::new();
+        assert!(std::ptr::eq(agc.apply(&mut state, &mut scratch, &block, 1, rate), &block[..]));
+
+        // Near silence is lifted no further than the max gain
+        agc.enabled.store(true, Ordering::Relaxed);
+        let max_gain = AgcSettings::default().max_gain;
+        run_agc(&agc, &mut state, &vec![0.001; rate as usize * 3], rate);
+        assert!((state.gain - max_gain).abs() < 0.01);
+
+        // A sudden shout at full gain is held under the ceiling, not clipped
+        let out = run_agc(&agc, &mut state, &vec![0.9; rate as usize / 10], rate);
+        assert!(out.iter().all(|s| s.abs() <= AGC_CEILING + f32::EPSILON));
+        assert!(state.gain < 1.2);
+
+        // Bypassed again: the gain reads as unity even if the last levelled
+        // block stored its gain after the switch
+        run_agc(&agc, &mut state, &vec![0.001; rate as usize], rate);
+        agc.enabled.store(false, Ordering::Relaxed);
+        agc.gain.store(max_gain.to_bits(), Ordering::Relaxed);
+        agc.apply(&mut state, &mut scratch, &block, 1, rate);
+        assert_eq!(f32::from_bits(agc.gain.load(Ordering::Relaxed)), 1.0);
+        assert_eq!(state.gain, 1.0);
+    }
+
+    #[test]
+    fn test_headphone_heuristic() {
+        assert!(is_likely_headphones("External Headphones"));
+        assert!(is_likely_headphones("AirPods Pro"));
+        assert!(!is_likely_headphones("MacBook Pro Speakers"));
+        assert!(!is_likely_headphones("HDMI Output"));
+    }
+}
@@ -106,11 +106,14 @@ pub mod log_bridge;
 // Re-export main types for convenience
 pub use registry::{MofaWidgetRegistry, MofaWidgetDef, WidgetCategory, WidgetSize};
 pub use app_data::{MofaAppData, AppConfig};
-pub use theme::{MofaTheme, ThemeColor, ThemeListener, THEME_TRANSITION_DURATION};
+pub use theme::{
+    MofaTheme, ThemeColor, ThemeListener, THEME_TRANSITION_DURATION,
+    apply_drop_down_dark_mode, apply_text_input_dark_mode,
+};
 pub use traits::{MofaWidget, Themeable, DoraConnected, Maximizable, Clearable, Animated, Focusable};
 
 // Re-export shared infrastructure
-pub use audio::{AudioManager, AudioDeviceInfo, MicLevelState};
+pub use audio::{AgcSettings, AudioManager, AudioDeviceInfo, AudioFormatInfo, MicLevelState, Recording, is_likely_headphones, test_tone};
 pub use log_bridge::{LogMessage, init as log_bridge_init, poll_logs, receiver as log_receiver};
 
 // Re-export widgets and their WidgetExt traits
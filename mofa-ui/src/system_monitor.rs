@@ -257,6 +257,11 @@ pub fn start_system_monitor() {
     });
 }
 
+/// Whether [`start_system_monitor`] was called; the getters return 0.0 before
+pub fn is_running() -> bool {
+    SYSTEM_MONITOR.get().is_some()
+}
+
 /// Get current CPU usage as a value between 0.0 and 1.0
 pub fn get_cpu_usage() -> f64 {
     SYSTEM_MONITOR
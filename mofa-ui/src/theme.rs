@@ -73,7 +73,7 @@ pub struct MofaTheme {
     /// Use this value in shader `dark_mode` instance variables.
     pub dark_mode_anim: f64,
 
-    /// Accent color (future use)
+    /// Accent color; embedded pages get it as `--mofa-accent`
     pub accent_color: ThemeColor,
 }
 
@@ -199,6 +199,19 @@ impl ThemeColor {
             }
         }
     }
+
+    /// Accent for embedded pages; blue is the palette's own accent, which
+    /// pages get in its light and dark variants
+    pub fn web_accent(&self) -> Option<[u8; 3]> {
+        match self {
+            ThemeColor::Blue => None,
+            ThemeColor::Indigo => Some([0x63, 0x66, 0xf1]),
+            ThemeColor::Green => Some([0x10, 0xb9, 0x81]),
+            ThemeColor::Red => Some([0xef, 0x44, 0x44]),
+            ThemeColor::Amber => Some([0xf5, 0x9e, 0x0b]),
+            ThemeColor::Custom(rgba) => Some([(rgba >> 24) as u8, (rgba >> 16) as u8, (rgba >> 8) as u8]),
+        }
+    }
 }
 
 impl Default for ThemeColor {
@@ -217,6 +230,50 @@ pub trait ThemeListener {
     fn apply_dark_mode(&self, cx: &mut makepad_widgets::Cx, dark_mode: f64);
 }
 
+/// Apply dark mode to a `DropDown`, including its popup menu and menu items
+///
+/// `apply_over` fails with "target class not found" when a shader has no
+/// `dark_mode` to set, so the dropdown's `draw_bg` and `draw_text`, its
+/// `popup_menu.draw_bg` and its `menu_item.draw_bg` / `draw_text` must all
+/// declare `instance dark_mode: 0.0` in `live_design!`.
+pub fn apply_drop_down_dark_mode(
+    cx: &mut makepad_widgets::Cx,
+    drop_down: &makepad_widgets::DropDownRef,
+    dark_mode: f64,
+) {
+    use makepad_widgets::*;
+
+    drop_down.apply_over(cx, live!{
+        draw_bg: { dark_mode: (dark_mode) }
+        draw_text: { dark_mode: (dark_mode) }
+        popup_menu: {
+            draw_bg: { dark_mode: (dark_mode) }
+            menu_item: {
+                draw_bg: { dark_mode: (dark_mode) }
+                draw_text: { dark_mode: (dark_mode) }
+            }
+        }
+    });
+}
+
+/// Apply dark mode to a `TextInput`
+///
+/// As with [`apply_drop_down_dark_mode`], its `draw_bg`, `draw_text` and
+/// `draw_selection` must declare `instance dark_mode: 0.0`.
+pub fn apply_text_input_dark_mode(
+    cx: &mut makepad_widgets::Cx,
+    text_input: &makepad_widgets::TextInputRef,
+    dark_mode: f64,
+) {
+    use makepad_widgets::*;
+
+    text_input.apply_over(cx, live!{
+        draw_bg: { dark_mode: (dark_mode) }
+        draw_text: { dark_mode: (dark_mode) }
+        draw_selection: { dark_mode: (dark_mode) }
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
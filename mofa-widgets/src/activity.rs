@@ -0,0 +1,223 @@
+//! # Running Activity
+//!
+//! A process-wide list of what keeps running in the background: local
+//! servers, loaded WebViews and polling timers. Together with
+//! [`jobs`](crate::jobs) and the plugin loader it lets the shell show
+//! everything that is running, whichever app started it.
+//!
+//! Owners register through [`ActivityRegistry::global`] and keep the
+//! returned [`ActivityHandle`] while the thing runs; dropping the handle
+//! removes the entry. An owner that can be stopped from outside marks the
+//! handle [`stoppable`](ActivityHandle::stoppable) and checks
+//! [`stop_requested`](ActivityHandle::stop_requested) where it handles
+//! events, then stops through its usual path so its own UI stays in sync.
+//!
+//! ```rust,ignore
+//! use mofa_widgets::activity::{ActivityKind, ActivityRegistry};
+//!
+//! let activity = ActivityRegistry::global()
+//!     .register(ActivityKind::Server, "mofa-converter", "Converter server")
+//!     .stoppable();
+//! activity.set_state(&format!("port {}", port));
+//! // in handle_event
+//! if activity.stop_requested() {
+//!     self.stop_server(cx); // drops the handle
+//! }
+//! ```
+
+use parking_lot::Mutex;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+static GLOBAL: OnceLock<ActivityRegistry> = OnceLock::new();
+
+/// What kind of thing is running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+    Server,
+    WebView,
+    Timer,
+}
+
+impl ActivityKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ActivityKind::Server => "Server",
+            ActivityKind::WebView => "WebView",
+            ActivityKind::Timer => "Timer",
+        }
+    }
+}
+
+/// A registered activity as seen by observers
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityInfo {
+    pub id: u64,
+    pub kind: ActivityKind,
+    /// App that owns the activity, e.g. `mofa-converter`; may be empty
+    pub app_id: String,
+    pub name: String,
+    /// Short state such as "port 8123" or the loaded URL
+    pub state: String,
+    pub started: Instant,
+    /// Whether the owner acts on [`ActivityRegistry::request_stop`]
+    pub stoppable: bool,
+}
+
+struct Entry {
+    info: ActivityInfo,
+    stop_requested: bool,
+}
+
+#[derive(Default)]
+struct Inner {
+    next_id: u64,
+    entries: Vec<Entry>,
+}
+
+/// Tracks running servers, WebViews and timers
+#[derive(Default)]
+pub struct ActivityRegistry {
+    inner: Mutex<Inner>,
+}
+
+impl ActivityRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The registry shared by all apps in the process
+    pub fn global() -> &'static ActivityRegistry {
+        GLOBAL.get_or_init(ActivityRegistry::new)
+    }
+
+    /// Register an activity; it is listed until the handle is dropped
+    pub fn register(&'static self, kind: ActivityKind, app_id: &str, name: &str) -> ActivityHandle {
+        let mut inner = self.inner.lock();
+        inner.next_id += 1;
+        let id = inner.next_id;
+        inner.entries.push(Entry {
+            info: ActivityInfo {
+                id,
+                kind,
+                app_id: app_id.to_string(),
+                name: name.to_string(),
+                state: String::new(),
+                started: Instant::now(),
+                stoppable: false,
+            },
+            stop_requested: false,
+        });
+        ActivityHandle { registry: self, id }
+    }
+
+    /// Running activities, oldest first
+    pub fn active(&self) -> Vec<ActivityInfo> {
+        self.inner.lock().entries.iter().map(|e| e.info.clone()).collect()
+    }
+
+    /// Ask the owner of a stoppable activity to stop it; `false` if the
+    /// activity is gone or cannot be stopped from outside
+    pub fn request_stop(&self, id: u64) -> bool {
+        let mut inner = self.inner.lock();
+        match inner.entries.iter_mut().find(|e| e.info.id == id) {
+            Some(entry) if entry.info.stoppable => {
+                entry.stop_requested = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn update(&self, id: u64, f: impl FnOnce(&mut Entry)) {
+        if let Some(entry) = self.inner.lock().entries.iter_mut().find(|e| e.info.id == id) {
+            f(entry);
+        }
+    }
+
+    fn stop_requested(&self, id: u64) -> bool {
+        self.inner.lock().entries.iter().any(|e| e.info.id == id && e.stop_requested)
+    }
+
+    fn remove(&self, id: u64) {
+        self.inner.lock().entries.retain(|e| e.info.id != id);
+    }
+}
+
+/// Keeps an activity listed; dropping it removes the entry
+pub struct ActivityHandle {
+    registry: &'static ActivityRegistry,
+    id: u64,
+}
+
+impl ActivityHandle {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Let observers ask for a stop; the owner must check
+    /// [`stop_requested`](Self::stop_requested)
+    pub fn stoppable(self) -> Self {
+        self.registry.update(self.id, |e| e.info.stoppable = true);
+        self
+    }
+
+    pub fn set_state(&self, state: &str) {
+        self.registry.update(self.id, |e| e.info.state = state.to_string());
+    }
+
+    /// Whether an observer asked the owner to stop
+    pub fn stop_requested(&self) -> bool {
+        self.registry.stop_requested(self.id)
+    }
+}
+
+impl Drop for ActivityHandle {
+    fn drop(&mut self) {
+        self.registry.remove(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> &'static ActivityRegistry {
+        Box::leak(Box::new(ActivityRegistry::new()))
+    }
+
+    #[test]
+    fn test_register_and_drop() {
+        let registry = registry();
+        let server = registry.register(ActivityKind::Server, "mofa-converter", "Converter server");
+        server.set_state("port 8123");
+        let _timer = registry.register(ActivityKind::Timer, "mofa-fm", "Audio polling");
+
+        let active = registry.active();
+        assert_eq!(active.len(), 2);
+        assert_eq!(active[0].kind, ActivityKind::Server);
+        assert_eq!(active[0].state, "port 8123");
+        assert_eq!(active[1].app_id, "mofa-fm");
+
+        drop(server);
+        assert_eq!(registry.active().len(), 1);
+    }
+
+    #[test]
+    fn test_stop_requests_need_a_stoppable_owner() {
+        let registry = registry();
+        let fixed = registry.register(ActivityKind::Server, "", "Control API");
+        let server = registry.register(ActivityKind::Server, "mofa-converter", "Converter server").stoppable();
+
+        assert!(!registry.request_stop(fixed.id()));
+        assert!(!fixed.stop_requested());
+
+        assert!(!server.stop_requested());
+        assert!(registry.request_stop(server.id()));
+        assert!(server.stop_requested());
+
+        let id = server.id();
+        drop(server);
+        assert!(!registry.request_stop(id));
+    }
+}
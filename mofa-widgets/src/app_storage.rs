@@ -0,0 +1,367 @@
+//! Per-app data locations under `~/.mofa-studio`
+//!
+//! Apps declare where they keep preferences, cached data and their WebView
+//! profile with an [`AppStorage`] in their [`AppInfo`](crate::AppInfo), and
+//! resolve their own paths through [`data_path`] and [`webview_profile_dir`].
+//! Settings uses the same declarations to preview and reset an app's data,
+//! so a new app is covered as soon as it declares its storage.
+//!
+//! Resetting is two-step: [`ResetPlan::preview`] measures what exists without
+//! touching anything, and [`ResetPlan::execute`] removes the categories the
+//! user kept selected.
+
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Root directory for all MoFA Studio app data
+pub fn data_root() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".mofa-studio")
+}
+
+/// Resolve a path declared relative to [`data_root`]
+pub fn data_path(relative: &str) -> PathBuf {
+    data_root().join(relative)
+}
+
+/// WebView profile (cookies, local storage, caches) for an app or plugin
+pub fn webview_profile_dir(id: &str) -> PathBuf {
+    data_root().join("webview").join(id)
+}
+
+/// Private data directory handed to a plugin's server process
+pub fn plugin_data_dir(plugin_id: &str) -> PathBuf {
+    data_root().join("plugin-data").join(plugin_id)
+}
+
+/// Kind of data an app keeps on disk
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DataCategory {
+    Preferences,
+    Cache,
+    WebViewProfile,
+}
+
+impl DataCategory {
+    pub const ALL: [DataCategory; 3] = [
+        DataCategory::Preferences,
+        DataCategory::Cache,
+        DataCategory::WebViewProfile,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DataCategory::Preferences => "Preferences",
+            DataCategory::Cache => "Data & cache",
+            DataCategory::WebViewProfile => "WebView profile",
+        }
+    }
+}
+
+/// Storage declared by an app, relative to [`data_root`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AppStorage {
+    /// Preference files
+    pub prefs: &'static [&'static str],
+    /// Cache and saved-data files or directories
+    pub cache: &'static [&'static str],
+    /// Whether the app runs in a WebView with its own profile
+    pub webview_profile: bool,
+}
+
+impl AppStorage {
+    /// Whether the app keeps nothing on disk
+    pub fn is_empty(&self) -> bool {
+        self.prefs.is_empty() && self.cache.is_empty() && !self.webview_profile
+    }
+
+    /// Resolve every declared location for `app_id`
+    pub fn locations(&self, app_id: &str) -> Vec<DataLocation> {
+        let mut locations = self.locations_in(&data_root());
+        if self.webview_profile {
+            locations.push(DataLocation {
+                category: DataCategory::WebViewProfile,
+                path: webview_profile_dir(app_id),
+            });
+        }
+        locations
+    }
+
+    /// Resolve prefs and cache entries under `root`.
+    ///
+    /// Entries that are absolute or climb out of the root are skipped, so a
+    /// bad declaration can never point a reset outside the data directory.
+    fn locations_in(&self, root: &Path) -> Vec<DataLocation> {
+        let declared = self.prefs.iter().map(|p| (DataCategory::Preferences, *p))
+            .chain(self.cache.iter().map(|p| (DataCategory::Cache, *p)));
+
+        declared
+            .filter(|(_, relative)| {
+                let contained = is_contained(relative);
+                if !contained {
+                    ::log::warn!("Ignoring storage entry outside the data directory: {}", relative);
+                }
+                contained
+            })
+            .map(|(category, relative)| DataLocation {
+                category,
+                path: root.join(relative),
+            })
+            .collect()
+    }
+}
+
+/// Locations owned by a dynamically loaded plugin
+pub fn plugin_locations(plugin_id: &str) -> Vec<DataLocation> {
+    vec![DataLocation {
+        category: DataCategory::Cache,
+        path: plugin_data_dir(plugin_id),
+    }]
+}
+
+fn is_contained(relative: &str) -> bool {
+    let path = Path::new(relative);
+    !relative.is_empty()
+        && path.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// A resolved path and the category it belongs to
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataLocation {
+    pub category: DataCategory,
+    pub path: PathBuf,
+}
+
+/// A location that exists on disk, with its size
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResetItem {
+    pub category: DataCategory,
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Dry-run result: everything a reset would remove
+#[derive(Clone, Debug, Default)]
+pub struct ResetPlan {
+    pub app_id: String,
+    pub app_name: String,
+    pub items: Vec<ResetItem>,
+}
+
+impl ResetPlan {
+    /// Measure the locations that exist. Nothing is deleted.
+    pub fn preview(app_id: &str, app_name: &str, locations: &[DataLocation]) -> Self {
+        let items = locations
+            .iter()
+            .filter_map(|location| {
+                let bytes = path_size(&location.path).ok()?;
+                Some(ResetItem {
+                    category: location.category,
+                    path: location.path.clone(),
+                    bytes,
+                })
+            })
+            .collect();
+
+        Self {
+            app_id: app_id.to_string(),
+            app_name: app_name.to_string(),
+            items,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Whether any existing data falls in `category`
+    pub fn has(&self, category: DataCategory) -> bool {
+        self.items.iter().any(|item| item.category == category)
+    }
+
+    /// Total size of the existing data in `category`
+    pub fn bytes(&self, category: DataCategory) -> u64 {
+        self.items
+            .iter()
+            .filter(|item| item.category == category)
+            .map(|item| item.bytes)
+            .sum()
+    }
+
+    /// Delete the items in the selected categories
+    pub fn execute(&self, categories: &[DataCategory]) -> ResetReport {
+        let mut report = ResetReport::default();
+
+        for item in self.items.iter().filter(|item| categories.contains(&item.category)) {
+            let result = match fs::symlink_metadata(&item.path) {
+                Ok(meta) if meta.is_dir() => fs::remove_dir_all(&item.path),
+                Ok(_) => fs::remove_file(&item.path),
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(()) => report.removed.push(item.clone()),
+                // Already gone since the preview; nothing to report
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => report.failed.push((item.path.clone(), e.to_string())),
+            }
+        }
+
+        report
+    }
+}
+
+/// What a reset actually removed
+#[derive(Clone, Debug, Default)]
+pub struct ResetReport {
+    pub removed: Vec<ResetItem>,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl ResetReport {
+    pub fn total_bytes(&self) -> u64 {
+        self.removed.iter().map(|item| item.bytes).sum()
+    }
+
+    /// Human readable list of removed items and failures
+    pub fn summary(&self) -> String {
+        if self.removed.is_empty() && self.failed.is_empty() {
+            return "Nothing to remove".to_string();
+        }
+
+        let mut lines: Vec<String> = self
+            .removed
+            .iter()
+            .map(|item| {
+                format!(
+                    "Removed {} ({}): {}",
+                    item.category.label(),
+                    format_size(item.bytes),
+                    item.path.display()
+                )
+            })
+            .collect();
+        for (path, error) in &self.failed {
+            lines.push(format!("Failed to remove {}: {}", path.display(), error));
+        }
+        lines.push(format!("Freed {}", format_size(self.total_bytes())));
+        lines.join("\n")
+    }
+}
+
+/// Format a byte count for display (e.g. "1.5 MB")
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Size of a file, or of everything below a directory. Symlinks are counted
+/// as links and never followed.
+fn path_size(path: &Path) -> io::Result<u64> {
+    let meta = fs::symlink_metadata(path)?;
+    if !meta.is_dir() {
+        return Ok(meta.len());
+    }
+
+    let mut total = 0;
+    for entry in fs::read_dir(path)? {
+        total += path_size(&entry?.path()).unwrap_or(0);
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("mofa-app-storage-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_locations_skip_entries_outside_root() {
+        let storage = AppStorage {
+            prefs: &["app.json", "../escape.json", "/etc/passwd"],
+            cache: &["app/cache", ""],
+            webview_profile: false,
+        };
+        let root = PathBuf::from("/data");
+        let locations = storage.locations_in(&root);
+
+        assert_eq!(locations, vec![
+            DataLocation { category: DataCategory::Preferences, path: root.join("app.json") },
+            DataLocation { category: DataCategory::Cache, path: root.join("app/cache") },
+        ]);
+    }
+
+    #[test]
+    fn test_preview_measures_without_deleting() {
+        let root = temp_root("preview");
+        fs::write(root.join("app.json"), b"{\"a\":1}").unwrap();
+        fs::create_dir_all(root.join("cache/nested")).unwrap();
+        fs::write(root.join("cache/one.bin"), vec![0u8; 100]).unwrap();
+        fs::write(root.join("cache/nested/two.bin"), vec![0u8; 50]).unwrap();
+
+        let storage = AppStorage {
+            prefs: &["app.json", "missing.json"],
+            cache: &["cache"],
+            webview_profile: false,
+        };
+        let plan = ResetPlan::preview("app", "App", &storage.locations_in(&root));
+
+        assert_eq!(plan.items.len(), 2);
+        assert_eq!(plan.bytes(DataCategory::Preferences), 7);
+        assert_eq!(plan.bytes(DataCategory::Cache), 150);
+        assert!(!plan.has(DataCategory::WebViewProfile));
+        assert!(root.join("app.json").exists());
+        assert!(root.join("cache/nested/two.bin").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_execute_removes_only_selected_categories() {
+        let root = temp_root("execute");
+        fs::write(root.join("app.json"), b"{}").unwrap();
+        fs::create_dir_all(root.join("cache")).unwrap();
+        fs::write(root.join("cache/data.bin"), vec![0u8; 10]).unwrap();
+
+        let storage = AppStorage {
+            prefs: &["app.json"],
+            cache: &["cache"],
+            webview_profile: false,
+        };
+        let plan = ResetPlan::preview("app", "App", &storage.locations_in(&root));
+        let report = plan.execute(&[DataCategory::Cache]);
+
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.total_bytes(), 10);
+        assert!(report.failed.is_empty());
+        assert!(!root.join("cache").exists());
+        assert!(root.join("app.json").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+}
@@ -60,6 +60,8 @@
 //! ```
 
 use makepad_widgets::{Cx, LiveId, Action, live_id, ButtonAction, WidgetActionCast};
+use crate::app_storage::AppStorage;
+use crate::tour::TourBuilder;
 
 /// Metadata about a registered app
 #[derive(Clone, Debug)]
@@ -76,6 +78,8 @@ pub struct AppInfo {
     pub page_id: Option<LiveId>,
     /// Whether this app is shown in the main sidebar (vs settings/system apps)
     pub show_in_sidebar: bool,
+    /// Data the app keeps on disk (used by Settings to reset it)
+    pub storage: AppStorage,
 }
 
 impl Default for AppInfo {
@@ -87,6 +91,7 @@ impl Default for AppInfo {
             tab_id: None,
             page_id: None,
             show_in_sidebar: true,
+            storage: AppStorage::default(),
         }
     }
 }
@@ -287,6 +292,16 @@ pub trait MofaApp {
 
     /// Register this app's widgets with Makepad
     fn live_design(cx: &mut Cx);
+
+    /// Add this app's steps to the onboarding tour, shown after the shell's
+    /// own steps while the app is active. Targets are paths from the window
+    /// root, so they start with the app's page id.
+    fn tour(tour: TourBuilder) -> TourBuilder
+    where
+        Self: Sized,
+    {
+        tour
+    }
 }
 
 /// Trait for apps with timer-based animations that need lifecycle control
@@ -301,6 +316,19 @@ pub trait TimerControl {
     fn start_timers(&self, cx: &mut Cx);
 }
 
+/// Trait for screens that keep UI state while their tab is hidden
+///
+/// The shell calls `on_deactivate` before hiding the page and `on_activate`
+/// after showing it again. [`TabState`](crate::tab_state::TabState) does the
+/// bookkeeping for scroll views and focused inputs.
+pub trait TabLifecycle {
+    /// Save scroll offsets and focus (call before the tab is hidden)
+    fn on_deactivate(&self, cx: &mut Cx);
+
+    /// Restore them after the next draw (call when the tab is shown again)
+    fn on_activate(&self, cx: &mut Cx);
+}
+
 /// Registry of all installed apps
 ///
 /// Note: Due to Makepad's architecture, apps must still be imported at compile time.
@@ -0,0 +1,214 @@
+//! # Audio Export
+//!
+//! Joining synthesized WAV pieces into one file and encoding the result,
+//! shared by the apps that render long text in chunks (Podcast episodes,
+//! Converter text-to-audio).
+//!
+//! [`concat_wav`] uses `sox` when it is installed and otherwise streams the
+//! pieces itself, one file in memory at a time. [`encode`] turns the joined
+//! WAV into an [`AudioFileFormat`] with `ffmpeg`, falling back to `lame`
+//! (MP3) or macOS `afconvert` (M4A, AIFF).
+//!
+//! ```rust,ignore
+//! use mofa_widgets::audio_export::{concat_wav, encode, AudioFileFormat};
+//!
+//! concat_wav(&chunk_files, &joined)?;
+//! encode(&joined, &output.with_extension("m4a"), AudioFileFormat::M4a)?;
+//! ```
+
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::tts::{self, TtsAudio};
+
+/// Container formats an export can be encoded to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AudioFileFormat {
+    #[default]
+    Wav,
+    Mp3,
+    M4a,
+    Aiff,
+}
+
+impl AudioFileFormat {
+    pub const ALL: [AudioFileFormat; 4] = [Self::Wav, Self::Mp3, Self::M4a, Self::Aiff];
+
+    /// File extension, without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Mp3 => "mp3",
+            Self::M4a => "m4a",
+            Self::Aiff => "aiff",
+        }
+    }
+
+    /// Format for an extension (case-insensitive, leading dot allowed)
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        let ext = ext.trim_start_matches('.').to_ascii_lowercase();
+        match ext.as_str() {
+            "aif" => Some(Self::Aiff),
+            _ => Self::ALL.into_iter().find(|f| f.extension() == ext),
+        }
+    }
+}
+
+/// Join WAV files end to end into `output`, using `sox` when available.
+/// All inputs must share a sample rate; the result is 16-bit mono.
+pub fn concat_wav(inputs: &[PathBuf], output: &Path) -> Result<(), String> {
+    match inputs {
+        [] => return Err("No audio to join".to_string()),
+        [single] => {
+            return std::fs::copy(single, output)
+                .map(|_| ())
+                .map_err(|e| format!("Failed to write {}: {}", output.display(), e));
+        }
+        _ => {}
+    }
+
+    let sox = Command::new("sox").args(inputs).arg(output).output();
+    if matches!(sox, Ok(ref out) if out.status.success()) {
+        return Ok(());
+    }
+    stream_concat(inputs, output)
+}
+
+/// Concatenation without external tools: write a header, append each
+/// input's samples, then patch the header sizes
+fn stream_concat(inputs: &[PathBuf], output: &Path) -> Result<(), String> {
+    let write_err = |e: std::io::Error| format!("Failed to write {}: {}", output.display(), e);
+    let mut writer = BufWriter::new(File::create(output).map_err(write_err)?);
+
+    let mut sample_rate = None;
+    let mut data_len: u64 = 0;
+    for input in inputs {
+        let audio = tts::read_wav(input).map_err(|e| format!("{}: {}", input.display(), e))?;
+        match sample_rate {
+            None => {
+                writer.write_all(&wav_header(audio.sample_rate, 0)).map_err(write_err)?;
+                sample_rate = Some(audio.sample_rate);
+            }
+            Some(rate) if rate != audio.sample_rate => {
+                drop(writer);
+                let _ = std::fs::remove_file(output);
+                return Err(format!(
+                    "{} is {} Hz, expected {} Hz",
+                    input.display(),
+                    audio.sample_rate,
+                    rate
+                ));
+            }
+            Some(_) => {}
+        }
+        writer.write_all(&pcm16(&audio)).map_err(write_err)?;
+        data_len += audio.samples.len() as u64 * 2;
+    }
+
+    let data_len = u32::try_from(data_len).map_err(|_| "Joined audio is larger than a WAV file can hold".to_string())?;
+    let mut file = writer.into_inner().map_err(|e| write_err(e.into_error()))?;
+    file.seek(SeekFrom::Start(0)).map_err(write_err)?;
+    file.write_all(&wav_header(sample_rate.unwrap_or_default(), data_len)).map_err(write_err)
+}
+
+/// 44-byte header of a 16-bit mono PCM WAV file
+fn wav_header(sample_rate: u32, data_len: u32) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(44);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36u32.saturating_add(data_len)).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    bytes
+}
+
+fn pcm16(audio: &TtsAudio) -> Vec<u8> {
+    audio
+        .samples
+        .iter()
+        .flat_map(|s| ((s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes())
+        .collect()
+}
+
+/// Encode a WAV file to `format`; WAV output is a plain copy
+pub fn encode(wav_path: &Path, output: &Path, format: AudioFileFormat) -> Result<(), String> {
+    let codec_args: &[&str] = match format {
+        AudioFileFormat::Wav => {
+            if wav_path != output {
+                std::fs::copy(wav_path, output).map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+            }
+            return Ok(());
+        }
+        AudioFileFormat::Mp3 => return tts::encode_mp3(wav_path, output),
+        AudioFileFormat::M4a => &["-codec:a", "aac", "-b:a", "128k"],
+        AudioFileFormat::Aiff => &["-codec:a", "pcm_s16be"],
+    };
+
+    let ffmpeg = Command::new("ffmpeg")
+        .args(["-y", "-loglevel", "error", "-i"])
+        .arg(wav_path)
+        .args(codec_args)
+        .arg(output)
+        .output();
+    if matches!(ffmpeg, Ok(ref out) if out.status.success()) {
+        return Ok(());
+    }
+
+    let afconvert_args: &[&str] = match format {
+        AudioFileFormat::M4a => &["-f", "m4af", "-d", "aac"],
+        _ => &["-f", "AIFF", "-d", "BEI16"],
+    };
+    match Command::new("afconvert").args(afconvert_args).arg(wav_path).arg(output).output() {
+        Ok(out) if out.status.success() => Ok(()),
+        Ok(out) => Err(format!("afconvert failed: {}", String::from_utf8_lossy(&out.stderr).trim())),
+        Err(_) => Err(format!(
+            "{} export needs ffmpeg (or afconvert on macOS)",
+            format.extension().to_uppercase()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_concat() {
+        let dir = std::env::temp_dir().join(format!("mofa-audio-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let parts: Vec<PathBuf> = (0..3).map(|i| dir.join(format!("part{}.wav", i))).collect();
+        for (i, part) in parts.iter().enumerate() {
+            let audio = TtsAudio { samples: vec![0.25 * i as f32; 100 + i], sample_rate: 16000 };
+            tts::write_wav(part, &audio).unwrap();
+        }
+
+        let joined = dir.join("joined.wav");
+        stream_concat(&parts, &joined).unwrap();
+        let read = tts::read_wav(&joined).unwrap();
+        assert_eq!(read.sample_rate, 16000);
+        assert_eq!(read.samples.len(), 303);
+        assert!((read.samples[302] - 0.5).abs() < 1e-3);
+
+        tts::write_wav(&parts[1], &TtsAudio { samples: vec![0.0; 10], sample_rate: 22050 }).unwrap();
+        let err = stream_concat(&parts, &joined).unwrap_err();
+        assert!(err.contains("22050 Hz"));
+        assert!(!joined.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(AudioFileFormat::from_extension(".MP3"), Some(AudioFileFormat::Mp3));
+        assert_eq!(AudioFileFormat::from_extension("aif"), Some(AudioFileFormat::Aiff));
+        assert_eq!(AudioFileFormat::from_extension("ogg"), None);
+    }
+}
@@ -0,0 +1,960 @@
+//! # Audio Tags
+//!
+//! Reads and writes metadata (title, artist, track, artwork, ...) for
+//! generated audio files. Shared by the apps that export finished episodes.
+//!
+//! | Container              | Where the tag lives                   |
+//! |------------------------|---------------------------------------|
+//! | `.mp3`                 | ID3v2 tag at the start of file        |
+//! | `.wav`                 | RIFF `id3 ` chunk                     |
+//! | `.aif`/`.aiff`         | FORM `ID3 ` chunk                     |
+//! | `.m4a`/`.m4b`/`.mp4`   | iTunes `moov/udta/meta/ilst` items    |
+//!
+//! ID3 tags are written as ID3v2.3 with UTF-16 text so CJK titles survive;
+//! MP4 items hold UTF-8 text. Other containers return
+//! [`TagError::Unsupported`].
+//!
+//! ```rust,ignore
+//! use mofa_widgets::audio_tags::{write_tags, AudioTags};
+//!
+//! let tags = AudioTags { title: Some("Pilot".into()), track: Some(1), ..Default::default() };
+//! write_tags(&path, &tags)?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Embedded cover image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artwork {
+    /// e.g. `image/jpeg`, `image/png`
+    pub mime: String,
+    pub data: Vec<u8>,
+}
+
+impl Artwork {
+    /// Load an image file, guessing the MIME type from its extension
+    pub fn from_file(path: &Path) -> std::io::Result<Self> {
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let mime = match ext.as_str() {
+            "png" => "image/png",
+            _ => "image/jpeg",
+        };
+        Ok(Self { mime: mime.to_string(), data: std::fs::read(path)? })
+    }
+
+    /// Stable content fingerprint (FNV-1a), used to detect edits
+    pub fn checksum(&self) -> u64 {
+        self.data.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+}
+
+/// Metadata fields written to audio files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AudioTags {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track: Option<u32>,
+    pub genre: Option<String>,
+    pub comment: Option<String>,
+    pub artwork: Option<Artwork>,
+}
+
+/// Serializable record of tags, with artwork reduced to its checksum
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagSnapshot {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track: Option<u32>,
+    pub genre: Option<String>,
+    pub comment: Option<String>,
+    pub artwork_checksum: Option<u64>,
+}
+
+impl AudioTags {
+    pub fn is_empty(&self) -> bool {
+        *self == AudioTags::default()
+    }
+
+    pub fn snapshot(&self) -> TagSnapshot {
+        TagSnapshot {
+            title: self.title.clone(),
+            artist: self.artist.clone(),
+            album: self.album.clone(),
+            track: self.track,
+            genre: self.genre.clone(),
+            comment: self.comment.clone(),
+            artwork_checksum: self.artwork.as_ref().map(Artwork::checksum),
+        }
+    }
+
+    /// Combine freshly generated tags with the tags found on disk.
+    ///
+    /// A field on disk that differs from what was last written was edited by
+    /// hand (e.g. in a tag editor) and is kept; every other field takes the
+    /// generated value.
+    pub fn merge_preserving_edits(&self, on_disk: &AudioTags, last_written: &TagSnapshot) -> AudioTags {
+        fn pick<T: Clone + PartialEq>(generated: &Option<T>, disk: &Option<T>, written: &Option<T>) -> Option<T> {
+            if disk != written {
+                disk.clone()
+            } else {
+                generated.clone()
+            }
+        }
+
+        let disk_checksum = on_disk.artwork.as_ref().map(Artwork::checksum);
+        let artwork = if disk_checksum != last_written.artwork_checksum {
+            on_disk.artwork.clone()
+        } else {
+            self.artwork.clone()
+        };
+
+        AudioTags {
+            title: pick(&self.title, &on_disk.title, &last_written.title),
+            artist: pick(&self.artist, &on_disk.artist, &last_written.artist),
+            album: pick(&self.album, &on_disk.album, &last_written.album),
+            track: pick(&self.track, &on_disk.track, &last_written.track),
+            genre: pick(&self.genre, &on_disk.genre, &last_written.genre),
+            comment: pick(&self.comment, &on_disk.comment, &last_written.comment),
+            artwork,
+        }
+    }
+}
+
+/// Errors from reading or writing tags
+#[derive(Debug)]
+pub enum TagError {
+    Io(std::io::Error),
+    /// The container format cannot carry tags
+    Unsupported(String),
+    /// The file is not a valid container of its claimed type
+    Malformed(String),
+}
+
+impl fmt::Display for TagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagError::Io(e) => write!(f, "I/O error: {}", e),
+            TagError::Unsupported(ext) => write!(f, "Tagging is not supported for .{} files", ext),
+            TagError::Malformed(msg) => write!(f, "Malformed audio file: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for TagError {}
+
+impl From<std::io::Error> for TagError {
+    fn from(e: std::io::Error) -> Self {
+        TagError::Io(e)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Container {
+    Mp3,
+    Wav,
+    Aiff,
+    Mp4,
+}
+
+fn container_for(path: &Path) -> Result<Container, TagError> {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "mp3" => Ok(Container::Mp3),
+        "wav" => Ok(Container::Wav),
+        "aif" | "aiff" => Ok(Container::Aiff),
+        "m4a" | "m4b" | "mp4" => Ok(Container::Mp4),
+        _ => Err(TagError::Unsupported(ext)),
+    }
+}
+
+/// Read tags from a file; `Ok(None)` if the file has no tag
+pub fn read_tags(path: &Path) -> Result<Option<AudioTags>, TagError> {
+    let container = container_for(path)?;
+    let data = std::fs::read(path)?;
+    if let Container::Mp4 = container {
+        return read_mp4(&data);
+    }
+
+    let tag_bytes = match container {
+        Container::Mp3 => {
+            if !data.starts_with(b"ID3") {
+                return Ok(None);
+            }
+            &data[..]
+        }
+        Container::Wav | Container::Aiff => {
+            let layout = ChunkLayout::parse(&data, container)?;
+            match layout.chunks.iter().find(|c| c.is_id3()) {
+                Some(chunk) => &data[chunk.body.clone()],
+                None => return Ok(None),
+            }
+        }
+        Container::Mp4 => unreachable!("MP4 tags are read above"),
+    };
+
+    Ok(decode_id3v2(tag_bytes))
+}
+
+/// Write tags into a file, replacing any existing tag
+pub fn write_tags(path: &Path, tags: &AudioTags) -> Result<(), TagError> {
+    let container = container_for(path)?;
+    let data = std::fs::read(path)?;
+
+    let output = match container {
+        Container::Mp3 => {
+            let tag = encode_id3v2(tags);
+            let audio_start = existing_id3_len(&data).min(data.len());
+            let mut out = Vec::with_capacity(tag.len() + data.len() - audio_start);
+            out.extend_from_slice(&tag);
+            out.extend_from_slice(&data[audio_start..]);
+            out
+        }
+        Container::Wav | Container::Aiff => {
+            ChunkLayout::parse(&data, container)?.rebuild_with_id3(&data, &encode_id3v2(tags))
+        }
+        Container::Mp4 => write_mp4(&data, tags)?,
+    };
+
+    // Write next to the target, then swap, so a failure never truncates audio
+    let tmp = path.with_extension("tagging.tmp");
+    std::fs::write(&tmp, &output)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+// ============================================================================
+// RIFF / AIFF chunk handling
+// ============================================================================
+
+struct Chunk {
+    id: [u8; 4],
+    body: std::ops::Range<usize>,
+}
+
+impl Chunk {
+    fn is_id3(&self) -> bool {
+        self.id.eq_ignore_ascii_case(b"id3 ")
+    }
+}
+
+struct ChunkLayout {
+    container: Container,
+    form_type: [u8; 4],
+    chunks: Vec<Chunk>,
+}
+
+impl ChunkLayout {
+    fn parse(data: &[u8], container: Container) -> Result<Self, TagError> {
+        let (magic, big_endian): (&[u8], bool) = match container {
+            Container::Wav => (b"RIFF", false),
+            Container::Aiff => (b"FORM", true),
+            Container::Mp3 | Container::Mp4 => unreachable!("only RIFF and AIFF have a chunk layout"),
+        };
+        if data.len() < 12 || &data[0..4] != magic {
+            return Err(TagError::Malformed(format!("missing {} header", String::from_utf8_lossy(magic))));
+        }
+        let form_type = [data[8], data[9], data[10], data[11]];
+
+        let mut chunks = Vec::new();
+        let mut pos = 12;
+        while pos + 8 <= data.len() {
+            let id = [data[pos], data[pos + 1], data[pos + 2], data[pos + 3]];
+            let size_bytes = [data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]];
+            let size = if big_endian {
+                u32::from_be_bytes(size_bytes)
+            } else {
+                u32::from_le_bytes(size_bytes)
+            } as usize;
+            let start = pos + 8;
+            let end = (start + size).min(data.len());
+            chunks.push(Chunk { id, body: start..end });
+            // Chunks are padded to an even length
+            pos = start + size + (size & 1);
+        }
+
+        Ok(Self { container, form_type, chunks })
+    }
+
+    fn rebuild_with_id3(&self, data: &[u8], tag: &[u8]) -> Vec<u8> {
+        let (magic, id3_id): (&[u8; 4], &[u8; 4]) = match self.container {
+            Container::Aiff => (b"FORM", b"ID3 "),
+            _ => (b"RIFF", b"id3 "),
+        };
+        let size_bytes = |size: usize| -> [u8; 4] {
+            match self.container {
+                Container::Aiff => (size as u32).to_be_bytes(),
+                _ => (size as u32).to_le_bytes(),
+            }
+        };
+
+        let mut body = Vec::with_capacity(data.len() + tag.len());
+        body.extend_from_slice(&self.form_type);
+        for chunk in self.chunks.iter().filter(|c| !c.is_id3()) {
+            let bytes = &data[chunk.body.clone()];
+            body.extend_from_slice(&chunk.id);
+            body.extend_from_slice(&size_bytes(bytes.len()));
+            body.extend_from_slice(bytes);
+            if bytes.len() % 2 == 1 {
+                body.push(0);
+            }
+        }
+        body.extend_from_slice(id3_id);
+        body.extend_from_slice(&size_bytes(tag.len()));
+        body.extend_from_slice(tag);
+        if tag.len() % 2 == 1 {
+            body.push(0);
+        }
+
+        let mut out = Vec::with_capacity(body.len() + 8);
+        out.extend_from_slice(magic);
+        out.extend_from_slice(&size_bytes(body.len()));
+        out.extend_from_slice(&body);
+        out
+    }
+}
+
+// ============================================================================
+// ID3v2 encoding / decoding
+// ============================================================================
+
+fn syncsafe(size: usize) -> [u8; 4] {
+    [
+        ((size >> 21) & 0x7f) as u8,
+        ((size >> 14) & 0x7f) as u8,
+        ((size >> 7) & 0x7f) as u8,
+        (size & 0x7f) as u8,
+    ]
+}
+
+fn from_syncsafe(bytes: &[u8]) -> usize {
+    bytes.iter().fold(0usize, |acc, b| (acc << 7) | (*b as usize & 0x7f))
+}
+
+/// Length of an ID3v2 tag at the start of `data` (0 if none)
+fn existing_id3_len(data: &[u8]) -> usize {
+    if data.len() < 10 || !data.starts_with(b"ID3") {
+        return 0;
+    }
+    let footer = if data[5] & 0x10 != 0 { 10 } else { 0 };
+    10 + from_syncsafe(&data[6..10]) + footer
+}
+
+/// UTF-16 with BOM (ID3v2.3 encoding 1)
+fn utf16_with_bom(text: &str) -> Vec<u8> {
+    let mut out = vec![0xff, 0xfe];
+    for unit in text.encode_utf16() {
+        out.extend_from_slice(&unit.to_le_bytes());
+    }
+    out
+}
+
+fn frame(id: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(body.len() + 10);
+    out.extend_from_slice(id);
+    out.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    out.extend_from_slice(&[0, 0]);
+    out.extend_from_slice(body);
+    out
+}
+
+fn text_frame(id: &[u8; 4], text: &str) -> Vec<u8> {
+    let mut body = vec![1u8];
+    body.extend_from_slice(&utf16_with_bom(text));
+    frame(id, &body)
+}
+
+fn encode_id3v2(tags: &AudioTags) -> Vec<u8> {
+    let mut frames = Vec::new();
+    let texts = [
+        (b"TIT2", tags.title.as_deref()),
+        (b"TPE1", tags.artist.as_deref()),
+        (b"TALB", tags.album.as_deref()),
+        (b"TCON", tags.genre.as_deref()),
+    ];
+    for (id, value) in texts {
+        if let Some(value) = value.filter(|v| !v.is_empty()) {
+            frames.extend(text_frame(id, value));
+        }
+    }
+    if let Some(track) = tags.track {
+        frames.extend(text_frame(b"TRCK", &track.to_string()));
+    }
+    if let Some(comment) = tags.comment.as_deref().filter(|c| !c.is_empty()) {
+        // encoding, language, empty description (BOM + terminator), text
+        let mut body = vec![1u8];
+        body.extend_from_slice(b"eng");
+        body.extend_from_slice(&[0xff, 0xfe, 0, 0]);
+        body.extend_from_slice(&utf16_with_bom(comment));
+        frames.extend(frame(b"COMM", &body));
+    }
+    if let Some(ref artwork) = tags.artwork {
+        // Latin-1 encoding, MIME, front cover, empty description, image
+        let mut body = vec![0u8];
+        body.extend_from_slice(artwork.mime.as_bytes());
+        body.push(0);
+        body.push(0x03);
+        body.push(0);
+        body.extend_from_slice(&artwork.data);
+        frames.extend(frame(b"APIC", &body));
+    }
+
+    let mut out = Vec::with_capacity(frames.len() + 10);
+    out.extend_from_slice(b"ID3");
+    out.extend_from_slice(&[3, 0, 0]);
+    out.extend_from_slice(&syncsafe(frames.len()));
+    out.extend_from_slice(&frames);
+    out
+}
+
+/// Decode text by ID3 encoding byte; stops at the first terminator
+fn decode_text(encoding: u8, bytes: &[u8]) -> String {
+    let utf16 = |bytes: &[u8], big_endian: bool| -> String {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| if big_endian { u16::from_be_bytes([c[0], c[1]]) } else { u16::from_le_bytes([c[0], c[1]]) })
+            .take_while(|u| *u != 0)
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+    match encoding {
+        1 => match bytes {
+            [0xfe, 0xff, rest @ ..] => utf16(rest, true),
+            [0xff, 0xfe, rest @ ..] => utf16(rest, false),
+            _ => utf16(bytes, false),
+        },
+        2 => utf16(bytes, true),
+        3 => String::from_utf8_lossy(bytes.split(|b| *b == 0).next().unwrap_or(&[])).to_string(),
+        _ => bytes.iter().take_while(|b| **b != 0).map(|b| *b as char).collect(),
+    }
+}
+
+/// Split `bytes` after the first string terminator for `encoding`
+fn skip_terminated(encoding: u8, bytes: &[u8]) -> &[u8] {
+    if encoding == 1 || encoding == 2 {
+        let mut i = 0;
+        while i + 1 < bytes.len() {
+            if bytes[i] == 0 && bytes[i + 1] == 0 {
+                return &bytes[i + 2..];
+            }
+            i += 2;
+        }
+        &[]
+    } else {
+        match bytes.iter().position(|b| *b == 0) {
+            Some(i) => &bytes[i + 1..],
+            None => &[],
+        }
+    }
+}
+
+fn decode_id3v2(data: &[u8]) -> Option<AudioTags> {
+    if data.len() < 10 || !data.starts_with(b"ID3") {
+        return None;
+    }
+    let version = data[3];
+    let flags = data[5];
+    // Unsynchronised tags are rare in practice and not produced by us
+    if !(3..=4).contains(&version) || flags & 0x80 != 0 {
+        return None;
+    }
+    let end = (10 + from_syncsafe(&data[6..10])).min(data.len());
+    let mut pos = 10;
+    if flags & 0x40 != 0 && pos + 4 <= end {
+        let ext_size = if version == 4 {
+            from_syncsafe(&data[pos..pos + 4])
+        } else {
+            u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize + 4
+        };
+        pos += ext_size;
+    }
+
+    let mut tags = AudioTags::default();
+    while pos + 10 <= end && data[pos] != 0 {
+        let id = &data[pos..pos + 4];
+        let size = if version == 4 {
+            from_syncsafe(&data[pos + 4..pos + 8])
+        } else {
+            u32::from_be_bytes([data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]]) as usize
+        };
+        let body_start = pos + 10;
+        let body_end = (body_start + size).min(end);
+        let body = &data[body_start..body_end];
+        pos = body_start + size;
+        if body.is_empty() {
+            continue;
+        }
+
+        let encoding = body[0];
+        match id {
+            b"TIT2" => tags.title = Some(decode_text(encoding, &body[1..])),
+            b"TPE1" => tags.artist = Some(decode_text(encoding, &body[1..])),
+            b"TALB" => tags.album = Some(decode_text(encoding, &body[1..])),
+            b"TCON" => tags.genre = Some(decode_text(encoding, &body[1..])),
+            b"TRCK" => {
+                // "3" or "3/10"
+                tags.track = decode_text(encoding, &body[1..])
+                    .split('/')
+                    .next()
+                    .and_then(|n| n.trim().parse().ok());
+            }
+            b"COMM" if body.len() > 4 => {
+                let text = skip_terminated(encoding, &body[4..]);
+                tags.comment = Some(decode_text(encoding, text));
+            }
+            b"APIC" => {
+                let after_mime = skip_terminated(0, &body[1..]);
+                let mime_len = body.len() - 1 - after_mime.len();
+                let mime = String::from_utf8_lossy(&body[1..1 + mime_len.saturating_sub(1)]).to_string();
+                if let Some((_picture_type, rest)) = after_mime.split_first() {
+                    let data = skip_terminated(encoding, rest);
+                    tags.artwork = Some(Artwork { mime, data: data.to_vec() });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(tags)
+}
+
+// ============================================================================
+// MP4 / M4A atoms
+// ============================================================================
+
+/// Atoms whose body is a list of child atoms; walked to reach `ilst` and the
+/// chunk offset tables
+const MP4_CONTAINERS: [[u8; 4]; 9] =
+    [*b"moov", *b"trak", *b"mdia", *b"minf", *b"stbl", *b"edts", *b"udta", *b"meta", *b"ilst"];
+
+const MP4_TITLE: [u8; 4] = *b"\xa9nam";
+const MP4_ARTIST: [u8; 4] = *b"\xa9ART";
+const MP4_ALBUM: [u8; 4] = *b"\xa9alb";
+const MP4_GENRE: [u8; 4] = *b"\xa9gen";
+const MP4_COMMENT: [u8; 4] = *b"\xa9cmt";
+const MP4_TRACK: [u8; 4] = *b"trkn";
+const MP4_ARTWORK: [u8; 4] = *b"covr";
+
+/// `ilst` items replaced on every write; any other item is kept
+const MP4_ITEMS: [[u8; 4]; 7] = [MP4_TITLE, MP4_ARTIST, MP4_ALBUM, MP4_GENRE, MP4_COMMENT, MP4_TRACK, MP4_ARTWORK];
+
+/// `data` atom types
+const MP4_UTF8: u32 = 1;
+const MP4_JPEG: u32 = 13;
+const MP4_PNG: u32 = 14;
+
+/// Handler marking `meta` as iTunes metadata: version/flags, pre-defined,
+/// `mdir`, `appl`, reserved, empty name
+const MP4_HDLR: &[u8; 25] = b"\0\0\0\0\0\0\0\0mdirappl\0\0\0\0\0\0\0\0\0";
+
+/// Position of an atom inside a byte buffer
+struct AtomSpan {
+    kind: [u8; 4],
+    start: usize,
+    body: std::ops::Range<usize>,
+}
+
+/// Split `data` into its atoms, handling 64-bit and to-end-of-file sizes
+fn atom_spans(data: &[u8]) -> Result<Vec<AtomSpan>, TagError> {
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    while pos + 8 <= data.len() {
+        let kind = [data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]];
+        let (header, size) = match u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) {
+            0 => (8, (data.len() - pos) as u64),
+            1 if pos + 16 <= data.len() => {
+                let mut large = [0u8; 8];
+                large.copy_from_slice(&data[pos + 8..pos + 16]);
+                (16, u64::from_be_bytes(large))
+            }
+            size => (8, size as u64),
+        };
+        if size < header as u64 || size > (data.len() - pos) as u64 {
+            return Err(TagError::Malformed(format!("bad size for atom {}", String::from_utf8_lossy(&kind))));
+        }
+        let end = pos + size as usize;
+        spans.push(AtomSpan { kind, start: pos, body: pos + header..end });
+        pos = end;
+    }
+    Ok(spans)
+}
+
+/// An atom read into memory; only the `moov` tree is, never the media data
+struct Atom {
+    kind: [u8; 4],
+    /// Whole body of a leaf atom; for containers, the bytes before the
+    /// children (the version and flags of `meta`)
+    data: Vec<u8>,
+    children: Vec<Atom>,
+}
+
+impl Atom {
+    fn leaf(kind: [u8; 4], data: Vec<u8>) -> Self {
+        Self { kind, data, children: Vec::new() }
+    }
+
+    fn parse(kind: [u8; 4], body: &[u8]) -> Result<Self, TagError> {
+        if !MP4_CONTAINERS.contains(&kind) {
+            return Ok(Self::leaf(kind, body.to_vec()));
+        }
+        // `meta` is a full box in MP4 files but a plain container in QuickTime ones
+        let head = if &kind == b"meta" && body.get(4..8) != Some(&b"hdlr"[..]) { body.len().min(4) } else { 0 };
+        let children = atom_spans(&body[head..])?
+            .into_iter()
+            .map(|span| Atom::parse(span.kind, &body[head..][span.body]))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { kind, data: body[..head].to_vec(), children })
+    }
+
+    fn encoded_len(&self) -> usize {
+        8 + self.data.len() + self.children.iter().map(Atom::encoded_len).sum::<usize>()
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&(self.encoded_len() as u32).to_be_bytes());
+        out.extend_from_slice(&self.kind);
+        out.extend_from_slice(&self.data);
+        for child in &self.children {
+            child.encode(out);
+        }
+    }
+
+    fn child(&self, kind: &[u8; 4]) -> Option<&Atom> {
+        self.children.iter().find(|c| &c.kind == kind)
+    }
+
+    fn child_or_insert(&mut self, kind: [u8; 4], data: Vec<u8>) -> &mut Atom {
+        let index = match self.children.iter().position(|c| c.kind == kind) {
+            Some(index) => index,
+            None => {
+                self.children.push(Atom::leaf(kind, data));
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[index]
+    }
+}
+
+/// `(type, payload)` of the `data` atom inside an `ilst` item's body
+fn mp4_item_data(body: &[u8]) -> Option<(u32, &[u8])> {
+    let span = atom_spans(body).ok()?.into_iter().find(|s| &s.kind == b"data")?;
+    let data = &body[span.body];
+    if data.len() < 8 {
+        return None;
+    }
+    Some((u32::from_be_bytes([data[0], data[1], data[2], data[3]]), &data[8..]))
+}
+
+/// An `ilst` item holding a single `data` atom
+fn mp4_item(kind: [u8; 4], data_type: u32, payload: &[u8]) -> Atom {
+    let mut data = Vec::with_capacity(payload.len() + 8);
+    data.extend_from_slice(&data_type.to_be_bytes());
+    data.extend_from_slice(&[0; 4]); // locale
+    data.extend_from_slice(payload);
+    Atom { kind, data: Vec::new(), children: vec![Atom::leaf(*b"data", data)] }
+}
+
+fn encode_mp4_items(tags: &AudioTags) -> Vec<Atom> {
+    let mut items = Vec::new();
+    let texts = [
+        (MP4_TITLE, tags.title.as_deref()),
+        (MP4_ARTIST, tags.artist.as_deref()),
+        (MP4_ALBUM, tags.album.as_deref()),
+        (MP4_GENRE, tags.genre.as_deref()),
+        (MP4_COMMENT, tags.comment.as_deref()),
+    ];
+    for (kind, value) in texts {
+        if let Some(value) = value.filter(|v| !v.is_empty()) {
+            items.push(mp4_item(kind, MP4_UTF8, value.as_bytes()));
+        }
+    }
+    if let Some(track) = tags.track {
+        // reserved, track, total (unknown), reserved
+        let track = track.min(u16::MAX as u32) as u16;
+        let mut payload = vec![0, 0];
+        payload.extend_from_slice(&track.to_be_bytes());
+        payload.extend_from_slice(&[0, 0, 0, 0]);
+        items.push(mp4_item(MP4_TRACK, 0, &payload));
+    }
+    if let Some(ref artwork) = tags.artwork {
+        let data_type = if artwork.mime == "image/png" { MP4_PNG } else { MP4_JPEG };
+        items.push(mp4_item(MP4_ARTWORK, data_type, &artwork.data));
+    }
+    items
+}
+
+fn decode_mp4_items(ilst: &Atom) -> AudioTags {
+    let mut tags = AudioTags::default();
+    for item in &ilst.children {
+        let Some((data_type, payload)) = mp4_item_data(&item.data) else {
+            continue;
+        };
+        let text = || Some(String::from_utf8_lossy(payload).to_string());
+        match item.kind {
+            MP4_TITLE => tags.title = text(),
+            MP4_ARTIST => tags.artist = text(),
+            MP4_ALBUM => tags.album = text(),
+            MP4_GENRE => tags.genre = text(),
+            MP4_COMMENT => tags.comment = text(),
+            MP4_TRACK if payload.len() >= 4 => {
+                tags.track = Some(u16::from_be_bytes([payload[2], payload[3]]) as u32);
+            }
+            MP4_ARTWORK => {
+                let mime = if data_type == MP4_PNG { "image/png" } else { "image/jpeg" };
+                tags.artwork = Some(Artwork { mime: mime.to_string(), data: payload.to_vec() });
+            }
+            _ => {}
+        }
+    }
+    tags
+}
+
+fn read_mp4(data: &[u8]) -> Result<Option<AudioTags>, TagError> {
+    let Some(span) = atom_spans(data)?.into_iter().find(|s| &s.kind == b"moov") else {
+        return Err(TagError::Malformed("missing moov atom".to_string()));
+    };
+    let moov = Atom::parse(span.kind, &data[span.body])?;
+    let ilst = moov.child(b"udta").and_then(|a| a.child(b"meta")).and_then(|a| a.child(b"ilst"));
+    Ok(ilst.map(decode_mp4_items))
+}
+
+/// Add `delta` to every chunk offset at or past `from`
+fn shift_chunk_offsets(atom: &mut Atom, from: u64, delta: i64) -> Result<(), TagError> {
+    let width = match &atom.kind {
+        b"stco" => 4,
+        b"co64" => 8,
+        _ => {
+            for child in &mut atom.children {
+                shift_chunk_offsets(child, from, delta)?;
+            }
+            return Ok(());
+        }
+    };
+    let malformed = || TagError::Malformed("bad chunk offset table".to_string());
+    let count = atom.data.get(4..8).ok_or_else(malformed)?;
+    let count = u32::from_be_bytes([count[0], count[1], count[2], count[3]]) as usize;
+    if atom.data.len() < 8 + count * width {
+        return Err(malformed());
+    }
+    for entry in atom.data[8..8 + count * width].chunks_exact_mut(width) {
+        let offset = entry.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+        if offset < from {
+            continue;
+        }
+        let shifted = offset.checked_add_signed(delta).ok_or_else(malformed)?;
+        if width == 4 {
+            let shifted = u32::try_from(shifted).map_err(|_| malformed())?;
+            entry.copy_from_slice(&shifted.to_be_bytes());
+        } else {
+            entry.copy_from_slice(&shifted.to_be_bytes());
+        }
+    }
+    Ok(())
+}
+
+fn write_mp4(data: &[u8], tags: &AudioTags) -> Result<Vec<u8>, TagError> {
+    let Some(span) = atom_spans(data)?.into_iter().find(|s| &s.kind == b"moov") else {
+        return Err(TagError::Malformed("missing moov atom".to_string()));
+    };
+    let mut moov = Atom::parse(span.kind, &data[span.body.clone()])?;
+
+    let meta = moov.child_or_insert(*b"udta", Vec::new()).child_or_insert(*b"meta", vec![0; 4]);
+    if meta.child(b"hdlr").is_none() {
+        meta.children.insert(0, Atom::leaf(*b"hdlr", MP4_HDLR.to_vec()));
+    }
+    let ilst = meta.child_or_insert(*b"ilst", Vec::new());
+    ilst.children.retain(|item| !MP4_ITEMS.contains(&item.kind));
+    ilst.children.extend(encode_mp4_items(tags));
+
+    // Media data stored after `moov` moves by however much `moov` grew
+    let end = span.body.end;
+    let delta = moov.encoded_len() as i64 - (end - span.start) as i64;
+    if delta != 0 {
+        shift_chunk_offsets(&mut moov, end as u64, delta)?;
+    }
+
+    let mut out = Vec::with_capacity(data.len() + moov.encoded_len());
+    out.extend_from_slice(&data[..span.start]);
+    moov.encode(&mut out);
+    out.extend_from_slice(&data[end..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_tags() -> AudioTags {
+        AudioTags {
+            title: Some("第一集 · Pilot".to_string()),
+            artist: Some("MoFA".to_string()),
+            album: None,
+            track: Some(7),
+            genre: Some("Podcast".to_string()),
+            comment: Some("Show notes".to_string()),
+            artwork: Some(Artwork { mime: "image/png".to_string(), data: vec![0x89, b'P', b'N', b'G', 0, 1, 2] }),
+        }
+    }
+
+    fn minimal_wav() -> Vec<u8> {
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        fmt.extend_from_slice(&1u16.to_le_bytes()); // mono
+        fmt.extend_from_slice(&8000u32.to_le_bytes());
+        fmt.extend_from_slice(&16000u32.to_le_bytes());
+        fmt.extend_from_slice(&2u16.to_le_bytes());
+        fmt.extend_from_slice(&16u16.to_le_bytes());
+        let samples = [0u8, 0, 1, 0, 2, 0];
+
+        let mut body = b"WAVE".to_vec();
+        body.extend_from_slice(b"fmt ");
+        body.extend_from_slice(&(fmt.len() as u32).to_le_bytes());
+        body.extend_from_slice(&fmt);
+        body.extend_from_slice(b"data");
+        body.extend_from_slice(&(samples.len() as u32).to_le_bytes());
+        body.extend_from_slice(&samples);
+
+        let mut out = b"RIFF".to_vec();
+        out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        out.extend_from_slice(&body);
+        out
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("mofa_audio_tags_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_id3_round_trip() {
+        let tags = sample_tags();
+        assert_eq!(decode_id3v2(&encode_id3v2(&tags)), Some(tags));
+    }
+
+    /// Shared with Podcast Factory's Python port (python/web/id3_tags.py)
+    #[test]
+    fn test_id3_matches_shared_fixture() {
+        let tags = AudioTags {
+            title: Some("第一集 · Pilot".to_string()),
+            artist: Some("Ada, Lin".to_string()),
+            album: Some("MoFA Weekly".to_string()),
+            track: Some(7),
+            genre: Some("Podcast".to_string()),
+            comment: Some("Show notes".to_string()),
+            artwork: None,
+        };
+        let fixture = include_bytes!("../tests/fixtures/episode.id3");
+        assert_eq!(encode_id3v2(&tags), fixture);
+        assert_eq!(decode_id3v2(fixture), Some(tags));
+    }
+
+    #[test]
+    fn test_wav_tags_replace_previous_chunk_and_keep_audio() {
+        let path = temp_path("episode.wav");
+        std::fs::write(&path, minimal_wav()).unwrap();
+        assert_eq!(read_tags(&path).unwrap(), None);
+
+        write_tags(&path, &AudioTags { title: Some("Old".to_string()), ..Default::default() }).unwrap();
+        write_tags(&path, &sample_tags()).unwrap();
+        assert_eq!(read_tags(&path).unwrap(), Some(sample_tags()));
+
+        let data = std::fs::read(&path).unwrap();
+        let layout = ChunkLayout::parse(&data, Container::Wav).unwrap();
+        assert_eq!(layout.chunks.iter().filter(|c| c.is_id3()).count(), 1);
+        let riff_size = u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize;
+        assert_eq!(riff_size, data.len() - 8);
+        let audio = layout.chunks.iter().find(|c| &c.id == b"data").unwrap();
+        assert_eq!(&data[audio.body.clone()], &[0, 0, 1, 0, 2, 0]);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_mp3_tag_is_replaced_not_stacked() {
+        let path = temp_path("episode.mp3");
+        let frames = [0xffu8, 0xfb, 0x90, 0x00];
+        std::fs::write(&path, frames).unwrap();
+
+        write_tags(&path, &sample_tags()).unwrap();
+        write_tags(&path, &sample_tags()).unwrap();
+        let data = std::fs::read(&path).unwrap();
+        assert_eq!(&data[existing_id3_len(&data)..], &frames);
+        assert_eq!(read_tags(&path).unwrap(), Some(sample_tags()));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_unsupported_container() {
+        let err = write_tags(Path::new("episode.ogg"), &sample_tags()).unwrap_err();
+        assert!(matches!(err, TagError::Unsupported(ref ext) if ext == "ogg"));
+    }
+
+    /// ftyp, then a moov whose single chunk offset points into the mdat after it
+    fn minimal_m4a() -> Vec<u8> {
+        fn atom(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+            let mut out = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+            out.extend_from_slice(kind);
+            out.extend_from_slice(body);
+            out
+        }
+        let ftyp = atom(b"ftyp", b"M4A \0\0\0\0M4A isom");
+        let moov = |offset: u32| {
+            let mut body = vec![0, 0, 0, 0, 0, 0, 0, 1];
+            body.extend_from_slice(&offset.to_be_bytes());
+            atom(b"moov", &atom(b"trak", &atom(b"mdia", &atom(b"minf", &atom(b"stbl", &atom(b"stco", &body))))))
+        };
+        let moov_len = moov(0).len();
+        let mut out = ftyp.clone();
+        out.extend(moov((ftyp.len() + moov_len + 8) as u32));
+        out.extend(atom(b"mdat", b"AUDIO"));
+        out
+    }
+
+    #[test]
+    fn test_m4a_tags_keep_chunk_offsets_pointing_at_audio() {
+        let path = temp_path("episode.m4a");
+        std::fs::write(&path, minimal_m4a()).unwrap();
+        assert_eq!(read_tags(&path).unwrap(), None);
+
+        write_tags(&path, &AudioTags { title: Some("Old".to_string()), ..Default::default() }).unwrap();
+        write_tags(&path, &sample_tags()).unwrap();
+        assert_eq!(read_tags(&path).unwrap(), Some(sample_tags()));
+
+        let data = std::fs::read(&path).unwrap();
+        let spans = atom_spans(&data).unwrap();
+        let moov = spans.iter().find(|s| &s.kind == b"moov").unwrap();
+        let moov = Atom::parse(moov.kind, &data[moov.body.clone()]).unwrap();
+        let ilst = moov.child(b"udta").unwrap().child(b"meta").unwrap().child(b"ilst").unwrap();
+        assert_eq!(ilst.children.iter().filter(|item| item.kind == MP4_TITLE).count(), 1);
+
+        let stco = &moov.children[0].children[0].children[0].children[0].children[0];
+        let offset = u32::from_be_bytes([stco.data[8], stco.data[9], stco.data[10], stco.data[11]]) as usize;
+        assert_eq!(&data[offset..offset + 5], b"AUDIO");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_merge_preserves_manual_edits() {
+        let last = sample_tags();
+        let mut on_disk = last.clone();
+        on_disk.title = Some("Edited by hand".to_string());
+
+        let mut generated = sample_tags();
+        generated.title = Some("New title".to_string());
+        generated.comment = Some("New notes".to_string());
+
+        let merged = generated.merge_preserving_edits(&on_disk, &last.snapshot());
+        assert_eq!(merged.title.as_deref(), Some("Edited by hand"));
+        assert_eq!(merged.comment.as_deref(), Some("New notes"));
+        assert_eq!(merged.artwork, last.artwork);
+    }
+}
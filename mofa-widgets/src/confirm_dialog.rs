@@ -0,0 +1,304 @@
+//! # Confirm Dialog Widget
+//!
+//! A modal confirmation dialog with a title, a message and Cancel/Confirm
+//! buttons. The dialog covers its parent with a dimmed overlay while visible.
+//!
+//! ## Usage
+//!
+//! Place it last inside a `flow: Overlay` container, or give it an `abs_pos`
+//! and size at runtime when the parent uses another flow:
+//!
+//! ```rust,ignore
+//! live_design! {
+//!     use mofa_widgets::confirm_dialog::ConfirmDialog;
+//!
+//!     MyScreen = <View> {
+//!         flow: Overlay
+//!         content = <View> { ... }
+//!         confirm = <ConfirmDialog> {}
+//!     }
+//! }
+//!
+//! // Ask for confirmation; the tag comes back with the action
+//! self.view.confirm_dialog(ids!(confirm))
+//!     .show(cx, live_id!(clear_logs), "Clear logs?", "This cannot be undone.");
+//!
+//! // Handle the answer
+//! if let Some(tag) = self.view.confirm_dialog(ids!(confirm)).confirmed(actions) {
+//!     if tag == live_id!(clear_logs) { ... }
+//! }
+//! ```
+
+use makepad_widgets::*;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::theme::*;
+
+    ConfirmDialogButton = <Button> {
+        width: Fit, height: 34
+        padding: {left: 16, right: 16}
+
+        animator: {
+            hover = {
+                default: off,
+                off = {
+                    from: {all: Forward {duration: 0.15}}
+                    apply: { draw_bg: {hover: 0.0} }
+                }
+                on = {
+                    from: {all: Forward {duration: 0.15}}
+                    apply: { draw_bg: {hover: 1.0} }
+                }
+            }
+            pressed = {
+                default: off,
+                off = {
+                    from: {all: Forward {duration: 0.1}}
+                    apply: { draw_bg: {pressed: 0.0} }
+                }
+                on = {
+                    from: {all: Forward {duration: 0.1}}
+                    apply: { draw_bg: {pressed: 1.0} }
+                }
+            }
+        }
+
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix((GRAY_700), (SLATE_300), self.dark_mode);
+            }
+        }
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            instance dark_mode: 0.0
+            border_radius: 6.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.border_radius);
+                let base = mix((HOVER_BG), (SLATE_600), self.dark_mode);
+                let hover_color = mix((SLATE_200), (SLATE_500), self.dark_mode);
+                let pressed_color = mix((SLATE_300), (SLATE_400), self.dark_mode);
+                sdf.fill(mix(mix(base, hover_color, self.hover), pressed_color, self.pressed));
+                return sdf.result;
+            }
+        }
+    }
+
+    pub ConfirmDialog = {{ConfirmDialog}} {
+        width: Fill, height: Fill
+        flow: Overlay
+        visible: false
+
+        // Dimmed background; swallows clicks behind the dialog
+        overlay = <View> {
+            width: Fill, height: Fill
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.5);
+                }
+            }
+        }
+
+        dialog_container = <View> {
+            width: Fill, height: Fill
+            align: {x: 0.5, y: 0.5}
+
+            dialog = <View> {
+                width: 360, height: Fit
+                padding: 20
+                flow: Down
+                spacing: 12
+
+                show_bg: true
+                draw_bg: {
+                    instance radius: 10.0
+                    instance dark_mode: 0.0
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, self.radius);
+                        sdf.fill(mix((WHITE), (SLATE_800), self.dark_mode));
+                        return sdf.result;
+                    }
+                }
+
+                title = <Label> {
+                    text: "Are you sure?"
+                    draw_text: {
+                        instance dark_mode: 0.0
+                        text_style: <FONT_BOLD>{ font_size: 14.0 }
+                        fn get_color(self) -> vec4 {
+                            return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                        }
+                    }
+                }
+
+                message = <Label> {
+                    width: Fill
+                    text: ""
+                    draw_text: {
+                        instance dark_mode: 0.0
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                        wrap: Word
+                        fn get_color(self) -> vec4 {
+                            return mix((TEXT_SECONDARY), (TEXT_SECONDARY_DARK), self.dark_mode);
+                        }
+                    }
+                }
+
+                actions = <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {x: 1.0, y: 0.5}
+                    spacing: 8
+                    margin: {top: 4}
+
+                    cancel_button = <ConfirmDialogButton> {
+                        text: "Cancel"
+                    }
+                    confirm_button = <ConfirmDialogButton> {
+                        text: "Confirm"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return (WHITE);
+                            }
+                        }
+                        draw_bg: {
+                            fn pixel(self) -> vec4 {
+                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.border_radius);
+                                let color = mix(mix((RED_500), (RED_600), self.hover), (RED_700), self.pressed);
+                                sdf.fill(color);
+                                return sdf.result;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ConfirmDialogAction {
+    None,
+    /// The user confirmed; carries the tag passed to `show`
+    Confirmed(LiveId),
+    /// The user cancelled; carries the tag passed to `show`
+    Cancelled(LiveId),
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct ConfirmDialog {
+    #[deref]
+    view: View,
+
+    /// Identifies what is being confirmed
+    #[rust]
+    tag: LiveId,
+}
+
+impl Widget for ConfirmDialog {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.view.visible() {
+            return;
+        }
+        self.view.handle_event(cx, event, scope);
+
+        let actions = match event {
+            Event::Actions(actions) => actions.as_slice(),
+            _ => return,
+        };
+
+        let uid = self.widget_uid();
+        if self.view.button(ids!(dialog_container.dialog.actions.confirm_button)).clicked(actions) {
+            self.view.set_visible(cx, false);
+            cx.widget_action(uid, &scope.path, ConfirmDialogAction::Confirmed(self.tag));
+            self.view.redraw(cx);
+        }
+        if self.view.button(ids!(dialog_container.dialog.actions.cancel_button)).clicked(actions) {
+            self.view.set_visible(cx, false);
+            cx.widget_action(uid, &scope.path, ConfirmDialogAction::Cancelled(self.tag));
+            self.view.redraw(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl ConfirmDialogRef {
+    /// Show the dialog; `tag` is returned with the resulting action
+    pub fn show(&self, cx: &mut Cx, tag: LiveId, title: &str, message: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.tag = tag;
+            inner.view.label(ids!(dialog_container.dialog.title)).set_text(cx, title);
+            inner.view.label(ids!(dialog_container.dialog.message)).set_text(cx, message);
+            inner.view.set_visible(cx, true);
+            inner.view.redraw(cx);
+        }
+    }
+
+    /// Set the confirm button label (defaults to "Confirm")
+    pub fn set_confirm_text(&self, cx: &mut Cx, text: &str) {
+        if let Some(inner) = self.borrow() {
+            inner.view.button(ids!(dialog_container.dialog.actions.confirm_button)).set_text(cx, text);
+        }
+    }
+
+    /// Hide without emitting an action
+    pub fn hide(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.view.set_visible(cx, false);
+            inner.view.redraw(cx);
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.borrow().map(|inner| inner.view.visible()).unwrap_or(false)
+    }
+
+    /// Returns the tag if the user confirmed in these actions
+    pub fn confirmed(&self, actions: &Actions) -> Option<LiveId> {
+        let uid = self.widget_uid();
+        actions.iter().find_map(|action| {
+            action.as_widget_action().and_then(|wa| {
+                if wa.widget_uid != uid {
+                    return None;
+                }
+                match wa.cast() {
+                    ConfirmDialogAction::Confirmed(tag) => Some(tag),
+                    _ => None,
+                }
+            })
+        })
+    }
+
+    /// Update dark mode for the dialog
+    pub fn update_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.view.view(ids!(dialog_container.dialog)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+            inner.view.label(ids!(dialog_container.dialog.title)).apply_over(cx, live!{
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.label(ids!(dialog_container.dialog.message)).apply_over(cx, live!{
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.button(ids!(dialog_container.dialog.actions.cancel_button)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.redraw(cx);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Handing files and folders to the desktop
+//!
+//! Opens folders in the system file manager and files in their default app
+//! or the user's text editor, using the platform's opener (`open` on macOS,
+//! `xdg-open` on Linux, `explorer` / `notepad` on Windows). The opener runs
+//! detached, so these return as soon as it has been launched.
+//!
+//! [`move_to_trash`] is the exception: it waits for the platform to confirm
+//! the file is in the trash, and never falls back to deleting it.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Show a folder in the system file manager
+pub fn open_folder(path: &Path) -> Result<(), String> {
+    if !path.is_dir() {
+        return Err(format!("Folder not found: {}", path.display()));
+    }
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        Command::new("explorer")
+    } else {
+        Command::new("xdg-open")
+    };
+    spawn(command.arg(path))
+}
+
+/// Open a file with its default app
+pub fn open_file(path: &Path) -> Result<(), String> {
+    if !path.is_file() {
+        return Err(format!("File not found: {}", path.display()));
+    }
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut start = Command::new("cmd");
+        start.args(["/C", "start", ""]);
+        start
+    } else {
+        Command::new("xdg-open")
+    };
+    spawn(command.arg(path))
+}
+
+/// Show a file in the system file manager, selected where the platform
+/// supports it (Linux file managers only get its folder)
+pub fn reveal(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Err(format!("Not found: {}", path.display()));
+    }
+    if cfg!(target_os = "macos") {
+        spawn(Command::new("open").arg("-R").arg(path))
+    } else if cfg!(target_os = "windows") {
+        let mut select = std::ffi::OsString::from("/select,");
+        select.push(path);
+        spawn(Command::new("explorer").arg(select))
+    } else {
+        match path.parent() {
+            Some(parent) if path.is_file() => open_folder(parent),
+            _ => open_folder(path),
+        }
+    }
+}
+
+/// Open a file in the default text editor
+pub fn open_in_editor(path: &Path) -> Result<(), String> {
+    if !path.is_file() {
+        return Err(format!("File not found: {}", path.display()));
+    }
+    let mut command = if cfg!(target_os = "macos") {
+        let mut open = Command::new("open");
+        open.arg("-t");
+        open
+    } else if cfg!(target_os = "windows") {
+        Command::new("notepad")
+    } else {
+        Command::new("xdg-open")
+    };
+    spawn(command.arg(path))
+}
+
+/// Move a file to the trash / recycle bin (Finder on macOS, `gio trash` on
+/// Linux, the shell's recycle bin on Windows)
+pub fn move_to_trash(path: &Path) -> Result<(), String> {
+    if !path.is_file() {
+        return Err(format!("File not found: {}", path.display()));
+    }
+    let mut command = if cfg!(target_os = "macos") {
+        // The path is passed as an argument, so it needs no quoting
+        let mut osascript = Command::new("osascript");
+        osascript.args([
+            "-e",
+            "on run argv",
+            "-e",
+            "tell application \"Finder\" to delete (POSIX file (item 1 of argv) as alias)",
+            "-e",
+            "end run",
+        ]);
+        osascript.arg(path);
+        osascript
+    } else if cfg!(target_os = "windows") {
+        let mut powershell = Command::new("powershell");
+        powershell
+            .args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                "Add-Type -AssemblyName Microsoft.VisualBasic; \
+                 [Microsoft.VisualBasic.FileIO.FileSystem]::DeleteFile(\
+                 $env:MOFA_TRASH_PATH, 'OnlyErrorDialogs', 'SendToRecycleBin')",
+            ])
+            .env("MOFA_TRASH_PATH", path);
+        powershell
+    } else {
+        let mut gio = Command::new("gio");
+        gio.arg("trash").arg(path);
+        gio
+    };
+
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to launch {:?}: {}", command.get_program(), e))?;
+    if output.status.success() && !path.exists() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("Could not move {} to the trash: {}", path.display(), stderr.trim()))
+    }
+}
+
+fn spawn(command: &mut Command) -> Result<(), String> {
+    command
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to launch {:?}: {}", command.get_program(), e))
+}
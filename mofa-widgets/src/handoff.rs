@@ -0,0 +1,167 @@
+//! # Hand-off Bus
+//!
+//! Lets one app pass files to another inside the studio, e.g. the Converter
+//! sending a finished recording to the Transcriber. The sender posts a
+//! [`Handoff`] to [`HandoffBus::global`]; the shell polls
+//! [`announce`](HandoffBus::announce) to bring the target app forward, and the
+//! target [`take`](HandoffBus::take)s its hand-offs once it can accept files.
+//!
+//! ```rust,ignore
+//! use mofa_widgets::handoff::{Handoff, HandoffBus};
+//!
+//! HandoffBus::global().send(Handoff::new("mofa-converter", "mofa-transcriber", files));
+//! // in the Transcriber, once its page is ready:
+//! for handoff in HandoffBus::global().take("mofa-transcriber") { ... }
+//! ```
+
+use parking_lot::Mutex;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static GLOBAL: OnceLock<HandoffBus> = OnceLock::new();
+
+/// Files sent from one app to another
+#[derive(Debug, Clone, PartialEq)]
+pub struct Handoff {
+    /// Sending app, e.g. `mofa-converter`
+    pub from: String,
+    /// Receiving app, e.g. `mofa-transcriber`
+    pub to: String,
+    pub files: Vec<PathBuf>,
+}
+
+impl Handoff {
+    pub fn new(from: &str, to: &str, files: Vec<PathBuf>) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            files,
+        }
+    }
+}
+
+struct Pending {
+    handoff: Handoff,
+    announced: bool,
+}
+
+/// Hand-offs waiting for their target app
+#[derive(Default)]
+pub struct HandoffBus {
+    pending: Mutex<Vec<Pending>>,
+}
+
+impl HandoffBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The bus shared by all apps in the process
+    pub fn global() -> &'static HandoffBus {
+        GLOBAL.get_or_init(HandoffBus::new)
+    }
+
+    /// Queue files for another app; empty hand-offs are dropped
+    pub fn send(&self, handoff: Handoff) {
+        if handoff.files.is_empty() {
+            return;
+        }
+        self.pending.lock().push(Pending { handoff, announced: false });
+    }
+
+    /// Target apps of hand-offs sent since the last call, each once
+    pub fn announce(&self) -> Vec<String> {
+        let mut targets: Vec<String> = Vec::new();
+        for pending in self.pending.lock().iter_mut().filter(|p| !p.announced) {
+            pending.announced = true;
+            if !targets.contains(&pending.handoff.to) {
+                targets.push(pending.handoff.to.clone());
+            }
+        }
+        targets
+    }
+
+    /// Remove and return the hand-offs for `app_id`, oldest first
+    pub fn take(&self, app_id: &str) -> Vec<Handoff> {
+        let mut pending = self.pending.lock();
+        let (taken, kept): (Vec<Pending>, Vec<Pending>) = std::mem::take(&mut *pending)
+            .into_iter()
+            .partition(|p| p.handoff.to == app_id);
+        *pending = kept;
+        taken.into_iter().map(|p| p.handoff).collect()
+    }
+
+    /// Whether any hand-off is waiting for `app_id`
+    pub fn has_pending(&self, app_id: &str) -> bool {
+        self.pending.lock().iter().any(|p| p.handoff.to == app_id)
+    }
+}
+
+/// Make `file` available at `target` without touching the original: a hard
+/// link where the filesystem allows one, a copy otherwise
+pub fn link_or_copy(file: &Path, target: &Path) -> io::Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let _ = fs::remove_file(target);
+    if fs::hard_link(file, target).is_ok() {
+        return Ok(());
+    }
+    fs::copy(file, target).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn test_announce_names_each_new_target_once() {
+        let bus = HandoffBus::new();
+        bus.send(Handoff::new("mofa-converter", "mofa-transcriber", files(&["a.mp3"])));
+        bus.send(Handoff::new("mofa-converter", "mofa-transcriber", files(&["b.mp3"])));
+        bus.send(Handoff::new("mofa-converter", "mofa-transcriber", Vec::new()));
+
+        assert_eq!(bus.announce(), ["mofa-transcriber"]);
+        assert!(bus.announce().is_empty());
+        assert!(bus.has_pending("mofa-transcriber"));
+    }
+
+    #[test]
+    fn test_take_only_returns_the_target_apps_handoffs() {
+        let bus = HandoffBus::new();
+        bus.send(Handoff::new("mofa-converter", "mofa-transcriber", files(&["a.mp3"])));
+        bus.send(Handoff::new("mofa-converter", "mofa-note-taker", files(&["b.txt"])));
+        bus.send(Handoff::new("mofa-podcast", "mofa-transcriber", files(&["c.mp3"])));
+
+        let taken = bus.take("mofa-transcriber");
+        assert_eq!(taken.len(), 2);
+        assert_eq!(taken[0].files, files(&["a.mp3"]));
+        assert_eq!(taken[1].from, "mofa-podcast");
+        assert!(!bus.has_pending("mofa-transcriber"));
+        assert!(bus.has_pending("mofa-note-taker"));
+    }
+
+    #[test]
+    fn test_link_or_copy_replaces_the_target() {
+        let dir = std::env::temp_dir().join(format!("mofa-handoff-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("talk.mp3");
+        fs::write(&file, "new").unwrap();
+        let target = dir.join("inbox").join("1-talk.mp3");
+        fs::create_dir_all(target.parent().unwrap()).unwrap();
+        fs::write(&target, "old").unwrap();
+
+        link_or_copy(&file, &target).unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+        assert!(file.exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,297 @@
+//! # Background Jobs
+//!
+//! A process-wide registry of long-running work (podcast rendering, model
+//! downloads, batch conversions) so the shell can show it outside the app
+//! that started it: the window title gets a progress summary and, on macOS,
+//! the dock badge counts active jobs.
+//!
+//! Apps register work through [`JobRegistry::global`] and keep the returned
+//! [`JobHandle`] for as long as the work runs. The handle is `Send + Sync`,
+//! so it can be moved to a worker thread or shared with a progress callback.
+//!
+//! ```rust,ignore
+//! use mofa_widgets::jobs::JobRegistry;
+//!
+//! let job = JobRegistry::global().start("mofa-podcast", "rendering");
+//! job.set_progress(0.62);
+//! // ...
+//! job.finish(); // dropping the handle instead counts as cancelled
+//! ```
+//!
+//! Jobs that can tell how long they still need report it with
+//! [`set_remaining`](JobHandle::set_remaining); the summary then reads
+//! "rendering 62%, ~4 min left".
+//!
+//! Work that can be interrupted marks its handle
+//! [`cancellable`](JobHandle::cancellable) and checks
+//! [`is_cancelled`](JobHandle::is_cancelled); the shell then offers to cancel
+//! it through [`JobRegistry::cancel`].
+
+use parking_lot::Mutex;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+static GLOBAL: OnceLock<JobRegistry> = OnceLock::new();
+
+/// A registered job as seen by observers
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobInfo {
+    pub id: u64,
+    /// App that started the job, e.g. `mofa-podcast`
+    pub app_id: String,
+    /// What the job is doing, shown in the title: "rendering", "downloading"
+    pub verb: String,
+    /// Fraction done (0.0 - 1.0), `None` until the job reports any
+    pub progress: Option<f32>,
+    /// Estimated seconds left, `None` when the job cannot tell
+    pub remaining_secs: Option<f64>,
+    pub started: Instant,
+    /// Whether the job checks for [`JobRegistry::cancel`]
+    pub cancellable: bool,
+}
+
+#[derive(Default)]
+struct Inner {
+    next_id: u64,
+    jobs: Vec<JobInfo>,
+    /// Ids of jobs asked to cancel that have not stopped yet
+    cancelled: Vec<u64>,
+    finished: u64,
+}
+
+/// Tracks running jobs and how many have finished
+#[derive(Default)]
+pub struct JobRegistry {
+    inner: Mutex<Inner>,
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The registry shared by all apps in the process
+    pub fn global() -> &'static JobRegistry {
+        GLOBAL.get_or_init(JobRegistry::new)
+    }
+
+    /// Register a job; it stays active until the handle is finished or dropped
+    pub fn start(&'static self, app_id: &str, verb: &str) -> JobHandle {
+        let mut inner = self.inner.lock();
+        inner.next_id += 1;
+        let id = inner.next_id;
+        inner.jobs.push(JobInfo {
+            id,
+            app_id: app_id.to_string(),
+            verb: verb.to_string(),
+            progress: None,
+            remaining_secs: None,
+            started: Instant::now(),
+            cancellable: false,
+        });
+        JobHandle { registry: self, id }
+    }
+
+    /// Active jobs, oldest first
+    pub fn active(&self) -> Vec<JobInfo> {
+        self.inner.lock().jobs.clone()
+    }
+
+    /// Jobs finished successfully since startup; grows by one per finish
+    pub fn finished_count(&self) -> u64 {
+        self.inner.lock().finished
+    }
+
+    /// Short progress summary such as "rendering 62%", `None` when idle
+    pub fn summary(&self) -> Option<String> {
+        summarize(&self.inner.lock().jobs)
+    }
+
+    /// Ask a cancellable job to stop; `false` if it is gone or cannot be
+    /// cancelled
+    pub fn cancel(&self, id: u64) -> bool {
+        let mut inner = self.inner.lock();
+        if !inner.jobs.iter().any(|j| j.id == id && j.cancellable) {
+            return false;
+        }
+        if !inner.cancelled.contains(&id) {
+            inner.cancelled.push(id);
+        }
+        true
+    }
+
+    fn set_cancellable(&self, id: u64) {
+        if let Some(job) = self.inner.lock().jobs.iter_mut().find(|j| j.id == id) {
+            job.cancellable = true;
+        }
+    }
+
+    fn is_cancelled(&self, id: u64) -> bool {
+        self.inner.lock().cancelled.contains(&id)
+    }
+
+    fn set_progress(&self, id: u64, progress: f32) {
+        let mut inner = self.inner.lock();
+        if let Some(job) = inner.jobs.iter_mut().find(|j| j.id == id) {
+            job.progress = Some(progress.clamp(0.0, 1.0));
+        }
+    }
+
+    fn set_remaining(&self, id: u64, secs: f64) {
+        let mut inner = self.inner.lock();
+        if let Some(job) = inner.jobs.iter_mut().find(|j| j.id == id) {
+            job.remaining_secs = (secs.is_finite() && secs >= 0.0).then_some(secs);
+        }
+    }
+
+    fn remove(&self, id: u64, finished: bool) {
+        let mut inner = self.inner.lock();
+        let before = inner.jobs.len();
+        inner.jobs.retain(|j| j.id != id);
+        inner.cancelled.retain(|&c| c != id);
+        if finished && inner.jobs.len() < before {
+            inner.finished += 1;
+        }
+    }
+}
+
+/// Keeps a job registered; dropping it without [`finish`](Self::finish)
+/// removes the job as cancelled or failed
+pub struct JobHandle {
+    registry: &'static JobRegistry,
+    id: u64,
+}
+
+impl JobHandle {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Report progress as a fraction (clamped to 0.0 - 1.0)
+    pub fn set_progress(&self, progress: f32) {
+        self.registry.set_progress(self.id, progress);
+    }
+
+    /// Report the estimated time left, in seconds
+    pub fn set_remaining(&self, secs: f64) {
+        self.registry.set_remaining(self.id, secs);
+    }
+
+    /// Let the shell offer to cancel the job; the owner must check
+    /// [`is_cancelled`](Self::is_cancelled)
+    pub fn cancellable(self) -> Self {
+        self.registry.set_cancellable(self.id);
+        self
+    }
+
+    /// Whether the job was asked to stop
+    pub fn is_cancelled(&self) -> bool {
+        self.registry.is_cancelled(self.id)
+    }
+
+    /// Mark the job as completed successfully
+    pub fn finish(&self) {
+        self.registry.remove(self.id, true);
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        self.registry.remove(self.id, false);
+    }
+}
+
+/// The oldest job's verb and progress, plus a count of the others
+fn summarize(jobs: &[JobInfo]) -> Option<String> {
+    let first = jobs.first()?;
+    let mut summary = match first.progress {
+        Some(p) => format!("{} {}%", first.verb, (p * 100.0).round() as u32),
+        None => first.verb.clone(),
+    };
+    if let Some(secs) = first.remaining_secs {
+        summary.push_str(&format!(", {} left", format_remaining(secs)));
+    }
+    if jobs.len() > 1 {
+        summary.push_str(&format!(", +{} more", jobs.len() - 1));
+    }
+    Some(summary)
+}
+
+/// Rough time left, e.g. "~6 min", "< 1 min" or "~1 h 20 min"
+pub fn format_remaining(secs: f64) -> String {
+    let minutes = (secs / 60.0).round() as u64;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => "< 1 min".to_string(),
+        (0, m) => format!("~{} min", m),
+        (h, 0) => format!("~{} h", h),
+        (h, m) => format!("~{} h {} min", h, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> &'static JobRegistry {
+        Box::leak(Box::new(JobRegistry::new()))
+    }
+
+    #[test]
+    fn test_summary() {
+        let registry = registry();
+        assert_eq!(registry.summary(), None);
+
+        let render = registry.start("mofa-podcast", "rendering");
+        assert_eq!(registry.summary().as_deref(), Some("rendering"));
+        render.set_progress(0.624);
+        assert_eq!(registry.summary().as_deref(), Some("rendering 62%"));
+        render.set_remaining(250.0);
+        assert_eq!(registry.summary().as_deref(), Some("rendering 62%, ~4 min left"));
+
+        let _download = registry.start("mofa-settings", "downloading");
+        assert_eq!(registry.summary().as_deref(), Some("rendering 62%, ~4 min left, +1 more"));
+
+        drop(render);
+        assert_eq!(registry.summary().as_deref(), Some("downloading"));
+    }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(20.0), "< 1 min");
+        assert_eq!(format_remaining(370.0), "~6 min");
+        assert_eq!(format_remaining(3600.0), "~1 h");
+        assert_eq!(format_remaining(4800.0), "~1 h 20 min");
+    }
+
+    #[test]
+    fn test_finish_counts_only_completed_jobs() {
+        let registry = registry();
+        let done = registry.start("mofa-converter", "converting");
+        let cancelled = registry.start("mofa-converter", "converting");
+        assert_eq!(registry.active().len(), 2);
+
+        done.finish();
+        drop(done);
+        drop(cancelled);
+        assert!(registry.active().is_empty());
+        assert_eq!(registry.finished_count(), 1);
+    }
+
+    #[test]
+    fn test_cancel_needs_cancellable_job() {
+        let registry = registry();
+        let fixed = registry.start("mofa-podcast", "rendering");
+        let download = registry.start("mofa-settings", "downloading").cancellable();
+        assert!(registry.active()[1].cancellable);
+
+        assert!(!registry.cancel(fixed.id()));
+        assert!(!fixed.is_cancelled());
+        assert!(registry.cancel(download.id()));
+        assert!(download.is_cancelled());
+
+        let id = download.id();
+        drop(download);
+        assert!(!registry.cancel(id));
+        assert_eq!(registry.finished_count(), 0);
+    }
+}
@@ -23,12 +23,30 @@
 //! ## Modules
 //!
 //! - [`theme`] - Color palette, fonts, and dark mode support
+//! - [`activity`] - Registry of running servers, WebViews and timers
 //! - [`app_trait`] - Plugin app interface (`MofaApp`, `AppRegistry`)
+//! - [`app_storage`] - Per-app data locations and data reset
 //! - [`participant_panel`] - User avatar with audio waveform
 //! - [`waveform_view`] - Real-time audio waveform visualization
 //! - [`log_panel`] - Scrollable Markdown log display
 //! - [`led_gauge`] - LED-style bar gauge for levels
+//! - [`audio_export`] - Joining WAV chunks and encoding to MP3/M4A/AIFF
 //! - [`audio_player`] - Audio playback engine
+//! - [`audio_tags`] - ID3 metadata for exported audio files
+//! - [`confirm_dialog`] - Modal confirmation dialog
+//! - [`desktop`] - Opening folders and files in the desktop's apps
+//! - [`handoff`] - Passing files from one app to another
+//! - [`jobs`] - Registry of long-running background jobs
+//! - [`local_server`] - Localhost HTTP server for app and plugin APIs
+//! - [`path_picker`] - File/folder picker row with recent paths
+//! - [`safe_mode`] - Starting without plugins, servers and WebViews
+//! - [`searchable_drop_down`] - Dropdown with a filter field for long lists
+//! - [`shortcuts`] - Keyboard shortcuts scoped to the app in front
+//! - [`tab_state`] - Scroll offsets and focus kept while a tab is hidden
+//! - [`tour`] - Onboarding tour steps and spotlight placement
+//! - [`tts`] - Text-to-speech backends for reading text aloud
+//! - [`usage`] - Token usage ledger shared by apps calling chat providers
+//! - [`voices`] - Voice catalogue across TTS backends, favorites and previews
 //!
 //! ## Theme System
 //!
@@ -74,18 +92,38 @@
 //! }
 //! ```
 
+pub mod activity;
+pub mod app_storage;
 pub mod app_trait;
+pub mod audio_export;
 pub mod audio_player;
+pub mod audio_tags;
+pub mod confirm_dialog;
+pub mod desktop;
+pub mod diagnostic_bundle;
+pub mod handoff;
+pub mod jobs;
 pub mod led_gauge;
+pub mod local_server;
 pub mod log_panel;
 pub mod participant_panel;
+pub mod path_picker;
 pub mod plugins;
+pub mod safe_mode;
+pub mod searchable_drop_down;
+pub mod shortcuts;
+pub mod tab_state;
 pub mod theme;
+pub mod tour;
+pub mod tts;
+pub mod usage;
+pub mod voices;
 pub mod waveform_view;
 pub mod webview;
 
 // Re-export app trait types for convenience
-pub use app_trait::{AppInfo, AppRegistry, MofaApp, PageId, PageRouter, StateChangeListener, TimerControl, tab_clicked};
+pub use app_trait::{AppInfo, AppRegistry, MofaApp, PageId, PageRouter, StateChangeListener, TabLifecycle, TimerControl, tab_clicked};
+pub use tour::{Tour, TourBuilder, TourStep};
 
 use makepad_widgets::Cx;
 
@@ -114,6 +152,9 @@ use makepad_widgets::Cx;
 /// 4. `log_panel` - Log display
 /// 5. `led_gauge` - Level indicators
 /// 6. `webview` - WebView container for embedding web content
+/// 7. `confirm_dialog` - Modal confirmation dialog
+/// 8. `path_picker` - File/folder picker row
+/// 9. `searchable_drop_down` - Dropdown with a filter field
 pub fn live_design(cx: &mut Cx) {
     // Theme provides fonts and base styles - must be first
     theme::live_design(cx);
@@ -124,6 +165,9 @@ pub fn live_design(cx: &mut Cx) {
     log_panel::live_design(cx);
     led_gauge::live_design(cx);
     webview::live_design(cx);
+    confirm_dialog::live_design(cx);
+    path_picker::live_design(cx);
+    searchable_drop_down::live_design(cx);
     plugins::live_design(cx);
 }
 
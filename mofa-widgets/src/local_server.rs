@@ -0,0 +1,634 @@
+//! # Local HTTP Server
+//!
+//! Minimal blocking HTTP/1.1 server bound to `127.0.0.1` on a random port.
+//! Used by Rust-backed WebView apps to serve their pages and JSON APIs, and by
+//! the plugin control API.
+//!
+//! Each connection is handled on its own thread and carries one request;
+//! responses are sent with `Connection: close`. Routing is up to the
+//! caller's handler. Only servers on a loopback address without a
+//! [`TokenAuth`] answer with `Access-Control-Allow-Origin: *`.
+//!
+//! Servers bound to another address are reachable from the network and
+//! should be started with [`LocalServer::start_authorized`], which checks
+//! each request's [`TokenAuth`] before reading its body. The token is
+//! accepted as `Authorization: Bearer <token>` or, for page loads that
+//! cannot set headers, as an `access_token` query parameter. The WebView
+//! keeps the token from the page URL and adds the header to the page's own
+//! `fetch` and `XMLHttpRequest` calls; the first authorized response also
+//! sets an `HttpOnly` session cookie, so `<script>`, `<link>` and `<img>`
+//! subresources are authorized too.
+//!
+//! Request heads are limited to [`MAX_HEAD_BYTES`] and bodies to
+//! [`MAX_BODY_BYTES`]; larger bodies get `413` without being read.
+//!
+//! ```rust,ignore
+//! use mofa_widgets::local_server::{HttpResponse, LocalServer};
+//!
+//! let mut server = LocalServer::default();
+//! let port = server.start(|req| match (req.method.as_str(), req.path.as_str()) {
+//!     ("GET", "/api/ping") => HttpResponse::json("200 OK", &serde_json::json!({ "ok": true })),
+//!     _ => HttpResponse::not_found(),
+//! })?;
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, TcpListener, TcpStream};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+/// Query parameter carrying the token on page loads
+pub const TOKEN_QUERY_PARAM: &str = "access_token";
+
+/// Largest request body read; larger ones are rejected with `413`
+pub const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Largest request line plus headers
+pub const MAX_HEAD_BYTES: u64 = 64 * 1024;
+
+/// A parsed HTTP request
+#[derive(Debug, Clone, Default)]
+pub struct HttpRequest {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Raw query string (without `?`), empty if none
+    pub query: String,
+    /// Header names are lowercased
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Look up a header by case-insensitive name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_ascii_lowercase();
+        self.headers
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Token from an `Authorization: Bearer <token>` header
+    pub fn bearer_token(&self) -> Option<&str> {
+        let value = self.header("authorization")?;
+        let (scheme, token) = value.split_once(' ')?;
+        if scheme.eq_ignore_ascii_case("bearer") {
+            Some(token.trim())
+        } else {
+            None
+        }
+    }
+
+    /// Value of a query parameter (not percent-decoded)
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Value of a cookie from the `Cookie` header
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.header("cookie")?
+            .split(';')
+            .filter_map(|pair| pair.trim().split_once('='))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Body length announced by `Content-Length` (0 if missing or invalid)
+    pub fn content_length(&self) -> usize {
+        self.header("content-length")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0)
+    }
+}
+
+/// An HTTP response produced by a handler
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// Status line, e.g. `"200 OK"`
+    pub status: &'static str,
+    pub content_type: &'static str,
+    /// Headers besides the content type, length and connection
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: &'static str, content_type: &'static str, body: Vec<u8>) -> Self {
+        Self { status, content_type, headers: Vec::new(), body }
+    }
+
+    pub fn with_header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// JSON response; serialization failures fall back to `{}`
+    pub fn json(status: &'static str, value: &serde_json::Value) -> Self {
+        let body = serde_json::to_vec(value).unwrap_or_else(|_| b"{}".to_vec());
+        Self::new(status, "application/json; charset=utf-8", body)
+    }
+
+    /// Plain text response
+    pub fn text(status: &'static str, text: &str) -> Self {
+        Self::new(status, "text/plain; charset=utf-8", text.as_bytes().to_vec())
+    }
+
+    pub fn not_found() -> Self {
+        Self::text("404 Not Found", "Not Found")
+    }
+
+    pub fn method_not_allowed() -> Self {
+        Self::text("405 Method Not Allowed", "Method Not Allowed")
+    }
+
+    pub fn unauthorized() -> Self {
+        Self::json("401 Unauthorized", &serde_json::json!({ "error": "missing or invalid token" }))
+    }
+
+    pub fn payload_too_large() -> Self {
+        Self::json(
+            "413 Payload Too Large",
+            &serde_json::json!({ "error": format!("request body over {} bytes", MAX_BODY_BYTES) }),
+        )
+    }
+}
+
+/// Generate an unguessable token: 16 bytes from the OS random number
+/// generator, hex-encoded
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).expect("OS random number generator unavailable");
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Compare secrets without returning early on the first differing byte
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Bearer token check for servers reachable from the network
+#[derive(Debug, Clone)]
+pub struct TokenAuth {
+    token: String,
+    /// Paths served without a token, e.g. `/health`
+    exempt: Vec<String>,
+}
+
+impl TokenAuth {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self { token: token.into(), exempt: Vec::new() }
+    }
+
+    /// A fresh token for this session
+    pub fn generate() -> Self {
+        Self::new(generate_token())
+    }
+
+    /// Serve `path` without a token
+    pub fn exempt(mut self, path: &str) -> Self {
+        self.exempt.push(path.to_string());
+        self
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Whether the request carries the token as a bearer header, query
+    /// parameter or session cookie (or needs none)
+    pub fn is_authorized(&self, request: &HttpRequest) -> bool {
+        if self.exempt.contains(&request.path) {
+            return true;
+        }
+        let matches = |token: &str| constant_time_eq(token.as_bytes(), self.token.as_bytes());
+        request.bearer_token().is_some_and(matches)
+            || request.query_param(TOKEN_QUERY_PARAM).is_some_and(matches)
+            || request.cookie(&self.cookie_name()).is_some_and(matches)
+    }
+
+    /// Session cookie name; derived from the token so servers sharing a
+    /// host (cookies ignore the port) do not overwrite each other's cookie
+    pub fn cookie_name(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        hasher.write(self.token.as_bytes());
+        format!("mofa_session_{:016x}", hasher.finish())
+    }
+
+    /// `Set-Cookie` value for an authorized request that does not carry the
+    /// session cookie yet
+    pub fn session_cookie(&self, request: &HttpRequest) -> Option<String> {
+        let name = self.cookie_name();
+        if request.cookie(&name).is_some_and(|c| constant_time_eq(c.as_bytes(), self.token.as_bytes())) {
+            return None;
+        }
+        Some(format!("{}={}; Path=/; HttpOnly; SameSite=Strict", name, self.token))
+    }
+
+    /// `url` with the token appended, for loading pages in the WebView
+    pub fn url_with_token(&self, url: &str) -> String {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        format!("{}{}{}={}", url, separator, TOKEN_QUERY_PARAM, self.token)
+    }
+}
+
+/// Request handler shared with the server thread
+pub type RequestHandler = Arc<dyn Fn(&HttpRequest) -> HttpResponse + Send + Sync>;
+
+/// Read one request from the stream (`Ok(None)` if the peer sent nothing).
+/// Fails with `InvalidData` when the body is over [`MAX_BODY_BYTES`].
+pub fn read_request(reader: &mut impl BufRead) -> std::io::Result<Option<HttpRequest>> {
+    let Some(mut request) = read_head(reader)? else {
+        return Ok(None);
+    };
+    read_body(reader, &mut request)?;
+    Ok(Some(request))
+}
+
+/// Read the request line and headers, leaving the body unread
+pub fn read_head(reader: &mut impl BufRead) -> std::io::Result<Option<HttpRequest>> {
+    let mut reader = reader.take(MAX_HEAD_BYTES);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line)? == 0 {
+        return Ok(None);
+    }
+    if request_line.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let raw_path = parts.next().unwrap_or("/");
+    let (path, query) = match raw_path.split_once('?') {
+        Some((path, query)) => (path.to_string(), query.to_string()),
+        None => (raw_path.to_string(), String::new()),
+    };
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        let n = reader.read_line(&mut line)?;
+        if n == 0 {
+            if reader.limit() == 0 {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "request head too large"));
+            }
+            break;
+        }
+        let trimmed = line.trim_end_matches(&['\r', '\n'][..]);
+        if trimmed.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = trimmed.split_once(':') {
+            headers.push((key.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    Ok(Some(HttpRequest { method, path, query, headers, body: Vec::new() }))
+}
+
+/// Read the body announced by the request's `Content-Length`, refusing
+/// anything over [`MAX_BODY_BYTES`] before allocating
+pub fn read_body(reader: &mut impl BufRead, request: &mut HttpRequest) -> std::io::Result<()> {
+    let length = request.content_length();
+    if length > MAX_BODY_BYTES {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "request body too large"));
+    }
+    let mut body = vec![0u8; length];
+    reader.read_exact(&mut body)?;
+    request.body = body;
+    Ok(())
+}
+
+/// Write a response with `Connection: close`
+pub fn write_response(stream: &mut impl Write, response: &HttpResponse) -> std::io::Result<()> {
+    let mut header = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        header.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    header.push_str("\r\n");
+    stream.write_all(header.as_bytes())?;
+    stream.write_all(&response.body)?;
+    Ok(())
+}
+
+/// State every connection of a server shares
+struct ServerShared {
+    handler: RequestHandler,
+    auth: Option<TokenAuth>,
+    /// Send `Access-Control-Allow-Origin: *`; only for unauthenticated
+    /// loopback servers
+    allow_any_origin: bool,
+}
+
+impl ServerShared {
+    fn respond(&self, stream: &mut TcpStream, mut response: HttpResponse) {
+        if self.allow_any_origin {
+            response.headers.push(("Access-Control-Allow-Origin".to_string(), "*".to_string()));
+        }
+        let _ = write_response(stream, &response);
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, shared: &ServerShared) {
+    // Accepted sockets may inherit non-blocking mode from the listener
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+    let _ = stream.set_write_timeout(Some(Duration::from_secs(5)));
+
+    let mut reader = match stream.try_clone() {
+        Ok(s) => BufReader::new(s),
+        Err(_) => return,
+    };
+
+    let mut request = match read_head(&mut reader) {
+        Ok(Some(req)) => req,
+        _ => return,
+    };
+
+    // Unauthenticated peers never get to make the server read a body
+    let auth = shared.auth.as_ref();
+    if auth.is_some_and(|auth| !auth.is_authorized(&request)) {
+        shared.respond(&mut stream, HttpResponse::unauthorized());
+        return;
+    }
+    if request.content_length() > MAX_BODY_BYTES {
+        shared.respond(&mut stream, HttpResponse::payload_too_large());
+        return;
+    }
+    if read_body(&mut reader, &mut request).is_err() {
+        return;
+    }
+
+    let mut response = (shared.handler)(&request);
+    if let Some(cookie) = auth
+        .filter(|_| response.status.starts_with('2'))
+        .and_then(|auth| auth.session_cookie(&request))
+    {
+        response.headers.push(("Set-Cookie".to_string(), cookie));
+    }
+    shared.respond(&mut stream, response);
+}
+
+fn server_loop(listener: TcpListener, shutdown_rx: mpsc::Receiver<()>, shared: Arc<ServerShared>) {
+    loop {
+        if shutdown_rx.try_recv().is_ok() {
+            break;
+        }
+
+        match listener.accept() {
+            Ok((stream, _)) => {
+                // A slow or idle peer must not hold up the others
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || handle_connection(stream, &shared));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(30));
+            }
+            Err(_) => break,
+        }
+    }
+}
+
+/// Background localhost server with start/stop lifecycle
+#[derive(Default)]
+pub struct LocalServer {
+    handle: Option<thread::JoinHandle<()>>,
+    shutdown: Option<mpsc::Sender<()>>,
+    port: u16,
+}
+
+impl LocalServer {
+    pub fn is_running(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    /// Bind a random localhost port and serve requests with `handler` until stopped
+    pub fn start<F>(&mut self, handler: F) -> Result<u16, String>
+    where
+        F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    {
+        self.start_on(IpAddr::V4(Ipv4Addr::LOCALHOST), handler)
+    }
+
+    /// Like [`start`](Self::start), bound to `addr` (e.g. `0.0.0.0` for LAN access)
+    pub fn start_on<F>(&mut self, addr: IpAddr, handler: F) -> Result<u16, String>
+    where
+        F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    {
+        self.start_authorized(addr, None, handler)
+    }
+
+    /// Like [`start_on`](Self::start_on), answering requests that fail
+    /// `auth` with `401` before their body is read
+    pub fn start_authorized<F>(&mut self, addr: IpAddr, auth: Option<TokenAuth>, handler: F) -> Result<u16, String>
+    where
+        F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    {
+        if self.handle.is_some() {
+            return Ok(self.port);
+        }
+
+        let listener = TcpListener::bind((addr, 0))
+            .map_err(|e| format!("Failed to bind server: {}", e))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to read port: {}", e))?
+            .port();
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| format!("Failed to set non-blocking: {}", e))?;
+
+        let shared = Arc::new(ServerShared {
+            handler: Arc::new(handler),
+            allow_any_origin: auth.is_none() && addr.is_loopback(),
+            auth,
+        });
+        let (tx, rx) = mpsc::channel();
+        let handle = thread::spawn(move || server_loop(listener, rx, shared));
+
+        self.shutdown = Some(tx);
+        self.handle = Some(handle);
+        self.port = port;
+
+        Ok(port)
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(tx) = self.shutdown.take() {
+            let _ = tx.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        self.port = 0;
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Local URL of the server; also valid when bound to all interfaces
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+}
+
+impl Drop for LocalServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_read_request_parses_headers_query_and_body() {
+        let raw = "POST /notify?x=1 HTTP/1.1\r\nAuthorization: Bearer abc\r\nContent-Length: 4\r\n\r\nping";
+        let mut reader = BufReader::new(raw.as_bytes());
+        let req = read_request(&mut reader).unwrap().unwrap();
+
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/notify");
+        assert_eq!(req.query, "x=1");
+        assert_eq!(req.header("AUTHORIZATION"), Some("Bearer abc"));
+        assert_eq!(req.bearer_token(), Some("abc"));
+        assert_eq!(req.body, b"ping");
+    }
+
+    #[test]
+    fn test_server_round_trip() {
+        let mut server = LocalServer::default();
+        let port = server
+            .start(|req| HttpResponse::text("200 OK", &format!("{} {}", req.method, req.path)))
+            .unwrap();
+
+        // An idle connection does not hold up the next one
+        let _idle = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        stream.write_all(b"GET /hello HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("Access-Control-Allow-Origin: *\r\n"));
+        assert!(response.ends_with("GET /hello"));
+        server.stop();
+        assert!(!server.is_running());
+    }
+
+    fn request(path: &str, query: &str, authorization: Option<&str>) -> HttpRequest {
+        HttpRequest {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            query: query.to_string(),
+            headers: authorization
+                .map(|value| vec![("authorization".to_string(), value.to_string())])
+                .unwrap_or_default(),
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_token_auth() {
+        let auth = TokenAuth::new("s3cret").exempt("/health");
+
+        // Missing, wrong and valid tokens
+        assert!(!auth.is_authorized(&request("/api/notes", "", None)));
+        assert!(!auth.is_authorized(&request("/api/notes", "", Some("Bearer s3cre"))));
+        assert!(!auth.is_authorized(&request("/api/notes", "", Some("Basic s3cret"))));
+        assert!(!auth.is_authorized(&request("/", "access_token=wrong", None)));
+        assert!(auth.is_authorized(&request("/api/notes", "", Some("Bearer s3cret"))));
+        assert!(auth.is_authorized(&request("/", "x=1&access_token=s3cret", None)));
+
+        // Exempt paths need no token
+        assert!(auth.is_authorized(&request("/health", "", None)));
+
+        // The session cookie set after a page load authorizes subresources
+        let page = request("/", "access_token=s3cret", None);
+        let cookie = auth.session_cookie(&page).unwrap();
+        assert!(cookie.ends_with("; Path=/; HttpOnly; SameSite=Strict"));
+        let mut script = request("/assets/app.js", "", None);
+        let pair = cookie.split(';').next().unwrap().to_string();
+        script.headers.push(("cookie".to_string(), format!("theme=dark; {}", pair)));
+        assert!(auth.is_authorized(&script));
+        assert!(auth.session_cookie(&script).is_none());
+        let other = TokenAuth::new("other");
+        assert_ne!(other.cookie_name(), auth.cookie_name());
+        assert!(!other.is_authorized(&script));
+
+        assert_eq!(auth.url_with_token("http://127.0.0.1:80/"), "http://127.0.0.1:80/?access_token=s3cret");
+        let token = TokenAuth::generate();
+        assert_eq!(token.token().len(), 32);
+        assert!(token.token().bytes().all(|b| b.is_ascii_hexdigit()));
+        assert_ne!(token.token(), TokenAuth::generate().token());
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"abcd"));
+        assert!(constant_time_eq(b"", b""));
+    }
+
+    #[test]
+    fn test_oversized_body_is_refused() {
+        let raw = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY_BYTES + 1);
+        let mut reader = BufReader::new(raw.as_bytes());
+        assert!(read_request(&mut reader).is_err());
+
+        let huge_head = format!("GET / HTTP/1.1\r\nX-Pad: {}\r\n\r\n", "a".repeat(MAX_HEAD_BYTES as usize));
+        assert!(read_request(&mut BufReader::new(huge_head.as_bytes())).is_err());
+    }
+
+    #[test]
+    fn test_unauthorized_request_gets_401() {
+        let auth = TokenAuth::new("s3cret").exempt("/health");
+        let mut server = LocalServer::default();
+        let port = server
+            .start_authorized(IpAddr::V4(Ipv4Addr::LOCALHOST), Some(auth.clone()), |_| {
+                HttpResponse::text("200 OK", "ok")
+            })
+            .unwrap();
+
+        let get = |head: &str| {
+            let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+            stream.write_all(head.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        assert!(get("GET /api HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 401"));
+        assert!(get("GET /api HTTP/1.1\r\nAuthorization: Bearer s3cret\r\n\r\n").starts_with("HTTP/1.1 200"));
+        assert!(get("GET /health HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 200"));
+        assert!(!get("GET /health HTTP/1.1\r\n\r\n").contains("Access-Control-Allow-Origin"));
+
+        // Refused before the (never sent) body would be read
+        assert!(get("POST /api HTTP/1.1\r\nContent-Length: 4000000000\r\n\r\n").starts_with("HTTP/1.1 401"));
+        assert!(get("POST /api HTTP/1.1\r\nAuthorization: Bearer s3cret\r\nContent-Length: 4000000000\r\n\r\n")
+            .starts_with("HTTP/1.1 413"));
+
+        let page = get("GET /?access_token=s3cret HTTP/1.1\r\n\r\n");
+        assert!(page.contains(&format!("Set-Cookie: {}=s3cret;", auth.cookie_name())));
+        server.stop();
+    }
+}
@@ -0,0 +1,584 @@
+//! # Path Picker Widget
+//!
+//! A labelled "path + Browse" row for choosing a file or folder. The path is
+//! shown with an ellipsis in the middle so both the root and the file name
+//! stay visible, the selection is validated inline (exists, right kind,
+//! writable) with an error tint, and recently used paths are offered in a
+//! dropdown. Recent paths are kept per `picker_id` in
+//! [`RECENT_PATHS_FILE`] under the MoFA Studio data directory.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! live_design! {
+//!     use mofa_widgets::path_picker::PathPicker;
+//!
+//!     output_picker = <PathPicker> {
+//!         picker_id: "podcast-output"
+//!         mode: Folder
+//!         label = { text: "Output folder" }
+//!     }
+//! }
+//!
+//! // Restore the saved value (does not emit an action)
+//! self.view.path_picker(ids!(output_picker)).set_path(cx, Some(&saved_dir));
+//!
+//! // React to the user picking something
+//! if let Some(path) = self.view.path_picker(ids!(output_picker)).path_changed(actions) {
+//!     ...
+//! }
+//! ```
+
+use crate::app_storage;
+use makepad_widgets::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Recent paths of every picker, relative to the MoFA Studio data directory
+pub const RECENT_PATHS_FILE: &str = "path-picker/recent.json";
+
+/// How many recent paths are remembered per picker
+pub const MAX_RECENT_PATHS: usize = 8;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::theme::*;
+
+    PathPickerButton = <Button> {
+        width: Fit, height: 30
+        padding: {left: 12, right: 12}
+        draw_bg: {
+            instance dark_mode: 0.0
+            instance hover: 0.0
+            instance pressed: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 4.0);
+                let base = mix((SLATE_100), (SLATE_700), self.dark_mode);
+                let hover_color = mix((SLATE_200), (SLATE_600), self.dark_mode);
+                let pressed_color = mix((SLATE_300), (SLATE_500), self.dark_mode);
+                sdf.fill(mix(mix(base, hover_color, self.hover), pressed_color, self.pressed));
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+            fn get_color(self) -> vec4 {
+                return mix((GRAY_700), (SLATE_300), self.dark_mode);
+            }
+        }
+    }
+
+    pub PathPicker = {{PathPicker}} {
+        width: Fill, height: Fit
+        flow: Down
+        spacing: 4
+
+        label = <Label> {
+            text: ""
+            draw_text: {
+                instance dark_mode: 0.0
+                text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                fn get_color(self) -> vec4 {
+                    return mix((TEXT_SECONDARY), (TEXT_SECONDARY_DARK), self.dark_mode);
+                }
+            }
+        }
+
+        row = <View> {
+            width: Fill, height: Fit
+            flow: Right
+            spacing: 6
+            align: {y: 0.5}
+
+            path_field = <View> {
+                width: Fill, height: 30
+                padding: {left: 8, right: 8}
+                align: {y: 0.5}
+                show_bg: true
+                draw_bg: {
+                    instance dark_mode: 0.0
+                    instance error: 0.0
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(1., 1., self.rect_size.x - 2., self.rect_size.y - 2., 4.0);
+                        let fill = mix((WHITE), (SLATE_800), self.dark_mode);
+                        sdf.fill(mix(fill, mix((RED_50), #3b1f24, self.dark_mode), self.error));
+                        let border = mix((BORDER), (BORDER_DARK), self.dark_mode);
+                        sdf.stroke(mix(border, (RED_500), self.error), 1.0);
+                        return sdf.result;
+                    }
+                }
+
+                path_label = <Label> {
+                    width: Fill
+                    text: "Not set"
+                    draw_text: {
+                        instance dark_mode: 0.0
+                        text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                        fn get_color(self) -> vec4 {
+                            return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                        }
+                    }
+                }
+            }
+
+            recent_dropdown = <DropDown> {
+                width: 84, height: 30
+                padding: {left: 8, right: 18}
+                visible: false
+                labels: ["Recent"]
+                draw_bg: {
+                    instance dark_mode: 0.0
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 4.0);
+                        sdf.fill(mix((SLATE_100), (SLATE_700), self.dark_mode));
+                        return sdf.result;
+                    }
+                }
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+                    fn get_color(self) -> vec4 {
+                        return mix((GRAY_700), (SLATE_300), self.dark_mode);
+                    }
+                }
+            }
+
+            browse_btn = <PathPickerButton> {
+                text: "Browse..."
+            }
+        }
+
+        error_label = <Label> {
+            visible: false
+            text: ""
+            draw_text: {
+                text_style: <FONT_REGULAR>{ font_size: 9.0 }
+                color: (RED_500)
+            }
+        }
+    }
+}
+
+/// What the picker selects
+#[derive(Copy, Clone, Debug, Live, LiveHook, PartialEq)]
+#[live_ignore]
+pub enum PathPickerMode {
+    #[pick]
+    Folder,
+    File,
+}
+
+/// Result of validating the current path
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PathStatus {
+    #[default]
+    Empty,
+    Valid,
+    Missing,
+    NotAFolder,
+    NotAFile,
+    NotWritable,
+}
+
+impl PathStatus {
+    pub fn is_valid(&self) -> bool {
+        *self == PathStatus::Valid
+    }
+
+    /// Inline error text, empty when there is nothing to report
+    pub fn message(&self) -> &'static str {
+        match self {
+            PathStatus::Empty | PathStatus::Valid => "",
+            PathStatus::Missing => "Path does not exist",
+            PathStatus::NotAFolder => "Not a folder",
+            PathStatus::NotAFile => "Not a file",
+            PathStatus::NotWritable => "Not writable",
+        }
+    }
+}
+
+/// Check that `path` exists, is of the kind `mode` asks for and, if
+/// `require_writable`, can be written to.
+pub fn validate_path(path: &Path, mode: PathPickerMode, require_writable: bool) -> PathStatus {
+    let Ok(meta) = fs::metadata(path) else {
+        return PathStatus::Missing;
+    };
+    match mode {
+        PathPickerMode::Folder if !meta.is_dir() => return PathStatus::NotAFolder,
+        PathPickerMode::File if !meta.is_file() => return PathStatus::NotAFile,
+        _ => {}
+    }
+    if !require_writable {
+        return PathStatus::Valid;
+    }
+
+    let writable = match mode {
+        // Permission bits don't tell the whole story (ACLs, read-only mounts),
+        // so try creating a file
+        PathPickerMode::Folder => {
+            let probe = path.join(".mofa-write-test");
+            let created = fs::OpenOptions::new().write(true).create_new(true).open(&probe).is_ok();
+            if created {
+                let _ = fs::remove_file(&probe);
+            }
+            created
+        }
+        PathPickerMode::File => !meta.permissions().readonly(),
+    };
+    if writable {
+        PathStatus::Valid
+    } else {
+        PathStatus::NotWritable
+    }
+}
+
+/// Shorten `text` to at most `max_chars` by replacing its middle with "…"
+pub fn ellipsize_middle(text: &str, max_chars: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= max_chars || max_chars < 3 {
+        return text.to_string();
+    }
+    // Favour the tail: it holds the file or folder name
+    let head = (max_chars - 1) / 2;
+    let tail = max_chars - 1 - head;
+    let mut out: String = chars[..head].iter().collect();
+    out.push('…');
+    out.extend(&chars[chars.len() - tail..]);
+    out
+}
+
+/// Recently used paths, keyed by picker id
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RecentPaths {
+    #[serde(flatten)]
+    pickers: HashMap<String, Vec<PathBuf>>,
+}
+
+impl RecentPaths {
+    pub fn path() -> PathBuf {
+        app_storage::data_path(RECENT_PATHS_FILE)
+    }
+
+    pub fn load() -> Self {
+        Self::load_from(&Self::path())
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        self.save_to(&Self::path())
+    }
+
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    /// Most recent first
+    pub fn get(&self, picker_id: &str) -> &[PathBuf] {
+        self.pickers.get(picker_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Move `path` to the front, dropping the oldest beyond [`MAX_RECENT_PATHS`]
+    pub fn push(&mut self, picker_id: &str, path: &Path) {
+        let list = self.pickers.entry(picker_id.to_string()).or_default();
+        list.retain(|p| p != path);
+        list.insert(0, path.to_path_buf());
+        list.truncate(MAX_RECENT_PATHS);
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum PathPickerAction {
+    None,
+    /// The user chose a path via Browse or the recent list
+    PathChanged(PathBuf),
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct PathPicker {
+    #[deref]
+    view: View,
+
+    /// Key for the recent-paths list; empty disables it
+    #[live]
+    picker_id: String,
+
+    #[live]
+    mode: PathPickerMode,
+
+    /// Flag paths that can't be written to (output locations)
+    #[live(true)]
+    require_writable: bool,
+
+    /// Longest path shown before it is shortened in the middle
+    #[live(48)]
+    max_chars: usize,
+
+    /// Title of the native dialog
+    #[live]
+    dialog_title: String,
+
+    #[rust]
+    path: Option<PathBuf>,
+
+    #[rust]
+    status: PathStatus,
+
+    #[rust]
+    recent: Vec<PathBuf>,
+
+    #[rust]
+    recent_loaded: bool,
+}
+
+impl Widget for PathPicker {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if !self.recent_loaded {
+            self.recent_loaded = true;
+            self.load_recent(cx);
+        }
+
+        let actions = match event {
+            Event::Actions(actions) => actions.as_slice(),
+            _ => return,
+        };
+
+        if self.view.button(ids!(row.browse_btn)).clicked(actions) {
+            if let Some(path) = self.browse() {
+                self.choose(cx, scope, path);
+            }
+        }
+
+        let dropdown = self.view.drop_down(ids!(row.recent_dropdown));
+        if let Some(index) = dropdown.selected(actions) {
+            // Item 0 is the "Recent" caption
+            dropdown.set_selected_item(cx, 0);
+            if let Some(path) = index.checked_sub(1).and_then(|i| self.recent.get(i)).cloned() {
+                self.choose(cx, scope, path);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl PathPicker {
+    fn browse(&self) -> Option<PathBuf> {
+        let mut dialog = rfd::FileDialog::new();
+        if !self.dialog_title.is_empty() {
+            dialog = dialog.set_title(&self.dialog_title);
+        }
+        let start = self.path.as_ref().and_then(|p| match self.mode {
+            PathPickerMode::Folder => Some(p.as_path()),
+            PathPickerMode::File => p.parent(),
+        });
+        if let Some(dir) = start.filter(|d| d.is_dir()) {
+            dialog = dialog.set_directory(dir);
+        }
+        match self.mode {
+            PathPickerMode::Folder => dialog.pick_folder(),
+            PathPickerMode::File => dialog.pick_file(),
+        }
+    }
+
+    /// Apply a path picked by the user, remember it and tell the parent
+    fn choose(&mut self, cx: &mut Cx, scope: &mut Scope, path: PathBuf) {
+        self.set_path_inner(cx, Some(path.clone()));
+        if self.status.is_valid() {
+            self.remember(cx, &path);
+        }
+        cx.widget_action(self.widget_uid(), &scope.path, PathPickerAction::PathChanged(path));
+    }
+
+    fn set_path_inner(&mut self, cx: &mut Cx, path: Option<PathBuf>) {
+        self.status = match &path {
+            Some(p) => validate_path(p, self.mode, self.require_writable),
+            None => PathStatus::Empty,
+        };
+        self.path = path;
+
+        let text = self.path.as_ref()
+            .map(|p| ellipsize_middle(&p.to_string_lossy(), self.max_chars))
+            .unwrap_or_else(|| "Not set".to_string());
+        self.view.label(ids!(row.path_field.path_label)).set_text(cx, &text);
+
+        let error = self.status.message();
+        let tint = if error.is_empty() { 0.0 } else { 1.0 };
+        self.view.view(ids!(row.path_field)).apply_over(cx, live!{
+            draw_bg: { error: (tint) }
+        });
+        let error_label = self.view.label(ids!(error_label));
+        error_label.set_text(cx, error);
+        error_label.set_visible(cx, !error.is_empty());
+        self.view.redraw(cx);
+    }
+
+    fn load_recent(&mut self, cx: &mut Cx) {
+        if self.picker_id.is_empty() {
+            return;
+        }
+        self.recent = RecentPaths::load().get(&self.picker_id).to_vec();
+        self.update_recent_ui(cx);
+    }
+
+    fn remember(&mut self, cx: &mut Cx, path: &Path) {
+        if self.picker_id.is_empty() {
+            return;
+        }
+        let mut recent = RecentPaths::load();
+        recent.push(&self.picker_id, path);
+        if let Err(e) = recent.save() {
+            ::log::warn!("Failed to save recent paths: {}", e);
+        }
+        self.recent = recent.get(&self.picker_id).to_vec();
+        self.update_recent_ui(cx);
+    }
+
+    fn update_recent_ui(&mut self, cx: &mut Cx) {
+        let dropdown = self.view.drop_down(ids!(row.recent_dropdown));
+        let labels = std::iter::once("Recent".to_string())
+            .chain(self.recent.iter().map(|p| ellipsize_middle(&p.to_string_lossy(), self.max_chars)))
+            .collect();
+        dropdown.set_labels(cx, labels);
+        dropdown.set_selected_item(cx, 0);
+        dropdown.set_visible(cx, !self.recent.is_empty());
+    }
+}
+
+impl PathPickerRef {
+    /// Current path, valid or not
+    pub fn path(&self) -> Option<PathBuf> {
+        self.borrow().and_then(|inner| inner.path.clone())
+    }
+
+    /// Set the path from code; validates it but emits no action
+    pub fn set_path(&self, cx: &mut Cx, path: Option<&Path>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_path_inner(cx, path.map(Path::to_path_buf));
+        }
+    }
+
+    pub fn status(&self) -> PathStatus {
+        self.borrow().map(|inner| inner.status).unwrap_or_default()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.status().is_valid()
+    }
+
+    /// Returns the new path if the user changed it in these actions
+    pub fn path_changed(&self, actions: &Actions) -> Option<PathBuf> {
+        let uid = self.widget_uid();
+        actions.iter().find_map(|action| {
+            action.as_widget_action().and_then(|wa| {
+                if wa.widget_uid != uid {
+                    return None;
+                }
+                match wa.cast() {
+                    PathPickerAction::PathChanged(path) => Some(path),
+                    _ => None,
+                }
+            })
+        })
+    }
+
+    /// Update dark mode for the picker
+    pub fn update_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.view.label(ids!(label)).apply_over(cx, live!{
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.view(ids!(row.path_field)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+            });
+            inner.view.label(ids!(row.path_field.path_label)).apply_over(cx, live!{
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.drop_down(ids!(row.recent_dropdown)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.button(ids!(row.browse_btn)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+                draw_text: { dark_mode: (dark_mode) }
+            });
+            inner.view.redraw(cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ellipsize_middle() {
+        assert_eq!(ellipsize_middle("/short/path", 20), "/short/path");
+        let long = "/Users/someone/Documents/MoFaPodcast/episodes";
+        let short = ellipsize_middle(long, 20);
+        assert_eq!(short.chars().count(), 20);
+        assert!(short.starts_with("/Users/so"));
+        assert!(short.ends_with("t/episodes"));
+        assert!(short.contains('…'));
+    }
+
+    #[test]
+    fn test_recent_paths_dedupe_and_limit() {
+        let mut recent = RecentPaths::default();
+        for i in 0..MAX_RECENT_PATHS + 2 {
+            recent.push("out", Path::new(&format!("/tmp/{}", i)));
+        }
+        recent.push("out", Path::new("/tmp/5"));
+
+        let list = recent.get("out");
+        assert_eq!(list.len(), MAX_RECENT_PATHS);
+        assert_eq!(list[0], PathBuf::from("/tmp/5"));
+        assert_eq!(list.iter().filter(|p| p.as_path() == Path::new("/tmp/5")).count(), 1);
+        assert!(recent.get("other").is_empty());
+
+        let file = std::env::temp_dir().join(format!("mofa-recent-{}.json", std::process::id()));
+        recent.save_to(&file).unwrap();
+        assert_eq!(RecentPaths::load_from(&file).get("out"), list);
+        let _ = fs::remove_file(&file);
+    }
+
+    #[test]
+    fn test_validate_path() {
+        let dir = std::env::temp_dir().join(format!("mofa-picker-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("a.txt");
+        fs::write(&file, "x").unwrap();
+
+        assert_eq!(validate_path(&dir, PathPickerMode::Folder, true), PathStatus::Valid);
+        assert_eq!(validate_path(&file, PathPickerMode::File, false), PathStatus::Valid);
+        assert_eq!(validate_path(&file, PathPickerMode::Folder, false), PathStatus::NotAFolder);
+        assert_eq!(validate_path(&dir, PathPickerMode::File, false), PathStatus::NotAFile);
+        assert_eq!(validate_path(&dir.join("missing"), PathPickerMode::Folder, false), PathStatus::Missing);
+        assert!(!dir.join(".mofa-write-test").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
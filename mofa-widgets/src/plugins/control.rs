@@ -0,0 +1,372 @@
+//! Plugin control API - a localhost endpoint plugin backends can query
+//!
+//! Plugin processes receive the endpoint URL and a per-plugin token through
+//! [`CONTROL_URL_ENV`] and [`CONTROL_TOKEN_ENV`]. Every request must carry
+//! `Authorization: Bearer <token>`. Tokens have the form
+//! `<plugin id>.<secret>`: the id picks the plugin's grant, the secret is
+//! compared in constant time, and the plugin's manifest `permissions` decide
+//! what it may do.
+//!
+//! | Endpoint           | Permission                                        |
+//! |--------------------|---------------------------------------------------|
+//! | `GET /theme`       | none                                              |
+//! | `GET /ai-provider` | `ai-provider` (key only with `ai-provider-key`)   |
+//! | `POST /notify`     | `notify`                                          |
+
+use super::{PluginManifest, FALLBACK_LOCALE};
+use crate::local_server::{constant_time_eq, generate_token, HttpRequest, HttpResponse, LocalServer};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Environment variable holding the control API base URL
+pub const CONTROL_URL_ENV: &str = "MOFA_CONTROL_URL";
+
+/// Environment variable holding the plugin's auth token
+pub const CONTROL_TOKEN_ENV: &str = "MOFA_CONTROL_TOKEN";
+
+/// Read the active AI provider (API key is redacted without the key permission)
+pub const PERMISSION_AI_PROVIDER: &str = "ai-provider";
+
+/// Read the active AI provider's API key in clear
+pub const PERMISSION_AI_PROVIDER_KEY: &str = "ai-provider-key";
+
+/// Post notifications to the studio
+pub const PERMISSION_NOTIFY: &str = "notify";
+
+/// Save files through the native save dialog (the page's `export_note`
+/// IPC request, see [`crate::webview::export`])
+pub const PERMISSION_SAVE_FILES: &str = "save-files";
+
+/// Longest notification message accepted (in characters)
+const MAX_NOTIFY_CHARS: usize = 500;
+
+/// The AI provider currently used by the studio
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AiProviderInfo {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub model: Option<String>,
+    pub api_key: Option<String>,
+}
+
+/// Studio state exposed to plugins; implemented by the shell
+pub trait StudioState: Send + Sync {
+    fn dark_mode(&self) -> bool;
+    fn ai_provider(&self) -> Option<AiProviderInfo>;
+}
+
+/// Severity of a plugin notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationLevel {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+/// A toast requested by a plugin through `POST /notify`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginNotification {
+    pub plugin_id: String,
+    pub title: String,
+    pub message: String,
+    pub level: NotificationLevel,
+}
+
+#[derive(Deserialize)]
+struct NotifyBody {
+    #[serde(default)]
+    title: String,
+    message: String,
+    #[serde(default)]
+    level: NotificationLevel,
+}
+
+/// Request handling and token bookkeeping, shared with the server thread
+struct ControlApi {
+    state: Arc<dyn StudioState>,
+    /// plugin id -> (token issued to it, its manifest)
+    grants: Mutex<HashMap<String, (String, PluginManifest)>>,
+    notifications: Mutex<Vec<PluginNotification>>,
+}
+
+impl ControlApi {
+    /// Manifest of the plugin `token` was issued to, looked up by the id it
+    /// starts with so the secret itself is only ever compared in constant time
+    fn authorize(&self, token: &str) -> Option<PluginManifest> {
+        let (plugin_id, _) = token.rsplit_once('.')?;
+        let grants = self.grants.lock().unwrap();
+        let (issued, manifest) = grants.get(plugin_id)?;
+        constant_time_eq(token.as_bytes(), issued.as_bytes()).then(|| manifest.clone())
+    }
+
+    fn handle(&self, request: &HttpRequest) -> HttpResponse {
+        let manifest = match request.bearer_token().and_then(|token| self.authorize(token)) {
+            Some(manifest) => manifest,
+            None => return error_response("401 Unauthorized", "missing or unknown token"),
+        };
+
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/theme") => {
+                let dark = self.state.dark_mode();
+                HttpResponse::json("200 OK", &json!({
+                    "dark_mode": dark,
+                    "theme": if dark { "dark" } else { "light" },
+                }))
+            }
+            ("GET", "/ai-provider") => {
+                if !manifest.has_permission(PERMISSION_AI_PROVIDER) {
+                    return error_response("403 Forbidden", "permission required: ai-provider");
+                }
+                let provider = self.state.ai_provider().map(|mut provider| {
+                    if !manifest.has_permission(PERMISSION_AI_PROVIDER_KEY) {
+                        provider.api_key = provider.api_key.map(|_| "[redacted]".to_string());
+                    }
+                    provider
+                });
+                HttpResponse::json("200 OK", &json!({ "provider": provider }))
+            }
+            ("POST", "/notify") => {
+                if !manifest.has_permission(PERMISSION_NOTIFY) {
+                    return error_response("403 Forbidden", "permission required: notify");
+                }
+                let body: NotifyBody = match serde_json::from_slice(&request.body) {
+                    Ok(body) => body,
+                    Err(e) => return error_response("400 Bad Request", &format!("invalid body: {}", e)),
+                };
+                let message: String = body.message.trim().chars().take(MAX_NOTIFY_CHARS).collect();
+                if message.is_empty() {
+                    return error_response("400 Bad Request", "message is empty");
+                }
+                let title = if body.title.trim().is_empty() {
+                    manifest.name.resolve(FALLBACK_LOCALE).to_string()
+                } else {
+                    body.title.trim().to_string()
+                };
+                self.notifications.lock().unwrap().push(PluginNotification {
+                    plugin_id: manifest.id.clone(),
+                    title,
+                    message,
+                    level: body.level,
+                });
+                HttpResponse::json("202 Accepted", &json!({ "ok": true }))
+            }
+            (_, "/theme") | (_, "/ai-provider") | (_, "/notify") => HttpResponse::method_not_allowed(),
+            _ => HttpResponse::not_found(),
+        }
+    }
+}
+
+fn error_response(status: &'static str, message: &str) -> HttpResponse {
+    HttpResponse::json(status, &json!({ "error": message }))
+}
+
+/// The running control API server
+pub struct ControlServer {
+    api: Arc<ControlApi>,
+    server: LocalServer,
+}
+
+impl ControlServer {
+    /// Start serving on a random localhost port
+    pub fn start(state: Arc<dyn StudioState>) -> Result<Self, String> {
+        let api = Arc::new(ControlApi {
+            state,
+            grants: Mutex::new(HashMap::new()),
+            notifications: Mutex::new(Vec::new()),
+        });
+        let mut server = LocalServer::default();
+        let handler_api = api.clone();
+        server.start(move |request| handler_api.handle(request))?;
+        Ok(Self { api, server })
+    }
+
+    pub fn url(&self) -> String {
+        self.server.url()
+    }
+
+    /// Issue a token for a plugin, replacing any token it held before
+    pub fn register_plugin(&self, manifest: &PluginManifest) -> String {
+        let token = format!("{}.{}", manifest.id, generate_token());
+        self.api
+            .grants
+            .lock()
+            .unwrap()
+            .insert(manifest.id.clone(), (token.clone(), manifest.clone()));
+        token
+    }
+
+    /// Invalidate the plugin's token
+    pub fn revoke_plugin(&self, plugin_id: &str) {
+        self.api.grants.lock().unwrap().remove(plugin_id);
+    }
+
+    /// Register the plugin and return the env vars to pass to its process
+    pub fn plugin_env(&self, manifest: &PluginManifest) -> Vec<(String, String)> {
+        vec![
+            (CONTROL_URL_ENV.to_string(), self.url()),
+            (CONTROL_TOKEN_ENV.to_string(), self.register_plugin(manifest)),
+        ]
+    }
+
+    /// Drain notifications posted since the last call
+    pub fn take_notifications(&self) -> Vec<PluginNotification> {
+        std::mem::take(&mut *self.api.notifications.lock().unwrap())
+    }
+
+    pub fn stop(&mut self) {
+        self.server.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    struct FakeStudio;
+
+    impl StudioState for FakeStudio {
+        fn dark_mode(&self) -> bool {
+            true
+        }
+
+        fn ai_provider(&self) -> Option<AiProviderInfo> {
+            Some(AiProviderInfo {
+                id: "openai".to_string(),
+                name: "OpenAI".to_string(),
+                url: "https://api.openai.com/v1".to_string(),
+                model: Some("gpt-4o-mini".to_string()),
+                api_key: Some("sk-secret".to_string()),
+            })
+        }
+    }
+
+    fn manifest(id: &str, permissions: &[&str]) -> PluginManifest {
+        serde_json::from_value(json!({
+            "id": id,
+            "name": "Fake Plugin",
+            "version": "0.1.0",
+            "permissions": permissions,
+        }))
+        .unwrap()
+    }
+
+    /// Minimal plugin-side client: reads the env pairs like a plugin process would
+    struct FakePluginClient {
+        url: String,
+        token: String,
+    }
+
+    impl FakePluginClient {
+        fn from_env(env: &[(String, String)]) -> Self {
+            let get = |key: &str| env.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone()).unwrap();
+            Self { url: get(CONTROL_URL_ENV), token: get(CONTROL_TOKEN_ENV) }
+        }
+
+        fn request(&self, method: &str, path: &str, body: &str) -> (u16, serde_json::Value) {
+            let addr = self.url.trim_start_matches("http://");
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(
+                stream,
+                "{} {} HTTP/1.1\r\nHost: {}\r\nAuthorization: Bearer {}\r\nContent-Length: {}\r\n\r\n{}",
+                method, path, addr, self.token, body.len(), body
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+
+            let status = response[9..12].parse().unwrap();
+            let body = response.split_once("\r\n\r\n").map(|(_, b)| b).unwrap_or("");
+            (status, serde_json::from_str(body).unwrap_or(serde_json::Value::Null))
+        }
+    }
+
+    #[test]
+    fn test_theme_and_unknown_token() {
+        let server = ControlServer::start(Arc::new(FakeStudio)).unwrap();
+        let client = FakePluginClient::from_env(&server.plugin_env(&manifest("p1", &[])));
+
+        let (status, body) = client.request("GET", "/theme", "");
+        assert_eq!(status, 200);
+        assert_eq!(body["theme"], "dark");
+
+        let intruder = FakePluginClient { url: server.url(), token: "nope".to_string() };
+        assert_eq!(intruder.request("GET", "/theme", "").0, 401);
+    }
+
+    #[test]
+    fn test_ai_provider_key_redaction() {
+        let server = ControlServer::start(Arc::new(FakeStudio)).unwrap();
+
+        let none = FakePluginClient::from_env(&server.plugin_env(&manifest("p1", &[])));
+        assert_eq!(none.request("GET", "/ai-provider", "").0, 403);
+
+        let redacted = FakePluginClient::from_env(&server.plugin_env(&manifest("p2", &["ai-provider"])));
+        let (status, body) = redacted.request("GET", "/ai-provider", "");
+        assert_eq!(status, 200);
+        assert_eq!(body["provider"]["model"], "gpt-4o-mini");
+        assert_eq!(body["provider"]["api_key"], "[redacted]");
+
+        let full = FakePluginClient::from_env(
+            &server.plugin_env(&manifest("p3", &["ai-provider", "ai-provider-key"])),
+        );
+        assert_eq!(full.request("GET", "/ai-provider", "").1["provider"]["api_key"], "sk-secret");
+    }
+
+    #[test]
+    fn test_notify_requires_permission() {
+        let server = ControlServer::start(Arc::new(FakeStudio)).unwrap();
+
+        let denied = FakePluginClient::from_env(&server.plugin_env(&manifest("p1", &[])));
+        assert_eq!(denied.request("POST", "/notify", r#"{"message":"hi"}"#).0, 403);
+
+        let allowed = FakePluginClient::from_env(&server.plugin_env(&manifest("p2", &["notify"])));
+        assert_eq!(allowed.request("POST", "/notify", "not json").0, 400);
+        let (status, _) = allowed.request("POST", "/notify", r#"{"message":" Export done ","level":"success"}"#);
+        assert_eq!(status, 202);
+
+        let notifications = server.take_notifications();
+        assert_eq!(notifications, vec![PluginNotification {
+            plugin_id: "p2".to_string(),
+            title: "Fake Plugin".to_string(),
+            message: "Export done".to_string(),
+            level: NotificationLevel::Success,
+        }]);
+        assert!(server.take_notifications().is_empty());
+    }
+
+    #[test]
+    fn test_reregister_and_revoke_invalidate_old_token() {
+        let server = ControlServer::start(Arc::new(FakeStudio)).unwrap();
+        let first = FakePluginClient::from_env(&server.plugin_env(&manifest("p1", &[])));
+        let second = FakePluginClient::from_env(&server.plugin_env(&manifest("p1", &[])));
+        assert_ne!(first.token, second.token);
+
+        assert_eq!(first.request("GET", "/theme", "").0, 401);
+        assert_eq!(second.request("GET", "/theme", "").0, 200);
+
+        server.revoke_plugin("p1");
+        assert_eq!(second.request("GET", "/theme", "").0, 401);
+    }
+
+    #[test]
+    fn test_token_needs_the_plugins_own_secret() {
+        let server = ControlServer::start(Arc::new(FakeStudio)).unwrap();
+        let p1 = FakePluginClient::from_env(&server.plugin_env(&manifest("p1", &[])));
+        let p2 = FakePluginClient::from_env(&server.plugin_env(&manifest("p2", &["notify"])));
+        assert!(p1.token.starts_with("p1."));
+
+        // p1 presenting p2's id with its own secret gets nowhere
+        let secret = p1.token.rsplit_once('.').unwrap().1;
+        let forged = FakePluginClient { url: server.url(), token: format!("p2.{}", secret) };
+        assert_eq!(forged.request("POST", "/notify", r#"{"message":"hi"}"#).0, 401);
+        assert_eq!(p2.request("POST", "/notify", r#"{"message":"hi"}"#).0, 202);
+    }
+}
@@ -1,10 +1,19 @@
 //! Plugin loader - discovers and loads plugins from the plugins directory
 
-use super::{PluginManifest, PluginType};
+use super::{ControlServer, NativePlugin, PluginManifest, PluginOutput, PluginType, FALLBACK_LOCALE};
+use crate::app_storage;
+use makepad_widgets::{Cx, WidgetRef};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::{Child, Command, Stdio};
 use std::net::TcpListener;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Environment variable pointing a plugin server at its private data directory
+pub const PLUGIN_DATA_DIR_ENV: &str = "MOFA_PLUGIN_DATA_DIR";
+
+const MANIFEST_FILE: &str = "manifest.json";
 
 /// A loaded plugin with its runtime state
 #[derive(Debug)]
@@ -21,6 +30,15 @@ pub struct LoadedPlugin {
     /// Server port (for WebView plugins)
     pub server_port: Option<u16>,
 
+    /// When the running server was started
+    pub started_at: Option<Instant>,
+
+    /// Recent server output (for WebView plugins)
+    pub output: PluginOutput,
+
+    /// Loaded library (for Native plugins), once its screen was opened
+    pub native: Option<NativePlugin>,
+
     /// Whether the plugin is enabled
     pub enabled: bool,
 }
@@ -33,17 +51,40 @@ impl LoadedPlugin {
             dir,
             server_process: None,
             server_port: None,
+            started_at: None,
+            output: PluginOutput::default(),
+            native: None,
             enabled: true,
         }
     }
 
+    /// Data this plugin keeps on disk (its private data directory)
+    pub fn data_locations(&self) -> Vec<app_storage::DataLocation> {
+        app_storage::plugin_locations(&self.manifest.id)
+    }
+
+    /// Path of this plugin's manifest.json
+    pub fn manifest_path(&self) -> PathBuf {
+        self.dir.join(MANIFEST_FILE)
+    }
+
+    /// Display name in `language`
+    pub fn name(&self, language: &str) -> &str {
+        self.manifest.name.resolve(language)
+    }
+
+    /// Description in `language`
+    pub fn description(&self, language: &str) -> &str {
+        self.manifest.description.resolve(language)
+    }
+
     /// Get the URL for this plugin's WebView
     pub fn get_url(&self) -> Option<String> {
         self.server_port.map(|port| format!("http://127.0.0.1:{}", port))
     }
 
-    /// Start the plugin's Python server
-    pub fn start_server(&mut self, python_cmd: &str) -> Result<u16, String> {
+    /// Start the plugin's Python server with extra environment variables
+    pub fn start_server(&mut self, python_cmd: &str, envs: &[(String, String)]) -> Result<u16, String> {
         if self.manifest.r#type != PluginType::WebView {
             return Err("Not a WebView plugin".to_string());
         }
@@ -62,22 +103,66 @@ impl LoadedPlugin {
             return Err(format!("Python entry not found: {:?}", python_entry));
         }
 
-        // Start the server
-        let child = Command::new(python_cmd)
+        // Start the server, keeping its recent output for diagnostics
+        let mut child = Command::new(python_cmd)
             .current_dir(&self.dir)
             .arg(&python_entry)
             .arg(port.to_string())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
+            .envs(envs.iter().cloned())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
             .spawn()
             .map_err(|e| format!("Failed to start plugin server: {}", e))?;
+        self.output.push(format!("--- server started on port {} ---", port));
+        if let Some(stdout) = child.stdout.take() {
+            self.output.capture(stdout, false);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            self.output.capture(stderr, true);
+        }
 
         self.server_process = Some(child);
         self.server_port = Some(port);
+        self.started_at = Some(Instant::now());
 
         Ok(port)
     }
 
+    /// Library of a Native plugin. A name without extension gets the
+    /// platform's (`.so`, `.dylib`, `.dll`); the `lib` prefix Cargo adds on
+    /// Unix may be left out.
+    pub fn library_path(&self) -> Option<PathBuf> {
+        let mut path = self.dir.join(self.manifest.library.as_deref()?);
+        if path.extension().is_none() {
+            path.set_extension(std::env::consts::DLL_EXTENSION);
+        }
+        let name = path.file_name()?.to_string_lossy().to_string();
+        let prefixed = path.with_file_name(format!("{}{}", std::env::consts::DLL_PREFIX, name));
+        if !path.exists() && prefixed.exists() {
+            return Some(prefixed);
+        }
+        Some(path)
+    }
+
+    /// Load a Native plugin's library if needed and create its screen
+    pub fn create_native_screen(&mut self, cx: &mut Cx) -> Result<WidgetRef, String> {
+        if self.manifest.r#type != PluginType::Native {
+            return Err("Not a native plugin".to_string());
+        }
+        let native = match self.native.take() {
+            Some(native) => native,
+            None => self.load_native()?,
+        };
+        self.native.insert(native).create_screen(cx)
+    }
+
+    /// The plugin's library, loaded now or shared with an earlier load
+    fn load_native(&self) -> Result<NativePlugin, String> {
+        let library = self.library_path()
+            .ok_or_else(|| "Manifest names no \"library\"".to_string())?;
+        NativePlugin::load(&library, &self.manifest.id)
+    }
+
     /// Stop the plugin's server
     pub fn stop_server(&mut self) {
         if let Some(mut child) = self.server_process.take() {
@@ -85,6 +170,7 @@ impl LoadedPlugin {
             let _ = child.wait();
         }
         self.server_port = None;
+        self.started_at = None;
     }
 
     /// Check if server is running
@@ -109,6 +195,12 @@ pub struct PluginLoader {
 
     /// Python command to use
     python_cmd: String,
+
+    /// Control API passed to plugin processes
+    control: Option<Arc<ControlServer>>,
+
+    /// Locale plugin names and descriptions are shown in (e.g. "zh-CN")
+    language: String,
 }
 
 impl PluginLoader {
@@ -125,9 +217,26 @@ impl PluginLoader {
             plugins_dir,
             plugins: HashMap::new(),
             python_cmd: get_python_cmd(),
+            control: None,
+            language: system_language(),
         }
     }
 
+    /// Show plugin names and descriptions in `language` from now on
+    pub fn set_language(&mut self, language: &str) {
+        self.language = language.to_string();
+    }
+
+    /// Locale plugin names and descriptions are shown in
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Expose the control API to plugins started from now on
+    pub fn set_control_server(&mut self, control: Arc<ControlServer>) {
+        self.control = Some(control);
+    }
+
     /// Get the plugins directory path
     pub fn plugins_dir(&self) -> &PathBuf {
         &self.plugins_dir
@@ -148,7 +257,7 @@ impl PluginLoader {
                 continue;
             }
 
-            let manifest_path = path.join("manifest.json");
+            let manifest_path = path.join(MANIFEST_FILE);
             if !manifest_path.exists() {
                 continue;
             }
@@ -156,7 +265,7 @@ impl PluginLoader {
             match PluginManifest::from_file(&manifest_path) {
                 Ok(manifest) => {
                     let id = manifest.id.clone();
-                    log::info!("Loaded plugin: {} v{}", manifest.name, manifest.version);
+                    log::info!("Loaded plugin: {} v{}", manifest.name.resolve(&self.language), manifest.version);
                     self.plugins.insert(id.clone(), LoadedPlugin::new(manifest, path));
                     loaded.push(id);
                 }
@@ -195,7 +304,57 @@ impl PluginLoader {
         let plugin = self.plugins.get_mut(id)
             .ok_or_else(|| format!("Plugin not found: {}", id))?;
 
-        plugin.start_server(&python_cmd)
+        // A running server keeps its token; only fresh starts get new env
+        let mut envs = match self.control {
+            Some(ref control) if !plugin.is_server_running() => control.plugin_env(&plugin.manifest),
+            _ => Vec::new(),
+        };
+        if !plugin.is_server_running() {
+            envs.push((
+                PLUGIN_DATA_DIR_ENV.to_string(),
+                app_storage::plugin_data_dir(id).to_string_lossy().to_string(),
+            ));
+        }
+        plugin.start_server(&python_cmd, &envs)
+    }
+
+    /// Create a Native plugin's screen, loading its library on first use
+    pub fn create_native_screen(&mut self, cx: &mut Cx, id: &str) -> Result<WidgetRef, String> {
+        self.plugins.get_mut(id)
+            .ok_or_else(|| format!("Plugin not found: {}", id))?
+            .create_native_screen(cx)
+    }
+
+    /// Stop a plugin's server and start it again with a fresh environment
+    pub fn restart_plugin(&mut self, id: &str) -> Result<u16, String> {
+        self.stop_plugin(id);
+        self.start_plugin(id)
+    }
+
+    /// Re-read one plugin's manifest.json, leaving its server running
+    ///
+    /// The new manifest must keep the plugin's ID; changes to the entry
+    /// point or permissions take effect on the next (re)start.
+    pub fn reload_manifest(&mut self, id: &str) -> Result<(), String> {
+        let plugin = self.plugins.get_mut(id)
+            .ok_or_else(|| format!("Plugin not found: {}", id))?;
+        let manifest = PluginManifest::from_file(&plugin.manifest_path())?;
+        if manifest.id != id {
+            return Err(format!("Manifest ID changed from {} to {}; rescan to load it", id, manifest.id));
+        }
+        log::info!("Reloaded manifest: {} v{}", manifest.name.resolve(&self.language), manifest.version);
+        plugin.manifest = manifest;
+        Ok(())
+    }
+
+    /// Command line that starts (or started) a plugin's server, for display
+    pub fn runtime_command(&self, id: &str) -> Option<String> {
+        let plugin = self.plugins.get(id)?;
+        let entry = plugin.dir.join(plugin.manifest.get_python_entry());
+        let port = plugin.server_port
+            .map(|port| port.to_string())
+            .unwrap_or_else(|| "<port>".to_string());
+        Some(format!("{} {} {}", self.python_cmd, entry.display(), port))
     }
 
     /// Stop a plugin's server
@@ -203,12 +362,18 @@ impl PluginLoader {
         if let Some(plugin) = self.plugins.get_mut(id) {
             plugin.stop_server();
         }
+        if let Some(ref control) = self.control {
+            control.revoke_plugin(id);
+        }
     }
 
     /// Stop all plugin servers
     pub fn stop_all(&mut self) {
         for plugin in self.plugins.values_mut() {
             plugin.stop_server();
+            if let Some(ref control) = self.control {
+                control.revoke_plugin(&plugin.manifest.id);
+            }
         }
     }
 
@@ -246,6 +411,25 @@ fn get_plugins_dir() -> PathBuf {
         .join("plugins")
 }
 
+/// Language of the user's locale ("zh_CN.UTF-8" becomes "zh-CN"), English
+/// when none is set
+pub fn system_language() -> String {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| locale_language(&value))
+        .unwrap_or_else(|| FALLBACK_LOCALE.to_string())
+}
+
+fn locale_language(locale: &str) -> Option<String> {
+    let tag = locale.split(['.', '@']).next()?.replace('_', "-");
+    match tag.as_str() {
+        "" | "C" | "POSIX" => None,
+        _ => Some(tag),
+    }
+}
+
 /// Find an available port
 fn find_available_port() -> Option<u16> {
     TcpListener::bind("127.0.0.1:0")
@@ -299,3 +483,17 @@ fn get_python_cmd() -> String {
 
     "python3".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_language() {
+        assert_eq!(locale_language("zh_CN.UTF-8").as_deref(), Some("zh-CN"));
+        assert_eq!(locale_language("de_DE@euro").as_deref(), Some("de-DE"));
+        assert_eq!(locale_language("en").as_deref(), Some("en"));
+        assert_eq!(locale_language("C.UTF-8"), None);
+        assert_eq!(locale_language("POSIX"), None);
+    }
+}
@@ -1,15 +1,25 @@
 //! Plugin manifest parsing
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+/// Locale used when a localized text has no entry for the active language
+pub const FALLBACK_LOCALE: &str = "en";
+
+/// Language subtags a manifest locale is checked against; others only warn
+const KNOWN_LANGUAGES: &[&str] = &[
+    "ar", "de", "en", "es", "fr", "hi", "id", "it", "ja", "ko", "ms", "nl",
+    "pl", "pt", "ru", "sv", "th", "tr", "uk", "vi", "zh",
+];
+
 /// Plugin type
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum PluginType {
     /// WebView-based plugin (Python + HTML)
     WebView,
-    /// Native Makepad plugin (requires compilation)
+    /// Native Makepad plugin (a compiled library, see `plugins::native`)
     Native,
 }
 
@@ -19,21 +29,65 @@ impl Default for PluginType {
     }
 }
 
+/// A manifest string: either plain text, or text per locale
+///
+/// ```json
+/// "name": { "en": "Podcast Helper", "zh-CN": "播客助手" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LocalizedText {
+    Plain(String),
+    Localized(BTreeMap<String, String>),
+}
+
+impl Default for LocalizedText {
+    fn default() -> Self {
+        Self::Plain(String::new())
+    }
+}
+
+impl LocalizedText {
+    /// Text for `language` (e.g. "zh-CN"). Falls back to the same language
+    /// in another region, then English, then whatever locale is listed first.
+    pub fn resolve(&self, language: &str) -> &str {
+        let map = match self {
+            Self::Plain(text) => return text,
+            Self::Localized(map) => map,
+        };
+        let wanted = normalize_locale(language);
+        let primary = |locale: &str| normalize_locale(locale).split('-').next().map(str::to_string);
+
+        map.iter()
+            .find(|(locale, _)| normalize_locale(locale) == wanted)
+            .or_else(|| map.iter().find(|(locale, _)| primary(locale) == primary(&wanted)))
+            .or_else(|| map.iter().find(|(locale, _)| primary(locale).as_deref() == Some(FALLBACK_LOCALE)))
+            .or_else(|| map.iter().next())
+            .map(|(_, text)| text.as_str())
+            .unwrap_or("")
+    }
+}
+
+/// "zh_CN" and "ZH-cn" both become "zh-cn"
+fn normalize_locale(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_lowercase()
+}
+
 /// Plugin manifest (manifest.json)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginManifest {
     /// Unique plugin identifier
     pub id: String,
 
-    /// Display name
-    pub name: String,
+    /// Display name, plain or per locale
+    pub name: LocalizedText,
 
     /// Version string
     pub version: String,
 
-    /// Description
+    /// Description, plain or per locale
     #[serde(default)]
-    pub description: String,
+    pub description: LocalizedText,
 
     /// Author name
     #[serde(default)]
@@ -55,6 +109,10 @@ pub struct PluginManifest {
     #[serde(default)]
     pub static_dir: Option<String>,
 
+    /// Compiled library, relative to plugin directory (for Native plugins)
+    #[serde(default)]
+    pub library: Option<String>,
+
     /// Whether to show in sidebar
     #[serde(default = "default_true")]
     pub show_in_sidebar: bool,
@@ -70,6 +128,11 @@ pub struct PluginManifest {
     /// Plugin repository URL
     #[serde(default)]
     pub repository: Option<String>,
+
+    /// Control API and page permissions (e.g. "ai-provider", "ai-provider-key",
+    /// "notify", "save-files")
+    #[serde(default)]
+    pub permissions: Vec<String>,
 }
 
 fn default_true() -> bool {
@@ -82,8 +145,34 @@ impl PluginManifest {
         let content = std::fs::read_to_string(path)
             .map_err(|e| format!("Failed to read manifest: {}", e))?;
 
-        serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse manifest: {}", e))
+        let manifest: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse manifest: {}", e))?;
+        for warning in manifest.validate()? {
+            log::warn!("{}: {}", path.display(), warning);
+        }
+        Ok(manifest)
+    }
+
+    /// Check the localized fields. An empty locale map is an error; locale
+    /// codes in an unknown language are returned as warnings.
+    pub fn validate(&self) -> Result<Vec<String>, String> {
+        let mut warnings = Vec::new();
+        for (field, text) in [("name", &self.name), ("description", &self.description)] {
+            let LocalizedText::Localized(map) = text else {
+                continue;
+            };
+            if map.is_empty() {
+                return Err(format!("Invalid manifest: \"{}\" has no locales", field));
+            }
+            for locale in map.keys() {
+                let normalized = normalize_locale(locale);
+                let language = normalized.split('-').next().unwrap_or_default();
+                if !KNOWN_LANGUAGES.contains(&language) {
+                    warnings.push(format!("Unknown locale \"{}\" in \"{}\"", locale, field));
+                }
+            }
+        }
+        Ok(warnings)
     }
 
     /// Get the Python entry point path relative to plugin directory
@@ -95,6 +184,11 @@ impl PluginManifest {
     pub fn get_static_dir(&self) -> &str {
         self.static_dir.as_deref().unwrap_or("static")
     }
+
+    /// Whether the manifest grants a control API permission
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.permissions.iter().any(|p| p == permission)
+    }
 }
 
 #[cfg(test)]
@@ -115,6 +209,71 @@ mod tests {
 
         let manifest: PluginManifest = serde_json::from_str(json).unwrap();
         assert_eq!(manifest.id, "test-plugin");
+        assert_eq!(manifest.name.resolve("zh-CN"), "Test Plugin");
         assert_eq!(manifest.r#type, PluginType::WebView);
+        assert!(manifest.permissions.is_empty());
+    }
+
+    #[test]
+    fn test_localized_name() {
+        let json = r#"{
+            "id": "podcast-helper",
+            "name": { "en": "Podcast Helper", "zh-CN": "播客助手" },
+            "version": "1.0.0",
+            "description": { "zh": "为中文播客准备的工具" }
+        }"#;
+
+        let manifest: PluginManifest = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.name.resolve("zh-CN"), "播客助手");
+        assert_eq!(manifest.name.resolve("zh_cn"), "播客助手");
+        // Same language in another region, then English
+        assert_eq!(manifest.name.resolve("zh-TW"), "播客助手");
+        assert_eq!(manifest.name.resolve("fr"), "Podcast Helper");
+        // No English either: the first listed locale
+        assert_eq!(manifest.description.resolve("en-US"), "为中文播客准备的工具");
+        assert_eq!(manifest.validate(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_validate_locales() {
+        let manifest = |name: &str| -> PluginManifest {
+            serde_json::from_str(&format!(r#"{{ "id": "p", "name": {}, "version": "1.0.0" }}"#, name)).unwrap()
+        };
+
+        assert!(manifest("{}").validate().is_err());
+        assert_eq!(
+            manifest(r#"{ "en": "Plugin", "xx-YY": "?" }"#).validate(),
+            Ok(vec!["Unknown locale \"xx-YY\" in \"name\"".to_string()])
+        );
+        assert_eq!(LocalizedText::Localized(BTreeMap::new()).resolve("en"), "");
+    }
+
+    #[test]
+    fn test_parse_native_manifest() {
+        let json = r#"{
+            "id": "hello-native",
+            "name": "Hello Native",
+            "version": "0.1.0",
+            "type": "native",
+            "library": "mofa_native_plugin_hello"
+        }"#;
+
+        let manifest: PluginManifest = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.r#type, PluginType::Native);
+        assert_eq!(manifest.library.as_deref(), Some("mofa_native_plugin_hello"));
+    }
+
+    #[test]
+    fn test_parse_permissions() {
+        let json = r#"{
+            "id": "test-plugin",
+            "name": "Test Plugin",
+            "version": "1.0.0",
+            "permissions": ["notify", "ai-provider"]
+        }"#;
+
+        let manifest: PluginManifest = serde_json::from_str(json).unwrap();
+        assert!(manifest.has_permission("notify"));
+        assert!(!manifest.has_permission("ai-provider-key"));
     }
 }
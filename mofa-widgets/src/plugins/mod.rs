@@ -1,15 +1,21 @@
 //! Plugin system for MoFA Studio
 //!
 //! Supports two types of plugins:
-//! - Native: Rust + Makepad (compiled into the app)
+//! - Native: Rust + Makepad, a compiled library loaded at runtime (see [`native`])
 //! - WebView: Python + HTML (dynamically loaded)
 
 mod manifest;
 mod loader;
+pub mod control;
+pub mod native;
+pub mod output;
 pub mod screen;
 
-pub use manifest::{PluginManifest, PluginType};
-pub use loader::{PluginLoader, LoadedPlugin};
+pub use manifest::{LocalizedText, PluginManifest, PluginType, FALLBACK_LOCALE};
+pub use loader::{system_language, PluginLoader, LoadedPlugin, PLUGIN_DATA_DIR_ENV};
+pub use control::{AiProviderInfo, ControlServer, PluginNotification, StudioState};
+pub use native::{NativePlugin, NativePluginInfo};
+pub use output::PluginOutput;
 pub use screen::{PluginScreen, PluginScreenRef, PluginScreenWidgetRefExt};
 
 use makepad_widgets::Cx;
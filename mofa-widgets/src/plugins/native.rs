@@ -0,0 +1,235 @@
+//! Native plugins - compiled `cdylib`s loaded at runtime
+//!
+//! A native plugin's manifest names its library; [`NativePlugin::load`]
+//! opens it, checks the version record it exports and only then calls into
+//! it (the ABI is defined in `mofa-plugin-api`). The plugin side catches
+//! panics in every call and reports them as errors here.
+//!
+//! Libraries are never unloaded: the plugin's widgets stay registered with
+//! Makepad, so its code has to outlive any screen. Loaded libraries are kept
+//! in a process-wide map by canonical path, and loading the same file again
+//! (after a plugin rescan, or when safe mode re-enables plugins) hands back
+//! the library that is already loaded.
+//!
+//! Loading needs the `native-plugins` feature; builds without it show an
+//! error in place of the plugin's screen.
+
+use makepad_widgets::{Cx, WidgetRef};
+use mofa_plugin_api::PluginVTable;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Libraries loaded so far, by canonical path
+#[cfg(feature = "native-plugins")]
+static LOADED_LIBRARIES: std::sync::Mutex<Option<std::collections::HashMap<PathBuf, NativePlugin>>> =
+    std::sync::Mutex::new(None);
+
+/// What a native plugin reports about itself
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativePluginInfo {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub description: String,
+}
+
+/// A loaded plugin library; clones share the library
+#[derive(Clone)]
+pub struct NativePlugin {
+    inner: Arc<LoadedLibrary>,
+}
+
+struct LoadedLibrary {
+    path: PathBuf,
+    info: NativePluginInfo,
+    vtable: &'static PluginVTable,
+    /// Whether the plugin's `live_design` ran
+    registered: AtomicBool,
+}
+
+impl fmt::Debug for NativePlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativePlugin")
+            .field("path", &self.inner.path)
+            .field("info", &self.inner.info)
+            .field("registered", &self.inner.registered)
+            .finish_non_exhaustive()
+    }
+}
+
+impl NativePlugin {
+    /// Load the library of plugin `id` and read its info, or reuse it if it
+    /// is loaded already. Fails without running any plugin code if the
+    /// library was built for another ABI or toolchain, and unloads it again
+    /// if it turns out to be another plugin.
+    #[cfg(feature = "native-plugins")]
+    pub fn load(path: &Path, id: &str) -> Result<Self, String> {
+        use mofa_plugin_api::{EntryFn, PluginAbi, ABI_SYMBOL, ENTRY_SYMBOL};
+
+        let path = path
+            .canonicalize()
+            .map_err(|e| format!("Plugin library {}: {}", path.display(), e))?;
+        let mut loaded = LOADED_LIBRARIES.lock().unwrap_or_else(|e| e.into_inner());
+        let loaded = loaded.get_or_insert_with(Default::default);
+        if let Some(plugin) = loaded.get(&path) {
+            check_id(&path, &plugin.inner.info, id)?;
+            return Ok(plugin.clone());
+        }
+
+        // Safety: opening runs the library's initializers. Plugins are code
+        // the user installed, trusted like the studio itself.
+        let library = unsafe { libloading::Library::new(&path) }
+            .map_err(|e| format!("Failed to load {}: {}", path.display(), e))?;
+
+        // Safety: the symbol is a `PluginAbi` static in every library built
+        // with `export_plugin!`; reading it runs no plugin code
+        let abi = unsafe {
+            let abi = library
+                .get::<*const PluginAbi>(ABI_SYMBOL.as_bytes())
+                .map_err(|_| format!("{} is not a MoFA plugin (no {})", path.display(), ABI_SYMBOL))?;
+            (**abi).check()
+        };
+        abi.map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        // Safety: the ABI check passed, so the entry point has the expected
+        // signature and returns a static vtable that lives as long as the
+        // library, which is leaked below
+        let vtable: &'static PluginVTable = unsafe {
+            let entry = library
+                .get::<EntryFn>(ENTRY_SYMBOL.as_bytes())
+                .map_err(|_| format!("{} has no {}", path.display(), ENTRY_SYMBOL))?;
+            entry()
+                .as_ref()
+                .ok_or_else(|| format!("{} returned no vtable", path.display()))?
+        };
+        let info = read_info(vtable).ok_or_else(|| format!("{} panicked in info()", path.display()))?;
+        // Before the library is kept: a mismatch drops (unloads) it here,
+        // having registered nothing
+        check_id(&path, &info, id)?;
+
+        std::mem::forget(library);
+        log::info!("Loaded native plugin {} v{} from {}", info.id, info.version, path.display());
+        let plugin = Self {
+            inner: Arc::new(LoadedLibrary { path: path.clone(), info, vtable, registered: AtomicBool::new(false) }),
+        };
+        loaded.insert(path, plugin.clone());
+        Ok(plugin)
+    }
+
+    /// Without the `native-plugins` feature there is nothing to load with
+    #[cfg(not(feature = "native-plugins"))]
+    pub fn load(path: &Path, _id: &str) -> Result<Self, String> {
+        Err(format!("Cannot load {}: native plugins are disabled in this build", path.display()))
+    }
+
+    /// Canonical path of the library
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    pub fn info(&self) -> &NativePluginInfo {
+        &self.inner.info
+    }
+
+    /// Register the plugin's widgets with Makepad; only the first call in
+    /// the process does anything
+    pub fn live_design(&self, cx: &mut Cx) -> Result<(), String> {
+        let inner = &self.inner;
+        if inner.registered.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        // Safety: the vtable checked out at load time and `cx` is a live `&mut Cx`
+        if !unsafe { (inner.vtable.live_design)((cx as *mut Cx).cast()) } {
+            return Err(format!("Plugin {} panicked in live_design", inner.info.id));
+        }
+        // Widgets registered after startup still need expanding
+        cx.live_expand();
+        cx.live_scan_dependencies();
+        inner.registered.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Create the plugin's screen, registering its widgets first if needed
+    pub fn create_screen(&self, cx: &mut Cx) -> Result<WidgetRef, String> {
+        self.live_design(cx)?;
+        // Safety: as in `live_design`; a non-null result is a boxed `WidgetRef`
+        // handed over to us
+        let screen = unsafe { (self.inner.vtable.create_screen)((cx as *mut Cx).cast()) };
+        if screen.is_null() {
+            return Err(format!("Plugin {} failed to create its screen", self.inner.info.id));
+        }
+        Ok(*unsafe { Box::from_raw(screen.cast::<WidgetRef>()) })
+    }
+}
+
+/// The library at `path` must be the plugin its manifest says it is
+#[cfg(feature = "native-plugins")]
+fn check_id(path: &Path, info: &NativePluginInfo, id: &str) -> Result<(), String> {
+    if info.id != id {
+        return Err(format!("{} is plugin {}, not {}", path.display(), info.id, id));
+    }
+    Ok(())
+}
+
+/// Ask the plugin for its info; `None` if it panicked
+#[cfg(feature = "native-plugins")]
+fn read_info(vtable: &PluginVTable) -> Option<NativePluginInfo> {
+    // Safety: `info()` returns null or a pointer to strings the plugin keeps
+    // for as long as it is loaded
+    let [id, name, version, description] = unsafe { (vtable.info)().as_ref()?.to_strings() };
+    Some(NativePluginInfo { id, name, version, description })
+}
+
+#[cfg(all(test, feature = "native-plugins"))]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    const EXAMPLE_PACKAGE: &str = "mofa-native-plugin-hello";
+
+    /// Build the example plugin into the test's own target directory and
+    /// return the library path
+    fn build_example() -> PathBuf {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        // target/<profile>/deps/<test binary>
+        let exe = std::env::current_exe().unwrap();
+        let target_dir = exe.ancestors().nth(3).unwrap();
+        let status = Command::new(env!("CARGO"))
+            .current_dir(workspace)
+            .args(["build", "-p", EXAMPLE_PACKAGE, "--target-dir"])
+            .arg(target_dir)
+            .status()
+            .expect("cargo runs");
+        assert!(status.success(), "building {} failed", EXAMPLE_PACKAGE);
+
+        let file = format!(
+            "{}{}.{}",
+            std::env::consts::DLL_PREFIX,
+            EXAMPLE_PACKAGE.replace('-', "_"),
+            std::env::consts::DLL_EXTENSION
+        );
+        target_dir.join("debug").join(file)
+    }
+
+    #[test]
+    fn test_load_example_plugin() {
+        let library = build_example();
+        // A manifest naming another id is refused before the library is kept
+        let err = NativePlugin::load(&library, "other").unwrap_err();
+        assert!(err.contains("is plugin hello-native, not other"), "{}", err);
+
+        let plugin = NativePlugin::load(&library, "hello-native").unwrap();
+        assert_eq!(plugin.info().id, "hello-native");
+        assert_eq!(plugin.info().name, "Hello Native");
+
+        // Loading again, as after a rescan, reuses the loaded library
+        let again = NativePlugin::load(&library, "hello-native").unwrap();
+        assert!(Arc::ptr_eq(&plugin.inner, &again.inner));
+        assert!(NativePlugin::load(&library, "other").is_err());
+
+        let err = NativePlugin::load(&library.with_file_name("missing.so"), "hello-native").unwrap_err();
+        assert!(err.contains("missing.so"), "{}", err);
+    }
+}
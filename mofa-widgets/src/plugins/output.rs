@@ -0,0 +1,85 @@
+//! Recent output of plugin server processes
+//!
+//! A WebView plugin's server writes to pipes read by one thread per stream.
+//! Each line is still echoed to the studio's own stdout or stderr, and the
+//! last [`MAX_OUTPUT_LINES`] are kept in the plugin's [`PluginOutput`] for
+//! the diagnostic bundle.
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+/// Lines kept per plugin, across server restarts
+pub const MAX_OUTPUT_LINES: usize = 500;
+
+/// Ring buffer of a plugin server's recent output; clones share the buffer
+#[derive(Debug, Clone, Default)]
+pub struct PluginOutput {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl PluginOutput {
+    /// Keep a line, dropping the oldest once the buffer is full
+    pub fn push(&self, line: String) {
+        let mut lines = self.lines.lock();
+        if lines.len() == MAX_OUTPUT_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    /// Kept lines, oldest first
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.lock().iter().cloned().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.lock().is_empty()
+    }
+
+    /// Read `stream` until it closes, echoing and keeping every line.
+    /// Lines from stderr are kept with a `[stderr]` prefix.
+    pub fn capture(&self, stream: impl Read + Send + 'static, stderr: bool) -> JoinHandle<()> {
+        let output = self.clone();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stream);
+            let mut line = Vec::new();
+            loop {
+                line.clear();
+                match reader.read_until(b'\n', &mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {}
+                }
+                let _ = if stderr {
+                    io::stderr().write_all(&line)
+                } else {
+                    io::stdout().write_all(&line)
+                };
+                let text = String::from_utf8_lossy(&line);
+                let text = text.trim_end_matches(['\r', '\n']);
+                output.push(if stderr { format!("[stderr] {}", text) } else { text.to_string() });
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_capture_keeps_the_last_lines() {
+        let output = PluginOutput::default();
+        let stdout: String = (0..MAX_OUTPUT_LINES + 2).map(|n| format!("line {}\n", n)).collect();
+        output.capture(Cursor::new(stdout), false).join().unwrap();
+        output.capture(Cursor::new(b"Traceback\r\nno newline".to_vec()), true).join().unwrap();
+
+        let lines = output.lines();
+        assert_eq!(lines.len(), MAX_OUTPUT_LINES);
+        assert_eq!(lines[0], "line 4");
+        assert_eq!(lines[MAX_OUTPUT_LINES - 2..], ["[stderr] Traceback", "[stderr] no newline"]);
+    }
+}
@@ -1,8 +1,19 @@
 //! Generic plugin screen - WebView container for dynamic plugins
+//!
+//! Native plugins bring their own screen instead (see [`super::native`]);
+//! once it is created it replaces the WebView and status bar entirely.
+//!
+//! With developer mode on (`mofa-studio --dev`) the status bar gets a "Dev"
+//! button that opens a panel for working on the plugin: reveal its folder,
+//! edit its manifest, restart its server, reload the manifest, and see the
+//! command and port the server runs with.
 
 use makepad_widgets::*;
+use crate::desktop;
 use crate::webview::{WebViewAction, WebViewContainerWidgetExt};
-use super::PluginLoader;
+use crate::webview::export::{self, ExportJob, EXPORT_NOTE_CHANNEL};
+use super::control::PERMISSION_SAVE_FILES;
+use super::{PluginLoader, PluginType};
 use std::sync::{Arc, Mutex};
 
 live_design! {
@@ -85,6 +96,59 @@ live_design! {
         }
     }
 
+    // Developer panel action
+    PluginDevButton = <Button> {
+        width: Fit, height: 26
+        padding: {left: 10, right: 10}
+        draw_bg: {
+            instance dark_mode: 0.0
+            instance hover: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 4.0);
+                let base = mix(
+                    vec4(0.88, 0.89, 0.91, 1.0),
+                    vec4(0.22, 0.24, 0.28, 1.0),
+                    self.dark_mode
+                );
+                let hover_color = mix(
+                    vec4(0.82, 0.84, 0.88, 1.0),
+                    vec4(0.28, 0.30, 0.35, 1.0),
+                    self.dark_mode
+                );
+                sdf.fill(mix(base, hover_color, self.hover));
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: { font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix(
+                    vec4(0.3, 0.3, 0.35, 1.0),
+                    vec4(0.85, 0.85, 0.9, 1.0),
+                    self.dark_mode
+                );
+            }
+        }
+    }
+
+    PluginDevDetail = <Label> {
+        width: Fill
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: { font_size: 10.0 }
+            wrap: Word
+            fn get_color(self) -> vec4 {
+                return mix(
+                    vec4(0.4, 0.4, 0.45, 1.0),
+                    vec4(0.6, 0.6, 0.65, 1.0),
+                    self.dark_mode
+                );
+            }
+        }
+    }
+
     StatusDot = <View> {
         width: 8, height: 8
         show_bg: true
@@ -151,6 +215,39 @@ live_design! {
             }
         }
 
+        // Developer tools, shown above the status bar
+        dev_panel = <View> {
+            visible: false
+            width: Fill, height: Fit
+            flow: Down
+            spacing: 6
+            padding: {left: 12, right: 16, top: 8, bottom: 8}
+            show_bg: true
+            draw_bg: {
+                instance dark_mode: 0.0
+                fn pixel(self) -> vec4 {
+                    return mix(
+                        vec4(0.90, 0.91, 0.93, 1.0),
+                        vec4(0.15, 0.16, 0.20, 1.0),
+                        self.dark_mode
+                    );
+                }
+            }
+
+            dev_actions = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 6
+                open_folder_btn = <PluginDevButton> { text: "Open plugin folder" }
+                open_manifest_btn = <PluginDevButton> { text: "Open manifest" }
+                restart_btn = <PluginDevButton> { text: "Restart server" }
+                reload_manifest_btn = <PluginDevButton> { text: "Reload manifest" }
+            }
+
+            dev_command = <PluginDevDetail> { text: "" }
+            dev_port = <PluginDevDetail> { text: "" }
+        }
+
         status_bar = <View> {
             width: Fill, height: 36
             flow: Right
@@ -213,6 +310,12 @@ live_design! {
                     }
                 }
             }
+
+            dev_btn = <PluginDevButton> {
+                visible: false
+                margin: {left: 8}
+                text: "Dev"
+            }
         }
     }
 }
@@ -241,10 +344,29 @@ pub struct PluginScreen {
     /// Whether we're waiting to load URL
     #[rust]
     pending_url_load: bool,
+
+    /// Whether developer tools are available
+    #[rust]
+    dev_mode: bool,
+
+    /// Screen created by a Native plugin, drawn instead of the WebView
+    #[rust]
+    native_screen: WidgetRef,
+
+    /// Note exports still writing, polled on `export_timer`
+    #[rust]
+    exports: Vec<ExportJob>,
+
+    #[rust]
+    export_timer: Timer,
 }
 
 impl Widget for PluginScreen {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.native_screen.is_empty() {
+            self.native_screen.handle_event(cx, event, scope);
+            return;
+        }
         self.view.handle_event(cx, event, scope);
 
         // Handle timer for delayed URL loading
@@ -255,6 +377,15 @@ impl Widget for PluginScreen {
             }
         }
 
+        if self.export_timer.is_event(event).is_some() {
+            self.poll_exports(cx);
+        }
+
+        // The server can be stopped from the shell's running panel
+        if matches!(event, Event::Timer(_)) && self.url_loaded && !self.is_server_running() {
+            self.show_stopped(cx);
+        }
+
         let actions = match event {
             Event::Actions(actions) => actions.as_slice(),
             _ => &[],
@@ -276,6 +407,10 @@ impl Widget for PluginScreen {
             self.reload();
         }
 
+        if self.dev_mode {
+            self.handle_dev_actions(cx, actions);
+        }
+
         // Handle WebView events
         let our_webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
         let our_uid = our_webview.widget_uid();
@@ -297,6 +432,9 @@ impl Widget for PluginScreen {
                         WebViewAction::InitFailed(err) => {
                             self.set_status(cx, &format!("WebView error: {}", err), 0.0);
                         }
+                        WebViewAction::IpcMessage { channel, data } if channel == EXPORT_NOTE_CHANNEL => {
+                            self.handle_export_note(cx, &data);
+                        }
                         _ => {}
                     }
                 }
@@ -305,6 +443,9 @@ impl Widget for PluginScreen {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.native_screen.is_empty() {
+            return self.native_screen.draw_walk(cx, scope, walk);
+        }
         self.view.draw_walk(cx, scope, walk)
     }
 }
@@ -312,15 +453,93 @@ impl Widget for PluginScreen {
 impl PluginScreen {
     /// Bind this screen to a plugin
     pub fn bind_plugin(&mut self, cx: &mut Cx, plugin_id: String, loader: Arc<Mutex<PluginLoader>>) {
-        self.plugin_id = Some(plugin_id.clone());
-        self.loader = Some(loader.clone());
+        self.plugin_id = Some(plugin_id);
+        self.loader = Some(loader);
+        self.update_plugin_name(cx);
+        self.update_dev_details(cx);
+        if self.is_native() && self.native_screen.is_empty() {
+            self.create_native_screen(cx);
+        }
+    }
 
-        // Update UI with plugin info
-        if let Ok(loader) = loader.lock() {
-            if let Some(plugin) = loader.get_plugin(&plugin_id) {
-                let name = format!("{} v{}", plugin.manifest.name, plugin.manifest.version);
-                self.view.label(ids!(status_bar.plugin_name)).set_text(cx, &name);
+    fn is_native(&self) -> bool {
+        self.with_plugin(|plugin| plugin.manifest.r#type == PluginType::Native)
+            .unwrap_or(false)
+    }
+
+    /// Load the Native plugin's library and show its screen, or the error
+    fn create_native_screen(&mut self, cx: &mut Cx) {
+        let (Some(plugin_id), Some(loader)) = (self.plugin_id.clone(), self.loader.clone()) else {
+            return;
+        };
+        let result = match loader.lock() {
+            Ok(mut loader) => loader.create_native_screen(cx, &plugin_id),
+            Err(_) => Err("Loader unavailable".to_string()),
+        };
+        match result {
+            Ok(screen) => {
+                self.native_screen = screen;
+                self.view.redraw(cx);
+            }
+            Err(e) => {
+                ::log::warn!("Native plugin {}: {}", plugin_id, e);
+                self.view.button(ids!(status_bar.start_btn)).set_visible(cx, false);
+                self.set_status(cx, &format!("Error: {}", e), 0.0);
+            }
+        }
+    }
+
+    /// Show the bound plugin's name, in the loader's language
+    fn update_plugin_name(&mut self, cx: &mut Cx) {
+        let name = self.plugin_id.as_ref().zip(self.loader.as_ref()).and_then(|(id, loader)| {
+            let loader = loader.lock().ok()?;
+            let plugin = loader.get_plugin(id)?;
+            Some(format!("{} v{}", plugin.name(loader.language()), plugin.manifest.version))
+        });
+        if let Some(name) = name {
+            self.view.label(ids!(status_bar.plugin_name)).set_text(cx, &name);
+        }
+    }
+
+    /// Export a note for the page, if the plugin may save files
+    fn handle_export_note(&mut self, cx: &mut Cx, data: &str) {
+        let plugin = self.plugin_id.as_ref().zip(self.loader.as_ref()).and_then(|(id, loader)| {
+            let loader = loader.lock().ok()?;
+            let plugin = loader.get_plugin(id)?;
+            Some((plugin.manifest.has_permission(PERMISSION_SAVE_FILES), plugin.get_url()))
+        });
+        match plugin {
+            Some((true, url)) => {
+                if self.exports.is_empty() {
+                    self.export_timer = cx.start_interval(0.2);
+                }
+                self.exports.push(export::handle_export_note(data, &url.unwrap_or_default()));
+            }
+            _ => {
+                ::log::warn!("Plugin {:?} asked to export a note without the {} permission", self.plugin_id, PERMISSION_SAVE_FILES);
+                let reply = export::permission_denied(data, PERMISSION_SAVE_FILES);
+                let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+                if let Err(e) = webview.send_to_js(EXPORT_NOTE_CHANNEL, &reply.to_string()) {
+                    ::log::warn!("Failed to send export reply: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Reply to the page for each finished export
+    fn poll_exports(&mut self, cx: &mut Cx) {
+        let webview = self.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
+        self.exports.retain(|job| match job.try_reply() {
+            Some(reply) => {
+                if let Err(e) = webview.send_to_js(EXPORT_NOTE_CHANNEL, &reply.to_string()) {
+                    ::log::warn!("Failed to send export reply: {}", e);
+                }
+                false
             }
+            None => true,
+        });
+        if self.exports.is_empty() {
+            cx.stop_timer(self.export_timer);
         }
     }
 
@@ -340,9 +559,7 @@ impl PluginScreen {
             if let Ok(mut loader) = loader.lock() {
                 loader.stop_plugin(&plugin_id);
             }
-            self.set_status(cx, "Stopped", 0.0);
-            self.url_loaded = false;
-            self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Start");
+            self.show_stopped(cx);
         } else {
             self.set_status(cx, "Starting...", 2.0);
 
@@ -356,6 +573,7 @@ impl PluginScreen {
                 Ok(port) => {
                     self.set_status(cx, &format!("Running on port {}", port), 2.0);
                     self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Stop");
+                    self.update_dev_details(cx);
 
                     // Schedule URL load after server has time to start
                     self.pending_url_load = true;
@@ -368,6 +586,13 @@ impl PluginScreen {
         }
     }
 
+    fn show_stopped(&mut self, cx: &mut Cx) {
+        self.set_status(cx, "Stopped", 0.0);
+        self.url_loaded = false;
+        self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Start");
+        self.update_dev_details(cx);
+    }
+
     fn is_server_running(&self) -> bool {
         let Some(plugin_id) = &self.plugin_id else { return false };
         let Some(loader) = &self.loader else { return false };
@@ -416,6 +641,112 @@ impl PluginScreen {
         let _ = webview.reload();
     }
 
+    fn set_dev_mode(&mut self, cx: &mut Cx, enabled: bool) {
+        self.dev_mode = enabled;
+        self.view.button(ids!(status_bar.dev_btn)).set_visible(cx, enabled);
+        if !enabled {
+            self.view.view(ids!(dev_panel)).set_visible(cx, false);
+        }
+        self.update_dev_details(cx);
+        self.view.redraw(cx);
+    }
+
+    fn handle_dev_actions(&mut self, cx: &mut Cx, actions: &[Action]) {
+        if self.view.button(ids!(status_bar.dev_btn)).clicked(actions) {
+            let panel = self.view.view(ids!(dev_panel));
+            panel.set_visible(cx, !panel.visible());
+            self.update_dev_details(cx);
+            self.view.redraw(cx);
+        }
+        if self.view.button(ids!(dev_panel.dev_actions.open_folder_btn)).clicked(actions) {
+            let dir = self.with_plugin(|plugin| plugin.dir.clone());
+            if let Some(Err(e)) = dir.map(|dir| desktop::open_folder(&dir)) {
+                self.set_status(cx, &e, 0.0);
+            }
+        }
+        if self.view.button(ids!(dev_panel.dev_actions.open_manifest_btn)).clicked(actions) {
+            let manifest = self.with_plugin(|plugin| plugin.manifest_path());
+            if let Some(Err(e)) = manifest.map(|path| desktop::open_in_editor(&path)) {
+                self.set_status(cx, &e, 0.0);
+            }
+        }
+        if self.view.button(ids!(dev_panel.dev_actions.restart_btn)).clicked(actions) {
+            self.restart_server(cx);
+        }
+        if self.view.button(ids!(dev_panel.dev_actions.reload_manifest_btn)).clicked(actions) {
+            self.reload_manifest(cx);
+        }
+    }
+
+    fn with_plugin<T>(&self, f: impl FnOnce(&super::LoadedPlugin) -> T) -> Option<T> {
+        let plugin_id = self.plugin_id.as_ref()?;
+        let loader = self.loader.as_ref()?.lock().ok()?;
+        loader.get_plugin(plugin_id).map(f)
+    }
+
+    fn restart_server(&mut self, cx: &mut Cx) {
+        let (Some(plugin_id), Some(loader)) = (self.plugin_id.clone(), self.loader.clone()) else {
+            return;
+        };
+        self.set_status(cx, "Restarting...", 2.0);
+        let result = match loader.lock() {
+            Ok(mut loader) => loader.restart_plugin(&plugin_id),
+            Err(_) => Err("Loader unavailable".to_string()),
+        };
+
+        self.url_loaded = false;
+        match result {
+            Ok(port) => {
+                self.set_status(cx, &format!("Restarted on port {}", port), 2.0);
+                self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Stop");
+                self.pending_url_load = true;
+                self.load_url_timer = cx.start_timeout(1.0);
+            }
+            Err(e) => {
+                self.set_status(cx, &format!("Error: {}", e), 0.0);
+                self.view.button(ids!(status_bar.start_btn)).set_text(cx, "Start");
+            }
+        }
+        self.update_dev_details(cx);
+    }
+
+    fn reload_manifest(&mut self, cx: &mut Cx) {
+        let (Some(plugin_id), Some(loader)) = (self.plugin_id.clone(), self.loader.clone()) else {
+            return;
+        };
+        let result = match loader.lock() {
+            Ok(mut loader) => loader.reload_manifest(&plugin_id),
+            Err(_) => Err("Loader unavailable".to_string()),
+        };
+        match result {
+            Ok(()) => {
+                self.bind_plugin(cx, plugin_id, loader);
+                let status = if self.is_server_running() { 1.0 } else { 0.0 };
+                self.set_status(cx, "Manifest reloaded", status);
+            }
+            Err(e) => self.set_status(cx, &e, 0.0),
+        }
+    }
+
+    /// Show the server command and port in the developer panel
+    fn update_dev_details(&mut self, cx: &mut Cx) {
+        if !self.dev_mode {
+            return;
+        }
+        let details = self.plugin_id.as_ref().zip(self.loader.as_ref()).and_then(|(id, loader)| {
+            let loader = loader.lock().ok()?;
+            let port = loader.get_plugin(id)?.server_port;
+            Some((loader.runtime_command(id)?, port))
+        });
+        let (command, port) = match details {
+            Some((command, Some(port))) => (command, format!("Port: {}", port)),
+            Some((command, None)) => (command, "Port: not running".to_string()),
+            None => (String::new(), String::new()),
+        };
+        self.view.label(ids!(dev_panel.dev_command)).set_text(cx, &format!("Command: {}", command));
+        self.view.label(ids!(dev_panel.dev_port)).set_text(cx, &port);
+    }
+
     fn set_status(&mut self, cx: &mut Cx, text: &str, status: f64) {
         self.view.label(ids!(status_bar.status_text)).set_text(cx, text);
         self.view.view(ids!(status_bar.status_dot)).apply_over(
@@ -435,12 +766,26 @@ impl PluginScreenRef {
         }
     }
 
+    /// Re-read the plugin's name after the studio language changed
+    pub fn refresh_plugin_name(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.update_plugin_name(cx);
+        }
+    }
+
+    /// Show or hide the developer tools (see the module docs)
+    pub fn set_dev_mode(&self, cx: &mut Cx, enabled: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_dev_mode(cx, enabled);
+        }
+    }
+
     /// Bind plugin and automatically start the server
     pub fn bind_plugin_and_start(&self, cx: &mut Cx, plugin_id: String, loader: Arc<Mutex<PluginLoader>>) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.bind_plugin(cx, plugin_id, loader);
-            // Auto-start server if not already running
-            if !inner.is_server_running() {
+            // Auto-start server if not already running; Native plugins have none
+            if !inner.is_native() && !inner.is_server_running() {
                 inner.toggle_server(cx);
             }
         }
@@ -457,11 +802,21 @@ impl PluginScreenRef {
             inner.view.button(ids!(status_bar.reload_btn)).apply_over(cx, live! { draw_bg: { dark_mode: (dark_mode) } draw_text: { dark_mode: (dark_mode) } });
             inner.view.label(ids!(status_bar.status_text)).apply_over(cx, live! { draw_text: { dark_mode: (dark_mode) } });
             inner.view.label(ids!(status_bar.plugin_name)).apply_over(cx, live! { draw_text: { dark_mode: (dark_mode) } });
+            inner.view.button(ids!(status_bar.dev_btn)).apply_over(cx, live! { draw_bg: { dark_mode: (dark_mode) } draw_text: { dark_mode: (dark_mode) } });
+            inner.view.view(ids!(dev_panel)).apply_over(cx, live! { draw_bg: { dark_mode: (dark_mode) } });
+            for button in [
+                ids!(dev_panel.dev_actions.open_folder_btn),
+                ids!(dev_panel.dev_actions.open_manifest_btn),
+                ids!(dev_panel.dev_actions.restart_btn),
+                ids!(dev_panel.dev_actions.reload_manifest_btn),
+            ] {
+                inner.view.button(button).apply_over(cx, live! { draw_bg: { dark_mode: (dark_mode) } draw_text: { dark_mode: (dark_mode) } });
+            }
+            inner.view.label(ids!(dev_panel.dev_command)).apply_over(cx, live! { draw_text: { dark_mode: (dark_mode) } });
+            inner.view.label(ids!(dev_panel.dev_port)).apply_over(cx, live! { draw_text: { dark_mode: (dark_mode) } });
 
             // Send theme to WebView
-            let webview = inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview));
-            let js = format!("if(window.setTheme) window.setTheme({});", dark_mode);
-            let _ = webview.eval(&js);
+            inner.view.web_view_container(ids!(content.webview_area.webview_wrapper.webview)).set_dark_mode(dark_mode);
 
             inner.view.redraw(cx);
         }
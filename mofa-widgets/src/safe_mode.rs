@@ -0,0 +1,135 @@
+//! # Safe Mode
+//!
+//! A process-wide switch for starting the studio without the parts that can
+//! keep it from reaching a usable window: dynamic plugins, servers started
+//! on page switches, and WebViews. The shell enters safe mode at startup
+//! (`--safe-mode`, or after repeated unclean exits) and the rest of the code
+//! asks whether a feature is allowed before starting it:
+//!
+//! ```rust,ignore
+//! use mofa_widgets::safe_mode::{SafeMode, SafeModeFeature};
+//!
+//! if SafeMode::global().allows(SafeModeFeature::WebViews) {
+//!     self.initialize_webview(cx);
+//! }
+//! ```
+//!
+//! The user can re-enable features one by one for the rest of the session.
+//! Re-enabling is one-way: a loaded plugin or a created WebView cannot be
+//! taken back safely, so there is no switching a feature off again.
+
+use parking_lot::Mutex;
+use std::sync::OnceLock;
+
+static GLOBAL: OnceLock<SafeMode> = OnceLock::new();
+
+/// What safe mode keeps from starting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeModeFeature {
+    /// Scanning and loading plugins, and the plugin control API
+    Plugins,
+    /// App servers the shell starts when their page is shown
+    Servers,
+    /// WebView initialization; containers show a placeholder instead
+    WebViews,
+}
+
+impl SafeModeFeature {
+    pub const ALL: [SafeModeFeature; 3] = [Self::Plugins, Self::Servers, Self::WebViews];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Plugins => "Plugins",
+            Self::Servers => "Servers",
+            Self::WebViews => "WebViews",
+        }
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    active: bool,
+    /// Features re-enabled during a safe-mode session
+    enabled: Vec<SafeModeFeature>,
+}
+
+/// Whether the process runs in safe mode and what was re-enabled
+#[derive(Default)]
+pub struct SafeMode {
+    inner: Mutex<Inner>,
+}
+
+impl SafeMode {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The switch shared by the whole process
+    pub fn global() -> &'static SafeMode {
+        GLOBAL.get_or_init(SafeMode::new)
+    }
+
+    /// Turn safe mode on with every feature disabled
+    pub fn enter(&self) {
+        let mut inner = self.inner.lock();
+        inner.active = true;
+        inner.enabled.clear();
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.inner.lock().active
+    }
+
+    /// Whether `feature` may start; always true outside safe mode
+    pub fn allows(&self, feature: SafeModeFeature) -> bool {
+        let inner = self.inner.lock();
+        !inner.active || inner.enabled.contains(&feature)
+    }
+
+    /// Allow `feature` for the rest of the session. Returns false if it was
+    /// already allowed.
+    pub fn enable(&self, feature: SafeModeFeature) -> bool {
+        let mut inner = self.inner.lock();
+        if !inner.active || inner.enabled.contains(&feature) {
+            return false;
+        }
+        inner.enabled.push(feature);
+        true
+    }
+
+    /// Features still disabled, in [`SafeModeFeature::ALL`] order
+    pub fn disabled(&self) -> Vec<SafeModeFeature> {
+        let inner = self.inner.lock();
+        if !inner.active {
+            return Vec::new();
+        }
+        SafeModeFeature::ALL
+            .into_iter()
+            .filter(|f| !inner.enabled.contains(f))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_features_are_re_enabled_one_by_one() {
+        let safe_mode = SafeMode::new();
+        assert!(safe_mode.allows(SafeModeFeature::WebViews));
+        assert!(!safe_mode.enable(SafeModeFeature::WebViews));
+        assert!(safe_mode.disabled().is_empty());
+
+        safe_mode.enter();
+        assert!(safe_mode.is_active());
+        assert!(!safe_mode.allows(SafeModeFeature::Plugins));
+        assert_eq!(safe_mode.disabled(), SafeModeFeature::ALL);
+
+        assert!(safe_mode.enable(SafeModeFeature::Servers));
+        assert!(!safe_mode.enable(SafeModeFeature::Servers));
+        assert!(safe_mode.allows(SafeModeFeature::Servers));
+        assert!(!safe_mode.allows(SafeModeFeature::WebViews));
+        assert_eq!(safe_mode.disabled(), [SafeModeFeature::Plugins, SafeModeFeature::WebViews]);
+    }
+}
@@ -0,0 +1,699 @@
+//! # Searchable Drop Down Widget
+//!
+//! A dropdown for long option lists (system voices, audio devices). Opening
+//! it shows a filter field above the list; typing narrows the options to
+//! those containing the text, with the matching part highlighted. Options
+//! can be grouped under section headers; typing a section's name keeps the
+//! whole section.
+//!
+//! Keyboard: Up/Down move through the matches, Return picks the highlighted
+//! one, Escape closes. Clicking outside closes the list too.
+//!
+//! The list opens below the field inside the layout rather than floating
+//! over it, so the dropdown also works inside scroll views and panels.
+//!
+//! ## Usage
+//!
+//! The API follows `DropDown`, so call sites only change the widget type
+//! and the `ids!` accessor:
+//!
+//! ```rust,ignore
+//! live_design! {
+//!     use mofa_widgets::searchable_drop_down::SearchableDropDown;
+//!
+//!     voice_dropdown = <SearchableDropDown> {
+//!         empty_text: "Search voices"
+//!     }
+//! }
+//!
+//! let dropdown = self.view.searchable_drop_down(ids!(voice_dropdown));
+//! dropdown.set_grouped_labels(cx, vec![
+//!     ("Chinese".to_string(), vec!["Ting-Ting".to_string(), "Mei-Jia".to_string()]),
+//!     ("English".to_string(), vec!["Samantha".to_string()]),
+//! ]);
+//! dropdown.set_selected_item(cx, 2);
+//!
+//! // Indexes count options across sections, headers excluded
+//! if let Some(index) = dropdown.selected(actions) {
+//!     ...
+//! }
+//! ```
+
+use makepad_widgets::*;
+use std::ops::Range;
+
+/// Rows shown before the list scrolls
+pub const MAX_VISIBLE_ROWS: usize = 8;
+
+/// Height of a list row, headers included
+const ROW_HEIGHT: f64 = 28.0;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::theme::*;
+
+    SearchableDropDownText = <Label> {
+        width: Fit
+        text: ""
+        draw_text: {
+            instance dark_mode: 0.0
+            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+            }
+        }
+    }
+
+    SearchableDropDownHeader = <View> {
+        width: Fill, height: 28
+        padding: {left: 10, right: 10, top: 8}
+        header_label = <Label> {
+            text: ""
+            draw_text: {
+                instance dark_mode: 0.0
+                text_style: <FONT_SEMIBOLD>{ font_size: 9.0 }
+                fn get_color(self) -> vec4 {
+                    return mix((TEXT_MUTED), (TEXT_MUTED_DARK), self.dark_mode);
+                }
+            }
+        }
+    }
+
+    SearchableDropDownItem = <View> {
+        width: Fill, height: 28
+        padding: {left: 16, right: 10}
+        align: {y: 0.5}
+        cursor: Hand
+        show_bg: true
+        draw_bg: {
+            instance dark_mode: 0.0
+            instance highlighted: 0.0
+            instance selected: 0.0
+            fn pixel(self) -> vec4 {
+                let base = mix((WHITE), (SLATE_800), self.dark_mode);
+                let selected = mix((BLUE_50), (SLATE_700), self.dark_mode);
+                let highlighted = mix((GRAY_100), (SLATE_600), self.dark_mode);
+                return mix(mix(base, selected, self.selected), highlighted, self.highlighted);
+            }
+        }
+
+        // The option's label split around the filter match
+        before = <SearchableDropDownText> {}
+        matched = <SearchableDropDownText> {
+            draw_text: {
+                text_style: <FONT_BOLD>{ font_size: 11.0 }
+                fn get_color(self) -> vec4 {
+                    return mix((ACCENT_BLUE), (ACCENT_BLUE_DARK), self.dark_mode);
+                }
+            }
+        }
+        after = <SearchableDropDownText> {}
+    }
+
+    pub SearchableDropDown = {{SearchableDropDown}} {
+        width: Fill, height: Fit
+        flow: Down
+        spacing: 2
+
+        field = <View> {
+            width: Fill, height: 32
+            padding: {left: 10, right: 10}
+            align: {y: 0.5}
+            spacing: 6
+            cursor: Hand
+            show_bg: true
+            draw_bg: {
+                instance dark_mode: 0.0
+                instance open: 0.0
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.box(1., 1., self.rect_size.x - 2., self.rect_size.y - 2., 4.0);
+                    sdf.fill(mix((SLATE_100), (SLATE_700), self.dark_mode));
+                    let border = mix((BORDER), (BORDER_DARK), self.dark_mode);
+                    sdf.stroke(mix(border, (ACCENT_BLUE), self.open), 1.0);
+                    return sdf.result;
+                }
+            }
+
+            selected_label = <SearchableDropDownText> {
+                width: Fill
+            }
+
+            chevron = <View> {
+                width: 9, height: 5
+                show_bg: true
+                draw_bg: {
+                    instance dark_mode: 0.0
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.move_to(0., 0.);
+                        sdf.line_to(self.rect_size.x, 0.);
+                        sdf.line_to(self.rect_size.x * 0.5, self.rect_size.y);
+                        sdf.close_path();
+                        sdf.fill(mix((GRAY_500), (SLATE_400), self.dark_mode));
+                        return sdf.result;
+                    }
+                }
+            }
+        }
+
+        popup = <View> {
+            visible: false
+            width: Fill, height: Fit
+            flow: Down
+            padding: 4
+            spacing: 4
+            show_bg: true
+            draw_bg: {
+                instance dark_mode: 0.0
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.box(1., 1., self.rect_size.x - 2., self.rect_size.y - 2., 4.0);
+                    sdf.fill(mix((WHITE), (SLATE_800), self.dark_mode));
+                    sdf.stroke(mix((BORDER), (BORDER_DARK), self.dark_mode), 1.0);
+                    return sdf.result;
+                }
+            }
+
+            filter_input = <TextInput> {
+                width: Fill, height: 30
+                padding: {left: 8, right: 8}
+                empty_text: "Search"
+                draw_bg: {
+                    instance dark_mode: 0.0
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 3.0);
+                        sdf.fill(mix((SLATE_50), (SLATE_900), self.dark_mode));
+                        return sdf.result;
+                    }
+                }
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                    fn get_color(self) -> vec4 {
+                        return mix((TEXT_PRIMARY), (TEXT_PRIMARY_DARK), self.dark_mode);
+                    }
+                }
+            }
+
+            option_list = <PortalList> {
+                width: Fill, height: 224
+                flow: Down
+
+                header_item = <SearchableDropDownHeader> {}
+                option_item = <SearchableDropDownItem> {}
+            }
+
+            empty_label = <Label> {
+                visible: false
+                width: Fill
+                padding: {left: 10, top: 6, bottom: 6}
+                text: "No matches"
+                draw_text: {
+                    instance dark_mode: 0.0
+                    text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                    fn get_color(self) -> vec4 {
+                        return mix((TEXT_SECONDARY), (TEXT_SECONDARY_DARK), self.dark_mode);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Options with optional section headers
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchableOptions {
+    labels: Vec<String>,
+    /// Section name and the index of its first option, in order
+    sections: Vec<(String, usize)>,
+}
+
+/// A row of the filtered list
+#[derive(Clone, Debug, PartialEq)]
+pub enum OptionRow {
+    Header(String),
+    Option {
+        index: usize,
+        /// Byte range of the label matching the filter
+        matched: Option<Range<usize>>,
+    },
+}
+
+impl SearchableOptions {
+    /// Options without sections
+    pub fn flat(labels: Vec<String>) -> Self {
+        Self { labels, sections: Vec::new() }
+    }
+
+    /// Options grouped under section headers; empty sections are left out
+    pub fn grouped(groups: Vec<(String, Vec<String>)>) -> Self {
+        let mut options = Self::default();
+        for (name, labels) in groups {
+            if labels.is_empty() {
+                continue;
+            }
+            options.sections.push((name, options.labels.len()));
+            options.labels.extend(labels);
+        }
+        options
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn label(&self, index: usize) -> Option<&str> {
+        self.labels.get(index).map(String::as_str)
+    }
+
+    /// Rows for `query`: options containing it (ignoring case) under their
+    /// headers, or every option of a section whose name contains it.
+    /// Headers of sections with no match are dropped.
+    pub fn filter(&self, query: &str) -> Vec<OptionRow> {
+        let query = query.trim();
+        let mut rows = Vec::new();
+        // Options before the first header form an unnamed section
+        let starts = std::iter::once(0).chain(self.sections.iter().map(|(_, start)| *start));
+        let ends = self.sections.iter().map(|(_, start)| *start).chain(std::iter::once(self.labels.len()));
+        let names = std::iter::once(None).chain(self.sections.iter().map(|(name, _)| Some(name.as_str())));
+        for ((start, end), name) in starts.zip(ends).zip(names) {
+            let section_matches = name.is_some_and(|name| find_match(name, query).is_some());
+            let options: Vec<OptionRow> = (start..end)
+                .filter_map(|index| {
+                    let matched = find_match(&self.labels[index], query);
+                    (query.is_empty() || section_matches || matched.is_some())
+                        .then_some(OptionRow::Option { index, matched })
+                })
+                .collect();
+            if options.is_empty() {
+                continue;
+            }
+            if let Some(name) = name {
+                rows.push(OptionRow::Header(name.to_string()));
+            }
+            rows.extend(options);
+        }
+        rows
+    }
+}
+
+/// Byte range of the first occurrence of `query` in `text`, ignoring case;
+/// `None` for an empty query
+pub fn find_match(text: &str, query: &str) -> Option<Range<usize>> {
+    let query: Vec<char> = query.chars().collect();
+    if query.is_empty() {
+        return None;
+    }
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let start = chars.windows(query.len()).position(|window| {
+        window.iter().zip(&query).all(|(&(_, a), &b)| a == b || a.to_lowercase().eq(b.to_lowercase()))
+    })?;
+    let end = chars.get(start + query.len()).map_or(text.len(), |&(i, _)| i);
+    Some(chars[start].0..end)
+}
+
+/// Row of the next option after (or before) row `from`, skipping headers.
+/// Without a current row, Down starts at the first option and Up at the
+/// last; at either end the highlight stays put.
+pub fn step_highlight(rows: &[OptionRow], from: Option<usize>, down: bool) -> Option<usize> {
+    let is_option = |row: &usize| matches!(rows[*row], OptionRow::Option { .. });
+    let next = match (from, down) {
+        (None, true) => (0..rows.len()).find(is_option),
+        (None, false) => (0..rows.len()).rev().find(is_option),
+        (Some(from), true) => (from + 1..rows.len()).find(is_option),
+        (Some(from), false) => (0..from.min(rows.len())).rev().find(is_option),
+    };
+    next.or(from)
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum SearchableDropDownAction {
+    None,
+    /// The user picked the option at this index
+    Select(usize),
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct SearchableDropDown {
+    #[deref]
+    view: View,
+
+    /// Placeholder of the filter field
+    #[live]
+    empty_text: String,
+
+    #[rust]
+    options: SearchableOptions,
+
+    #[rust]
+    selected_item: usize,
+
+    #[rust]
+    open: bool,
+
+    /// Rows matching the current filter
+    #[rust]
+    rows: Vec<OptionRow>,
+
+    /// Row picked with the arrow keys
+    #[rust]
+    highlighted: Option<usize>,
+
+    #[rust]
+    dark_mode: f64,
+}
+
+impl Widget for SearchableDropDown {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if self.open {
+            match event {
+                Event::KeyDown(key) => match key.key_code {
+                    KeyCode::ArrowDown | KeyCode::ArrowUp => {
+                        let down = key.key_code == KeyCode::ArrowDown;
+                        self.highlighted = step_highlight(&self.rows, self.highlighted, down);
+                        self.scroll_to_highlight(cx);
+                        self.view.redraw(cx);
+                    }
+                    KeyCode::ReturnKey => {
+                        if let Some(OptionRow::Option { index, .. }) = self.highlighted.and_then(|row| self.rows.get(row)) {
+                            let index = *index;
+                            self.choose(cx, scope, index);
+                        }
+                    }
+                    KeyCode::Escape => self.set_open(cx, false),
+                    _ => {}
+                },
+                Event::MouseDown(mouse) if !self.view.area().rect(cx).contains(mouse.abs) => {
+                    self.set_open(cx, false);
+                }
+                _ => {}
+            }
+        }
+
+        let actions = match event {
+            Event::Actions(actions) => actions.as_slice(),
+            _ => return,
+        };
+
+        if self.view.view(ids!(field)).finger_up(actions).is_some() {
+            let open = !self.open;
+            self.set_open(cx, open);
+        }
+        if !self.open {
+            return;
+        }
+
+        if self.view.text_input(ids!(popup.filter_input)).changed(actions).is_some() {
+            self.apply_filter(cx);
+        }
+
+        let list = self.view.portal_list(ids!(popup.option_list));
+        for (row, item) in list.items_with_actions(actions) {
+            if item.as_view().finger_up(actions).is_none() {
+                continue;
+            }
+            if let Some(&OptionRow::Option { index, .. }) = self.rows.get(row) {
+                self.choose(cx, scope, index);
+                break;
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, self.rows.len());
+
+                while let Some(row) = list.next_visible_item(cx) {
+                    let Some(option_row) = self.rows.get(row) else {
+                        continue;
+                    };
+                    let dm = self.dark_mode;
+                    match option_row {
+                        OptionRow::Header(name) => {
+                            let item = list.item(cx, row, live_id!(header_item));
+                            item.label(ids!(header_label)).set_text(cx, name);
+                            item.apply_over(cx, live!{
+                                header_label = { draw_text: { dark_mode: (dm) } }
+                            });
+                            item.draw_all(cx, scope);
+                        }
+                        OptionRow::Option { index, matched } => {
+                            let item = list.item(cx, row, live_id!(option_item));
+                            let label = self.options.label(*index).unwrap_or_default();
+                            let (before, found, after) = match matched {
+                                Some(range) => (&label[..range.start], &label[range.clone()], &label[range.end..]),
+                                None => (label, "", ""),
+                            };
+                            item.label(ids!(before)).set_text(cx, before);
+                            item.label(ids!(matched)).set_text(cx, found);
+                            item.label(ids!(after)).set_text(cx, after);
+
+                            let highlighted = if self.highlighted == Some(row) { 1.0 } else { 0.0 };
+                            let selected = if *index == self.selected_item { 1.0 } else { 0.0 };
+                            item.apply_over(cx, live!{
+                                draw_bg: { dark_mode: (dm) highlighted: (highlighted) selected: (selected) }
+                                before = { draw_text: { dark_mode: (dm) } }
+                                matched = { draw_text: { dark_mode: (dm) } }
+                                after = { draw_text: { dark_mode: (dm) } }
+                            });
+                            item.draw_all(cx, scope);
+                        }
+                    }
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+impl SearchableDropDown {
+    fn set_open(&mut self, cx: &mut Cx, open: bool) {
+        self.open = open;
+        let filter_input = self.view.text_input(ids!(popup.filter_input));
+        if open {
+            if !self.empty_text.is_empty() {
+                let empty_text = self.empty_text.clone();
+                filter_input.apply_over(cx, live!{ empty_text: (empty_text) });
+            }
+            filter_input.set_text(cx, "");
+            self.apply_filter(cx);
+            filter_input.set_key_focus(cx);
+        }
+        self.view.view(ids!(popup)).set_visible(cx, open);
+        let open = if open { 1.0 } else { 0.0 };
+        self.view.view(ids!(field)).apply_over(cx, live!{
+            draw_bg: { open: (open) }
+        });
+        self.view.redraw(cx);
+    }
+
+    fn apply_filter(&mut self, cx: &mut Cx) {
+        let query = self.view.text_input(ids!(popup.filter_input)).text();
+        self.rows = self.options.filter(&query);
+        // Keep the current choice highlighted while it matches
+        self.highlighted = self.rows
+            .iter()
+            .position(|row| matches!(row, OptionRow::Option { index, .. } if *index == self.selected_item))
+            .or_else(|| step_highlight(&self.rows, None, true));
+
+        let visible_rows = self.rows.len().clamp(1, MAX_VISIBLE_ROWS);
+        let list = self.view.portal_list(ids!(popup.option_list));
+        list.apply_over(cx, live!{ height: (visible_rows as f64 * ROW_HEIGHT) });
+        list.set_visible(cx, !self.rows.is_empty());
+        self.scroll_to_highlight(cx);
+
+        let empty_label = self.view.label(ids!(popup.empty_label));
+        empty_label.set_text(cx, &format!("No matches for \"{}\"", query.trim()));
+        empty_label.set_visible(cx, self.rows.is_empty());
+        self.view.redraw(cx);
+    }
+
+    /// Scroll the list so the highlighted row is in view
+    fn scroll_to_highlight(&mut self, cx: &mut Cx) {
+        let Some(row) = self.highlighted else {
+            return;
+        };
+        let list = self.view.portal_list(ids!(popup.option_list));
+        let first = list.first_id();
+        if row < first {
+            list.set_first_id_and_scroll(row, 0.0);
+        } else if row >= first + MAX_VISIBLE_ROWS {
+            list.set_first_id_and_scroll(row + 1 - MAX_VISIBLE_ROWS, 0.0);
+        }
+        list.redraw(cx);
+    }
+
+    /// Apply a choice made by the user, close and tell the parent
+    fn choose(&mut self, cx: &mut Cx, scope: &mut Scope, index: usize) {
+        self.set_selected_inner(cx, index);
+        self.set_open(cx, false);
+        cx.widget_action(self.widget_uid(), &scope.path, SearchableDropDownAction::Select(index));
+    }
+
+    fn set_selected_inner(&mut self, cx: &mut Cx, index: usize) {
+        self.selected_item = index;
+        let label = self.options.label(index).unwrap_or_default().to_string();
+        self.view.label(ids!(field.selected_label)).set_text(cx, &label);
+        self.view.redraw(cx);
+    }
+
+    fn set_options(&mut self, cx: &mut Cx, options: SearchableOptions) {
+        self.options = options;
+        let selected = self.selected_item.min(self.options.len().saturating_sub(1));
+        self.set_selected_inner(cx, selected);
+        if self.open {
+            self.apply_filter(cx);
+        }
+    }
+}
+
+impl SearchableDropDownRef {
+    /// Replace the options with a flat list, like `DropDown::set_labels`
+    pub fn set_labels(&self, cx: &mut Cx, labels: Vec<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_options(cx, SearchableOptions::flat(labels));
+        }
+    }
+
+    /// Replace the options with sections of labels. Option indexes run
+    /// through all sections in order.
+    pub fn set_grouped_labels(&self, cx: &mut Cx, groups: Vec<(String, Vec<String>)>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_options(cx, SearchableOptions::grouped(groups));
+        }
+    }
+
+    /// Select an option from code; emits no action
+    pub fn set_selected_item(&self, cx: &mut Cx, index: usize) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if index < inner.options.len() {
+                inner.set_selected_inner(cx, index);
+            }
+        }
+    }
+
+    pub fn selected_item(&self) -> usize {
+        self.borrow().map(|inner| inner.selected_item).unwrap_or(0)
+    }
+
+    pub fn selected_label(&self) -> String {
+        self.borrow()
+            .and_then(|inner| inner.options.label(inner.selected_item).map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    /// Returns the option the user picked in these actions
+    pub fn selected(&self, actions: &Actions) -> Option<usize> {
+        let uid = self.widget_uid();
+        actions.iter().find_map(|action| {
+            action.as_widget_action().and_then(|wa| {
+                if wa.widget_uid != uid {
+                    return None;
+                }
+                match wa.cast() {
+                    SearchableDropDownAction::Select(index) => Some(index),
+                    _ => None,
+                }
+            })
+        })
+    }
+
+    /// Update dark mode for the field, filter and list
+    pub fn update_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.dark_mode = dark_mode;
+            inner.view.view(ids!(field)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+                selected_label = { draw_text: { dark_mode: (dark_mode) } }
+                chevron = { draw_bg: { dark_mode: (dark_mode) } }
+            });
+            inner.view.view(ids!(popup)).apply_over(cx, live!{
+                draw_bg: { dark_mode: (dark_mode) }
+                filter_input = { draw_bg: { dark_mode: (dark_mode) } draw_text: { dark_mode: (dark_mode) } }
+                empty_label = { draw_text: { dark_mode: (dark_mode) } }
+            });
+            inner.view.redraw(cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_match_ignores_case() {
+        assert_eq!(find_match("Samantha (English)", "ENG"), Some(10..13));
+        assert_eq!(find_match("Mei-Jia", "jia"), Some(4..7));
+        assert_eq!(find_match("Amélie", "ÉLIE"), Some(2..7));
+        assert_eq!(find_match("MacBook Pro Speakers", "mic"), None);
+        assert_eq!(find_match("anything", ""), None);
+    }
+
+    #[test]
+    fn test_filter_keeps_headers_of_matching_sections() {
+        let options = SearchableOptions::grouped(vec![
+            ("Chinese".to_string(), labels(&["Ting-Ting", "Mei-Jia"])),
+            ("Empty".to_string(), Vec::new()),
+            ("English".to_string(), labels(&["Samantha", "Alex", "Daniel"])),
+        ]);
+        assert_eq!(options.len(), 5);
+        assert_eq!(options.filter("").len(), 7);
+
+        assert_eq!(
+            options.filter(" an "),
+            vec![
+                OptionRow::Header("English".to_string()),
+                OptionRow::Option { index: 2, matched: Some(3..5) },
+                OptionRow::Option { index: 4, matched: Some(1..3) },
+            ]
+        );
+        // A section's name keeps all of its options
+        assert_eq!(
+            options.filter("chin"),
+            vec![
+                OptionRow::Header("Chinese".to_string()),
+                OptionRow::Option { index: 0, matched: None },
+                OptionRow::Option { index: 1, matched: None },
+            ]
+        );
+        assert!(options.filter("xyz").is_empty());
+
+        let flat = SearchableOptions::flat(labels(&["Built-in Microphone", "USB Mic"]));
+        assert_eq!(flat.filter("mic").len(), 2);
+        assert_eq!(flat.label(1), Some("USB Mic"));
+    }
+
+    #[test]
+    fn test_step_highlight_skips_headers() {
+        let rows = vec![
+            OptionRow::Header("A".to_string()),
+            OptionRow::Option { index: 0, matched: None },
+            OptionRow::Header("B".to_string()),
+            OptionRow::Option { index: 1, matched: None },
+        ];
+        assert_eq!(step_highlight(&rows, None, true), Some(1));
+        assert_eq!(step_highlight(&rows, None, false), Some(3));
+        assert_eq!(step_highlight(&rows, Some(1), true), Some(3));
+        assert_eq!(step_highlight(&rows, Some(3), true), Some(3));
+        assert_eq!(step_highlight(&rows, Some(3), false), Some(1));
+        assert_eq!(step_highlight(&rows, Some(1), false), Some(1));
+        assert_eq!(step_highlight(&[], None, true), None);
+    }
+}
@@ -0,0 +1,138 @@
+//! # App Shortcuts
+//!
+//! A process-wide registry of keyboard shortcuts scoped to one app. An app
+//! registers its keys with a command name; the shell tells the registry
+//! which app is in front whenever the page or tab changes; the app's screen
+//! asks for the command behind each key event and only gets one while its
+//! app is active.
+//!
+//! ```rust,ignore
+//! use mofa_widgets::shortcuts::ShortcutRegistry;
+//!
+//! let shortcuts = ShortcutRegistry::global();
+//! shortcuts.register("mofa-personal-news", KeyCode::KeyJ, "next", "Next article");
+//! // shell, when the page changes
+//! shortcuts.set_active_app(Some("mofa-personal-news"));
+//! // screen, in handle_event
+//! if let Some(command) = shortcuts.command("mofa-personal-news", event) {
+//!     self.send_to_page("news_command", &json!({ "command": command }));
+//! }
+//! ```
+//!
+//! Shortcuts are plain key presses: with Ctrl, Alt or Cmd held the key is
+//! left to the focused widget. Keys typed into a WebView never reach the
+//! shell, so a page keeps its own keyboard handling while it has focus.
+
+use makepad_widgets::{Event, KeyCode, KeyModifiers};
+use parking_lot::Mutex;
+use std::sync::OnceLock;
+
+static GLOBAL: OnceLock<ShortcutRegistry> = OnceLock::new();
+
+/// A key bound to a command of one app
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppShortcut {
+    pub app_id: String,
+    pub key: KeyCode,
+    /// Name the app acts on, e.g. "next"
+    pub command: String,
+    /// Shown to the user, e.g. "Next article"
+    pub description: String,
+}
+
+#[derive(Default)]
+struct Inner {
+    shortcuts: Vec<AppShortcut>,
+    active_app: Option<String>,
+}
+
+/// Shortcuts of all apps and which app currently receives them
+#[derive(Default)]
+pub struct ShortcutRegistry {
+    inner: Mutex<Inner>,
+}
+
+impl ShortcutRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The registry shared by all apps in the process
+    pub fn global() -> &'static ShortcutRegistry {
+        GLOBAL.get_or_init(ShortcutRegistry::new)
+    }
+
+    /// Bind `key` to `command` for `app_id`, replacing an earlier binding of
+    /// the same key in that app
+    pub fn register(&self, app_id: &str, key: KeyCode, command: &str, description: &str) {
+        let mut inner = self.inner.lock();
+        inner.shortcuts.retain(|s| !(s.app_id == app_id && s.key == key));
+        inner.shortcuts.push(AppShortcut {
+            app_id: app_id.to_string(),
+            key,
+            command: command.to_string(),
+            description: description.to_string(),
+        });
+    }
+
+    /// App in front; `None` while no app page is (e.g. a tab covers it)
+    pub fn set_active_app(&self, app_id: Option<&str>) {
+        self.inner.lock().active_app = app_id.map(str::to_string);
+    }
+
+    pub fn active_app(&self) -> Option<String> {
+        self.inner.lock().active_app.clone()
+    }
+
+    /// Shortcuts registered by one app, in registration order
+    pub fn shortcuts(&self, app_id: &str) -> Vec<AppShortcut> {
+        self.inner.lock().shortcuts.iter().filter(|s| s.app_id == app_id).cloned().collect()
+    }
+
+    /// Command of `app_id` bound to this key press, if the app is active
+    pub fn command(&self, app_id: &str, event: &Event) -> Option<String> {
+        match event {
+            Event::KeyDown(key) => self.command_for_key(app_id, key.key_code, &key.modifiers),
+            _ => None,
+        }
+    }
+
+    fn command_for_key(&self, app_id: &str, key: KeyCode, modifiers: &KeyModifiers) -> Option<String> {
+        if modifiers.control || modifiers.alt || modifiers.logo {
+            return None;
+        }
+        let inner = self.inner.lock();
+        if inner.active_app.as_deref() != Some(app_id) {
+            return None;
+        }
+        inner
+            .shortcuts
+            .iter()
+            .find(|s| s.app_id == app_id && s.key == key)
+            .map(|s| s.command.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands_follow_active_app() {
+        let registry = ShortcutRegistry::new();
+        registry.register("mofa-personal-news", KeyCode::KeyJ, "next", "Next article");
+        registry.register("mofa-personal-news", KeyCode::KeyJ, "down", "Scroll down");
+        registry.register("mofa-note-taker", KeyCode::KeyJ, "other", "Other app");
+        let plain = KeyModifiers::default();
+
+        assert_eq!(registry.command_for_key("mofa-personal-news", KeyCode::KeyJ, &plain), None);
+        registry.set_active_app(Some("mofa-personal-news"));
+        assert_eq!(registry.command_for_key("mofa-personal-news", KeyCode::KeyJ, &plain).as_deref(), Some("down"));
+        assert_eq!(registry.command_for_key("mofa-note-taker", KeyCode::KeyJ, &plain), None);
+        assert_eq!(registry.command_for_key("mofa-personal-news", KeyCode::KeyK, &plain), None);
+
+        let ctrl = KeyModifiers { control: true, ..Default::default() };
+        assert_eq!(registry.command_for_key("mofa-personal-news", KeyCode::KeyJ, &ctrl), None);
+        assert_eq!(registry.shortcuts("mofa-personal-news").len(), 1);
+    }
+}
@@ -0,0 +1,147 @@
+//! # Tab State
+//!
+//! Keeps a screen's scroll offsets and text focus while its tab is hidden.
+//! Hiding a page drops key focus, and a `ScrollYView` that is drawn again
+//! after being invisible starts from the top, so switching tabs loses both.
+//! A `PortalList` keeps its first visible item and that item's offset.
+//!
+//! A screen lists the views to keep once, saves them from
+//! [`TabLifecycle::on_deactivate`](crate::TabLifecycle) and asks for a restore
+//! from `on_activate`. The restore runs after the page's first draw, when the
+//! scroll views know their content size again:
+//!
+//! ```rust,ignore
+//! let state = TabState::new()
+//!     .scroll(ids!(chat_section.chat_scroll))
+//!     .focusable(ids!(prompt_row.prompt_input));
+//!
+//! // on_deactivate
+//! inner.tab_state.save(cx, &inner.view);
+//! // on_activate
+//! inner.tab_state.restore_after_draw();
+//! // draw_walk, once the view is done
+//! self.tab_state.apply_pending(cx, &self.view);
+//! ```
+//!
+//! WebView tabs need none of this: deactivating only hides the native view,
+//! so the page keeps its own scroll position and focused element.
+
+use makepad_widgets::*;
+
+/// Scroll offsets and focus of one screen, kept across tab switches
+#[derive(Debug, Default)]
+pub struct TabState {
+    /// Tracked scroll views and their offsets at the last save
+    scrolls: Vec<(Vec<LiveId>, Option<DVec2>)>,
+    /// Tracked portal lists and their first item and its offset at the last save
+    portal_lists: Vec<(Vec<LiveId>, Option<(usize, f64)>)>,
+    /// Tracked widgets that can hold key focus
+    focusables: Vec<Vec<LiveId>>,
+    /// Index into `focusables` of the widget focused at the last save
+    focused: Option<usize>,
+    /// Restore on the next finished draw
+    pending: bool,
+}
+
+impl TabState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the offset of a scrolling `View` (path from the screen's root)
+    pub fn scroll(mut self, path: &[LiveId]) -> Self {
+        self.scrolls.push((path.to_vec(), None));
+        self
+    }
+
+    /// Keep the scroll position of a `PortalList` (path from the screen's root)
+    pub fn portal_list(mut self, path: &[LiveId]) -> Self {
+        self.portal_lists.push((path.to_vec(), None));
+        self
+    }
+
+    /// Give key focus back to this widget if it had it when the tab was left
+    pub fn focusable(mut self, path: &[LiveId]) -> Self {
+        self.focusables.push(path.to_vec());
+        self
+    }
+
+    /// Nothing is tracked yet
+    pub fn is_empty(&self) -> bool {
+        self.scrolls.is_empty() && self.portal_lists.is_empty() && self.focusables.is_empty()
+    }
+
+    /// Remember the current offsets and focus; call before the tab is hidden
+    pub fn save(&mut self, cx: &Cx, view: &View) {
+        for (path, saved) in &mut self.scrolls {
+            *saved = Some(view.view(path).get_scroll_pos());
+        }
+        for (path, saved) in &mut self.portal_lists {
+            let list = view.portal_list(path);
+            *saved = Some((list.first_id(), list.scroll_position()));
+        }
+        self.focused = self
+            .focusables
+            .iter()
+            .position(|path| cx.has_key_focus(view.widget(path).area()));
+        self.pending = false;
+    }
+
+    /// Restore what [`save`](Self::save) kept once the page is drawn again
+    pub fn restore_after_draw(&mut self) {
+        self.pending = self.scrolls.iter().any(|(_, saved)| saved.is_some())
+            || self.portal_lists.iter().any(|(_, saved)| saved.is_some())
+            || self.focused.is_some();
+    }
+
+    /// Apply a pending restore; call at the end of the screen's `draw_walk`.
+    /// Returns whether anything was restored.
+    pub fn apply_pending(&mut self, cx: &mut Cx, view: &View) -> bool {
+        if !std::mem::take(&mut self.pending) {
+            return false;
+        }
+        for (path, saved) in &self.scrolls {
+            if let Some(pos) = saved {
+                view.view(path).set_scroll_pos(cx, *pos);
+            }
+        }
+        for (path, saved) in &self.portal_lists {
+            if let Some((first_id, scroll)) = saved {
+                view.portal_list(path).set_first_id_and_scroll(*first_id, *scroll);
+            }
+        }
+        if let Some(path) = self.focused.take().and_then(|i| self.focusables.get(i)) {
+            cx.set_key_focus(view.widget(path).area());
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_needs_a_save() {
+        let mut state = TabState::new().scroll(&[live_id!(chat_scroll)]);
+        assert!(!state.is_empty());
+        state.restore_after_draw();
+        assert!(!state.pending);
+
+        state.scrolls[0].1 = Some(DVec2 { x: 0.0, y: 120.0 });
+        state.restore_after_draw();
+        assert!(state.pending);
+    }
+
+    #[test]
+    fn test_portal_list_restore_needs_a_save() {
+        let mut state = TabState::new().portal_list(&[live_id!(log_list)]);
+        assert!(!state.is_empty());
+        state.restore_after_draw();
+        assert!(!state.pending);
+
+        state.portal_lists[0].1 = Some((42, -8.0));
+        state.restore_after_draw();
+        assert!(state.pending);
+    }
+}
@@ -274,3 +274,69 @@ live_design! {
 }
 
 // live_design function is generated by the live_design! macro above
+
+/// Semantic colors as CSS hex strings, for code outside `live_design!` such
+/// as web pages (see [`crate::webview::theme_bridge`]). Must match the
+/// definitions above; a test checks they do.
+pub mod colors {
+    pub const DARK_BG: &str = "#f5f7fa";
+    pub const PANEL_BG: &str = "#ffffff";
+    pub const ACCENT_BLUE: &str = "#3b82f6";
+    pub const ACCENT_GREEN: &str = "#10b981";
+    pub const ACCENT_RED: &str = "#ef4444";
+    pub const ACCENT_YELLOW: &str = "#f59f0b";
+    pub const TEXT_PRIMARY: &str = "#1f2937";
+    pub const TEXT_SECONDARY: &str = "#6b7280";
+    pub const BORDER: &str = "#e5e7eb";
+    pub const HOVER_BG: &str = "#f1f5f9";
+
+    pub const DARK_BG_DARK: &str = "#0f172a";
+    pub const PANEL_BG_DARK: &str = "#1f293b";
+    pub const TEXT_PRIMARY_DARK: &str = "#f1f5f9";
+    pub const TEXT_SECONDARY_DARK: &str = "#94a3b8";
+    pub const BORDER_DARK: &str = "#334155";
+    pub const HOVER_BG_DARK: &str = "#334155";
+    pub const ACCENT_BLUE_DARK: &str = "#60a5fa";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::colors;
+
+    #[test]
+    fn test_colors_match_live_design() {
+        let source = include_str!("theme.rs");
+        let defined = |name: &str| -> String {
+            let prefix = format!("pub {} = ", name);
+            let line = source
+                .lines()
+                .map(str::trim)
+                .find(|line| line.starts_with(&prefix))
+                .unwrap_or_else(|| panic!("{} is not defined in live_design!", name));
+            line[prefix.len()..].split_whitespace().next().unwrap().to_string()
+        };
+
+        let colors = [
+            ("DARK_BG", colors::DARK_BG),
+            ("PANEL_BG", colors::PANEL_BG),
+            ("ACCENT_BLUE", colors::ACCENT_BLUE),
+            ("ACCENT_GREEN", colors::ACCENT_GREEN),
+            ("ACCENT_RED", colors::ACCENT_RED),
+            ("ACCENT_YELLOW", colors::ACCENT_YELLOW),
+            ("TEXT_PRIMARY", colors::TEXT_PRIMARY),
+            ("TEXT_SECONDARY", colors::TEXT_SECONDARY),
+            ("BORDER", colors::BORDER),
+            ("HOVER_BG", colors::HOVER_BG),
+            ("DARK_BG_DARK", colors::DARK_BG_DARK),
+            ("PANEL_BG_DARK", colors::PANEL_BG_DARK),
+            ("TEXT_PRIMARY_DARK", colors::TEXT_PRIMARY_DARK),
+            ("TEXT_SECONDARY_DARK", colors::TEXT_SECONDARY_DARK),
+            ("BORDER_DARK", colors::BORDER_DARK),
+            ("HOVER_BG_DARK", colors::HOVER_BG_DARK),
+            ("ACCENT_BLUE_DARK", colors::ACCENT_BLUE_DARK),
+        ];
+        for (name, value) in colors {
+            assert_eq!(defined(name), value, "{} differs from live_design!", name);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Onboarding tour
+//!
+//! A tour is a list of steps, each pointing at a widget by its path from the
+//! window root. The shell builds it from its own steps followed by those of
+//! the active app (see [`MofaApp::tour`](crate::MofaApp::tour)), then dims
+//! the window around the target's rect and places a caption next to it.
+//!
+//! ```rust,ignore
+//! fn tour(tour: TourBuilder) -> TourBuilder {
+//!     tour.step(ids!(fm_page.device_container), "Audio devices", "Pick a microphone and speaker.")
+//! }
+//! ```
+
+use makepad_widgets::{dvec2, DVec2, LiveId, Rect};
+
+/// Space left around a target inside its spotlight
+pub const SPOTLIGHT_PADDING: f64 = 6.0;
+
+/// Gap between the spotlight and the caption, and the caption and the window edge
+pub const CAPTION_MARGIN: f64 = 12.0;
+
+/// One stop of the tour
+#[derive(Clone, Debug, PartialEq)]
+pub struct TourStep {
+    /// Widget path from the window root; the first id only needs to be
+    /// unique, e.g. the app's page id
+    pub target: Vec<LiveId>,
+    pub title: String,
+    pub text: String,
+}
+
+/// Collects tour steps in the order they are shown
+#[derive(Clone, Debug, Default)]
+pub struct TourBuilder {
+    steps: Vec<TourStep>,
+}
+
+impl TourBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a step highlighting the widget at `target`
+    pub fn step(mut self, target: &[LiveId], title: &str, text: &str) -> Self {
+        self.steps.push(TourStep {
+            target: target.to_vec(),
+            title: title.to_string(),
+            text: text.to_string(),
+        });
+        self
+    }
+
+    pub fn build(self) -> Tour {
+        Tour {
+            steps: self.steps,
+            current: 0,
+        }
+    }
+}
+
+/// A tour in progress
+#[derive(Clone, Debug)]
+pub struct Tour {
+    steps: Vec<TourStep>,
+    current: usize,
+}
+
+impl Tour {
+    /// The step being shown, `None` once the tour is over
+    pub fn current(&self) -> Option<&TourStep> {
+        self.steps.get(self.current)
+    }
+
+    /// Move to the next step. Returns `false` when there is none.
+    pub fn advance(&mut self) -> bool {
+        self.current = (self.current + 1).min(self.steps.len());
+        self.current < self.steps.len()
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.current + 1 >= self.steps.len()
+    }
+
+    /// "2 of 5"
+    pub fn progress(&self) -> String {
+        format!("{} of {}", (self.current + 1).min(self.steps.len()), self.steps.len())
+    }
+}
+
+/// The cutout for a target's rect, padded and clipped to the window.
+/// `None` when the target is not on screen (hidden, or not drawn yet).
+pub fn spotlight_rect(target: Rect, window: DVec2) -> Option<Rect> {
+    if target.size.x <= 0.0 || target.size.y <= 0.0 {
+        return None;
+    }
+    let x0 = (target.pos.x - SPOTLIGHT_PADDING).max(0.0);
+    let y0 = (target.pos.y - SPOTLIGHT_PADDING).max(0.0);
+    let x1 = (target.pos.x + target.size.x + SPOTLIGHT_PADDING).min(window.x);
+    let y1 = (target.pos.y + target.size.y + SPOTLIGHT_PADDING).min(window.y);
+    if x1 <= x0 || y1 <= y0 {
+        return None;
+    }
+    Some(Rect {
+        pos: dvec2(x0, y0),
+        size: dvec2(x1 - x0, y1 - y0),
+    })
+}
+
+/// Top-left corner for a caption of `size`: below the spotlight, above it
+/// when there is no room below, and centred without a spotlight. A target
+/// too tall for either gets the caption over its lower part.
+pub fn caption_pos(spotlight: Option<Rect>, size: DVec2, window: DVec2) -> DVec2 {
+    let Some(spot) = spotlight else {
+        return dvec2(
+            ((window.x - size.x) / 2.0).max(0.0),
+            ((window.y - size.y) / 2.0).max(0.0),
+        );
+    };
+
+    let below = spot.pos.y + spot.size.y + CAPTION_MARGIN;
+    let above = spot.pos.y - CAPTION_MARGIN - size.y;
+    let y = if below + size.y + CAPTION_MARGIN <= window.y {
+        below
+    } else if above >= CAPTION_MARGIN {
+        above
+    } else {
+        window.y - CAPTION_MARGIN - size.y
+    };
+    let x = spot.pos.x.min(window.x - CAPTION_MARGIN - size.x);
+
+    dvec2(x.max(CAPTION_MARGIN), y.max(CAPTION_MARGIN))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use makepad_widgets::live_id;
+
+    fn rect(x: f64, y: f64, w: f64, h: f64) -> Rect {
+        Rect {
+            pos: dvec2(x, y),
+            size: dvec2(w, h),
+        }
+    }
+
+    #[test]
+    fn test_tour_steps() {
+        let mut tour = TourBuilder::new()
+            .step(&[live_id!(sidebar)], "Apps", "Switch apps here")
+            .step(&[live_id!(fm_page), live_id!(device_container)], "Devices", "Pick a microphone")
+            .build();
+
+        assert_eq!(tour.current().map(|s| s.title.as_str()), Some("Apps"));
+        assert_eq!(tour.progress(), "1 of 2");
+        assert!(!tour.is_last());
+
+        assert!(tour.advance());
+        assert_eq!(tour.current().unwrap().target, vec![live_id!(fm_page), live_id!(device_container)]);
+        assert!(tour.is_last());
+
+        assert!(!tour.advance());
+        assert_eq!(tour.current(), None);
+        assert!(!tour.advance());
+
+        let empty = TourBuilder::new().build();
+        assert_eq!(empty.current(), None);
+        assert_eq!(empty.progress(), "0 of 0");
+    }
+
+    #[test]
+    fn test_spotlight_rect() {
+        let window = dvec2(1000.0, 800.0);
+        assert_eq!(spotlight_rect(rect(100.0, 100.0, 50.0, 20.0), window), Some(rect(94.0, 94.0, 62.0, 32.0)));
+        // Clipped at the window's edges
+        assert_eq!(spotlight_rect(rect(0.0, 0.0, 34.0, 34.0), window), Some(rect(0.0, 0.0, 40.0, 40.0)));
+        assert_eq!(spotlight_rect(rect(980.0, 790.0, 40.0, 40.0), window), Some(rect(974.0, 784.0, 26.0, 16.0)));
+        // Not drawn, or scrolled out of the window
+        assert_eq!(spotlight_rect(Rect::default(), window), None);
+        assert_eq!(spotlight_rect(rect(1200.0, 100.0, 50.0, 20.0), window), None);
+    }
+
+    #[test]
+    fn test_caption_pos() {
+        let window = dvec2(1000.0, 800.0);
+        let size = dvec2(300.0, 150.0);
+
+        // Below the spotlight, aligned with its left edge
+        assert_eq!(caption_pos(Some(rect(100.0, 50.0, 40.0, 40.0)), size, window), dvec2(100.0, 102.0));
+        // Above it near the bottom, and kept inside on the right
+        assert_eq!(caption_pos(Some(rect(900.0, 700.0, 80.0, 40.0)), size, window), dvec2(688.0, 538.0));
+        // A full-height panel gets the caption over its lower part
+        assert_eq!(caption_pos(Some(rect(0.0, 72.0, 250.0, 728.0)), size, window), dvec2(12.0, 638.0));
+        // No spotlight: centred
+        assert_eq!(caption_pos(None, size, window), dvec2(350.0, 325.0));
+    }
+}
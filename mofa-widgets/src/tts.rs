@@ -0,0 +1,837 @@
+//! # Text to Speech
+//!
+//! Speech synthesis shared by the apps that read text aloud (Podcast episode
+//! rendering, Personal News briefings).
+//!
+//! A [`TtsBackend`] turns text into mono PCM ([`TtsAudio`]) that can be fed
+//! straight into an [`AudioPlayer`](crate::audio_player::AudioPlayer) or
+//! written out with [`write_wav`]. [`SayBackend`] wraps the macOS `say`
+//! command; [`default_backend`] picks the backend for the current platform
+//! and [`backends`] lists every registered backend.
+//!
+//! Text with relative pacing (slower, faster or emphasized runs) is passed
+//! as [`TtsSpan`]s to [`TtsBackend::synthesize_spans`]. Backends apply what
+//! they can: the default splits at span boundaries and renders each run at
+//! its own speed; SSML-capable backends can render [`to_ssml`] instead.
+//! Long documents are split with [`chunk_text`] and synthesized piece by
+//! piece. Every backend declares the longest text it reliably renders in
+//! one request ([`TtsBackend::max_chars`]); [`chunk_spans`] splits paced
+//! text to fit it.
+//!
+//! What a render costs comes from [`TtsBackend::cost_model`]: a price per
+//! character and how long rendering takes compared to the audio it makes.
+//! Backends only know their nominal speed; [`TtsSpeedLog`] keeps the
+//! factors measured in recent runs and [`TtsSpeedLog::cost_model`] uses
+//! them in its place. [`TtsCostModel::estimate`] is a rough guide for the
+//! user, never a reason to refuse a render.
+//!
+//! ```rust,ignore
+//! use mofa_widgets::tts::{default_backend, TtsOptions};
+//!
+//! let backend = default_backend();
+//! let options = TtsOptions { voice: Some("Samantha".into()), speed: 1.25 };
+//! let audio = backend.synthesize("Good morning", &options)?;
+//! player.write_audio(&audio.samples, None, None);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::app_storage;
+use crate::jobs::format_remaining;
+
+/// Slowest and fastest supported speaking speed (1.0 = backend default)
+pub const MIN_SPEED: f32 = 0.5;
+pub const MAX_SPEED: f32 = 2.0;
+
+/// Speed of [`TtsSpan::slow`] and [`TtsSpan::fast`] runs, relative to the
+/// surrounding text
+pub const SLOW_RATE: f32 = 0.8;
+pub const FAST_RATE: f32 = 1.25;
+
+/// Emphasized runs are spoken slightly slower and louder
+const EMPHASIS_RATE: f32 = 0.9;
+const EMPHASIS_GAIN: f32 = 1.2;
+
+/// Measured render speeds, relative to the data root
+pub const TTS_SPEED_FILE: &str = "tts/speeds.json";
+
+/// Runs per backend [`TtsSpeedLog`] averages over
+pub const MAX_SPEED_SAMPLES: usize = 10;
+
+/// A voice offered by a backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TtsVoice {
+    /// Identifier passed back in [`TtsOptions::voice`]
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Locale such as `en_US` or `zh_CN`; empty when unknown
+    pub language: String,
+    /// `female` or `male`; empty when unknown
+    pub gender: String,
+}
+
+/// Per-request synthesis options
+#[derive(Debug, Clone, PartialEq)]
+pub struct TtsOptions {
+    /// Voice id; `None` uses the system default voice
+    pub voice: Option<String>,
+    /// Speaking speed multiplier, clamped to [`MIN_SPEED`]..=[`MAX_SPEED`]
+    pub speed: f32,
+}
+
+impl Default for TtsOptions {
+    fn default() -> Self {
+        Self { voice: None, speed: 1.0 }
+    }
+}
+
+impl TtsOptions {
+    pub fn clamped_speed(&self) -> f32 {
+        if self.speed.is_finite() {
+            self.speed.clamp(MIN_SPEED, MAX_SPEED)
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Synthesized mono audio
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TtsAudio {
+    /// Samples in -1.0..=1.0
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl TtsAudio {
+    pub fn duration_secs(&self) -> f64 {
+        if self.sample_rate == 0 {
+            return 0.0;
+        }
+        self.samples.len() as f64 / self.sample_rate as f64
+    }
+
+    /// Append `other`, which must have the same sample rate
+    pub fn append(&mut self, other: &TtsAudio) {
+        if self.samples.is_empty() {
+            self.sample_rate = other.sample_rate;
+        }
+        debug_assert_eq!(self.sample_rate, other.sample_rate);
+        self.samples.extend_from_slice(&other.samples);
+    }
+}
+
+/// A run of text spoken with the same pacing
+#[derive(Debug, Clone, PartialEq)]
+pub struct TtsSpan {
+    pub text: String,
+    /// Speed relative to the request's [`TtsOptions::speed`]
+    pub rate: f32,
+    pub emphasis: bool,
+}
+
+impl TtsSpan {
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self { text: text.into(), rate: 1.0, emphasis: false }
+    }
+
+    pub fn slow(text: impl Into<String>) -> Self {
+        Self { rate: SLOW_RATE, ..Self::plain(text) }
+    }
+
+    pub fn fast(text: impl Into<String>) -> Self {
+        Self { rate: FAST_RATE, ..Self::plain(text) }
+    }
+
+    pub fn emphasized(mut self) -> Self {
+        self.emphasis = true;
+        self
+    }
+
+    /// Whether the span is spoken like unmarked text
+    pub fn is_plain(&self) -> bool {
+        !self.emphasis && (self.rate - 1.0).abs() <= f32::EPSILON
+    }
+
+    /// `options` with this span's rate applied
+    pub fn options(&self, options: &TtsOptions) -> TtsOptions {
+        let emphasis = if self.emphasis { EMPHASIS_RATE } else { 1.0 };
+        TtsOptions {
+            voice: options.voice.clone(),
+            speed: (options.clamped_speed() * self.rate * emphasis).clamp(MIN_SPEED, MAX_SPEED),
+        }
+    }
+}
+
+/// What rendering costs on a backend
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TtsCostModel {
+    /// Price per input character in US dollars; 0 for local backends
+    pub price_per_char: f64,
+    /// Seconds of rendering per second of audio (0.1 renders ten times
+    /// faster than real time)
+    pub realtime_factor: f64,
+}
+
+impl Default for TtsCostModel {
+    /// Free, and as slow as real time until measured
+    fn default() -> Self {
+        Self { price_per_char: 0.0, realtime_factor: 1.0 }
+    }
+}
+
+impl TtsCostModel {
+    /// Cost of rendering `chars` characters that read for about `audio_secs`
+    pub fn estimate(&self, chars: usize, audio_secs: f64) -> TtsEstimate {
+        TtsEstimate {
+            chars,
+            price: chars as f64 * self.price_per_char,
+            render_secs: audio_secs.max(0.0) * self.realtime_factor,
+        }
+    }
+}
+
+/// Approximate cost of a render
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TtsEstimate {
+    pub chars: usize,
+    /// US dollars
+    pub price: f64,
+    pub render_secs: f64,
+}
+
+impl TtsEstimate {
+    /// One line for the UI, e.g. "~38k chars · est. $0.57 · ~6 min"
+    pub fn summary(&self) -> String {
+        let chars = match self.chars {
+            0..=999 => format!("{} chars", self.chars),
+            1000..=9999 => format!("~{:.1}k chars", self.chars as f64 / 1000.0),
+            _ => format!("~{}k chars", (self.chars as f64 / 1000.0).round() as u64),
+        };
+        let price = if self.price <= 0.0 {
+            "free".to_string()
+        } else if self.price < 0.01 {
+            "est. < $0.01".to_string()
+        } else {
+            format!("est. ${:.2}", self.price)
+        };
+        format!("{} · {} · {}", chars, price, format_remaining(self.render_secs))
+    }
+}
+
+/// Realtime factors measured per backend, most recent last
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TtsSpeedLog {
+    /// Keyed by [`TtsBackend::name`]
+    factors: BTreeMap<String, Vec<f64>>,
+}
+
+impl TtsSpeedLog {
+    pub fn path() -> PathBuf {
+        app_storage::data_path(TTS_SPEED_FILE)
+    }
+
+    /// Saved measurements, empty if none or unreadable
+    pub fn load() -> Self {
+        Self::load_from(&Self::path())
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        self.save_to(&Self::path())
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| e.to_string())
+    }
+
+    /// Record a run that took `render_secs` to make `audio_secs` of audio.
+    /// Returns false for runs too short or broken to measure anything.
+    pub fn record(&mut self, backend: &str, render_secs: f64, audio_secs: f64) -> bool {
+        let factor = render_secs / audio_secs;
+        if !(audio_secs >= 1.0 && factor.is_finite() && factor > 0.0) {
+            return false;
+        }
+        let factors = self.factors.entry(backend.to_string()).or_default();
+        factors.push(factor);
+        if factors.len() > MAX_SPEED_SAMPLES {
+            factors.remove(0);
+        }
+        true
+    }
+
+    /// Average of the recent runs on `backend`, `None` before the first
+    pub fn realtime_factor(&self, backend: &str) -> Option<f64> {
+        let factors = self.factors.get(backend).filter(|f| !f.is_empty())?;
+        Some(factors.iter().sum::<f64>() / factors.len() as f64)
+    }
+
+    /// The backend's cost model with its measured speed, if any
+    pub fn cost_model(&self, backend: &dyn TtsBackend) -> TtsCostModel {
+        let nominal = backend.cost_model();
+        TtsCostModel {
+            realtime_factor: self.realtime_factor(backend.name()).unwrap_or(nominal.realtime_factor),
+            ..nominal
+        }
+    }
+}
+
+/// A speech synthesizer
+pub trait TtsBackend: Send + Sync {
+    /// Short name shown in logs and errors
+    fn name(&self) -> &str;
+
+    /// Sample rate of the audio returned by [`TtsBackend::synthesize`]
+    fn sample_rate(&self) -> u32;
+
+    /// Longest text, in characters, the backend renders reliably in one
+    /// request. Longer text may fail or be cut off mid-sentence, so split
+    /// it first (see [`chunk_text`] and [`chunk_spans`]).
+    fn max_chars(&self) -> usize;
+
+    /// Price and nominal speed. Actual speed depends on the machine and
+    /// network; prefer [`TtsSpeedLog::cost_model`], which measures it.
+    fn cost_model(&self) -> TtsCostModel {
+        TtsCostModel::default()
+    }
+
+    /// Voices installed for this backend. Blocks while the backend is
+    /// queried; an error means the backend is unavailable.
+    fn voices(&self) -> Result<Vec<TtsVoice>, String>;
+
+    /// Render `text` to audio. Blocks until synthesis is done, so call it
+    /// from a worker thread.
+    fn synthesize(&self, text: &str, options: &TtsOptions) -> Result<TtsAudio, String>;
+
+    /// Render paced text. The default synthesizes each span at its own
+    /// speed and joins the runs, raising the volume of emphasized ones;
+    /// backends that understand SSML can override this with [`to_ssml`].
+    fn synthesize_spans(&self, spans: &[TtsSpan], options: &TtsOptions) -> Result<TtsAudio, String> {
+        let mut audio = TtsAudio { samples: Vec::new(), sample_rate: self.sample_rate() };
+        for span in spans.iter().filter(|span| !span.text.trim().is_empty()) {
+            let mut run = self.synthesize(&span.text, &span.options(options))?;
+            if span.emphasis {
+                for sample in &mut run.samples {
+                    *sample = (*sample * EMPHASIS_GAIN).clamp(-1.0, 1.0);
+                }
+            }
+            audio.append(&run);
+        }
+        Ok(audio)
+    }
+}
+
+/// SSML document for `spans`, for backends that accept SSML
+pub fn to_ssml(spans: &[TtsSpan]) -> String {
+    let mut ssml = String::from("<speak>");
+    for span in spans {
+        let mut text = escape_xml(&span.text);
+        if span.emphasis {
+            text = format!("<emphasis>{}</emphasis>", text);
+        }
+        if (span.rate - 1.0).abs() > f32::EPSILON {
+            text = format!("<prosody rate=\"{}%\">{}</prosody>", (span.rate * 100.0).round() as u32, text);
+        }
+        ssml.push_str(&text);
+    }
+    ssml.push_str("</speak>");
+    ssml
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Split `text` into pieces of at most `max_chars`, breaking after sentence
+/// ends (including CJK punctuation) where possible
+pub fn chunk_text(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    for c in text.chars() {
+        current.push(c);
+        if matches!(c, '.' | '!' | '?' | '。' | '！' | '？' | '；' | '\n') {
+            sentences.push(std::mem::take(&mut current));
+        }
+    }
+    sentences.push(current);
+
+    let mut chunks: Vec<String> = Vec::new();
+    let mut chunk = String::new();
+    for sentence in sentences {
+        let sentence = sentence.trim();
+        if sentence.is_empty() {
+            continue;
+        }
+        if !chunk.is_empty() && chunk.chars().count() + 1 + sentence.chars().count() > max_chars {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+        if sentence.chars().count() > max_chars {
+            // A single overlong sentence is cut after its last comma or
+            // space within the limit, or at the limit if it has none
+            let mut rest: Vec<char> = sentence.chars().collect();
+            while !rest.is_empty() {
+                let cut = if rest.len() <= max_chars {
+                    rest.len()
+                } else {
+                    rest[..max_chars]
+                        .iter()
+                        .rposition(|&c| matches!(c, ',' | '，' | '、' | ';') || c.is_whitespace())
+                        .map_or(max_chars, |i| i + 1)
+                };
+                let piece: String = rest.drain(..cut).collect();
+                if !piece.trim().is_empty() {
+                    chunks.push(piece.trim().to_string());
+                }
+            }
+            continue;
+        }
+        if !chunk.is_empty() {
+            chunk.push(' ');
+        }
+        chunk.push_str(sentence);
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+/// Split paced text into parts of at most `max_chars` characters, breaking
+/// at sentence ends like [`chunk_text`]. Each piece keeps the pacing of the
+/// span it came from.
+pub fn chunk_spans(spans: &[TtsSpan], max_chars: usize) -> Vec<Vec<TtsSpan>> {
+    let mut parts = Vec::new();
+    let mut part: Vec<TtsSpan> = Vec::new();
+    let mut part_chars = 0;
+    for span in spans {
+        for piece in chunk_text(&span.text, max_chars) {
+            let chars = piece.chars().count();
+            if !part.is_empty() && part_chars + chars > max_chars {
+                parts.push(std::mem::take(&mut part));
+                part_chars = 0;
+            }
+            part_chars += chars;
+            part.push(TtsSpan { text: piece, ..span.clone() });
+        }
+    }
+    if !part.is_empty() {
+        parts.push(part);
+    }
+    parts
+}
+
+/// Backend for the current platform
+pub fn default_backend() -> Box<dyn TtsBackend> {
+    Box::new(SayBackend::default())
+}
+
+/// Every registered backend, the default first
+pub fn backends() -> Vec<Box<dyn TtsBackend>> {
+    vec![default_backend()]
+}
+
+/// Backend registered under `name`
+pub fn backend(name: &str) -> Option<Box<dyn TtsBackend>> {
+    backends().into_iter().find(|backend| backend.name() == name)
+}
+
+/// macOS `say` command
+pub struct SayBackend {
+    sample_rate: u32,
+}
+
+impl Default for SayBackend {
+    fn default() -> Self {
+        Self::new(22050)
+    }
+}
+
+impl SayBackend {
+    /// `say`'s words per minute at speed 1.0
+    const BASE_RATE: f32 = 175.0;
+
+    /// Very long input makes `say` stall or stop early
+    const MAX_CHARS: usize = 3000;
+
+    /// Rendering to a file takes about a tenth of the spoken time
+    const REALTIME_FACTOR: f64 = 0.1;
+
+    pub fn new(sample_rate: u32) -> Self {
+        Self { sample_rate }
+    }
+
+    /// Render `text` straight to a 16-bit WAV file
+    pub fn render_to_file(&self, text: &str, options: &TtsOptions, output_path: &Path) -> Result<(), String> {
+        let mut command = Command::new("say");
+        if let Some(ref voice) = options.voice {
+            command.arg("-v").arg(voice);
+        }
+        let speed = options.clamped_speed();
+        // Leave the voice's own rate alone at normal speed
+        if (speed - 1.0).abs() > f32::EPSILON {
+            let rate = (Self::BASE_RATE * speed).round() as u32;
+            command.arg("-r").arg(rate.to_string());
+        }
+        command
+            .arg("--file-format=WAVE")
+            .arg(format!("--data-format=LEI16@{}", self.sample_rate))
+            .arg("-o")
+            .arg(output_path)
+            // Read the text from stdin so it is never parsed as options
+            .arg("-f")
+            .arg("-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| format!("Failed to run say command: {}", e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .map_err(|e| format!("Failed to send text to say: {}", e))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("say command failed: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("say command failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(())
+    }
+}
+
+impl TtsBackend for SayBackend {
+    fn name(&self) -> &str {
+        "say"
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn max_chars(&self) -> usize {
+        Self::MAX_CHARS
+    }
+
+    fn cost_model(&self) -> TtsCostModel {
+        TtsCostModel { price_per_char: 0.0, realtime_factor: Self::REALTIME_FACTOR }
+    }
+
+    fn voices(&self) -> Result<Vec<TtsVoice>, String> {
+        let out = Command::new("say")
+            .arg("-v")
+            .arg("?")
+            .output()
+            .map_err(|e| format!("Failed to run say command: {}", e))?;
+        if !out.status.success() {
+            return Err(format!("say command failed: {}", String::from_utf8_lossy(&out.stderr).trim()));
+        }
+        Ok(String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .filter_map(parse_say_voice)
+            .collect())
+    }
+
+    fn synthesize(&self, text: &str, options: &TtsOptions) -> Result<TtsAudio, String> {
+        let path = temp_wav_path();
+        let result = self
+            .render_to_file(text, options, &path)
+            .and_then(|_| read_wav(&path));
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+}
+
+/// Parse one line of `say -v ?`, e.g.
+/// `Samantha            en_US    # Hello! My name is Samantha.`
+fn parse_say_voice(line: &str) -> Option<TtsVoice> {
+    let head = line.split('#').next()?.trim();
+    let mut parts: Vec<&str> = head.split_whitespace().collect();
+    // Voice names may contain spaces ("Grandma (English (US))"); the locale is last
+    let language = if parts.len() > 1 { parts.pop()?.to_string() } else { String::new() };
+    let name = parts.join(" ");
+    if name.is_empty() {
+        return None;
+    }
+    let gender = say_voice_gender(&name).to_string();
+    Some(TtsVoice { id: name.clone(), name, language, gender })
+}
+
+/// `say -v ?` does not report genders; these are the common system voices
+fn say_voice_gender(name: &str) -> &'static str {
+    const FEMALE: &[&str] = &[
+        "Alice", "Amélie", "Anna", "Ellen", "Joana", "Karen", "Kyoko", "Lili", "Mei-Jia", "Melina",
+        "Milena", "Moira", "Monica", "Paulina", "Samantha", "Sara", "Sin-ji", "Tessa", "Ting-Ting",
+        "Veena", "Victoria", "Yu-shu", "Yuna", "Zosia", "Zuzana",
+    ];
+    const MALE: &[&str] = &[
+        "Alex", "Daniel", "Diego", "Fred", "Jorge", "Juan", "Luca", "Maged", "Nicolas", "Rishi",
+        "Thomas", "Xander", "Yuri",
+    ];
+    // Enhanced variants are listed as e.g. "Samantha (Enhanced)"
+    let base = name.split(" (").next().unwrap_or(name);
+    if FEMALE.contains(&base) {
+        "female"
+    } else if MALE.contains(&base) {
+        "male"
+    } else {
+        ""
+    }
+}
+
+fn temp_wav_path() -> PathBuf {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    std::env::temp_dir().join(format!(
+        "mofa-tts-{}-{}.wav",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Write mono audio as a 16-bit PCM WAV file
+pub fn write_wav(path: &Path, audio: &TtsAudio) -> Result<(), String> {
+    let data_len = (audio.samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+    bytes.extend_from_slice(&audio.sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(audio.sample_rate * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in &audio.samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    std::fs::write(path, bytes).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Read a 16-bit PCM WAV file, mixing multiple channels down to mono
+pub fn read_wav(path: &Path) -> Result<TtsAudio, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("Not a WAV file".to_string());
+    }
+
+    let mut format: Option<(u16, u16, u32, u16)> = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = u32::from_le_bytes([bytes[pos + 4], bytes[pos + 5], bytes[pos + 6], bytes[pos + 7]]) as usize;
+        let body = &bytes[pos + 8..(pos + 8 + size).min(bytes.len())];
+
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                format = Some((
+                    u16::from_le_bytes([body[0], body[1]]),
+                    u16::from_le_bytes([body[2], body[3]]),
+                    u32::from_le_bytes([body[4], body[5], body[6], body[7]]),
+                    u16::from_le_bytes([body[14], body[15]]),
+                ));
+            }
+            b"data" => {
+                let (tag, channels, sample_rate, bits) = format.ok_or("WAV data before format chunk")?;
+                // 0xFFFE is WAVE_FORMAT_EXTENSIBLE, which `say` and afconvert use for PCM too
+                if (tag != 1 && tag != 0xFFFE) || bits != 16 || channels == 0 {
+                    return Err(format!("Unsupported WAV format ({} channels, {} bits)", channels, bits));
+                }
+                let channels = channels as usize;
+                let samples = body
+                    .chunks_exact(2 * channels)
+                    .map(|frame| {
+                        let sum: f32 = frame
+                            .chunks_exact(2)
+                            .map(|s| i16::from_le_bytes([s[0], s[1]]) as f32 / 32768.0)
+                            .sum();
+                        sum / channels as f32
+                    })
+                    .collect();
+                return Ok(TtsAudio { samples, sample_rate });
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even size
+        pos += 8 + size + (size & 1);
+    }
+    Err("WAV file has no data chunk".to_string())
+}
+
+/// Encode a WAV file to MP3 with `ffmpeg`, falling back to `lame`
+pub fn encode_mp3(wav_path: &Path, mp3_path: &Path) -> Result<(), String> {
+    let ffmpeg = Command::new("ffmpeg")
+        .args(["-y", "-loglevel", "error", "-i"])
+        .arg(wav_path)
+        .args(["-codec:a", "libmp3lame", "-q:a", "4"])
+        .arg(mp3_path)
+        .output();
+    if matches!(ffmpeg, Ok(ref out) if out.status.success()) {
+        return Ok(());
+    }
+
+    match Command::new("lame").args(["--quiet", "-V", "4"]).arg(wav_path).arg(mp3_path).output() {
+        Ok(out) if out.status.success() => Ok(()),
+        Ok(out) => Err(format!("lame failed: {}", String::from_utf8_lossy(&out.stderr).trim())),
+        Err(_) => Err("MP3 export needs ffmpeg or lame on the PATH".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wav_round_trip() {
+        let path = std::env::temp_dir().join(format!("mofa-tts-test-{}.wav", std::process::id()));
+        let audio = TtsAudio { samples: vec![0.0, 0.5, -0.5, 1.0, -1.0], sample_rate: 22050 };
+        write_wav(&path, &audio).unwrap();
+
+        let read = read_wav(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(read.sample_rate, 22050);
+        assert_eq!(read.samples.len(), audio.samples.len());
+        for (a, b) in read.samples.iter().zip(&audio.samples) {
+            assert!((a - b).abs() < 0.001, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_chunk_text_breaks_at_sentences() {
+        let text = "今日要闻。股市上涨！天气晴朗？Short one. Another";
+        assert_eq!(chunk_text(text, 100), vec!["今日要闻。 股市上涨！ 天气晴朗？ Short one. Another"]);
+        assert_eq!(
+            chunk_text(text, 12),
+            vec!["今日要闻。 股市上涨！", "天气晴朗？", "Short one.", "Another"]
+        );
+        // A sentence longer than the limit is cut
+        assert_eq!(chunk_text("abcdefgh", 3), vec!["abc", "def", "gh"]);
+        // ... preferably after a comma or space
+        assert_eq!(chunk_text("one two, three four", 10), vec!["one two,", "three four"]);
+    }
+
+    #[test]
+    fn test_chunk_spans_keeps_pacing() {
+        let spans = vec![TtsSpan::plain("第一句话。第二句话。"), TtsSpan::slow("慢慢说。").emphasized()];
+        let parts = chunk_spans(&spans, 11);
+        assert_eq!(
+            parts,
+            vec![
+                vec![TtsSpan::plain("第一句话。 第二句话。")],
+                vec![TtsSpan::slow("慢慢说。").emphasized()],
+            ]
+        );
+        assert_eq!(chunk_spans(&spans, 100).len(), 1);
+        assert!(chunk_text("  \n ", 10).is_empty());
+    }
+
+    #[test]
+    fn test_parse_say_voice() {
+        let voice = parse_say_voice("Samantha            en_US    # Hello! My name is Samantha.").unwrap();
+        assert_eq!(voice.id, "Samantha");
+        assert_eq!(voice.language, "en_US");
+        assert_eq!(voice.gender, "female");
+
+        let voice = parse_say_voice("Grandma (English (US)) en_US    # Hello!").unwrap();
+        assert_eq!(voice.name, "Grandma (English (US))");
+        assert_eq!(voice.gender, "");
+
+        let voice = parse_say_voice("Daniel (Enhanced)   en_GB    # Hello! My name is Daniel.").unwrap();
+        assert_eq!(voice.gender, "male");
+
+        assert!(parse_say_voice("   # nothing").is_none());
+    }
+
+    #[test]
+    fn test_speed_is_clamped() {
+        let options = |speed| TtsOptions { voice: None, speed };
+        assert_eq!(options(5.0).clamped_speed(), MAX_SPEED);
+        assert_eq!(options(0.1).clamped_speed(), MIN_SPEED);
+        assert_eq!(options(f32::NAN).clamped_speed(), 1.0);
+    }
+
+    #[test]
+    fn test_estimate_summary() {
+        let remote = TtsCostModel { price_per_char: 0.000015, realtime_factor: 0.5 };
+        assert_eq!(remote.estimate(38_000, 720.0).summary(), "~38k chars · est. $0.57 · ~6 min");
+        assert_eq!(remote.estimate(120, 6.0).summary(), "120 chars · est. < $0.01 · < 1 min");
+        let local = SayBackend::default().cost_model();
+        assert_eq!(local.estimate(2_450, 900.0).summary(), "~2.5k chars · free · ~2 min");
+    }
+
+    #[test]
+    fn test_speed_log_replaces_nominal_speed() {
+        let backend = SayBackend::default();
+        let mut log = TtsSpeedLog::default();
+        assert_eq!(log.cost_model(&backend), backend.cost_model());
+        // Too short or broken runs say nothing about speed
+        assert!(!log.record("say", 0.1, 0.5));
+        assert!(!log.record("say", f64::NAN, 60.0));
+
+        assert!(log.record("say", 30.0, 60.0));
+        assert!(log.record("say", 10.0, 100.0));
+        assert_eq!(log.realtime_factor("say"), Some(0.3));
+        assert_eq!(log.cost_model(&backend).realtime_factor, 0.3);
+        assert_eq!(log.realtime_factor("other"), None);
+
+        for _ in 0..MAX_SPEED_SAMPLES {
+            log.record("say", 20.0, 100.0);
+        }
+        assert!((log.realtime_factor("say").unwrap() - 0.2).abs() < 1e-9);
+
+        let path = std::env::temp_dir().join(format!("mofa-tts-speeds-{}.json", std::process::id()));
+        log.save_to(&path).unwrap();
+        let loaded = TtsSpeedLog::load_from(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(loaded, log);
+    }
+
+    #[test]
+    fn test_span_options_and_ssml() {
+        let base = TtsOptions { voice: Some("Samantha".into()), speed: 1.0 };
+        assert!(TtsSpan::plain("hi").is_plain());
+        assert!(!TtsSpan::plain("hi").emphasized().is_plain());
+        assert_eq!(TtsSpan::slow("hi").options(&base).speed, SLOW_RATE);
+        assert_eq!(TtsSpan::fast("hi").options(&base).voice.as_deref(), Some("Samantha"));
+        // Pacing never leaves the supported range
+        let fastest = TtsOptions { voice: None, speed: MAX_SPEED };
+        assert_eq!(TtsSpan::fast("hi").options(&fastest).speed, MAX_SPEED);
+
+        let spans = [
+            TtsSpan::plain("Say "),
+            TtsSpan::slow("R&D").emphasized(),
+            TtsSpan::fast(" quickly"),
+        ];
+        assert_eq!(
+            to_ssml(&spans),
+            "<speak>Say <prosody rate=\"80%\"><emphasis>R&amp;D</emphasis></prosody><prosody rate=\"125%\"> quickly</prosody></speak>"
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! # Token usage across apps
+//!
+//! Apps that call a chat provider report what each request used with
+//! [`record`]. Records are appended to `~/.mofa-studio/usage/usage.jsonl`,
+//! one JSON object per line, so every app writes to the same ledger without
+//! coordinating, and totals can be taken per app, provider or model:
+//!
+//! ```rust,ignore
+//! use mofa_widgets::usage::{self, TokenUsage};
+//!
+//! let used = TokenUsage::from_response(&response_json);
+//! usage::record("mofa-transcriber", &provider_id, &model, used);
+//!
+//! let mine = usage::totals(usage::load().iter().filter(|r| r.app == "mofa-transcriber"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::ops::AddAssign;
+use std::path::{Path, PathBuf};
+
+use crate::app_storage;
+
+/// Usage ledger, relative to the data root
+pub const USAGE_FILE: &str = "usage/usage.jsonl";
+
+/// Tokens used by one or more requests
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+impl TokenUsage {
+    /// Read the `usage` object of an OpenAI-style chat completion response;
+    /// zero if the provider does not report it
+    pub fn from_response(response: &serde_json::Value) -> Self {
+        let count = |key: &str| {
+            response
+                .pointer(&format!("/usage/{}", key))
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0)
+        };
+        Self {
+            prompt_tokens: count("prompt_tokens"),
+            completion_tokens: count("completion_tokens"),
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+}
+
+impl AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+    }
+}
+
+/// One request in the ledger
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// Unix timestamp (milliseconds)
+    pub timestamp: u64,
+    /// [`AppInfo::id`](crate::AppInfo::id) of the calling app
+    pub app: String,
+    pub provider: String,
+    pub model: String,
+    #[serde(flatten)]
+    pub usage: TokenUsage,
+}
+
+impl UsageRecord {
+    pub fn new(app: &str, provider: &str, model: &str, usage: TokenUsage) -> Self {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        Self {
+            timestamp,
+            app: app.to_string(),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            usage,
+        }
+    }
+}
+
+pub fn path() -> PathBuf {
+    app_storage::data_path(USAGE_FILE)
+}
+
+/// Add a request to the ledger; failures are logged, never returned, so
+/// tracking cannot break the call it tracks
+pub fn record(app: &str, provider: &str, model: &str, usage: TokenUsage) {
+    if usage.is_empty() {
+        return;
+    }
+    if let Err(e) = append_to(&path(), &UsageRecord::new(app, provider, model, usage)) {
+        ::log::warn!("Failed to record token usage: {}", e);
+    }
+}
+
+pub fn append_to(path: &Path, record: &UsageRecord) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(record).map_err(io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+/// Every record in the ledger, oldest first
+pub fn load() -> Vec<UsageRecord> {
+    load_from(&path())
+}
+
+/// Records in `path`; unreadable lines are skipped
+pub fn load_from(path: &Path) -> Vec<UsageRecord> {
+    fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Sum of the records' usage
+pub fn totals<'a>(records: impl IntoIterator<Item = &'a UsageRecord>) -> TokenUsage {
+    let mut total = TokenUsage::default();
+    for record in records {
+        total += record.usage;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_from_response() {
+        let response = serde_json::json!({
+            "choices": [],
+            "usage": { "prompt_tokens": 1200, "completion_tokens": 300, "total_tokens": 1500 }
+        });
+        let usage = TokenUsage::from_response(&response);
+        assert_eq!((usage.prompt_tokens, usage.completion_tokens, usage.total()), (1200, 300, 1500));
+        assert!(TokenUsage::from_response(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_ledger_roundtrip() {
+        let path = std::env::temp_dir().join(format!("mofa-usage-test-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let used = |prompt, completion| TokenUsage { prompt_tokens: prompt, completion_tokens: completion };
+        append_to(&path, &UsageRecord::new("mofa-transcriber", "openai", "gpt-4o-mini", used(100, 20))).unwrap();
+        append_to(&path, &UsageRecord::new("mofa-debate", "openai", "gpt-4o-mini", used(50, 5))).unwrap();
+        // A damaged line does not hide the others
+        fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"app\":\n").unwrap();
+        append_to(&path, &UsageRecord::new("mofa-transcriber", "deepseek", "deepseek-chat", used(10, 1))).unwrap();
+
+        let records = load_from(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(records.len(), 3);
+        assert_eq!(totals(&records), used(160, 26));
+        assert_eq!(records[0].app, "mofa-transcriber");
+        assert!(records[0].timestamp > 0);
+    }
+}
@@ -0,0 +1,364 @@
+//! # Voice catalogue and favorites
+//!
+//! Settings lists the voices of every registered [`TtsBackend`](crate::tts::TtsBackend)
+//! with [`catalogue`], lets the user star favorites and edit the sample
+//! sentence previewed per language. Both are stored in
+//! `~/.mofa-studio/voices/favorites.json` as [`VoicePrefs`].
+//!
+//! Apps with a voice dropdown put the user's favorites on top:
+//!
+//! ```rust,ignore
+//! use mofa_widgets::voices::{favorite_voices, favorites_first};
+//!
+//! let mut voices = backend.voices()?;
+//! favorites_first(&mut voices, &favorite_voices(), backend.name(), |voice| &voice.id);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::app_storage;
+use crate::audio_player::AudioPlayer;
+use crate::tts::{self, TtsOptions, TtsVoice};
+
+/// Favorites and sample texts, relative to the data root
+pub const VOICE_PREFS_FILE: &str = "voices/favorites.json";
+
+/// Sample sentences for languages without a configured text
+const DEFAULT_SAMPLES: &[(&str, &str)] = &[
+    ("en", "Hello! This is how I sound when I read your text aloud."),
+    ("zh", "你好！这就是我朗读文字时的声音。"),
+    ("ja", "こんにちは。これが私の読み上げの声です。"),
+    ("de", "Hallo! So klinge ich, wenn ich deinen Text vorlese."),
+    ("fr", "Bonjour ! Voici ma voix quand je lis votre texte."),
+    ("es", "¡Hola! Así sueno cuando leo tu texto en voz alta."),
+];
+
+/// Language whose sample is used when nothing matches
+const FALLBACK_LANGUAGE: &str = "en";
+
+/// How often a preview checks playback and cancellation
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A starred voice
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FavoriteVoice {
+    /// [`TtsBackend::name`](crate::tts::TtsBackend::name) of the voice's backend
+    pub backend: String,
+    /// [`TtsVoice::id`]
+    pub id: String,
+}
+
+/// Voice preferences shared by Settings and the apps
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VoicePrefs {
+    /// In the order they were starred
+    pub favorites: Vec<FavoriteVoice>,
+    /// Preview sentence per primary language (`en`, `zh`, ...)
+    pub sample_texts: BTreeMap<String, String>,
+}
+
+impl VoicePrefs {
+    pub fn path() -> PathBuf {
+        app_storage::data_path(VOICE_PREFS_FILE)
+    }
+
+    /// Saved preferences, defaults if none or unreadable
+    pub fn load() -> Self {
+        Self::load_from(&Self::path())
+    }
+
+    pub fn load_from(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        self.save_to(&Self::path())
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| e.to_string())
+    }
+
+    pub fn is_favorite(&self, backend: &str, id: &str) -> bool {
+        is_favorite(&self.favorites, backend, id)
+    }
+
+    /// Star or unstar a voice; returns whether it is now a favorite
+    pub fn toggle_favorite(&mut self, backend: &str, id: &str) -> bool {
+        if self.is_favorite(backend, id) {
+            self.favorites.retain(|f| f.backend != backend || f.id != id);
+            false
+        } else {
+            self.favorites.push(FavoriteVoice {
+                backend: backend.to_string(),
+                id: id.to_string(),
+            });
+            true
+        }
+    }
+
+    /// Preview sentence for a voice speaking `language` (`en_US`, `zh-CN`, ...)
+    pub fn sample_text(&self, language: &str) -> String {
+        let key = primary_language(language);
+        if let Some(text) = self.sample_texts.get(&key) {
+            return text.clone();
+        }
+        let default = |lang: &str| DEFAULT_SAMPLES.iter().find(|(l, _)| *l == lang).map(|(_, text)| *text);
+        default(&key)
+            .or_else(|| default(FALLBACK_LANGUAGE))
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Configure the preview sentence for `language`; empty restores the default
+    pub fn set_sample_text(&mut self, language: &str, text: &str) {
+        let key = primary_language(language);
+        let text = text.trim();
+        if text.is_empty() {
+            self.sample_texts.remove(&key);
+        } else {
+            self.sample_texts.insert(key, text.to_string());
+        }
+    }
+}
+
+/// The user's starred voices, for ordering voice dropdowns
+pub fn favorite_voices() -> Vec<FavoriteVoice> {
+    VoicePrefs::load().favorites
+}
+
+fn is_favorite(favorites: &[FavoriteVoice], backend: &str, id: &str) -> bool {
+    favorites.iter().any(|f| f.backend == backend && f.id == id)
+}
+
+/// Move the favorites of `backend` to the front, keeping the order otherwise
+pub fn favorites_first<T>(items: &mut [T], favorites: &[FavoriteVoice], backend: &str, id: impl Fn(&T) -> &str) {
+    items.sort_by_key(|item| !is_favorite(favorites, backend, id(item)));
+}
+
+/// `en` for `en_US`, `zh` for `zh-CN`; empty for an unknown locale
+pub fn primary_language(language: &str) -> String {
+    language
+        .split(['_', '-'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+/// Voices of one backend, or why they could not be listed
+#[derive(Debug, Clone)]
+pub struct BackendVoices {
+    pub backend: String,
+    pub voices: Result<Vec<TtsVoice>, String>,
+}
+
+/// Query every registered backend. Blocks while the backends are asked;
+/// one failing does not affect the others.
+pub fn catalogue() -> Vec<BackendVoices> {
+    tts::backends()
+        .iter()
+        .map(|backend| BackendVoices {
+            backend: backend.name().to_string(),
+            voices: backend.voices(),
+        })
+        .collect()
+}
+
+/// Whether a voice matches a filter typed by the user. Every word must
+/// appear in the name, locale or backend, or start the gender, so `male`
+/// does not match female voices.
+pub fn matches_filter(backend: &str, voice: &TtsVoice, filter: &str) -> bool {
+    let haystack = format!("{} {} {}", voice.name, voice.language, backend).to_lowercase();
+    let gender = voice.gender.to_lowercase();
+    filter
+        .to_lowercase()
+        .split_whitespace()
+        .all(|word| haystack.contains(word) || (!gender.is_empty() && gender.starts_with(word)))
+}
+
+/// Progress of a voice preview
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewState {
+    Synthesizing,
+    Playing,
+    Done,
+    Failed(String),
+}
+
+/// Sample sentence being synthesized and played on a worker thread.
+/// Dropping the preview stops it.
+pub struct VoicePreview {
+    voice_name: String,
+    state: Arc<Mutex<PreviewState>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl VoicePreview {
+    pub fn start(backend: &str, voice: &TtsVoice, text: &str) -> Self {
+        let state = Arc::new(Mutex::new(PreviewState::Synthesizing));
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let backend = backend.to_string();
+        let options = TtsOptions {
+            voice: Some(voice.id.clone()),
+            ..Default::default()
+        };
+        let text = text.to_string();
+        let worker_state = state.clone();
+        let worker_cancel = cancel.clone();
+        std::thread::spawn(move || {
+            let result = play_preview(&backend, &text, &options, &worker_state, &worker_cancel)
+                .unwrap_or_else(PreviewState::Failed);
+            *worker_state.lock().unwrap() = result;
+        });
+
+        Self {
+            voice_name: voice.name.clone(),
+            state,
+            cancel,
+        }
+    }
+
+    pub fn voice_name(&self) -> &str {
+        &self.voice_name
+    }
+
+    pub fn state(&self) -> PreviewState {
+        self.state.lock().unwrap().clone()
+    }
+
+    pub fn stop(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for VoicePreview {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn play_preview(
+    backend: &str,
+    text: &str,
+    options: &TtsOptions,
+    state: &Mutex<PreviewState>,
+    cancel: &AtomicBool,
+) -> Result<PreviewState, String> {
+    let backend = tts::backend(backend).ok_or_else(|| format!("Unknown TTS backend: {}", backend))?;
+    let audio = backend.synthesize(text, options)?;
+    if cancel.load(Ordering::Relaxed) {
+        return Ok(PreviewState::Done);
+    }
+
+    let player = AudioPlayer::new(audio.sample_rate)?;
+    player.write_audio(&audio.samples, None, None);
+    *state.lock().unwrap() = PreviewState::Playing;
+
+    // Give the audio thread a moment to pick up the samples
+    std::thread::sleep(POLL_INTERVAL);
+    while player.buffer_seconds() > 0.0 {
+        if cancel.load(Ordering::Relaxed) {
+            player.reset();
+            break;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    Ok(PreviewState::Done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voice(name: &str, language: &str, gender: &str) -> TtsVoice {
+        TtsVoice {
+            id: name.to_string(),
+            name: name.to_string(),
+            language: language.to_string(),
+            gender: gender.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_favorites_round_trip() {
+        let path = std::env::temp_dir().join(format!("mofa-voices-test-{}.json", std::process::id()));
+        let mut prefs = VoicePrefs::default();
+        assert!(prefs.toggle_favorite("say", "Samantha"));
+        assert!(prefs.toggle_favorite("say", "Ting-Ting"));
+        assert!(prefs.toggle_favorite("cloud", "Samantha"));
+        assert!(!prefs.toggle_favorite("say", "Samantha"));
+        prefs.set_sample_text("zh_CN", "  大家好  ");
+        prefs.save_to(&path).unwrap();
+
+        let loaded = VoicePrefs::load_from(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(loaded, prefs);
+        assert!(loaded.is_favorite("say", "Ting-Ting"));
+        assert!(!loaded.is_favorite("say", "Samantha"));
+        assert!(loaded.is_favorite("cloud", "Samantha"));
+        assert_eq!(loaded.sample_texts["zh"], "大家好");
+
+        // Missing or broken files fall back to defaults
+        assert_eq!(VoicePrefs::load_from(&path), VoicePrefs::default());
+    }
+
+    #[test]
+    fn test_favorites_first() {
+        let favorites = vec![
+            FavoriteVoice { backend: "say".into(), id: "Daniel".into() },
+            FavoriteVoice { backend: "say".into(), id: "Mei-Jia".into() },
+            FavoriteVoice { backend: "cloud".into(), id: "Alex".into() },
+        ];
+        let mut ids = ["Ting-Ting", "Mei-Jia", "Sin-ji", "Samantha", "Alex", "Daniel"];
+        favorites_first(&mut ids, &favorites, "say", |id| id);
+        assert_eq!(ids, ["Mei-Jia", "Daniel", "Ting-Ting", "Sin-ji", "Samantha", "Alex"]);
+
+        let mut voices = vec![voice("Alex", "en_US", "male"), voice("Samantha", "en_US", "female")];
+        favorites_first(&mut voices, &[], "say", |voice| &voice.id);
+        assert_eq!(voices[0].id, "Alex");
+    }
+
+    #[test]
+    fn test_sample_text_per_language() {
+        let mut prefs = VoicePrefs::default();
+        assert!(prefs.sample_text("zh-TW").starts_with("你好"));
+        assert!(prefs.sample_text("en_GB").starts_with("Hello"));
+        // Languages without a sample read the English one
+        assert_eq!(prefs.sample_text("fi_FI"), prefs.sample_text("en_US"));
+        assert_eq!(prefs.sample_text(""), prefs.sample_text("en_US"));
+
+        prefs.set_sample_text("en_US", "Testing, one two three.");
+        assert_eq!(prefs.sample_text("en_AU"), "Testing, one two three.");
+        prefs.set_sample_text("en", " ");
+        assert!(prefs.sample_text("en_AU").starts_with("Hello"));
+    }
+
+    #[test]
+    fn test_matches_filter() {
+        let samantha = voice("Samantha", "en_US", "female");
+        let daniel = voice("Daniel", "en_GB", "male");
+        assert!(matches_filter("say", &samantha, ""));
+        assert!(matches_filter("say", &samantha, "SAM"));
+        assert!(matches_filter("say", &samantha, "en_us say"));
+        assert!(!matches_filter("say", &samantha, "en_gb"));
+        assert!(matches_filter("say", &samantha, "fem"));
+        assert!(!matches_filter("say", &samantha, "male"));
+        assert!(matches_filter("say", &daniel, "male en"));
+        assert!(!matches_filter("say", &voice("Grandma", "en_US", ""), "male"));
+    }
+}
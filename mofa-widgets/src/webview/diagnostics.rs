@@ -0,0 +1,288 @@
+//! Initialization diagnostics
+//!
+//! [`WebViewContainer`](super::WebViewContainer) retries creating its native
+//! view until the window is ready. Every try is kept as an [`InitAttempt`] in
+//! a small ring buffer, so a failure can say what was tried: the bounds, whether
+//! the window handle was found, and what wry reported. Screens get the last
+//! attempts with [`WebViewAction::InitFailed`](super::WebViewAction) and can
+//! show [`WebViewContainerRef::debug_report`](super::WebViewContainerRef::debug_report)
+//! from their error state. Containers also [`publish_report`] after every
+//! attempt, so the diagnostic bundle can include reports from screens that
+//! are not open.
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::fmt::{self, Write};
+use std::time::{Duration, Instant};
+
+use super::wry_wrapper::{WebViewBounds, WebViewError};
+
+/// Attempts kept in the ring buffer
+pub const MAX_RECORDED_ATTEMPTS: usize = 10;
+/// Attempts included with an [`InitFailure`]
+pub const REPORTED_ATTEMPTS: usize = 3;
+/// Containers whose latest report is kept for the diagnostic bundle
+pub const MAX_PUBLISHED_REPORTS: usize = 16;
+
+/// Latest report per container (by widget uid), oldest first
+static PUBLISHED: Mutex<Vec<(u64, String)>> = Mutex::new(Vec::new());
+
+/// Keep a container's latest report, replacing the one it published before
+pub fn publish_report(container: u64, report: String) {
+    let mut published = PUBLISHED.lock();
+    published.retain(|(id, _)| *id != container);
+    if published.len() == MAX_PUBLISHED_REPORTS {
+        published.remove(0);
+    }
+    published.push((container, report));
+}
+
+/// The latest report of every container that tried to initialize
+pub fn published_reports() -> Vec<String> {
+    PUBLISHED.lock().iter().map(|(_, report)| report.clone()).collect()
+}
+
+/// One try at creating the native WebView
+#[derive(Debug, Clone)]
+pub struct InitAttempt {
+    /// 1-based attempt number
+    pub attempt: u32,
+    /// Time since initialization began when this attempt started
+    pub elapsed_ms: u64,
+    /// How long the attempt itself took
+    pub duration_ms: u64,
+    pub bounds: WebViewBounds,
+    /// False when the widget had not been laid out and a default size was used
+    pub bounds_from_layout: bool,
+    /// Whether the native window handle was acquired
+    pub handle_acquired: bool,
+    /// Why the attempt failed (the handle or wry error); `None` on success
+    pub error: Option<String>,
+}
+
+impl InitAttempt {
+    pub fn new(
+        attempt: u32,
+        elapsed_ms: u64,
+        duration: Duration,
+        bounds: WebViewBounds,
+        bounds_from_layout: bool,
+        result: &Result<(), WebViewError>,
+    ) -> Self {
+        Self {
+            attempt,
+            elapsed_ms,
+            duration_ms: duration.as_millis() as u64,
+            bounds,
+            bounds_from_layout,
+            handle_acquired: !matches!(result, Err(WebViewError::PlatformHandle(_))),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        }
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl fmt::Display for InitAttempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let b = &self.bounds;
+        write!(
+            f,
+            "#{} at {} ms (took {} ms): bounds {},{} {}x{}{}, handle {}",
+            self.attempt,
+            self.elapsed_ms,
+            self.duration_ms,
+            b.x,
+            b.y,
+            b.width,
+            b.height,
+            if self.bounds_from_layout { "" } else { " (default, not laid out)" },
+            if self.handle_acquired { "acquired" } else { "missing" },
+        )?;
+        match &self.error {
+            Some(error) => write!(f, ", {}", error),
+            None => write!(f, ", ok"),
+        }
+    }
+}
+
+/// Payload of [`WebViewAction::InitFailed`](super::WebViewAction)
+///
+/// Displays as a one-line message, so it can go straight into a status bar.
+#[derive(Debug, Clone)]
+pub struct InitFailure {
+    /// Error of the attempt that just failed
+    pub message: String,
+    pub attempt: u32,
+    pub max_attempts: u32,
+    /// The last few attempts, oldest first, ending with this one
+    pub recent: Vec<InitAttempt>,
+}
+
+impl InitFailure {
+    /// No more attempts will be made
+    pub fn is_final(&self) -> bool {
+        self.attempt >= self.max_attempts
+    }
+}
+
+impl fmt::Display for InitFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_final() {
+            write!(f, "{} (gave up after {} attempts)", self.message, self.attempt)
+        } else {
+            write!(f, "{} (attempt {}/{}, retrying)", self.message, self.attempt, self.max_attempts)
+        }
+    }
+}
+
+/// Attempts and timing of one container's initialization
+#[derive(Debug, Default)]
+pub struct InitDiagnostics {
+    started: Option<Instant>,
+    attempts: VecDeque<InitAttempt>,
+    time_to_initialize: Option<Duration>,
+}
+
+impl InitDiagnostics {
+    /// Start the clock; later calls keep the first start
+    pub fn begin(&mut self) {
+        self.started.get_or_insert_with(Instant::now);
+    }
+
+    /// Milliseconds since [`begin`](Self::begin), 0 before it
+    pub fn elapsed_ms(&self) -> u64 {
+        self.started.map_or(0, |s| s.elapsed().as_millis() as u64)
+    }
+
+    /// Keep an attempt, dropping the oldest once the buffer is full
+    pub fn record(&mut self, attempt: InitAttempt) {
+        if self.attempts.len() == MAX_RECORDED_ATTEMPTS {
+            self.attempts.pop_front();
+        }
+        if attempt.succeeded() {
+            self.time_to_initialize = self.started.map(|s| s.elapsed());
+        }
+        self.attempts.push_back(attempt);
+    }
+
+    /// Recorded attempts, oldest first
+    pub fn attempts(&self) -> impl Iterator<Item = &InitAttempt> {
+        self.attempts.iter()
+    }
+
+    /// The last `count` attempts, oldest first
+    pub fn recent(&self, count: usize) -> Vec<InitAttempt> {
+        let skip = self.attempts.len().saturating_sub(count);
+        self.attempts.iter().skip(skip).cloned().collect()
+    }
+
+    /// Failure payload for the attempt just recorded
+    pub fn failure(&self, max_attempts: u32) -> Option<InitFailure> {
+        let last = self.attempts.back()?;
+        Some(InitFailure {
+            message: last.error.clone()?,
+            attempt: last.attempt,
+            max_attempts,
+            recent: self.recent(REPORTED_ATTEMPTS),
+        })
+    }
+
+    /// From [`begin`](Self::begin) to the successful attempt
+    pub fn time_to_initialize(&self) -> Option<Duration> {
+        self.time_to_initialize
+    }
+
+    /// Outcome and every recorded attempt, one per line
+    pub fn report(&self, max_attempts: u32) -> String {
+        let tried = self.attempts.back().map_or(0, |a| a.attempt);
+        let mut report = match self.time_to_initialize {
+            Some(time) => format!("Initialized after {} attempt(s) in {} ms\n", tried, time.as_millis()),
+            None if tried >= max_attempts => format!("Failed: gave up after {} attempts\n", tried),
+            None if self.started.is_some() => {
+                format!("Initializing: {}/{} attempts, {} ms so far\n", tried, max_attempts, self.elapsed_ms())
+            }
+            None => "Not started (the container was never activated)\n".to_string(),
+        };
+        if tried as usize > self.attempts.len() {
+            let _ = writeln!(report, "(earliest {} attempts not kept)", tried as usize - self.attempts.len());
+        }
+        for attempt in &self.attempts {
+            let _ = writeln!(report, "  {}", attempt);
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webview::platform_handle::PlatformHandleError;
+
+    fn attempt(n: u32, result: &Result<(), WebViewError>) -> InitAttempt {
+        InitAttempt::new(n, n as u64 * 500, Duration::from_millis(2), WebViewBounds::new(0, 0, 800, 600), n > 1, result)
+    }
+
+    #[test]
+    fn test_attempts_are_classified() {
+        let no_window = attempt(1, &Err(PlatformHandleError::NoWindow.into()));
+        assert!(!no_window.handle_acquired);
+        assert_eq!(no_window.error.as_deref(), Some("Platform handle error: No window available"));
+        assert!(no_window.to_string().contains("(default, not laid out), handle missing"));
+
+        let ok = attempt(2, &Ok(()));
+        assert!(ok.handle_acquired && ok.succeeded());
+        assert_eq!(ok.to_string(), "#2 at 1000 ms (took 2 ms): bounds 0,0 800x600, handle acquired, ok");
+    }
+
+    #[test]
+    fn test_ring_buffer_and_failure_payload() {
+        let mut diagnostics = InitDiagnostics::default();
+        diagnostics.begin();
+        for n in 1..=12 {
+            diagnostics.record(attempt(n, &Err(PlatformHandleError::NoWindow.into())));
+        }
+        assert_eq!(diagnostics.attempts().count(), MAX_RECORDED_ATTEMPTS);
+        assert_eq!(diagnostics.attempts().next().unwrap().attempt, 3);
+
+        let failure = diagnostics.failure(12).unwrap();
+        assert!(failure.is_final());
+        assert_eq!(failure.recent.iter().map(|a| a.attempt).collect::<Vec<_>>(), vec![10, 11, 12]);
+        assert_eq!(failure.to_string(), "Platform handle error: No window available (gave up after 12 attempts)");
+
+        let report = diagnostics.report(12);
+        assert!(report.starts_with("Failed: gave up after 12 attempts\n(earliest 2 attempts not kept)\n"));
+        assert_eq!(report.lines().count(), 2 + MAX_RECORDED_ATTEMPTS);
+    }
+
+    #[test]
+    fn test_time_to_initialize() {
+        let mut diagnostics = InitDiagnostics::default();
+        assert!(diagnostics.report(10).starts_with("Not started"));
+
+        diagnostics.begin();
+        diagnostics.record(attempt(1, &Err(PlatformHandleError::NoWindow.into())));
+        assert!(diagnostics.failure(10).is_some_and(|f| !f.is_final()));
+        assert!(diagnostics.time_to_initialize().is_none());
+
+        diagnostics.record(attempt(2, &Ok(())));
+        assert!(diagnostics.time_to_initialize().is_some());
+        assert!(diagnostics.failure(10).is_none());
+        assert!(diagnostics.report(10).starts_with("Initialized after 2 attempt(s) in "));
+    }
+
+    #[test]
+    fn test_published_reports_keep_the_latest_per_container() {
+        for n in 0..=MAX_PUBLISHED_REPORTS as u64 {
+            publish_report(n, format!("report {}", n));
+        }
+        publish_report(5, "report 5, retried".to_string());
+
+        let reports = published_reports();
+        assert_eq!(reports.len(), MAX_PUBLISHED_REPORTS);
+        assert_eq!(reports[0], "report 1");
+        assert_eq!(reports.last().unwrap(), "report 5, retried");
+    }
+}
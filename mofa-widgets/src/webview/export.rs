@@ -0,0 +1,415 @@
+//! Exporting a page's note to disk
+//!
+//! Pages cannot show a native save dialog, so they ask the host over IPC on
+//! [`EXPORT_NOTE_CHANNEL`], sending the payload JSON-encoded as a string so
+//! newlines survive the bridge:
+//!
+//! ```js
+//! window.__mofa_ipc.send("export_note", JSON.stringify({
+//!     request_id: "export-1",
+//!     title: "Meeting",
+//!     markdown: "# Meeting\n![plan](/api/files/plan.png)",
+//!     attachments: [
+//!         { name: "plan.png", url: "/api/files/plan.png" },
+//!         { name: "todo.txt", data: "<base64>" },
+//!     ],
+//! }));
+//! ```
+//!
+//! The user picks the file in a save dialog defaulting to `<title>.md`. The
+//! markdown is written there and the attachments to a `<name>_attachments`
+//! folder beside it; links to an attachment's `url` are rewritten to point at
+//! the copy. Attachments with a `url` are streamed from the page's own
+//! server, so large files never pass through IPC; `data` is meant for small
+//! content the page only has in memory. Writing happens on a worker thread:
+//! [`handle_export_note`] returns an [`ExportJob`] the screen polls for the
+//! reply.
+//!
+//! The reply arrives on the same channel with the request's `request_id` and
+//! one of `path`, `cancelled: true` or `error`.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use base64::Engine;
+
+/// IPC channel of export requests and their replies
+pub const EXPORT_NOTE_CHANNEL: &str = "export_note";
+
+/// Longest file name derived from a title (in characters)
+const MAX_NAME_CHARS: usize = 100;
+
+/// A note to export, as sent by the page
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ExportRequest {
+    /// Echoed in the reply so the page can match it
+    pub request_id: Value,
+    pub title: String,
+    pub markdown: String,
+    pub attachments: Vec<ExportAttachment>,
+}
+
+/// A file exported next to the note
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ExportAttachment {
+    pub name: String,
+    /// Path or URL on the page's server to stream the content from
+    pub url: Option<String>,
+    /// Base64 content, for attachments the server does not have
+    pub data: Option<String>,
+}
+
+/// How an export ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportOutcome {
+    Saved(PathBuf),
+    Cancelled,
+    Failed(String),
+}
+
+impl ExportOutcome {
+    /// Reply sent back to the page
+    pub fn reply(&self, request_id: &Value) -> Value {
+        match self {
+            ExportOutcome::Saved(path) => json!({
+                "request_id": request_id,
+                "path": path.to_string_lossy(),
+            }),
+            ExportOutcome::Cancelled => json!({ "request_id": request_id, "cancelled": true }),
+            ExportOutcome::Failed(error) => json!({ "request_id": request_id, "error": error }),
+        }
+    }
+}
+
+/// An export being written on a worker thread
+pub struct ExportJob {
+    rx: Receiver<Value>,
+}
+
+impl ExportJob {
+    /// A job that already has its reply
+    fn done(reply: Value) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let _ = tx.send(reply);
+        Self { rx }
+    }
+
+    /// Write `request` to `path` on a worker thread
+    pub fn spawn(request: ExportRequest, path: PathBuf, page_url: String) -> Self {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let outcome = match write_export(&request, &path, &page_url) {
+                Ok(()) => {
+                    ::log::info!("Exported note to {}", path.display());
+                    ExportOutcome::Saved(path)
+                }
+                Err(e) => {
+                    ::log::warn!("Failed to export note to {}: {}", path.display(), e);
+                    ExportOutcome::Failed(e)
+                }
+            };
+            let _ = tx.send(outcome.reply(&request.request_id));
+        });
+        Self { rx }
+    }
+
+    /// The reply for the page once the export has finished
+    pub fn try_reply(&self) -> Option<Value> {
+        match self.rx.try_recv() {
+            Ok(reply) => Some(reply),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                Some(ExportOutcome::Failed("export worker exited".to_string()).reply(&Value::Null))
+            }
+        }
+    }
+}
+
+/// Parse a request, sent either as an object or JSON-encoded in a string
+pub fn parse_request(data: &str) -> Result<ExportRequest, String> {
+    let value = match serde_json::from_str(data) {
+        Ok(Value::String(inner)) => serde_json::from_str(&inner),
+        other => other,
+    }
+    .map_err(|e| format!("Invalid export request: {}", e))?;
+    serde_json::from_value(value).map_err(|e| format!("Invalid export request: {}", e))
+}
+
+/// Handle an export request from a page loaded from `page_url`: ask where
+/// to save, then write the note and its attachments on a worker thread
+pub fn handle_export_note(data: &str, page_url: &str) -> ExportJob {
+    let request = match parse_request(data) {
+        Ok(request) => request,
+        Err(e) => return ExportJob::done(ExportOutcome::Failed(e).reply(&Value::Null)),
+    };
+
+    let mut dialog = rfd::FileDialog::new()
+        .set_title("Export note")
+        .set_file_name(default_file_name(&request.title))
+        .add_filter("Markdown", &["md"]);
+    if let Some(dir) = dirs::document_dir() {
+        dialog = dialog.set_directory(dir);
+    }
+
+    match dialog.save_file() {
+        None => ExportJob::done(ExportOutcome::Cancelled.reply(&request.request_id)),
+        Some(path) => ExportJob::spawn(request, path, page_url.to_string()),
+    }
+}
+
+/// Reply for a page that may not export files
+pub fn permission_denied(data: &str, permission: &str) -> Value {
+    let request_id = parse_request(data).map(|r| r.request_id).unwrap_or(Value::Null);
+    ExportOutcome::Failed(format!("permission required: {}", permission)).reply(&request_id)
+}
+
+/// `<title>.md` with characters file systems reject replaced
+pub fn default_file_name(title: &str) -> String {
+    format!("{}.md", sanitize_file_name(title, "Untitled"))
+}
+
+fn sanitize_file_name(name: &str, fallback: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .take(MAX_NAME_CHARS)
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('.').trim();
+    if cleaned.is_empty() {
+        fallback.to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// Folder the attachments of the note saved at `path` go to
+pub fn attachments_dir(path: &Path) -> PathBuf {
+    let stem = path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+    path.with_file_name(format!("{}_attachments", stem))
+}
+
+/// Absolute URL of an attachment on the page's server; `None` for URLs on
+/// other hosts, so a page cannot make the host fetch arbitrary content
+pub fn resolve_url(page_url: &str, url: &str) -> Option<String> {
+    let scheme_end = page_url.find("://")? + 3;
+    let origin_end = page_url[scheme_end..].find('/').map_or(page_url.len(), |i| scheme_end + i);
+    let origin = &page_url[..origin_end];
+
+    if url.starts_with('/') && !url.starts_with("//") {
+        Some(format!("{}{}", origin, url))
+    } else if url.strip_prefix(origin).is_some_and(|rest| rest.starts_with('/')) {
+        Some(url.to_string())
+    } else {
+        None
+    }
+}
+
+/// Write the note to `path` and its attachments beside it
+pub fn write_export(request: &ExportRequest, path: &Path, page_url: &str) -> Result<(), String> {
+    let mut markdown = request.markdown.clone();
+
+    if !request.attachments.is_empty() {
+        let dir = attachments_dir(path);
+        fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        let dir_name = dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+
+        let mut used = Vec::new();
+        for attachment in &request.attachments {
+            let name = unique_name(&sanitize_file_name(&attachment.name, "attachment"), &used);
+            let dest = dir.join(&name);
+            write_attachment(attachment, &dest, page_url)?;
+
+            if let Some(url) = attachment.url.as_deref().filter(|u| !u.is_empty()) {
+                let link = format!("{}/{}", dir_name, name).replace(' ', "%20");
+                markdown = markdown.replace(url, &link);
+            }
+            used.push(name);
+        }
+    }
+
+    fs::write(path, markdown).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn write_attachment(attachment: &ExportAttachment, dest: &Path, page_url: &str) -> Result<(), String> {
+    let fail = |e: String| format!("Attachment {}: {}", attachment.name, e);
+
+    if let Some(url) = &attachment.url {
+        let url = resolve_url(page_url, url).ok_or_else(|| fail(format!("not on the page's server: {}", url)))?;
+        let response = ureq::get(&url).call().map_err(|e| fail(e.to_string()))?;
+        let mut file = File::create(dest).map_err(|e| fail(e.to_string()))?;
+        io::copy(&mut response.into_reader(), &mut file).map_err(|e| fail(e.to_string()))?;
+        Ok(())
+    } else if let Some(data) = &attachment.data {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data.trim())
+            .map_err(|e| fail(format!("invalid base64: {}", e)))?;
+        fs::write(dest, bytes).map_err(|e| fail(e.to_string()))
+    } else {
+        Err(fail("no url or data".to_string()))
+    }
+}
+
+/// `name`, or `name (2)`, `name (3)`... if already taken
+fn unique_name(name: &str, used: &[String]) -> String {
+    if !used.iter().any(|u| u == name) {
+        return name.to_string();
+    }
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, format!(".{}", ext)),
+        _ => (name, String::new()),
+    };
+    (2..)
+        .map(|n| format!("{} ({}){}", stem, n, ext))
+        .find(|candidate| !used.contains(candidate))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_server::{HttpResponse, LocalServer};
+
+    #[test]
+    fn test_parse_request_forms() {
+        let payload = r##"{"request_id":"export-1","title":"Plan","markdown":"# Plan\nsteps","attachments":[{"name":"a.png","url":"/api/files/a.png"}]}"##;
+        let request = parse_request(payload).unwrap();
+        assert_eq!(request.request_id, json!("export-1"));
+        assert_eq!(request.markdown, "# Plan\nsteps");
+        assert_eq!(request.attachments[0].url.as_deref(), Some("/api/files/a.png"));
+
+        // JSON-encoded in a string, as pages send it
+        let wrapped = serde_json::to_string(payload).unwrap();
+        assert_eq!(parse_request(&wrapped).unwrap(), request);
+        assert!(parse_request("not json").is_err());
+    }
+
+    #[test]
+    fn test_file_names() {
+        assert_eq!(default_file_name("Meeting: Q3 / plan?"), "Meeting- Q3 - plan-.md");
+        assert_eq!(default_file_name("  ..  "), "Untitled.md");
+        assert_eq!(default_file_name(&"x".repeat(300)).len(), MAX_NAME_CHARS + 3);
+        assert_eq!(
+            attachments_dir(Path::new("/tmp/notes/Plan.md")),
+            PathBuf::from("/tmp/notes/Plan_attachments")
+        );
+
+        let used = vec!["a.png".to_string(), "a (2).png".to_string()];
+        assert_eq!(unique_name("b.png", &used), "b.png");
+        assert_eq!(unique_name("a.png", &used), "a (3).png");
+    }
+
+    #[test]
+    fn test_resolve_url_stays_on_page_server() {
+        let page = "http://127.0.0.1:8123/index.html";
+        assert_eq!(resolve_url(page, "/api/files/a.png").as_deref(), Some("http://127.0.0.1:8123/api/files/a.png"));
+        assert_eq!(
+            resolve_url(page, "http://127.0.0.1:8123/a.png").as_deref(),
+            Some("http://127.0.0.1:8123/a.png")
+        );
+        assert_eq!(resolve_url(page, "http://127.0.0.1:81234/a.png"), None);
+        assert_eq!(resolve_url(page, "//evil.example/a.png"), None);
+        assert_eq!(resolve_url(page, "https://evil.example/a.png"), None);
+        assert_eq!(resolve_url("about:blank", "/a.png"), None);
+    }
+
+    #[test]
+    fn test_write_export_streams_and_relinks_attachments() {
+        let mut server = LocalServer::default();
+        let port = server
+            .start(|req| match req.path.as_str() {
+                "/api/files/plan.png" => HttpResponse::new("200 OK", "image/png", b"PNG".to_vec()),
+                _ => HttpResponse::not_found(),
+            })
+            .unwrap();
+        let page_url = format!("http://127.0.0.1:{}/index.html", port);
+
+        let dir = std::env::temp_dir().join(format!("mofa-export-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("My plan.md");
+
+        let request = ExportRequest {
+            title: "My plan".to_string(),
+            markdown: "![plan](/api/files/plan.png)\nSee [todo](blob:todo)".to_string(),
+            attachments: vec![
+                ExportAttachment { name: "plan.png".to_string(), url: Some("/api/files/plan.png".to_string()), data: None },
+                ExportAttachment { name: "todo.txt".to_string(), url: None, data: Some("aGVsbG8=".to_string()) },
+            ],
+            ..Default::default()
+        };
+        write_export(&request, &path, &page_url).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        let image = fs::read(dir.join("My plan_attachments/plan.png")).unwrap();
+        let text = fs::read_to_string(dir.join("My plan_attachments/todo.txt")).unwrap();
+
+        let missing = ExportRequest {
+            attachments: vec![ExportAttachment { name: "x".to_string(), url: Some("/api/files/x".to_string()), data: None }],
+            ..request.clone()
+        };
+        let missing_error = write_export(&missing, &path, &page_url).unwrap_err();
+        let foreign = ExportRequest {
+            attachments: vec![ExportAttachment { name: "x".to_string(), url: Some("https://evil.example/x".to_string()), data: None }],
+            ..request.clone()
+        };
+        let foreign_error = write_export(&foreign, &path, &page_url).unwrap_err();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(saved, "![plan](My%20plan_attachments/plan.png)\nSee [todo](blob:todo)");
+        assert_eq!(image, b"PNG");
+        assert_eq!(text, "hello");
+        assert!(missing_error.starts_with("Attachment x:"));
+        assert!(foreign_error.contains("not on the page's server"));
+    }
+
+    #[test]
+    fn test_export_job_replies_once_written() {
+        let dir = std::env::temp_dir().join(format!("mofa-export-job-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Plan.md");
+        let request = ExportRequest {
+            request_id: json!("export-2"),
+            markdown: "# Plan".to_string(),
+            attachments: vec![ExportAttachment { name: "a.txt".to_string(), url: None, data: Some("aGk=".to_string()) }],
+            ..Default::default()
+        };
+
+        let job = ExportJob::spawn(request, path.clone(), "http://127.0.0.1:1/".to_string());
+        let reply = loop {
+            if let Some(reply) = job.try_reply() {
+                break reply;
+            }
+            thread::sleep(std::time::Duration::from_millis(5));
+        };
+        let attachment = fs::read_to_string(dir.join("Plan_attachments/a.txt")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(reply, json!({ "request_id": "export-2", "path": path.to_string_lossy() }));
+        assert_eq!(attachment, "hi");
+    }
+
+    #[test]
+    fn test_replies() {
+        let path = PathBuf::from("/tmp/Plan.md");
+        assert_eq!(
+            ExportOutcome::Saved(path.clone()).reply(&json!(7)),
+            json!({ "request_id": 7, "path": "/tmp/Plan.md" })
+        );
+        assert_eq!(ExportOutcome::Cancelled.reply(&json!(7))["cancelled"], true);
+        assert_eq!(
+            permission_denied(r#"{"request_id":"e1"}"#, "save-files"),
+            json!({ "request_id": "e1", "error": "permission required: save-files" })
+        );
+    }
+}
@@ -23,29 +23,71 @@
 //! }
 //! ```
 //!
+//! ## Theming
+//!
+//! Pages get the studio theme as CSS custom properties and a `mofa-theme`
+//! event (see [`theme_bridge`]). Screens forward their dark mode with
+//! [`WebViewContainerRef::set_dark_mode`] instead of evaluating scripts.
+//!
+//! ## Saving files
+//!
+//! Pages ask for a native save dialog with an `export_note` IPC request;
+//! screens pass it to [`export::handle_export_note`] and poll the returned
+//! [`export::ExportJob`] for the reply.
+//!
+//! ## Diagnostics
+//!
+//! Initialization is retried while the window is not ready. Each attempt is
+//! recorded (see [`diagnostics`]); `InitFailed` carries the last attempts,
+//! `InitTiming` reports the time to initialize, and
+//! [`WebViewContainerRef::debug_report`] describes the whole sequence.
+//!
+//! ## Safe mode
+//!
+//! While [`SafeModeFeature::WebViews`] is not allowed, containers do not
+//! create a WebView and show a notice instead; the screen gets no
+//! `Initialized` and its WebView calls return `NotInitialized`. Once the
+//! feature is re-enabled, active containers initialize on their next draw.
+//!
 //! ## Limitations
 //!
-//! - **Z-order**: WebView is always on top; Makepad elements cannot overlay it
+//! - **Z-order**: WebView is always on top; Makepad elements cannot overlay it.
+//!   Inside a scrolling layout the native view is clipped to the visible part
+//!   of the container, so it does not cover headers or status bars.
 //! - **Linux Wayland**: Only X11 is supported (wry limitation)
 //! - **Multi-window**: Uses key window by default; multi-window needs extra handling
 //! - **Timing**: Must initialize after window is created
 
+pub mod diagnostics;
+pub mod export;
 pub mod ipc;
 pub mod platform_handle;
+pub mod session;
+pub mod theme_bridge;
 pub mod wry_wrapper;
 
 use makepad_widgets::*;
 use std::sync::Arc;
 use parking_lot::Mutex;
 
+use crate::activity::{ActivityHandle, ActivityKind, ActivityRegistry};
+use crate::safe_mode::{SafeMode, SafeModeFeature};
+use self::diagnostics::InitDiagnostics;
+
+pub use self::diagnostics::{InitAttempt, InitFailure};
 pub use self::ipc::{IpcHandler, IpcMessage};
-pub use self::wry_wrapper::{ManagedWebView, WebViewBounds, WebViewConfig, WebViewError};
+pub use self::session::NavigationHistory;
+pub use self::theme_bridge::{WebTheme, THEME_EVENT};
+pub use self::wry_wrapper::{
+    ContentOffset, ManagedWebView, WebViewBounds, WebViewConfig, WebViewError, CLIP_READY_CHANNEL,
+};
 
 live_design! {
     use link::theme::*;
     use link::shaders::*;
     use link::widgets::*;
 
+    use crate::theme::SLATE_400;
     use crate::theme::SLATE_800;
 
     pub WebViewContainer = {{WebViewContainer}} <View> {
@@ -58,20 +100,40 @@ live_design! {
                 return (SLATE_800);
             }
         }
+
+        safe_mode_notice = <View> {
+            width: Fill, height: Fill
+            align: {x: 0.5, y: 0.5}
+            visible: false
+
+            <Label> {
+                draw_text: {
+                    text_style: { font_size: 12.0 }
+                    color: (SLATE_400)
+                }
+                text: "Web content is disabled in safe mode"
+            }
+        }
     }
 }
 
+/// Page shown while nothing is loaded
+const BLANK_URL: &str = "about:blank";
+
 /// Actions emitted by WebViewContainer
 #[derive(Clone, Debug, DefaultNone)]
 pub enum WebViewAction {
     None,
     /// WebView has been initialized
     Initialized,
-    /// WebView initialization failed
-    InitFailed(String),
+    /// An initialization attempt failed; retried until
+    /// [`InitFailure::is_final`]
+    InitFailed(InitFailure),
+    /// Sent after `Initialized`: time from activation to a working WebView
+    InitTiming { elapsed_ms: u64, attempts: u32 },
     /// Received IPC message from JavaScript
     IpcMessage { channel: String, data: String },
-    /// URL navigation occurred
+    /// A page finished loading at this URL
     UrlChanged(String),
 }
 
@@ -93,6 +155,15 @@ pub struct WebViewContainer {
     #[live(false)]
     transparent: bool,
 
+    /// Profile id for a private data directory (see `app_storage::webview_profile_dir`).
+    /// Empty uses the shared default profile.
+    #[live]
+    profile: String,
+
+    /// Private session: cookies and storage stay in memory, not in the profile
+    #[rust]
+    incognito: bool,
+
     /// Whether WebView is active (controls initialization and visibility)
     /// Set to false by default - must be activated explicitly
     #[rust]
@@ -110,6 +181,10 @@ pub struct WebViewContainer {
     #[rust]
     cached_rect: Option<Rect>,
 
+    /// Cached visible part of the widget (clipped by scrolling parents)
+    #[rust]
+    cached_clip: Option<Rect>,
+
     /// Frame count for delayed initialization
     #[rust]
     frame_count: u32,
@@ -117,6 +192,22 @@ pub struct WebViewContainer {
     /// Last initialization attempt frame
     #[rust]
     last_init_frame: u32,
+
+    /// Studio dark mode, kept for WebViews created later
+    #[rust]
+    dark_mode: f64,
+
+    /// Listed in the activity registry while a page is loaded
+    #[rust]
+    activity: Option<ActivityHandle>,
+
+    /// Recorded initialization attempts
+    #[rust]
+    init_diagnostics: InitDiagnostics,
+
+    /// Whether the safe-mode notice is shown
+    #[rust]
+    safe_mode_notice: bool,
 }
 
 impl WebViewContainer {
@@ -127,6 +218,15 @@ impl WebViewContainer {
     /// Initial delay frames before first attempt
     const INITIAL_DELAY: u32 = 10;
 
+    /// Error for calls made before a WebView exists
+    fn missing_webview() -> WebViewError {
+        if SafeMode::global().allows(SafeModeFeature::WebViews) {
+            WebViewError::NotInitialized
+        } else {
+            WebViewError::DisabledInSafeMode
+        }
+    }
+
     /// Initialize the WebView
     ///
     /// This should be called after the window is created and the widget
@@ -140,6 +240,7 @@ impl WebViewContainer {
         self.last_init_frame = self.frame_count;
 
         // Get widget bounds
+        let bounds_from_layout = self.cached_rect.is_some();
         let bounds = if let Some(rect) = self.cached_rect {
             WebViewBounds {
                 x: rect.pos.x as i32,
@@ -157,7 +258,7 @@ impl WebViewContainer {
         };
 
         let url = if self.url.is_empty() {
-            "about:blank".to_string()
+            BLANK_URL.to_string()
         } else {
             self.url.clone()
         };
@@ -168,13 +269,41 @@ impl WebViewContainer {
             devtools: self.devtools,
             transparent: self.transparent,
             user_agent: None,
+            data_directory: if self.profile.is_empty() {
+                None
+            } else {
+                Some(crate::app_storage::webview_profile_dir(&self.profile))
+            },
+            incognito: self.incognito,
+            theme: WebTheme::new(self.dark_mode),
         };
 
         let mut webview = ManagedWebView::new(config);
 
-        match webview.initialize() {
+        let elapsed_ms = self.init_diagnostics.elapsed_ms();
+        let started = std::time::Instant::now();
+        let result = webview.initialize();
+        self.init_diagnostics.record(InitAttempt::new(
+            self.init_attempts,
+            elapsed_ms,
+            started.elapsed(),
+            bounds,
+            bounds_from_layout,
+            &result,
+        ));
+        diagnostics::publish_report(self.widget_uid().0, self.debug_report());
+
+        match result {
             Ok(()) => {
-                ::log::info!("[WebViewContainer] WebView initialized successfully");
+                let elapsed_ms = self
+                    .init_diagnostics
+                    .time_to_initialize()
+                    .map_or(0, |t| t.as_millis() as u64);
+                ::log::info!(
+                    "[WebViewContainer] WebView initialized successfully ({} ms, attempt {})",
+                    elapsed_ms,
+                    self.init_attempts
+                );
 
                 // Inject IPC bridge
                 if let Err(e) = webview.inject_ipc_bridge() {
@@ -182,45 +311,83 @@ impl WebViewContainer {
                 }
 
                 self.webview = Some(webview);
+                if let Some(rect) = self.cached_rect {
+                    self.sync_bounds(rect, self.cached_clip.unwrap_or(rect));
+                }
                 cx.widget_action(
                     self.widget_uid(),
                     &Scope::empty().path,
                     WebViewAction::Initialized,
                 );
+                cx.widget_action(
+                    self.widget_uid(),
+                    &Scope::empty().path,
+                    WebViewAction::InitTiming {
+                        elapsed_ms,
+                        attempts: self.init_attempts,
+                    },
+                );
             }
             Err(e) => {
                 ::log::error!("[WebViewContainer] Failed to initialize WebView: {}", e);
+                let Some(failure) = self.init_diagnostics.failure(Self::MAX_INIT_ATTEMPTS) else {
+                    return;
+                };
+                if failure.is_final() {
+                    ::log::error!("[WebViewContainer] Giving up:\n{}", self.debug_report());
+                }
                 cx.widget_action(
                     self.widget_uid(),
                     &Scope::empty().path,
-                    WebViewAction::InitFailed(e.to_string()),
+                    WebViewAction::InitFailed(failure),
                 );
             }
         }
     }
 
-    /// Update the WebView position to match widget bounds
-    fn sync_bounds(&mut self, rect: Rect) {
-        if let Some(ref mut webview) = self.webview {
-            let bounds = WebViewBounds {
-                x: rect.pos.x as i32,
-                y: rect.pos.y as i32,
-                width: rect.size.x.max(1.0) as u32,
-                height: rect.size.y.max(1.0) as u32,
-            };
+    /// Initialization outcome and attempts, for logs and bug reports
+    pub fn debug_report(&self) -> String {
+        format!(
+            "WebView on {}\nurl: {}\nprofile: {}{}\nactive: {}\n{}",
+            std::env::consts::OS,
+            if self.url.is_empty() { BLANK_URL } else { &self.url },
+            if self.profile.is_empty() { "(default)" } else { &self.profile },
+            if self.incognito { " (incognito)" } else { "" },
+            self.active,
+            self.init_diagnostics.report(Self::MAX_INIT_ATTEMPTS)
+        )
+    }
 
-            if let Err(e) = webview.set_bounds(bounds) {
+    /// Update the WebView position to match widget bounds, clipped to the
+    /// part of the widget that is actually visible
+    fn sync_bounds(&mut self, rect: Rect, clip: Rect) {
+        if let Some(ref mut webview) = self.webview {
+            if let Err(e) = webview.set_clipped_bounds(to_bounds(rect), to_bounds(clip)) {
                 ::log::warn!("[WebViewContainer] Failed to sync bounds: {}", e);
             }
         }
     }
 
+    /// Keep the activity entry in step with the loaded page
+    fn track_page(&mut self, url: &str) {
+        if url == BLANK_URL {
+            self.activity = None;
+            return;
+        }
+        let activity = self.activity.get_or_insert_with(|| {
+            ActivityRegistry::global()
+                .register(ActivityKind::WebView, &self.profile, "Embedded page")
+                .stoppable()
+        });
+        activity.set_state(url);
+    }
+
     /// Navigate to a URL
     pub fn load_url(&self, url: &str) -> Result<(), WebViewError> {
         if let Some(ref webview) = self.webview {
             webview.load_url(url)
         } else {
-            Err(WebViewError::NotInitialized)
+            Err(Self::missing_webview())
         }
     }
 
@@ -229,7 +396,18 @@ impl WebViewContainer {
         if let Some(ref webview) = self.webview {
             webview.eval(js)
         } else {
-            Err(WebViewError::NotInitialized)
+            Err(Self::missing_webview())
+        }
+    }
+
+    /// Follow the studio's dark mode; applied once the WebView exists if
+    /// it does not yet
+    pub fn set_dark_mode(&mut self, dark_mode: f64) {
+        self.dark_mode = dark_mode;
+        if let Some(ref mut webview) = self.webview {
+            if let Err(e) = webview.set_theme(WebTheme::new(dark_mode)) {
+                ::log::warn!("[WebViewContainer] Failed to apply theme: {}", e);
+            }
         }
     }
 
@@ -238,7 +416,7 @@ impl WebViewContainer {
         if let Some(ref webview) = self.webview {
             webview.go_back()
         } else {
-            Err(WebViewError::NotInitialized)
+            Err(Self::missing_webview())
         }
     }
 
@@ -247,7 +425,7 @@ impl WebViewContainer {
         if let Some(ref webview) = self.webview {
             webview.go_forward()
         } else {
-            Err(WebViewError::NotInitialized)
+            Err(Self::missing_webview())
         }
     }
 
@@ -256,7 +434,16 @@ impl WebViewContainer {
         if let Some(ref webview) = self.webview {
             webview.reload()
         } else {
-            Err(WebViewError::NotInitialized)
+            Err(Self::missing_webview())
+        }
+    }
+
+    /// Clear cookies, storage and caches of the profile
+    pub fn clear_browsing_data(&self) -> Result<(), WebViewError> {
+        if let Some(ref webview) = self.webview {
+            webview.clear_browsing_data()
+        } else {
+            Err(Self::missing_webview())
         }
     }
 
@@ -265,7 +452,7 @@ impl WebViewContainer {
         if let Some(ref webview) = self.webview {
             webview.send_to_js(channel, data)
         } else {
-            Err(WebViewError::NotInitialized)
+            Err(Self::missing_webview())
         }
     }
 
@@ -284,10 +471,33 @@ impl WebViewContainer {
         if let Some(ref mut webview) = self.webview {
             webview.set_visible(visible)
         } else {
-            Err(WebViewError::NotInitialized)
+            Err(Self::missing_webview())
+        }
+    }
+
+    /// Switch between the profile and a private in-memory session. A
+    /// WebView that already exists is recreated at `url`, as its data
+    /// store is fixed when it is built.
+    pub fn set_incognito(&mut self, cx: &mut Cx, incognito: bool, url: &str) {
+        if self.incognito == incognito {
+            return;
+        }
+        self.incognito = incognito;
+        if self.webview.take().is_some() {
+            self.url = url.to_string();
+            self.activity = None;
+            self.init_attempts = 0;
+            self.init_diagnostics = InitDiagnostics::default();
+            cx.new_next_frame();
+            self.view.redraw(cx);
         }
     }
 
+    /// Whether the session is private
+    pub fn is_incognito(&self) -> bool {
+        self.incognito
+    }
+
     /// Set active state - controls whether WebView initializes and shows
     pub fn set_active(&mut self, cx: &mut Cx, active: bool) {
         if self.active == active {
@@ -316,10 +526,28 @@ impl Widget for WebViewContainer {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
 
+        // Unloading the page is how a WebView is stopped from outside
+        if self.activity.as_ref().is_some_and(|a| a.stop_requested()) {
+            self.activity = None;
+            if let Err(e) = self.load_url(BLANK_URL) {
+                ::log::warn!("[WebViewContainer] Failed to unload page: {}", e);
+            }
+        }
+
         // Process IPC messages
         if let Some(ref webview) = self.webview {
             let messages = webview.ipc_handler().lock().poll_messages();
             for msg in messages {
+                // A page finished loading; restore the clip offset
+                if msg.channel == CLIP_READY_CHANNEL {
+                    let _ = webview.apply_content_offset();
+                    let _ = webview.apply_theme();
+                    if let Ok(url) = serde_json::from_str::<String>(&msg.data) {
+                        self.track_page(&url);
+                        cx.widget_action(self.widget_uid(), &scope.path, WebViewAction::UrlChanged(url));
+                    }
+                    continue;
+                }
                 cx.widget_action(
                     self.widget_uid(),
                     &scope.path,
@@ -335,14 +563,15 @@ impl Widget for WebViewContainer {
             Event::NextFrame(_) => {
                 self.frame_count += 1;
 
-                // Only initialize when active
-                if !self.active {
+                // Only initialize when active, and not in safe mode
+                if !self.active || !SafeMode::global().allows(SafeModeFeature::WebViews) {
                     return;
                 }
 
                 // Delay initialization to ensure window is ready
                 // Initial delay, then retry with interval if failed
                 if self.webview.is_none() && self.init_attempts < Self::MAX_INIT_ATTEMPTS {
+                    self.init_diagnostics.begin();
                     let should_try = if self.init_attempts == 0 {
                         // First attempt after initial delay
                         self.frame_count >= Self::INITIAL_DELAY
@@ -370,7 +599,7 @@ impl Widget for WebViewContainer {
                 // Sync bounds when window geometry changes (only if active)
                 if self.active {
                     if let Some(rect) = self.cached_rect {
-                        self.sync_bounds(rect);
+                        self.sync_bounds(rect, self.cached_clip.unwrap_or(rect));
                     }
                 }
             }
@@ -379,25 +608,32 @@ impl Widget for WebViewContainer {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let allowed = SafeMode::global().allows(SafeModeFeature::WebViews);
+        let notice = self.active && self.webview.is_none() && !allowed;
+        if notice != self.safe_mode_notice {
+            self.safe_mode_notice = notice;
+            self.view.view(ids!(safe_mode_notice)).set_visible(cx, notice);
+        }
+
         // Request NextFrame for initialization timing and retries (only when active)
-        if self.active && self.webview.is_none() && self.init_attempts < Self::MAX_INIT_ATTEMPTS {
+        if self.active && allowed && self.webview.is_none() && self.init_attempts < Self::MAX_INIT_ATTEMPTS {
             cx.new_next_frame();
         }
 
         let result = self.view.draw_walk(cx, scope, walk);
 
-        // Cache the absolute rect for WebView positioning
-        let rect = cx.turtle().rect();
-        let new_rect = Rect {
-            pos: rect.pos,
-            size: rect.size,
-        };
+        // Cache the absolute rect for WebView positioning, and the part of it
+        // left visible by scrolling parents (the placeholder's draw clip)
+        let area = self.view.area();
+        let new_rect = area.rect(cx);
+        let new_clip = area.clipped_rect(cx);
 
         // Update bounds if changed (only sync if active)
-        if self.cached_rect != Some(new_rect) {
+        if self.cached_rect != Some(new_rect) || self.cached_clip != Some(new_clip) {
             self.cached_rect = Some(new_rect);
+            self.cached_clip = Some(new_clip);
             if self.active && self.webview.is_some() {
-                self.sync_bounds(new_rect);
+                self.sync_bounds(new_rect, new_clip);
             }
         }
 
@@ -405,7 +641,25 @@ impl Widget for WebViewContainer {
     }
 }
 
+fn to_bounds(rect: Rect) -> WebViewBounds {
+    WebViewBounds {
+        x: rect.pos.x.round() as i32,
+        y: rect.pos.y.round() as i32,
+        width: rect.size.x.max(0.0).round() as u32,
+        height: rect.size.y.max(0.0).round() as u32,
+    }
+}
+
 impl WebViewContainerRef {
+    /// URL to open when the WebView is created; no effect once initialized
+    pub fn set_initial_url(&self, url: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if inner.webview.is_none() {
+                inner.url = url.to_string();
+            }
+        }
+    }
+
     /// Navigate to a URL
     pub fn load_url(&self, url: &str) -> Result<(), WebViewError> {
         if let Some(inner) = self.borrow() {
@@ -424,6 +678,13 @@ impl WebViewContainerRef {
         }
     }
 
+    /// Follow the studio's dark mode (0.0 light to 1.0 dark)
+    pub fn set_dark_mode(&self, dark_mode: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_dark_mode(dark_mode);
+        }
+    }
+
     /// Go back in navigation history
     pub fn go_back(&self) -> Result<(), WebViewError> {
         if let Some(inner) = self.borrow() {
@@ -451,6 +712,15 @@ impl WebViewContainerRef {
         }
     }
 
+    /// Clear cookies, storage and caches of the profile
+    pub fn clear_browsing_data(&self) -> Result<(), WebViewError> {
+        if let Some(inner) = self.borrow() {
+            inner.clear_browsing_data()
+        } else {
+            Err(WebViewError::NotInitialized)
+        }
+    }
+
     /// Send message to JavaScript
     pub fn send_to_js(&self, channel: &str, data: &str) -> Result<(), WebViewError> {
         if let Some(inner) = self.borrow() {
@@ -465,6 +735,12 @@ impl WebViewContainerRef {
         self.borrow().map_or(false, |inner| inner.is_initialized())
     }
 
+    /// Initialization outcome and attempts, e.g. to log or copy from an
+    /// error state
+    pub fn debug_report(&self) -> String {
+        self.borrow().map(|inner| inner.debug_report()).unwrap_or_default()
+    }
+
     /// Set visibility
     pub fn set_visible(&self, visible: bool) -> Result<(), WebViewError> {
         if let Some(mut inner) = self.borrow_mut() {
@@ -474,6 +750,14 @@ impl WebViewContainerRef {
         }
     }
 
+    /// Switch between the profile and a private in-memory session,
+    /// recreating the WebView at `url` if it exists
+    pub fn set_incognito(&self, cx: &mut Cx, incognito: bool, url: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_incognito(cx, incognito, url);
+        }
+    }
+
     /// Set active state
     pub fn set_active(&self, cx: &mut Cx, active: bool) {
         if let Some(mut inner) = self.borrow_mut() {
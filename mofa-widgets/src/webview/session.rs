@@ -0,0 +1,171 @@
+//! Navigation history for a WebView profile
+//!
+//! The native WebView forgets its history when the app quits, so apps that
+//! want to reopen where the user left off keep a [`NavigationHistory`] of
+//! their own: record each [`WebViewAction::UrlChanged`](super::WebViewAction),
+//! drive back/forward through it, and [`save`](NavigationHistory::save) it.
+//! The file lives in the profile directory, so resetting the WebView profile
+//! from Settings clears it along with cookies and storage.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Entries kept per profile; the oldest are dropped first
+pub const MAX_HISTORY: usize = 100;
+
+const SESSION_FILE: &str = "session.json";
+
+/// Visited URLs with a cursor on the current one
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NavigationHistory {
+    entries: Vec<String>,
+    index: usize,
+}
+
+impl NavigationHistory {
+    /// Saved history of `profile`, empty if none or unreadable
+    pub fn load(profile: &str) -> Self {
+        let mut history: Self = std::fs::read_to_string(Self::path(profile))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        history.index = history.index.min(history.entries.len().saturating_sub(1));
+        history
+    }
+
+    pub fn save(&self, profile: &str) -> Result<(), String> {
+        let path = Self::path(profile);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&path, json).map_err(|e| e.to_string())
+    }
+
+    /// Remove the saved history of `profile`
+    pub fn delete(profile: &str) -> Result<(), String> {
+        match std::fs::remove_file(Self::path(profile)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    fn path(profile: &str) -> PathBuf {
+        crate::app_storage::webview_profile_dir(profile).join(SESSION_FILE)
+    }
+
+    pub fn current(&self) -> Option<&str> {
+        self.entries.get(self.index).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.index > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.index + 1 < self.entries.len()
+    }
+
+    /// Record a navigation, dropping any forward entries
+    pub fn visit(&mut self, url: &str) {
+        if self.current() == Some(url) {
+            return;
+        }
+        if !self.entries.is_empty() {
+            self.entries.truncate(self.index + 1);
+        }
+        self.entries.push(url.to_string());
+        if self.entries.len() > MAX_HISTORY {
+            let excess = self.entries.len() - MAX_HISTORY;
+            self.entries.drain(..excess);
+        }
+        self.index = self.entries.len() - 1;
+    }
+
+    /// Replace the current entry, e.g. with the URL a redirect ended on
+    pub fn replace_current(&mut self, url: &str) {
+        match self.entries.get_mut(self.index) {
+            Some(entry) => *entry = url.to_string(),
+            None => self.visit(url),
+        }
+    }
+
+    /// Move back one entry and return its URL
+    pub fn back(&mut self) -> Option<&str> {
+        if !self.can_go_back() {
+            return None;
+        }
+        self.index -= 1;
+        self.current()
+    }
+
+    /// Move forward one entry and return its URL
+    pub fn forward(&mut self) -> Option<&str> {
+        if !self.can_go_forward() {
+            return None;
+        }
+        self.index += 1;
+        self.current()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.index = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_back_forward_and_branching() {
+        let mut history = NavigationHistory::default();
+        assert!(!history.can_go_back() && !history.can_go_forward());
+
+        history.visit("https://a.test/");
+        history.visit("https://b.test/");
+        history.visit("https://b.test/");
+        history.visit("https://c.test/");
+        assert_eq!(history.len(), 3);
+
+        assert_eq!(history.back(), Some("https://b.test/"));
+        assert_eq!(history.back(), Some("https://a.test/"));
+        assert_eq!(history.back(), None);
+        assert!(history.can_go_forward());
+
+        // A new visit drops the forward entries
+        history.visit("https://d.test/");
+        assert!(!history.can_go_forward());
+        assert_eq!(history.back(), Some("https://a.test/"));
+        assert_eq!(history.forward(), Some("https://d.test/"));
+
+        history.replace_current("https://d.test/home");
+        assert_eq!(history.current(), Some("https://d.test/home"));
+        assert_eq!(history.len(), 2);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut history = NavigationHistory::default();
+        for i in 0..MAX_HISTORY + 20 {
+            history.visit(&format!("https://site.test/{}", i));
+        }
+        assert_eq!(history.len(), MAX_HISTORY);
+        assert_eq!(history.current(), Some(format!("https://site.test/{}", MAX_HISTORY + 19).as_str()));
+        assert!(!history.can_go_forward());
+
+        history.clear();
+        assert!(history.is_empty());
+        assert_eq!(history.current(), None);
+    }
+}
@@ -0,0 +1,218 @@
+//! Studio theme for embedded pages
+//!
+//! Every page loaded in a [`WebViewContainer`](super::WebViewContainer) gets
+//! the studio theme without implementing anything:
+//!
+//! - CSS custom properties on `<html>`: colors (`--mofa-bg`, `--mofa-panel`,
+//!   `--mofa-fg`, `--mofa-fg-muted`, `--mofa-border`, `--mofa-hover`,
+//!   `--mofa-accent`, `--mofa-success`, `--mofa-danger`, `--mofa-warning`),
+//!   a spacing scale (`--mofa-space-1` to `--mofa-space-6`), `--mofa-radius`
+//!   and `--mofa-font`
+//!   `--mofa-accent` follows the studio's accent color ([`set_accent`])
+//! - `data-mofa-theme="dark|light"` on `<html>`
+//! - a `mofa-theme` event on `window` when the theme changes, with the
+//!   theme as `event.detail`; the current theme is `window.__mofa_theme.current`
+//!
+//! Pages that still define `window.setTheme(darkMode)` keep being called.
+//!
+//! The bridge is installed as an initialization script, so it runs before
+//! the page's own scripts on every navigation. The last theme is kept in
+//! `sessionStorage` so a reloaded page starts with it instead of flashing the
+//! theme the WebView was created with.
+
+use crate::theme::colors;
+use parking_lot::Mutex;
+
+/// Event dispatched on `window` when the theme changes
+pub const THEME_EVENT: &str = "mofa-theme";
+
+/// Spacing scale in px, `--mofa-space-1` first
+const SPACING: [u32; 6] = [4, 8, 12, 16, 24, 32];
+
+const RADIUS_PX: u32 = 6;
+
+const FONT_STACK: &str = "Manrope, system-ui, -apple-system, \"Segoe UI\", sans-serif";
+
+/// Accent picked by the shell for every page, see [`set_accent`]
+static ACCENT: Mutex<Option<[u8; 3]>> = Mutex::new(None);
+
+/// Set the accent color (RGB) new themes carry; `None` is the palette's
+/// blue. Pages pick it up with the next theme applied to them.
+pub fn set_accent(accent: Option<[u8; 3]>) {
+    *ACCENT.lock() = accent;
+}
+
+/// Theme values handed to pages
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WebTheme {
+    pub dark: bool,
+    /// Accent color (RGB); `None` uses the palette's blue for the mode
+    pub accent: Option<[u8; 3]>,
+}
+
+impl Default for WebTheme {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl WebTheme {
+    /// Theme for the studio's `dark_mode` value (0.0 light to 1.0 dark),
+    /// with the accent from [`set_accent`]
+    pub fn new(dark_mode: f64) -> Self {
+        Self {
+            dark: dark_mode >= 0.5,
+            accent: *ACCENT.lock(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        if self.dark {
+            "dark"
+        } else {
+            "light"
+        }
+    }
+
+    /// CSS custom properties, taken from the centralized palette
+    pub fn css_vars(&self) -> Vec<(String, String)> {
+        let pick = |light: &str, dark: &str| if self.dark { dark } else { light }.to_string();
+        let accent = match self.accent {
+            Some([r, g, b]) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+            None => pick(colors::ACCENT_BLUE, colors::ACCENT_BLUE_DARK),
+        };
+        let mut vars = vec![
+            ("--mofa-bg".to_string(), pick(colors::DARK_BG, colors::DARK_BG_DARK)),
+            ("--mofa-panel".to_string(), pick(colors::PANEL_BG, colors::PANEL_BG_DARK)),
+            ("--mofa-fg".to_string(), pick(colors::TEXT_PRIMARY, colors::TEXT_PRIMARY_DARK)),
+            ("--mofa-fg-muted".to_string(), pick(colors::TEXT_SECONDARY, colors::TEXT_SECONDARY_DARK)),
+            ("--mofa-border".to_string(), pick(colors::BORDER, colors::BORDER_DARK)),
+            ("--mofa-hover".to_string(), pick(colors::HOVER_BG, colors::HOVER_BG_DARK)),
+            ("--mofa-accent".to_string(), accent),
+            ("--mofa-success".to_string(), colors::ACCENT_GREEN.to_string()),
+            ("--mofa-danger".to_string(), colors::ACCENT_RED.to_string()),
+            ("--mofa-warning".to_string(), colors::ACCENT_YELLOW.to_string()),
+        ];
+        for (i, px) in SPACING.iter().enumerate() {
+            vars.push((format!("--mofa-space-{}", i + 1), format!("{}px", px)));
+        }
+        vars.push(("--mofa-radius".to_string(), format!("{}px", RADIUS_PX)));
+        vars.push(("--mofa-font".to_string(), FONT_STACK.to_string()));
+        vars
+    }
+
+    /// The theme as a JS object literal
+    pub fn to_json(&self) -> String {
+        let vars: serde_json::Map<String, serde_json::Value> = self
+            .css_vars()
+            .into_iter()
+            .map(|(name, value)| (name, serde_json::Value::String(value)))
+            .collect();
+        serde_json::json!({
+            "name": self.name(),
+            "dark": self.dark,
+            "vars": vars,
+        })
+        .to_string()
+    }
+}
+
+/// Initialization script defining `window.__mofa_theme`; `initial` is
+/// applied unless the page's session already has a theme
+pub fn bridge_script(initial: &WebTheme) -> String {
+    format!(
+        r#"(function() {{
+    var STORAGE_KEY = "__mofa_theme";
+    var bridge = window.__mofa_theme = {{
+        current: null,
+        apply: function(theme) {{
+            var root = document.documentElement;
+            if (!root) {{
+                document.addEventListener("DOMContentLoaded", function() {{ bridge.apply(theme); }});
+                return;
+            }}
+            for (var name in theme.vars) {{
+                root.style.setProperty(name, theme.vars[name]);
+            }}
+            root.setAttribute("data-mofa-theme", theme.name);
+            try {{ sessionStorage.setItem(STORAGE_KEY, JSON.stringify(theme)); }} catch (e) {{}}
+
+            var changed = JSON.stringify(theme) !== JSON.stringify(bridge.current);
+            bridge.current = theme;
+            if (changed) {{
+                window.dispatchEvent(new CustomEvent("{event}", {{ detail: theme }}));
+            }}
+            // Pages written before the bridge
+            if (typeof window.setTheme === "function") {{
+                try {{ window.setTheme(theme.dark ? 1.0 : 0.0); }} catch (e) {{ console.error(e); }}
+            }}
+        }}
+    }};
+
+    var theme = {initial};
+    try {{
+        var stored = sessionStorage.getItem(STORAGE_KEY);
+        if (stored) theme = JSON.parse(stored);
+    }} catch (e) {{}}
+    bridge.apply(theme);
+    // Page scripts define setTheme after this runs
+    document.addEventListener("DOMContentLoaded", function() {{ bridge.apply(bridge.current); }});
+}})();"#,
+        event = THEME_EVENT,
+        initial = initial.to_json(),
+    )
+}
+
+/// Script applying `theme` to the loaded page
+pub fn apply_script(theme: &WebTheme) -> String {
+    format!(
+        "if (window.__mofa_theme) window.__mofa_theme.apply({});",
+        theme.to_json()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_from_dark_mode() {
+        assert!(!WebTheme::new(0.0).dark);
+        assert!(!WebTheme::new(0.49).dark);
+        assert!(WebTheme::new(0.5).dark);
+        assert_eq!(WebTheme::new(1.0).name(), "dark");
+        assert_eq!(WebTheme::default().name(), "light");
+    }
+
+    #[test]
+    fn test_css_vars_follow_palette() {
+        let var = |theme: WebTheme, name: &str| {
+            theme.css_vars().into_iter().find(|(n, _)| n == name).map(|(_, v)| v).unwrap()
+        };
+        let light = WebTheme::new(0.0);
+        let dark = WebTheme::new(1.0);
+        assert_eq!(var(light, "--mofa-bg"), colors::DARK_BG);
+        assert_eq!(var(dark, "--mofa-bg"), colors::DARK_BG_DARK);
+        assert_eq!(var(light, "--mofa-accent"), colors::ACCENT_BLUE);
+        assert_eq!(var(dark, "--mofa-accent"), colors::ACCENT_BLUE_DARK);
+        assert_eq!(var(dark, "--mofa-space-1"), "4px");
+        assert_eq!(var(dark, "--mofa-space-6"), "32px");
+
+        let green = WebTheme { accent: Some([0x10, 0xb9, 0x81]), ..dark };
+        assert_eq!(var(green, "--mofa-accent"), "#10b981");
+    }
+
+    #[test]
+    fn test_scripts_carry_theme() {
+        let dark = WebTheme::new(1.0);
+        let json: serde_json::Value = serde_json::from_str(&dark.to_json()).unwrap();
+        assert_eq!(json["name"], "dark");
+        assert_eq!(json["dark"], true);
+        assert_eq!(json["vars"]["--mofa-fg"], colors::TEXT_PRIMARY_DARK);
+
+        let bridge = bridge_script(&WebTheme::default());
+        assert!(bridge.contains(r#""name":"light""#));
+        assert!(bridge.contains(THEME_EVENT));
+        assert!(apply_script(&dark).contains(r#""name":"dark""#));
+    }
+}
@@ -3,12 +3,14 @@
 //! This module provides a high-level wrapper around wry's WebView,
 //! managing lifecycle, positioning, and IPC communication.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use parking_lot::Mutex;
-use wry::{WebView, WebViewBuilder, Rect};
+use wry::{WebContext, WebView, WebViewBuilder, Rect};
 use raw_window_handle::{HasWindowHandle, HandleError};
 
 use super::ipc::{IpcHandler, IpcMessage};
+use super::theme_bridge::{self, WebTheme};
 use super::platform_handle::{get_native_handle, NativeWindowHandle, PlatformHandleError};
 
 /// Configuration for creating a WebView
@@ -24,6 +26,12 @@ pub struct WebViewConfig {
     pub transparent: bool,
     /// Custom user agent
     pub user_agent: Option<String>,
+    /// Profile directory for cookies and storage (shared default when None)
+    pub data_directory: Option<PathBuf>,
+    /// Keep cookies and storage in memory only; `data_directory` is ignored
+    pub incognito: bool,
+    /// Studio theme handed to pages (see [`theme_bridge`])
+    pub theme: WebTheme,
 }
 
 impl Default for WebViewConfig {
@@ -34,6 +42,9 @@ impl Default for WebViewConfig {
             devtools: cfg!(debug_assertions),
             transparent: false,
             user_agent: None,
+            data_directory: None,
+            incognito: false,
+            theme: WebTheme::default(),
         }
     }
 }
@@ -51,6 +62,45 @@ impl WebViewBounds {
     pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
         Self { x, y, width, height }
     }
+
+    /// Overlapping area of two bounds, `None` if they don't overlap
+    pub fn intersect(&self, other: &WebViewBounds) -> Option<WebViewBounds> {
+        let left = self.x.max(other.x);
+        let top = self.y.max(other.y);
+        let right = (self.x + self.width as i32).min(other.x + other.width as i32);
+        let bottom = (self.y + self.height as i32).min(other.y + other.height as i32);
+        if right <= left || bottom <= top {
+            return None;
+        }
+        Some(WebViewBounds::new(left, top, (right - left) as u32, (bottom - top) as u32))
+    }
+}
+
+/// How a page laid out at full size is shown through a smaller native view
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentOffset {
+    /// Offset of the visible part within the full page area
+    pub x: i32,
+    pub y: i32,
+    /// Full (unclipped) size the page is laid out at
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ContentOffset {
+    /// Offset for showing `visible`, a part of `full`
+    pub fn new(full: &WebViewBounds, visible: &WebViewBounds) -> Self {
+        Self {
+            x: visible.x - full.x,
+            y: visible.y - full.y,
+            width: full.width,
+            height: full.height,
+        }
+    }
+
+    pub fn is_clipped(&self, visible: &WebViewBounds) -> bool {
+        self.x != 0 || self.y != 0 || visible.width != self.width || visible.height != self.height
+    }
 }
 
 impl From<WebViewBounds> for Rect {
@@ -75,6 +125,8 @@ pub enum WebViewError {
     WryError(wry::Error),
     NotInitialized,
     AlreadyInitialized,
+    /// No WebView is created while safe mode disables them
+    DisabledInSafeMode,
 }
 
 impl std::fmt::Display for WebViewError {
@@ -84,6 +136,7 @@ impl std::fmt::Display for WebViewError {
             Self::WryError(e) => write!(f, "Wry error: {}", e),
             Self::NotInitialized => write!(f, "WebView not initialized"),
             Self::AlreadyInitialized => write!(f, "WebView already initialized"),
+            Self::DisabledInSafeMode => write!(f, "WebViews are disabled in safe mode"),
         }
     }
 }
@@ -118,19 +171,79 @@ impl HasWindowHandle for WindowHandleWrapper {
 /// Managed WebView instance
 pub struct ManagedWebView {
     webview: Option<WebView>,
+    /// Must outlive the WebView, so it is declared (and dropped) after it
+    web_context: Option<WebContext>,
     config: WebViewConfig,
     ipc_handler: Arc<Mutex<IpcHandler>>,
     visible: bool,
+    /// Fully scrolled out of its clip rect; hidden regardless of `visible`
+    clipped_out: bool,
+    /// Set when only part of the page area is visible
+    content_offset: Option<ContentOffset>,
 }
 
+/// IPC channel the page uses after each load to report its URL and ask for
+/// the clip offset
+pub const CLIP_READY_CHANNEL: &str = "__mofa_clip_ready";
+
+/// Keeps an `access_token` from the page URL (see `local_server::TokenAuth`)
+/// and sends it as a bearer token with the page's same-origin requests.
+/// Subresources (`<script src>`, `<link href>`, `<img src>`) are authorized
+/// by the session cookie the server sets on the first page load instead.
+const AUTH_TOKEN_SCRIPT: &str = r#"(function() {
+    var key = "__mofa_access_token:" + location.host;
+    var params = new URLSearchParams(location.search);
+    var token = params.get("access_token");
+    if (token) {
+        sessionStorage.setItem(key, token);
+        params.delete("access_token");
+        var query = params.toString();
+        history.replaceState(history.state, "", location.pathname + (query ? "?" + query : "") + location.hash);
+    } else {
+        token = sessionStorage.getItem(key);
+    }
+    if (!token) return;
+
+    function sameOrigin(url) {
+        try { return new URL(url, location.href).origin === location.origin; } catch (e) { return false; }
+    }
+    var originalFetch = window.fetch;
+    window.fetch = function(input, init) {
+        var url = typeof input === "string" ? input : (input && input.url) || String(input);
+        if (!sameOrigin(url)) return originalFetch.call(this, input, init);
+        init = init || {};
+        var headers = new Headers(init.headers || (input instanceof Request ? input.headers : undefined));
+        if (!headers.has("Authorization")) headers.set("Authorization", "Bearer " + token);
+        init.headers = headers;
+        return originalFetch.call(this, input, init);
+    };
+    var originalOpen = XMLHttpRequest.prototype.open;
+    var originalSend = XMLHttpRequest.prototype.send;
+    XMLHttpRequest.prototype.open = function(method, url) {
+        this.__mofaSameOrigin = sameOrigin(url);
+        return originalOpen.apply(this, arguments);
+    };
+    XMLHttpRequest.prototype.send = function() {
+        if (this.__mofaSameOrigin) this.setRequestHeader("Authorization", "Bearer " + token);
+        return originalSend.apply(this, arguments);
+    };
+})();"#;
+
 impl ManagedWebView {
     /// Create a new managed WebView (not yet initialized)
     pub fn new(config: WebViewConfig) -> Self {
         Self {
             webview: None,
+            web_context: config
+                .data_directory
+                .clone()
+                .filter(|_| !config.incognito)
+                .map(|dir| WebContext::new(Some(dir))),
             config,
             ipc_handler: Arc::new(Mutex::new(IpcHandler::new())),
             visible: true,
+            clipped_out: false,
+            content_offset: None,
         }
     }
 
@@ -150,12 +263,26 @@ impl ManagedWebView {
         let ipc = self.ipc_handler.clone();
 
         // Build the WebView
-        let mut builder = WebViewBuilder::new()
+        let builder = match self.web_context.as_mut() {
+            Some(context) => WebViewBuilder::with_web_context(context),
+            None => WebViewBuilder::new(),
+        };
+        let mut builder = builder
             .with_bounds(self.config.bounds.into())
             .with_url(&self.config.url)
             .with_devtools(self.config.devtools)
             .with_transparent(self.config.transparent)
             .with_clipboard(true)  // Enable clipboard (copy/paste)
+            .with_incognito(self.config.incognito)
+            // New pages start unshifted; ask for the clip offset again
+            .with_initialization_script(&format!(
+                r#"window.addEventListener("DOMContentLoaded", function() {{
+                    window.ipc.postMessage(JSON.stringify({{ channel: "{}", data: location.href }}));
+                }});"#,
+                CLIP_READY_CHANNEL
+            ))
+            .with_initialization_script(AUTH_TOKEN_SCRIPT)
+            .with_initialization_script(&theme_bridge::bridge_script(&self.config.theme))
             .with_ipc_handler(move |msg| {
                 let mut handler = ipc.lock();
                 handler.handle_message(IpcMessage::from_js(msg.body()));
@@ -186,6 +313,82 @@ impl ManagedWebView {
         Ok(())
     }
 
+    /// Show the part of a `full`-sized page area that falls inside `clip`.
+    ///
+    /// The native view is shrunk to the visible part and the page is shifted
+    /// so content stays where it would be unclipped. Hides the view when
+    /// nothing is visible.
+    pub fn set_clipped_bounds(&mut self, full: WebViewBounds, clip: WebViewBounds) -> Result<(), WebViewError> {
+        let Some(visible) = full.intersect(&clip) else {
+            if !self.clipped_out {
+                self.clipped_out = true;
+                if let Some(ref webview) = self.webview {
+                    webview.set_visible(false)?;
+                }
+            }
+            return Ok(());
+        };
+
+        self.set_bounds(visible)?;
+        if self.clipped_out {
+            self.clipped_out = false;
+            if let Some(ref webview) = self.webview {
+                webview.set_visible(self.visible)?;
+            }
+        }
+
+        let offset = ContentOffset::new(&full, &visible);
+        let offset = offset.is_clipped(&visible).then_some(offset);
+        if offset != self.content_offset {
+            self.content_offset = offset;
+            self.apply_content_offset()?;
+        }
+        Ok(())
+    }
+
+    /// Shift the page to match the current clip (re-run after each page load)
+    pub fn apply_content_offset(&self) -> Result<(), WebViewError> {
+        if self.webview.is_none() {
+            return Ok(());
+        }
+        let js = match self.content_offset {
+            Some(offset) => format!(
+                r#"(function() {{
+                    var s = document.documentElement.style;
+                    s.transformOrigin = "0 0";
+                    s.transform = "translate({}px, {}px)";
+                    s.width = "{}px";
+                    s.height = "{}px";
+                }})();"#,
+                -offset.x, -offset.y, offset.width, offset.height
+            ),
+            None => r#"(function() {
+                    var s = document.documentElement.style;
+                    s.transformOrigin = "";
+                    s.transform = "";
+                    s.width = "";
+                    s.height = "";
+                })();"#
+                .to_string(),
+        };
+        self.eval(&js)
+    }
+
+    /// Hand a new theme to the page and to pages loaded later
+    pub fn set_theme(&mut self, theme: WebTheme) -> Result<(), WebViewError> {
+        self.config.theme = theme;
+        self.apply_theme()
+    }
+
+    /// Re-apply the theme, e.g. after a navigation to another origin
+    /// started the page with the theme the WebView was created with
+    pub fn apply_theme(&self) -> Result<(), WebViewError> {
+        if self.webview.is_none() {
+            return Ok(());
+        }
+        self.eval(&theme_bridge::apply_script(&self.config.theme))
+    }
+
     /// Get current bounds
     pub fn bounds(&self) -> WebViewBounds {
         self.config.bounds
@@ -228,10 +431,18 @@ impl ManagedWebView {
         self.eval("location.reload()")
     }
 
+    /// Clear cookies, storage and caches of the WebView's profile
+    pub fn clear_browsing_data(&self) -> Result<(), WebViewError> {
+        if let Some(ref webview) = self.webview {
+            webview.clear_all_browsing_data()?;
+        }
+        Ok(())
+    }
+
     /// Set visibility
     pub fn set_visible(&mut self, visible: bool) -> Result<(), WebViewError> {
         if let Some(ref webview) = self.webview {
-            webview.set_visible(visible)?;
+            webview.set_visible(visible && !self.clipped_out)?;
             self.visible = visible;
         }
         Ok(())
@@ -304,3 +515,41 @@ impl Drop for ManagedWebView {
         self.webview = None;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersect() {
+        let full = WebViewBounds::new(0, 100, 800, 400);
+        let clip = WebViewBounds::new(0, 150, 800, 600);
+        let visible = full.intersect(&clip).unwrap();
+        assert_eq!((visible.x, visible.y, visible.width, visible.height), (0, 150, 800, 350));
+
+        // Touching edges don't overlap
+        let below = WebViewBounds::new(0, 500, 800, 100);
+        assert!(full.intersect(&below).is_none());
+    }
+
+    #[test]
+    fn test_content_offset() {
+        let full = WebViewBounds::new(220, 100, 800, 400);
+
+        // Scrolled so the top 50px are hidden under the header
+        let visible = full.intersect(&WebViewBounds::new(220, 150, 800, 600)).unwrap();
+        let offset = ContentOffset::new(&full, &visible);
+        assert_eq!(offset, ContentOffset { x: 0, y: 50, width: 800, height: 400 });
+        assert!(offset.is_clipped(&visible));
+
+        // Bottom clipped: no shift, but still smaller than the page
+        let visible = full.intersect(&WebViewBounds::new(0, 0, 1200, 300)).unwrap();
+        let offset = ContentOffset::new(&full, &visible);
+        assert_eq!((offset.x, offset.y), (0, 0));
+        assert!(offset.is_clipped(&visible));
+
+        // Fully inside the clip
+        let visible = full.intersect(&WebViewBounds::new(0, 0, 1200, 900)).unwrap();
+        assert!(!ContentOffset::new(&full, &visible).is_clipped(&visible));
+    }
+}